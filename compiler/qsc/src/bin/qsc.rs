@@ -5,9 +5,15 @@ allocator::assign_global!();
 
 use clap::{crate_version, ArgGroup, Parser, ValueEnum};
 use log::info;
-use miette::{Context, IntoDiagnostic, Report};
-use qsc::compile::compile;
-use qsc_codegen::qir_base;
+use miette::{Context, IntoDiagnostic, NamedSource, Report};
+use qsc::{
+    codegen::OptimizationLevel,
+    compile::{compile, compile_dependencies},
+    interpret::GenericReceiver,
+    target::Profile,
+    SparseSim,
+};
+use qsc_codegen::{qir_base, qir_reader};
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
 use qsc_frontend::{
     compile::{PackageStore, SourceContents, SourceMap, SourceName},
@@ -21,12 +27,14 @@ use std::{
     io::{self, Read},
     path::{Path, PathBuf},
     process::ExitCode,
+    str::FromStr,
     string::String,
 };
 
 #[derive(Debug, Parser)]
 #[command(version = concat!(crate_version!(), " (", env!("QSHARP_GIT_HASH"), ")"), arg_required_else_help(false))]
 #[clap(group(ArgGroup::new("input").args(["entry", "sources"]).required(false).multiple(true)))]
+#[clap(group(ArgGroup::new("program").args(["qir", "rir"]).required(false).multiple(false)))]
 struct Cli {
     /// Disable automatic inclusion of the standard library.
     #[arg(long)]
@@ -40,7 +48,7 @@ struct Cli {
     #[arg(long = "outdir", value_name = "DIR")]
     out_dir: Option<PathBuf>,
 
-    /// Enable verbose output.
+    /// Enable verbose output, including statistics about the gates removed by optimization.
     #[arg(short, long)]
     verbose: bool,
 
@@ -59,6 +67,28 @@ struct Cli {
     /// Language features to compile with
     #[arg(short, long)]
     features: Vec<String>,
+
+    /// Target profile to emit QIR for: `base`, or `quantinuum` for Adaptive profile QIR.
+    #[arg(long, value_parser = parse_profile, default_value = "base")]
+    profile: Profile,
+
+    /// Optimization level to use when emitting QIR for profiles other than Base: `none`, `basic` or `full`.
+    #[arg(long, value_parser = parse_optimization_level, default_value = "basic")]
+    optimization_level: OptimizationLevel,
+
+    /// Read a Base or Adaptive profile QIR program and write its RIR to `rir.txt` instead of compiling Q#.
+    #[arg(long, value_name = "FILE")]
+    qir: Option<PathBuf>,
+
+    /// Read a RIR program in its textual form and run it on the sparse simulator instead of compiling Q#.
+    #[arg(long, value_name = "FILE")]
+    rir: Option<PathBuf>,
+
+    /// Run the program read with `--qir` or `--rir` on the sparse simulator for the given number of shots and print
+    /// the output records of each shot. A QIR program is only run, instead of having its RIR written, when this is
+    /// given, and a RIR program is run once by default.
+    #[arg(long, requires = "program")]
+    shots: Option<u32>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -70,11 +100,28 @@ enum Emit {
 fn main() -> miette::Result<ExitCode> {
     env_logger::init();
     let cli = Cli::parse();
+    if let Some(path) = &cli.qir {
+        if let Some(shots) = cli.shots {
+            return Ok(run_program(&read_qir(path)?, shots));
+        }
+        let out_dir = cli.out_dir.as_ref().map_or(".".as_ref(), PathBuf::as_path);
+        emit_rir_from_qir(path, out_dir)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(path) = &cli.rir {
+        return Ok(run_program(&read_rir(path)?, cli.shots.unwrap_or(1)));
+    }
+
     let mut store = PackageStore::new(qsc::compile::core());
     let mut dependencies = Vec::new();
 
     let (package_type, capabilities) = if cli.emit.contains(&Emit::Qir) {
-        (PackageType::Exe, TargetCapabilityFlags::empty())
+        if cli.profile == Profile::Unrestricted {
+            return Err(miette::miette!(
+                "QIR cannot be emitted for the unrestricted profile"
+            ));
+        }
+        (PackageType::Exe, cli.profile.into())
     } else {
         (PackageType::Lib, TargetCapabilityFlags::all())
     };
@@ -103,6 +150,21 @@ fn main() -> miette::Result<ExitCode> {
             features.merge(LanguageFeatures::from_iter(
                 manifest.manifest.language_features,
             ));
+
+            let (direct, dependency_errors) = compile_dependencies(
+                &mut store,
+                &dependencies,
+                &project.dependencies,
+                capabilities,
+            );
+            if !dependency_errors.is_empty() {
+                for error in dependency_errors {
+                    eprintln!("{:?}", Report::new(error));
+                }
+
+                return Ok(ExitCode::FAILURE);
+            }
+            dependencies.extend(direct);
         }
     }
 
@@ -125,7 +187,14 @@ fn main() -> miette::Result<ExitCode> {
             Emit::Hir => emit_hir(&unit.package, out_dir)?,
             Emit::Qir => {
                 if errors.is_empty() {
-                    emit_qir(out_dir, &store, package_id)?;
+                    emit_qir(
+                        out_dir,
+                        &store,
+                        package_id,
+                        capabilities,
+                        cli.optimization_level,
+                        cli.verbose,
+                    )?;
                 }
             }
         }
@@ -172,8 +241,38 @@ fn emit_hir(package: &Package, dir: impl AsRef<Path>) -> miette::Result<()> {
         .with_context(|| format!("could not emit HIR file `{}`", path.display()))
 }
 
-fn emit_qir(out_dir: &Path, store: &PackageStore, package_id: PackageId) -> Result<(), Report> {
+fn parse_profile(s: &str) -> Result<Profile, String> {
+    Profile::from_str(s).map_err(|()| format!("invalid profile `{s}`"))
+}
+
+fn parse_optimization_level(s: &str) -> Result<OptimizationLevel, String> {
+    OptimizationLevel::from_str(s).map_err(|()| format!("invalid optimization level `{s}`"))
+}
+
+fn emit_qir(
+    out_dir: &Path,
+    store: &PackageStore,
+    package_id: PackageId,
+    capabilities: TargetCapabilityFlags,
+    optimization_level: OptimizationLevel,
+    verbose: bool,
+) -> Result<(), Report> {
     let path = out_dir.join("qir.ll");
+    if capabilities != TargetCapabilityFlags::empty() {
+        let (qir, stats) =
+            qsc::codegen::get_qir_from_store(store, package_id, capabilities, optimization_level)
+                .map_err(|error| miette::miette!("{error}"))?;
+        if verbose {
+            eprintln!("Gate optimization: {stats}");
+        }
+        info!(
+            "Writing QIR output file to: {}",
+            path.to_str().unwrap_or_default()
+        );
+        return fs::write(&path, qir)
+            .into_diagnostic()
+            .with_context(|| format!("could not emit QIR file `{}`", path.display()));
+    }
     let result = qir_base::generate_qir(store, package_id);
     match result {
         Ok(qir) => {
@@ -191,3 +290,51 @@ fn emit_qir(out_dir: &Path, store: &PackageStore, package_id: PackageId) -> Resu
         }
     }
 }
+
+fn read_qir(path: &Path) -> miette::Result<qsc::rir::Program> {
+    let (name, contents) = read_source(path)?;
+    qir_reader::read_qir(&contents).map_err(|error| {
+        Report::new(error).with_source_code(NamedSource::new(name, contents.to_string()))
+    })
+}
+
+fn read_rir(path: &Path) -> miette::Result<qsc::rir::Program> {
+    let (name, contents) = read_source(path)?;
+    qsc::rir::parse_program(&contents).map_err(|error| {
+        Report::new(error).with_source_code(NamedSource::new(name, contents.to_string()))
+    })
+}
+
+fn run_program(program: &qsc::rir::Program, shots: u32) -> ExitCode {
+    let mut sim = SparseSim::new();
+    let mut stdout = io::stdout();
+    for _ in 0..shots {
+        let records = qsc::rir::exec(program, &mut sim, &mut GenericReceiver::new(&mut stdout));
+        match records {
+            Ok(records) => {
+                println!("START");
+                for record in records {
+                    println!("{record}");
+                }
+                println!("END\t0");
+            }
+            Err(error) => {
+                eprintln!("{:?}", Report::new(error));
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn emit_rir_from_qir(path: &Path, out_dir: &Path) -> miette::Result<()> {
+    let program = read_qir(path)?;
+    let path = out_dir.join("rir.txt");
+    info!(
+        "Writing RIR output file to: {}",
+        path.to_str().unwrap_or_default()
+    );
+    fs::write(&path, program.to_string())
+        .into_diagnostic()
+        .with_context(|| format!("could not emit RIR file `{}`", path.display()))
+}
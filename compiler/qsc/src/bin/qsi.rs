@@ -4,12 +4,13 @@
 allocator::assign_global!();
 
 use clap::{crate_version, Parser};
-use miette::{Context, IntoDiagnostic, Report, Result};
+use miette::{miette, Context, IntoDiagnostic, Report, Result};
 use num_bigint::BigUint;
 use num_complex::Complex64;
 use qsc::interpret::{self, InterpretResult, Interpreter};
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
 use qsc_eval::{
+    backend::{format_measurement_record, parse_measurement_record},
     output::{self, Receiver},
     state::format_state_id,
     val::Value,
@@ -52,6 +53,14 @@ struct Cli {
     /// Language features to compile with
     #[arg(short, long)]
     features: Vec<String>,
+
+    /// Write the outcome of every measurement to the given file on exit.
+    #[arg(long)]
+    record_measurements: Option<PathBuf>,
+
+    /// Force the measurement outcomes recorded in the given file, in order.
+    #[arg(long)]
+    replay_measurements: Option<PathBuf>,
 }
 
 struct TerminalReceiver;
@@ -86,6 +95,7 @@ fn main() -> miette::Result<ExitCode> {
         .collect::<miette::Result<Vec<_>>>()?;
 
     let mut features = LanguageFeatures::from_iter(cli.features);
+    let mut dependencies = Vec::new();
 
     if sources.is_empty() {
         let fs = StdFs;
@@ -95,6 +105,7 @@ fn main() -> miette::Result<ExitCode> {
             let mut project_sources = project.sources;
 
             sources.append(&mut project_sources);
+            dependencies = project.dependencies;
 
             features.merge(LanguageFeatures::from_iter(
                 manifest.manifest.language_features,
@@ -102,8 +113,9 @@ fn main() -> miette::Result<ExitCode> {
         }
     }
     if cli.exec {
-        let mut interpreter = match Interpreter::new(
+        let mut interpreter = match Interpreter::with_dependencies(
             !cli.nostdlib,
+            &dependencies,
             SourceMap::new(sources, cli.entry.map(std::convert::Into::into)),
             PackageType::Exe,
             TargetCapabilityFlags::all(),
@@ -117,13 +129,19 @@ fn main() -> miette::Result<ExitCode> {
                 return Ok(ExitCode::FAILURE);
             }
         };
-        return Ok(print_exec_result(
-            interpreter.eval_entry(&mut TerminalReceiver),
-        ));
+        if let Some(path) = &cli.replay_measurements {
+            replay_measurements(&mut interpreter, path)?;
+        }
+        let exit_code = print_exec_result(interpreter.eval_entry(&mut TerminalReceiver));
+        if let Some(path) = &cli.record_measurements {
+            record_measurements(&interpreter, path)?;
+        }
+        return Ok(exit_code);
     }
 
-    let mut interpreter = match Interpreter::new(
+    let mut interpreter = match Interpreter::with_dependencies(
         !cli.nostdlib,
+        &dependencies,
         SourceMap::new(sources, None),
         PackageType::Lib,
         TargetCapabilityFlags::all(),
@@ -138,12 +156,20 @@ fn main() -> miette::Result<ExitCode> {
         }
     };
 
+    if let Some(path) = &cli.replay_measurements {
+        replay_measurements(&mut interpreter, path)?;
+    }
+
     if let Some(entry) = cli.entry {
         print_interpret_result(interpreter.eval_fragments(&mut TerminalReceiver, &entry));
     }
 
     repl(&mut interpreter, &mut TerminalReceiver).into_diagnostic()?;
 
+    if let Some(path) = &cli.record_measurements {
+        record_measurements(&interpreter, path)?;
+    }
+
     Ok(ExitCode::SUCCESS)
 }
 
@@ -176,6 +202,25 @@ fn repl(interpreter: &mut Interpreter, receiver: &mut impl Receiver) -> io::Resu
     Ok(())
 }
 
+fn replay_measurements(interpreter: &mut Interpreter, path: &Path) -> miette::Result<()> {
+    let contents = fs::read_to_string(path)
+        .into_diagnostic()
+        .with_context(|| format!("could not read measurement record `{}`", path.display()))?;
+    let outcomes = parse_measurement_record(&contents)
+        .map_err(|error| miette!("invalid measurement record `{}`: {error}", path.display()))?;
+    interpreter.set_measurement_replay(outcomes);
+    Ok(())
+}
+
+fn record_measurements(interpreter: &Interpreter, path: &Path) -> miette::Result<()> {
+    fs::write(
+        path,
+        format_measurement_record(interpreter.recorded_measurements()),
+    )
+    .into_diagnostic()
+    .with_context(|| format!("could not write measurement record `{}`", path.display()))
+}
+
 fn read_source(path: impl AsRef<Path>) -> miette::Result<(SourceName, SourceContents)> {
     let path = path.as_ref();
     let contents = fs::read_to_string(path)
@@ -4,40 +4,126 @@
 #[cfg(test)]
 mod tests;
 
-use qsc_codegen::qir::fir_to_qir;
+use qsc_codegen::{
+    qir::{fir_to_qir, fir_to_qir_with_stats, fir_to_rir},
+    qir_reader,
+};
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
-use qsc_frontend::compile::{PackageStore, SourceMap};
+use qsc_fir::fir::PackageStore;
+use qsc_frontend::compile::SourceMap;
+use qsc_hir::hir::PackageId;
 use qsc_partial_eval::ProgramEntry;
 use qsc_passes::{PackageType, PassContext};
-use qsc_rca::Analyzer;
+use qsc_project::DependencyPackage;
+use qsc_rca::{Analyzer, PackageStoreComputeProperties};
+use qsc_rir::rir;
+
+pub use qsc_rir::{passes::GateOptimizationStats, rir::OptimizationLevel};
 
 use crate::compile;
 
 pub fn get_qir(
+    dependencies: &[DependencyPackage],
     sources: SourceMap,
     language_features: LanguageFeatures,
     capabilities: TargetCapabilityFlags,
+    optimization_level: OptimizationLevel,
 ) -> Result<String, String> {
+    let (fir_store, compute_properties, entry) = prepare_codegen(
+        dependencies,
+        sources,
+        language_features,
+        capabilities,
+        "QIR",
+    )?;
+    fir_to_qir(
+        &fir_store,
+        capabilities,
+        optimization_level,
+        Some(compute_properties),
+        &entry,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Generates QIR for the entry expression of a package in the given store, which must have been
+/// compiled without errors as an executable for the given capabilities, along with statistics about
+/// the gates removed by optimization.
+pub fn get_qir_from_store(
+    package_store: &qsc_frontend::compile::PackageStore,
+    package_id: PackageId,
+    capabilities: TargetCapabilityFlags,
+    optimization_level: OptimizationLevel,
+) -> Result<(String, GateOptimizationStats), String> {
+    let (fir_store, compute_properties, entry) =
+        prepare_entry(package_store, package_id, capabilities, "QIR")?;
+    fir_to_qir_with_stats(
+        &fir_store,
+        capabilities,
+        optimization_level,
+        Some(compute_properties),
+        &entry,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Partially evaluates the given sources and returns the resulting RIR program, transformed and
+/// optimized in the same way as it is before QIR generation.
+pub fn get_rir(
+    dependencies: &[DependencyPackage],
+    sources: SourceMap,
+    language_features: LanguageFeatures,
+    capabilities: TargetCapabilityFlags,
+    optimization_level: OptimizationLevel,
+) -> Result<rir::Program, String> {
+    let (fir_store, compute_properties, entry) = prepare_codegen(
+        dependencies,
+        sources,
+        language_features,
+        capabilities,
+        "RIR",
+    )?;
+    fir_to_rir(
+        &fir_store,
+        capabilities,
+        optimization_level,
+        Some(compute_properties),
+        &entry,
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn prepare_codegen(
+    dependencies: &[DependencyPackage],
+    sources: SourceMap,
+    language_features: LanguageFeatures,
+    capabilities: TargetCapabilityFlags,
+    output: &str,
+) -> Result<(PackageStore, PackageStoreComputeProperties, ProgramEntry), String> {
     let core = compile::core();
-    let mut package_store = PackageStore::new(core);
+    let mut package_store = qsc_frontend::compile::PackageStore::new(core);
     let std = compile::std(&package_store, capabilities);
     let std = package_store.insert(std);
 
-    let (unit, errors) = crate::compile::compile(
+    let (direct, mut errors) =
+        compile::compile_dependencies(&mut package_store, &[std], dependencies, capabilities);
+    let package_ids = std::iter::once(std).chain(direct).collect::<Vec<_>>();
+    let (unit, unit_errors) = crate::compile::compile(
         &package_store,
-        &[std],
+        &package_ids,
         sources,
         PackageType::Exe,
         capabilities,
         language_features,
     );
+    errors.extend(unit_errors);
 
     // Ensure it compiles before trying to add it to the store.
     if !errors.is_empty() {
-        // This will happen when QIR generation is attempted on a program that has errors.
+        // This will happen when code generation is attempted on a program that has errors.
         // This can happen in the playground.
         let mut error_message =
-            String::from("Failed to generate QIR. Could not compile sources.:\n");
+            format!("Failed to generate {output}. Could not compile sources.:\n");
         for error in errors {
             error_message.push_str(&format!("{error}\n"));
         }
@@ -46,7 +132,16 @@ pub fn get_qir(
     }
 
     let package_id = package_store.insert(unit);
-    let (fir_store, fir_package_id) = qsc_passes::lower_hir_to_fir(&package_store, package_id);
+    prepare_entry(&package_store, package_id, capabilities, output)
+}
+
+fn prepare_entry(
+    package_store: &qsc_frontend::compile::PackageStore,
+    package_id: PackageId,
+    capabilities: TargetCapabilityFlags,
+    output: &str,
+) -> Result<(PackageStore, PackageStoreComputeProperties, ProgramEntry), String> {
+    let (fir_store, fir_package_id) = qsc_passes::lower_hir_to_fir(package_store, package_id);
     let package = fir_store.get(fir_package_id);
     let entry = ProgramEntry {
         exec_graph: package.entry_exec_graph.clone(),
@@ -67,12 +162,28 @@ pub fn get_qir(
         PassContext::run_fir_passes_on_fir(&fir_store, fir_package_id, capabilities)
     };
 
-    let Ok(compute_properties) = compute_properties else {
+    let compute_properties = compute_properties.map_err(|errors| {
         // This should never happen, as the program should be checked for errors before trying to
-        // generate code for it. But just in case, simply report the failure.
-        return Err("Failed to generate QIR. Could not generate compute properties.".to_string());
-    };
+        // generate code for it. But just in case, report the failure along with its errors.
+        let messages = errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("Failed to generate {output}. Could not generate compute properties.\n{messages}")
+    })?;
+
+    Ok((fir_store, compute_properties, entry))
+}
+
+/// Reads a Base or Adaptive profile QIR program, in LLVM textual form, into the equivalent RIR program, which
+/// can be executed on any backend with `qsc::rir::exec`.
+pub fn read_qir(qir: &str) -> Result<rir::Program, qir_reader::Error> {
+    qir_reader::read_qir(qir)
+}
 
-    fir_to_qir(&fir_store, capabilities, Some(compute_properties), &entry)
-        .map_err(|e| e.to_string())
+/// Reads a Base or Adaptive profile QIR program, in LLVM textual form, and returns the equivalent RIR program
+/// in its textual form.
+pub fn qir_to_rir(qir: &str) -> Result<String, qir_reader::Error> {
+    read_qir(qir).map(|program| program.to_string())
 }
@@ -5,7 +5,7 @@ use expect_test::expect;
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
 use qsc_frontend::compile::SourceMap;
 
-use crate::codegen::get_qir;
+use crate::codegen::{get_qir, OptimizationLevel};
 
 #[test]
 fn code_with_errors_returns_errors() {
@@ -25,7 +25,13 @@ fn code_with_errors_returns_errors() {
             "Failed to generate QIR. Could not compile sources.:\nsyntax error\n",
         )
     "#]]
-    .assert_debug_eq(&get_qir(sources, language_features, capabilities));
+    .assert_debug_eq(&get_qir(
+        &[],
+        sources,
+        language_features,
+        capabilities,
+        OptimizationLevel::Basic,
+    ));
 }
 
 mod base_profile {
@@ -33,7 +39,7 @@ mod base_profile {
     use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
     use qsc_frontend::compile::SourceMap;
 
-    use crate::codegen::get_qir;
+    use crate::codegen::{get_qir, OptimizationLevel};
 
     #[test]
     fn simple() {
@@ -56,8 +62,14 @@ mod base_profile {
         let language_features = LanguageFeatures::default();
         let capabilities = TargetCapabilityFlags::empty();
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -106,8 +118,14 @@ mod base_profile {
         let language_features = LanguageFeatures::default();
         let capabilities = TargetCapabilityFlags::empty();
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -159,8 +177,14 @@ mod base_profile {
         let language_features = LanguageFeatures::default();
         let capabilities = TargetCapabilityFlags::empty();
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -205,7 +229,7 @@ mod adaptive_profile {
     use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
     use qsc_frontend::compile::SourceMap;
 
-    use crate::codegen::get_qir;
+    use crate::codegen::{get_qir, OptimizationLevel};
 
     #[test]
     fn simple() {
@@ -228,8 +252,14 @@ mod adaptive_profile {
         let language_features = LanguageFeatures::default();
         let capabilities = TargetCapabilityFlags::Adaptive;
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -285,8 +315,14 @@ mod adaptive_profile {
         let language_features = LanguageFeatures::default();
         let capabilities = TargetCapabilityFlags::Adaptive;
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -345,8 +381,14 @@ mod adaptive_profile {
         let language_features = LanguageFeatures::default();
         let capabilities = TargetCapabilityFlags::Adaptive;
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -391,6 +433,77 @@ mod adaptive_profile {
             !10 = !{i32 1, !"multiple_target_branching", i1 false}
         "#]].assert_eq(&qir);
     }
+
+    #[test]
+    fn loop_with_classical_break_and_continue() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                for i in 0..9 {
+                    if i % 2 == 1 {
+                        continue;
+                    }
+                    if i > 4 {
+                        break;
+                    }
+                    X(q);
+                }
+                MResetZ(q)
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        expect![[r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              call void @__quantum__qis__x__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__x__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__x__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__mz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+              call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 0 to %Result*), i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__x__body(%Qubit*)
+
+            declare void @__quantum__rt__result_record_output(%Result*, i8*)
+
+            declare void @__quantum__qis__mz__body(%Qubit*, %Result*) #1
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="1" }
+            attributes #1 = { "irreversible" }
+
+            ; module flags
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 false}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"classical_ints", i1 false}
+            !5 = !{i32 1, !"classical_floats", i1 false}
+            !6 = !{i32 1, !"backwards_branching", i1 false}
+            !7 = !{i32 1, !"qubit_resetting", i1 false}
+            !8 = !{i32 1, !"classical_fixed_points", i1 false}
+            !9 = !{i32 1, !"user_functions", i1 false}
+            !10 = !{i32 1, !"multiple_target_branching", i1 false}
+        "#]].assert_eq(&qir);
+    }
 }
 
 mod quantinuum_profile {
@@ -398,7 +511,7 @@ mod quantinuum_profile {
     use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
     use qsc_frontend::compile::SourceMap;
 
-    use crate::codegen::get_qir;
+    use crate::codegen::{get_qir, OptimizationLevel};
 
     #[test]
     fn simple() {
@@ -423,8 +536,14 @@ mod quantinuum_profile {
             | TargetCapabilityFlags::QubitReset
             | TargetCapabilityFlags::IntegerComputations;
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -482,8 +601,14 @@ mod quantinuum_profile {
             | TargetCapabilityFlags::QubitReset
             | TargetCapabilityFlags::IntegerComputations;
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -544,8 +669,14 @@ mod quantinuum_profile {
             | TargetCapabilityFlags::QubitReset
             | TargetCapabilityFlags::IntegerComputations;
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -607,8 +738,14 @@ mod quantinuum_profile {
             | TargetCapabilityFlags::QubitReset
             | TargetCapabilityFlags::IntegerComputations;
 
-        let qir =
-            get_qir(sources, language_features, capabilities).expect("Failed to generate QIR");
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
         expect![[r#"
             %Result = type opaque
             %Qubit = type opaque
@@ -658,4 +795,1087 @@ mod quantinuum_profile {
             !10 = !{i32 1, !"multiple_target_branching", i1 false}
         "#]].assert_eq(&qir);
     }
+
+    #[test]
+    fn dynamic_double_with_floating_point_computations_supported() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                H(q);
+                let theta = MResetZ(q) == Zero ? 0.0 | 1.0;
+                Rx(theta * 2.0, q);
+                MResetZ(q)
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive
+            | TargetCapabilityFlags::QubitReset
+            | TargetCapabilityFlags::IntegerComputations
+            | TargetCapabilityFlags::FloatingPointComputations;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        expect![[r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              call void @__quantum__qis__h__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+              %var_0 = call i1 @__quantum__qis__read_result__body(%Result* inttoptr (i64 0 to %Result*))
+              %var_1 = icmp eq i1 %var_0, false
+              br i1 %var_1, label %block_1, label %block_2
+            block_1:
+              br label %block_3
+            block_2:
+              br label %block_3
+            block_3:
+              %var_4 = phi double [0.0, %block_1], [1.0, %block_2]
+              %var_3 = fmul double %var_4, 2.0
+              call void @__quantum__qis__rx__body(double %var_3, %Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 1 to %Result*))
+              call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 1 to %Result*), i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__h__body(%Qubit*)
+
+            declare void @__quantum__qis__mresetz__body(%Qubit*, %Result*) #1
+
+            declare i1 @__quantum__qis__read_result__body(%Result*)
+
+            declare void @__quantum__qis__rx__body(double, %Qubit*)
+
+            declare void @__quantum__rt__result_record_output(%Result*, i8*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="2" }
+            attributes #1 = { "irreversible" }
+
+            ; module flags
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 false}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"classical_ints", i1 true}
+            !5 = !{i32 1, !"classical_floats", i1 true}
+            !6 = !{i32 1, !"qubit_resetting", i1 true}
+            !7 = !{i32 1, !"backwards_branching", i1 false}
+            !8 = !{i32 1, !"classical_fixed_points", i1 false}
+            !9 = !{i32 1, !"user_functions", i1 false}
+            !10 = !{i32 1, !"multiple_target_branching", i1 false}
+        "#]].assert_eq(&qir);
+    }
+
+    #[test]
+    fn repeat_until_success_loop_with_backwards_branching_supported() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                use q = Qubit();
+                mutable attempts = 0;
+                repeat {
+                    H(q);
+                    set attempts += 1;
+                } until MResetZ(q) == Zero;
+                attempts
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive
+            | TargetCapabilityFlags::QubitReset
+            | TargetCapabilityFlags::IntegerComputations
+            | TargetCapabilityFlags::BackwardsBranching;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        expect![[r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              br label %block_1
+            block_1:
+              %var_8 = phi i1 [true, %block_0], [%var_6, %block_2]
+              %var_7 = phi i64 [0, %block_0], [%var_3, %block_2]
+              br i1 %var_8, label %block_2, label %block_3
+            block_2:
+              call void @__quantum__qis__h__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              %var_3 = add i64 %var_7, 1
+              call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+              %var_4 = call i1 @__quantum__qis__read_result__body(%Result* inttoptr (i64 0 to %Result*))
+              %var_5 = icmp eq i1 %var_4, false
+              %var_6 = xor i1 %var_5, true
+              br label %block_1
+            block_3:
+              call void @__quantum__rt__integer_record_output(i64 %var_7, i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__h__body(%Qubit*)
+
+            declare void @__quantum__qis__mresetz__body(%Qubit*, %Result*) #1
+
+            declare i1 @__quantum__qis__read_result__body(%Result*)
+
+            declare void @__quantum__rt__integer_record_output(i64, i8*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="1" }
+            attributes #1 = { "irreversible" }
+
+            ; module flags
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 false}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"classical_ints", i1 true}
+            !5 = !{i32 1, !"backwards_branching", i1 true}
+            !6 = !{i32 1, !"qubit_resetting", i1 true}
+            !7 = !{i32 1, !"classical_floats", i1 false}
+            !8 = !{i32 1, !"classical_fixed_points", i1 false}
+            !9 = !{i32 1, !"user_functions", i1 false}
+            !10 = !{i32 1, !"multiple_target_branching", i1 false}
+        "#]]
+        .assert_eq(&qir);
+    }
+
+    #[test]
+    fn repeat_until_success_loop_without_optimization_supported() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                use q = Qubit();
+                mutable attempts = 0;
+                set attempts += 1;
+                repeat {
+                    H(q);
+                    set attempts += 1;
+                } until MResetZ(q) == Zero;
+                attempts
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive
+            | TargetCapabilityFlags::QubitReset
+            | TargetCapabilityFlags::IntegerComputations
+            | TargetCapabilityFlags::BackwardsBranching;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::None,
+        )
+        .expect("Failed to generate QIR");
+        expect![[r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              br label %block_1
+            block_1:
+              %var_8 = phi i1 [true, %block_0], [%var_6, %block_2]
+              %var_7 = phi i64 [1, %block_0], [%var_3, %block_2]
+              br i1 %var_8, label %block_2, label %block_3
+            block_2:
+              call void @__quantum__qis__h__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              %var_3 = add i64 %var_7, 1
+              call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+              %var_4 = call i1 @__quantum__qis__read_result__body(%Result* inttoptr (i64 0 to %Result*))
+              %var_5 = icmp eq i1 %var_4, false
+              %var_6 = xor i1 %var_5, true
+              br label %block_1
+            block_3:
+              call void @__quantum__rt__integer_record_output(i64 %var_7, i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__h__body(%Qubit*)
+
+            declare void @__quantum__qis__mresetz__body(%Qubit*, %Result*) #1
+
+            declare i1 @__quantum__qis__read_result__body(%Result*)
+
+            declare void @__quantum__rt__integer_record_output(i64, i8*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="1" }
+            attributes #1 = { "irreversible" }
+
+            ; module flags
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 false}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"classical_ints", i1 true}
+            !5 = !{i32 1, !"backwards_branching", i1 true}
+            !6 = !{i32 1, !"qubit_resetting", i1 true}
+            !7 = !{i32 1, !"classical_floats", i1 false}
+            !8 = !{i32 1, !"classical_fixed_points", i1 false}
+            !9 = !{i32 1, !"user_functions", i1 false}
+            !10 = !{i32 1, !"multiple_target_branching", i1 false}
+        "#]].assert_eq(&qir);
+    }
+}
+
+mod higher_level_constructs {
+    use expect_test::expect;
+    use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+    use qsc_frontend::compile::SourceMap;
+
+    use crate::codegen::{get_qir, OptimizationLevel};
+
+    #[test]
+    fn no_inline_operation_generates_user_function() {
+        let source = "namespace Test {
+            @NoInline()
+            operation PrepareAndCorrect(q : Qubit, flip : Bool) : Unit {
+                H(q);
+                if flip {
+                    X(q);
+                }
+            }
+            @EntryPoint()
+            operation Main() : (Result, Result) {
+                use (q0, q1) = (Qubit(), Qubit());
+                let r = MResetZ(q0);
+                PrepareAndCorrect(q0, r == One);
+                PrepareAndCorrect(q1, false);
+                (MResetZ(q0), MResetZ(q1))
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::all();
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        expect![[r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+              %var_0 = call i1 @__quantum__qis__read_result__body(%Result* inttoptr (i64 0 to %Result*))
+              %var_1 = icmp eq i1 %var_0, true
+              call void @PrepareAndCorrect__body(%Qubit* inttoptr (i64 0 to %Qubit*), i1 %var_1)
+              call void @PrepareAndCorrect__body__1(%Qubit* inttoptr (i64 1 to %Qubit*))
+              call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 1 to %Result*))
+              call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 1 to %Qubit*), %Result* inttoptr (i64 2 to %Result*))
+              call void @__quantum__rt__tuple_record_output(i64 2, i8* null)
+              call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 1 to %Result*), i8* null)
+              call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 2 to %Result*), i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__mresetz__body(%Qubit*, %Result*) #1
+
+            declare i1 @__quantum__qis__read_result__body(%Result*)
+
+            define void @PrepareAndCorrect__body(%Qubit* %var_2, i1 %var_3) {
+            block_1:
+              call void @__quantum__qis__h__body(%Qubit* %var_2)
+              br i1 %var_3, label %block_2, label %block_3
+            block_2:
+              call void @__quantum__qis__x__body(%Qubit* %var_2)
+              br label %block_3
+            block_3:
+              ret void
+            }
+
+            declare void @__quantum__qis__h__body(%Qubit*)
+
+            declare void @__quantum__qis__x__body(%Qubit*)
+
+            define void @PrepareAndCorrect__body__1(%Qubit* %var_4) {
+            block_4:
+              call void @__quantum__qis__h__body(%Qubit* %var_4)
+              ret void
+            }
+
+            declare void @__quantum__rt__tuple_record_output(i64, i8*)
+
+            declare void @__quantum__rt__result_record_output(%Result*, i8*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="2" "required_num_results"="3" }
+            attributes #1 = { "irreversible" }
+
+            ; module flags
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 true}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"classical_ints", i1 true}
+            !5 = !{i32 1, !"classical_floats", i1 true}
+            !6 = !{i32 1, !"backwards_branching", i1 true}
+            !7 = !{i32 1, !"qubit_resetting", i1 true}
+            !8 = !{i32 1, !"classical_fixed_points", i1 false}
+            !9 = !{i32 1, !"user_functions", i1 true}
+            !10 = !{i32 1, !"multiple_target_branching", i1 false}
+        "#]]
+        .assert_eq(&qir);
+    }
+}
+
+mod dynamic_qubit_management {
+    use expect_test::expect;
+    use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+    use qsc_frontend::compile::SourceMap;
+
+    use crate::codegen::{get_qir, OptimizationLevel};
+
+    #[test]
+    fn qubit_allocated_in_dynamic_branch_uses_runtime_functions() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q0 = Qubit();
+                H(q0);
+                let r = MResetZ(q0);
+                if r == One {
+                    use q1 = Qubit();
+                    CNOT(q0, q1);
+                    Reset(q1);
+                }
+                r
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive
+            | TargetCapabilityFlags::QubitReset
+            | TargetCapabilityFlags::DynamicQubitManagement;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        expect![[r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              call void @__quantum__qis__h__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+              %var_0 = call i1 @__quantum__qis__read_result__body(%Result* inttoptr (i64 0 to %Result*))
+              %var_1 = icmp eq i1 %var_0, true
+              br i1 %var_1, label %block_1, label %block_2
+            block_1:
+              %var_2 = call %Qubit* @__quantum__rt__qubit_allocate()
+              call void @__quantum__qis__cx__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Qubit* %var_2)
+              call void @__quantum__qis__reset__body(%Qubit* %var_2)
+              call void @__quantum__rt__qubit_release(%Qubit* %var_2)
+              br label %block_2
+            block_2:
+              call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 0 to %Result*), i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__h__body(%Qubit*)
+
+            declare void @__quantum__qis__mresetz__body(%Qubit*, %Result*) #1
+
+            declare i1 @__quantum__qis__read_result__body(%Result*)
+
+            declare %Qubit* @__quantum__rt__qubit_allocate()
+
+            declare void @__quantum__qis__cx__body(%Qubit*, %Qubit*)
+
+            declare void @__quantum__qis__reset__body(%Qubit*)
+
+            declare void @__quantum__rt__qubit_release(%Qubit*)
+
+            declare void @__quantum__rt__result_record_output(%Result*, i8*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="1" }
+            attributes #1 = { "irreversible" }
+
+            ; module flags
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 true}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"qubit_resetting", i1 true}
+            !5 = !{i32 1, !"classical_ints", i1 false}
+            !6 = !{i32 1, !"classical_floats", i1 false}
+            !7 = !{i32 1, !"backwards_branching", i1 false}
+            !8 = !{i32 1, !"classical_fixed_points", i1 false}
+            !9 = !{i32 1, !"user_functions", i1 false}
+            !10 = !{i32 1, !"multiple_target_branching", i1 false}
+        "#]]
+        .assert_eq(&qir);
+    }
+
+    #[test]
+    fn qubit_array_allocated_in_dynamic_branch_uses_array_runtime_functions() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q0 = Qubit();
+                H(q0);
+                let r = MResetZ(q0);
+                if r == One {
+                    use qs = Qubit[2];
+                    CNOT(q0, qs[1]);
+                    ResetAll(qs);
+                }
+                r
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive
+            | TargetCapabilityFlags::QubitReset
+            | TargetCapabilityFlags::DynamicQubitManagement;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        expect![[r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              call void @__quantum__qis__h__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+              %var_0 = call i1 @__quantum__qis__read_result__body(%Result* inttoptr (i64 0 to %Result*))
+              %var_1 = icmp eq i1 %var_0, true
+              br i1 %var_1, label %block_1, label %block_2
+            block_1:
+              %var_2 = call i8* @__quantum__rt__qubit_allocate_array(i64 2)
+              %var_3 = call i8* @__quantum__rt__array_get_element_ptr_1d(i8* %var_2, i64 0)
+              %var_4.ptr = bitcast i8* %var_3 to %Qubit**
+              %var_4 = load %Qubit*, %Qubit** %var_4.ptr
+              %var_5 = call i8* @__quantum__rt__array_get_element_ptr_1d(i8* %var_2, i64 1)
+              %var_6.ptr = bitcast i8* %var_5 to %Qubit**
+              %var_6 = load %Qubit*, %Qubit** %var_6.ptr
+              call void @__quantum__qis__cx__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Qubit* %var_6)
+              call void @__quantum__qis__reset__body(%Qubit* %var_4)
+              call void @__quantum__qis__reset__body(%Qubit* %var_6)
+              call void @__quantum__rt__qubit_release_array(i8* %var_2)
+              br label %block_2
+            block_2:
+              call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 0 to %Result*), i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__h__body(%Qubit*)
+
+            declare void @__quantum__qis__mresetz__body(%Qubit*, %Result*) #1
+
+            declare i1 @__quantum__qis__read_result__body(%Result*)
+
+            declare i8* @__quantum__rt__qubit_allocate_array(i64)
+
+            declare i8* @__quantum__rt__array_get_element_ptr_1d(i8*, i64)
+
+            declare void @__quantum__qis__cx__body(%Qubit*, %Qubit*)
+
+            declare void @__quantum__qis__reset__body(%Qubit*)
+
+            declare void @__quantum__rt__qubit_release_array(i8*)
+
+            declare void @__quantum__rt__result_record_output(%Result*, i8*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="1" }
+            attributes #1 = { "irreversible" }
+
+            ; module flags
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 true}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"qubit_resetting", i1 true}
+            !5 = !{i32 1, !"classical_ints", i1 false}
+            !6 = !{i32 1, !"classical_floats", i1 false}
+            !7 = !{i32 1, !"backwards_branching", i1 false}
+            !8 = !{i32 1, !"classical_fixed_points", i1 false}
+            !9 = !{i32 1, !"user_functions", i1 false}
+            !10 = !{i32 1, !"multiple_target_branching", i1 false}
+        "#]].assert_eq(&qir);
+    }
+}
+
+mod qir_reading {
+    use expect_test::expect;
+    use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+    use qsc_frontend::compile::SourceMap;
+
+    use crate::{
+        codegen::{get_qir, qir_to_rir, read_qir, OptimizationLevel},
+        interpret::{output::output_value, GenericReceiver},
+        SparseSim,
+    };
+
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn generated_adaptive_qir_can_be_read() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : (Result, Bool) {
+                use (q0, q1) = (Qubit(), Qubit());
+                H(q0);
+                let r = MResetZ(q0);
+                if r == One {
+                    X(q1);
+                }
+                (MResetZ(q1), r == Zero)
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive
+            | TargetCapabilityFlags::IntegerComputations
+            | TargetCapabilityFlags::QubitReset;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        let rir = qir_to_rir(&qir).expect("Failed to read QIR");
+        expect![[r"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: ENTRYPOINT__main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__qis__h__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__qis__mresetz__body
+                        call_type: Measurement
+                        input_type:
+                            [0]: Qubit
+                            [1]: Result
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__qis__read_result__body
+                        call_type: Readout
+                        input_type:
+                            [0]: Result
+                        output_type: Boolean
+                        body: <NONE>
+                    Callable 4: Callable:
+                        name: __quantum__qis__x__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 5: Callable:
+                        name: __quantum__rt__tuple_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Integer
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 6: Callable:
+                        name: __quantum__rt__result_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Result
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 7: Callable:
+                        name: __quantum__rt__bool_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Boolean
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Call id(1), args( Qubit(0), )
+                        Call id(2), args( Qubit(0), Result(0), )
+                        Variable(0, Boolean) = Call id(3), args( Result(0), )
+                        Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+                        Branch Variable(1, Boolean), 1, 2
+                    Block 1: Block:
+                        Call id(4), args( Qubit(1), )
+                        Jump(2)
+                    Block 2: Block:
+                        Call id(2), args( Qubit(1), Result(1), )
+                        Variable(2, Boolean) = Call id(3), args( Result(0), )
+                        Variable(3, Boolean) = Icmp Eq, Variable(2, Boolean), Bool(false)
+                        Call id(5), args( Integer(2), Pointer, )
+                        Call id(6), args( Result(1), Pointer, )
+                        Call id(7), args( Variable(3, Boolean), Pointer, )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | QubitReset)
+                num_qubits: 2
+                num_results: 2"]]
+        .assert_eq(&rir);
+    }
+
+    #[test]
+    fn generated_base_qir_can_be_read() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Result[] {
+                use (q0, q1) = (Qubit(), Qubit());
+                H(q0);
+                CNOT(q0, q1);
+                [MResetZ(q0), MResetZ(q1)]
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::empty();
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        let rir = qir_to_rir(&qir).expect("Failed to read QIR");
+        expect![[r"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: ENTRYPOINT__main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__qis__h__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__qis__cx__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                            [1]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__qis__mz__body
+                        call_type: Measurement
+                        input_type:
+                            [0]: Qubit
+                            [1]: Result
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 4: Callable:
+                        name: __quantum__rt__array_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Integer
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 5: Callable:
+                        name: __quantum__rt__result_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Result
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Call id(1), args( Qubit(0), )
+                        Call id(2), args( Qubit(0), Qubit(1), )
+                        Call id(3), args( Qubit(0), Result(0), )
+                        Call id(3), args( Qubit(1), Result(1), )
+                        Call id(4), args( Integer(2), Pointer, )
+                        Call id(5), args( Result(0), Pointer, )
+                        Call id(5), args( Result(1), Pointer, )
+                        Return
+                config: Config:
+                    capabilities: Base
+                num_qubits: 2
+                num_results: 2"]]
+        .assert_eq(&rir);
+    }
+
+    #[test]
+    fn generated_adaptive_qir_can_be_read_and_run() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : (Result, Bool) {
+                use (q0, q1) = (Qubit(), Qubit());
+                X(q0);
+                let r = MResetZ(q0);
+                if r == One {
+                    X(q1);
+                }
+                (MResetZ(q1), r == Zero)
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive
+            | TargetCapabilityFlags::IntegerComputations
+            | TargetCapabilityFlags::QubitReset;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        let program = read_qir(&qir).expect("Failed to read QIR");
+        let mut sink = std::io::sink();
+        let records = crate::rir::exec(
+            &program,
+            &mut SparseSim::new(),
+            &mut GenericReceiver::new(&mut sink),
+        )
+        .expect("Failed to run QIR");
+        let value = output_value(&records).expect("output records should form a value");
+        expect!["(One, false)"].assert_eq(&value.to_string());
+    }
+
+    #[test]
+    fn generated_qir_with_runtime_qubit_array_can_be_read_and_run() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : (Result, Result) {
+                use (q0, q1) = (Qubit(), Qubit());
+                X(q0);
+                let r = MResetZ(q0);
+                if r == One {
+                    use qs = Qubit[2];
+                    X(qs[1]);
+                    CNOT(qs[1], q1);
+                    ResetAll(qs);
+                }
+                (r, MResetZ(q1))
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive
+            | TargetCapabilityFlags::QubitReset
+            | TargetCapabilityFlags::DynamicQubitManagement;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate QIR");
+        let program = read_qir(&qir).expect("Failed to read QIR");
+        let mut sink = std::io::sink();
+        let records = crate::rir::exec(
+            &program,
+            &mut SparseSim::new(),
+            &mut GenericReceiver::new(&mut sink),
+        )
+        .expect("Failed to run QIR");
+        let value = output_value(&records).expect("output records should form a value");
+        expect!["(One, One)"].assert_eq(&value.to_string());
+    }
+}
+
+mod full_optimization {
+    use expect_test::expect;
+    use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+    use qsc_frontend::compile::SourceMap;
+
+    use crate::codegen::{get_qir, OptimizationLevel};
+
+    #[test]
+    fn rotations_on_same_qubit_are_merged() {
+        let source = "namespace Test {
+            open Microsoft.Quantum.Math;
+            open QIR.Intrinsic;
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                let pi_over_two = 4.0 / 2.0;
+                __quantum__qis__rz__body(pi_over_two, q);
+                mutable some_angle = ArcSin(0.0);
+                __quantum__qis__rz__body(some_angle, q);
+                set some_angle = ArcCos(-1.0) / PI();
+                __quantum__qis__rz__body(some_angle, q);
+                __quantum__qis__mresetz__body(q)
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Full,
+        )
+        .expect("Failed to generate QIR");
+        expect![[r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              call void @__quantum__qis__rz__body(double 3.0, %Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__mz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+              call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 0 to %Result*), i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__rz__body(double, %Qubit*)
+
+            declare void @__quantum__rt__result_record_output(%Result*, i8*)
+
+            declare void @__quantum__qis__mz__body(%Qubit*, %Result*) #1
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="1" }
+            attributes #1 = { "irreversible" }
+
+            ; module flags
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 false}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"classical_ints", i1 false}
+            !5 = !{i32 1, !"classical_floats", i1 false}
+            !6 = !{i32 1, !"backwards_branching", i1 false}
+            !7 = !{i32 1, !"qubit_resetting", i1 false}
+            !8 = !{i32 1, !"classical_fixed_points", i1 false}
+            !9 = !{i32 1, !"user_functions", i1 false}
+            !10 = !{i32 1, !"multiple_target_branching", i1 false}
+        "#]].assert_eq(&qir);
+    }
+
+    #[test]
+    fn self_inverse_gates_from_unrolled_loop_are_cancelled() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                for i in 0..9 {
+                    if i % 2 == 1 {
+                        continue;
+                    }
+                    if i > 4 {
+                        break;
+                    }
+                    X(q);
+                }
+                MResetZ(q)
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities = TargetCapabilityFlags::Adaptive;
+
+        let qir = get_qir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Full,
+        )
+        .expect("Failed to generate QIR");
+        expect![[r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              call void @__quantum__qis__x__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__mz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+              call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 0 to %Result*), i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__x__body(%Qubit*)
+
+            declare void @__quantum__rt__result_record_output(%Result*, i8*)
+
+            declare void @__quantum__qis__mz__body(%Qubit*, %Result*) #1
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="1" }
+            attributes #1 = { "irreversible" }
+
+            ; module flags
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 false}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"classical_ints", i1 false}
+            !5 = !{i32 1, !"classical_floats", i1 false}
+            !6 = !{i32 1, !"backwards_branching", i1 false}
+            !7 = !{i32 1, !"qubit_resetting", i1 false}
+            !8 = !{i32 1, !"classical_fixed_points", i1 false}
+            !9 = !{i32 1, !"user_functions", i1 false}
+            !10 = !{i32 1, !"multiple_target_branching", i1 false}
+        "#]].assert_eq(&qir);
+    }
+}
+
+mod rir_generation {
+    use expect_test::expect;
+    use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+    use qsc_frontend::compile::SourceMap;
+
+    use crate::codegen::{get_rir, OptimizationLevel};
+
+    #[test]
+    fn dynamic_branch_is_in_ssa_form() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                use q = Qubit();
+                let i = if M(q) == One { 1 } else { 2 };
+                Reset(q);
+                i
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+        let language_features = LanguageFeatures::default();
+        let capabilities =
+            TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::IntegerComputations;
+
+        let program = get_rir(
+            &[],
+            sources,
+            language_features,
+            capabilities,
+            OptimizationLevel::Basic,
+        )
+        .expect("Failed to generate RIR");
+        let blocks = program
+            .blocks
+            .iter()
+            .map(|(id, block)| format!("Block {}: {block}", id.0))
+            .collect::<Vec<_>>()
+            .join("\n");
+        expect![[r"
+            Block 0: Block:
+                Call id(5), args( Qubit(0), Qubit(1), )
+                Call id(1), args( Qubit(0), Result(0), )
+                Variable(0, Boolean) = Call id(2), args( Result(0), )
+                Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+                Branch Variable(1, Boolean), 1, 2
+            Block 1: Block:
+                Jump(3)
+            Block 2: Block:
+                Jump(3)
+            Block 3: Block:
+                Variable(2, Integer) = Phi ( [Integer(1), 1], [Integer(2), 2], )
+                Call id(4), args( Variable(2, Integer), Pointer, )
+                Return"]]
+        .assert_eq(&blocks);
+    }
+
+    #[test]
+    fn code_with_errors_returns_errors() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit()
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+
+        let Err(error) = get_rir(
+            &[],
+            sources,
+            LanguageFeatures::default(),
+            TargetCapabilityFlags::Adaptive,
+            OptimizationLevel::Basic,
+        ) else {
+            panic!("code should not compile");
+        };
+        expect![[r"
+            Failed to generate RIR. Could not compile sources.:
+            syntax error
+        "]]
+        .assert_eq(&error);
+    }
 }
@@ -8,7 +8,8 @@ use qsc_frontend::{
     error::WithSource,
 };
 use qsc_hir::hir::PackageId;
-use qsc_passes::{run_core_passes, run_default_passes, PackageType};
+use qsc_passes::{fold_consts, run_core_passes, run_default_passes, PackageType};
+use qsc_project::DependencyPackage;
 use thiserror::Error;
 
 pub type Error = WithSource<ErrorKind>;
@@ -73,6 +74,43 @@ pub fn compile(
     process_compile_unit(store, package_type, capabilities, unit)
 }
 
+/// Compiles the packages a project depends on into the store, ahead of the project itself.
+/// Each package is compiled as a library that sees the `base` packages, such as the standard
+/// library, and the packages it depends on directly, but none of their `internal` items.
+/// Returns the ids of the packages the project depends on directly, along with any errors
+/// from compiling the dependencies.
+#[must_use]
+pub fn compile_dependencies(
+    store: &mut PackageStore,
+    base: &[PackageId],
+    dependencies: &[DependencyPackage],
+    capabilities: TargetCapabilityFlags,
+) -> (Vec<PackageId>, Vec<Error>) {
+    let mut package_ids: Vec<PackageId> = Vec::with_capacity(dependencies.len());
+    let mut errors = Vec::new();
+    for dependency in dependencies {
+        let mut package_dependencies = base.to_vec();
+        package_dependencies.extend(dependency.dependencies.iter().map(|&i| package_ids[i]));
+        let (unit, unit_errors) = compile(
+            store,
+            &package_dependencies,
+            SourceMap::new(dependency.sources.iter().cloned(), None),
+            PackageType::Lib,
+            capabilities,
+            LanguageFeatures::from_iter(&dependency.manifest.language_features),
+        );
+        errors.extend(unit_errors);
+        package_ids.push(store.insert(unit));
+    }
+
+    let direct = dependencies
+        .iter()
+        .zip(package_ids)
+        .filter_map(|(dependency, id)| dependency.direct.then_some(id))
+        .collect();
+    (direct, errors)
+}
+
 #[must_use]
 #[allow(clippy::module_name_repetitions)]
 fn process_compile_unit(
@@ -92,6 +130,12 @@ fn process_compile_unit(
         }
     }
 
+    if errors.is_empty() {
+        for error in fold_consts(store, None, &mut unit.package, &mut unit.assigner) {
+            errors.push(WithSource::from_map(&unit.sources, error.into()));
+        }
+    }
+
     (unit, errors)
 }
 
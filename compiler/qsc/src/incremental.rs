@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::compile::{self, compile, core, std};
+use crate::compile::{self, compile, compile_dependencies, core, std};
 use miette::Diagnostic;
 
 use qsc_ast::ast;
@@ -12,8 +12,9 @@ use qsc_frontend::{
     error::WithSource,
     incremental::Increment,
 };
-use qsc_hir::hir::PackageId;
+use qsc_hir::{assigner::Assigner, hir::PackageId};
 use qsc_passes::{PackageType, PassContext};
+use qsc_project::DependencyPackage;
 
 /// An incremental Q# compiler.
 pub struct Compiler {
@@ -42,6 +43,28 @@ impl Compiler {
         package_type: PackageType,
         capabilities: TargetCapabilityFlags,
         language_features: LanguageFeatures,
+    ) -> Result<Self, Errors> {
+        Self::with_dependencies(
+            include_std,
+            &[],
+            sources,
+            package_type,
+            capabilities,
+            language_features,
+        )
+    }
+
+    /// Creates a new incremental compiler, compiling the packages the sources
+    /// depend on followed by the passed in sources.
+    /// # Errors
+    /// If compiling the dependencies or the sources fails, compiler errors are returned.
+    pub fn with_dependencies(
+        include_std: bool,
+        package_dependencies: &[DependencyPackage],
+        sources: SourceMap,
+        package_type: PackageType,
+        capabilities: TargetCapabilityFlags,
+        language_features: LanguageFeatures,
     ) -> Result<Self, Errors> {
         let core = core();
         let mut store = PackageStore::new(core);
@@ -52,6 +75,17 @@ impl Compiler {
             dependencies.push(id);
         }
 
+        let (direct, errors) = compile_dependencies(
+            &mut store,
+            &dependencies,
+            package_dependencies,
+            capabilities,
+        );
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        dependencies.extend(direct);
+
         let (unit, errors) = compile(
             &store,
             &dependencies,
@@ -176,6 +210,8 @@ impl Compiler {
             );
 
             accumulate_errors(into_errors_with_source(pass_errors, &unit.sources))?;
+            let const_errors = self.fold_consts(&mut increment);
+            accumulate_errors(const_errors)?;
         }
 
         Ok(increment)
@@ -222,6 +258,8 @@ impl Compiler {
             );
 
             accumulate_errors(into_errors_with_source(pass_errors, &unit.sources))?;
+            let const_errors = self.fold_consts(&mut increment);
+            accumulate_errors(const_errors)?;
         }
 
         Ok(increment)
@@ -255,9 +293,34 @@ impl Compiler {
             return Err(into_errors_with_source(pass_errors, &unit.sources));
         }
 
+        let const_errors = self.fold_consts(&mut increment);
+        if !const_errors.is_empty() {
+            return Err(const_errors);
+        }
+
         Ok(increment)
     }
 
+    /// Evaluates the constants declared in an increment and replaces the uses of constants
+    /// in the increment with their values.
+    fn fold_consts(&mut self, increment: &mut Increment) -> Errors {
+        let open = self.store.open_package_id();
+        // Constants are evaluated against the store, which includes the open package, so its
+        // assigner is moved out for the duration of the evaluation.
+        let (_, unit) = self.store.get_open_mut();
+        let mut assigner = std::mem::replace(&mut unit.assigner, Assigner::new());
+        let errors = qsc_passes::fold_consts(
+            self.store.package_store(),
+            Some(open),
+            &mut increment.hir,
+            &mut assigner,
+        );
+
+        let (_, unit) = self.store.get_open_mut();
+        unit.assigner = assigner;
+        into_errors_with_source(errors, &unit.sources)
+    }
+
     /// Updates the current compilation with the AST and HIR packages,
     /// and any associated context, returned from a previous incremental compilation.
     /// Entry expressions are ignored.
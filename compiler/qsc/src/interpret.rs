@@ -16,6 +16,7 @@ use std::rc::Rc;
 
 pub use qsc_eval::{
     debug::Frame,
+    limits::{CancellationToken, Limits},
     output::{self, GenericReceiver},
     val::Closure,
     val::Range as ValueRange,
@@ -28,6 +29,7 @@ use qsc_partial_eval::ProgramEntry;
 use qsc_rca::PackageStoreComputeProperties;
 
 use crate::{
+    codegen::OptimizationLevel,
     error::{self, WithStack},
     incremental::Compiler,
     location::Location,
@@ -49,7 +51,7 @@ use qsc_data_structures::{
     target::TargetCapabilityFlags,
 };
 use qsc_eval::{
-    backend::{Backend, Chain as BackendChain, SparseSim},
+    backend::{Backend, Chain as BackendChain, MeasurementReplay, SparseSim},
     output::Receiver,
     val, Env, State, VariableInfo,
 };
@@ -64,6 +66,7 @@ use qsc_frontend::{
     incremental::Increment,
 };
 use qsc_passes::{PackageType, PassContext};
+use qsc_project::DependencyPackage;
 use rustc_hash::FxHashSet;
 use thiserror::Error;
 
@@ -104,6 +107,9 @@ pub enum Error {
     #[error("partial evaluation error")]
     #[diagnostic(transparent)]
     PartialEvaluation(#[from] WithSource<qsc_partial_eval::Error>),
+    #[error("evaluation stopped before returning a value")]
+    #[diagnostic(code("Qsc.Interpret.EvaluationIncomplete"))]
+    EvaluationIncomplete,
 }
 
 /// A Q# interpreter.
@@ -128,7 +134,7 @@ pub struct Interpreter {
     /// This ID is valid both for the FIR store and the `PackageStore`.
     source_package: PackageId,
     /// The default simulator backend.
-    sim: BackendChain<SparseSim, CircuitBuilder>,
+    sim: BackendChain<MeasurementReplay<SparseSim>, CircuitBuilder>,
     /// The quantum seed, if any. This is cached here so that it can be used in calls to
     /// `run_internal` which use a passed instance of the simulator instead of the one above.
     quantum_seed: Option<u64>,
@@ -137,6 +143,12 @@ pub struct Interpreter {
     classical_seed: Option<u64>,
     /// The evaluator environment.
     env: Env,
+    /// The token checked during evaluation, allowing a host to stop a running program.
+    cancellation_token: CancellationToken,
+    /// The limits applied to each evaluation.
+    limits: Limits,
+    /// The optimization level used when generating QIR for targets other than Base.
+    optimization_level: OptimizationLevel,
 }
 
 pub type InterpretResult = std::result::Result<Value, Vec<Error>>;
@@ -155,6 +167,7 @@ impl Interpreter {
         Self::new_internal(
             false,
             std,
+            &[],
             sources,
             package_type,
             capabilities,
@@ -162,11 +175,36 @@ impl Interpreter {
         )
     }
 
-    /// Creates a new incremental compiler with debugging stmts enabled, compiling the passed in sources.
+    /// Creates a new incremental compiler, compiling the packages the sources
+    /// depend on followed by the passed in sources.
     /// # Errors
-    /// If compiling the sources fails, compiler errors are returned.
+    /// If compiling the dependencies or the sources fails, compiler errors are returned.
+    pub fn with_dependencies(
+        std: bool,
+        dependencies: &[DependencyPackage],
+        sources: SourceMap,
+        package_type: PackageType,
+        capabilities: TargetCapabilityFlags,
+        language_features: LanguageFeatures,
+    ) -> std::result::Result<Self, Vec<Error>> {
+        Self::new_internal(
+            false,
+            std,
+            dependencies,
+            sources,
+            package_type,
+            capabilities,
+            language_features,
+        )
+    }
+
+    /// Creates a new incremental compiler with debugging stmts enabled, compiling the packages the
+    /// sources depend on followed by the passed in sources.
+    /// # Errors
+    /// If compiling the dependencies or the sources fails, compiler errors are returned.
     pub fn new_with_debug(
         std: bool,
+        dependencies: &[DependencyPackage],
         sources: SourceMap,
         package_type: PackageType,
         capabilities: TargetCapabilityFlags,
@@ -175,6 +213,7 @@ impl Interpreter {
         Self::new_internal(
             true,
             std,
+            dependencies,
             sources,
             package_type,
             capabilities,
@@ -185,13 +224,21 @@ impl Interpreter {
     fn new_internal(
         dbg: bool,
         std: bool,
+        dependencies: &[DependencyPackage],
         sources: SourceMap,
         package_type: PackageType,
         capabilities: TargetCapabilityFlags,
         language_features: LanguageFeatures,
     ) -> std::result::Result<Self, Vec<Error>> {
-        let compiler = Compiler::new(std, sources, package_type, capabilities, language_features)
-            .map_err(into_errors)?;
+        let compiler = Compiler::with_dependencies(
+            std,
+            dependencies,
+            sources,
+            package_type,
+            capabilities,
+            language_features,
+        )
+        .map_err(into_errors)?;
 
         let mut fir_store = fir::PackageStore::new();
         for (id, unit) in compiler.package_store() {
@@ -217,6 +264,9 @@ impl Interpreter {
             classical_seed: None,
             package: map_hir_package_to_fir(package_id),
             source_package: map_hir_package_to_fir(source_package_id),
+            cancellation_token: CancellationToken::default(),
+            limits: Limits::default(),
+            optimization_level: OptimizationLevel::Basic,
         })
     }
 
@@ -253,6 +303,9 @@ impl Interpreter {
             classical_seed: None,
             package: map_hir_package_to_fir(package_id),
             source_package: map_hir_package_to_fir(source_package_id),
+            cancellation_token: CancellationToken::default(),
+            limits: Limits::default(),
+            optimization_level: OptimizationLevel::Basic,
         })
     }
 
@@ -264,6 +317,44 @@ impl Interpreter {
     pub fn set_classical_seed(&mut self, seed: Option<u64>) {
         self.classical_seed = seed;
     }
+
+    /// Returns the token checked during evaluation. Cancelling it, for example from another
+    /// thread, stops the running evaluation with a runtime error. Once cancelled, evaluations
+    /// keep failing until the token is reset.
+    #[must_use]
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
+
+    /// Replaces the token checked during evaluation, allowing several interpreters to share one.
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation_token = token;
+    }
+
+    /// Sets the limits applied to each evaluation. Exceeding a limit stops the evaluation
+    /// with a runtime error.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// Sets the optimization level used when generating QIR for targets other than Base.
+    pub fn set_optimization_level(&mut self, optimization_level: OptimizationLevel) {
+        self.optimization_level = optimization_level;
+    }
+
+    /// Forces the given outcomes, in order, on the next measurements performed by the
+    /// simulator, and clears the recorded outcomes. Measurements beyond the end of the
+    /// replayed outcomes are simulated normally.
+    pub fn set_measurement_replay(&mut self, outcomes: Vec<bool>) {
+        self.sim.main.set_replay(outcomes);
+    }
+
+    /// Returns the outcomes of the measurements performed by the simulator since the
+    /// interpreter was created or the measurement replay was last set.
+    #[must_use]
+    pub fn recorded_measurements(&self) -> &[bool] {
+        self.sim.main.recorded()
+    }
     /// Executes the entry expression until the end of execution.
     /// # Errors
     /// Returns a vector of errors if evaluating the entry point fails.
@@ -281,6 +372,9 @@ impl Interpreter {
             &mut Env::default(),
             &mut self.sim,
             receiver,
+            &self.cancellation_token,
+            self.limits,
+            true,
         )
     }
 
@@ -304,6 +398,9 @@ impl Interpreter {
             &mut Env::default(),
             sim,
             receiver,
+            &self.cancellation_token,
+            self.limits,
+            true,
         )
     }
 
@@ -380,17 +477,25 @@ impl Interpreter {
             &mut self.env,
             &mut self.sim,
             receiver,
+            &self.cancellation_token,
+            self.limits,
+            false,
         )
     }
 
     /// Runs the given entry expression on a new instance of the environment and simulator,
-    /// but using the current compilation.
+    /// but using the current compilation. Measurement outcomes are still recorded and
+    /// replayed as for the interpreter's own simulator.
     pub fn run(
         &mut self,
         receiver: &mut impl Receiver,
         expr: &str,
     ) -> std::result::Result<InterpretResult, Vec<Error>> {
-        self.run_with_sim(&mut SparseSim::new(), receiver, expr)
+        let mut sim = MeasurementReplay::new(SparseSim::new());
+        sim.swap_outcomes(&mut self.sim.main);
+        let result = self.run_with_sim(&mut sim, receiver, expr);
+        sim.swap_outcomes(&mut self.sim.main);
+        result
     }
 
     /// Gets the current quantum state of the simulator.
@@ -444,6 +549,7 @@ impl Interpreter {
             fir_to_qir(
                 &self.fir_store,
                 self.capabilities,
+                self.optimization_level,
                 Some(compute_properties),
                 &entry,
             )
@@ -531,6 +637,9 @@ impl Interpreter {
             &mut Env::default(),
             sim,
             receiver,
+            &self.cancellation_token,
+            self.limits,
+            true,
         ))
     }
 
@@ -563,6 +672,9 @@ impl Interpreter {
             &mut Env::default(),
             sim,
             &mut out,
+            &self.cancellation_token,
+            self.limits,
+            false,
         )
     }
 
@@ -679,9 +791,9 @@ impl Interpreter {
     }
 }
 
-fn sim_circuit_backend() -> BackendChain<SparseSim, CircuitBuilder> {
+fn sim_circuit_backend() -> BackendChain<MeasurementReplay<SparseSim>, CircuitBuilder> {
     BackendChain::new(
-        SparseSim::new(),
+        MeasurementReplay::new(SparseSim::new()),
         CircuitBuilder::new(CircuitConfig {
             // When using in conjunction with the simulator,
             // the circuit builder should *not* perform base profile
@@ -720,6 +832,7 @@ pub struct Debugger {
 
 impl Debugger {
     pub fn new(
+        dependencies: &[DependencyPackage],
         sources: SourceMap,
         capabilities: TargetCapabilityFlags,
         position_encoding: Encoding,
@@ -727,6 +840,7 @@ impl Debugger {
     ) -> std::result::Result<Self, Vec<Error>> {
         let interpreter = Interpreter::new_with_debug(
             true,
+            dependencies,
             sources,
             PackageType::Exe,
             capabilities,
@@ -735,10 +849,14 @@ impl Debugger {
         let source_package_id = interpreter.source_package;
         let unit = interpreter.fir_store.get(source_package_id);
         let entry_exec_graph = unit.entry_exec_graph.clone();
+        let state = State::new(source_package_id, entry_exec_graph, None)
+            .with_cancellation_token(interpreter.cancellation_token())
+            .with_limits(interpreter.limits)
+            .with_output_recording();
         Ok(Self {
             interpreter,
             position_encoding,
-            state: State::new(source_package_id, entry_exec_graph, None),
+            state,
         })
     }
 
@@ -807,6 +925,17 @@ impl Debugger {
         self.interpreter.sim.capture_quantum_state()
     }
 
+    /// Forces the given outcomes, in order, on the next measurements performed while debugging.
+    pub fn set_measurement_replay(&mut self, outcomes: Vec<bool>) {
+        self.interpreter.set_measurement_replay(outcomes);
+    }
+
+    /// Returns the outcomes of the measurements performed while debugging.
+    #[must_use]
+    pub fn recorded_measurements(&self) -> &[bool] {
+        self.interpreter.recorded_measurements()
+    }
+
     pub fn circuit(&self) -> Circuit {
         self.interpreter.get_circuit()
     }
@@ -867,17 +996,22 @@ fn eval(
     env: &mut Env,
     sim: &mut impl Backend<ResultType = impl Into<val::Result>>,
     receiver: &mut impl Receiver,
+    cancellation_token: &CancellationToken,
+    limits: Limits,
+    record_output: bool,
 ) -> InterpretResult {
-    qsc_eval::eval(
-        package,
-        classical_seed,
-        exec_graph,
-        fir_store,
-        env,
-        sim,
-        receiver,
-    )
-    .map_err(|(error, call_stack)| eval_error(package_store, fir_store, call_stack, error))
+    let mut state = State::new(package, exec_graph, classical_seed)
+        .with_cancellation_token(cancellation_token.clone())
+        .with_limits(limits);
+    if record_output {
+        state = state.with_output_recording();
+    }
+    match state.eval(fir_store, env, sim, receiver, &[], StepAction::Continue) {
+        Ok(StepResult::Return(value)) => Ok(value),
+        // Evaluation only pauses at breakpoints or between steps, neither of which apply here.
+        Ok(_) => Err(vec![Error::EvaluationIncomplete]),
+        Err((error, call_stack)) => Err(eval_error(package_store, fir_store, call_stack, error)),
+    }
 }
 
 /// Represents a stack frame for debugging.
@@ -780,6 +780,7 @@ mod debugger_stepping {
     fn generate_circuit_steps(code: &str, profile: Profile) -> String {
         let sources = SourceMap::new([("test.qs".into(), code.into())], None);
         let mut debugger = Debugger::new(
+            &[],
             sources,
             profile.into(),
             Encoding::Utf8,
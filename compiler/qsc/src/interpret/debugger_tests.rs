@@ -132,6 +132,7 @@ mod given_debugger {
             use qsc_data_structures::language_features::LanguageFeatures;
             let sources = SourceMap::new([("test".into(), STEPPING_SOURCE.into())], None);
             let mut debugger = Debugger::new(
+                &[],
                 sources,
                 TargetCapabilityFlags::all(),
                 Encoding::Utf8,
@@ -155,6 +156,7 @@ mod given_debugger {
         fn next_crosses_operation_works() -> Result<(), Vec<crate::interpret::Error>> {
             let sources = SourceMap::new([("test".into(), STEPPING_SOURCE.into())], None);
             let mut debugger = Debugger::new(
+                &[],
                 sources,
                 TargetCapabilityFlags::all(),
                 Encoding::Utf8,
@@ -174,6 +176,7 @@ mod given_debugger {
         fn in_multiple_operations_works() -> Result<(), Vec<crate::interpret::Error>> {
             let sources = SourceMap::new([("test".into(), STEPPING_SOURCE.into())], None);
             let mut debugger = Debugger::new(
+                &[],
                 sources,
                 TargetCapabilityFlags::all(),
                 Encoding::Utf8,
@@ -200,6 +203,7 @@ mod given_debugger {
         fn out_multiple_operations_works() -> Result<(), Vec<crate::interpret::Error>> {
             let sources = SourceMap::new([("test".into(), STEPPING_SOURCE.into())], None);
             let mut debugger = Debugger::new(
+                &[],
                 sources,
                 TargetCapabilityFlags::all(),
                 Encoding::Utf8,
@@ -220,4 +224,71 @@ mod given_debugger {
             Ok(())
         }
     }
+
+    #[test]
+    fn loop_jumps_leave_loop_body_scopes() -> Result<(), Vec<crate::interpret::Error>> {
+        use qsc_data_structures::target::TargetCapabilityFlags;
+        let source = r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                mutable total = 0;
+                for i in 0..5 {
+                    let doubled = i * 2;
+                    if i == 1 {
+                        let skipped = doubled;
+                        continue;
+                    }
+                    if i == 3 {
+                        let stopped = doubled;
+                        break;
+                    }
+                    set total += doubled;
+                }
+                let after = total;
+                after
+            }
+        }"#;
+        let sources = SourceMap::new([("test".into(), source.into())], None);
+        let mut debugger = Debugger::new(
+            &[],
+            sources,
+            TargetCapabilityFlags::all(),
+            Encoding::Utf8,
+            LanguageFeatures::default(),
+        )?;
+        let after_line = u32::try_from(
+            source
+                .lines()
+                .position(|line| line.contains("let after"))
+                .expect("source should contain the statement after the loop"),
+        )
+        .expect("line should fit into u32");
+        let bp = debugger
+            .get_breakpoints("test")
+            .into_iter()
+            .find(|bp| bp.range.start.line == after_line)
+            .expect("statement after the loop should have a breakpoint");
+        let bp_id: StmtId = bp.id.into();
+        match step(&mut debugger, &[bp_id], StepAction::Continue).0 {
+            Ok(StepResult::BreakpointHit(actual_id)) => assert!(actual_id == bp_id),
+            Ok(v) => panic!("Expected BP, got {v:?}"),
+            Err(e) => panic!("Expected BP, got {e:?}"),
+        }
+        let locals = debugger
+            .get_locals()
+            .into_iter()
+            .map(|local| local.name.to_string())
+            .collect::<Vec<_>>();
+        assert!(locals.contains(&"total".to_string()), "{locals:?}");
+        for name in ["i", "doubled", "skipped", "stopped"] {
+            assert!(!locals.contains(&name.to_string()), "{locals:?}");
+        }
+        match step(&mut debugger, &[], StepAction::Continue).0 {
+            Ok(StepResult::Return(value)) => assert_eq!(value.to_string(), "4"),
+            Ok(v) => panic!("Expected Return, got {v:?}"),
+            Err(e) => panic!("Expected Return, got {e:?}"),
+        }
+        Ok(())
+    }
 }
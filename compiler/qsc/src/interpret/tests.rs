@@ -4,14 +4,21 @@
 #![allow(clippy::needless_raw_string_hashes)]
 
 mod given_interpreter {
-    use crate::interpret::{Error, InterpretResult, Interpreter};
+    use crate::codegen::OptimizationLevel;
+    use crate::interpret::{Error, InterpretResult, Interpreter, Limits};
     use expect_test::Expect;
     use miette::Diagnostic;
+    use num_bigint::BigUint;
+    use num_complex::Complex64;
+    use qsc_data_structures::functors::FunctorApp;
     use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
-    use qsc_eval::{output::CursorReceiver, val::Value};
+    use qsc_eval::{
+        output::{self, CursorReceiver, OutputRecord, Receiver},
+        val::{self, Value},
+    };
     use qsc_frontend::compile::SourceMap;
     use qsc_passes::PackageType;
-    use std::{fmt::Write, io::Cursor, iter, str::from_utf8};
+    use std::{fmt::Write, io::Cursor, iter, str::from_utf8, thread, time::Duration};
 
     fn line(interpreter: &mut Interpreter, line: &str) -> (InterpretResult, String) {
         let mut cursor = Cursor::new(Vec::<u8>::new());
@@ -31,6 +38,76 @@ mod given_interpreter {
         (interpreter.run(&mut receiver, expr), receiver.dump())
     }
 
+    /// Collects every output event as a line of text, including the structured events
+    /// that the text-based receivers ignore.
+    #[derive(Default)]
+    struct EventReceiver {
+        events: Vec<String>,
+    }
+
+    impl Receiver for EventReceiver {
+        fn state(
+            &mut self,
+            state: Vec<(BigUint, Complex64)>,
+            qubit_count: usize,
+        ) -> Result<(), output::Error> {
+            self.events.push(format!(
+                "state: {} entries, {qubit_count} qubits",
+                state.len()
+            ));
+            Ok(())
+        }
+
+        fn message(&mut self, msg: &str) -> Result<(), output::Error> {
+            self.events.push(format!("message: {msg}"));
+            Ok(())
+        }
+
+        fn measurement(&mut self, qubit: usize, result: val::Result) -> Result<(), output::Error> {
+            self.events
+                .push(format!("measurement: q{qubit} {result:?}"));
+            Ok(())
+        }
+
+        fn operation_start(
+            &mut self,
+            name: &str,
+            functor: FunctorApp,
+        ) -> Result<(), output::Error> {
+            self.events
+                .push(format!("start: {}", call_name(name, functor)));
+            Ok(())
+        }
+
+        fn operation_end(&mut self, name: &str, functor: FunctorApp) -> Result<(), output::Error> {
+            self.events
+                .push(format!("end: {}", call_name(name, functor)));
+            Ok(())
+        }
+
+        fn output_record(&mut self, record: OutputRecord) -> Result<(), output::Error> {
+            self.events.push(record.to_string());
+            Ok(())
+        }
+    }
+
+    fn call_name(name: &str, functor: FunctorApp) -> String {
+        if functor == FunctorApp::default() {
+            name.to_string()
+        } else {
+            format!("{functor} {name}")
+        }
+    }
+
+    fn run_with_events(interpreter: &mut Interpreter, expr: &str) -> String {
+        let mut receiver = EventReceiver::default();
+        interpreter
+            .run(&mut receiver, expr)
+            .expect("compilation should succeed")
+            .expect("execution should succeed");
+        receiver.events.join("\n")
+    }
+
     fn entry(
         interpreter: &mut Interpreter,
     ) -> (Result<Value, Vec<crate::interpret::Error>>, String) {
@@ -516,6 +593,207 @@ mod given_interpreter {
             is_only_value(&result, &output, &Value::unit());
         }
 
+        #[test]
+        fn const_items_are_evaluated() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {r#"
+                    const Size : Int = 2 ^ 3;
+                    function Zeros() : Int[] { [0, size = Size] }
+                "#},
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let (result, output) = line(&mut interpreter, "Length(Zeros()) + Size");
+            is_only_value(&result, &output, &Value::Int(16));
+        }
+
+        #[test]
+        fn const_eval_failure_is_compile_error() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(&mut interpreter, "const Bad : Int = 1 / 0;");
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                constant evaluation failed: division by zero
+                   [line_0] [0]
+            "#]],
+            );
+            let (result, output) = line(&mut interpreter, "Bad");
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                constant has no value
+                   [line_1] [Bad]
+            "#]],
+            );
+        }
+
+        #[test]
+        fn omitted_args_are_given_default_values() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {r#"
+                    function Scale(x : Int, factor : Int = 2 ^ 2, offset : Int = 1) : Int {
+                        x * factor + offset
+                    }
+                "#},
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let (result, output) =
+                line(&mut interpreter, "[Scale(1), Scale(1, 2), Scale(1, 2, 0)]");
+            is_only_value(
+                &result,
+                &output,
+                &Value::Array(vec![Value::Int(5), Value::Int(3), Value::Int(2)].into()),
+            );
+        }
+
+        #[test]
+        fn omitted_args_are_given_default_values_in_generated_specializations() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {r#"
+                    operation Rotate(q : Qubit, theta : Double = 3.141592653589793) : Unit is Adj + Ctl {
+                        Rx(theta, q);
+                    }
+                    operation Flip(q : Qubit) : Unit is Adj + Ctl {
+                        Rotate(q);
+                    }
+                "#},
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {r#"
+                    {
+                        use (ctl, q) = (Qubit(), Qubit());
+                        X(ctl);
+                        Controlled Flip([ctl], q);
+                        Adjoint Flip(q);
+                        Flip(q);
+                        let results = [MResetZ(ctl), MResetZ(q)];
+                        results
+                    }
+                "#},
+            );
+            is_only_value(
+                &result,
+                &output,
+                &Value::Array(vec![Value::RESULT_ONE, Value::RESULT_ONE].into()),
+            );
+        }
+
+        #[test]
+        fn default_eval_failure_is_runtime_error() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(
+                &mut interpreter,
+                "function Bad(x : Int = 1 / 0) : Int { x }",
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let (result, output) = line(&mut interpreter, "Bad(2)");
+            is_only_value(&result, &output, &Value::Int(2));
+            let (result, output) = line(&mut interpreter, "Bad()");
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                runtime error: division by zero
+                  cannot divide by zero [line_0] [0]
+            "#]],
+            );
+        }
+
+        #[test]
+        fn default_values_use_earlier_params() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {r#"
+                    function Args(start : Int, count : Int = 3, stop : Int = start + count) : Int[] {
+                        [start, count, stop]
+                    }
+                "#},
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let (result, output) = line(&mut interpreter, "Args(1) + Args(1, 2)");
+            is_only_value(
+                &result,
+                &output,
+                &Value::Array(
+                    vec![
+                        Value::Int(1),
+                        Value::Int(3),
+                        Value::Int(4),
+                        Value::Int(1),
+                        Value::Int(2),
+                        Value::Int(3),
+                    ]
+                    .into(),
+                ),
+            );
+        }
+
+        #[test]
+        fn qubit_and_callable_default_values() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {r#"
+                    operation Apply(control : Qubit, target : Qubit = control, op : Qubit => Unit = X) : Unit {
+                        op(target);
+                    }
+                "#},
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {r#"
+                    {
+                        use (a, b) = (Qubit(), Qubit());
+                        Apply(a);
+                        Apply(a, b, H);
+                        Apply(b, b);
+                        let results = [MResetZ(a), Microsoft.Quantum.Measurement.MResetX(b)];
+                        results
+                    }
+                "#},
+            );
+            is_only_value(
+                &result,
+                &output,
+                &Value::Array(vec![Value::RESULT_ONE, Value::RESULT_ZERO].into()),
+            );
+        }
+
+        #[test]
+        fn partial_application_omits_defaulted_args() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {r#"
+                    function Scale(x : Int, factor : Int, offset : Int = factor) : Int {
+                        x * factor + offset
+                    }
+                "#},
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let (result, output) = line(
+                &mut interpreter,
+                "Microsoft.Quantum.Arrays.Mapped(Scale(_, 2), [1, 2])",
+            );
+            is_only_value(
+                &result,
+                &output,
+                &Value::Array(vec![Value::Int(4), Value::Int(6)].into()),
+            );
+        }
+
         #[test]
         fn callables_failing_profile_validation_are_still_registered() {
             fn verify_same_error<E>(result: &Result<Value, Vec<E>>, output: &str)
@@ -796,6 +1074,80 @@ mod given_interpreter {
             .assert_eq(&res);
         }
 
+        #[test]
+        fn adaptive_qirgen_with_full_optimization_merges_rotations() {
+            let mut interpreter = Interpreter::new(
+                true,
+                SourceMap::default(),
+                PackageType::Lib,
+                TargetCapabilityFlags::Adaptive
+                    | TargetCapabilityFlags::QubitReset
+                    | TargetCapabilityFlags::IntegerComputations,
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created");
+            interpreter.set_optimization_level(OptimizationLevel::Full);
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {r#"
+                namespace Test {
+                    open Microsoft.Quantum.Math;
+                    open QIR.Intrinsic;
+                    @EntryPoint()
+                    operation Main() : Result {
+                        use q = Qubit();
+                        let pi_over_2 = 4.0 / 2.0;
+                        __quantum__qis__rz__body(pi_over_2, q);
+                        mutable some_angle = ArcSin(0.0);
+                        __quantum__qis__rz__body(some_angle, q);
+                        set some_angle = ArcCos(-1.0) / PI();
+                        __quantum__qis__rz__body(some_angle, q);
+                        __quantum__qis__mresetz__body(q)
+                    }
+                }"#
+                },
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let res = interpreter.qirgen("Test.Main()").expect("expected success");
+            expect![[r#"
+                %Result = type opaque
+                %Qubit = type opaque
+
+                define void @ENTRYPOINT__main() #0 {
+                block_0:
+                  call void @__quantum__qis__rz__body(double 3.0, %Qubit* inttoptr (i64 0 to %Qubit*))
+                  call void @__quantum__qis__mresetz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* inttoptr (i64 0 to %Result*))
+                  call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 0 to %Result*), i8* null)
+                  ret void
+                }
+
+                declare void @__quantum__qis__rz__body(double, %Qubit*)
+
+                declare void @__quantum__qis__mresetz__body(%Qubit*, %Result*) #1
+
+                declare void @__quantum__rt__result_record_output(%Result*, i8*)
+
+                attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="1" }
+                attributes #1 = { "irreversible" }
+
+                ; module flags
+
+                !llvm.module.flags = !{!0, !1, !2, !3, !4, !5, !6, !7, !8, !9, !10}
+
+                !0 = !{i32 1, !"qir_major_version", i32 1}
+                !1 = !{i32 7, !"qir_minor_version", i32 0}
+                !2 = !{i32 1, !"dynamic_qubit_management", i1 false}
+                !3 = !{i32 1, !"dynamic_result_management", i1 false}
+                !4 = !{i32 1, !"classical_ints", i1 true}
+                !5 = !{i32 1, !"qubit_resetting", i1 true}
+                !6 = !{i32 1, !"classical_floats", i1 false}
+                !7 = !{i32 1, !"backwards_branching", i1 false}
+                !8 = !{i32 1, !"classical_fixed_points", i1 false}
+                !9 = !{i32 1, !"user_functions", i1 false}
+                !10 = !{i32 1, !"multiple_target_branching", i1 false}
+            "#]].assert_eq(&res);
+        }
+
         #[test]
         fn adaptive_qirgen_nested_output_types() {
             let mut interpreter = Interpreter::new(
@@ -1337,30 +1689,470 @@ mod given_interpreter {
         }
 
         #[test]
-        fn base_prof_non_result_return() {
-            let mut interpreter = Interpreter::new(
-                true,
-                SourceMap::default(),
-                PackageType::Lib,
-                TargetCapabilityFlags::empty(),
-                LanguageFeatures::default(),
-            )
-            .expect("interpreter should be created");
-            let (result, output) = line(&mut interpreter, "123");
-            is_only_value(&result, &output, &Value::Int(123));
+        fn run_stops_at_step_limit() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                max_steps: Some(1000),
+                ..Limits::default()
+            });
+            let (result, output) = run(
+                &mut interpreter,
+                "{ mutable x = 0; while true { set x += 1; } x }",
+            );
+            is_only_error(
+                &result.expect("compilation should succeed"),
+                &output,
+                &expect![[r#"
+                    runtime error: evaluation exceeded the limit of 1000 steps
+                      step limit reached here [<entry>] [set x += 1]
+                "#]],
+            );
         }
-    }
 
-    fn get_interpreter() -> Interpreter {
-        Interpreter::new(
-            true,
-            SourceMap::default(),
-            PackageType::Lib,
-            TargetCapabilityFlags::all(),
-            LanguageFeatures::default(),
-        )
-        .expect("interpreter should be created")
-    }
+        #[test]
+        fn run_stops_at_time_limit() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                time_limit: Some(Duration::from_millis(10)),
+                ..Limits::default()
+            });
+            let (result, output) = run(
+                &mut interpreter,
+                "{ mutable x = 0; while true { set x += 1; } x }",
+            );
+            // The span where evaluation stops depends on timing, so only the error code is checked.
+            is_only_error_code(
+                &result.expect("compilation should succeed"),
+                &output,
+                "Qsc.Eval.TimeLimitExceeded",
+            );
+        }
+
+        #[test]
+        fn run_can_be_cancelled_from_another_thread() {
+            let mut interpreter = get_interpreter();
+            let token = interpreter.cancellation_token();
+            let canceller = thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                token.cancel();
+            });
+            let (result, output) = run(
+                &mut interpreter,
+                "{ mutable x = 0; while true { set x += 1; } x }",
+            );
+            canceller.join().expect("cancelling thread should succeed");
+            is_only_error_code(
+                &result.expect("compilation should succeed"),
+                &output,
+                "Qsc.Eval.Cancelled",
+            );
+        }
+
+        #[test]
+        fn cancelled_token_can_be_reset() {
+            let mut interpreter = get_interpreter();
+            interpreter.cancellation_token().cancel();
+            let (result, output) = line(&mut interpreter, "1 + 1");
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                    runtime error: evaluation was cancelled
+                      cancelled while evaluating this [line_0] [1]
+                "#]],
+            );
+            interpreter.cancellation_token().reset();
+            let (result, output) = line(&mut interpreter, "1 + 1");
+            is_only_value(&result, &output, &Value::Int(2));
+        }
+
+        #[test]
+        fn qubit_limit_exceeded() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                max_qubits: Some(2),
+                ..Limits::default()
+            });
+            let (result, output) = line(
+                &mut interpreter,
+                "use (q0, q1, q2) = (Qubit(), Qubit(), Qubit());",
+            );
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                runtime error: evaluation exceeded the limit of 2 qubits
+                  qubit limit reached here [line_0] [Qubit()]
+            "#]],
+            );
+        }
+
+        #[test]
+        fn qubits_allocated_by_earlier_lines_count_towards_qubit_limit() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                max_qubits: Some(2),
+                ..Limits::default()
+            });
+            let (result, output) = line(&mut interpreter, "use (q0, q1) = (Qubit(), Qubit());");
+            is_only_value(&result, &output, &Value::unit());
+            let (result, output) = line(&mut interpreter, "use q2 = Qubit();");
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                runtime error: evaluation exceeded the limit of 2 qubits
+                  qubit limit reached here [line_1] [Qubit()]
+            "#]],
+            );
+        }
+
+        #[test]
+        fn released_qubits_do_not_count_towards_qubit_limit() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                max_qubits: Some(2),
+                ..Limits::default()
+            });
+            let (result, output) =
+                line(&mut interpreter, "for _ in 1..10 { use qs = Qubit[2]; } 1");
+            is_only_value(&result, &output, &Value::Int(1));
+        }
+
+        #[test]
+        fn qubits_leaked_by_failed_lines_do_not_count_towards_qubit_limit() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                max_qubits: Some(2),
+                ..Limits::default()
+            });
+            let (result, _) = line(&mut interpreter, "{ use qs = Qubit[2]; fail \"boom\"; }");
+            assert!(result.is_err(), "line should fail");
+            let (result, output) = line(&mut interpreter, "{ use qs = Qubit[2]; 1 }");
+            is_only_value(&result, &output, &Value::Int(1));
+        }
+
+        #[test]
+        fn array_length_limit_exceeded_by_repeat() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                max_array_len: Some(100),
+                ..Limits::default()
+            });
+            let (result, output) = line(&mut interpreter, "[0, size = 1000000000]");
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                runtime error: array length exceeds the limit of 100 items
+                  this array has too many items [line_0] [[0, size = 1000000000]]
+            "#]],
+            );
+        }
+
+        #[test]
+        fn array_length_limit_exceeded_by_append() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                max_array_len: Some(100),
+                ..Limits::default()
+            });
+            let (result, output) = line(
+                &mut interpreter,
+                "{ mutable arr = []; for i in 0..1000 { set arr += [i]; } arr }",
+            );
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                runtime error: array length exceeds the limit of 100 items
+                  this array has too many items [line_0] [arr]
+            "#]],
+            );
+        }
+
+        #[test]
+        fn array_length_limit_exceeded_by_concatenation() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                max_array_len: Some(100),
+                ..Limits::default()
+            });
+            let (result, output) = line(&mut interpreter, "[0, size = 60] + [1, size = 60]");
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                runtime error: array length exceeds the limit of 100 items
+                  this array has too many items [line_0] [[1, size = 60]]
+            "#]],
+            );
+        }
+
+        #[test]
+        fn call_depth_limit_exceeded() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_limits(Limits {
+                max_call_depth: Some(100),
+                ..Limits::default()
+            });
+            let (result, output) = line(
+                &mut interpreter,
+                "function Recurse(n : Int) : Int { Recurse(n + 1) }",
+            );
+            is_only_value(&result, &output, &Value::unit());
+            let (result, output) = run(&mut interpreter, "Recurse(0)");
+            is_only_error(
+                &result.expect("compilation should succeed"),
+                &output,
+                &expect![[r#"
+                    runtime error: call depth exceeds the limit of 100 frames
+                      call depth limit reached here [line_0] [Recurse]
+                "#]],
+            );
+        }
+
+        #[test]
+        fn measurements_are_recorded() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(
+                &mut interpreter,
+                "{ use qs = Qubit[2]; X(qs[1]); let rs = MeasureEachZ(qs); ResetAll(qs); rs }",
+            );
+            is_only_value(
+                &result,
+                &output,
+                &Value::Array(vec![Value::RESULT_ZERO, Value::RESULT_ONE].into()),
+            );
+            assert_eq!(interpreter.recorded_measurements(), [false, true]);
+        }
+
+        #[test]
+        fn replayed_measurements_are_forced() {
+            let mut interpreter = get_interpreter();
+            let program = "{ use qs = Qubit[8]; ApplyToEach(H, qs); MResetEachZ(qs) }";
+            let (first, _) = line(&mut interpreter, program);
+            let recorded = interpreter.recorded_measurements().to_vec();
+            interpreter.set_measurement_replay(recorded.clone());
+            let (second, output) = line(&mut interpreter, program);
+            is_only_value(&second, &output, &first.expect("program should succeed"));
+            assert_eq!(interpreter.recorded_measurements(), recorded);
+        }
+
+        #[test]
+        fn replayed_measurement_collapses_state() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_measurement_replay(vec![true]);
+            let (result, output) = line(
+                &mut interpreter,
+                "{ use (q0, q1) = (Qubit(), Qubit()); H(q0); CNOT(q0, q1); let r = M(q0); let r1 = M(q1); ResetAll([q0, q1]); (r, r1) }",
+            );
+            is_only_value(
+                &result,
+                &output,
+                &Value::Tuple(vec![Value::RESULT_ONE, Value::RESULT_ONE].into()),
+            );
+        }
+
+        #[test]
+        fn replayed_unlikely_measurement_is_forced() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_measurement_replay(vec![true]);
+            let (result, output) = line(
+                &mut interpreter,
+                "{ use q = Qubit(); Ry(0.01, q); let r0 = M(q); let r1 = M(q); Reset(q); (r0, r1) }",
+            );
+            is_only_value(
+                &result,
+                &output,
+                &Value::Tuple(vec![Value::RESULT_ONE, Value::RESULT_ONE].into()),
+            );
+        }
+
+        #[test]
+        fn replayed_measurement_is_forced_after_lower_qubit_is_released() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_measurement_replay(vec![true]);
+            let (result, output) = line(
+                &mut interpreter,
+                "{ let low = QIR.Runtime.__quantum__rt__qubit_allocate(); use q = Qubit(); QIR.Runtime.__quantum__rt__qubit_release(low); H(q); let r0 = M(q); let r1 = M(q); Reset(q); (r0, r1) }",
+            );
+            is_only_value(
+                &result,
+                &output,
+                &Value::Tuple(vec![Value::RESULT_ONE, Value::RESULT_ONE].into()),
+            );
+        }
+
+        #[test]
+        fn replayed_measurement_keeps_amplitudes_and_phases_of_remaining_qubits() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_measurement_replay(vec![true]);
+            let (result, output) = line(
+                &mut interpreter,
+                "use qs = Qubit[3]; H(qs[0]); CNOT(qs[0], qs[1]); Ry(0.7, qs[2]); CNOT(qs[1], qs[2]); H(qs[1]); T(qs[2]); M(qs[0])",
+            );
+            is_only_value(&result, &output, &Value::RESULT_ONE);
+
+            // Natural measurement of `One` would leave (|0⟩ - |1⟩) ⊗ (sin(0.35)|0⟩ + e^(iπ/4) cos(0.35)|1⟩) / √2
+            // on the remaining qubits.
+            let (sin, cos) = (0.35_f64.sin(), 0.35_f64.cos());
+            let t = Complex64::from_polar(1.0, std::f64::consts::FRAC_PI_4);
+            let expected = [
+                (4_u32, Complex64::new(sin, 0.0)),
+                (5, t * cos),
+                (6, Complex64::new(-sin, 0.0)),
+                (7, -t * cos),
+            ];
+            let (state, count) = interpreter.get_quantum_state();
+            assert_eq!(count, 3);
+            assert_eq!(state.len(), expected.len(), "unexpected state {state:?}");
+            for ((index, amplitude), (expected_index, expected_amplitude)) in
+                state.iter().zip(expected)
+            {
+                assert_eq!(*index, BigUint::from(expected_index));
+                assert!(
+                    (amplitude - expected_amplitude / 2.0_f64.sqrt()).norm() < 1e-9,
+                    "unexpected state {state:?}"
+                );
+            }
+        }
+
+        #[test]
+        fn replaying_impossible_measurement_fails() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_measurement_replay(vec![true]);
+            let (result, output) = line(&mut interpreter, "{ use q = Qubit(); M(q) }");
+            is_only_error(
+                &result,
+                &output,
+                &expect![[r#"
+                    runtime error: intrinsic callable `__quantum__qis__m__body` failed: cannot replay measurement 1: outcome One on qubit 0 has zero probability
+                       [qir.qs] [operation __quantum__qis__m__body(target : Qubit) : Result {
+                            body intrinsic;
+                        }]
+                "#]],
+            );
+        }
+
+        #[test]
+        fn run_replays_measurements() {
+            let mut interpreter = get_interpreter();
+            interpreter.set_measurement_replay(vec![true, false]);
+            let (result, output) = run(
+                &mut interpreter,
+                "{ use q = Qubit(); H(q); let r0 = MResetZ(q); H(q); let r1 = MResetZ(q); (r0, r1) }",
+            );
+            is_only_value(
+                &result.expect("compilation should succeed"),
+                &output,
+                &Value::Tuple(vec![Value::RESULT_ONE, Value::RESULT_ZERO].into()),
+            );
+            assert_eq!(interpreter.recorded_measurements(), [true, false]);
+        }
+
+        #[test]
+        fn run_reports_operation_calls_and_measurements() {
+            let mut interpreter = get_interpreter();
+            let (result, output) = line(
+                &mut interpreter,
+                indoc! {"
+                    operation Flip(q : Qubit) : Unit is Adj { X(q); }
+                    operation Foo() : Result {
+                        use q = Qubit();
+                        Adjoint Flip(q);
+                        Message(\"flipped\");
+                        MResetZ(q)
+                    }
+                "},
+            );
+            is_only_value(&result, &output, &Value::unit());
+            expect![[r#"
+                start: Foo
+                start: __quantum__rt__qubit_allocate
+                end: __quantum__rt__qubit_allocate
+                start: Adjoint Flip
+                start: Adjoint X
+                start: __quantum__qis__x__body
+                end: __quantum__qis__x__body
+                end: Adjoint X
+                end: Adjoint Flip
+                message: flipped
+                start: MResetZ
+                start: __quantum__qis__mresetz__body
+                measurement: q0 Val(true)
+                end: __quantum__qis__mresetz__body
+                end: MResetZ
+                start: __quantum__rt__qubit_release
+                end: __quantum__rt__qubit_release
+                end: Foo
+                OUTPUT	RESULT	1"#]]
+            .assert_eq(&run_with_events(&mut interpreter, "Foo()"));
+        }
+
+        #[test]
+        fn run_reports_return_value_as_output_records() {
+            let mut interpreter = get_interpreter();
+            expect![[r#"
+                OUTPUT	TUPLE	4
+                OUTPUT	INT	1
+                OUTPUT	ARRAY	2
+                OUTPUT	BOOL	true
+                OUTPUT	BOOL	false
+                OUTPUT	DOUBLE	2.5
+                OUTPUT	ARRAY	2
+                OUTPUT	RESULT	0
+                OUTPUT	RESULT	1"#]]
+            .assert_eq(&run_with_events(
+                &mut interpreter,
+                "(1, [true, false], 2.5, [Zero, One])",
+            ));
+        }
+
+        #[test]
+        fn run_skips_output_records_for_unsupported_return_value() {
+            let mut interpreter = get_interpreter();
+            expect![[r#""#]].assert_eq(&run_with_events(&mut interpreter, "(1, \"two\")"));
+        }
+
+        #[test]
+        fn fragments_do_not_report_output_records() {
+            let mut interpreter = get_interpreter();
+            let mut receiver = EventReceiver::default();
+            let result = interpreter.eval_fragments(&mut receiver, "(1, 2)");
+            is_only_value(
+                &result,
+                "",
+                &Value::Tuple(vec![Value::Int(1), Value::Int(2)].into()),
+            );
+            assert!(receiver.events.is_empty());
+        }
+
+        #[test]
+        fn base_prof_non_result_return() {
+            let mut interpreter = Interpreter::new(
+                true,
+                SourceMap::default(),
+                PackageType::Lib,
+                TargetCapabilityFlags::empty(),
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created");
+            let (result, output) = line(&mut interpreter, "123");
+            is_only_value(&result, &output, &Value::Int(123));
+        }
+    }
+
+    fn get_interpreter() -> Interpreter {
+        Interpreter::new(
+            true,
+            SourceMap::default(),
+            PackageType::Lib,
+            TargetCapabilityFlags::all(),
+            LanguageFeatures::default(),
+        )
+        .expect("interpreter should be created")
+    }
 
     fn get_interpreter_with_capbilities(capabilities: TargetCapabilityFlags) -> Interpreter {
         Interpreter::new(
@@ -1416,6 +2208,19 @@ mod given_interpreter {
         }
     }
 
+    fn is_only_error_code(result: &InterpretResult, output: &str, expected_code: &str) {
+        assert_eq!("", output);
+
+        match result {
+            Ok(value) => panic!("Expected error, got {value:?}"),
+            Err(errors) => {
+                assert_eq!(1, errors.len(), "Expected a single error, got {errors:?}");
+                let code = errors[0].code().map(|code| code.to_string());
+                assert_eq!(Some(expected_code), code.as_deref());
+            }
+        }
+    }
+
     fn is_error<E>(errors: &Vec<E>, expected_errors: &Expect)
     where
         E: Diagnostic,
@@ -1455,6 +2260,7 @@ mod given_interpreter {
         use super::*;
         use crate::interpret::Debugger;
         use crate::line_column::Encoding;
+        use crate::project::DependencyPackage;
         use expect_test::expect;
         use indoc::indoc;
 
@@ -1510,6 +2316,198 @@ mod given_interpreter {
             is_unit_with_output(&result, &output, "hello there...");
         }
 
+        fn library_dependency() -> DependencyPackage {
+            DependencyPackage {
+                name: "Library".into(),
+                sources: vec![(
+                    "library.qs".into(),
+                    indoc! {"
+                    namespace Library {
+                        function Four() : Int {
+                            Two() * 2
+                        }
+                        internal function Two() : Int {
+                            2
+                        }
+                        function Scaled(x : Int, factor : Int = Two()) : Int {
+                            x * factor
+                        }
+                    }"}
+                    .into(),
+                )],
+                direct: true,
+                ..DependencyPackage::default()
+            }
+        }
+
+        #[test]
+        fn dependency_members_can_be_accessed_from_sources_and_fragments() {
+            let source = indoc! { r#"
+            namespace Test {
+                function Eight() : Int {
+                    Library.Four() * 2
+                }
+            }"#};
+
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            let mut interpreter = Interpreter::with_dependencies(
+                true,
+                &[library_dependency()],
+                sources,
+                PackageType::Lib,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created");
+
+            let (result, output) = line(&mut interpreter, "Test.Eight() + Library.Four()");
+            is_only_value(&result, &output, &Value::Int(12));
+        }
+
+        #[test]
+        fn dependency_callables_omit_defaulted_args() {
+            let sources = SourceMap::new([("test".into(), "".into())], None);
+            let mut interpreter = Interpreter::with_dependencies(
+                true,
+                &[library_dependency()],
+                sources,
+                PackageType::Lib,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created");
+
+            let (result, output) =
+                line(&mut interpreter, "Library.Scaled(3) + Library.Scaled(3, 3)");
+            is_only_value(&result, &output, &Value::Int(15));
+        }
+
+        #[test]
+        fn dependency_internal_members_are_not_accessible() {
+            let source = indoc! { r#"
+            namespace Test {
+                function Two() : Int {
+                    Library.Two()
+                }
+            }"#};
+
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            let result = Interpreter::with_dependencies(
+                true,
+                &[library_dependency()],
+                sources,
+                PackageType::Lib,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            );
+            match result {
+                Ok(_) => panic!("Expected error, got interpreter."),
+                Err(errors) => is_error(
+                    &errors,
+                    &expect![[r#"
+                    name error: `Two` not found
+                       [test] [Two]
+                "#]],
+                ),
+            }
+        }
+
+        #[test]
+        fn debugger_compiles_dependencies_as_separate_packages() {
+            let source = indoc! { r#"
+            namespace Test {
+                @EntryPoint()
+                function Main() : Int {
+                    Library.Four()
+                }
+            }"#};
+
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            let debugger = Debugger::new(
+                &[library_dependency()],
+                sources,
+                TargetCapabilityFlags::all(),
+                Encoding::Utf8,
+                LanguageFeatures::default(),
+            )
+            .expect("debugger should be created");
+            assert!(debugger.get_breakpoints("library.qs").is_empty());
+
+            let source = indoc! { r#"
+            namespace Test {
+                @EntryPoint()
+                function Main() : Int {
+                    Library.Two()
+                }
+            }"#};
+
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            match Debugger::new(
+                &[library_dependency()],
+                sources,
+                TargetCapabilityFlags::all(),
+                Encoding::Utf8,
+                LanguageFeatures::default(),
+            ) {
+                Ok(_) => panic!("Expected error, got debugger."),
+                Err(errors) => is_error(
+                    &errors,
+                    &expect![[r#"
+                    name error: `Two` not found
+                       [test] [Two]
+                "#]],
+                ),
+            }
+        }
+
+        #[test]
+        fn dependency_exports_can_be_accessed_through_facade_namespace() {
+            let dependency = DependencyPackage {
+                name: "Library".into(),
+                sources: vec![(
+                    "library.qs".into(),
+                    indoc! {"
+                    namespace Library.Arithmetic {
+                        function Double(x : Int) : Int {
+                            x * 2
+                        }
+                    }
+                    namespace Library.Geometry {
+                        internal function Square(x : Int) : Int {
+                            x * x
+                        }
+                    }
+                    namespace Library {
+                        export Library.Arithmetic.Double, Library.Geometry.Square as Area;
+                    }"}
+                    .into(),
+                )],
+                direct: true,
+                ..DependencyPackage::default()
+            };
+            let source = indoc! { r#"
+            namespace Test {
+                open Library;
+                function Compute() : Int {
+                    Area(Double(2))
+                }
+            }"#};
+
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            let mut interpreter = Interpreter::with_dependencies(
+                true,
+                &[dependency],
+                sources,
+                PackageType::Lib,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created");
+
+            let (result, output) = line(&mut interpreter, "Test.Compute() + Library.Double(1)");
+            is_only_value(&result, &output, &Value::Int(18));
+        }
+
         #[test]
         fn members_from_namespaced_sources_are_in_context() {
             let source = indoc! { r#"
@@ -1569,6 +2567,7 @@ mod given_interpreter {
 
             let sources = SourceMap::new(sources, None);
             let debugger = Debugger::new(
+                &[],
                 sources,
                 TargetCapabilityFlags::all(),
                 Encoding::Utf8,
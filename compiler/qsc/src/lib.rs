@@ -30,7 +30,9 @@ pub mod ast {
 }
 
 pub mod project {
-    pub use qsc_project::{DirEntry, EntryType, FileSystem, Manifest, ManifestDescriptor};
+    pub use qsc_project::{
+        DependencyPackage, DirEntry, EntryType, FileSystem, Manifest, ManifestDescriptor,
+    };
 }
 
 pub use qsc_data_structures::{
@@ -44,10 +46,19 @@ pub mod line_column {
 }
 
 pub use qsc_eval::{
-    backend::{Backend, SparseSim},
-    state::{fmt_basis_state_label, fmt_complex, format_state_id, get_latex, get_phase},
+    backend::{
+        format_measurement_record, parse_measurement_record, Backend, MeasurementReplay, SparseSim,
+    },
+    state::{
+        fmt_basis_state_label, fmt_complex, format_state_id, get_latex, get_matrix_latex, get_phase,
+    },
 };
 
+pub mod rir {
+    pub use qsc_eval::rir::{exec, Error};
+    pub use qsc_rir::{parse::parse_program, rir::Program};
+}
+
 pub mod linter {
     pub use qsc_linter::{run_lints, LintConfig, LintKind, LintLevel};
 }
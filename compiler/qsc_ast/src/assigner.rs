@@ -3,8 +3,9 @@
 
 use crate::{
     ast::{
-        Attr, Block, CallableDecl, Expr, FunctorExpr, Ident, Item, Namespace, NodeId, Package, Pat,
-        Path, QubitInit, SpecDecl, Stmt, Ty, TyDef, Visibility,
+        Attr, Block, CallableDecl, ConstDecl, EnumDecl, ExportDecl, ExportItem, Expr, FieldAssign,
+        FieldDef, FunctorExpr, Ident, Item, MatchArm, Namespace, NodeId, Package, Pat, Path,
+        QubitInit, SpecDecl, Stmt, StructDecl, Ty, TyDef, VariantDef, Visibility,
     },
     mut_visit::{self, MutVisitor},
 };
@@ -76,6 +77,41 @@ impl MutVisitor for Assigner {
         mut_visit::walk_callable_decl(self, decl);
     }
 
+    fn visit_struct_decl(&mut self, decl: &mut StructDecl) {
+        self.assign(&mut decl.id);
+        mut_visit::walk_struct_decl(self, decl);
+    }
+
+    fn visit_field_def(&mut self, def: &mut FieldDef) {
+        self.assign(&mut def.id);
+        mut_visit::walk_field_def(self, def);
+    }
+
+    fn visit_enum_decl(&mut self, decl: &mut EnumDecl) {
+        self.assign(&mut decl.id);
+        mut_visit::walk_enum_decl(self, decl);
+    }
+
+    fn visit_variant_def(&mut self, def: &mut VariantDef) {
+        self.assign(&mut def.id);
+        mut_visit::walk_variant_def(self, def);
+    }
+
+    fn visit_export_decl(&mut self, decl: &mut ExportDecl) {
+        self.assign(&mut decl.id);
+        mut_visit::walk_export_decl(self, decl);
+    }
+
+    fn visit_export_item(&mut self, item: &mut ExportItem) {
+        self.assign(&mut item.id);
+        mut_visit::walk_export_item(self, item);
+    }
+
+    fn visit_const_decl(&mut self, decl: &mut ConstDecl) {
+        self.assign(&mut decl.id);
+        mut_visit::walk_const_decl(self, decl);
+    }
+
     fn visit_spec_decl(&mut self, decl: &mut SpecDecl) {
         self.assign(&mut decl.id);
         mut_visit::walk_spec_decl(self, decl);
@@ -106,6 +142,16 @@ impl MutVisitor for Assigner {
         mut_visit::walk_expr(self, expr);
     }
 
+    fn visit_field_assign(&mut self, assign: &mut FieldAssign) {
+        self.assign(&mut assign.id);
+        mut_visit::walk_field_assign(self, assign);
+    }
+
+    fn visit_match_arm(&mut self, arm: &mut MatchArm) {
+        self.assign(&mut arm.id);
+        mut_visit::walk_match_arm(self, arm);
+    }
+
     fn visit_pat(&mut self, pat: &mut Pat) {
         self.assign(&mut pat.id);
         mut_visit::walk_pat(self, pat);
@@ -262,6 +262,14 @@ pub enum ItemKind {
     Open(Box<Ident>, Option<Box<Ident>>),
     /// A `newtype` declaration.
     Ty(Box<Ident>, Box<TyDef>),
+    /// A `struct` declaration.
+    Struct(Box<StructDecl>),
+    /// An `enum` declaration.
+    Enum(Box<EnumDecl>),
+    /// An `export` declaration.
+    Export(Box<ExportDecl>),
+    /// A `const` declaration.
+    Const(Box<ConstDecl>),
 }
 
 impl Display for ItemKind {
@@ -274,6 +282,10 @@ impl Display for ItemKind {
                 None => write!(f, "Open ({name})")?,
             },
             ItemKind::Ty(name, t) => write!(f, "New Type ({name}): {t}")?,
+            ItemKind::Struct(s) => write!(f, "{s}")?,
+            ItemKind::Enum(e) => write!(f, "{e}")?,
+            ItemKind::Export(e) => write!(f, "{e}")?,
+            ItemKind::Const(c) => write!(f, "{c}")?,
         }
         Ok(())
     }
@@ -390,6 +402,208 @@ impl Display for TyDefKind {
     }
 }
 
+/// A struct declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructDecl {
+    /// The node ID.
+    pub id: NodeId,
+    /// The span.
+    pub span: Span,
+    /// The name of the struct.
+    pub name: Box<Ident>,
+    /// The fields of the struct.
+    pub fields: Box<[Box<FieldDef>]>,
+}
+
+impl Display for StructDecl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut indent = set_indentation(indented(f), 0);
+        write!(indent, "Struct {} {} ({}):", self.id, self.span, self.name)?;
+        if self.fields.is_empty() {
+            write!(indent, " <empty>")?;
+        } else {
+            indent = set_indentation(indent, 1);
+            for field in &*self.fields {
+                write!(indent, "\n{field}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A struct field definition.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldDef {
+    /// The node ID.
+    pub id: NodeId,
+    /// The span.
+    pub span: Span,
+    /// The name of the field.
+    pub name: Box<Ident>,
+    /// The type of the field.
+    pub ty: Box<Ty>,
+}
+
+impl Display for FieldDef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FieldDef {} {} ({}): {}",
+            self.id, self.span, self.name, self.ty
+        )
+    }
+}
+
+impl WithSpan for FieldDef {
+    fn with_span(self, span: Span) -> Self {
+        Self { span, ..self }
+    }
+}
+
+/// An enum declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumDecl {
+    /// The node ID.
+    pub id: NodeId,
+    /// The span.
+    pub span: Span,
+    /// The name of the enum.
+    pub name: Box<Ident>,
+    /// The variants of the enum.
+    pub variants: Box<[Box<VariantDef>]>,
+}
+
+impl Display for EnumDecl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut indent = set_indentation(indented(f), 0);
+        write!(indent, "Enum {} {} ({}):", self.id, self.span, self.name)?;
+        if self.variants.is_empty() {
+            write!(indent, " <empty>")?;
+        } else {
+            indent = set_indentation(indent, 1);
+            for variant in &*self.variants {
+                write!(indent, "\n{variant}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An enum variant definition with an optional payload type.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VariantDef {
+    /// The node ID.
+    pub id: NodeId,
+    /// The span.
+    pub span: Span,
+    /// The name of the variant.
+    pub name: Box<Ident>,
+    /// The type of the payload carried by the variant, if any.
+    pub ty: Option<Box<Ty>>,
+}
+
+impl Display for VariantDef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "VariantDef {} {} ({})", self.id, self.span, self.name)?;
+        if let Some(ty) = &self.ty {
+            write!(f, ": {ty}")?;
+        }
+        Ok(())
+    }
+}
+
+impl WithSpan for VariantDef {
+    fn with_span(self, span: Span) -> Self {
+        Self { span, ..self }
+    }
+}
+
+/// An export declaration, making items visible through the enclosing namespace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportDecl {
+    /// The node ID.
+    pub id: NodeId,
+    /// The span.
+    pub span: Span,
+    /// The exported items.
+    pub items: Box<[Box<ExportItem>]>,
+}
+
+impl Display for ExportDecl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut indent = set_indentation(indented(f), 0);
+        write!(indent, "Export {} {}:", self.id, self.span)?;
+        indent = set_indentation(indent, 1);
+        for item in &*self.items {
+            write!(indent, "\n{item}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An item in an export declaration, with an optional alias.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExportItem {
+    /// The node ID.
+    pub id: NodeId,
+    /// The span.
+    pub span: Span,
+    /// The path to the exported item.
+    pub path: Box<Path>,
+    /// The name the item is exported as, if different from its own name.
+    pub alias: Option<Box<Ident>>,
+}
+
+impl ExportItem {
+    /// The name the item is visible as in the exporting namespace.
+    #[must_use]
+    pub fn name(&self) -> &Ident {
+        self.alias.as_deref().unwrap_or(&self.path.name)
+    }
+}
+
+impl Display for ExportItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "ExportItem {} {}: {}", self.id, self.span, self.path)?;
+        if let Some(alias) = &self.alias {
+            write!(f, " as {alias}")?;
+        }
+        Ok(())
+    }
+}
+
+impl WithSpan for ExportItem {
+    fn with_span(self, span: Span) -> Self {
+        Self { span, ..self }
+    }
+}
+
+/// A constant declaration, whose value is evaluated at compile time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstDecl {
+    /// The node ID.
+    pub id: NodeId,
+    /// The span.
+    pub span: Span,
+    /// The name of the constant.
+    pub name: Box<Ident>,
+    /// The type of the constant.
+    pub ty: Box<Ty>,
+    /// The value of the constant.
+    pub value: Box<Expr>,
+}
+
+impl Display for ConstDecl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut indent = set_indentation(indented(f), 0);
+        write!(indent, "Const {} {} ({}):", self.id, self.span, self.name)?;
+        indent = set_indentation(indent, 1);
+        write!(indent, "\ntype: {}", self.ty)?;
+        write!(indent, "\nvalue: {}", self.value)?;
+        Ok(())
+    }
+}
+
 /// A callable declaration header.
 #[derive(Clone, Debug, PartialEq)]
 pub struct CallableDecl {
@@ -402,9 +616,11 @@ pub struct CallableDecl {
     /// The name of the callable.
     pub name: Box<Ident>,
     /// The generic parameters to the callable.
-    pub generics: Box<[Box<Ident>]>,
+    pub generics: Box<[TypeParameter]>,
     /// The input to the callable.
     pub input: Box<Pat>,
+    /// The default values of the trailing parameters in the input.
+    pub defaults: Box<[Box<Expr>]>,
     /// The return type of the callable.
     pub output: Box<Ty>,
     /// The functors supported by the callable.
@@ -432,6 +648,14 @@ impl Display for CallableDecl {
             indent = set_indentation(indent, 1);
         }
         write!(indent, "\ninput: {}", self.input)?;
+        if !self.defaults.is_empty() {
+            write!(indent, "\ndefaults:")?;
+            indent = set_indentation(indent, 2);
+            for default in &*self.defaults {
+                write!(indent, "\n{default}")?;
+            }
+            indent = set_indentation(indent, 1);
+        }
         write!(indent, "\noutput: {}", self.output)?;
         if let Some(f) = &self.functors {
             write!(indent, "\nfunctors: {}", f.as_ref())?;
@@ -441,6 +665,43 @@ impl Display for CallableDecl {
     }
 }
 
+/// A generic type parameter of a callable, with the classes it is bounded by.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TypeParameter {
+    /// The span.
+    pub span: Span,
+    /// The name of the type parameter.
+    pub name: Box<Ident>,
+    /// The classes that the type parameter must belong to.
+    pub bounds: Box<[Box<Ident>]>,
+}
+
+impl Display for TypeParameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut indent = set_indentation(indented(f), 0);
+        write!(indent, "TypeParameter {}: {}", self.span, self.name)?;
+        if !self.bounds.is_empty() {
+            indent = set_indentation(indent, 1);
+            write!(indent, "\nbounds:")?;
+            indent = set_indentation(indent, 2);
+            for bound in &*self.bounds {
+                write!(indent, "\n{bound}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WithSpan for TypeParameter {
+    fn with_span(self, span: Span) -> Self {
+        Self {
+            span,
+            name: Box::new((*self.name).with_span(span)),
+            ..self
+        }
+    }
+}
+
 /// The body of a callable.
 #[derive(Clone, Debug, PartialEq)]
 pub enum CallableBody {
@@ -768,10 +1029,14 @@ pub enum ExprKind {
     BinOp(BinOp, Box<Expr>, Box<Expr>),
     /// A block: `{ ... }`.
     Block(Box<Block>),
+    /// A break out of the innermost loop: `break`.
+    Break,
     /// A call: `a(b)`.
     Call(Box<Expr>, Box<Expr>),
     /// A conjugation: `within { ... } apply { ... }`.
     Conjugate(Box<Block>, Box<Block>),
+    /// A jump to the next iteration of the innermost loop: `continue`.
+    Continue,
     /// An expression with invalid syntax that can't be parsed.
     #[default]
     Err,
@@ -797,6 +1062,8 @@ pub enum ExprKind {
     Lambda(CallableKind, Box<Pat>, Box<Expr>),
     /// A literal.
     Lit(Box<Lit>),
+    /// A match expression: `match a { B.C(d) => e, _ => f }`.
+    Match(Box<Expr>, Box<[Box<MatchArm>]>),
     /// Parentheses: `(a)`.
     Paren(Box<Expr>),
     /// A path: `a` or `a.b`.
@@ -807,6 +1074,9 @@ pub enum ExprKind {
     Repeat(Box<Block>, Box<Expr>, Option<Box<Block>>),
     /// A return: `return a`.
     Return(Box<Expr>),
+    /// A struct constructor: `new A { B = c }`, optionally copying the fields that are not
+    /// assigned from an existing value: `new A { ...a, B = c }`.
+    Struct(Box<Path>, Option<Box<Expr>>, Box<[Box<FieldAssign>]>),
     /// A ternary operator.
     TernOp(TernOp, Box<Expr>, Box<Expr>, Box<Expr>),
     /// A tuple: `(a, b, c)`.
@@ -830,8 +1100,10 @@ impl Display for ExprKind {
             }
             ExprKind::BinOp(op, lhs, rhs) => display_bin_op(indent, *op, lhs, rhs)?,
             ExprKind::Block(block) => write!(indent, "Expr Block: {block}")?,
+            ExprKind::Break => write!(indent, "Break")?,
             ExprKind::Call(callable, arg) => display_call(indent, callable, arg)?,
             ExprKind::Conjugate(within, apply) => display_conjugate(indent, within, apply)?,
+            ExprKind::Continue => write!(indent, "Continue")?,
             ExprKind::Err => write!(indent, "Err")?,
             ExprKind::Fail(e) => write!(indent, "Fail: {e}")?,
             ExprKind::Field(expr, id) => display_field(indent, expr, id)?,
@@ -842,11 +1114,15 @@ impl Display for ExprKind {
             ExprKind::Interpolate(components) => display_interpolate(indent, components)?,
             ExprKind::Lambda(kind, param, expr) => display_lambda(indent, *kind, param, expr)?,
             ExprKind::Lit(lit) => write!(indent, "Lit: {lit}")?,
+            ExprKind::Match(expr, arms) => display_match(indent, expr, arms)?,
             ExprKind::Paren(e) => write!(indent, "Paren: {e}")?,
             ExprKind::Path(p) => write!(indent, "Path: {p}")?,
             ExprKind::Range(start, step, end) => display_range(indent, start, step, end)?,
             ExprKind::Repeat(repeat, until, fixup) => display_repeat(indent, repeat, until, fixup)?,
             ExprKind::Return(e) => write!(indent, "Return: {e}")?,
+            ExprKind::Struct(name, copy, fields) => {
+                display_struct(indent, name, copy.as_deref(), fields)?;
+            }
             ExprKind::TernOp(op, expr1, expr2, expr3) => {
                 display_tern_op(indent, *op, expr1, expr2, expr3)?;
             }
@@ -951,6 +1227,27 @@ fn display_field(mut indent: Indented<Formatter>, expr: &Expr, id: &Ident) -> fm
     Ok(())
 }
 
+fn display_struct(
+    mut indent: Indented<Formatter>,
+    name: &Path,
+    copy: Option<&Expr>,
+    fields: &[Box<FieldAssign>],
+) -> fmt::Result {
+    write!(indent, "Struct ({name}):")?;
+    if copy.is_none() && fields.is_empty() {
+        write!(indent, " <empty>")?;
+        return Ok(());
+    }
+    indent = set_indentation(indent, 1);
+    if let Some(copy) = copy {
+        write!(indent, "\nCopy: {copy}")?;
+    }
+    for field in fields {
+        write!(indent, "\n{field}")?;
+    }
+    Ok(())
+}
+
 fn display_for(
     mut indent: Indented<Formatter>,
     iter: &Pat,
@@ -1005,6 +1302,20 @@ fn display_interpolate(
     Ok(())
 }
 
+fn display_match(
+    mut indent: Indented<Formatter>,
+    expr: &Expr,
+    arms: &[Box<MatchArm>],
+) -> fmt::Result {
+    write!(indent, "Match:")?;
+    indent = set_indentation(indent, 1);
+    write!(indent, "\n{expr}")?;
+    for arm in arms {
+        write!(indent, "\n{arm}")?;
+    }
+    Ok(())
+}
+
 fn display_lambda(
     mut indent: Indented<Formatter>,
     kind: CallableKind,
@@ -1101,6 +1412,69 @@ fn display_while(mut indent: Indented<Formatter>, cond: &Expr, block: &Block) ->
     Ok(())
 }
 
+/// A field assignment in a struct constructor expression: `A = b`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldAssign {
+    /// The node ID.
+    pub id: NodeId,
+    /// The span.
+    pub span: Span,
+    /// The field to assign.
+    pub field: Box<Ident>,
+    /// The value assigned to the field.
+    pub value: Box<Expr>,
+}
+
+impl Display for FieldAssign {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut indent = set_indentation(indented(f), 0);
+        write!(
+            indent,
+            "FieldAssign {} {} ({}):",
+            self.id, self.span, self.field
+        )?;
+        indent = set_indentation(indent, 1);
+        write!(indent, "\n{}", self.value)?;
+        Ok(())
+    }
+}
+
+impl WithSpan for FieldAssign {
+    fn with_span(self, span: Span) -> Self {
+        Self { span, ..self }
+    }
+}
+
+/// An arm of a match expression: `A.B(c) => d`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchArm {
+    /// The node ID.
+    pub id: NodeId,
+    /// The span.
+    pub span: Span,
+    /// The pattern matched by the arm.
+    pub pat: Box<Pat>,
+    /// The expression evaluated when the pattern matches.
+    pub body: Box<Expr>,
+}
+
+impl Display for MatchArm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut indent = set_indentation(indented(f), 0);
+        write!(indent, "MatchArm {} {}:", self.id, self.span)?;
+        indent = set_indentation(indent, 1);
+        write!(indent, "\n{}", self.pat)?;
+        write!(indent, "\n{}", self.body)?;
+        Ok(())
+    }
+}
+
+impl WithSpan for MatchArm {
+    fn with_span(self, span: Span) -> Self {
+        Self { span, ..self }
+    }
+}
+
 /// An interpolated string component.
 #[derive(Clone, Debug, PartialEq)]
 pub enum StringComponent {
@@ -1146,6 +1520,10 @@ pub enum PatKind {
     Paren(Box<Pat>),
     /// A tuple: `(a, b, c)`.
     Tuple(Box<[Box<Pat>]>),
+    /// An array with an optional pattern for the remaining items: `[a, b, ...rest]`.
+    Array(Box<[Box<Pat>]>, Option<Box<Pat>>),
+    /// An enum variant with an optional payload pattern: `A.B` or `A.B(c)`.
+    Variant(Box<Path>, Option<Box<Pat>>),
     /// An invalid pattern.
     #[default]
     Err,
@@ -1188,6 +1566,24 @@ impl Display for PatKind {
                     }
                 }
             }
+            PatKind::Array(ps, rest) => {
+                write!(indent, "Array:")?;
+                indent = set_indentation(indent, 1);
+                for p in ps {
+                    write!(indent, "\n{p}")?;
+                }
+                if let Some(rest) = rest {
+                    write!(indent, "\nRest: {rest}")?;
+                }
+            }
+            PatKind::Variant(path, payload) => match payload {
+                Some(p) => {
+                    write!(indent, "Variant ({path}):")?;
+                    indent = set_indentation(indent, 1);
+                    write!(indent, "\n{p}")?;
+                }
+                None => write!(indent, "Variant ({path})")?,
+            },
             PatKind::Err => write!(indent, "Err")?,
         }
         Ok(())
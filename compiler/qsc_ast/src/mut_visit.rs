@@ -2,9 +2,10 @@
 // Licensed under the MIT License.
 
 use crate::ast::{
-    Attr, Block, CallableBody, CallableDecl, Expr, ExprKind, FunctorExpr, FunctorExprKind, Ident,
-    Item, ItemKind, Namespace, Package, Pat, PatKind, Path, QubitInit, QubitInitKind, SpecBody,
-    SpecDecl, Stmt, StmtKind, StringComponent, TopLevelNode, Ty, TyDef, TyDefKind, TyKind,
+    Attr, Block, CallableBody, CallableDecl, ConstDecl, EnumDecl, ExportDecl, ExportItem, Expr,
+    ExprKind, FieldAssign, FieldDef, FunctorExpr, FunctorExprKind, Ident, Item, ItemKind, MatchArm,
+    Namespace, Package, Pat, PatKind, Path, QubitInit, QubitInitKind, SpecBody, SpecDecl, Stmt,
+    StmtKind, StringComponent, StructDecl, TopLevelNode, Ty, TyDef, TyDefKind, TyKind, VariantDef,
     Visibility,
 };
 use qsc_data_structures::span::Span;
@@ -36,6 +37,34 @@ pub trait MutVisitor: Sized {
         walk_callable_decl(self, decl);
     }
 
+    fn visit_struct_decl(&mut self, decl: &mut StructDecl) {
+        walk_struct_decl(self, decl);
+    }
+
+    fn visit_field_def(&mut self, def: &mut FieldDef) {
+        walk_field_def(self, def);
+    }
+
+    fn visit_enum_decl(&mut self, decl: &mut EnumDecl) {
+        walk_enum_decl(self, decl);
+    }
+
+    fn visit_variant_def(&mut self, def: &mut VariantDef) {
+        walk_variant_def(self, def);
+    }
+
+    fn visit_export_decl(&mut self, decl: &mut ExportDecl) {
+        walk_export_decl(self, decl);
+    }
+
+    fn visit_export_item(&mut self, item: &mut ExportItem) {
+        walk_export_item(self, item);
+    }
+
+    fn visit_const_decl(&mut self, decl: &mut ConstDecl) {
+        walk_const_decl(self, decl);
+    }
+
     fn visit_spec_decl(&mut self, decl: &mut SpecDecl) {
         walk_spec_decl(self, decl);
     }
@@ -60,6 +89,14 @@ pub trait MutVisitor: Sized {
         walk_expr(self, expr);
     }
 
+    fn visit_field_assign(&mut self, assign: &mut FieldAssign) {
+        walk_field_assign(self, assign);
+    }
+
+    fn visit_match_arm(&mut self, arm: &mut MatchArm) {
+        walk_match_arm(self, arm);
+    }
+
     fn visit_pat(&mut self, pat: &mut Pat) {
         walk_pat(self, pat);
     }
@@ -111,6 +148,10 @@ pub fn walk_item(vis: &mut impl MutVisitor, item: &mut Item) {
             vis.visit_ident(ident);
             vis.visit_ty_def(def);
         }
+        ItemKind::Struct(decl) => vis.visit_struct_decl(decl),
+        ItemKind::Enum(decl) => vis.visit_enum_decl(decl),
+        ItemKind::Export(decl) => vis.visit_export_decl(decl),
+        ItemKind::Const(decl) => vis.visit_const_decl(decl),
     }
 }
 
@@ -134,11 +175,60 @@ pub fn walk_ty_def(vis: &mut impl MutVisitor, def: &mut TyDef) {
     }
 }
 
+pub fn walk_struct_decl(vis: &mut impl MutVisitor, decl: &mut StructDecl) {
+    vis.visit_span(&mut decl.span);
+    vis.visit_ident(&mut decl.name);
+    decl.fields.iter_mut().for_each(|f| vis.visit_field_def(f));
+}
+
+pub fn walk_field_def(vis: &mut impl MutVisitor, def: &mut FieldDef) {
+    vis.visit_span(&mut def.span);
+    vis.visit_ident(&mut def.name);
+    vis.visit_ty(&mut def.ty);
+}
+
+pub fn walk_enum_decl(vis: &mut impl MutVisitor, decl: &mut EnumDecl) {
+    vis.visit_span(&mut decl.span);
+    vis.visit_ident(&mut decl.name);
+    decl.variants
+        .iter_mut()
+        .for_each(|v| vis.visit_variant_def(v));
+}
+
+pub fn walk_variant_def(vis: &mut impl MutVisitor, def: &mut VariantDef) {
+    vis.visit_span(&mut def.span);
+    vis.visit_ident(&mut def.name);
+    def.ty.iter_mut().for_each(|t| vis.visit_ty(t));
+}
+
+pub fn walk_export_decl(vis: &mut impl MutVisitor, decl: &mut ExportDecl) {
+    vis.visit_span(&mut decl.span);
+    decl.items.iter_mut().for_each(|i| vis.visit_export_item(i));
+}
+
+pub fn walk_export_item(vis: &mut impl MutVisitor, item: &mut ExportItem) {
+    vis.visit_span(&mut item.span);
+    vis.visit_path(&mut item.path);
+    item.alias.iter_mut().for_each(|a| vis.visit_ident(a));
+}
+
+pub fn walk_const_decl(vis: &mut impl MutVisitor, decl: &mut ConstDecl) {
+    vis.visit_span(&mut decl.span);
+    vis.visit_ident(&mut decl.name);
+    vis.visit_ty(&mut decl.ty);
+    vis.visit_expr(&mut decl.value);
+}
+
 pub fn walk_callable_decl(vis: &mut impl MutVisitor, decl: &mut CallableDecl) {
     vis.visit_span(&mut decl.span);
     vis.visit_ident(&mut decl.name);
-    decl.generics.iter_mut().for_each(|p| vis.visit_ident(p));
+    decl.generics.iter_mut().for_each(|p| {
+        vis.visit_span(&mut p.span);
+        vis.visit_ident(&mut p.name);
+        p.bounds.iter_mut().for_each(|b| vis.visit_ident(b));
+    });
     vis.visit_pat(&mut decl.input);
+    decl.defaults.iter_mut().for_each(|d| vis.visit_expr(d));
     vis.visit_ty(&mut decl.output);
     decl.functors
         .iter_mut()
@@ -277,6 +367,10 @@ pub fn walk_expr(vis: &mut impl MutVisitor, expr: &mut Expr) {
             vis.visit_pat(pat);
             vis.visit_expr(expr);
         }
+        ExprKind::Match(expr, arms) => {
+            vis.visit_expr(expr);
+            arms.iter_mut().for_each(|a| vis.visit_match_arm(a));
+        }
         ExprKind::Paren(expr) | ExprKind::Return(expr) | ExprKind::UnOp(_, expr) => {
             vis.visit_expr(expr);
         }
@@ -291,6 +385,13 @@ pub fn walk_expr(vis: &mut impl MutVisitor, expr: &mut Expr) {
             vis.visit_expr(until);
             fixup.iter_mut().for_each(|f| vis.visit_block(f));
         }
+        ExprKind::Struct(name, copy, fields) => {
+            vis.visit_path(name);
+            if let Some(copy) = copy {
+                vis.visit_expr(copy);
+            }
+            fields.iter_mut().for_each(|f| vis.visit_field_assign(f));
+        }
         ExprKind::TernOp(_, e1, e2, e3) => {
             vis.visit_expr(e1);
             vis.visit_expr(e2);
@@ -301,10 +402,26 @@ pub fn walk_expr(vis: &mut impl MutVisitor, expr: &mut Expr) {
             vis.visit_expr(cond);
             vis.visit_block(block);
         }
-        ExprKind::Err | ExprKind::Hole | ExprKind::Lit(_) => {}
+        ExprKind::Break
+        | ExprKind::Continue
+        | ExprKind::Err
+        | ExprKind::Hole
+        | ExprKind::Lit(_) => {}
     }
 }
 
+pub fn walk_field_assign(vis: &mut impl MutVisitor, assign: &mut FieldAssign) {
+    vis.visit_span(&mut assign.span);
+    vis.visit_ident(&mut assign.field);
+    vis.visit_expr(&mut assign.value);
+}
+
+pub fn walk_match_arm(vis: &mut impl MutVisitor, arm: &mut MatchArm) {
+    vis.visit_span(&mut arm.span);
+    vis.visit_pat(&mut arm.pat);
+    vis.visit_expr(&mut arm.body);
+}
+
 pub fn walk_pat(vis: &mut impl MutVisitor, pat: &mut Pat) {
     vis.visit_span(&mut pat.span);
 
@@ -317,6 +434,16 @@ pub fn walk_pat(vis: &mut impl MutVisitor, pat: &mut Pat) {
         PatKind::Elided | PatKind::Err => {}
         PatKind::Paren(pat) => vis.visit_pat(pat),
         PatKind::Tuple(pats) => pats.iter_mut().for_each(|p| vis.visit_pat(p)),
+        PatKind::Array(pats, rest) => pats
+            .iter_mut()
+            .chain(rest.iter_mut())
+            .for_each(|p| vis.visit_pat(p)),
+        PatKind::Variant(path, payload) => {
+            vis.visit_path(path);
+            if let Some(p) = payload {
+                vis.visit_pat(p);
+            }
+        }
     }
 }
 
@@ -3,8 +3,9 @@
 
 use crate::{
     ast::{
-        Attr, Block, CallableDecl, Expr, FunctorExpr, Ident, Item, Namespace, NodeId, Package, Pat,
-        Path, QubitInit, SpecDecl, Stmt, Ty, TyDef, Visibility,
+        Attr, Block, CallableDecl, ConstDecl, EnumDecl, ExportDecl, ExportItem, Expr, FieldAssign,
+        FieldDef, FunctorExpr, Ident, Item, MatchArm, Namespace, NodeId, Package, Pat, Path,
+        QubitInit, SpecDecl, Stmt, StructDecl, Ty, TyDef, VariantDef, Visibility,
     },
     visit::{self, Visitor},
 };
@@ -63,6 +64,41 @@ impl Visitor<'_> for Validator {
         visit::walk_callable_decl(self, decl);
     }
 
+    fn visit_struct_decl(&mut self, decl: &StructDecl) {
+        self.check(decl.id, decl);
+        visit::walk_struct_decl(self, decl);
+    }
+
+    fn visit_field_def(&mut self, def: &FieldDef) {
+        self.check(def.id, def);
+        visit::walk_field_def(self, def);
+    }
+
+    fn visit_enum_decl(&mut self, decl: &EnumDecl) {
+        self.check(decl.id, decl);
+        visit::walk_enum_decl(self, decl);
+    }
+
+    fn visit_variant_def(&mut self, def: &VariantDef) {
+        self.check(def.id, def);
+        visit::walk_variant_def(self, def);
+    }
+
+    fn visit_export_decl(&mut self, decl: &ExportDecl) {
+        self.check(decl.id, decl);
+        visit::walk_export_decl(self, decl);
+    }
+
+    fn visit_export_item(&mut self, item: &ExportItem) {
+        self.check(item.id, item);
+        visit::walk_export_item(self, item);
+    }
+
+    fn visit_const_decl(&mut self, decl: &ConstDecl) {
+        self.check(decl.id, decl);
+        visit::walk_const_decl(self, decl);
+    }
+
     fn visit_spec_decl(&mut self, decl: &SpecDecl) {
         self.check(decl.id, decl);
         visit::walk_spec_decl(self, decl);
@@ -93,6 +129,16 @@ impl Visitor<'_> for Validator {
         visit::walk_expr(self, expr);
     }
 
+    fn visit_field_assign(&mut self, assign: &FieldAssign) {
+        self.check(assign.id, assign);
+        visit::walk_field_assign(self, assign);
+    }
+
+    fn visit_match_arm(&mut self, arm: &MatchArm) {
+        self.check(arm.id, arm);
+        visit::walk_match_arm(self, arm);
+    }
+
     fn visit_pat(&mut self, pat: &Pat) {
         self.check(pat.id, pat);
         visit::walk_pat(self, pat);
@@ -2,9 +2,10 @@
 // Licensed under the MIT License.
 
 use crate::ast::{
-    Attr, Block, CallableBody, CallableDecl, Expr, ExprKind, FunctorExpr, FunctorExprKind, Ident,
-    Item, ItemKind, Namespace, Package, Pat, PatKind, Path, QubitInit, QubitInitKind, SpecBody,
-    SpecDecl, Stmt, StmtKind, StringComponent, TopLevelNode, Ty, TyDef, TyDefKind, TyKind,
+    Attr, Block, CallableBody, CallableDecl, ConstDecl, EnumDecl, ExportDecl, ExportItem, Expr,
+    ExprKind, FieldAssign, FieldDef, FunctorExpr, FunctorExprKind, Ident, Item, ItemKind, MatchArm,
+    Namespace, Package, Pat, PatKind, Path, QubitInit, QubitInitKind, SpecBody, SpecDecl, Stmt,
+    StmtKind, StringComponent, StructDecl, TopLevelNode, Ty, TyDef, TyDefKind, TyKind, VariantDef,
     Visibility,
 };
 
@@ -35,6 +36,34 @@ pub trait Visitor<'a>: Sized {
         walk_callable_decl(self, decl);
     }
 
+    fn visit_struct_decl(&mut self, decl: &'a StructDecl) {
+        walk_struct_decl(self, decl);
+    }
+
+    fn visit_field_def(&mut self, def: &'a FieldDef) {
+        walk_field_def(self, def);
+    }
+
+    fn visit_enum_decl(&mut self, decl: &'a EnumDecl) {
+        walk_enum_decl(self, decl);
+    }
+
+    fn visit_variant_def(&mut self, def: &'a VariantDef) {
+        walk_variant_def(self, def);
+    }
+
+    fn visit_export_decl(&mut self, decl: &'a ExportDecl) {
+        walk_export_decl(self, decl);
+    }
+
+    fn visit_export_item(&mut self, item: &'a ExportItem) {
+        walk_export_item(self, item);
+    }
+
+    fn visit_const_decl(&mut self, decl: &'a ConstDecl) {
+        walk_const_decl(self, decl);
+    }
+
     fn visit_spec_decl(&mut self, decl: &'a SpecDecl) {
         walk_spec_decl(self, decl);
     }
@@ -59,6 +88,14 @@ pub trait Visitor<'a>: Sized {
         walk_expr(self, expr);
     }
 
+    fn visit_field_assign(&mut self, assign: &'a FieldAssign) {
+        walk_field_assign(self, assign);
+    }
+
+    fn visit_match_arm(&mut self, arm: &'a MatchArm) {
+        walk_match_arm(self, arm);
+    }
+
     fn visit_pat(&mut self, pat: &'a Pat) {
         walk_pat(self, pat);
     }
@@ -101,6 +138,10 @@ pub fn walk_item<'a>(vis: &mut impl Visitor<'a>, item: &'a Item) {
             vis.visit_ident(ident);
             vis.visit_ty_def(def);
         }
+        ItemKind::Struct(decl) => vis.visit_struct_decl(decl),
+        ItemKind::Enum(decl) => vis.visit_enum_decl(decl),
+        ItemKind::Export(decl) => vis.visit_export_decl(decl),
+        ItemKind::Const(decl) => vis.visit_const_decl(decl),
     }
 }
 
@@ -121,10 +162,49 @@ pub fn walk_ty_def<'a>(vis: &mut impl Visitor<'a>, def: &'a TyDef) {
     }
 }
 
+pub fn walk_struct_decl<'a>(vis: &mut impl Visitor<'a>, decl: &'a StructDecl) {
+    vis.visit_ident(&decl.name);
+    decl.fields.iter().for_each(|f| vis.visit_field_def(f));
+}
+
+pub fn walk_field_def<'a>(vis: &mut impl Visitor<'a>, def: &'a FieldDef) {
+    vis.visit_ident(&def.name);
+    vis.visit_ty(&def.ty);
+}
+
+pub fn walk_enum_decl<'a>(vis: &mut impl Visitor<'a>, decl: &'a EnumDecl) {
+    vis.visit_ident(&decl.name);
+    decl.variants.iter().for_each(|v| vis.visit_variant_def(v));
+}
+
+pub fn walk_variant_def<'a>(vis: &mut impl Visitor<'a>, def: &'a VariantDef) {
+    vis.visit_ident(&def.name);
+    def.ty.iter().for_each(|t| vis.visit_ty(t));
+}
+
+pub fn walk_export_decl<'a>(vis: &mut impl Visitor<'a>, decl: &'a ExportDecl) {
+    decl.items.iter().for_each(|i| vis.visit_export_item(i));
+}
+
+pub fn walk_export_item<'a>(vis: &mut impl Visitor<'a>, item: &'a ExportItem) {
+    vis.visit_path(&item.path);
+    item.alias.iter().for_each(|a| vis.visit_ident(a));
+}
+
+pub fn walk_const_decl<'a>(vis: &mut impl Visitor<'a>, decl: &'a ConstDecl) {
+    vis.visit_ident(&decl.name);
+    vis.visit_ty(&decl.ty);
+    vis.visit_expr(&decl.value);
+}
+
 pub fn walk_callable_decl<'a>(vis: &mut impl Visitor<'a>, decl: &'a CallableDecl) {
     vis.visit_ident(&decl.name);
-    decl.generics.iter().for_each(|p| vis.visit_ident(p));
+    decl.generics.iter().for_each(|p| {
+        vis.visit_ident(&p.name);
+        p.bounds.iter().for_each(|b| vis.visit_ident(b));
+    });
     vis.visit_pat(&decl.input);
+    decl.defaults.iter().for_each(|d| vis.visit_expr(d));
     vis.visit_ty(&decl.output);
     decl.functors.iter().for_each(|f| vis.visit_functor_expr(f));
     match &*decl.body {
@@ -249,6 +329,10 @@ pub fn walk_expr<'a>(vis: &mut impl Visitor<'a>, expr: &'a Expr) {
             vis.visit_pat(pat);
             vis.visit_expr(expr);
         }
+        ExprKind::Match(expr, arms) => {
+            vis.visit_expr(expr);
+            arms.iter().for_each(|a| vis.visit_match_arm(a));
+        }
         ExprKind::Paren(expr) | ExprKind::Return(expr) | ExprKind::UnOp(_, expr) => {
             vis.visit_expr(expr);
         }
@@ -263,6 +347,13 @@ pub fn walk_expr<'a>(vis: &mut impl Visitor<'a>, expr: &'a Expr) {
             vis.visit_expr(until);
             fixup.iter().for_each(|f| vis.visit_block(f));
         }
+        ExprKind::Struct(name, copy, fields) => {
+            vis.visit_path(name);
+            if let Some(copy) = copy {
+                vis.visit_expr(copy);
+            }
+            fields.iter().for_each(|f| vis.visit_field_assign(f));
+        }
         ExprKind::TernOp(_, e1, e2, e3) => {
             vis.visit_expr(e1);
             vis.visit_expr(e2);
@@ -273,10 +364,24 @@ pub fn walk_expr<'a>(vis: &mut impl Visitor<'a>, expr: &'a Expr) {
             vis.visit_expr(cond);
             vis.visit_block(block);
         }
-        ExprKind::Err | ExprKind::Hole | ExprKind::Lit(_) => {}
+        ExprKind::Break
+        | ExprKind::Continue
+        | ExprKind::Err
+        | ExprKind::Hole
+        | ExprKind::Lit(_) => {}
     }
 }
 
+pub fn walk_field_assign<'a>(vis: &mut impl Visitor<'a>, assign: &'a FieldAssign) {
+    vis.visit_ident(&assign.field);
+    vis.visit_expr(&assign.value);
+}
+
+pub fn walk_match_arm<'a>(vis: &mut impl Visitor<'a>, arm: &'a MatchArm) {
+    vis.visit_pat(&arm.pat);
+    vis.visit_expr(&arm.body);
+}
+
 pub fn walk_pat<'a>(vis: &mut impl Visitor<'a>, pat: &'a Pat) {
     match &*pat.kind {
         PatKind::Bind(name, ty) => {
@@ -287,6 +392,13 @@ pub fn walk_pat<'a>(vis: &mut impl Visitor<'a>, pat: &'a Pat) {
         PatKind::Elided | PatKind::Err => {}
         PatKind::Paren(pat) => vis.visit_pat(pat),
         PatKind::Tuple(pats) => pats.iter().for_each(|p| vis.visit_pat(p)),
+        PatKind::Array(pats, rest) => pats.iter().chain(rest).for_each(|p| vis.visit_pat(p)),
+        PatKind::Variant(path, payload) => {
+            vis.visit_path(path);
+            if let Some(p) = payload {
+                vis.visit_pat(p);
+            }
+        }
     }
 }
 
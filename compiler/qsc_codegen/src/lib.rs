@@ -3,5 +3,6 @@
 
 pub mod qir;
 pub mod qir_base;
+pub mod qir_reader;
 pub mod qsharp;
 pub mod remapper;
@@ -12,8 +12,8 @@ use qsc_lowerer::map_hir_package_to_fir;
 use qsc_partial_eval::{partially_evaluate, ProgramEntry};
 use qsc_rca::PackageStoreComputeProperties;
 use qsc_rir::{
-    passes::check_and_transform,
-    rir::{self, ConditionCode},
+    passes::{check_and_transform, optimize_gates, GateOptimizationStats},
+    rir::{self, ConditionCode, FcmpConditionCode},
     utils::get_all_block_successors,
 };
 
@@ -30,22 +30,85 @@ fn lower_store(package_store: &qsc_frontend::compile::PackageStore) -> qsc_fir::
 pub fn hir_to_qir(
     package_store: &qsc_frontend::compile::PackageStore,
     capabilities: TargetCapabilityFlags,
+    optimization_level: rir::OptimizationLevel,
     compute_properties: Option<PackageStoreComputeProperties>,
     entry: &ProgramEntry,
 ) -> Result<String, qsc_partial_eval::Error> {
     let fir_store = lower_store(package_store);
-    fir_to_qir(&fir_store, capabilities, compute_properties, entry)
+    fir_to_qir(
+        &fir_store,
+        capabilities,
+        optimization_level,
+        compute_properties,
+        entry,
+    )
 }
 
 pub fn fir_to_qir(
     fir_store: &qsc_fir::fir::PackageStore,
     capabilities: TargetCapabilityFlags,
+    optimization_level: rir::OptimizationLevel,
     compute_properties: Option<PackageStoreComputeProperties>,
     entry: &ProgramEntry,
 ) -> Result<String, qsc_partial_eval::Error> {
+    fir_to_qir_with_stats(
+        fir_store,
+        capabilities,
+        optimization_level,
+        compute_properties,
+        entry,
+    )
+    .map(|(qir, _)| qir)
+}
+
+/// converts the given FIR to QIR, along with statistics about the gates removed by optimization.
+pub fn fir_to_qir_with_stats(
+    fir_store: &qsc_fir::fir::PackageStore,
+    capabilities: TargetCapabilityFlags,
+    optimization_level: rir::OptimizationLevel,
+    compute_properties: Option<PackageStoreComputeProperties>,
+    entry: &ProgramEntry,
+) -> Result<(String, GateOptimizationStats), qsc_partial_eval::Error> {
+    let (program, stats) = fir_to_rir_with_stats(
+        fir_store,
+        capabilities,
+        optimization_level,
+        compute_properties,
+        entry,
+    )?;
+    Ok((ToQir::<String>::to_qir(&program, &program), stats))
+}
+
+/// converts the given FIR to RIR, transformed and optimized in the same way as for QIR generation.
+pub fn fir_to_rir(
+    fir_store: &qsc_fir::fir::PackageStore,
+    capabilities: TargetCapabilityFlags,
+    optimization_level: rir::OptimizationLevel,
+    compute_properties: Option<PackageStoreComputeProperties>,
+    entry: &ProgramEntry,
+) -> Result<rir::Program, qsc_partial_eval::Error> {
+    fir_to_rir_with_stats(
+        fir_store,
+        capabilities,
+        optimization_level,
+        compute_properties,
+        entry,
+    )
+    .map(|(program, _)| program)
+}
+
+fn fir_to_rir_with_stats(
+    fir_store: &qsc_fir::fir::PackageStore,
+    capabilities: TargetCapabilityFlags,
+    optimization_level: rir::OptimizationLevel,
+    compute_properties: Option<PackageStoreComputeProperties>,
+    entry: &ProgramEntry,
+) -> Result<(rir::Program, GateOptimizationStats), qsc_partial_eval::Error> {
     let mut program = get_rir_from_compilation(fir_store, compute_properties, entry, capabilities)?;
+    program.config.optimization_level = optimization_level;
     check_and_transform(&mut program);
-    Ok(ToQir::<String>::to_qir(&program, &program))
+    let stats = optimize_gates(&mut program);
+    Ok((program, stats))
 }
 
 fn get_rir_from_compilation(
@@ -149,6 +212,29 @@ impl ToQir<String> for rir::ConditionCode {
     }
 }
 
+impl ToQir<String> for rir::FcmpConditionCode {
+    fn to_qir(&self, _program: &rir::Program) -> String {
+        match self {
+            rir::FcmpConditionCode::False => "false".to_string(),
+            rir::FcmpConditionCode::OrderedAndEqual => "oeq".to_string(),
+            rir::FcmpConditionCode::OrderedAndGreaterThan => "ogt".to_string(),
+            rir::FcmpConditionCode::OrderedAndGreaterThanOrEqual => "oge".to_string(),
+            rir::FcmpConditionCode::OrderedAndLessThan => "olt".to_string(),
+            rir::FcmpConditionCode::OrderedAndLessThanOrEqual => "ole".to_string(),
+            rir::FcmpConditionCode::OrderedAndNotEqual => "one".to_string(),
+            rir::FcmpConditionCode::Ordered => "ord".to_string(),
+            rir::FcmpConditionCode::UnorderedOrEqual => "ueq".to_string(),
+            rir::FcmpConditionCode::UnorderedOrGreaterThan => "ugt".to_string(),
+            rir::FcmpConditionCode::UnorderedOrGreaterThanOrEqual => "uge".to_string(),
+            rir::FcmpConditionCode::UnorderedOrLessThan => "ult".to_string(),
+            rir::FcmpConditionCode::UnorderedOrLessThanOrEqual => "ule".to_string(),
+            rir::FcmpConditionCode::UnorderedOrNotEqual => "une".to_string(),
+            rir::FcmpConditionCode::Unordered => "uno".to_string(),
+            rir::FcmpConditionCode::True => "true".to_string(),
+        }
+    }
+}
+
 impl ToQir<String> for rir::Instruction {
     fn to_qir(&self, program: &rir::Program) -> String {
         match self {
@@ -196,9 +282,31 @@ impl ToQir<String> for rir::Instruction {
             rir::Instruction::Icmp(op, lhs, rhs, variable) => {
                 icmp_to_qir(*op, lhs, rhs, *variable, program)
             }
+            rir::Instruction::Fadd(lhs, rhs, variable) => {
+                fbinop_to_qir("fadd", lhs, rhs, *variable, program)
+            }
+            rir::Instruction::Fsub(lhs, rhs, variable) => {
+                fbinop_to_qir("fsub", lhs, rhs, *variable, program)
+            }
+            rir::Instruction::Fmul(lhs, rhs, variable) => {
+                fbinop_to_qir("fmul", lhs, rhs, *variable, program)
+            }
+            rir::Instruction::Fdiv(lhs, rhs, variable) => {
+                fbinop_to_qir("fdiv", lhs, rhs, *variable, program)
+            }
+            rir::Instruction::Fcmp(op, lhs, rhs, variable) => {
+                fcmp_to_qir(*op, lhs, rhs, *variable, program)
+            }
+            rir::Instruction::Sitofp(value, variable) => {
+                convert_to_qir("sitofp", ("i64", "double"), value, *variable, program)
+            }
+            rir::Instruction::Fptosi(value, variable) => {
+                convert_to_qir("fptosi", ("double", "i64"), value, *variable, program)
+            }
             rir::Instruction::Jump(block_id) => {
                 format!("  br label %{}", ToQir::<String>::to_qir(block_id, program))
             }
+            rir::Instruction::Load(pointer, variable) => load_to_qir(pointer, *variable, program),
             rir::Instruction::Phi(args, variable) => phi_to_qir(args, *variable, program),
             rir::Instruction::Return => "  ret void".to_string(),
             rir::Instruction::Sdiv(lhs, rhs, variable) => {
@@ -339,6 +447,101 @@ fn icmp_to_qir(
     )
 }
 
+fn fcmp_to_qir(
+    op: FcmpConditionCode,
+    lhs: &rir::Operand,
+    rhs: &rir::Operand,
+    variable: rir::Variable,
+    program: &rir::Program,
+) -> String {
+    let lhs_ty = get_value_ty(lhs);
+    let rhs_ty = get_value_ty(rhs);
+    let var_ty = get_variable_ty(variable);
+    assert_eq!(
+        lhs_ty, rhs_ty,
+        "mismatched input types ({lhs_ty}, {rhs_ty}) for fcmp {op}"
+    );
+    assert_eq!(lhs_ty, "double", "unsupported input type {lhs_ty} for fcmp");
+
+    assert_eq!(var_ty, "i1", "unsupported output type {var_ty} for fcmp");
+    format!(
+        "  {} = fcmp {} {lhs_ty} {}, {}",
+        ToQir::<String>::to_qir(&variable.variable_id, program),
+        ToQir::<String>::to_qir(&op, program),
+        get_value_as_str(lhs, program),
+        get_value_as_str(rhs, program)
+    )
+}
+
+fn fbinop_to_qir(
+    op: &str,
+    lhs: &rir::Operand,
+    rhs: &rir::Operand,
+    variable: rir::Variable,
+    program: &rir::Program,
+) -> String {
+    let lhs_ty = get_value_ty(lhs);
+    let rhs_ty = get_value_ty(rhs);
+    let var_ty = get_variable_ty(variable);
+    assert_eq!(
+        lhs_ty, rhs_ty,
+        "mismatched input types ({lhs_ty}, {rhs_ty}) for {op}"
+    );
+    assert_eq!(
+        lhs_ty, var_ty,
+        "mismatched input/output types ({lhs_ty}, {var_ty}) for {op}"
+    );
+    assert_eq!(var_ty, "double", "unsupported type {var_ty} for {op}");
+
+    format!(
+        "  {} = {op} {var_ty} {}, {}",
+        ToQir::<String>::to_qir(&variable.variable_id, program),
+        get_value_as_str(lhs, program),
+        get_value_as_str(rhs, program)
+    )
+}
+
+fn convert_to_qir(
+    op: &str,
+    (expected_value_ty, expected_var_ty): (&str, &str),
+    value: &rir::Operand,
+    variable: rir::Variable,
+    program: &rir::Program,
+) -> String {
+    let value_ty = get_value_ty(value);
+    let var_ty = get_variable_ty(variable);
+    assert_eq!(
+        value_ty, expected_value_ty,
+        "unsupported input type {value_ty} for {op}"
+    );
+    assert_eq!(
+        var_ty, expected_var_ty,
+        "unsupported output type {var_ty} for {op}"
+    );
+
+    format!(
+        "  {} = {op} {value_ty} {} to {var_ty}",
+        ToQir::<String>::to_qir(&variable.variable_id, program),
+        get_value_as_str(value, program)
+    )
+}
+
+fn load_to_qir(pointer: &rir::Operand, variable: rir::Variable, program: &rir::Program) -> String {
+    let pointer_ty = get_value_ty(pointer);
+    assert_eq!(
+        pointer_ty, "i8*",
+        "unsupported input type {pointer_ty} for load"
+    );
+    let var_ty = get_variable_ty(variable);
+    let var_name = ToQir::<String>::to_qir(&variable.variable_id, program);
+
+    // Pointers are untyped in the RIR, so the pointer is cast to the type of the loaded value first.
+    format!(
+        "  {var_name}.ptr = bitcast i8* {} to {var_ty}*\n  {var_name} = load {var_ty}, {var_ty}* {var_name}.ptr",
+        get_value_as_str(pointer, program)
+    )
+}
+
 fn binop_to_qir(
     op: &str,
     lhs: &rir::Operand,
@@ -455,7 +658,7 @@ fn get_value_ty(lhs: &rir::Operand) -> &str {
         rir::Operand::Literal(lit) => match lit {
             rir::Literal::Integer(_) => "i64",
             rir::Literal::Bool(_) => "i1",
-            rir::Literal::Double(_) => "f64",
+            rir::Literal::Double(_) => "double",
             rir::Literal::Qubit(_) => "%Qubit*",
             rir::Literal::Result(_) => "%Result*",
             rir::Literal::Pointer => "i8*",
@@ -468,7 +671,7 @@ fn get_variable_ty(variable: rir::Variable) -> &'static str {
     match variable.ty {
         rir::Ty::Integer => "i64",
         rir::Ty::Boolean => "i1",
-        rir::Ty::Double => "f64",
+        rir::Ty::Double => "double",
         rir::Ty::Qubit => "%Qubit*",
         rir::Ty::Result => "%Result*",
         rir::Ty::Pointer => "i8*",
@@ -523,6 +726,16 @@ impl ToQir<String> for rir::Callable {
                 ToQir::<String>::to_qir(block, program)
             ));
         }
+        if !std::ptr::eq(self, program.get_callable(program.entry)) {
+            // Callables other than the entry point refer to their inputs through their parameters.
+            let params = self
+                .params
+                .iter()
+                .map(|param| ToQir::<String>::to_qir(param, program))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return format!("define {output_type} @{}({params}) {{\n{body}}}", self.name);
+        }
         assert!(
             input_type.is_empty(),
             "entry point should not have an input"
@@ -556,17 +769,19 @@ impl ToQir<String> for rir::Program {
 /// Create the module metadata for the given program.
 /// creating the `llvm.module.flags` and its associated values.
 fn get_module_metadata(program: &rir::Program) -> String {
-    let mut flags = String::new();
-
-    // push the default attrs, we don't have any config values
-    // for now that would change any of them.
-    flags.push_str(
+    // start with the default attrs, only dynamic qubit management depends
+    // on the config values.
+    let dynamic_qubit_management = program
+        .config
+        .capabilities
+        .contains(TargetCapabilityFlags::DynamicQubitManagement);
+    let mut flags = format!(
         r#"
-!0 = !{i32 1, !"qir_major_version", i32 1}
-!1 = !{i32 7, !"qir_minor_version", i32 0}
-!2 = !{i32 1, !"dynamic_qubit_management", i1 false}
-!3 = !{i32 1, !"dynamic_result_management", i1 false}
-"#,
+!0 = !{{i32 1, !"qir_major_version", i32 1}}
+!1 = !{{i32 7, !"qir_minor_version", i32 0}}
+!2 = !{{i32 1, !"dynamic_qubit_management", i1 {dynamic_qubit_management}}}
+!3 = !{{i32 1, !"dynamic_result_management", i1 false}}
+"#
     );
 
     let mut index = 4;
@@ -609,17 +824,22 @@ fn get_module_metadata(program: &rir::Program) -> String {
             index += 1;
         }
 
-        // Add the remaining extension capabilities as not supported.
-        // We can't generate these values yet so we just add them as false.
+        // Add the remaining extension capabilities.
+        // We can't generate fixed points or multiple target branches yet so we just add them as false, while user
+        // functions are used when callables other than the entry point have a body.
+        let has_user_functions = program
+            .callables
+            .iter()
+            .any(|(callable_id, callable)| callable_id != program.entry && callable.body.is_some());
         let unmapped_capabilities = [
-            "classical_fixed_points",
-            "user_functions",
-            "multiple_target_branching",
+            ("classical_fixed_points", false),
+            ("user_functions", has_user_functions),
+            ("multiple_target_branching", false),
         ];
-        for capability in unmapped_capabilities {
+        for (capability, value) in unmapped_capabilities {
             flags.push_str(&format!(
                 "!{} = !{{i32 {}, !\"{}\", i1 {}}}\n",
-                index, 1, capability, false
+                index, 1, capability, value
             ));
             index += 1;
         }
@@ -2,10 +2,11 @@
 // Licensed under the MIT License.
 
 use crate::qir::ToQir;
+use expect_test::expect;
 use qsc_rir::rir;
 
 #[test]
-#[should_panic(expected = "unsupported type f64 for add")]
+#[should_panic(expected = "unsupported type double for add")]
 fn add_double_literals() {
     let inst = rir::Instruction::Add(
         rir::Operand::Literal(rir::Literal::Double(core::f64::consts::PI)),
@@ -19,7 +20,7 @@ fn add_double_literals() {
 }
 
 #[test]
-#[should_panic(expected = "unsupported type f64 for ashr")]
+#[should_panic(expected = "unsupported type double for ashr")]
 fn ashr_double_literals() {
     let inst = rir::Instruction::Ashr(
         rir::Operand::Literal(rir::Literal::Double(core::f64::consts::PI)),
@@ -33,7 +34,7 @@ fn ashr_double_literals() {
 }
 
 #[test]
-#[should_panic(expected = "unsupported type f64 for and")]
+#[should_panic(expected = "unsupported type double for and")]
 fn bitwise_and_double_literals() {
     let inst = rir::Instruction::BitwiseAnd(
         rir::Operand::Literal(rir::Literal::Double(core::f64::consts::PI)),
@@ -47,7 +48,7 @@ fn bitwise_and_double_literals() {
 }
 
 #[test]
-#[should_panic(expected = "unsupported type f64 for not")]
+#[should_panic(expected = "unsupported type double for not")]
 fn bitwise_not_double_literals() {
     let inst = rir::Instruction::BitwiseNot(
         rir::Operand::Literal(rir::Literal::Double(core::f64::consts::PI)),
@@ -60,7 +61,7 @@ fn bitwise_not_double_literals() {
 }
 
 #[test]
-#[should_panic(expected = "unsupported type f64 for or")]
+#[should_panic(expected = "unsupported type double for or")]
 fn bitwise_or_double_literals() {
     let inst = rir::Instruction::BitwiseOr(
         rir::Operand::Literal(rir::Literal::Double(core::f64::consts::PI)),
@@ -74,7 +75,7 @@ fn bitwise_or_double_literals() {
 }
 
 #[test]
-#[should_panic(expected = "unsupported type f64 for xor")]
+#[should_panic(expected = "unsupported type double for xor")]
 fn bitwise_xor_double_literals() {
     let inst = rir::Instruction::BitwiseXor(
         rir::Operand::Literal(rir::Literal::Double(core::f64::consts::PI)),
@@ -86,3 +87,215 @@ fn bitwise_xor_double_literals() {
     );
     let _ = &inst.to_qir(&rir::Program::default());
 }
+
+#[test]
+fn fadd_double_literals() {
+    let inst = rir::Instruction::Fadd(
+        rir::Operand::Literal(rir::Literal::Double(2.0)),
+        rir::Operand::Literal(rir::Literal::Double(0.5)),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Double,
+        },
+    );
+    expect!["  %var_0 = fadd double 2.0, 0.5"].assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+fn fadd_double_variables() {
+    let inst = rir::Instruction::Fadd(
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(1),
+            ty: rir::Ty::Double,
+        }),
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(2),
+            ty: rir::Ty::Double,
+        }),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Double,
+        },
+    );
+    expect!["  %var_0 = fadd double %var_1, %var_2"]
+        .assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+fn fsub_double_variables() {
+    let inst = rir::Instruction::Fsub(
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(1),
+            ty: rir::Ty::Double,
+        }),
+        rir::Operand::Literal(rir::Literal::Double(1.5)),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Double,
+        },
+    );
+    expect!["  %var_0 = fsub double %var_1, 1.5"].assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+fn fmul_double_variables() {
+    let inst = rir::Instruction::Fmul(
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(1),
+            ty: rir::Ty::Double,
+        }),
+        rir::Operand::Literal(rir::Literal::Double(2.0)),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Double,
+        },
+    );
+    expect!["  %var_0 = fmul double %var_1, 2.0"].assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+fn fdiv_double_variables() {
+    let inst = rir::Instruction::Fdiv(
+        rir::Operand::Literal(rir::Literal::Double(1.0)),
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(1),
+            ty: rir::Ty::Double,
+        }),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Double,
+        },
+    );
+    expect!["  %var_0 = fdiv double 1.0, %var_1"].assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+fn fcmp_oeq_double_variables() {
+    let inst = rir::Instruction::Fcmp(
+        rir::FcmpConditionCode::OrderedAndEqual,
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(1),
+            ty: rir::Ty::Double,
+        }),
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(2),
+            ty: rir::Ty::Double,
+        }),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Boolean,
+        },
+    );
+    expect!["  %var_0 = fcmp oeq double %var_1, %var_2"]
+        .assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+fn fcmp_une_double_variables() {
+    let inst = rir::Instruction::Fcmp(
+        rir::FcmpConditionCode::UnorderedOrNotEqual,
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(1),
+            ty: rir::Ty::Double,
+        }),
+        rir::Operand::Literal(rir::Literal::Double(0.0)),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Boolean,
+        },
+    );
+    expect!["  %var_0 = fcmp une double %var_1, 0.0"]
+        .assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+fn fcmp_olt_double_variables() {
+    let inst = rir::Instruction::Fcmp(
+        rir::FcmpConditionCode::OrderedAndLessThan,
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(1),
+            ty: rir::Ty::Double,
+        }),
+        rir::Operand::Literal(rir::Literal::Double(0.5)),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Boolean,
+        },
+    );
+    expect!["  %var_0 = fcmp olt double %var_1, 0.5"]
+        .assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+#[should_panic(expected = "unsupported input type i64 for fcmp")]
+fn fcmp_integer_variables_should_panic() {
+    let inst = rir::Instruction::Fcmp(
+        rir::FcmpConditionCode::OrderedAndEqual,
+        rir::Operand::Literal(rir::Literal::Integer(1)),
+        rir::Operand::Literal(rir::Literal::Integer(2)),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Boolean,
+        },
+    );
+    let _ = &inst.to_qir(&rir::Program::default());
+}
+
+#[test]
+#[should_panic(expected = "unsupported type i64 for fadd")]
+fn fadd_integer_literals_should_panic() {
+    let inst = rir::Instruction::Fadd(
+        rir::Operand::Literal(rir::Literal::Integer(2)),
+        rir::Operand::Literal(rir::Literal::Integer(5)),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Integer,
+        },
+    );
+    let _ = &inst.to_qir(&rir::Program::default());
+}
+
+#[test]
+fn sitofp_integer_variable() {
+    let inst = rir::Instruction::Sitofp(
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(1),
+            ty: rir::Ty::Integer,
+        }),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Double,
+        },
+    );
+    expect!["  %var_0 = sitofp i64 %var_1 to double"]
+        .assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+fn fptosi_double_variable() {
+    let inst = rir::Instruction::Fptosi(
+        rir::Operand::Variable(rir::Variable {
+            variable_id: rir::VariableId(1),
+            ty: rir::Ty::Double,
+        }),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Integer,
+        },
+    );
+    expect!["  %var_0 = fptosi double %var_1 to i64"]
+        .assert_eq(&inst.to_qir(&rir::Program::default()));
+}
+
+#[test]
+#[should_panic(expected = "unsupported output type i64 for sitofp")]
+fn sitofp_integer_output_should_panic() {
+    let inst = rir::Instruction::Sitofp(
+        rir::Operand::Literal(rir::Literal::Integer(1)),
+        rir::Variable {
+            variable_id: rir::VariableId(0),
+            ty: rir::Ty::Integer,
+        },
+    );
+    let _ = &inst.to_qir(&rir::Program::default());
+}
@@ -0,0 +1,1410 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Reading of Base and Adaptive profile QIR programs, in LLVM textual form, into RIR programs.
+//! The reader supports the subset of LLVM used by those profiles: a single entry point function whose blocks contain
+//! calls to `__quantum__qis__*` and `__quantum__rt__*` functions, integer, boolean and floating-point computations,
+//! phi nodes and branches. Qubits and results must be typed pointers (`%Qubit*` and `%Result*`) with constant ids,
+//! except for qubits allocated at runtime by programs that use dynamic qubit management. The items of qubit arrays
+//! allocated at runtime are read with `bitcast` and `load`.
+
+#[cfg(test)]
+mod tests;
+
+use miette::Diagnostic;
+use qsc_data_structures::{span::Span, target::TargetCapabilityFlags};
+use qsc_rir::rir::{
+    Block, BlockId, Callable, CallableId, CallableType, ConditionCode, FcmpConditionCode,
+    Instruction, Literal, Operand, Program, Ty, Variable, VariableId,
+};
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+
+/// An error reading a QIR program.
+#[derive(Clone, Debug, Diagnostic, Eq, Error, PartialEq)]
+pub enum Error {
+    #[error("expected {0}, found {1}")]
+    #[diagnostic(code("Qsc.QirReader.Token"))]
+    Token(String, String, #[label] Span),
+
+    #[error("unsupported QIR construct: {0}")]
+    #[diagnostic(code("Qsc.QirReader.Unsupported"))]
+    #[diagnostic(help("only programs for the Base and Adaptive profiles of QIR can be read"))]
+    Unsupported(String, #[label] Span),
+
+    #[error("use of undefined {0} `{1}`")]
+    #[diagnostic(code("Qsc.QirReader.Undefined"))]
+    Undefined(&'static str, String, #[label] Span),
+
+    #[error("{0} `{1}` is defined more than once")]
+    #[diagnostic(code("Qsc.QirReader.Redefined"))]
+    Redefined(&'static str, String, #[label] Span),
+
+    #[error("`{0}` is used with type {1} but was previously used with type {2}")]
+    #[diagnostic(code("Qsc.QirReader.TypeMismatch"))]
+    TypeMismatch(String, String, String, #[label] Span),
+
+    #[error("the program does not define an entry point")]
+    #[diagnostic(code("Qsc.QirReader.MissingEntryPoint"))]
+    MissingEntryPoint,
+}
+
+/// Reads a QIR program in LLVM textual form into a RIR program.
+/// The entry point becomes the callable with id 0, and every function it calls becomes a callable without a body,
+/// numbered in the order in which they are first called.
+/// # Errors
+/// Returns an error if the input is not well-formed or uses constructs that are not supported by the Base and
+/// Adaptive profiles.
+pub fn read_qir(input: &str) -> Result<Program, Error> {
+    let mut reader = Reader::default();
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let tokens = tokenize(line, offset)?;
+        offset += line.len();
+        if !tokens.is_empty() {
+            let end = Span {
+                lo: to_u32(offset - line.len() + line.trim_end().len()),
+                hi: to_u32(offset - line.len() + line.trim_end().len()),
+            };
+            reader.line(&mut Line {
+                tokens,
+                pos: 0,
+                end,
+            })?;
+        }
+    }
+    reader.finish()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TokenKind<'a> {
+    /// A keyword, type or label name, such as `call`, `i64` or `block_0`.
+    Ident(&'a str),
+    /// A local name, such as `%var_0` or `%Qubit`.
+    Local(&'a str),
+    /// A global name, such as `@__quantum__qis__h__body`.
+    Global(&'a str),
+    /// An attribute group reference, such as `#0`.
+    AttrGroup(&'a str),
+    /// A metadata name, such as `!0` or `!llvm.module.flags`.
+    Meta(&'a str),
+    /// A metadata string, such as `!"qir_major_version"`.
+    MetaStr(&'a str),
+    /// A numeric literal.
+    Number(&'a str),
+    /// A string literal, without its quotes.
+    Str(&'a str),
+    Punct(char),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Token<'a> {
+    kind: TokenKind<'a>,
+    span: Span,
+}
+
+fn to_u32(offset: usize) -> u32 {
+    offset.try_into().expect("offset should fit into u32")
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$' | '-')
+}
+
+fn tokenize(line: &str, offset: usize) -> Result<Vec<Token<'_>>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((lo, c)) = chars.next() {
+        let take_name = |chars: &mut std::iter::Peekable<std::str::CharIndices>, start: usize| {
+            let mut end = start;
+            while let Some((i, c)) = chars.peek().copied() {
+                if !is_name_char(c) {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            end
+        };
+        let take_string = |chars: &mut std::iter::Peekable<std::str::CharIndices>| {
+            for (i, c) in chars.by_ref() {
+                if c == '"' {
+                    return Some(i);
+                }
+            }
+            None
+        };
+
+        let kind = match c {
+            ';' => break,
+            c if c.is_whitespace() => continue,
+            '%' | '@' | '#' | '!' => {
+                let start = lo + 1;
+                if c == '!' && chars.peek().is_some_and(|(_, c)| *c == '"') {
+                    chars.next();
+                    let Some(end) = take_string(&mut chars) else {
+                        return Err(unterminated_string(offset, lo, line.len()));
+                    };
+                    TokenKind::MetaStr(&line[start + 1..end])
+                } else {
+                    let end = take_name(&mut chars, start);
+                    let name = &line[start..end];
+                    match c {
+                        '%' => TokenKind::Local(name),
+                        '@' => TokenKind::Global(name),
+                        '#' => TokenKind::AttrGroup(name),
+                        _ if name.is_empty() => TokenKind::Punct('!'),
+                        _ => TokenKind::Meta(name),
+                    }
+                }
+            }
+            '"' => {
+                let Some(end) = take_string(&mut chars) else {
+                    return Err(unterminated_string(offset, lo, line.len()));
+                };
+                TokenKind::Str(&line[lo + 1..end])
+            }
+            'c' if chars.peek().is_some_and(|(_, c)| *c == '"') => {
+                chars.next();
+                let Some(end) = take_string(&mut chars) else {
+                    return Err(unterminated_string(offset, lo, line.len()));
+                };
+                TokenKind::Str(&line[lo + 2..end])
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                let mut end = take_name(&mut chars, lo + 1);
+                // Exponents may have a sign, as in `1.0e+00`.
+                while line[..end].ends_with(['e', 'E'])
+                    && chars.peek().is_some_and(|(_, c)| matches!(c, '+' | '-'))
+                {
+                    chars.next();
+                    end = take_name(&mut chars, end + 1);
+                }
+                TokenKind::Number(&line[lo..end])
+            }
+            c if is_name_char(c) => TokenKind::Ident(&line[lo..take_name(&mut chars, lo + 1)]),
+            c => TokenKind::Punct(c),
+        };
+        let hi = chars.peek().map_or(line.len(), |(i, _)| *i);
+        tokens.push(Token {
+            kind,
+            span: Span {
+                lo: to_u32(offset + lo),
+                hi: to_u32(offset + hi),
+            },
+        });
+    }
+    Ok(tokens)
+}
+
+fn unterminated_string(offset: usize, lo: usize, len: usize) -> Error {
+    Error::Token(
+        "`\"`".to_string(),
+        "end of line".to_string(),
+        Span {
+            lo: to_u32(offset + lo),
+            hi: to_u32(offset + len),
+        },
+    )
+}
+
+fn describe(token: Option<&Token>) -> String {
+    match token.map(|token| token.kind) {
+        None => "end of line".to_string(),
+        Some(TokenKind::Ident(name) | TokenKind::Number(name)) => format!("`{name}`"),
+        Some(TokenKind::Local(name)) => format!("`%{name}`"),
+        Some(TokenKind::Global(name)) => format!("`@{name}`"),
+        Some(TokenKind::AttrGroup(name)) => format!("`#{name}`"),
+        Some(TokenKind::Meta(name)) => format!("`!{name}`"),
+        Some(TokenKind::MetaStr(value)) => format!("`!\"{value}\"`"),
+        Some(TokenKind::Str(value)) => format!("`\"{value}\"`"),
+        Some(TokenKind::Punct(c)) => format!("`{c}`"),
+    }
+}
+
+/// The tokens of a single line, with a cursor.
+struct Line<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+    /// The span at the end of the line, used for errors about missing tokens.
+    end: Span,
+}
+
+impl<'a> Line<'a> {
+    fn peek(&self) -> Option<TokenKind<'a>> {
+        self.tokens.get(self.pos).map(|token| token.kind)
+    }
+
+    fn span(&self) -> Span {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.end, |token| token.span)
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.pos).copied();
+        self.pos += 1;
+        token
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        if self.peek() == Some(kind) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_ident(&mut self, ident: &str) -> bool {
+        self.eat(TokenKind::Ident(ident))
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<(), Error> {
+        if self.eat(kind) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&describe(Some(&Token {
+                kind,
+                span: Span::default(),
+            }))))
+        }
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<(), Error> {
+        self.expect(TokenKind::Punct(c))
+    }
+
+    fn expect_ident(&mut self, ident: &str) -> Result<(), Error> {
+        self.expect(TokenKind::Ident(ident))
+    }
+
+    fn expect_end(&self) -> Result<(), Error> {
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(self.unexpected("end of line"))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> Error {
+        Error::Token(
+            expected.to_string(),
+            describe(self.tokens.get(self.pos)),
+            self.span(),
+        )
+    }
+
+    fn number(&mut self, what: &str) -> Result<(&'a str, Span), Error> {
+        match self.peek() {
+            Some(TokenKind::Number(value)) => {
+                let span = self.span();
+                self.pos += 1;
+                Ok((value, span))
+            }
+            _ => Err(self.unexpected(what)),
+        }
+    }
+
+    /// Skips tokens up to and including the parenthesis that closes one that was already consumed.
+    fn skip_parenthesized(&mut self) -> Result<(), Error> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next().map(|token| token.kind) {
+                Some(TokenKind::Punct('(')) => depth += 1,
+                Some(TokenKind::Punct(')')) => depth -= 1,
+                Some(_) => {}
+                None => return Err(self.unexpected("`)`")),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A function declared in the program.
+struct Declaration {
+    input_type: Vec<Ty>,
+    output_type: Option<Ty>,
+    attr_group: Option<String>,
+}
+
+/// A name in the program, such as a variable or block label, with the span of its first use.
+struct Name<T> {
+    id: T,
+    span: Span,
+    defined: bool,
+}
+
+#[derive(Default)]
+struct Reader {
+    program: Program,
+    declarations: FxHashMap<String, Declaration>,
+    callables: FxHashMap<String, CallableId>,
+    attr_groups: FxHashMap<String, Vec<(String, Option<String>)>>,
+    module_flags: FxHashMap<String, bool>,
+    entry: Option<(String, Option<String>)>,
+    in_function: bool,
+    finished_function: bool,
+    current_block: Option<BlockId>,
+    blocks: FxHashMap<String, Name<BlockId>>,
+    variables: FxHashMap<String, Name<Variable>>,
+    /// The pointers cast to `%Qubit**` by `bitcast`, which are only used to load the qubits of arrays allocated at
+    /// runtime and so are not variables of their own.
+    qubit_pointers: FxHashMap<String, Operand>,
+    /// Calls are resolved once the whole program has been read, since functions may be declared after their use.
+    calls: Vec<PendingCall>,
+}
+
+struct PendingCall {
+    block_id: BlockId,
+    index: usize,
+    name: String,
+    span: Span,
+    args: Vec<Operand>,
+    output: Option<Variable>,
+}
+
+impl Reader {
+    fn line(&mut self, line: &mut Line) -> Result<(), Error> {
+        if self.in_function {
+            return self.function_line(line);
+        }
+
+        let span = line.span();
+        match line.peek() {
+            // Named types, such as `%Qubit = type opaque`, and globals, such as string constants used as output
+            // labels, only matter where they are used.
+            Some(
+                TokenKind::Ident("source_filename" | "target")
+                | TokenKind::Local(_)
+                | TokenKind::Global(_),
+            ) => Ok(()),
+            Some(TokenKind::Ident("declare")) => {
+                line.next();
+                self.declaration(line)
+            }
+            Some(TokenKind::Ident("define")) => {
+                line.next();
+                self.definition(line)
+            }
+            Some(TokenKind::Ident("attributes")) => {
+                line.next();
+                self.attribute_group(line)
+            }
+            Some(TokenKind::Meta(_)) => self.metadata(line),
+            _ => Err(Error::Unsupported(
+                format!(
+                    "top-level entity starting with {}",
+                    describe(line.tokens.first())
+                ),
+                span,
+            )),
+        }
+    }
+
+    fn declaration(&mut self, line: &mut Line) -> Result<(), Error> {
+        let output_type = ty(line, true)?;
+        let Some(Token {
+            kind: TokenKind::Global(name),
+            span,
+        }) = line.next()
+        else {
+            line.pos -= 1;
+            return Err(line.unexpected("function name"));
+        };
+        line.expect_punct('(')?;
+        let mut input_type = Vec::new();
+        while !line.eat(TokenKind::Punct(')')) {
+            if !input_type.is_empty() {
+                line.expect_punct(',')?;
+            }
+            input_type.push(ty(line, false)?.expect("parameter type should not be void"));
+            // Parameter attributes, such as `writeonly`, do not affect the program.
+            while matches!(line.peek(), Some(TokenKind::Ident(_))) {
+                line.next();
+            }
+        }
+        let attr_group = match line.peek() {
+            Some(TokenKind::AttrGroup(group)) => {
+                line.next();
+                Some(group.to_string())
+            }
+            _ => None,
+        };
+        line.expect_end()?;
+
+        if self.declarations.contains_key(name) {
+            return Err(Error::Redefined("function", name.to_string(), span));
+        }
+        self.declarations.insert(
+            name.to_string(),
+            Declaration {
+                input_type,
+                output_type,
+                attr_group,
+            },
+        );
+        Ok(())
+    }
+
+    fn definition(&mut self, line: &mut Line) -> Result<(), Error> {
+        let span = line.span();
+        if self.finished_function {
+            return Err(Error::Unsupported(
+                "definition of more than one function".to_string(),
+                span,
+            ));
+        }
+        // The entry point may return an exit code, which is ignored.
+        ty(line, true)?;
+        let Some(TokenKind::Global(name)) = line.next().map(|token| token.kind) else {
+            line.pos -= 1;
+            return Err(line.unexpected("function name"));
+        };
+        line.expect_punct('(')?;
+        if !line.eat(TokenKind::Punct(')')) {
+            return Err(Error::Unsupported(
+                "entry point with parameters".to_string(),
+                line.span(),
+            ));
+        }
+        let attr_group = match line.peek() {
+            Some(TokenKind::AttrGroup(group)) => {
+                line.next();
+                Some(group.to_string())
+            }
+            _ => None,
+        };
+        line.expect_punct('{')?;
+        line.expect_end()?;
+
+        self.entry = Some((name.to_string(), attr_group));
+        self.in_function = true;
+        Ok(())
+    }
+
+    fn attribute_group(&mut self, line: &mut Line) -> Result<(), Error> {
+        let Some(TokenKind::AttrGroup(group)) = line.peek() else {
+            return Err(line.unexpected("attribute group"));
+        };
+        line.next();
+        line.expect_punct('=')?;
+        line.expect_punct('{')?;
+        let mut attrs = Vec::new();
+        while !line.eat(TokenKind::Punct('}')) {
+            match line.next().map(|token| token.kind) {
+                Some(TokenKind::Str(key)) => {
+                    let value = if line.eat(TokenKind::Punct('=')) {
+                        let Some(TokenKind::Str(value)) = line.next().map(|token| token.kind)
+                        else {
+                            line.pos -= 1;
+                            return Err(line.unexpected("attribute value"));
+                        };
+                        Some(value.to_string())
+                    } else {
+                        None
+                    };
+                    attrs.push((key.to_string(), value));
+                }
+                Some(TokenKind::Ident(key)) => attrs.push((key.to_string(), None)),
+                _ => {
+                    line.pos -= 1;
+                    return Err(line.unexpected("attribute"));
+                }
+            }
+        }
+        line.expect_end()?;
+        self.attr_groups.insert(group.to_string(), attrs);
+        Ok(())
+    }
+
+    /// Reads a metadata node. Only module flags of the form `!{i32 <behavior>, !"<name>", i1 <value>}` are used,
+    /// to determine the capabilities of the program.
+    fn metadata(&mut self, line: &mut Line) -> Result<(), Error> {
+        line.next();
+        line.expect_punct('=')?;
+        line.eat_ident("distinct");
+        line.expect_punct('!')?;
+        line.expect_punct('{')?;
+        let items = &line.tokens[line.pos..];
+        if let [Token {
+            kind: TokenKind::Ident("i32"),
+            ..
+        }, Token {
+            kind: TokenKind::Number(_),
+            ..
+        }, Token {
+            kind: TokenKind::Punct(','),
+            ..
+        }, Token {
+            kind: TokenKind::MetaStr(name),
+            ..
+        }, Token {
+            kind: TokenKind::Punct(','),
+            ..
+        }, Token {
+            kind: TokenKind::Ident("i1"),
+            ..
+        }, Token {
+            kind: TokenKind::Ident(value @ ("true" | "false")),
+            ..
+        }, Token {
+            kind: TokenKind::Punct('}'),
+            ..
+        }] = items
+        {
+            self.module_flags
+                .insert((*name).to_string(), *value == "true");
+        }
+        Ok(())
+    }
+
+    fn function_line(&mut self, line: &mut Line) -> Result<(), Error> {
+        if line.eat(TokenKind::Punct('}')) {
+            line.expect_end()?;
+            self.in_function = false;
+            self.finished_function = true;
+            return Ok(());
+        }
+
+        // A label starts a new block.
+        if let (
+            Some(Token {
+                kind: TokenKind::Ident(label) | TokenKind::Number(label),
+                span,
+            }),
+            Some(Token {
+                kind: TokenKind::Punct(':'),
+                ..
+            }),
+        ) = (line.tokens.first().copied(), line.tokens.get(1))
+        {
+            line.pos = 2;
+            line.expect_end()?;
+            let block_id = self.block_id(label, span);
+            let block = self.blocks.get_mut(label).expect("block should be named");
+            if block.defined {
+                return Err(Error::Redefined("block", label.to_string(), span));
+            }
+            block.defined = true;
+            self.current_block = Some(block_id);
+            self.program.blocks.insert(block_id, Block::default());
+            return Ok(());
+        }
+
+        let block_id = if let Some(block_id) = self.current_block {
+            block_id
+        } else {
+            // The entry block does not need a label.
+            let block_id = self.block_id("", line.span());
+            self.blocks
+                .get_mut("")
+                .expect("block should be named")
+                .defined = true;
+            self.program.blocks.insert(block_id, Block::default());
+            self.current_block = Some(block_id);
+            block_id
+        };
+        if let Some(instr) = self.instruction(line, block_id)? {
+            self.program.get_block_mut(block_id).0.push(instr);
+        }
+        Ok(())
+    }
+
+    fn block_id(&mut self, label: &str, span: Span) -> BlockId {
+        let next_id = BlockId(to_u32(self.blocks.len()));
+        self.blocks
+            .entry(label.to_string())
+            .or_insert(Name {
+                id: next_id,
+                span,
+                defined: false,
+            })
+            .id
+    }
+
+    fn label(&mut self, line: &mut Line) -> Result<BlockId, Error> {
+        line.expect_ident("label")?;
+        let Some(Token {
+            kind: TokenKind::Local(label),
+            span,
+        }) = line.next()
+        else {
+            line.pos -= 1;
+            return Err(line.unexpected("block label"));
+        };
+        Ok(self.block_id(label, span))
+    }
+
+    fn variable(&mut self, name: &str, ty: Ty, span: Span) -> Result<Variable, Error> {
+        let next_id = VariableId(to_u32(self.variables.len()));
+        let variable = self
+            .variables
+            .entry(name.to_string())
+            .or_insert(Name {
+                id: Variable {
+                    variable_id: next_id,
+                    ty,
+                },
+                span,
+                defined: false,
+            })
+            .id;
+        if variable.ty == ty {
+            Ok(variable)
+        } else {
+            Err(Error::TypeMismatch(
+                format!("%{name}"),
+                ty_name(ty).to_string(),
+                ty_name(variable.ty).to_string(),
+                span,
+            ))
+        }
+    }
+
+    fn define_variable(&mut self, name: &str, ty: Ty, span: Span) -> Result<Variable, Error> {
+        let variable = self.variable(name, ty, span)?;
+        let entry = self
+            .variables
+            .get_mut(name)
+            .expect("variable should be named");
+        if entry.defined {
+            return Err(Error::Redefined("variable", format!("%{name}"), span));
+        }
+        entry.defined = true;
+        Ok(variable)
+    }
+
+    /// Reads a value of the given type, such as `%var_0`, `5` or `inttoptr (i64 1 to %Qubit*)`.
+    fn value(&mut self, line: &mut Line, ty: Ty) -> Result<Operand, Error> {
+        let span = line.span();
+        if let Some(TokenKind::Local(name)) = line.peek() {
+            line.next();
+            return Ok(Operand::Variable(self.variable(name, ty, span)?));
+        }
+        let literal = match (line.peek(), ty) {
+            (Some(TokenKind::Ident(value @ ("true" | "false"))), Ty::Boolean) => {
+                line.next();
+                Literal::Bool(value == "true")
+            }
+            (Some(TokenKind::Number(value)), Ty::Integer) => {
+                line.next();
+                let value = value
+                    .parse()
+                    .map_err(|_| Error::Token("integer".to_string(), format!("`{value}`"), span))?;
+                Literal::Integer(value)
+            }
+            (Some(TokenKind::Number(value)), Ty::Double) => {
+                line.next();
+                Literal::Double(parse_double(value).ok_or_else(|| {
+                    Error::Token("double".to_string(), format!("`{value}`"), span)
+                })?)
+            }
+            (Some(TokenKind::Ident("null")), Ty::Qubit) => {
+                line.next();
+                Literal::Qubit(0)
+            }
+            (Some(TokenKind::Ident("null")), Ty::Result) => {
+                line.next();
+                Literal::Result(0)
+            }
+            (Some(TokenKind::Ident("inttoptr")), Ty::Qubit | Ty::Result) => {
+                line.next();
+                line.expect_punct('(')?;
+                line.expect_ident("i64")?;
+                let (id, id_span) = line.number("integer")?;
+                let id = id.parse().map_err(|_| {
+                    let expected = "qubit or result id".to_string();
+                    Error::Token(expected, format!("`{id}`"), id_span)
+                })?;
+                line.expect_ident("to")?;
+                if ty_or_void(line)? != Some(ty) {
+                    return Err(Error::Token(
+                        ty_name(ty).to_string(),
+                        "another type".to_string(),
+                        span,
+                    ));
+                }
+                line.expect_punct(')')?;
+                if ty == Ty::Qubit {
+                    Literal::Qubit(id)
+                } else {
+                    Literal::Result(id)
+                }
+            }
+            // Pointers to constant strings are output labels, which are not part of the RIR.
+            (Some(TokenKind::Ident("getelementptr" | "bitcast")), Ty::Pointer) => {
+                line.next();
+                line.eat_ident("inbounds");
+                line.expect_punct('(')?;
+                line.skip_parenthesized()?;
+                Literal::Pointer
+            }
+            (Some(TokenKind::Ident("null") | TokenKind::Global(_)), Ty::Pointer) => {
+                line.next();
+                Literal::Pointer
+            }
+            _ => {
+                let expected = format!("value of type {}", ty_name(ty));
+                return Err(line.unexpected(&expected));
+            }
+        };
+        Ok(Operand::Literal(literal))
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn instruction(
+        &mut self,
+        line: &mut Line,
+        block_id: BlockId,
+    ) -> Result<Option<Instruction>, Error> {
+        let result = match line.peek() {
+            Some(TokenKind::Local(name)) => {
+                let span = line.span();
+                line.next();
+                line.expect_punct('=')?;
+                Some((name, span))
+            }
+            _ => None,
+        };
+
+        let op_span = line.span();
+        let Some(TokenKind::Ident(op)) = line.next().map(|token| token.kind) else {
+            line.pos -= 1;
+            return Err(line.unexpected("instruction"));
+        };
+        let needs_result = !matches!(op, "call" | "tail" | "musttail" | "notail" | "br" | "ret");
+        if needs_result && result.is_none() {
+            return Err(Error::Unsupported(format!("instruction `{op}`"), op_span));
+        }
+
+        let instr = match op {
+            "ret" => {
+                // The exit code of the entry point, if any, is ignored.
+                if let Some(ty) = ty_or_void(line)? {
+                    self.value(line, ty)?;
+                }
+                Instruction::Return
+            }
+            "br" => {
+                if line.peek() == Some(TokenKind::Ident("label")) {
+                    Instruction::Jump(self.label(line)?)
+                } else {
+                    line.expect_ident("i1")?;
+                    let span = line.span();
+                    let Operand::Variable(cond) = self.value(line, Ty::Boolean)? else {
+                        return Err(Error::Unsupported(
+                            "branch on a constant condition".to_string(),
+                            span,
+                        ));
+                    };
+                    line.expect_punct(',')?;
+                    let if_true = self.label(line)?;
+                    line.expect_punct(',')?;
+                    let if_false = self.label(line)?;
+                    Instruction::Branch(cond, if_true, if_false)
+                }
+            }
+            "call" | "tail" | "musttail" | "notail" => {
+                if op != "call" {
+                    line.expect_ident("call")?;
+                }
+                return self.call(line, block_id, result).map(|()| None);
+            }
+            "add" | "sub" | "mul" | "sdiv" | "srem" | "shl" | "ashr" | "and" | "or" | "xor" => {
+                // Flags such as `nsw` do not change the result of well-defined programs.
+                while matches!(line.peek(), Some(TokenKind::Ident("nsw" | "nuw" | "exact"))) {
+                    line.next();
+                }
+                let ty = ty(line, false)?.expect("operand type should not be void");
+                let lhs = self.value(line, ty)?;
+                line.expect_punct(',')?;
+                let rhs = self.value(line, ty)?;
+                let (name, span) = result.expect("instruction should have a result");
+                let var = self.define_variable(name, ty, span)?;
+                match (op, ty) {
+                    ("add", Ty::Integer) => Instruction::Add(lhs, rhs, var),
+                    ("sub", Ty::Integer) => Instruction::Sub(lhs, rhs, var),
+                    ("mul", Ty::Integer) => Instruction::Mul(lhs, rhs, var),
+                    ("sdiv", Ty::Integer) => Instruction::Sdiv(lhs, rhs, var),
+                    ("srem", Ty::Integer) => Instruction::Srem(lhs, rhs, var),
+                    ("shl", Ty::Integer) => Instruction::Shl(lhs, rhs, var),
+                    ("ashr", Ty::Integer) => Instruction::Ashr(lhs, rhs, var),
+                    ("and", Ty::Integer) => Instruction::BitwiseAnd(lhs, rhs, var),
+                    ("or", Ty::Integer) => Instruction::BitwiseOr(lhs, rhs, var),
+                    ("xor", Ty::Integer) if rhs == Operand::Literal(Literal::Integer(-1)) => {
+                        Instruction::BitwiseNot(lhs, var)
+                    }
+                    ("xor", Ty::Integer) => Instruction::BitwiseXor(lhs, rhs, var),
+                    ("and", Ty::Boolean) => Instruction::LogicalAnd(lhs, rhs, var),
+                    ("or", Ty::Boolean) => Instruction::LogicalOr(lhs, rhs, var),
+                    ("xor", Ty::Boolean) if rhs == Operand::Literal(Literal::Bool(true)) => {
+                        Instruction::LogicalNot(lhs, var)
+                    }
+                    ("xor", Ty::Boolean) => Instruction::Icmp(ConditionCode::Ne, lhs, rhs, var),
+                    _ => {
+                        return Err(Error::Unsupported(
+                            format!("instruction `{op}` on type {}", ty_name(ty)),
+                            op_span,
+                        ))
+                    }
+                }
+            }
+            "fadd" | "fsub" | "fmul" | "fdiv" => {
+                skip_fast_math_flags(line);
+                let ty = ty(line, false)?.expect("operand type should not be void");
+                if ty != Ty::Double {
+                    return Err(Error::Unsupported(
+                        format!("instruction `{op}` on type {}", ty_name(ty)),
+                        op_span,
+                    ));
+                }
+                let lhs = self.value(line, ty)?;
+                line.expect_punct(',')?;
+                let rhs = self.value(line, ty)?;
+                let (name, span) = result.expect("instruction should have a result");
+                let var = self.define_variable(name, ty, span)?;
+                match op {
+                    "fadd" => Instruction::Fadd(lhs, rhs, var),
+                    "fsub" => Instruction::Fsub(lhs, rhs, var),
+                    "fmul" => Instruction::Fmul(lhs, rhs, var),
+                    _ => Instruction::Fdiv(lhs, rhs, var),
+                }
+            }
+            "icmp" | "fcmp" => {
+                skip_fast_math_flags(line);
+                let cond_span = line.span();
+                let Some(TokenKind::Ident(cond)) = line.next().map(|token| token.kind) else {
+                    line.pos -= 1;
+                    return Err(line.unexpected("condition code"));
+                };
+                let ty = ty(line, false)?.expect("operand type should not be void");
+                let lhs = self.value(line, ty)?;
+                line.expect_punct(',')?;
+                let rhs = self.value(line, ty)?;
+                let (name, span) = result.expect("instruction should have a result");
+                let var = self.define_variable(name, Ty::Boolean, span)?;
+                if op == "icmp" && matches!(ty, Ty::Integer | Ty::Boolean) {
+                    let cond = condition_code(cond).ok_or_else(|| {
+                        Error::Unsupported(format!("integer comparison `{cond}`"), cond_span)
+                    })?;
+                    Instruction::Icmp(cond, lhs, rhs, var)
+                } else if op == "fcmp" && ty == Ty::Double {
+                    let cond = fcmp_condition_code(cond).ok_or_else(|| {
+                        Error::Token("condition code".to_string(), format!("`{cond}`"), cond_span)
+                    })?;
+                    Instruction::Fcmp(cond, lhs, rhs, var)
+                } else {
+                    return Err(Error::Unsupported(
+                        format!("instruction `{op}` on type {}", ty_name(ty)),
+                        op_span,
+                    ));
+                }
+            }
+            "sitofp" | "fptosi" => {
+                let (in_ty, out_ty) = if op == "sitofp" {
+                    (Ty::Integer, Ty::Double)
+                } else {
+                    (Ty::Double, Ty::Integer)
+                };
+                let ty_span = line.span();
+                if ty(line, false)? != Some(in_ty) {
+                    return Err(Error::Unsupported(
+                        format!(
+                            "instruction `{op}` from a type other than {}",
+                            ty_name(in_ty)
+                        ),
+                        ty_span,
+                    ));
+                }
+                let value = self.value(line, in_ty)?;
+                line.expect_ident("to")?;
+                let ty_span = line.span();
+                if ty(line, false)? != Some(out_ty) {
+                    return Err(Error::Unsupported(
+                        format!(
+                            "instruction `{op}` to a type other than {}",
+                            ty_name(out_ty)
+                        ),
+                        ty_span,
+                    ));
+                }
+                let (name, span) = result.expect("instruction should have a result");
+                let var = self.define_variable(name, out_ty, span)?;
+                if op == "sitofp" {
+                    Instruction::Sitofp(value, var)
+                } else {
+                    Instruction::Fptosi(value, var)
+                }
+            }
+            "bitcast" => {
+                let ty_span = line.span();
+                if ty(line, false)? != Some(Ty::Pointer) {
+                    return Err(Error::Unsupported(
+                        "instruction `bitcast` from a type other than i8*".to_string(),
+                        ty_span,
+                    ));
+                }
+                let value = self.value(line, Ty::Pointer)?;
+                line.expect_ident("to")?;
+                qubit_pointer_ty(line, op)?;
+                line.expect_end()?;
+                let (name, span) = result.expect("instruction should have a result");
+                if self.variables.contains_key(name)
+                    || self
+                        .qubit_pointers
+                        .insert(name.to_string(), value)
+                        .is_some()
+                {
+                    return Err(Error::Redefined("variable", format!("%{name}"), span));
+                }
+                return Ok(None);
+            }
+            "load" => {
+                let ty_span = line.span();
+                if ty(line, false)? != Some(Ty::Qubit) {
+                    return Err(Error::Unsupported(
+                        "instruction `load` of a type other than %Qubit*".to_string(),
+                        ty_span,
+                    ));
+                }
+                line.expect_punct(',')?;
+                qubit_pointer_ty(line, op)?;
+                let Some(Token {
+                    kind: TokenKind::Local(pointer_name),
+                    span: pointer_span,
+                }) = line.next()
+                else {
+                    line.pos -= 1;
+                    return Err(line.unexpected("pointer to a qubit"));
+                };
+                let Some(pointer) = self.qubit_pointers.get(pointer_name).copied() else {
+                    return Err(if self.variables.contains_key(pointer_name) {
+                        Error::Unsupported(
+                            "instruction `load` from a pointer that is not cast to %Qubit**"
+                                .to_string(),
+                            pointer_span,
+                        )
+                    } else {
+                        Error::Undefined("variable", format!("%{pointer_name}"), pointer_span)
+                    });
+                };
+                // The alignment of the load does not change its meaning.
+                if line.eat(TokenKind::Punct(',')) {
+                    line.expect_ident("align")?;
+                    line.number("alignment")?;
+                }
+                let (name, span) = result.expect("instruction should have a result");
+                Instruction::Load(pointer, self.define_variable(name, Ty::Qubit, span)?)
+            }
+            "phi" => {
+                skip_fast_math_flags(line);
+                let ty = ty(line, false)?.expect("phi type should not be void");
+                let mut args = Vec::new();
+                loop {
+                    line.expect_punct('[')?;
+                    let value = self.value(line, ty)?;
+                    line.expect_punct(',')?;
+                    let Some(Token {
+                        kind: TokenKind::Local(label),
+                        span,
+                    }) = line.next()
+                    else {
+                        line.pos -= 1;
+                        return Err(line.unexpected("block label"));
+                    };
+                    args.push((value, self.block_id(label, span)));
+                    line.expect_punct(']')?;
+                    if !line.eat(TokenKind::Punct(',')) {
+                        break;
+                    }
+                }
+                let (name, span) = result.expect("instruction should have a result");
+                Instruction::Phi(args, self.define_variable(name, ty, span)?)
+            }
+            _ => return Err(Error::Unsupported(format!("instruction `{op}`"), op_span)),
+        };
+        if result.is_some() && matches!(instr, Instruction::Jump(_) | Instruction::Branch(..)) {
+            return Err(Error::Unsupported(
+                "result of a terminator".to_string(),
+                op_span,
+            ));
+        }
+        line.expect_end()?;
+        Ok(Some(instr))
+    }
+
+    /// Reads a call, which is added to the block once the called function is resolved.
+    fn call(
+        &mut self,
+        line: &mut Line,
+        block_id: BlockId,
+        result: Option<(&str, Span)>,
+    ) -> Result<(), Error> {
+        let output_type = ty(line, true)?;
+        let Some(Token {
+            kind: TokenKind::Global(name),
+            span,
+        }) = line.next()
+        else {
+            line.pos -= 1;
+            return Err(line.unexpected("function name"));
+        };
+        if !name.starts_with("__quantum__qis__") && !name.starts_with("__quantum__rt__") {
+            return Err(Error::Unsupported(
+                format!("call to function `{name}`"),
+                span,
+            ));
+        }
+        line.expect_punct('(')?;
+        let mut args = Vec::new();
+        while !line.eat(TokenKind::Punct(')')) {
+            if !args.is_empty() {
+                line.expect_punct(',')?;
+            }
+            let ty = ty(line, false)?.expect("argument type should not be void");
+            while matches!(line.peek(), Some(TokenKind::Ident(attr)) if is_param_attr(attr)) {
+                line.next();
+            }
+            args.push(self.value(line, ty)?);
+        }
+        // Attributes of the call site do not change its meaning.
+        while matches!(line.peek(), Some(TokenKind::AttrGroup(_))) {
+            line.next();
+        }
+        line.expect_end()?;
+
+        let output = match (result, output_type) {
+            (Some((var_name, var_span)), Some(ty)) => {
+                Some(self.define_variable(var_name, ty, var_span)?)
+            }
+            (Some((_, var_span)), None) => {
+                return Err(Error::Unsupported(
+                    "result of a call to a void function".to_string(),
+                    var_span,
+                ))
+            }
+            (None, _) => None,
+        };
+        let block = self.program.get_block_mut(block_id);
+        self.calls.push(PendingCall {
+            block_id,
+            index: block.0.len(),
+            name: name.to_string(),
+            span,
+            args,
+            output,
+        });
+        // The callable is filled in once it has been resolved.
+        block
+            .0
+            .push(Instruction::Call(CallableId(0), Vec::new(), None));
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Program, Error> {
+        let Some((entry_name, entry_attrs)) = self.entry.take() else {
+            return Err(Error::MissingEntryPoint);
+        };
+        if self.in_function {
+            return Err(Error::Token(
+                "`}`".to_string(),
+                "end of input".to_string(),
+                Span::default(),
+            ));
+        }
+        if let Some((label, block)) = self.blocks.iter().find(|(_, block)| !block.defined) {
+            return Err(Error::Undefined("block", format!("%{label}"), block.span));
+        }
+        if let Some((name, variable)) = self.variables.iter().find(|(_, var)| !var.defined) {
+            return Err(Error::Undefined(
+                "variable",
+                format!("%{name}"),
+                variable.span,
+            ));
+        }
+        if self.program.blocks.iter().next().is_none() {
+            return Err(Error::Unsupported(
+                "entry point without a body".to_string(),
+                Span::default(),
+            ));
+        }
+
+        self.program.entry = CallableId(0);
+        self.program.callables.insert(
+            CallableId(0),
+            Callable {
+                name: entry_name,
+                input_type: Vec::new(),
+                params: Vec::new(),
+                output_type: None,
+                body: Some(BlockId(0)),
+                call_type: CallableType::Regular,
+            },
+        );
+        for call in std::mem::take(&mut self.calls) {
+            let callable_id = self.callable_id(&call.name, call.span)?;
+            self.program.get_block_mut(call.block_id).0[call.index] =
+                Instruction::Call(callable_id, call.args, call.output);
+        }
+
+        let entry_attrs = entry_attrs
+            .and_then(|group| self.attr_groups.get(&group))
+            .cloned()
+            .unwrap_or_default();
+        let get_attr = |key: &str| {
+            entry_attrs
+                .iter()
+                .find(|(k, _)| k == key)
+                .and_then(|(_, value)| value.as_deref())
+        };
+        self.program.config.capabilities = match get_attr("qir_profiles") {
+            Some("base_profile") => TargetCapabilityFlags::empty(),
+            Some("adaptive_profile") | None => self.adaptive_capabilities(),
+            Some(profile) => {
+                return Err(Error::Unsupported(
+                    format!("profile `{profile}`"),
+                    Span::default(),
+                ))
+            }
+        };
+        let (max_qubit, max_result) = get_max_ids(&self.program);
+        self.program.num_qubits = get_attr("required_num_qubits")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(max_qubit);
+        self.program.num_results = get_attr("required_num_results")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(max_result);
+        Ok(self.program)
+    }
+
+    fn adaptive_capabilities(&self) -> TargetCapabilityFlags {
+        let mut capabilities = TargetCapabilityFlags::Adaptive;
+        for (flag, capability) in [
+            ("classical_ints", TargetCapabilityFlags::IntegerComputations),
+            (
+                "classical_floats",
+                TargetCapabilityFlags::FloatingPointComputations,
+            ),
+            (
+                "backwards_branching",
+                TargetCapabilityFlags::BackwardsBranching,
+            ),
+            ("qubit_resetting", TargetCapabilityFlags::QubitReset),
+            (
+                "dynamic_qubit_management",
+                TargetCapabilityFlags::DynamicQubitManagement,
+            ),
+        ] {
+            if self.module_flags.get(flag).copied().unwrap_or_default() {
+                capabilities |= capability;
+            }
+        }
+        capabilities
+    }
+
+    fn callable_id(&mut self, name: &str, span: Span) -> Result<CallableId, Error> {
+        if let Some(id) = self.callables.get(name) {
+            return Ok(*id);
+        }
+        let Some(declaration) = self.declarations.get(name) else {
+            return Err(Error::Undefined("function", format!("@{name}"), span));
+        };
+        let irreversible = declaration
+            .attr_group
+            .as_ref()
+            .and_then(|group| self.attr_groups.get(group))
+            .is_some_and(|attrs| attrs.iter().any(|(key, _)| key == "irreversible"));
+        let call_type = match name {
+            "__quantum__qis__reset__body" => CallableType::Reset,
+            "__quantum__qis__read_result__body" | "__quantum__rt__read_result" => {
+                CallableType::Readout
+            }
+            "__quantum__qis__m__body"
+            | "__quantum__qis__mz__body"
+            | "__quantum__qis__mresetz__body" => CallableType::Measurement,
+            _ if irreversible => CallableType::Measurement,
+            _ if name.starts_with("__quantum__rt__") && name.ends_with("_record_output") => {
+                CallableType::OutputRecording
+            }
+            _ => CallableType::Regular,
+        };
+        let id = CallableId(to_u32(self.callables.len() + 1));
+        self.program.callables.insert(
+            id,
+            Callable {
+                name: name.to_string(),
+                input_type: declaration.input_type.clone(),
+                params: Vec::new(),
+                output_type: declaration.output_type,
+                body: None,
+                call_type,
+            },
+        );
+        self.callables.insert(name.to_string(), id);
+        Ok(id)
+    }
+}
+
+/// Returns one more than the highest qubit and result ids used in the program.
+fn get_max_ids(program: &Program) -> (u32, u32) {
+    let mut max_qubit = 0;
+    let mut max_result = 0;
+    for block in program.blocks.values() {
+        for instr in &block.0 {
+            if let Instruction::Call(_, args, _) = instr {
+                for arg in args {
+                    match arg {
+                        Operand::Literal(Literal::Qubit(id)) => max_qubit = max_qubit.max(id + 1),
+                        Operand::Literal(Literal::Result(id)) => {
+                            max_result = max_result.max(id + 1);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    (max_qubit, max_result)
+}
+
+fn skip_fast_math_flags(line: &mut Line) {
+    while matches!(
+        line.peek(),
+        Some(TokenKind::Ident(
+            "fast" | "nnan" | "ninf" | "nsz" | "arcp" | "contract" | "afn" | "reassoc"
+        ))
+    ) {
+        line.next();
+    }
+}
+
+fn is_param_attr(attr: &str) -> bool {
+    matches!(
+        attr,
+        "writeonly" | "readonly" | "nocapture" | "noundef" | "nonnull" | "signext" | "zeroext"
+    )
+}
+
+/// Reads a type, which may be `void` if allowed.
+fn ty(line: &mut Line, allow_void: bool) -> Result<Option<Ty>, Error> {
+    let span = line.span();
+    let ty = ty_or_void(line)?;
+    if ty.is_none() && !allow_void {
+        return Err(Error::Token("type".to_string(), "`void`".to_string(), span));
+    }
+    Ok(ty)
+}
+
+fn ty_or_void(line: &mut Line) -> Result<Option<Ty>, Error> {
+    let span = line.span();
+    let ty = match line.next().map(|token| token.kind) {
+        Some(TokenKind::Ident("void")) => return Ok(None),
+        Some(TokenKind::Ident("i1")) => Ty::Boolean,
+        Some(TokenKind::Ident("i64")) => Ty::Integer,
+        Some(TokenKind::Ident("double")) => Ty::Double,
+        Some(TokenKind::Ident("i8")) => {
+            line.expect_punct('*')?;
+            Ty::Pointer
+        }
+        Some(TokenKind::Local("Qubit")) => {
+            line.expect_punct('*')?;
+            Ty::Qubit
+        }
+        Some(TokenKind::Local("Result")) => {
+            line.expect_punct('*')?;
+            Ty::Result
+        }
+        Some(TokenKind::Ident("ptr")) => {
+            return Err(Error::Unsupported(
+                "opaque pointer type `ptr`".to_string(),
+                span,
+            ))
+        }
+        Some(TokenKind::Ident(name)) if name.starts_with('i') || name == "float" => {
+            return Err(Error::Unsupported(format!("type `{name}`"), span))
+        }
+        Some(TokenKind::Local(name)) => {
+            return Err(Error::Unsupported(format!("type `%{name}`"), span))
+        }
+        _ => {
+            line.pos -= 1;
+            return Err(line.unexpected("type"));
+        }
+    };
+    Ok(Some(ty))
+}
+
+/// Reads the `%Qubit**` type, the only pointer type other than `i8*` that is supported, as the operand of the given
+/// instruction.
+fn qubit_pointer_ty(line: &mut Line, op: &str) -> Result<(), Error> {
+    let span = line.span();
+    if line.eat(TokenKind::Local("Qubit"))
+        && line.eat(TokenKind::Punct('*'))
+        && line.eat(TokenKind::Punct('*'))
+    {
+        Ok(())
+    } else {
+        Err(Error::Unsupported(
+            format!("instruction `{op}` with a pointer type other than %Qubit**"),
+            span,
+        ))
+    }
+}
+
+fn ty_name(ty: Ty) -> &'static str {
+    match ty {
+        Ty::Boolean => "i1",
+        Ty::Integer => "i64",
+        Ty::Double => "double",
+        Ty::Qubit => "%Qubit*",
+        Ty::Result => "%Result*",
+        Ty::Pointer => "i8*",
+    }
+}
+
+/// Parses a floating-point literal, which LLVM writes either in decimal or as the hexadecimal bits of the value.
+fn parse_double(value: &str) -> Option<f64> {
+    match value.strip_prefix("0x") {
+        Some(bits) => u64::from_str_radix(bits, 16).ok().map(f64::from_bits),
+        None => value.parse().ok(),
+    }
+}
+
+fn condition_code(cond: &str) -> Option<ConditionCode> {
+    Some(match cond {
+        "eq" => ConditionCode::Eq,
+        "ne" => ConditionCode::Ne,
+        "slt" => ConditionCode::Slt,
+        "sle" => ConditionCode::Sle,
+        "sgt" => ConditionCode::Sgt,
+        "sge" => ConditionCode::Sge,
+        _ => return None,
+    })
+}
+
+fn fcmp_condition_code(cond: &str) -> Option<FcmpConditionCode> {
+    Some(match cond {
+        "false" => FcmpConditionCode::False,
+        "oeq" => FcmpConditionCode::OrderedAndEqual,
+        "ogt" => FcmpConditionCode::OrderedAndGreaterThan,
+        "oge" => FcmpConditionCode::OrderedAndGreaterThanOrEqual,
+        "olt" => FcmpConditionCode::OrderedAndLessThan,
+        "ole" => FcmpConditionCode::OrderedAndLessThanOrEqual,
+        "one" => FcmpConditionCode::OrderedAndNotEqual,
+        "ord" => FcmpConditionCode::Ordered,
+        "ueq" => FcmpConditionCode::UnorderedOrEqual,
+        "ugt" => FcmpConditionCode::UnorderedOrGreaterThan,
+        "uge" => FcmpConditionCode::UnorderedOrGreaterThanOrEqual,
+        "ult" => FcmpConditionCode::UnorderedOrLessThan,
+        "ule" => FcmpConditionCode::UnorderedOrLessThanOrEqual,
+        "une" => FcmpConditionCode::UnorderedOrNotEqual,
+        "uno" => FcmpConditionCode::Unordered,
+        "true" => FcmpConditionCode::True,
+        _ => return None,
+    })
+}
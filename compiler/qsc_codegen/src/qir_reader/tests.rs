@@ -0,0 +1,755 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::too_many_lines, clippy::needless_raw_string_hashes)]
+
+use super::read_qir;
+use crate::qir::ToQir;
+use expect_test::{expect, Expect};
+use indoc::indoc;
+
+fn check(input: &str, expect: &Expect) {
+    match read_qir(input) {
+        Ok(program) => expect.assert_eq(&program.to_string()),
+        Err(error) => expect.assert_debug_eq(&error),
+    }
+}
+
+#[test]
+fn base_profile_program() {
+    check(
+        indoc! {r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              call void @__quantum__qis__h__body(%Qubit* inttoptr (i64 0 to %Qubit*))
+              call void @__quantum__qis__cx__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Qubit* inttoptr (i64 1 to %Qubit*))
+              call void @__quantum__qis__rz__body(double 1.5, %Qubit* null)
+              call void @__quantum__qis__mz__body(%Qubit* inttoptr (i64 0 to %Qubit*), %Result* null) #1
+              call void @__quantum__qis__mz__body(%Qubit* inttoptr (i64 1 to %Qubit*), %Result* inttoptr (i64 1 to %Result*))
+              call void @__quantum__rt__array_record_output(i64 2, i8* null)
+              call void @__quantum__rt__result_record_output(%Result* null, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @0, i64 0, i64 0))
+              call void @__quantum__rt__result_record_output(%Result* inttoptr (i64 1 to %Result*), i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__h__body(%Qubit*)
+
+            declare void @__quantum__qis__cx__body(%Qubit*, %Qubit*)
+
+            declare void @__quantum__qis__rz__body(double, %Qubit*)
+
+            declare void @__quantum__qis__mz__body(%Qubit*, %Result* writeonly) #1
+
+            declare void @__quantum__rt__array_record_output(i64, i8*)
+
+            declare void @__quantum__rt__result_record_output(%Result*, i8*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="base_profile" "required_num_qubits"="2" "required_num_results"="2" }
+            attributes #1 = { "irreversible" }
+
+            !llvm.module.flags = !{!0, !1, !2, !3}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 false}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+        "#},
+        &expect![[r#"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: ENTRYPOINT__main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__qis__h__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__qis__cx__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                            [1]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__qis__rz__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Double
+                            [1]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 4: Callable:
+                        name: __quantum__qis__mz__body
+                        call_type: Measurement
+                        input_type:
+                            [0]: Qubit
+                            [1]: Result
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 5: Callable:
+                        name: __quantum__rt__array_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Integer
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 6: Callable:
+                        name: __quantum__rt__result_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Result
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Call id(1), args( Qubit(0), )
+                        Call id(2), args( Qubit(0), Qubit(1), )
+                        Call id(3), args( Double(1.5), Qubit(0), )
+                        Call id(4), args( Qubit(0), Result(0), )
+                        Call id(4), args( Qubit(1), Result(1), )
+                        Call id(5), args( Integer(2), Pointer, )
+                        Call id(6), args( Result(0), Pointer, )
+                        Call id(6), args( Result(1), Pointer, )
+                        Return
+                config: Config:
+                    capabilities: Base
+                num_qubits: 2
+                num_results: 2"#]],
+    );
+}
+
+#[test]
+fn adaptive_profile_program_with_branches() {
+    check(
+        indoc! {r#"
+            %Result = type opaque
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              call void @__quantum__qis__mresetz__body(%Qubit* null, %Result* null)
+              %var_0 = call i1 @__quantum__qis__read_result__body(%Result* null)
+              br i1 %var_0, label %block_1, label %block_2
+            block_1:
+              %var_1 = add i64 3, 4
+              %var_2 = sitofp i64 %var_1 to double
+              %var_3 = fmul double %var_2, 0x400921FB54442D18
+              br label %block_3
+            block_2:
+              %var_4 = xor i1 %var_0, true
+              br label %block_3
+            block_3:
+              %var_5 = phi double [%var_3, %block_1], [1.000000e+00, %block_2]
+              %var_6 = fcmp olt double %var_5, 2.0
+              %var_7 = icmp sle i64 %var_1, 7
+              call void @__quantum__qis__rx__body(double %var_5, %Qubit* null)
+              call void @__quantum__rt__bool_record_output(i1 %var_6, i8* null)
+              ret void
+            }
+
+            declare void @__quantum__qis__mresetz__body(%Qubit*, %Result*) #1
+
+            declare i1 @__quantum__qis__read_result__body(%Result*)
+
+            declare void @__quantum__qis__rx__body(double, %Qubit*)
+
+            declare void @__quantum__rt__bool_record_output(i1, i8*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="1" "required_num_results"="1" }
+            attributes #1 = { "irreversible" }
+
+            !llvm.module.flags = !{!0, !1, !2, !3, !4, !5}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 false}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+            !4 = !{i32 1, !"classical_ints", i1 true}
+            !5 = !{i32 1, !"classical_floats", i1 true}
+            !6 = !{i32 1, !"backwards_branching", i1 false}
+        "#},
+        &expect![[r#"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: ENTRYPOINT__main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__qis__mresetz__body
+                        call_type: Measurement
+                        input_type:
+                            [0]: Qubit
+                            [1]: Result
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__qis__read_result__body
+                        call_type: Readout
+                        input_type:
+                            [0]: Result
+                        output_type: Boolean
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__qis__rx__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Double
+                            [1]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 4: Callable:
+                        name: __quantum__rt__bool_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Boolean
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Call id(1), args( Qubit(0), Result(0), )
+                        Variable(0, Boolean) = Call id(2), args( Result(0), )
+                        Branch Variable(0, Boolean), 1, 2
+                    Block 1: Block:
+                        Variable(1, Integer) = Add Integer(3), Integer(4)
+                        Variable(2, Double) = Sitofp Variable(1, Integer)
+                        Variable(3, Double) = Fmul Variable(2, Double), Double(3.141592653589793)
+                        Jump(3)
+                    Block 2: Block:
+                        Variable(4, Boolean) = LogicalNot Variable(0, Boolean)
+                        Jump(3)
+                    Block 3: Block:
+                        Variable(5, Double) = Phi ( [Variable(3, Double), 1], [Double(1), 2], )
+                        Variable(6, Boolean) = Fcmp Olt, Variable(5, Double), Double(2)
+                        Variable(7, Boolean) = Icmp Sle, Variable(1, Integer), Integer(7)
+                        Call id(3), args( Variable(5, Double), Qubit(0), )
+                        Call id(4), args( Variable(6, Boolean), Pointer, )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations)
+                num_qubits: 1
+                num_results: 1"#]],
+    );
+}
+
+#[test]
+fn generated_qir_round_trips() {
+    let program = qsc_rir::parse::parse_program(indoc! {"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: __quantum__qis__m__body
+                    call_type: Measurement
+                    input_type:
+                        [0]: Qubit
+                        [1]: Result
+                    output_type: <VOID>
+                    body: <NONE>
+                Callable 2: Callable:
+                    name: __quantum__qis__read_result__body
+                    call_type: Readout
+                    input_type:
+                        [0]: Result
+                    output_type: Boolean
+                    body: <NONE>
+                Callable 3: Callable:
+                    name: __quantum__rt__integer_record_output
+                    call_type: OutputRecording
+                    input_type:
+                        [0]: Integer
+                        [1]: Pointer
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Call id(1), args( Qubit(0), Result(0), )
+                    Variable(0, Boolean) = Call id(2), args( Result(0), )
+                    Branch Variable(0, Boolean), 1, 2
+                Block 1: Block:
+                    Variable(1, Integer) = Mul Integer(2), Integer(3)
+                    Jump(2)
+                Block 2: Block:
+                    Variable(2, Integer) = Phi ( [Integer(0), 0], [Variable(1, Integer), 1], )
+                    Call id(3), args( Variable(2, Integer), Pointer, )
+                    Return
+            config: Config:
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations)
+                optimization_level: None
+            num_qubits: 1
+            num_results: 1"})
+    .expect("program should parse");
+    let qir = program.to_qir(&program);
+    let read = read_qir(&qir).expect("generated QIR should be readable");
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: ENTRYPOINT__main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: __quantum__qis__m__body
+                    call_type: Measurement
+                    input_type:
+                        [0]: Qubit
+                        [1]: Result
+                    output_type: <VOID>
+                    body: <NONE>
+                Callable 2: Callable:
+                    name: __quantum__qis__read_result__body
+                    call_type: Readout
+                    input_type:
+                        [0]: Result
+                    output_type: Boolean
+                    body: <NONE>
+                Callable 3: Callable:
+                    name: __quantum__rt__integer_record_output
+                    call_type: OutputRecording
+                    input_type:
+                        [0]: Integer
+                        [1]: Pointer
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Call id(1), args( Qubit(0), Result(0), )
+                    Variable(0, Boolean) = Call id(2), args( Result(0), )
+                    Branch Variable(0, Boolean), 1, 2
+                Block 1: Block:
+                    Variable(1, Integer) = Mul Integer(2), Integer(3)
+                    Jump(2)
+                Block 2: Block:
+                    Variable(2, Integer) = Phi ( [Integer(0), 0], [Variable(1, Integer), 1], )
+                    Call id(3), args( Variable(2, Integer), Pointer, )
+                    Return
+            config: Config:
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations)
+            num_qubits: 1
+            num_results: 1"#]]
+    .assert_eq(&read.to_string());
+}
+
+#[test]
+fn missing_attributes_are_inferred_from_program() {
+    check(
+        indoc! {r#"
+            define void @main() {
+              call void @__quantum__qis__x__body(%Qubit* inttoptr (i64 3 to %Qubit*))
+              ret void
+            }
+
+            declare void @__quantum__qis__x__body(%Qubit*)
+        "#},
+        &expect![[r#"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__qis__x__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Call id(1), args( Qubit(3), )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive)
+                num_qubits: 4
+                num_results: 0"#]],
+    );
+}
+
+#[test]
+fn runtime_qubit_allocation_is_read_with_dynamic_qubit_management() {
+    check(
+        indoc! {r#"
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              %var_0 = call %Qubit* @__quantum__rt__qubit_allocate()
+              call void @__quantum__qis__x__body(%Qubit* %var_0)
+              call void @__quantum__rt__qubit_release(%Qubit* %var_0)
+              ret void
+            }
+
+            declare %Qubit* @__quantum__rt__qubit_allocate()
+
+            declare void @__quantum__qis__x__body(%Qubit*)
+
+            declare void @__quantum__rt__qubit_release(%Qubit*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="0" "required_num_results"="0" }
+
+            !llvm.module.flags = !{!0, !1, !2, !3}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 true}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+        "#},
+        &expect![[r#"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: ENTRYPOINT__main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__rt__qubit_allocate
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: Qubit
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__qis__x__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__rt__qubit_release
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Qubit) = Call id(1), args( )
+                        Call id(2), args( Variable(0, Qubit), )
+                        Call id(3), args( Variable(0, Qubit), )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | DynamicQubitManagement)
+                num_qubits: 0
+                num_results: 0"#]],
+    );
+}
+
+#[test]
+fn runtime_qubit_array_is_read_with_dynamic_qubit_management() {
+    check(
+        indoc! {r#"
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              %var_0 = call i8* @__quantum__rt__qubit_allocate_array(i64 1)
+              %var_1 = call i8* @__quantum__rt__array_get_element_ptr_1d(i8* %var_0, i64 0)
+              %var_2.ptr = bitcast i8* %var_1 to %Qubit**
+              %var_2 = load %Qubit*, %Qubit** %var_2.ptr, align 8
+              call void @__quantum__qis__x__body(%Qubit* %var_2)
+              call void @__quantum__rt__qubit_release_array(i8* %var_0)
+              ret void
+            }
+
+            declare i8* @__quantum__rt__qubit_allocate_array(i64)
+
+            declare i8* @__quantum__rt__array_get_element_ptr_1d(i8*, i64)
+
+            declare void @__quantum__qis__x__body(%Qubit*)
+
+            declare void @__quantum__rt__qubit_release_array(i8*)
+
+            attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="adaptive_profile" "required_num_qubits"="0" "required_num_results"="0" }
+
+            !llvm.module.flags = !{!0, !1, !2, !3}
+
+            !0 = !{i32 1, !"qir_major_version", i32 1}
+            !1 = !{i32 7, !"qir_minor_version", i32 0}
+            !2 = !{i32 1, !"dynamic_qubit_management", i1 true}
+            !3 = !{i32 1, !"dynamic_result_management", i1 false}
+        "#},
+        &expect![[r#"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: ENTRYPOINT__main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__rt__qubit_allocate_array
+                        call_type: Regular
+                        input_type:
+                            [0]: Integer
+                        output_type: Pointer
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__rt__array_get_element_ptr_1d
+                        call_type: Regular
+                        input_type:
+                            [0]: Pointer
+                            [1]: Integer
+                        output_type: Pointer
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__qis__x__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 4: Callable:
+                        name: __quantum__rt__qubit_release_array
+                        call_type: Regular
+                        input_type:
+                            [0]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Pointer) = Call id(1), args( Integer(1), )
+                        Variable(1, Pointer) = Call id(2), args( Variable(0, Pointer), Integer(0), )
+                        Variable(2, Qubit) = Load Variable(1, Pointer)
+                        Call id(3), args( Variable(2, Qubit), )
+                        Call id(4), args( Variable(0, Pointer), )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | DynamicQubitManagement)
+                num_qubits: 0
+                num_results: 0"#]],
+    );
+}
+
+#[test]
+fn load_from_pointer_that_is_not_cast_is_error() {
+    check(
+        indoc! {r#"
+            %Qubit = type opaque
+
+            define void @ENTRYPOINT__main() #0 {
+            block_0:
+              %var_0 = call i8* @__quantum__rt__qubit_allocate_array(i64 1)
+              %var_1 = load %Qubit*, %Qubit** %var_0
+              ret void
+            }
+
+            declare i8* @__quantum__rt__qubit_allocate_array(i64)
+
+            attributes #0 = { "entry_point" "qir_profiles"="adaptive_profile" }
+        "#},
+        &expect![[r#"
+            Unsupported(
+                "instruction `load` from a pointer that is not cast to %Qubit**",
+                Span {
+                    lo: 166,
+                    hi: 172,
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn opaque_pointers_are_unsupported() {
+    check(
+        indoc! {r#"
+            define void @main() #0 {
+              call void @__quantum__qis__x__body(ptr null)
+              ret void
+            }
+        "#},
+        &expect![[r#"
+            Unsupported(
+                "opaque pointer type `ptr`",
+                Span {
+                    lo: 62,
+                    hi: 65,
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn user_functions_are_unsupported() {
+    check(
+        indoc! {r#"
+            define void @main() #0 {
+              call void @helper(%Qubit* null)
+              ret void
+            }
+
+            declare void @helper(%Qubit*)
+        "#},
+        &expect![[r#"
+            Unsupported(
+                "call to function `helper`",
+                Span {
+                    lo: 37,
+                    hi: 44,
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn unsupported_instruction_is_error() {
+    check(
+        indoc! {r#"
+            define void @main() #0 {
+              %0 = alloca i64
+              ret void
+            }
+        "#},
+        &expect![[r#"
+            Unsupported(
+                "instruction `alloca`",
+                Span {
+                    lo: 32,
+                    hi: 38,
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn multiple_definitions_are_unsupported() {
+    check(
+        indoc! {r#"
+            define void @main() {
+              ret void
+            }
+
+            define void @other() {
+              ret void
+            }
+        "#},
+        &expect![[r#"
+            Unsupported(
+                "definition of more than one function",
+                Span {
+                    lo: 43,
+                    hi: 47,
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn undeclared_function_is_error() {
+    check(
+        indoc! {r#"
+            define void @main() {
+              call void @__quantum__qis__x__body(%Qubit* null)
+              ret void
+            }
+        "#},
+        &expect![[r#"
+            Undefined(
+                "function",
+                "@__quantum__qis__x__body",
+                Span {
+                    lo: 34,
+                    hi: 58,
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn undefined_variable_is_error() {
+    check(
+        indoc! {r#"
+            define void @main() {
+              %var_1 = add i64 %var_0, 1
+              ret void
+            }
+        "#},
+        &expect![[r#"
+            Undefined(
+                "variable",
+                "%var_0",
+                Span {
+                    lo: 41,
+                    hi: 47,
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn variable_used_with_different_types_is_error() {
+    check(
+        indoc! {r#"
+            define void @main() {
+              %var_0 = add i64 1, 1
+              %var_1 = and i1 %var_0, true
+              ret void
+            }
+        "#},
+        &expect![[r#"
+            TypeMismatch(
+                "%var_0",
+                "i1",
+                "i64",
+                Span {
+                    lo: 64,
+                    hi: 70,
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn missing_entry_point_is_error() {
+    check(
+        indoc! {r#"
+            declare void @__quantum__qis__x__body(%Qubit*)
+        "#},
+        &expect![[r#"
+            MissingEntryPoint
+        "#]],
+    );
+}
@@ -14,11 +14,11 @@ use std::io::Write;
 use std::vec;
 
 use qsc_ast::ast::{
-    self, Attr, BinOp, Block, CallableBody, CallableDecl, CallableKind, Expr, ExprKind, Functor,
-    FunctorExpr, FunctorExprKind, Ident, Item, ItemKind, Lit, Mutability, Pat, PatKind, Path,
-    Pauli, QubitInit, QubitInitKind, QubitSource, SetOp, SpecBody, SpecDecl, SpecGen, Stmt,
-    StmtKind, StringComponent, TernOp, TopLevelNode, Ty, TyDef, TyDefKind, TyKind, UnOp,
-    Visibility, VisibilityKind,
+    self, Attr, BinOp, Block, CallableBody, CallableDecl, CallableKind, ExportItem, Expr, ExprKind,
+    FieldAssign, FieldDef, Functor, FunctorExpr, FunctorExprKind, Ident, Item, ItemKind, Lit,
+    MatchArm, Mutability, Pat, PatKind, Path, Pauli, QubitInit, QubitInitKind, QubitSource, SetOp,
+    SpecBody, SpecDecl, SpecGen, Stmt, StmtKind, StringComponent, TernOp, TopLevelNode, Ty, TyDef,
+    TyDefKind, TyKind, TypeParameter, UnOp, VariantDef, Visibility, VisibilityKind,
 };
 use qsc_ast::ast::{Namespace, Package};
 use qsc_ast::visit::Visitor;
@@ -82,6 +82,18 @@ where
         self.write("\n");
     }
 
+    fn write_type_parameter(&mut self, param: &TypeParameter) {
+        self.visit_ident(&param.name);
+        if let Some((first, rest)) = param.bounds.split_first() {
+            self.write(" : ");
+            self.visit_ident(first);
+            for bound in rest {
+                self.write(" + ");
+                self.visit_ident(bound);
+            }
+        }
+    }
+
     /// special case for tuple with one element
     /// otherwise we are changing the semantics of the program
     fn ensure_trailing_comma_for_arity_one_tuples<T>(&mut self, most: &[T]) {
@@ -89,6 +101,33 @@ where
             self.write(",");
         }
     }
+
+    fn write_input_with_defaults(&mut self, input: &Pat, defaults: &[Box<Expr>]) {
+        let params = match &*input.kind {
+            PatKind::Paren(pat) => std::slice::from_ref(pat),
+            PatKind::Tuple(pats) => &pats[..],
+            _ => {
+                self.visit_pat(input);
+                return;
+            }
+        };
+        let first_default = params.len().saturating_sub(defaults.len());
+        self.write("(");
+        for (i, param) in params.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+            self.visit_pat(param);
+            if let Some(default) = i.checked_sub(first_default).and_then(|i| defaults.get(i)) {
+                self.write(" = ");
+                self.visit_expr(default);
+            }
+        }
+        if matches!(&*input.kind, PatKind::Tuple(_)) {
+            self.ensure_trailing_comma_for_arity_one_tuples(&params[1..]);
+        }
+        self.write(")");
+    }
 }
 
 impl<W: Write> Visitor<'_> for QSharpGen<W> {
@@ -138,9 +177,90 @@ impl<W: Write> Visitor<'_> for QSharpGen<W> {
                 self.visit_ty_def(def);
                 self.writeln(";");
             }
+            ItemKind::Struct(decl) => {
+                self.write("struct ");
+                self.visit_ident(&decl.name);
+                self.write(" { ");
+                if let Some((last, most)) = decl.fields.split_last() {
+                    for field in most {
+                        self.visit_field_def(field);
+                        self.write(", ");
+                    }
+                    self.visit_field_def(last);
+                }
+                self.writeln(" }");
+            }
+            ItemKind::Enum(decl) => {
+                self.write("enum ");
+                self.visit_ident(&decl.name);
+                self.write(" { ");
+                if let Some((last, most)) = decl.variants.split_last() {
+                    for variant in most {
+                        self.visit_variant_def(variant);
+                        self.write(", ");
+                    }
+                    self.visit_variant_def(last);
+                }
+                self.writeln(" }");
+            }
+            ItemKind::Export(decl) => {
+                self.write("export ");
+                if let Some((last, most)) = decl.items.split_last() {
+                    for export in most {
+                        self.visit_export_item(export);
+                        self.write(", ");
+                    }
+                    self.visit_export_item(last);
+                }
+                self.writeln(";");
+            }
+            ItemKind::Const(decl) => {
+                self.write("const ");
+                self.visit_ident(&decl.name);
+                self.write(" : ");
+                self.visit_ty(&decl.ty);
+                self.write(" = ");
+                self.visit_expr(&decl.value);
+                self.writeln(";");
+            }
         }
     }
 
+    fn visit_export_item(&mut self, item: &'_ ExportItem) {
+        self.visit_path(&item.path);
+        if let Some(alias) = &item.alias {
+            self.write(" as ");
+            self.visit_ident(alias);
+        }
+    }
+
+    fn visit_variant_def(&mut self, def: &'_ VariantDef) {
+        self.visit_ident(&def.name);
+        if let Some(ty) = &def.ty {
+            self.write("(");
+            self.visit_ty(ty);
+            self.write(")");
+        }
+    }
+
+    fn visit_match_arm(&mut self, arm: &'_ MatchArm) {
+        self.visit_pat(&arm.pat);
+        self.write(" => ");
+        self.visit_expr(&arm.body);
+    }
+
+    fn visit_field_def(&mut self, def: &'_ FieldDef) {
+        self.visit_ident(&def.name);
+        self.write(" : ");
+        self.visit_ty(&def.ty);
+    }
+
+    fn visit_field_assign(&mut self, assign: &'_ FieldAssign) {
+        self.visit_ident(&assign.field);
+        self.write(" = ");
+        self.visit_expr(&assign.value);
+    }
+
     fn visit_attr(&mut self, attr: &'_ Attr) {
         self.write("@");
         self.visit_ident(&attr.name);
@@ -190,17 +310,21 @@ impl<W: Write> Visitor<'_> for QSharpGen<W> {
         if !decl.generics.is_empty() {
             self.write("<");
             if let Some((last, most)) = decl.generics.split_last() {
-                for i in most {
-                    self.visit_ident(i);
+                for param in most {
+                    self.write_type_parameter(param);
                     self.write(", ");
                 }
-                self.visit_ident(last);
+                self.write_type_parameter(last);
             }
 
             self.write(">");
         }
 
-        self.visit_pat(&decl.input);
+        if decl.defaults.is_empty() {
+            self.visit_pat(&decl.input);
+        } else {
+            self.write_input_with_defaults(&decl.input, &decl.defaults);
+        }
         self.write(" : ");
         self.visit_ty(&decl.output);
         if let Some(functors) = decl.functors.as_deref() {
@@ -409,6 +533,7 @@ impl<W: Write> Visitor<'_> for QSharpGen<W> {
                 self.visit_expr(value);
             }
             ExprKind::Block(block) => self.visit_block(block),
+            ExprKind::Break => self.write("break"),
             ExprKind::Call(callee, arg) => {
                 self.visit_expr(callee);
                 self.visit_expr(arg);
@@ -419,6 +544,7 @@ impl<W: Write> Visitor<'_> for QSharpGen<W> {
                 self.write("apply");
                 self.visit_block(apply);
             }
+            ExprKind::Continue => self.write("continue"),
             ExprKind::Fail(msg) => {
                 self.write("fail ");
                 self.visit_expr(msg);
@@ -481,6 +607,16 @@ impl<W: Write> Visitor<'_> for QSharpGen<W> {
                 }
                 self.visit_expr(expr);
             }
+            ExprKind::Match(scrutinee, arms) => {
+                self.write("match ");
+                self.visit_expr(scrutinee);
+                self.writeln(" {");
+                for arm in arms {
+                    self.visit_match_arm(arm);
+                    self.writeln(",");
+                }
+                self.write("}");
+            }
             ExprKind::Paren(expr) => {
                 self.write("(");
                 self.visit_expr(expr);
@@ -576,6 +712,26 @@ impl<W: Write> Visitor<'_> for QSharpGen<W> {
                     }
                 }
             }
+            ExprKind::Struct(name, copy, fields) => {
+                self.write("new ");
+                self.visit_path(name);
+                self.write(" { ");
+                if let Some(copy) = copy {
+                    self.write("...");
+                    self.visit_expr(copy);
+                    if !fields.is_empty() {
+                        self.write(", ");
+                    }
+                }
+                if let Some((last, most)) = fields.split_last() {
+                    for field in most {
+                        self.visit_field_assign(field);
+                        self.write(", ");
+                    }
+                    self.visit_field_assign(last);
+                }
+                self.write(" }");
+            }
             ExprKind::Tuple(exprs) => {
                 self.write("(");
                 if let Some((last, most)) = exprs.split_last() {
@@ -659,6 +815,14 @@ impl<W: Write> Visitor<'_> for QSharpGen<W> {
             PatKind::Elided => {
                 self.write("...");
             }
+            PatKind::Variant(path, payload) => {
+                self.visit_path(path);
+                if let Some(payload) = payload {
+                    self.write("(");
+                    self.visit_pat(payload);
+                    self.write(")");
+                }
+            }
             PatKind::Paren(pat) => {
                 self.write("(");
                 self.visit_pat(pat);
@@ -676,6 +840,24 @@ impl<W: Write> Visitor<'_> for QSharpGen<W> {
                 }
                 self.write(")");
             }
+            PatKind::Array(pats, rest) => {
+                self.write("[");
+                if let Some((last, most)) = pats.split_last() {
+                    for pat in most {
+                        self.visit_pat(pat);
+                        self.write(", ");
+                    }
+                    self.visit_pat(last);
+                    if rest.is_some() {
+                        self.write(", ");
+                    }
+                }
+                if let Some(rest) = rest {
+                    self.write("...");
+                    self.visit_pat(rest);
+                }
+                self.write("]");
+            }
             PatKind::Err => {
                 unreachable!();
             }
@@ -716,6 +716,58 @@ fn while_loops() {
     );
 }
 
+#[test]
+fn break_and_continue() {
+    check(
+        indoc! {r#"
+            namespace A {
+                operation A() : Unit {
+                    for i in 0..10 {
+                        if i % 2 == 0 {
+                            continue;
+                        }
+                        if i > 7 {
+                            break;
+                        }
+                    }
+                }
+            }"#},
+        None,
+        &expect![[r#"
+            namespace A {
+                operation A() : Unit {
+                    for i in 0..10 {
+                        if i % 2 == 0 {
+                            continue;
+                        }
+                        if i > 7 {
+                            break;
+                        }
+                    }
+                }
+            }"#]],
+    );
+}
+
+#[test]
+fn type_parameters_with_class_bounds() {
+    check(
+        indoc! {r#"
+            namespace A {
+                function Plus<'T : Add + Eq, 'U>(x : 'T, y : 'U) : 'T {
+                    x + x
+                }
+            }"#},
+        None,
+        &expect![[r#"
+            namespace A {
+                function Plus<'T : Add + Eq, 'U>(x : 'T, y : 'U) : 'T {
+                    x + x
+                }
+            }"#]],
+    );
+}
+
 #[test]
 fn repeat_loops() {
     check(
@@ -922,3 +974,20 @@ fn bases_and_readable_values() {
             }"#]],
     );
 }
+
+#[test]
+fn callable_with_default_values() {
+    check(
+        indoc! {r#"
+            namespace Sample {
+                function Foo(x : Int, y : Double = 2.0 * 0.5, z : Bool = true) : Unit {}
+                function Bar(x : Int = 1) : Unit {}
+            }"#},
+        None,
+        &expect![[r#"
+            namespace Sample {
+                function Foo(x : Int, y : Double = 2. * 0.5, z : Bool = true) : Unit {}
+                function Bar(x : Int = 1) : Unit {}
+            }"#]],
+    );
+}
@@ -12,6 +12,7 @@ bitflags! {
         const BackwardsBranching = 0b0000_1000;
         const HigherLevelConstructs = 0b0001_0000;
         const QubitReset = 0b0010_0000;
+        const DynamicQubitManagement = 0b0100_0000;
     }
 }
 
@@ -27,6 +28,7 @@ impl std::str::FromStr for TargetCapabilityFlags {
             "BackwardsBranching" => Ok(TargetCapabilityFlags::BackwardsBranching),
             "HigherLevelConstructs" => Ok(TargetCapabilityFlags::HigherLevelConstructs),
             "QubitReset" => Ok(TargetCapabilityFlags::QubitReset),
+            "DynamicQubitManagement" => Ok(TargetCapabilityFlags::DynamicQubitManagement),
             "Unrestricted" => Ok(TargetCapabilityFlags::all()),
             _ => Err(()),
         }
@@ -88,6 +88,16 @@ impl<'a> CodeDisplay<'a> {
         IdentTyDef { ident, def }
     }
 
+    #[must_use]
+    pub fn ast_struct_decl(&self, decl: &'a ast::StructDecl) -> impl Display + 'a {
+        AstStructDecl { decl }
+    }
+
+    #[must_use]
+    pub fn ast_enum_decl(&self, decl: &'a ast::EnumDecl) -> impl Display + 'a {
+        AstEnumDecl { decl }
+    }
+
     #[must_use]
     pub fn hir_udt(&self, udt: &'a ty::Udt) -> impl Display + '_ {
         HirUdt { udt }
@@ -98,6 +108,11 @@ impl<'a> CodeDisplay<'a> {
         HirPat { pat }
     }
 
+    #[must_use]
+    pub fn hir_default(&self, expr: &'a hir::Expr) -> impl Display + '_ {
+        HirDefault { expr }
+    }
+
     #[must_use]
     pub fn get_param_offset(&self, decl: &hir::CallableDecl) -> u32 {
         HirCallableDecl { decl }.get_param_offset()
@@ -171,10 +186,28 @@ impl Display for HirCallableDecl<'_> {
         let input = HirPat {
             pat: &self.decl.input,
         };
-        if matches!(self.decl.input.kind, hir::PatKind::Tuple(_)) {
-            write!(f, "{input}")?;
-        } else {
-            write!(f, "({input})")?;
+        match &self.decl.input.kind {
+            hir::PatKind::Tuple(items) if !self.decl.defaults.is_empty() => {
+                let first_default = items.len().saturating_sub(self.decl.defaults.len());
+                write!(f, "(")?;
+                let mut sep = "";
+                for (i, item) in items.iter().enumerate() {
+                    write!(f, "{sep}{}", HirPat { pat: item })?;
+                    if let Some(default) = i
+                        .checked_sub(first_default)
+                        .and_then(|i| self.decl.defaults.get(i))
+                    {
+                        write!(f, " = {}", HirDefault { expr: default })?;
+                    }
+                    sep = ", ";
+                }
+                write!(f, ")")?;
+            }
+            hir::PatKind::Tuple(_) => write!(f, "{input}")?,
+            _ => match self.decl.defaults.first() {
+                Some(default) => write!(f, "({input} = {})", HirDefault { expr: default })?,
+                None => write!(f, "({input})")?,
+            },
         }
         write!(
             f,
@@ -187,6 +220,63 @@ impl Display for HirCallableDecl<'_> {
     }
 }
 
+/// A default value of a callable parameter. Default values are evaluated to literals when the
+/// callable is compiled, so other expressions are only shown as `...`.
+struct HirDefault<'a> {
+    expr: &'a hir::Expr,
+}
+
+impl Display for HirDefault<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match &self.expr.kind {
+            hir::ExprKind::Lit(lit) => match lit {
+                hir::Lit::BigInt(val) => write!(f, "{val}L"),
+                hir::Lit::Bool(val) => write!(f, "{val}"),
+                hir::Lit::Double(val) => write!(f, "{val:?}"),
+                hir::Lit::Int(val) => write!(f, "{val}"),
+                hir::Lit::Pauli(pauli) => match pauli {
+                    hir::Pauli::I => write!(f, "PauliI"),
+                    hir::Pauli::X => write!(f, "PauliX"),
+                    hir::Pauli::Y => write!(f, "PauliY"),
+                    hir::Pauli::Z => write!(f, "PauliZ"),
+                },
+                hir::Lit::Result(hir::Result::Zero) => write!(f, "Zero"),
+                hir::Lit::Result(hir::Result::One) => write!(f, "One"),
+            },
+            hir::ExprKind::UnOp(hir::UnOp::Neg, operand) => {
+                write!(f, "-{}", HirDefault { expr: operand })
+            }
+            hir::ExprKind::Array(items) => {
+                write!(f, "[")?;
+                let mut sep = "";
+                for item in items {
+                    write!(f, "{sep}{}", HirDefault { expr: item })?;
+                    sep = ", ";
+                }
+                write!(f, "]")
+            }
+            hir::ExprKind::Tuple(items) => {
+                write!(f, "(")?;
+                let mut sep = "";
+                for item in items {
+                    write!(f, "{sep}{}", HirDefault { expr: item })?;
+                    sep = ", ";
+                }
+                if items.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            hir::ExprKind::String(components) => match &components[..] {
+                [] => write!(f, "\"\""),
+                [hir::StringComponent::Lit(s)] => write!(f, "{:?}", s.as_ref()),
+                _ => write!(f, "..."),
+            },
+            _ => write!(f, "..."),
+        }
+    }
+}
+
 struct AstCallableDecl<'a> {
     lookup: &'a dyn Lookup,
     decl: &'a ast::CallableDecl,
@@ -208,7 +298,14 @@ impl<'a> Display for AstCallableDecl<'a> {
                 .decl
                 .generics
                 .iter()
-                .map(|p| p.name.clone())
+                .map(|p| {
+                    if p.bounds.is_empty() {
+                        p.name.name.to_string()
+                    } else {
+                        let bounds = p.bounds.iter().map(|b| b.name.as_ref()).collect::<Vec<_>>();
+                        format!("{} : {}", p.name.name, bounds.join(" + "))
+                    }
+                })
                 .collect::<Vec<_>>()
                 .join(", ");
             write!(f, "<{type_params}>")?;
@@ -254,6 +351,18 @@ impl<'a> Display for HirPat<'a> {
                     write!(f, "()")
                 }
             }
+            hir::PatKind::Array(items, rest) => {
+                write!(f, "[")?;
+                let mut sep = "";
+                for item in items {
+                    write!(f, "{sep}{}", HirPat { pat: item })?;
+                    sep = ", ";
+                }
+                if let Some(rest) = rest {
+                    write!(f, "{sep}...{}", HirPat { pat: rest })?;
+                }
+                write!(f, "]")
+            }
             hir::PatKind::Err => write!(f, "?"),
         }
     }
@@ -291,6 +400,18 @@ impl<'a> Display for AstPat<'a> {
                 ),
             },
             ast::PatKind::Elided => write!(f, "..."),
+            ast::PatKind::Variant(path, payload) => match payload {
+                Some(payload) => write!(
+                    f,
+                    "{}({})",
+                    AstPath { path },
+                    AstPat {
+                        lookup: self.lookup,
+                        pat: payload,
+                    }
+                ),
+                None => write!(f, "{}", AstPath { path }),
+            },
             ast::PatKind::Paren(item) => write!(
                 f,
                 "{}",
@@ -325,6 +446,19 @@ impl<'a> Display for AstPat<'a> {
                     write!(f, "()")
                 }
             }
+            ast::PatKind::Array(items, rest) => {
+                let lookup = self.lookup;
+                write!(f, "[")?;
+                let mut sep = "";
+                for pat in items {
+                    write!(f, "{sep}{}", AstPat { lookup, pat })?;
+                    sep = ", ";
+                }
+                if let Some(pat) = rest {
+                    write!(f, "{sep}...{}", AstPat { lookup, pat })?;
+                }
+                write!(f, "]")
+            }
             ast::PatKind::Err => write!(f, "?"),
         }
     }
@@ -346,12 +480,73 @@ impl<'a> Display for IdentTyDef<'a> {
     }
 }
 
+struct AstStructDecl<'a> {
+    decl: &'a ast::StructDecl,
+}
+
+impl Display for AstStructDecl<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "struct {} {{", self.decl.name.name)?;
+        if let Some((last, most)) = self.decl.fields.split_last() {
+            write!(f, " ")?;
+            for field in most {
+                let (ident, ty) = (&*field.name, &*field.ty);
+                write!(f, "{}, ", IdentTy { ident, ty })?;
+            }
+            let (ident, ty) = (&*last.name, &*last.ty);
+            write!(f, "{} ", IdentTy { ident, ty })?;
+        }
+        write!(f, "}}")
+    }
+}
+
+struct AstEnumDecl<'a> {
+    decl: &'a ast::EnumDecl,
+}
+
+impl Display for AstEnumDecl<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "enum {} {{", self.decl.name.name)?;
+        if !self.decl.variants.is_empty() {
+            write!(f, " ")?;
+            for (i, variant) in self.decl.variants.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", variant.name.name)?;
+                if let Some(ty) = &variant.ty {
+                    write!(f, "({})", AstTy { ty })?;
+                }
+            }
+            write!(f, " ")?;
+        }
+        write!(f, "}}")
+    }
+}
+
 struct HirUdt<'a> {
     udt: &'a ty::Udt,
 }
 
 impl<'a> Display for HirUdt<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if let Some(variants) = self.udt.variants() {
+            write!(f, "enum {} {{", self.udt.name)?;
+            if !variants.is_empty() {
+                write!(f, " ")?;
+                for (i, variant) in variants.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", variant.name)?;
+                    if let Some(ty) = &variant.ty {
+                        write!(f, "({})", ty.display())?;
+                    }
+                }
+                write!(f, " ")?;
+            }
+            return write!(f, "}}");
+        }
         let udt_def = UdtDef::new(&self.udt.definition);
         write!(f, "newtype {} = {}", self.udt.name, udt_def)
     }
@@ -378,6 +573,9 @@ impl<'a> UdtDef<'a> {
                 name: None,
                 kind: UdtDefKind::TupleTy(defs.iter().map(UdtDef::new).collect()),
             },
+            ty::UdtDefKind::Enum(_) => {
+                unreachable!("enum variants should only appear at the top of a type definition")
+            }
         }
     }
 }
@@ -548,7 +746,12 @@ fn display_type_params(generics: &[GenericParam]) -> String {
     let type_params = generics
         .iter()
         .filter_map(|generic| match generic {
-            GenericParam::Ty(name) => Some(name.name.clone()),
+            GenericParam::Ty { name, bounds } => Some(if bounds.is_empty() {
+                name.name.to_string()
+            } else {
+                let bounds = bounds.iter().map(ToString::to_string).collect::<Vec<_>>();
+                format!("{} : {}", name.name, bounds.join(" + "))
+            }),
             GenericParam::Functor(_) => None,
         })
         .collect::<Vec<_>>()
@@ -7,8 +7,9 @@ mod tests;
 use crate::display::{increase_header_level, parse_doc_for_summary};
 use crate::display::{CodeDisplay, Lookup};
 use qsc_ast::ast;
+use qsc_data_structures::language_features::LanguageFeatures;
 use qsc_data_structures::target::TargetCapabilityFlags;
-use qsc_frontend::compile::{self, PackageStore};
+use qsc_frontend::compile::{self, PackageStore, SourceMap};
 use qsc_frontend::resolve;
 use qsc_hir::hir::{CallableKind, Item, ItemKind, Package, PackageId, Visibility};
 use qsc_hir::{hir, ty};
@@ -28,9 +29,21 @@ struct Compilation {
 
 impl Compilation {
     /// Creates a new `Compilation` by compiling sources.
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(additional_sources: Option<SourceMap>) -> Self {
         let mut package_store = PackageStore::new(compile::core());
-        package_store.insert(compile::std(&package_store, TargetCapabilityFlags::all()));
+        let std_id =
+            package_store.insert(compile::std(&package_store, TargetCapabilityFlags::all()));
+
+        if let Some(sources) = additional_sources {
+            let unit = compile::compile(
+                &package_store,
+                &[std_id],
+                sources,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            );
+            package_store.insert(unit);
+        }
 
         Self { package_store }
     }
@@ -105,7 +118,12 @@ impl Lookup for Compilation {
 
 #[must_use]
 pub fn generate_docs() -> Files {
-    let compilation = Compilation::new();
+    generate_docs_for_sources(None)
+}
+
+/// Generates the docs for the standard library, along with any additional sources.
+fn generate_docs_for_sources(additional_sources: Option<SourceMap>) -> Files {
+    let compilation = Compilation::new(additional_sources);
     let mut files: Files = vec![];
 
     let display = &CodeDisplay {
@@ -141,8 +159,17 @@ fn generate_doc_for_item<'a>(
     // Get namespace for item
     let ns = get_namespace(package, item)?;
 
+    // Exported items are documented in the exporting namespace, under their exported name
+    let (item, alias) = match &item.kind {
+        ItemKind::Export(name, target) if target.package.is_none() => {
+            (package.items.get(target.item)?, Some(name.name.clone()))
+        }
+        ItemKind::Export(..) => return None,
+        _ => (item, None),
+    };
+
     // Add file
-    let (metadata, content) = generate_file(&ns, item, display)?;
+    let (metadata, content) = generate_file(&ns, item, alias, display)?;
     let file_name: Arc<str> = Arc::from(format!("{ns}/{}.md", metadata.name).as_str());
     let file_metadata: Arc<str> = Arc::from(metadata.to_string().as_str());
     let file_content: Arc<str> = Arc::from(content.as_str());
@@ -177,8 +204,13 @@ fn get_namespace(package: &Package, item: &Item) -> Option<Rc<str>> {
     }
 }
 
-fn generate_file(ns: &Rc<str>, item: &Item, display: &CodeDisplay) -> Option<(Metadata, String)> {
-    let metadata = get_metadata(ns.clone(), item, display)?;
+fn generate_file(
+    ns: &Rc<str>,
+    item: &Item,
+    alias: Option<Rc<str>>,
+    display: &CodeDisplay,
+) -> Option<(Metadata, String)> {
+    let metadata = get_metadata(ns.clone(), item, alias, display)?;
 
     let doc = increase_header_level(&item.doc);
     let title = &metadata.title;
@@ -245,22 +277,41 @@ enum MetadataKind {
     Udt,
 }
 
-fn get_metadata(ns: Rc<str>, item: &Item, display: &CodeDisplay) -> Option<Metadata> {
+fn get_metadata(
+    ns: Rc<str>,
+    item: &Item,
+    alias: Option<Rc<str>>,
+    display: &CodeDisplay,
+) -> Option<Metadata> {
     let (name, signature, kind) = match &item.kind {
-        ItemKind::Callable(decl) => Some((
-            decl.name.name.clone(),
-            display.hir_callable_decl(decl).to_string(),
-            match &decl.kind {
-                CallableKind::Function => MetadataKind::Function,
-                CallableKind::Operation => MetadataKind::Operation,
-            },
-        )),
-        ItemKind::Ty(ident, udt) => Some((
-            ident.name.clone(),
-            display.hir_udt(udt).to_string(),
-            MetadataKind::Udt,
-        )),
-        ItemKind::Namespace(_, _) => None,
+        ItemKind::Callable(decl) => {
+            let mut decl = decl.clone();
+            if let Some(alias) = alias {
+                decl.name.name = alias;
+            }
+            let signature = display.hir_callable_decl(&decl).to_string();
+            Some((
+                decl.name.name.clone(),
+                signature,
+                match &decl.kind {
+                    CallableKind::Function => MetadataKind::Function,
+                    CallableKind::Operation => MetadataKind::Operation,
+                },
+            ))
+        }
+        ItemKind::Ty(ident, udt) => {
+            let mut udt = udt.clone();
+            if let Some(alias) = &alias {
+                udt.name = alias.clone();
+            }
+            let signature = display.hir_udt(&udt).to_string();
+            Some((
+                alias.unwrap_or_else(|| ident.name.clone()),
+                signature,
+                MetadataKind::Udt,
+            ))
+        }
+        ItemKind::Namespace(_, _) | ItemKind::Export(_, _) | ItemKind::Const(_, _) => None,
     }?;
 
     let summary = parse_doc_for_summary(&item.doc)
@@ -3,8 +3,9 @@
 
 #![allow(clippy::needless_raw_string_hashes)]
 
-use super::generate_docs;
+use super::{generate_docs, generate_docs_for_sources};
 use expect_test::expect;
+use qsc_frontend::compile::SourceMap;
 
 #[test]
 fn docs_generation() {
@@ -52,3 +53,55 @@ fn docs_generation() {
     "#]]
     .assert_eq(full_contents.as_str());
 }
+
+#[test]
+fn exported_items_are_documented_in_exporting_namespace() {
+    let sources = SourceMap::new(
+        [(
+            "test.qs".into(),
+            indoc::indoc! {"
+                namespace Implementation {
+                    /// # Summary
+                    /// Adds one to the input.
+                    function AddOne(x : Int) : Int { x + 1 }
+                }
+                namespace Facade {
+                    export Implementation.AddOne as Increment;
+                }
+            "}
+            .into(),
+        )],
+        None,
+    );
+    let files = generate_docs_for_sources(Some(sources));
+    let (_, metadata, contents) = files
+        .iter()
+        .find(|(file_name, _, _)| &**file_name == "Facade/Increment.md")
+        .expect("Could not file doc file for Increment");
+    let full_contents = format!("{metadata}\n\n{contents}");
+
+    expect![[r#"
+        ---
+        uid: Qdk.Facade.Increment
+        title: Increment function
+        ms.date: {TIMESTAMP}
+        ms.topic: managed-reference
+        qsharp.kind: function
+        qsharp.namespace: Facade
+        qsharp.name: Increment
+        qsharp.summary: "Adds one to the input."
+        ---
+
+        # Increment function
+
+        Namespace: Facade
+
+        ```qsharp
+        function Increment(x : Int) : Int
+        ```
+
+        ## Summary
+        Adds one to the input.
+    "#]]
+    .assert_eq(full_contents.as_str());
+}
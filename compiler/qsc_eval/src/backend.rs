@@ -4,7 +4,8 @@
 use num_bigint::BigUint;
 use num_complex::Complex;
 use quantum_sparse_sim::QuantumSim;
-use rand::RngCore;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use std::collections::{BTreeSet, VecDeque};
 
 use crate::val::Value;
 
@@ -34,6 +35,11 @@ pub trait Backend {
     fn mresetz(&mut self, _q: usize) -> Self::ResultType {
         unimplemented!("mresetz operation");
     }
+    /// Measures the qubit, forcing the given outcome and collapsing the state to match it.
+    /// Returns an error if the outcome cannot be produced, e.g. because it has zero probability.
+    fn m_forced(&mut self, _q: usize, _result: bool) -> Result<Self::ResultType, String> {
+        unimplemented!("forced m operation");
+    }
     fn reset(&mut self, _q: usize) {
         unimplemented!("reset gate");
     }
@@ -96,12 +102,22 @@ pub trait Backend {
         None
     }
 
+    /// Returns, and clears, the failure raised by the most recent intrinsic that has no other way
+    /// of reporting one, such as a measurement.
+    fn take_failure(&mut self) -> Option<String> {
+        None
+    }
+
     fn set_seed(&mut self, _seed: Option<u64>) {}
 }
 
 /// Default backend used when targeting sparse simulation.
 pub struct SparseSim {
     pub sim: QuantumSim,
+    /// The ids of the allocated qubits, whose order gives the bits of the simulator's state indices.
+    qubits: BTreeSet<usize>,
+    /// Source of the seeds used to force measurement outcomes.
+    seeds: StdRng,
 }
 
 impl Default for SparseSim {
@@ -115,10 +131,15 @@ impl SparseSim {
     pub fn new() -> Self {
         Self {
             sim: QuantumSim::new(),
+            qubits: BTreeSet::new(),
+            seeds: StdRng::from_entropy(),
         }
     }
 }
 
+/// The largest number of seeds tried when looking for one that forces a measurement outcome.
+const MAX_FORCING_SEEDS: usize = 1 << 20;
+
 impl Backend for SparseSim {
     type ResultType = bool;
 
@@ -154,6 +175,54 @@ impl Backend for SparseSim {
         res
     }
 
+    fn m_forced(&mut self, q: usize, result: bool) -> Result<Self::ResultType, String> {
+        let (state, _) = self.sim.get_state();
+        let bit = self.qubits.range(..q).count() as u64;
+        let probability_one = state
+            .iter()
+            .filter(|(index, _)| index.bit(bit))
+            .map(|(_, amplitude)| amplitude.norm_sqr())
+            .sum::<f64>()
+            .clamp(0.0, 1.0);
+        let probability = if result {
+            probability_one
+        } else {
+            1.0 - probability_one
+        };
+        if probability <= f64::EPSILON {
+            return Err(format!(
+                "outcome {} on qubit {q} has zero probability",
+                if result { "One" } else { "Zero" }
+            ));
+        }
+
+        // The simulator decides a measurement from its seed and the probability of `One`, so a seed
+        // that produces the forced outcome is found by measuring a lone qubit with the same probability
+        // in a probe simulator. Measuring with that seed then collapses the state as a natural
+        // measurement of the outcome would.
+        let theta = 2.0 * probability_one.sqrt().asin();
+        let seed = (0..MAX_FORCING_SEEDS)
+            .map(|_| self.seeds.next_u64())
+            .find(|&seed| {
+                let mut probe = QuantumSim::new();
+                probe.set_rng_seed(seed);
+                let qubit = probe.allocate();
+                probe.ry(theta, qubit);
+                probe.measure(qubit) == result
+            })
+            .ok_or_else(|| format!("outcome probability {probability:e} is too small to force"))?;
+        self.sim.set_rng_seed(seed);
+        let actual = self.sim.measure(q);
+        self.sim.set_rng_seed(self.seeds.next_u64());
+        if actual == result {
+            Ok(actual)
+        } else {
+            Err(format!(
+                "simulator did not produce the forced outcome on qubit {q}"
+            ))
+        }
+    }
+
     fn reset(&mut self, q: usize) {
         self.mresetz(q);
     }
@@ -233,11 +302,14 @@ impl Backend for SparseSim {
     }
 
     fn qubit_allocate(&mut self) -> usize {
-        self.sim.allocate()
+        let q = self.sim.allocate();
+        self.qubits.insert(q);
+        q
     }
 
     fn qubit_release(&mut self, q: usize) {
         self.sim.release(q);
+        self.qubits.remove(&q);
     }
 
     fn capture_quantum_state(&mut self) -> (Vec<(BigUint, Complex<f64>)>, usize) {
@@ -276,10 +348,9 @@ impl Backend for SparseSim {
     }
 
     fn set_seed(&mut self, seed: Option<u64>) {
-        match seed {
-            Some(seed) => self.sim.set_rng_seed(seed),
-            None => self.sim.set_rng_seed(rand::thread_rng().next_u64()),
-        }
+        let seed = seed.unwrap_or_else(|| rand::thread_rng().next_u64());
+        self.sim.set_rng_seed(seed);
+        self.seeds = StdRng::seed_from_u64(seed);
     }
 }
 
@@ -348,6 +419,11 @@ where
         self.main.mresetz(q)
     }
 
+    fn m_forced(&mut self, q: usize, result: bool) -> Result<Self::ResultType, String> {
+        let _ = self.chained.m(q);
+        self.main.m_forced(q, result)
+    }
+
     fn reset(&mut self, q: usize) {
         self.chained.reset(q);
         self.main.reset(q);
@@ -455,8 +531,234 @@ where
         self.main.custom_intrinsic(name, arg)
     }
 
+    fn take_failure(&mut self) -> Option<String> {
+        let _ = self.chained.take_failure();
+        self.main.take_failure()
+    }
+
     fn set_seed(&mut self, seed: Option<u64>) {
         self.chained.set_seed(seed);
         self.main.set_seed(seed);
     }
 }
+
+/// Backend wrapper that records the outcome of every measurement performed by the wrapped
+/// backend, and can replay a previously recorded sequence of outcomes by forcing the wrapped
+/// backend to produce them. Once the replayed outcomes run out, measurements proceed normally.
+pub struct MeasurementReplay<T> {
+    pub backend: T,
+    recorded: Vec<bool>,
+    replay: VecDeque<bool>,
+    failure: Option<String>,
+}
+
+impl<T> MeasurementReplay<T>
+where
+    T: Backend<ResultType = bool>,
+{
+    pub fn new(backend: T) -> MeasurementReplay<T> {
+        MeasurementReplay {
+            backend,
+            recorded: Vec::new(),
+            replay: VecDeque::new(),
+            failure: None,
+        }
+    }
+
+    /// Sets the outcomes forced on subsequent measurements, in order, and clears the record.
+    pub fn set_replay(&mut self, outcomes: Vec<bool>) {
+        self.replay = outcomes.into();
+        self.recorded.clear();
+    }
+
+    /// The outcomes of the measurements performed since the replay was last set.
+    #[must_use]
+    pub fn recorded(&self) -> &[bool] {
+        &self.recorded
+    }
+
+    /// Exchanges the recorded and pending outcomes with those of another wrapper, allowing a
+    /// fresh backend to continue the record and replay of an existing one.
+    pub fn swap_outcomes<U>(&mut self, other: &mut MeasurementReplay<U>) {
+        std::mem::swap(&mut self.recorded, &mut other.recorded);
+        std::mem::swap(&mut self.replay, &mut other.replay);
+    }
+
+    fn measure(&mut self, q: usize, measure: impl FnOnce(&mut T, usize) -> bool) -> bool {
+        let result = if let Some(forced) = self.replay.pop_front() {
+            self.backend.m_forced(q, forced).unwrap_or_else(|message| {
+                self.failure = Some(format!(
+                    "cannot replay measurement {}: {message}",
+                    self.recorded.len() + 1
+                ));
+                forced
+            })
+        } else {
+            measure(&mut self.backend, q)
+        };
+        self.recorded.push(result);
+        result
+    }
+}
+
+/// Formats measurement outcomes for storage, one `0` or `1` per line.
+#[must_use]
+pub fn format_measurement_record(outcomes: &[bool]) -> String {
+    outcomes
+        .iter()
+        .map(|&result| if result { "1\n" } else { "0\n" })
+        .collect()
+}
+
+/// Parses measurement outcomes stored by [`format_measurement_record`].
+/// # Errors
+/// Returns an error naming the first line that is neither blank, `0` nor `1`.
+pub fn parse_measurement_record(contents: &str) -> Result<Vec<bool>, String> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| match line.trim() {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            other => Err(format!(
+                "invalid measurement outcome `{other}` on line {}",
+                index + 1
+            )),
+        })
+        .collect()
+}
+
+impl<T> Backend for MeasurementReplay<T>
+where
+    T: Backend<ResultType = bool>,
+{
+    type ResultType = bool;
+
+    fn ccx(&mut self, ctl0: usize, ctl1: usize, q: usize) {
+        self.backend.ccx(ctl0, ctl1, q);
+    }
+
+    fn cx(&mut self, ctl: usize, q: usize) {
+        self.backend.cx(ctl, q);
+    }
+
+    fn cy(&mut self, ctl: usize, q: usize) {
+        self.backend.cy(ctl, q);
+    }
+
+    fn cz(&mut self, ctl: usize, q: usize) {
+        self.backend.cz(ctl, q);
+    }
+
+    fn h(&mut self, q: usize) {
+        self.backend.h(q);
+    }
+
+    fn m(&mut self, q: usize) -> Self::ResultType {
+        self.measure(q, T::m)
+    }
+
+    fn mresetz(&mut self, q: usize) -> Self::ResultType {
+        let forced = !self.replay.is_empty();
+        let result = self.measure(q, T::mresetz);
+        if forced && result {
+            self.backend.x(q);
+        }
+        result
+    }
+
+    fn m_forced(&mut self, q: usize, result: bool) -> Result<Self::ResultType, String> {
+        let result = self.backend.m_forced(q, result)?;
+        self.recorded.push(result);
+        Ok(result)
+    }
+
+    fn reset(&mut self, q: usize) {
+        self.backend.reset(q);
+    }
+
+    fn rx(&mut self, theta: f64, q: usize) {
+        self.backend.rx(theta, q);
+    }
+
+    fn rxx(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.backend.rxx(theta, q0, q1);
+    }
+
+    fn ry(&mut self, theta: f64, q: usize) {
+        self.backend.ry(theta, q);
+    }
+
+    fn ryy(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.backend.ryy(theta, q0, q1);
+    }
+
+    fn rz(&mut self, theta: f64, q: usize) {
+        self.backend.rz(theta, q);
+    }
+
+    fn rzz(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.backend.rzz(theta, q0, q1);
+    }
+
+    fn sadj(&mut self, q: usize) {
+        self.backend.sadj(q);
+    }
+
+    fn s(&mut self, q: usize) {
+        self.backend.s(q);
+    }
+
+    fn swap(&mut self, q0: usize, q1: usize) {
+        self.backend.swap(q0, q1);
+    }
+
+    fn tadj(&mut self, q: usize) {
+        self.backend.tadj(q);
+    }
+
+    fn t(&mut self, q: usize) {
+        self.backend.t(q);
+    }
+
+    fn x(&mut self, q: usize) {
+        self.backend.x(q);
+    }
+
+    fn y(&mut self, q: usize) {
+        self.backend.y(q);
+    }
+
+    fn z(&mut self, q: usize) {
+        self.backend.z(q);
+    }
+
+    fn qubit_allocate(&mut self) -> usize {
+        self.backend.qubit_allocate()
+    }
+
+    fn qubit_release(&mut self, q: usize) {
+        self.backend.qubit_release(q);
+    }
+
+    fn capture_quantum_state(&mut self) -> (Vec<(BigUint, Complex<f64>)>, usize) {
+        self.backend.capture_quantum_state()
+    }
+
+    fn qubit_is_zero(&mut self, q: usize) -> bool {
+        self.backend.qubit_is_zero(q)
+    }
+
+    fn custom_intrinsic(&mut self, name: &str, arg: Value) -> Option<Result<Value, String>> {
+        self.backend.custom_intrinsic(name, arg)
+    }
+
+    fn take_failure(&mut self) -> Option<String> {
+        self.failure.take().or_else(|| self.backend.take_failure())
+    }
+
+    fn set_seed(&mut self, seed: Option<u64>) {
+        self.backend.set_seed(seed);
+    }
+}
@@ -44,14 +44,7 @@ pub(crate) fn call(
             }
         }
         "DumpRegister" => {
-            let qubits = arg.unwrap_array();
-            let qubits = qubits
-                .iter()
-                .map(|q| q.clone().unwrap_qubit().0)
-                .collect::<Vec<_>>();
-            if qubits.len() != qubits.iter().collect::<FxHashSet<_>>().len() {
-                return Err(Error::QubitUniqueness(arg_span));
-            }
+            let qubits = unique_qubits(arg, arg_span)?;
             let (state, qubit_count) = sim.capture_quantum_state();
             let state = utils::split_state(&qubits, state, qubit_count)
                 .map_err(|()| Error::QubitsNotSeparable(arg_span))?;
@@ -60,6 +53,17 @@ pub(crate) fn call(
                 Err(_) => Err(Error::OutputFail(name_span)),
             }
         }
+        "DumpMatrix" => {
+            let qubits = unique_qubits(arg, arg_span)?;
+            let (state, qubit_count) = sim.capture_quantum_state();
+            let state = utils::split_state(&qubits, state, qubit_count)
+                .map_err(|()| Error::QubitsNotSeparable(arg_span))?;
+            let matrix = utils::state_to_matrix(state, qubits.len() / 2);
+            match out.matrix(matrix) {
+                Ok(()) => Ok(Value::unit()),
+                Err(_) => Err(Error::OutputFail(name_span)),
+            }
+        }
         "Message" => match out.message(&arg.unwrap_string()) {
             Ok(()) => Ok(Value::unit()),
             Err(_) => Err(Error::OutputFail(name_span)),
@@ -146,9 +150,27 @@ pub(crate) fn call(
         "__quantum__qis__z__body" => Ok(one_qubit_gate(|q| sim.z(q), arg)),
         "__quantum__qis__swap__body" => two_qubit_gate(|q0, q1| sim.swap(q0, q1), arg, arg_span),
         "__quantum__qis__reset__body" => Ok(one_qubit_gate(|q| sim.reset(q), arg)),
-        "__quantum__qis__m__body" => Ok(Value::Result(sim.m(arg.unwrap_qubit().0).into())),
+        "__quantum__qis__m__body" => {
+            let qubit = arg.unwrap_qubit().0;
+            let result = sim.m(qubit).into();
+            if let Some(message) = sim.take_failure() {
+                return Err(Error::IntrinsicFail(name.to_string(), message, name_span));
+            }
+            match out.measurement(qubit, result) {
+                Ok(()) => Ok(Value::Result(result)),
+                Err(_) => Err(Error::OutputFail(name_span)),
+            }
+        }
         "__quantum__qis__mresetz__body" => {
-            Ok(Value::Result(sim.mresetz(arg.unwrap_qubit().0).into()))
+            let qubit = arg.unwrap_qubit().0;
+            let result = sim.mresetz(qubit).into();
+            if let Some(message) = sim.take_failure() {
+                return Err(Error::IntrinsicFail(name.to_string(), message, name_span));
+            }
+            match out.measurement(qubit, result) {
+                Ok(()) => Ok(Value::Result(result)),
+                Err(_) => Err(Error::OutputFail(name_span)),
+            }
         }
         _ => {
             if let Some(result) = sim.custom_intrinsic(name, arg) {
@@ -228,6 +250,19 @@ fn two_qubit_rotation(
     }
 }
 
+fn unique_qubits(arg: Value, arg_span: PackageSpan) -> Result<Vec<usize>, Error> {
+    let qubits = arg
+        .unwrap_array()
+        .iter()
+        .map(|q| q.clone().unwrap_qubit().0)
+        .collect::<Vec<_>>();
+    if qubits.len() == qubits.iter().collect::<FxHashSet<_>>().len() {
+        Ok(qubits)
+    } else {
+        Err(Error::QubitUniqueness(arg_span))
+    }
+}
+
 fn unwrap_tuple<const N: usize>(value: Value) -> [Value; N] {
     let values = value.unwrap_tuple();
     array::from_fn(|i| values[i].clone())
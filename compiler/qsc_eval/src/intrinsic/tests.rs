@@ -465,6 +465,83 @@ fn dump_register_qubits_not_unique_fails() {
     );
 }
 
+#[test]
+fn dump_operation_single_qubit() {
+    check_intrinsic_output(
+        "",
+        indoc! {"{
+            Microsoft.Quantum.Diagnostics.DumpOperation(1, qs => H(qs[0]));
+        }"},
+        &expect![[r#"
+            MATRIX:
+            0.7071+0.0000𝑖 0.7071+0.0000𝑖
+            0.7071+0.0000𝑖 −0.7071+0.0000𝑖
+        "#]],
+    );
+}
+
+#[test]
+fn dump_operation_two_qubits() {
+    check_intrinsic_output(
+        "",
+        indoc! {"{
+            Microsoft.Quantum.Diagnostics.DumpOperation(2, qs => CNOT(qs[0], qs[1]));
+        }"},
+        &expect![[r#"
+            MATRIX:
+            1.0000+0.0000𝑖 0.0000+0.0000𝑖 0.0000+0.0000𝑖 0.0000+0.0000𝑖
+            0.0000+0.0000𝑖 1.0000+0.0000𝑖 0.0000+0.0000𝑖 0.0000+0.0000𝑖
+            0.0000+0.0000𝑖 0.0000+0.0000𝑖 0.0000+0.0000𝑖 1.0000+0.0000𝑖
+            0.0000+0.0000𝑖 0.0000+0.0000𝑖 1.0000+0.0000𝑖 0.0000+0.0000𝑖
+        "#]],
+    );
+}
+
+#[test]
+fn dump_operation_normalizes_global_phase() {
+    check_intrinsic_output(
+        "",
+        indoc! {"{
+            Microsoft.Quantum.Diagnostics.DumpOperation(1, qs => Rz(Microsoft.Quantum.Math.PI() / 2.0, qs[0]));
+        }"},
+        &expect![[r#"
+            MATRIX:
+            1.0000+0.0000𝑖 0.0000+0.0000𝑖
+            0.0000+0.0000𝑖 0.0000+1.0000𝑖
+        "#]],
+    );
+}
+
+#[test]
+fn dump_operation_with_other_qubits_allocated() {
+    check_intrinsic_output(
+        "",
+        indoc! {"{
+            use q = Qubit();
+            X(q);
+            Microsoft.Quantum.Diagnostics.DumpOperation(1, qs => S(qs[0]));
+            X(q);
+        }"},
+        &expect![[r#"
+            MATRIX:
+            1.0000+0.0000𝑖 0.0000+0.0000𝑖
+            0.0000+0.0000𝑖 0.0000+1.0000𝑖
+        "#]],
+    );
+}
+
+#[test]
+fn dump_operation_entangled_with_other_qubits_fails() {
+    check_intrinsic_result(
+        "",
+        indoc! {"{
+            use q = Qubit();
+            Microsoft.Quantum.Diagnostics.DumpOperation(1, qs => CNOT(qs[0], q));
+        }"},
+        &expect!["qubits are not separable"],
+    );
+}
+
 #[test]
 fn message() {
     check_intrinsic_output(
@@ -49,6 +49,39 @@ pub fn split_state(
     Ok(dump_state)
 }
 
+/// Given the state of a register holding the Choi state of an operation, that is, the qubits the
+/// operation acted on followed by the same number of reference qubits they were entangled with,
+/// reconstruct the matrix of the operation.
+/// Since the state is only known up to a global phase, the phase is chosen so that the first non-zero
+/// entry of the matrix is a positive real number.
+pub fn state_to_matrix(
+    state: Vec<(BigUint, Complex64)>,
+    qubit_count: usize,
+) -> Vec<Vec<Complex64>> {
+    let dim = 1_usize << qubit_count;
+    let norm = state
+        .iter()
+        .map(|(_, val)| val.norm_sqr())
+        .sum::<f64>()
+        .sqrt();
+    // Each amplitude of the Choi state is an entry of the matrix scaled by `1/√dim`.
+    #[allow(clippy::cast_precision_loss)]
+    let scale = (dim as f64).sqrt() / norm;
+    // The state is sorted by label, so the first entry corresponds to the first non-zero matrix entry.
+    let phase = state
+        .first()
+        .map_or_else(Complex64::one, |(_, val)| val / val.norm());
+
+    let mut matrix = vec![vec![Complex64::zero(); dim]; dim];
+    for (label, val) in state {
+        let index = usize::try_from(&label).expect("matrix index should fit in usize");
+        // The operation qubits make up the high bits of the label, so they select the row,
+        // while the reference qubits select the column.
+        matrix[index / dim][index % dim] = val / phase * scale;
+    }
+    matrix
+}
+
 /// From the qubit identifiers provided, compute the bit masks for the qubits to dump and the remaining qubits.
 /// These masks can be applied to the state labels to separate the label into the two parts needed.
 fn compute_mask(qubit_count: usize, qubits: &[usize]) -> (BigUint, BigUint) {
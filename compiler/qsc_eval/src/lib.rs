@@ -21,7 +21,9 @@ pub mod backend;
 pub mod debug;
 mod error;
 mod intrinsic;
+pub mod limits;
 pub mod output;
+pub mod rir;
 pub mod state;
 pub mod val;
 
@@ -29,14 +31,15 @@ use crate::val::Value;
 use backend::Backend;
 use debug::{CallStack, Frame};
 use error::PackageSpan;
+use limits::{CancellationToken, Interrupt, Limits, Watchdog};
 use miette::Diagnostic;
 use num_bigint::BigInt;
 use output::Receiver;
 use qsc_data_structures::{functors::FunctorApp, index_map::IndexMap, span::Span};
 use qsc_fir::fir::{
-    self, BinOp, CallableImpl, ExecGraphNode, Expr, ExprId, ExprKind, Field, Functor, Global, Lit,
-    LocalItemId, LocalVarId, PackageId, PackageStoreLookup, PatId, PatKind, PrimField, Res, StmtId,
-    StoreItemId, StringComponent, UnOp,
+    self, BinOp, CallableImpl, CallableKind, ExecGraphNode, Expr, ExprId, ExprKind, Field, Functor,
+    Global, Lit, LocalItemId, LocalVarId, PackageId, PackageStoreLookup, PatId, PatKind, PrimField,
+    Res, StmtId, StoreItemId, StringComponent, UnOp,
 };
 use qsc_fir::ty::Ty;
 use qsc_lowerer::map_fir_package_to_hir;
@@ -61,6 +64,18 @@ pub enum Error {
     #[diagnostic(code("Qsc.Eval.InvalidArrayLength"))]
     InvalidArrayLength(i64, #[label("cannot be used as a length")] PackageSpan),
 
+    #[error("array pattern expects {0} items, found {1}")]
+    #[diagnostic(code("Qsc.Eval.ArrayPatternMismatch"))]
+    ArrayPatternMismatch(
+        String,
+        usize,
+        #[label("array does not match this pattern")] PackageSpan,
+    ),
+
+    #[error("evaluation was cancelled")]
+    #[diagnostic(code("Qsc.Eval.Cancelled"))]
+    Cancelled(#[label("cancelled while evaluating this")] PackageSpan),
+
     #[error("division by zero")]
     #[diagnostic(code("Qsc.Eval.DivZero"))]
     DivZero(#[label("cannot divide by zero")] PackageSpan),
@@ -136,6 +151,32 @@ pub enum Error {
     #[diagnostic(code("Qsc.Eval.UnsupportedIntrinsicType"))]
     UnsupportedIntrinsicType(String, #[label] PackageSpan),
 
+    #[error("evaluation exceeded the limit of {0} steps")]
+    #[diagnostic(help("the program may contain an unbounded loop or recursion"))]
+    #[diagnostic(code("Qsc.Eval.StepLimitExceeded"))]
+    StepLimitExceeded(u64, #[label("step limit reached here")] PackageSpan),
+
+    #[error("evaluation exceeded the limit of {0} qubits")]
+    #[diagnostic(code("Qsc.Eval.QubitLimitExceeded"))]
+    QubitLimitExceeded(usize, #[label("qubit limit reached here")] PackageSpan),
+
+    #[error("array length exceeds the limit of {0} items")]
+    #[diagnostic(code("Qsc.Eval.ArrayLengthLimitExceeded"))]
+    ArrayLengthLimitExceeded(usize, #[label("this array has too many items")] PackageSpan),
+
+    #[error("call depth exceeds the limit of {0} frames")]
+    #[diagnostic(help("the program may contain unbounded recursion"))]
+    #[diagnostic(code("Qsc.Eval.CallDepthLimitExceeded"))]
+    CallDepthLimitExceeded(usize, #[label("call depth limit reached here")] PackageSpan),
+
+    #[error("evaluation exceeded the time limit of {0:?}")]
+    #[diagnostic(help("the program may contain an unbounded loop or recursion"))]
+    #[diagnostic(code("Qsc.Eval.TimeLimitExceeded"))]
+    TimeLimitExceeded(
+        std::time::Duration,
+        #[label("time limit reached here")] PackageSpan,
+    ),
+
     #[error("program failed: {0}")]
     #[diagnostic(code("Qsc.Eval.UserFail"))]
     UserFail(String, #[label("explicit fail")] PackageSpan),
@@ -146,6 +187,10 @@ impl Error {
     pub fn span(&self) -> &PackageSpan {
         match self {
             Error::ArrayTooLarge(span)
+            | Error::ArrayLengthLimitExceeded(_, span)
+            | Error::ArrayPatternMismatch(_, _, span)
+            | Error::CallDepthLimitExceeded(_, span)
+            | Error::Cancelled(span)
             | Error::DivZero(span)
             | Error::EmptyRange(span)
             | Error::IndexOutOfRange(_, span)
@@ -155,11 +200,14 @@ impl Error {
             | Error::InvalidRotationAngle(_, span)
             | Error::InvalidNegativeInt(_, span)
             | Error::OutputFail(span)
+            | Error::QubitLimitExceeded(_, span)
             | Error::QubitUniqueness(span)
             | Error::QubitsNotSeparable(span)
             | Error::RangeStepZero(span)
             | Error::ReleasedQubitNotZero(_, span)
             | Error::ResultComparisonUnsupported(span)
+            | Error::StepLimitExceeded(_, span)
+            | Error::TimeLimitExceeded(_, span)
             | Error::UnboundName(span)
             | Error::UnknownIntrinsic(_, span)
             | Error::UnsupportedIntrinsicType(_, span)
@@ -318,24 +366,42 @@ impl Range {
     }
 }
 
-pub struct Env(Vec<Scope>);
+pub struct Env {
+    scopes: Vec<Scope>,
+    /// The number of qubits currently allocated. It is kept with the environment rather than
+    /// the evaluator state so that qubits allocated by earlier evaluations sharing the same
+    /// environment keep counting towards the qubit limit.
+    live_qubits: usize,
+}
 
 impl Default for Env {
     #[must_use]
     fn default() -> Self {
         // Always create a global scope for top-level statements.
-        Self(vec![Scope::default()])
+        Self {
+            scopes: vec![Scope::default()],
+            live_qubits: 0,
+        }
     }
 }
 
 impl Env {
+    /// Gets the variable bound to a local in the innermost scope that binds it.
+    /// This is public so that partial evaluation, which evaluates classical code with this environment, can read the
+    /// locals that the evaluator assigned.
     #[must_use]
-    fn get(&self, id: LocalVarId) -> Option<&Variable> {
-        self.0.iter().rev().find_map(|scope| scope.bindings.get(id))
+    pub fn get(&self, id: LocalVarId) -> Option<&Variable> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.bindings.get(id))
     }
 
-    fn get_mut(&mut self, id: LocalVarId) -> Option<&mut Variable> {
-        self.0
+    /// Gets the variable bound to a local in the innermost scope that binds it, for updating its value in place.
+    /// This is public so that partial evaluation can rebind mutable locals to the runtime variables that hold them,
+    /// which has to update the scope the local was bound in rather than shadow it in the top frame.
+    pub fn get_mut(&mut self, id: LocalVarId) -> Option<&mut Variable> {
+        self.scopes
             .iter_mut()
             .rev()
             .find_map(|scope| scope.bindings.get_mut(id))
@@ -346,21 +412,21 @@ impl Env {
             frame_id,
             ..Default::default()
         };
-        self.0.push(scope);
+        self.scopes.push(scope);
     }
 
     pub fn leave_scope(&mut self) {
         // Only pop the scope if there is more than one scope in the stack,
         // because the global/top-level scope cannot be exited.
-        if self.0.len() > 1 {
-            self.0
+        if self.scopes.len() > 1 {
+            self.scopes
                 .pop()
                 .expect("scope should have more than one entry.");
         }
     }
 
     pub fn bind_variable_in_top_frame(&mut self, local_var_id: LocalVarId, var: Variable) {
-        let Some(scope) = self.0.last_mut() else {
+        let Some(scope) = self.scopes.last_mut() else {
             panic!("no frames in scope");
         };
 
@@ -369,7 +435,7 @@ impl Env {
 
     #[must_use]
     pub fn get_variables_in_top_frame(&self) -> Vec<VariableInfo> {
-        if let Some(scope) = self.0.last() {
+        if let Some(scope) = self.scopes.last() {
             self.get_variables_in_frame(scope.frame_id)
         } else {
             vec![]
@@ -379,7 +445,7 @@ impl Env {
     #[must_use]
     pub fn get_variables_in_frame(&self, frame_id: usize) -> Vec<VariableInfo> {
         let candidate_scopes: Vec<_> = self
-            .0
+            .scopes
             .iter()
             .filter(|scope| scope.frame_id == frame_id)
             .map(|scope| scope.bindings.iter())
@@ -401,10 +467,18 @@ impl Env {
         variables_by_scope.into_iter().flatten().collect::<Vec<_>>()
     }
 
+    fn allocate_qubit(&mut self) {
+        self.live_qubits += 1;
+    }
+
+    fn release_qubit(&mut self) {
+        self.live_qubits = self.live_qubits.saturating_sub(1);
+    }
+
     #[allow(clippy::len_without_is_empty)]
     #[must_use]
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.scopes.len()
     }
 }
 
@@ -425,6 +499,10 @@ pub struct State {
     call_stack: CallStack,
     current_span: Span,
     rng: RefCell<StdRng>,
+    watchdog: Watchdog,
+    record_output: bool,
+    /// The number of qubits allocated in the environment when evaluation started.
+    initial_live_qubits: Option<usize>,
 }
 
 impl State {
@@ -449,9 +527,35 @@ impl State {
             call_stack: CallStack::default(),
             current_span: Span::default(),
             rng,
+            watchdog: Watchdog::default(),
+            record_output: false,
+            initial_live_qubits: None,
         }
     }
 
+    /// Sets a token that is checked between execution graph nodes, allowing the evaluation
+    /// to be stopped with [`Error::Cancelled`] from outside the evaluator.
+    #[must_use]
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.watchdog.set_token(token);
+        self
+    }
+
+    /// Sets the limits applied to each call to [`State::eval`].
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.watchdog.set_limits(limits);
+        self
+    }
+
+    /// Reports the value returned at the end of evaluation to the receiver as output records,
+    /// as is done for the return value of an entry point.
+    #[must_use]
+    pub fn with_output_recording(mut self) -> Self {
+        self.record_output = true;
+        self
+    }
+
     fn push_frame(
         &mut self,
         exec_graph: Rc<[ExecGraphNode]>,
@@ -471,13 +575,15 @@ impl State {
         self.package = id.package;
     }
 
-    fn leave_frame(&mut self) {
-        if let Some(frame) = self.call_stack.pop_frame() {
+    fn leave_frame(&mut self) -> Option<Frame> {
+        let frame = self.call_stack.pop_frame();
+        if let Some(frame) = frame {
             self.package = frame.caller;
         };
         self.val_stack.pop();
         self.idx = self.idx_stack.pop().unwrap_or_default();
         self.exec_graph_stack.pop();
+        frame
     }
 
     fn push_scope(&mut self, env: &mut Env) {
@@ -539,10 +645,35 @@ impl State {
         out: &mut impl Receiver,
         breakpoints: &[StmtId],
         step: StepAction,
+    ) -> Result<StepResult, (Error, Vec<Frame>)> {
+        let initial_live_qubits = *self.initial_live_qubits.get_or_insert(env.live_qubits);
+        let res = self.eval_steps(globals, env, sim, out, breakpoints, step);
+        if res.is_err() {
+            // The qubits allocated by a failed evaluation are never released, so they stop counting
+            // towards the qubit limit of later evaluations that share the environment.
+            env.live_qubits = initial_live_qubits;
+        }
+        res
+    }
+
+    fn eval_steps(
+        &mut self,
+        globals: &impl PackageStoreLookup,
+        env: &mut Env,
+        sim: &mut impl Backend<ResultType = impl Into<val::Result>>,
+        out: &mut impl Receiver,
+        breakpoints: &[StmtId],
+        step: StepAction,
     ) -> Result<StepResult, (Error, Vec<Frame>)> {
         let current_frame = self.call_stack.len();
+        self.watchdog.start();
 
         while !self.exec_graph_stack.is_empty() {
+            if let Some(interrupt) = self.watchdog.tick() {
+                let error = self.interrupt_error(interrupt, self.current_span);
+                return Err((error, self.get_stack_frames()));
+            }
+
             let exec_graph = self
                 .exec_graph_stack
                 .last()
@@ -550,7 +681,8 @@ impl State {
             let res = match exec_graph.get(self.idx as usize) {
                 Some(ExecGraphNode::Bind(pat)) => {
                     self.idx += 1;
-                    self.eval_bind(env, globals, *pat);
+                    self.eval_bind(env, globals, *pat)
+                        .map_err(|e| (e, self.get_stack_frames()))?;
                     continue;
                 }
                 Some(ExecGraphNode::Expr(expr)) => {
@@ -601,8 +733,8 @@ impl State {
                     continue;
                 }
                 Some(ExecGraphNode::Ret) => {
-                    self.leave_frame();
-                    env.leave_scope();
+                    self.eval_ret(env, globals, out)
+                        .map_err(|e| (e, self.get_stack_frames()))?;
                     continue;
                 }
                 Some(ExecGraphNode::PushScope) => {
@@ -633,7 +765,36 @@ impl State {
             return Ok(res);
         }
 
-        Ok(StepResult::Return(self.get_result()))
+        let value = self.get_result();
+        if self.record_output {
+            self.record_output(&value, out)
+                .map_err(|e| (e, Vec::new()))?;
+        }
+        Ok(StepResult::Return(value))
+    }
+
+    fn eval_ret(
+        &mut self,
+        env: &mut Env,
+        globals: &impl PackageStoreLookup,
+        out: &mut impl Receiver,
+    ) -> Result<(), Error> {
+        if let Some(frame) = self.leave_frame() {
+            if let Some(name) = operation_name(globals, frame.id) {
+                out.operation_end(&name, frame.functor)
+                    .map_err(|_| Error::OutputFail(self.to_global_span(self.current_span)))?;
+            }
+        }
+        env.leave_scope();
+        Ok(())
+    }
+
+    fn record_output(&self, value: &Value, out: &mut impl Receiver) -> Result<(), Error> {
+        for record in output::output_records(value).unwrap_or_default() {
+            out.output_record(record)
+                .map_err(|_| Error::OutputFail(self.to_global_span(self.current_span)))?;
+        }
+        Ok(())
     }
 
     fn check_for_break(
@@ -731,6 +892,7 @@ impl State {
                 self.eval_binop(*op, rhs_span)?;
             }
             ExprKind::Block(..) => panic!("block expr should be handled by control flow"),
+            ExprKind::Break => panic!("break expr should be handled by control flow"),
             ExprKind::Call(callee_expr, args_expr) => {
                 let callable_span = globals.get_expr((self.package, *callee_expr).into()).span;
                 let args_span = globals.get_expr((self.package, *args_expr).into()).span;
@@ -746,6 +908,7 @@ impl State {
                     self.to_global_span(expr.span),
                 ));
             }
+            ExprKind::Continue => panic!("continue expr should be handled by control flow"),
             ExprKind::Field(_, field) => self.eval_field(field.clone()),
             ExprKind::Hole => panic!("hole expr should be disallowed by passes"),
             ExprKind::If(..) => {
@@ -833,6 +996,9 @@ impl State {
         match (&lhs.kind, rhs) {
             (&ExprKind::Var(Res::Local(id), _), rhs) => match env.get_mut(id) {
                 Some(var) => {
+                    if let (Value::Array(arr), Value::Array(append_arr)) = (&var.value, &rhs) {
+                        self.check_array_len(arr.len() + append_arr.len(), lhs.span)?;
+                    }
                     var.value.append_array(rhs);
                 }
                 None => return Err(Error::UnboundName(self.to_global_span(lhs.span))),
@@ -852,6 +1018,7 @@ impl State {
                 self.to_global_span(span),
             )),
         }?;
+        self.check_array_len(s, span)?;
         self.set_val_register(Value::Array(vec![item_val; s].into()));
         Ok(())
     }
@@ -866,14 +1033,27 @@ impl State {
         self.update_binding(env, globals, lhs, rhs)
     }
 
-    fn eval_bind(&mut self, env: &mut Env, globals: &impl PackageStoreLookup, pat: PatId) {
+    fn eval_bind(
+        &mut self,
+        env: &mut Env,
+        globals: &impl PackageStoreLookup,
+        pat: PatId,
+    ) -> Result<(), Error> {
         let val = self.take_val_register();
-        self.bind_value(env, globals, pat, val);
+        self.bind_value(env, globals, pat, val)
     }
 
     fn eval_binop(&mut self, op: BinOp, span: Span) -> Result<(), Error> {
         match op {
-            BinOp::Add => self.eval_binop_simple(eval_binop_add),
+            BinOp::Add => {
+                if let (Some(Value::Array(rhs)), Some(Value::Array(lhs))) = (
+                    &self.val_register,
+                    self.val_stack.last().and_then(|vals| vals.last()),
+                ) {
+                    self.check_array_len(lhs.len() + rhs.len(), span)?;
+                }
+                self.eval_binop_simple(eval_binop_add);
+            }
             BinOp::AndB => self.eval_binop_simple(eval_binop_andb),
             BinOp::Div => self.eval_binop_with_error(span, eval_binop_div)?,
             BinOp::Eq => self.eval_binop_with_error(span, eval_binop_eq)?,
@@ -944,12 +1124,27 @@ impl State {
 
         let callee_span = self.to_global_span(callee.span);
 
+        if let Some(interrupt) = self.watchdog.check_call_depth(self.call_stack.len() + 1) {
+            return Err(self.interrupt_error(interrupt, callable_span));
+        }
+
+        if let Some(name) = operation_name(globals, callee_id) {
+            out.operation_start(&name, functor)
+                .map_err(|_| Error::OutputFail(self.to_global_span(callable_span)))?;
+        }
+
         let spec = spec_from_functor_app(functor);
         match &callee.implementation {
             CallableImpl::Intrinsic => {
+                let name = &callee.name.name;
+                if name.as_ref() == "__quantum__rt__qubit_allocate" {
+                    if let Some(interrupt) = self.watchdog.check_qubit_count(env.live_qubits) {
+                        return Err(self.interrupt_error(interrupt, callable_span));
+                    }
+                }
+
                 self.push_frame(Vec::new().into(), callee_id, functor);
 
-                let name = &callee.name.name;
                 let val = intrinsic::call(
                     name,
                     callee_span,
@@ -959,6 +1154,12 @@ impl State {
                     &mut self.rng.borrow_mut(),
                     out,
                 )?;
+                // The qubit count only changes once the simulator has accepted the allocation or release.
+                match name.as_ref() {
+                    "__quantum__rt__qubit_allocate" => env.allocate_qubit(),
+                    "__quantum__rt__qubit_release" => env.release_qubit(),
+                    _ => {}
+                }
                 if val == Value::unit() && callee.output != Ty::UNIT {
                     return Err(Error::UnsupportedIntrinsicType(
                         callee.name.name.to_string(),
@@ -967,6 +1168,10 @@ impl State {
                 }
                 self.set_val_register(val);
                 self.leave_frame();
+                if callee.kind == CallableKind::Operation {
+                    out.operation_end(name, functor)
+                        .map_err(|_| Error::OutputFail(self.to_global_span(callable_span)))?;
+                }
                 Ok(())
             }
             CallableImpl::Spec(specialized_implementation) => {
@@ -988,8 +1193,7 @@ impl State {
                     arg,
                     functor.controlled,
                     fixed_args,
-                );
-                Ok(())
+                )
             }
         }
     }
@@ -1216,11 +1420,17 @@ impl State {
         self.set_val_register(update);
     }
 
-    fn bind_value(&self, env: &mut Env, globals: &impl PackageStoreLookup, pat: PatId, val: Value) {
+    fn bind_value(
+        &self,
+        env: &mut Env,
+        globals: &impl PackageStoreLookup,
+        pat: PatId,
+        val: Value,
+    ) -> Result<(), Error> {
         let pat = globals.get_pat((self.package, pat).into());
         match &pat.kind {
             PatKind::Bind(variable) => {
-                let scope = env.0.last_mut().expect("binding should have a scope");
+                let scope = env.scopes.last_mut().expect("binding should have a scope");
                 scope.bindings.insert(
                     variable.id,
                     Variable {
@@ -1234,10 +1444,36 @@ impl State {
             PatKind::Tuple(tup) => {
                 let val_tup = val.unwrap_tuple();
                 for (pat, val) in tup.iter().zip(val_tup.iter()) {
-                    self.bind_value(env, globals, *pat, val.clone());
+                    self.bind_value(env, globals, *pat, val.clone())?;
+                }
+            }
+            PatKind::Array(items, rest) => {
+                let val_arr = val.unwrap_array();
+                let len_matches = match rest {
+                    Some(_) => val_arr.len() >= items.len(),
+                    None => val_arr.len() == items.len(),
+                };
+                if !len_matches {
+                    let expected = match rest {
+                        Some(_) => format!("at least {}", items.len()),
+                        None => items.len().to_string(),
+                    };
+                    return Err(Error::ArrayPatternMismatch(
+                        expected,
+                        val_arr.len(),
+                        self.to_global_span(pat.span),
+                    ));
+                }
+                for (pat, val) in items.iter().zip(val_arr.iter()) {
+                    self.bind_value(env, globals, *pat, val.clone())?;
+                }
+                if let Some(rest) = rest {
+                    let rest_val = Value::Array(val_arr[items.len()..].to_vec().into());
+                    self.bind_value(env, globals, *rest, rest_val)?;
                 }
             }
         }
+        Ok(())
     }
 
     #[allow(clippy::similar_names)]
@@ -1343,7 +1579,7 @@ impl State {
         args_val: Value,
         ctl_count: u8,
         fixed_args: Option<Rc<[Value]>>,
-    ) {
+    ) -> Result<(), Error> {
         match spec_pat {
             Some(spec_pat) => {
                 assert!(
@@ -1361,8 +1597,8 @@ impl State {
                     tup = rest.clone();
                 }
 
-                self.bind_value(env, globals, spec_pat, Value::Array(ctls.into()));
-                self.bind_value(env, globals, decl_pat, merge_fixed_args(fixed_args, tup));
+                self.bind_value(env, globals, spec_pat, Value::Array(ctls.into()))?;
+                self.bind_value(env, globals, decl_pat, merge_fixed_args(fixed_args, tup))
             }
             None => self.bind_value(
                 env,
@@ -1379,6 +1615,39 @@ impl State {
             span,
         }
     }
+
+    fn interrupt_error(&self, interrupt: Interrupt, span: Span) -> Error {
+        let span = self.to_global_span(span);
+        match interrupt {
+            Interrupt::Cancelled => Error::Cancelled(span),
+            Interrupt::StepLimit(max_steps) => Error::StepLimitExceeded(max_steps, span),
+            Interrupt::TimeLimit(time_limit) => Error::TimeLimitExceeded(time_limit, span),
+            Interrupt::QubitLimit(max_qubits) => Error::QubitLimitExceeded(max_qubits, span),
+            Interrupt::ArrayLengthLimit(max_array_len) => {
+                Error::ArrayLengthLimitExceeded(max_array_len, span)
+            }
+            Interrupt::CallDepthLimit(max_call_depth) => {
+                Error::CallDepthLimitExceeded(max_call_depth, span)
+            }
+        }
+    }
+
+    fn check_array_len(&self, len: usize, span: Span) -> Result<(), Error> {
+        match self.watchdog.check_array_len(len) {
+            Some(interrupt) => Err(self.interrupt_error(interrupt, span)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns the name of the callable if it is an operation, so that calls to it can be reported.
+fn operation_name(globals: &impl PackageStoreLookup, id: StoreItemId) -> Option<Rc<str>> {
+    match globals.get_global(id) {
+        Some(Global::Callable(decl)) if decl.kind == CallableKind::Operation => {
+            Some(decl.name.name.clone())
+        }
+        _ => None,
+    }
 }
 
 fn merge_fixed_args(fixed_args: Option<Rc<[Value]>>, arg: Value) -> Value {
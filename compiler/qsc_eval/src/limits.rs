@@ -0,0 +1,169 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// How many steps are taken between checks of the time limit, since reading the clock
+/// is much more expensive than executing a single node of the execution graph.
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+/// A handle that can be used to request that a running evaluation stop.
+/// Clones share the same underlying flag, so a token can be handed to another thread
+/// (or a signal handler) and cancelled from there.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation. The evaluator stops before executing the next node.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Clears a previous cancellation request so the token can be reused.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Bounds on the work a single call to the evaluator may do before it is stopped with an error.
+/// A `None` value means that no limit is applied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of execution graph nodes to evaluate.
+    pub max_steps: Option<u64>,
+    /// The maximum wall-clock time to spend evaluating.
+    /// Not supported on targets without a system clock, such as `wasm32-unknown-unknown`.
+    pub time_limit: Option<Duration>,
+    /// The maximum number of qubits allocated at the same time.
+    pub max_qubits: Option<usize>,
+    /// The maximum length of any array created during evaluation by a repeat expression,
+    /// concatenation or append. These are the only constructions that can produce an array
+    /// longer than both the program text and the arrays that already exist, since literals,
+    /// slices and copy-and-update expressions never do.
+    pub max_array_len: Option<usize>,
+    /// The maximum depth of the call stack.
+    pub max_call_depth: Option<usize>,
+}
+
+/// The reason an evaluation was interrupted.
+#[derive(Clone, Copy)]
+pub(crate) enum Interrupt {
+    Cancelled,
+    StepLimit(u64),
+    TimeLimit(Duration),
+    QubitLimit(usize),
+    ArrayLengthLimit(usize),
+    CallDepthLimit(usize),
+}
+
+/// Reading the clock panics on `wasm32-unknown-unknown`, so the time limit is ignored there.
+#[cfg(not(target_arch = "wasm32"))]
+fn deadline(time_limit: Option<Duration>) -> Option<Instant> {
+    time_limit.map(|time_limit| Instant::now() + time_limit)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn deadline(_time_limit: Option<Duration>) -> Option<Instant> {
+    None
+}
+
+/// Tracks the progress of an evaluation against its cancellation token and limits.
+#[derive(Default)]
+pub(crate) struct Watchdog {
+    token: Option<CancellationToken>,
+    limits: Limits,
+    steps: u64,
+    deadline: Option<Instant>,
+}
+
+impl Watchdog {
+    pub(crate) fn set_token(&mut self, token: CancellationToken) {
+        self.token = Some(token);
+    }
+
+    pub(crate) fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// Restarts the step count and the clock for a new call to the evaluator.
+    pub(crate) fn start(&mut self) {
+        self.steps = 0;
+        self.deadline = deadline(self.limits.time_limit);
+    }
+
+    /// Records one evaluation step, returning the reason evaluation should stop, if any.
+    pub(crate) fn tick(&mut self) -> Option<Interrupt> {
+        if self
+            .token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Some(Interrupt::Cancelled);
+        }
+
+        self.steps += 1;
+        if let Some(max_steps) = self.limits.max_steps {
+            if self.steps > max_steps {
+                return Some(Interrupt::StepLimit(max_steps));
+            }
+        }
+
+        if let Some(deadline) = self.deadline {
+            if self.steps.is_multiple_of(TIME_CHECK_INTERVAL) && Instant::now() >= deadline {
+                return Some(Interrupt::TimeLimit(
+                    self.limits
+                        .time_limit
+                        .expect("time limit should be set when there is a deadline"),
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// Checks the number of qubits that are live before allocating another one.
+    pub(crate) fn check_qubit_count(&self, live_qubits: usize) -> Option<Interrupt> {
+        match self.limits.max_qubits {
+            Some(max_qubits) if live_qubits >= max_qubits => {
+                Some(Interrupt::QubitLimit(max_qubits))
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks the length of an array that is about to be created.
+    pub(crate) fn check_array_len(&self, len: usize) -> Option<Interrupt> {
+        match self.limits.max_array_len {
+            Some(max_array_len) if len > max_array_len => {
+                Some(Interrupt::ArrayLengthLimit(max_array_len))
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks the depth the call stack will have after entering a callable.
+    pub(crate) fn check_call_depth(&self, depth: usize) -> Option<Interrupt> {
+        match self.limits.max_call_depth {
+            Some(max_call_depth) if depth > max_call_depth => {
+                Some(Interrupt::CallDepthLimit(max_call_depth))
+            }
+            _ => None,
+        }
+    }
+}
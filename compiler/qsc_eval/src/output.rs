@@ -1,15 +1,119 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::io::{Cursor, Write};
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{Cursor, Write},
+};
 
-use crate::state::{fmt_complex, format_state_id};
+use crate::{
+    state::{fmt_complex, format_state_id},
+    val::{self, Value},
+};
 use num_bigint::BigUint;
 use num_complex::Complex64;
+use qsc_data_structures::functors::FunctorApp;
 
 #[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Error;
 
+/// A record in the output recording schema used by QIR programs. The value returned by
+/// an entry point is reported as a sequence of these records, with each tuple or array
+/// record followed by the records for its items.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputRecord {
+    Tuple(usize),
+    Array(usize),
+    Result(val::Result),
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+}
+
+impl Display for OutputRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            OutputRecord::Tuple(len) => write!(f, "OUTPUT\tTUPLE\t{len}"),
+            OutputRecord::Array(len) => write!(f, "OUTPUT\tARRAY\t{len}"),
+            OutputRecord::Result(val::Result::Val(v)) => {
+                write!(f, "OUTPUT\tRESULT\t{}", u8::from(*v))
+            }
+            OutputRecord::Result(val::Result::Id(id)) => write!(f, "OUTPUT\tRESULT\tr{id}"),
+            OutputRecord::Bool(v) => write!(f, "OUTPUT\tBOOL\t{v}"),
+            OutputRecord::Int(v) => write!(f, "OUTPUT\tINT\t{v}"),
+            OutputRecord::Double(v) => write!(f, "OUTPUT\tDOUBLE\t{v:?}"),
+        }
+    }
+}
+
+/// Converts a value into output records, or returns `None` if the value contains
+/// items that the output recording schema cannot represent, such as strings or qubits.
+#[must_use]
+pub fn output_records(value: &Value) -> Option<Vec<OutputRecord>> {
+    let mut records = Vec::new();
+    push_output_records(value, &mut records).then_some(records)
+}
+
+/// Converts output records back into the value they record, or returns `None` if the records
+/// do not describe exactly one value.
+#[must_use]
+pub fn output_value(records: &[OutputRecord]) -> Option<Value> {
+    let mut records = records.iter();
+    let value = pop_output_value(&mut records)?;
+    records.next().is_none().then_some(value)
+}
+
+fn pop_output_value<'a>(records: &mut impl Iterator<Item = &'a OutputRecord>) -> Option<Value> {
+    Some(match records.next()? {
+        OutputRecord::Tuple(len) => Value::Tuple(
+            (0..*len)
+                .map(|_| pop_output_value(records))
+                .collect::<Option<Vec<_>>>()?
+                .into(),
+        ),
+        OutputRecord::Array(len) => Value::Array(
+            (0..*len)
+                .map(|_| pop_output_value(records))
+                .collect::<Option<Vec<_>>>()?
+                .into(),
+        ),
+        OutputRecord::Result(r) => Value::Result(*r),
+        OutputRecord::Bool(b) => Value::Bool(*b),
+        OutputRecord::Int(i) => Value::Int(*i),
+        OutputRecord::Double(d) => Value::Double(*d),
+    })
+}
+
+fn push_output_records(value: &Value, records: &mut Vec<OutputRecord>) -> bool {
+    match value {
+        Value::Array(arr) => {
+            records.push(OutputRecord::Array(arr.len()));
+            arr.iter().all(|item| push_output_records(item, records))
+        }
+        Value::Tuple(tup) => {
+            records.push(OutputRecord::Tuple(tup.len()));
+            tup.iter().all(|item| push_output_records(item, records))
+        }
+        Value::Result(r) => {
+            records.push(OutputRecord::Result(*r));
+            true
+        }
+        Value::Bool(b) => {
+            records.push(OutputRecord::Bool(*b));
+            true
+        }
+        Value::Int(i) => {
+            records.push(OutputRecord::Int(*i));
+            true
+        }
+        Value::Double(d) => {
+            records.push(OutputRecord::Double(*d));
+            true
+        }
+        _ => false,
+    }
+}
+
 pub trait Receiver {
     /// Receive state output
     /// # Errors
@@ -20,6 +124,48 @@ pub trait Receiver {
     /// # Errors
     /// This will return an error if handling the output fails.
     fn message(&mut self, msg: &str) -> Result<(), Error>;
+
+    /// Receive the unitary matrix of an operation, with rows in order.
+    /// By default, the matrix is formatted as a message.
+    /// # Errors
+    /// This will return an error if handling the output fails.
+    fn matrix(&mut self, matrix: Vec<Vec<Complex64>>) -> Result<(), Error> {
+        let mut msg = String::from("MATRIX:");
+        for row in matrix {
+            msg.push('\n');
+            let row = row.iter().map(fmt_complex).collect::<Vec<_>>();
+            msg.push_str(&row.join(" "));
+        }
+        self.message(&msg)
+    }
+
+    /// Receive the result of measuring a qubit. Ignored by default.
+    /// # Errors
+    /// This will return an error if handling the output fails.
+    fn measurement(&mut self, _qubit: usize, _result: val::Result) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Receive notice that a call to an operation has started. Ignored by default.
+    /// # Errors
+    /// This will return an error if handling the output fails.
+    fn operation_start(&mut self, _name: &str, _functor: FunctorApp) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Receive notice that a call to an operation has returned. Ignored by default.
+    /// # Errors
+    /// This will return an error if handling the output fails.
+    fn operation_end(&mut self, _name: &str, _functor: FunctorApp) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Receive a record of the value returned by the entry point. Ignored by default.
+    /// # Errors
+    /// This will return an error if handling the output fails.
+    fn output_record(&mut self, _record: OutputRecord) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 pub struct GenericReceiver<'a> {
@@ -0,0 +1,808 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Execution of RIR programs, such as those produced by partial evaluation, on a quantum backend.
+//! The qubits and results of the program are mapped onto backend qubits and measurement results,
+//! and the calls to output recording intrinsics are turned into the output records of the program.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    backend::Backend,
+    output::{OutputRecord, Receiver},
+    val::{self, Qubit, Value},
+};
+use miette::Diagnostic;
+use qsc_rir::rir::{
+    BlockId, Callable, CallableId, CallableType, ConditionCode, FcmpConditionCode, Instruction,
+    Literal, Operand, Program, Variable, VariableId,
+};
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Diagnostic, Error)]
+pub enum Error {
+    #[error("division by zero")]
+    #[diagnostic(code("Qsc.Eval.Rir.DivZero"))]
+    DivZero,
+
+    #[error("integer too large for operation: {0}")]
+    #[diagnostic(code("Qsc.Eval.Rir.IntTooLarge"))]
+    IntTooLarge(i64),
+
+    #[error("invalid rotation angle: {0}")]
+    #[diagnostic(code("Qsc.Eval.Rir.InvalidRotationAngle"))]
+    InvalidRotationAngle(f64),
+
+    #[error("qubits in invocation are not unique")]
+    #[diagnostic(code("Qsc.Eval.Rir.QubitUniqueness"))]
+    QubitUniqueness,
+
+    #[error("qubit {0} is out of range for a program using {1} qubits")]
+    #[diagnostic(code("Qsc.Eval.Rir.QubitOutOfRange"))]
+    QubitOutOfRange(u32, u32),
+
+    #[error("qubit {0} is used after it is released")]
+    #[diagnostic(code("Qsc.Eval.Rir.QubitReleased"))]
+    QubitReleased(u32),
+
+    #[error("index {0} is out of range for an array of length {1}")]
+    #[diagnostic(code("Qsc.Eval.Rir.IndexOutOfRange"))]
+    IndexOutOfRange(i64, usize),
+
+    #[error("result {0} is out of range for a program using {1} results")]
+    #[diagnostic(code("Qsc.Eval.Rir.ResultOutOfRange"))]
+    ResultOutOfRange(u32, u32),
+
+    #[error("result {0} is read before it is measured")]
+    #[diagnostic(code("Qsc.Eval.Rir.ResultNotMeasured"))]
+    ResultNotMeasured(u32),
+
+    #[error("the value of result {0} is not known to the backend")]
+    #[diagnostic(code("Qsc.Eval.Rir.ResultValueUnknown"))]
+    #[diagnostic(help(
+        "backends that do not simulate measurements cannot run programs that branch on results"
+    ))]
+    ResultValueUnknown(u32),
+
+    #[error("expected {0} operand, found {1}")]
+    #[diagnostic(code("Qsc.Eval.Rir.TypeMismatch"))]
+    TypeMismatch(&'static str, String),
+
+    #[error("callable {0} is not defined")]
+    #[diagnostic(code("Qsc.Eval.Rir.UnknownCallable"))]
+    UnknownCallable(u32),
+
+    #[error("block {0} is not defined")]
+    #[diagnostic(code("Qsc.Eval.Rir.UnknownBlock"))]
+    UnknownBlock(u32),
+
+    #[error("variable {0} is used before it is assigned")]
+    #[diagnostic(code("Qsc.Eval.Rir.UnassignedVariable"))]
+    UnassignedVariable(u32),
+
+    #[error("block {0} has no phi value for its predecessor")]
+    #[diagnostic(code("Qsc.Eval.Rir.MissingPhiValue"))]
+    MissingPhiValue(u32),
+
+    #[error("intrinsic callable `{0}` failed: {1}")]
+    #[diagnostic(code("Qsc.Eval.Rir.IntrinsicFail"))]
+    IntrinsicFail(String, String),
+
+    #[error("unknown intrinsic `{0}`")]
+    #[diagnostic(code("Qsc.Eval.Rir.UnknownIntrinsic"))]
+    UnknownIntrinsic(String),
+
+    #[error("unsupported call to `{0}`: {1}")]
+    #[diagnostic(code("Qsc.Eval.Rir.UnsupportedCall"))]
+    UnsupportedCall(String, &'static str),
+
+    #[error("output failure")]
+    #[diagnostic(code("Qsc.Eval.Rir.OutputFail"))]
+    OutputFail,
+}
+
+/// Executes the entry point of the given program on the backend, and returns the records produced by
+/// its calls to output recording intrinsics. The records, as well as the measurements performed by the
+/// program, are also reported to the receiver.
+/// # Errors
+/// Returns the first error encountered while executing the program.
+pub fn exec(
+    program: &Program,
+    sim: &mut dyn Backend<ResultType = impl Into<val::Result>>,
+    out: &mut dyn Receiver,
+) -> Result<Vec<OutputRecord>, Error> {
+    let qubits = (0..program.num_qubits)
+        .map(|_| Some(sim.qubit_allocate()))
+        .collect();
+    let mut interpreter = Interpreter {
+        program,
+        sim,
+        out,
+        qubits,
+        results: vec![None; program.num_results as usize],
+        variables: FxHashMap::default(),
+        pointers: FxHashMap::default(),
+        records: Vec::new(),
+    };
+    let result = interpreter.exec_entry();
+    for qubit in std::mem::take(&mut interpreter.qubits)
+        .into_iter()
+        .flatten()
+    {
+        interpreter.sim.qubit_release(qubit);
+    }
+    result.map(|()| interpreter.records)
+}
+
+struct Interpreter<'a, 'b, R> {
+    program: &'a Program,
+    sim: &'b mut dyn Backend<ResultType = R>,
+    out: &'b mut dyn Receiver,
+    /// The backend qubit for each qubit in the program, followed by the qubits allocated at runtime.
+    /// Qubits released at runtime no longer have a backend qubit.
+    qubits: Vec<Option<usize>>,
+    /// The value of each result in the program, once it has been measured.
+    results: Vec<Option<val::Result>>,
+    variables: FxHashMap<VariableId, Literal>,
+    /// What each pointer variable points to, since pointer literals do not carry an address.
+    pointers: FxHashMap<VariableId, Pointer>,
+    records: Vec<OutputRecord>,
+}
+
+/// The target of a pointer returned by the array runtime functions.
+#[derive(Clone, Debug)]
+enum Pointer {
+    /// An array of qubits allocated at runtime.
+    QubitArray(Vec<u32>),
+    /// An item of an array of qubits.
+    Qubit(u32),
+}
+
+impl<'a, R: Into<val::Result>> Interpreter<'a, '_, R> {
+    fn exec_entry(&mut self) -> Result<(), Error> {
+        let entry = self.callable(self.program.entry)?;
+        let Some(block_id) = entry.body else {
+            return Err(Error::UnsupportedCall(
+                entry.name.clone(),
+                "entry point should have a body",
+            ));
+        };
+        self.exec_block(block_id)
+    }
+
+    fn exec_block(&mut self, mut block_id: BlockId) -> Result<(), Error> {
+        let mut predecessor = None;
+        loop {
+            let mut next = None;
+            // The phi nodes at the start of a block all read the values from the end of the predecessor, so they
+            // are only assigned once all of them have been evaluated.
+            let mut phi_values = Vec::new();
+            let mut phi_pointers = Vec::new();
+            let block = self
+                .program
+                .blocks
+                .get(block_id)
+                .ok_or(Error::UnknownBlock(block_id.0))?;
+            for instr in &block.0 {
+                if !matches!(instr, Instruction::Phi(..)) {
+                    self.variables.extend(phi_values.drain(..));
+                    self.pointers.extend(phi_pointers.drain(..));
+                }
+                match instr {
+                    Instruction::Jump(target) => next = Some(*target),
+                    Instruction::Branch(cond, if_true, if_false) => {
+                        next = Some(if self.bool(&Operand::Variable(*cond))? {
+                            *if_true
+                        } else {
+                            *if_false
+                        });
+                    }
+                    Instruction::Return => return Ok(()),
+                    Instruction::Phi(args, var) => {
+                        let Some((value, _)) =
+                            args.iter().find(|(_, pred)| Some(*pred) == predecessor)
+                        else {
+                            return Err(Error::MissingPhiValue(block_id.0));
+                        };
+                        phi_values.push((var.variable_id, self.eval(value)?));
+                        if let Some(pointer) = self.operand_pointer(value) {
+                            phi_pointers.push((var.variable_id, pointer.clone()));
+                        }
+                    }
+                    _ => self.exec_instr(instr)?,
+                }
+                if next.is_some() {
+                    break;
+                }
+            }
+            let Some(next) = next else {
+                // A block without a terminator ends the program.
+                return Ok(());
+            };
+            predecessor = Some(block_id);
+            block_id = next;
+        }
+    }
+
+    fn exec_instr(&mut self, instr: &Instruction) -> Result<(), Error> {
+        let (value, var) = match instr {
+            Instruction::Store(value, var) => {
+                if let Some(pointer) = self.operand_pointer(value) {
+                    self.pointers.insert(var.variable_id, pointer.clone());
+                }
+                (self.eval(value)?, *var)
+            }
+            Instruction::Call(callable_id, args, var)
+                if is_array_call(self.callable(*callable_id)?) =>
+            {
+                return self.array_call(self.callable(*callable_id)?, args, *var);
+            }
+            Instruction::Call(callable_id, args, var) => {
+                let value = self.call(*callable_id, args)?;
+                match (var, value) {
+                    (Some(var), Some(value)) => (value, *var),
+                    _ => return Ok(()),
+                }
+            }
+            Instruction::Add(lhs, rhs, var) => {
+                self.int_binop(lhs, rhs, *var, |lhs, rhs| Ok(lhs.wrapping_add(rhs)))?
+            }
+            Instruction::Sub(lhs, rhs, var) => {
+                self.int_binop(lhs, rhs, *var, |lhs, rhs| Ok(lhs.wrapping_sub(rhs)))?
+            }
+            Instruction::Mul(lhs, rhs, var) => {
+                self.int_binop(lhs, rhs, *var, |lhs, rhs| Ok(lhs.wrapping_mul(rhs)))?
+            }
+            Instruction::Sdiv(lhs, rhs, var) => self.int_binop(lhs, rhs, *var, |lhs, rhs| {
+                if rhs == 0 {
+                    Err(Error::DivZero)
+                } else {
+                    Ok(lhs.wrapping_div(rhs))
+                }
+            })?,
+            Instruction::Srem(lhs, rhs, var) => self.int_binop(lhs, rhs, *var, |lhs, rhs| {
+                if rhs == 0 {
+                    Err(Error::DivZero)
+                } else {
+                    Ok(lhs.wrapping_rem(rhs))
+                }
+            })?,
+            Instruction::Shl(lhs, rhs, var) => self.int_binop(lhs, rhs, *var, shl)?,
+            Instruction::Ashr(lhs, rhs, var) => {
+                self.int_binop(lhs, rhs, *var, |lhs, rhs| shl(lhs, rhs.wrapping_neg()))?
+            }
+            Instruction::BitwiseAnd(lhs, rhs, var) => {
+                self.int_binop(lhs, rhs, *var, |lhs, rhs| Ok(lhs & rhs))?
+            }
+            Instruction::BitwiseOr(lhs, rhs, var) => {
+                self.int_binop(lhs, rhs, *var, |lhs, rhs| Ok(lhs | rhs))?
+            }
+            Instruction::BitwiseXor(lhs, rhs, var) => {
+                self.int_binop(lhs, rhs, *var, |lhs, rhs| Ok(lhs ^ rhs))?
+            }
+            Instruction::BitwiseNot(value, var) => (Literal::Integer(!self.int(value)?), *var),
+            Instruction::Icmp(cond, lhs, rhs, var) => {
+                (Literal::Bool(self.icmp(*cond, lhs, rhs)?), *var)
+            }
+            Instruction::Fadd(lhs, rhs, var) => {
+                (Literal::Double(self.double(lhs)? + self.double(rhs)?), *var)
+            }
+            Instruction::Fsub(lhs, rhs, var) => {
+                (Literal::Double(self.double(lhs)? - self.double(rhs)?), *var)
+            }
+            Instruction::Fmul(lhs, rhs, var) => {
+                (Literal::Double(self.double(lhs)? * self.double(rhs)?), *var)
+            }
+            Instruction::Fdiv(lhs, rhs, var) => {
+                (Literal::Double(self.double(lhs)? / self.double(rhs)?), *var)
+            }
+            Instruction::Fcmp(cond, lhs, rhs, var) => (
+                Literal::Bool(fcmp(*cond, self.double(lhs)?, self.double(rhs)?)),
+                *var,
+            ),
+            #[allow(clippy::cast_precision_loss)]
+            Instruction::Sitofp(value, var) => (Literal::Double(self.int(value)? as f64), *var),
+            #[allow(clippy::cast_possible_truncation)]
+            Instruction::Fptosi(value, var) => (Literal::Integer(self.double(value)? as i64), *var),
+            Instruction::Load(pointer, var) => (self.load(pointer)?, *var),
+            Instruction::LogicalNot(value, var) => (Literal::Bool(!self.bool(value)?), *var),
+            Instruction::LogicalAnd(lhs, rhs, var) => {
+                (Literal::Bool(self.bool(lhs)? && self.bool(rhs)?), *var)
+            }
+            Instruction::LogicalOr(lhs, rhs, var) => {
+                (Literal::Bool(self.bool(lhs)? || self.bool(rhs)?), *var)
+            }
+            Instruction::Jump(..)
+            | Instruction::Branch(..)
+            | Instruction::Phi(..)
+            | Instruction::Return => panic!("control flow should be handled by the block"),
+        };
+        self.variables.insert(var.variable_id, value);
+        Ok(())
+    }
+
+    fn int_binop(
+        &mut self,
+        lhs: &Operand,
+        rhs: &Operand,
+        var: Variable,
+        op: impl FnOnce(i64, i64) -> Result<i64, Error>,
+    ) -> Result<(Literal, Variable), Error> {
+        let value = op(self.int(lhs)?, self.int(rhs)?)?;
+        Ok((Literal::Integer(value), var))
+    }
+
+    fn icmp(&mut self, cond: ConditionCode, lhs: &Operand, rhs: &Operand) -> Result<bool, Error> {
+        let (lhs, rhs) = match (self.eval(lhs)?, self.eval(rhs)?) {
+            (Literal::Bool(lhs), Literal::Bool(rhs)) => (i64::from(lhs), i64::from(rhs)),
+            (lhs, rhs) => (int_literal(lhs)?, int_literal(rhs)?),
+        };
+        Ok(match cond {
+            ConditionCode::Eq => lhs == rhs,
+            ConditionCode::Ne => lhs != rhs,
+            ConditionCode::Slt => lhs < rhs,
+            ConditionCode::Sle => lhs <= rhs,
+            ConditionCode::Sgt => lhs > rhs,
+            ConditionCode::Sge => lhs >= rhs,
+        })
+    }
+
+    /// Calls a callable, returning its output value, if any.
+    fn call(
+        &mut self,
+        callable_id: CallableId,
+        args: &[Operand],
+    ) -> Result<Option<Literal>, Error> {
+        let callable = self.callable(callable_id)?;
+        let args = args
+            .iter()
+            .map(|arg| self.eval(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(body) = callable.body {
+            return self.call_body(callable, body, &args).map(|()| None);
+        }
+        match callable.call_type {
+            CallableType::Measurement => self.measure(callable, &args).map(|()| None),
+            CallableType::Readout => match args[..] {
+                [Literal::Result(id)] => {
+                    self.read_result(id).map(|value| Some(Literal::Bool(value)))
+                }
+                _ => Err(Error::UnsupportedCall(
+                    callable.name.clone(),
+                    "readout should take a single result",
+                )),
+            },
+            CallableType::OutputRecording => self.record_output(callable, &args).map(|()| None),
+            CallableType::Reset | CallableType::Regular => self.gate(callable, &args),
+        }
+    }
+
+    /// Calls a callable with a body, whose parameters are bound to the arguments in a separate set of variables.
+    fn call_body(
+        &mut self,
+        callable: &Callable,
+        body: BlockId,
+        args: &[Literal],
+    ) -> Result<(), Error> {
+        if callable.output_type.is_some() {
+            return Err(Error::UnsupportedCall(
+                callable.name.clone(),
+                "callables with a body cannot return a value",
+            ));
+        }
+        if callable.params.len() != args.len() {
+            return Err(Error::UnsupportedCall(
+                callable.name.clone(),
+                "arguments should match the parameters",
+            ));
+        }
+        let variables = callable
+            .params
+            .iter()
+            .map(|param| param.variable_id)
+            .zip(args.iter().copied())
+            .collect();
+        let caller_variables = std::mem::replace(&mut self.variables, variables);
+        let result = self.exec_block(body);
+        self.variables = caller_variables;
+        result
+    }
+
+    fn measure(&mut self, callable: &Callable, args: &[Literal]) -> Result<(), Error> {
+        let [Literal::Qubit(qubit), Literal::Result(result_id)] = args[..] else {
+            return Err(Error::UnsupportedCall(
+                callable.name.clone(),
+                "measurements should take a qubit and a result",
+            ));
+        };
+        let qubit = self.qubit(qubit)?;
+        let slot = self.result_slot(result_id)?;
+        let result = match callable.name.as_str() {
+            "__quantum__qis__mz__body" | "__quantum__qis__m__body" => self.sim.m(qubit).into(),
+            "__quantum__qis__mresetz__body" => self.sim.mresetz(qubit).into(),
+            _ => return Err(Error::UnknownIntrinsic(callable.name.clone())),
+        };
+        if let Some(message) = self.sim.take_failure() {
+            return Err(Error::IntrinsicFail(callable.name.clone(), message));
+        }
+        self.results[slot] = Some(result);
+        self.out
+            .measurement(qubit, result)
+            .map_err(|_| Error::OutputFail)
+    }
+
+    fn read_result(&mut self, id: u32) -> Result<bool, Error> {
+        match self.results[self.result_slot(id)?] {
+            Some(val::Result::Val(value)) => Ok(value),
+            Some(val::Result::Id(_)) => Err(Error::ResultValueUnknown(id)),
+            None => Err(Error::ResultNotMeasured(id)),
+        }
+    }
+
+    fn record_output(&mut self, callable: &Callable, args: &[Literal]) -> Result<(), Error> {
+        let record = match (callable.name.as_str(), args.first()) {
+            ("__quantum__rt__tuple_record_output", Some(Literal::Integer(len))) => {
+                OutputRecord::Tuple(usize::try_from(*len).map_err(|_| Error::IntTooLarge(*len))?)
+            }
+            ("__quantum__rt__array_record_output", Some(Literal::Integer(len))) => {
+                OutputRecord::Array(usize::try_from(*len).map_err(|_| Error::IntTooLarge(*len))?)
+            }
+            ("__quantum__rt__result_record_output", Some(Literal::Result(id))) => {
+                let slot = self.result_slot(*id)?;
+                OutputRecord::Result(self.results[slot].ok_or(Error::ResultNotMeasured(*id))?)
+            }
+            ("__quantum__rt__bool_record_output", Some(Literal::Bool(value))) => {
+                OutputRecord::Bool(*value)
+            }
+            ("__quantum__rt__integer_record_output", Some(Literal::Integer(value))) => {
+                OutputRecord::Int(*value)
+            }
+            ("__quantum__rt__double_record_output", Some(Literal::Double(value))) => {
+                OutputRecord::Double(*value)
+            }
+            _ => return Err(Error::UnknownIntrinsic(callable.name.clone())),
+        };
+        self.records.push(record);
+        self.out
+            .output_record(record)
+            .map_err(|_| Error::OutputFail)
+    }
+
+    fn gate(&mut self, callable: &Callable, args: &[Literal]) -> Result<Option<Literal>, Error> {
+        let name = callable.name.as_str();
+        match (name, args) {
+            ("__quantum__rt__initialize", _) => {}
+            ("__quantum__rt__qubit_allocate", []) => {
+                let id = u32::try_from(self.qubits.len()).expect("qubit count should fit in u32");
+                self.qubits.push(Some(self.sim.qubit_allocate()));
+                return Ok(Some(Literal::Qubit(id)));
+            }
+            ("__quantum__rt__qubit_release", [Literal::Qubit(q)]) => {
+                let qubit = self.qubit(*q)?;
+                self.qubits[*q as usize] = None;
+                self.sim.qubit_release(qubit);
+            }
+            ("__quantum__qis__reset__body", [Literal::Qubit(q)]) => self.sim.reset(self.qubit(*q)?),
+            ("__quantum__qis__h__body", [Literal::Qubit(q)]) => self.sim.h(self.qubit(*q)?),
+            ("__quantum__qis__s__body", [Literal::Qubit(q)]) => self.sim.s(self.qubit(*q)?),
+            ("__quantum__qis__s__adj", [Literal::Qubit(q)]) => self.sim.sadj(self.qubit(*q)?),
+            ("__quantum__qis__t__body", [Literal::Qubit(q)]) => self.sim.t(self.qubit(*q)?),
+            ("__quantum__qis__t__adj", [Literal::Qubit(q)]) => self.sim.tadj(self.qubit(*q)?),
+            ("__quantum__qis__x__body", [Literal::Qubit(q)]) => self.sim.x(self.qubit(*q)?),
+            ("__quantum__qis__y__body", [Literal::Qubit(q)]) => self.sim.y(self.qubit(*q)?),
+            ("__quantum__qis__z__body", [Literal::Qubit(q)]) => self.sim.z(self.qubit(*q)?),
+            ("__quantum__qis__cx__body", [Literal::Qubit(ctl), Literal::Qubit(q)]) => {
+                let [ctl, q] = self.unique_qubits([*ctl, *q])?;
+                self.sim.cx(ctl, q);
+            }
+            ("__quantum__qis__cy__body", [Literal::Qubit(ctl), Literal::Qubit(q)]) => {
+                let [ctl, q] = self.unique_qubits([*ctl, *q])?;
+                self.sim.cy(ctl, q);
+            }
+            ("__quantum__qis__cz__body", [Literal::Qubit(ctl), Literal::Qubit(q)]) => {
+                let [ctl, q] = self.unique_qubits([*ctl, *q])?;
+                self.sim.cz(ctl, q);
+            }
+            ("__quantum__qis__swap__body", [Literal::Qubit(q0), Literal::Qubit(q1)]) => {
+                let [q0, q1] = self.unique_qubits([*q0, *q1])?;
+                self.sim.swap(q0, q1);
+            }
+            (
+                "__quantum__qis__ccx__body",
+                [Literal::Qubit(ctl0), Literal::Qubit(ctl1), Literal::Qubit(q)],
+            ) => {
+                let [ctl0, ctl1, q] = self.unique_qubits([*ctl0, *ctl1, *q])?;
+                self.sim.ccx(ctl0, ctl1, q);
+            }
+            ("__quantum__qis__rx__body", [Literal::Double(theta), Literal::Qubit(q)]) => {
+                let q = self.qubit(*q)?;
+                self.sim.rx(rotation_angle(*theta)?, q);
+            }
+            ("__quantum__qis__ry__body", [Literal::Double(theta), Literal::Qubit(q)]) => {
+                let q = self.qubit(*q)?;
+                self.sim.ry(rotation_angle(*theta)?, q);
+            }
+            ("__quantum__qis__rz__body", [Literal::Double(theta), Literal::Qubit(q)]) => {
+                let q = self.qubit(*q)?;
+                self.sim.rz(rotation_angle(*theta)?, q);
+            }
+            (
+                "__quantum__qis__rxx__body",
+                [Literal::Double(theta), Literal::Qubit(q0), Literal::Qubit(q1)],
+            ) => {
+                let [q0, q1] = self.unique_qubits([*q0, *q1])?;
+                self.sim.rxx(rotation_angle(*theta)?, q0, q1);
+            }
+            (
+                "__quantum__qis__ryy__body",
+                [Literal::Double(theta), Literal::Qubit(q0), Literal::Qubit(q1)],
+            ) => {
+                let [q0, q1] = self.unique_qubits([*q0, *q1])?;
+                self.sim.ryy(rotation_angle(*theta)?, q0, q1);
+            }
+            (
+                "__quantum__qis__rzz__body",
+                [Literal::Double(theta), Literal::Qubit(q0), Literal::Qubit(q1)],
+            ) => {
+                let [q0, q1] = self.unique_qubits([*q0, *q1])?;
+                self.sim.rzz(rotation_angle(*theta)?, q0, q1);
+            }
+            _ => return self.custom_intrinsic(callable, args),
+        }
+        Ok(None)
+    }
+
+    /// Calls one of the runtime functions for arrays, whose pointer arguments and output are tracked by variable.
+    fn array_call(
+        &mut self,
+        callable: &Callable,
+        args: &[Operand],
+        var: Option<Variable>,
+    ) -> Result<(), Error> {
+        let pointer = match (callable.name.as_str(), args, var) {
+            ("__quantum__rt__qubit_allocate_array", [len], Some(_)) => {
+                let len = self.int(len)?;
+                let len = usize::try_from(len).map_err(|_| Error::IntTooLarge(len))?;
+                let mut ids = Vec::with_capacity(len);
+                for _ in 0..len {
+                    ids.push(
+                        u32::try_from(self.qubits.len()).expect("qubit count should fit in u32"),
+                    );
+                    self.qubits.push(Some(self.sim.qubit_allocate()));
+                }
+                Pointer::QubitArray(ids)
+            }
+            ("__quantum__rt__qubit_release_array", [array], None) => {
+                let Pointer::QubitArray(ids) = self.pointer(array)?.clone() else {
+                    return Err(Error::TypeMismatch(
+                        "pointer to an array",
+                        array.to_string(),
+                    ));
+                };
+                for id in ids {
+                    let qubit = self.qubit(id)?;
+                    self.qubits[id as usize] = None;
+                    self.sim.qubit_release(qubit);
+                }
+                return Ok(());
+            }
+            ("__quantum__rt__array_get_element_ptr_1d", [array, index], Some(_)) => {
+                let Pointer::QubitArray(ids) = self.pointer(array)? else {
+                    return Err(Error::TypeMismatch(
+                        "pointer to an array",
+                        array.to_string(),
+                    ));
+                };
+                let index = self.int(index)?;
+                let id = usize::try_from(index)
+                    .ok()
+                    .and_then(|i| ids.get(i))
+                    .ok_or(Error::IndexOutOfRange(index, ids.len()))?;
+                Pointer::Qubit(*id)
+            }
+            _ => {
+                return Err(Error::UnsupportedCall(
+                    callable.name.clone(),
+                    "array runtime functions should take the arguments of their declaration",
+                ))
+            }
+        };
+        let var = var.expect("array runtime functions with an output should assign it");
+        self.variables.insert(var.variable_id, Literal::Pointer);
+        self.pointers.insert(var.variable_id, pointer);
+        Ok(())
+    }
+
+    /// Calls an intrinsic that is not known to the interpreter, which the backend may still support.
+    fn custom_intrinsic(
+        &mut self,
+        callable: &Callable,
+        args: &[Literal],
+    ) -> Result<Option<Literal>, Error> {
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            values.push(match *arg {
+                Literal::Qubit(q) => Value::Qubit(Qubit(self.qubit(q)?)),
+                Literal::Result(id) => {
+                    let slot = self.result_slot(id)?;
+                    Value::Result(self.results[slot].ok_or(Error::ResultNotMeasured(id))?)
+                }
+                Literal::Bool(value) => Value::Bool(value),
+                Literal::Integer(value) => Value::Int(value),
+                Literal::Double(value) => Value::Double(value),
+                Literal::Pointer => Value::unit(),
+            });
+        }
+        let arg = if values.len() == 1 {
+            values.pop().expect("there should be one argument")
+        } else {
+            Value::Tuple(values.into())
+        };
+        let name = callable.name.clone();
+        match self.sim.custom_intrinsic(&name, arg) {
+            Some(Ok(value)) => Ok(match value {
+                Value::Bool(value) => Some(Literal::Bool(value)),
+                Value::Int(value) => Some(Literal::Integer(value)),
+                Value::Double(value) => Some(Literal::Double(value)),
+                _ => None,
+            }),
+            Some(Err(message)) => Err(Error::IntrinsicFail(name, message)),
+            None => Err(Error::UnknownIntrinsic(name)),
+        }
+    }
+
+    fn qubit(&self, id: u32) -> Result<usize, Error> {
+        self.qubits
+            .get(id as usize)
+            .ok_or(Error::QubitOutOfRange(id, self.program.num_qubits))?
+            .ok_or(Error::QubitReleased(id))
+    }
+
+    fn unique_qubits<const N: usize>(&self, ids: [u32; N]) -> Result<[usize; N], Error> {
+        for (i, id) in ids.iter().enumerate() {
+            if ids[i + 1..].contains(id) {
+                return Err(Error::QubitUniqueness);
+            }
+        }
+        let mut qubits = [0; N];
+        for (qubit, id) in qubits.iter_mut().zip(ids) {
+            *qubit = self.qubit(id)?;
+        }
+        Ok(qubits)
+    }
+
+    fn result_slot(&self, id: u32) -> Result<usize, Error> {
+        let slot = id as usize;
+        if slot < self.results.len() {
+            Ok(slot)
+        } else {
+            Err(Error::ResultOutOfRange(id, self.program.num_results))
+        }
+    }
+
+    fn eval(&self, operand: &Operand) -> Result<Literal, Error> {
+        match operand {
+            Operand::Literal(literal) => Ok(*literal),
+            Operand::Variable(var) => self
+                .variables
+                .get(&var.variable_id)
+                .copied()
+                .ok_or(Error::UnassignedVariable(var.variable_id.0)),
+        }
+    }
+
+    /// Gets the target of a pointer operand, or `None` if it is not a pointer returned by the array runtime functions.
+    fn operand_pointer(&self, operand: &Operand) -> Option<&Pointer> {
+        match operand {
+            Operand::Variable(var) => self.pointers.get(&var.variable_id),
+            Operand::Literal(_) => None,
+        }
+    }
+
+    fn pointer(&self, operand: &Operand) -> Result<&Pointer, Error> {
+        match operand {
+            Operand::Variable(var) => self
+                .pointers
+                .get(&var.variable_id)
+                .ok_or(Error::UnassignedVariable(var.variable_id.0)),
+            Operand::Literal(literal) => Err(Error::TypeMismatch("pointer", literal.to_string())),
+        }
+    }
+
+    fn load(&self, pointer: &Operand) -> Result<Literal, Error> {
+        match self.pointer(pointer)? {
+            Pointer::Qubit(q) => Ok(Literal::Qubit(*q)),
+            Pointer::QubitArray(_) => Err(Error::TypeMismatch(
+                "pointer to a qubit",
+                pointer.to_string(),
+            )),
+        }
+    }
+
+    fn bool(&self, operand: &Operand) -> Result<bool, Error> {
+        match self.eval(operand)? {
+            Literal::Bool(value) => Ok(value),
+            literal => Err(Error::TypeMismatch("boolean", literal.to_string())),
+        }
+    }
+
+    fn int(&self, operand: &Operand) -> Result<i64, Error> {
+        int_literal(self.eval(operand)?)
+    }
+
+    fn double(&self, operand: &Operand) -> Result<f64, Error> {
+        match self.eval(operand)? {
+            Literal::Double(value) => Ok(value),
+            literal => Err(Error::TypeMismatch("double", literal.to_string())),
+        }
+    }
+
+    fn callable(&self, id: CallableId) -> Result<&'a Callable, Error> {
+        self.program
+            .callables
+            .get(id)
+            .ok_or(Error::UnknownCallable(id.0))
+    }
+}
+
+/// Determines whether a callable is one of the runtime functions for arrays, which are handled by the interpreter.
+fn is_array_call(callable: &Callable) -> bool {
+    callable.body.is_none()
+        && matches!(
+            callable.name.as_str(),
+            "__quantum__rt__qubit_allocate_array"
+                | "__quantum__rt__qubit_release_array"
+                | "__quantum__rt__array_get_element_ptr_1d"
+        )
+}
+
+fn int_literal(literal: Literal) -> Result<i64, Error> {
+    match literal {
+        Literal::Integer(value) => Ok(value),
+        literal => Err(Error::TypeMismatch("integer", literal.to_string())),
+    }
+}
+
+/// Shifts left by the given amount, or right if it is negative, matching the evaluator.
+fn shl(value: i64, shift: i64) -> Result<i64, Error> {
+    let amount = u32::try_from(shift.unsigned_abs()).map_err(|_| Error::IntTooLarge(shift))?;
+    if shift >= 0 {
+        value.checked_shl(amount)
+    } else {
+        value.checked_shr(amount)
+    }
+    .ok_or(Error::IntTooLarge(shift))
+}
+
+fn rotation_angle(theta: f64) -> Result<f64, Error> {
+    if theta.is_nan() || theta.is_infinite() {
+        Err(Error::InvalidRotationAngle(theta))
+    } else {
+        Ok(theta)
+    }
+}
+
+#[allow(clippy::float_cmp)]
+fn fcmp(cond: FcmpConditionCode, lhs: f64, rhs: f64) -> bool {
+    let unordered = lhs.is_nan() || rhs.is_nan();
+    match cond {
+        FcmpConditionCode::False => false,
+        FcmpConditionCode::True => true,
+        FcmpConditionCode::Ordered => !unordered,
+        FcmpConditionCode::Unordered => unordered,
+        FcmpConditionCode::OrderedAndEqual => !unordered && lhs == rhs,
+        FcmpConditionCode::OrderedAndGreaterThan => !unordered && lhs > rhs,
+        FcmpConditionCode::OrderedAndGreaterThanOrEqual => !unordered && lhs >= rhs,
+        FcmpConditionCode::OrderedAndLessThan => !unordered && lhs < rhs,
+        FcmpConditionCode::OrderedAndLessThanOrEqual => !unordered && lhs <= rhs,
+        FcmpConditionCode::OrderedAndNotEqual => !unordered && lhs != rhs,
+        FcmpConditionCode::UnorderedOrEqual => unordered || lhs == rhs,
+        FcmpConditionCode::UnorderedOrGreaterThan => unordered || lhs > rhs,
+        FcmpConditionCode::UnorderedOrGreaterThanOrEqual => unordered || lhs >= rhs,
+        FcmpConditionCode::UnorderedOrLessThan => unordered || lhs < rhs,
+        FcmpConditionCode::UnorderedOrLessThanOrEqual => unordered || lhs <= rhs,
+        FcmpConditionCode::UnorderedOrNotEqual => unordered || lhs != rhs,
+    }
+}
@@ -0,0 +1,690 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::exec;
+use crate::{
+    backend::{Backend, SparseSim},
+    output::{GenericReceiver, OutputRecord},
+    val,
+};
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc_rir::{
+    builder,
+    parse::parse_program,
+    rir::{BlockId, Instruction, Program},
+};
+
+fn run(program: &Program, sim: &mut dyn Backend<ResultType = impl Into<val::Result>>) -> String {
+    let mut stdout = Vec::new();
+    let mut out = GenericReceiver::new(&mut stdout);
+    match exec(program, sim, &mut out) {
+        Ok(records) => records
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Err(error) => format!("{error:?}"),
+    }
+}
+
+fn check(program: &str, expect: &Expect) {
+    let program = parse_program(program).unwrap_or_else(|error| panic!("{error:?}"));
+    let mut sim = SparseSim::new();
+    sim.set_seed(Some(0));
+    expect.assert_eq(&run(&program, &mut sim));
+}
+
+/// A backend that does not simulate measurements, and instead identifies each result.
+#[derive(Default)]
+struct IdSim {
+    next_qubit: usize,
+    next_result: usize,
+}
+
+impl Backend for IdSim {
+    type ResultType = usize;
+
+    fn h(&mut self, _q: usize) {}
+
+    fn cx(&mut self, _ctl: usize, _q: usize) {}
+
+    fn x(&mut self, _q: usize) {}
+
+    fn z(&mut self, _q: usize) {}
+
+    fn m(&mut self, _q: usize) -> Self::ResultType {
+        self.next_result += 1;
+        self.next_result - 1
+    }
+
+    fn mresetz(&mut self, q: usize) -> Self::ResultType {
+        self.m(q)
+    }
+
+    fn qubit_allocate(&mut self) -> usize {
+        self.next_qubit += 1;
+        self.next_qubit - 1
+    }
+
+    fn qubit_release(&mut self, _q: usize) {}
+}
+
+#[test]
+fn bell_program_records_correlated_results() {
+    let program = builder::bell_program();
+    for seed in 0..8 {
+        let mut sim = SparseSim::new();
+        sim.set_seed(Some(seed));
+        let mut stdout = Vec::new();
+        let mut out = GenericReceiver::new(&mut stdout);
+        let records = exec(&program, &mut sim, &mut out).expect("program should run");
+        let [OutputRecord::Array(2), OutputRecord::Result(r0), OutputRecord::Result(r1)] =
+            records[..]
+        else {
+            panic!("unexpected records {records:?}");
+        };
+        assert_eq!(r0, r1);
+    }
+}
+
+#[test]
+fn teleport_program_records_teleported_state() {
+    let program = builder::teleport_program();
+    for seed in 0..8 {
+        let mut sim = SparseSim::new();
+        sim.set_seed(Some(seed));
+        expect!["OUTPUT\tRESULT\t1"].assert_eq(&run(&program, &mut sim));
+    }
+}
+
+#[test]
+fn results_from_backend_without_simulation_are_recorded_by_id() {
+    let program = builder::bell_program();
+    expect![[r#"
+        OUTPUT	ARRAY	2
+        OUTPUT	RESULT	r0
+        OUTPUT	RESULT	r1"#]]
+    .assert_eq(&run(&program, &mut IdSim::default()));
+}
+
+#[test]
+fn branching_on_result_from_backend_without_simulation_is_error() {
+    let program = builder::teleport_program();
+    expect!["ResultValueUnknown(0)"].assert_eq(&run(&program, &mut IdSim::default()));
+}
+
+#[test]
+fn classical_computations_and_loops() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__rt__tuple_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Integer
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__rt__integer_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Integer
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__rt__double_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Double
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 4: Callable:
+                        name: __quantum__rt__bool_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Boolean
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Jump(1)
+                    Block 1: Block:
+                        Variable(0, Integer) = Phi ( [Integer(0), 0], [Variable(2, Integer), 1], )
+                        Variable(1, Integer) = Phi ( [Integer(1), 0], [Variable(3, Integer), 1], )
+                        Variable(2, Integer) = Add Variable(0, Integer), Integer(1)
+                        Variable(3, Integer) = Shl Variable(1, Integer), Integer(1)
+                        Variable(4, Boolean) = Icmp Slt, Variable(2, Integer), Integer(5)
+                        Branch Variable(4, Boolean), 1, 2
+                    Block 2: Block:
+                        Variable(5, Double) = Sitofp Variable(3, Integer)
+                        Variable(6, Double) = Fdiv Variable(5, Double), Double(4)
+                        Variable(7, Boolean) = Fcmp Ogt, Variable(6, Double), Double(7.5)
+                        Variable(8, Integer) = Srem Variable(3, Integer), Integer(5)
+                        Call id(1), args( Integer(3), Pointer, )
+                        Call id(2), args( Variable(8, Integer), Pointer, )
+                        Call id(3), args( Variable(6, Double), Pointer, )
+                        Call id(4), args( Variable(7, Boolean), Pointer, )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching)
+                num_qubits: 0
+                num_results: 0"},
+        &expect![[r#"
+            OUTPUT	TUPLE	3
+            OUTPUT	INT	2
+            OUTPUT	DOUBLE	8.0
+            OUTPUT	BOOL	true"#]],
+    );
+}
+
+#[test]
+fn phi_nodes_read_values_from_predecessor_before_any_is_assigned() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__rt__tuple_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Integer
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__rt__integer_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Integer
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Jump(1)
+                    Block 1: Block:
+                        Variable(0, Integer) = Phi ( [Integer(0), 0], [Variable(1, Integer), 1], )
+                        Variable(1, Integer) = Phi ( [Integer(1), 0], [Variable(0, Integer), 1], )
+                        Variable(2, Integer) = Phi ( [Integer(0), 0], [Variable(3, Integer), 1], )
+                        Variable(3, Integer) = Add Variable(2, Integer), Integer(1)
+                        Variable(4, Boolean) = Icmp Slt, Variable(3, Integer), Integer(2)
+                        Branch Variable(4, Boolean), 1, 2
+                    Block 2: Block:
+                        Call id(1), args( Integer(2), Pointer, )
+                        Call id(2), args( Variable(0, Integer), Pointer, )
+                        Call id(2), args( Variable(1, Integer), Pointer, )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | BackwardsBranching)
+                num_qubits: 0
+                num_results: 0"},
+        &expect![[r#"
+            OUTPUT	TUPLE	2
+            OUTPUT	INT	1
+            OUTPUT	INT	0"#]],
+    );
+}
+
+#[test]
+fn calls_to_callable_with_body_bind_parameters_to_arguments() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: FlipIfPositive__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                            [1]: Integer
+                        params:
+                            [0]: Variable(0, Qubit)
+                            [1]: Variable(1, Integer)
+                        output_type: <VOID>
+                        body: 1
+                    Callable 2: Callable:
+                        name: __quantum__qis__x__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__qis__mresetz__body
+                        call_type: Measurement
+                        input_type:
+                            [0]: Qubit
+                            [1]: Result
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 4: Callable:
+                        name: __quantum__rt__array_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Integer
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 5: Callable:
+                        name: __quantum__rt__result_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Result
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Call id(1), args( Qubit(0), Integer(1), )
+                        Call id(1), args( Qubit(1), Integer(0), )
+                        Call id(3), args( Qubit(0), Result(0), )
+                        Call id(3), args( Qubit(1), Result(1), )
+                        Call id(4), args( Integer(2), Pointer, )
+                        Call id(5), args( Result(0), Pointer, )
+                        Call id(5), args( Result(1), Pointer, )
+                        Return
+                    Block 1: Block:
+                        Variable(2, Boolean) = Icmp Sgt, Variable(1, Integer), Integer(0)
+                        Branch Variable(2, Boolean), 2, 3
+                    Block 2: Block:
+                        Call id(2), args( Variable(0, Qubit), )
+                        Jump(3)
+                    Block 3: Block:
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | HigherLevelConstructs)
+                num_qubits: 2
+                num_results: 2"},
+        &expect![[r#"
+            OUTPUT	ARRAY	2
+            OUTPUT	RESULT	1
+            OUTPUT	RESULT	0"#]],
+    );
+}
+
+#[test]
+fn qubits_allocated_at_runtime_are_distinct_from_program_qubits() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__rt__qubit_allocate
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: Qubit
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__rt__qubit_release
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__qis__x__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 4: Callable:
+                        name: __quantum__qis__mresetz__body
+                        call_type: Measurement
+                        input_type:
+                            [0]: Qubit
+                            [1]: Result
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 5: Callable:
+                        name: __quantum__rt__result_record_output
+                        call_type: OutputRecording
+                        input_type:
+                            [0]: Result
+                            [1]: Pointer
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Qubit) = Call id(1), args( )
+                        Call id(3), args( Variable(0, Qubit), )
+                        Call id(4), args( Qubit(0), Result(0), )
+                        Call id(2), args( Variable(0, Qubit), )
+                        Call id(5), args( Result(0), Pointer, )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | DynamicQubitManagement)
+                num_qubits: 1
+                num_results: 1"},
+        &expect!["OUTPUT	RESULT	0"],
+    );
+}
+
+#[test]
+fn use_of_qubit_after_runtime_release_is_error() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__rt__qubit_allocate
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: Qubit
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__rt__qubit_release
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                    Callable 3: Callable:
+                        name: __quantum__qis__x__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Qubit) = Call id(1), args( )
+                        Call id(2), args( Variable(0, Qubit), )
+                        Call id(3), args( Variable(0, Qubit), )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | DynamicQubitManagement)
+                num_qubits: 0
+                num_results: 0"},
+        &expect!["QubitReleased(0)"],
+    );
+}
+
+#[test]
+fn index_out_of_range_of_runtime_qubit_array_is_error() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__rt__qubit_allocate_array
+                        call_type: Regular
+                        input_type:
+                            [0]: Integer
+                        output_type: Pointer
+                        body: <NONE>
+                    Callable 2: Callable:
+                        name: __quantum__rt__array_get_element_ptr_1d
+                        call_type: Regular
+                        input_type:
+                            [0]: Pointer
+                            [1]: Integer
+                        output_type: Pointer
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Pointer) = Call id(1), args( Integer(2), )
+                        Variable(1, Pointer) = Call id(2), args( Variable(0, Pointer), Integer(2), )
+                        Variable(2, Qubit) = Load Variable(1, Pointer)
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | DynamicQubitManagement)
+                num_qubits: 0
+                num_results: 0"},
+        &expect!["IndexOutOfRange(2, 2)"],
+    );
+}
+
+#[test]
+fn division_by_zero_is_error() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Integer) = Sdiv Integer(1), Integer(0)
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations)
+                num_qubits: 0
+                num_results: 0"},
+        &expect!["DivZero"],
+    );
+}
+
+#[test]
+fn reading_unmeasured_result_is_error() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__qis__read_result__body
+                        call_type: Readout
+                        input_type:
+                            [0]: Result
+                        output_type: Boolean
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Boolean) = Call id(1), args( Result(0), )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive)
+                num_qubits: 1
+                num_results: 1"},
+        &expect!["ResultNotMeasured(0)"],
+    );
+}
+
+#[test]
+fn unknown_intrinsic_is_error() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                    Callable 1: Callable:
+                        name: __quantum__qis__unknown__body
+                        call_type: Regular
+                        input_type:
+                            [0]: Qubit
+                        output_type: <VOID>
+                        body: <NONE>
+                blocks:
+                    Block 0: Block:
+                        Call id(1), args( Qubit(0), )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive)
+                num_qubits: 1
+                num_results: 0"},
+        &expect![[r#"UnknownIntrinsic("__quantum__qis__unknown__body")"#]],
+    );
+}
+
+#[test]
+fn repeated_qubit_in_gate_is_error() {
+    let mut program = builder::bell_program();
+    let block = program.get_block_mut(BlockId(0));
+    let Instruction::Call(_, args, _) = &mut block.0[1] else {
+        panic!("expected call");
+    };
+    args[1] = args[0];
+    let mut sim = SparseSim::new();
+    expect!["QubitUniqueness"].assert_eq(&run(&program, &mut sim));
+}
+
+#[test]
+fn ill_typed_integer_operand_is_error() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Integer) = Add Integer(1), Bool(true)
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations)
+                num_qubits: 0
+                num_results: 0"},
+        &expect![[r#"TypeMismatch("integer", "Bool(true)")"#]],
+    );
+}
+
+#[test]
+fn ill_typed_double_operand_is_error() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Double) = Fadd Double(1), Integer(2)
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | FloatingPointComputations)
+                num_qubits: 0
+                num_results: 0"},
+        &expect![[r#"TypeMismatch("double", "Integer(2)")"#]],
+    );
+}
+
+#[test]
+fn branch_on_non_boolean_is_error() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                blocks:
+                    Block 0: Block:
+                        Variable(0, Integer) = Store Integer(1)
+                        Branch Variable(0, Integer), 1, 1
+                    Block 1: Block:
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations)
+                num_qubits: 0
+                num_results: 0"},
+        &expect![[r#"TypeMismatch("boolean", "Integer(1)")"#]],
+    );
+}
+
+#[test]
+fn call_to_undefined_callable_is_error() {
+    check(
+        indoc! {"
+            Program:
+                entry: 0
+                callables:
+                    Callable 0: Callable:
+                        name: main
+                        call_type: Regular
+                        input_type: <VOID>
+                        output_type: <VOID>
+                        body: 0
+                blocks:
+                    Block 0: Block:
+                        Call id(3), args( )
+                        Return
+                config: Config:
+                    capabilities: TargetCapabilityFlags(Adaptive)
+                num_qubits: 0
+                num_results: 0"},
+        &expect!["UnknownCallable(3)"],
+    );
+}
@@ -381,6 +381,58 @@ pub fn get_latex(state: &Vec<(BigUint, Complex64)>, qubit_count: usize) -> Strin
     latex
 }
 
+/// Get the matrix represented as a formula in the LaTeX format if possible.
+/// Empty string is returned if the matrix has more than 8 rows, as larger matrices are not readable.
+#[must_use]
+pub fn get_matrix_latex(matrix: &[Vec<Complex64>]) -> String {
+    if matrix.len() > 8 {
+        return String::new();
+    }
+
+    let mut latex: String = String::with_capacity(500);
+    latex.push_str("$\\begin{bmatrix} ");
+    for (i, row) in matrix.iter().enumerate() {
+        if i > 0 {
+            latex.push_str(" \\\\ ");
+        }
+        for (j, element) in row.iter().enumerate() {
+            if j > 0 {
+                latex.push_str(" & ");
+            }
+            write_latex_for_complex_number(&mut latex, element);
+        }
+    }
+    latex.push_str(" \\end{bmatrix}$");
+    latex.shrink_to_fit();
+
+    latex
+}
+
+/// Write latex for a complex number that stands on its own, such as a matrix element.
+/// Unlike the coefficients of terms, 0 and 1 are always rendered.
+fn write_latex_for_complex_number(latex: &mut String, number: &Complex64) {
+    if !is_significant(number.re) && !is_significant(number.im) {
+        latex.push('0');
+        return;
+    }
+    match ComplexNumber::recognize(number.re, number.im) {
+        ComplexNumber::Polar(polar_form) => {
+            write_latex_for_polar_form(latex, &polar_form, false);
+        }
+        ComplexNumber::Cartesian(cartesian_form) => {
+            if let RealNumber::Zero = cartesian_form.imaginary_part {
+                // Only real part present
+                if cartesian_form.sign < 0 {
+                    latex.push('-');
+                }
+                write_latex_for_real_number(latex, &cartesian_form.real_part, true);
+            } else {
+                write_latex_for_cartesian_form(latex, &cartesian_form, false);
+            }
+        }
+    }
+}
+
 /// Write latex for one term of quantum state.
 /// Latex is rendered for coefficient only (not for basis vector).
 /// + is rendered only if ``render_plus`` is true.
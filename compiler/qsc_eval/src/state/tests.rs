@@ -4,7 +4,7 @@
 #![allow(clippy::needless_raw_string_hashes)]
 
 use super::{
-    get_latex, write_latex_for_algebraic_number, write_latex_for_cartesian_form,
+    get_latex, get_matrix_latex, write_latex_for_algebraic_number, write_latex_for_cartesian_form,
     write_latex_for_decimal_number, write_latex_for_polar_form, write_latex_for_real_number,
     write_latex_for_term, AlgebraicNumber, CartesianForm, ComplexNumber, DecimalNumber, PolarForm,
     RationalNumber, RealNumber, Term,
@@ -887,6 +887,41 @@ fn check_get_latex() {
     ));
 }
 
+#[test]
+fn check_get_matrix_latex() {
+    let h = 1.0 / 2.0_f64.sqrt();
+    expect!([r#"
+        "$\\begin{bmatrix} \\frac{\\sqrt{2}}{2} & \\frac{\\sqrt{2}}{2} \\\\ \\frac{\\sqrt{2}}{2} & -\\frac{\\sqrt{2}}{2} \\end{bmatrix}$"
+    "#]).assert_debug_eq(&get_matrix_latex(&[
+        vec![Complex64::new(h, 0.0), Complex64::new(h, 0.0)],
+        vec![Complex64::new(h, 0.0), Complex64::new(-h, 0.0)],
+    ]));
+    expect!([r#"
+        "$\\begin{bmatrix} 1 & 0 \\\\ 0 &  e^{ i \\pi / 4} \\end{bmatrix}$"
+    "#])
+    .assert_debug_eq(&get_matrix_latex(&[
+        vec![Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)],
+        vec![
+            Complex64::new(0.0, 0.0),
+            Complex64::new((PI / 4.0).cos(), (PI / 4.0).sin()),
+        ],
+    ]));
+    expect!([r#"
+        "$\\begin{bmatrix} 0 & -i \\\\ i & 0 \\end{bmatrix}$"
+    "#])
+    .assert_debug_eq(&get_matrix_latex(&[
+        vec![Complex64::new(0.0, 0.0), Complex64::new(0.0, -1.0)],
+        vec![Complex64::new(0.0, 1.0), Complex64::new(0.0, 0.0)],
+    ]));
+    expect!([r#"
+        ""
+    "#])
+    .assert_debug_eq(&get_matrix_latex(&vec![
+        vec![Complex64::new(1.0, 0.0); 16];
+        16
+    ]));
+}
+
 #[test]
 fn check_get_latex_perf() {
     // This is not a CI gate for performance, just prints out data.
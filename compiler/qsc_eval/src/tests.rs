@@ -260,6 +260,165 @@ fn block_let_bind_tuple_expr() {
     );
 }
 
+#[test]
+fn block_let_bind_array_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            let [a, b, c] = [1, 2, 3];
+            (c, b, a)
+        }"},
+        &expect!["(3, 2, 1)"],
+    );
+}
+
+#[test]
+fn block_let_bind_array_rest_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            let [first, ...rest] = [1, 2, 3];
+            (first, rest)
+        }"},
+        &expect!["(1, [2, 3])"],
+    );
+}
+
+#[test]
+fn block_let_bind_array_empty_rest_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            let [first, ...rest] = [1];
+            (first, rest)
+        }"},
+        &expect!["(1, [])"],
+    );
+}
+
+#[test]
+fn block_let_bind_array_nested_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            mutable sum = 0;
+            for [(a, b), ..._] in [[(1, 2), (3, 4)], [(5, 6)]] {
+                set sum += a * b;
+            }
+            sum
+        }"},
+        &expect!["32"],
+    );
+}
+
+#[test]
+fn block_let_bind_array_length_mismatch_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            let [a, b] = [1, 2, 3];
+            a
+        }"},
+        &expect![[r#"
+            (
+                ArrayPatternMismatch(
+                    "2",
+                    3,
+                    PackageSpan {
+                        package: PackageId(
+                            2,
+                        ),
+                        span: Span {
+                            lo: 10,
+                            hi: 16,
+                        },
+                    },
+                ),
+                [],
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn block_let_bind_array_rest_length_mismatch_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            let [a, b, ...rest] = [1];
+            a
+        }"},
+        &expect![[r#"
+            (
+                ArrayPatternMismatch(
+                    "at least 2",
+                    1,
+                    PackageSpan {
+                        package: PackageId(
+                            2,
+                        ),
+                        span: Span {
+                            lo: 10,
+                            hi: 25,
+                        },
+                    },
+                ),
+                [],
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn call_array_param_expr() {
+    check_expr(
+        indoc! {"
+            namespace Test {
+                function Swap([a : Int, b : Int], [c : Int, ...rest : Int[]]) : (Int, Int, Int, Int[]) {
+                    (b, a, c, rest)
+                }
+            }
+        "},
+        "Test.Swap([1, 2], [3, 4, 5])",
+        &expect!["(2, 1, 3, [4, 5])"],
+    );
+}
+
+#[test]
+fn call_array_param_in_specializations_expr() {
+    check_expr(
+        indoc! {"
+            namespace Test {
+                operation Foo([q : Qubit, ..._ : Qubit[]]) : Unit is Adj + Ctl {
+                    X(q);
+                }
+            }
+        "},
+        indoc! {"{
+            use (ctl, qs) = (Qubit(), Qubit[2]);
+            X(ctl);
+            Controlled Test.Foo([ctl], qs);
+            Adjoint Test.Foo([qs[1]]);
+            let res = (M(qs[0]), M(qs[1]));
+            ResetAll([ctl] + qs);
+            res
+        }"},
+        &expect!["(One, One)"],
+    );
+}
+
+#[test]
+fn lambda_array_param_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            let f = [a, ...rest] -> a + Length(rest);
+            f([10, 20, 30])
+        }"},
+        &expect!["12"],
+    );
+}
+
 #[test]
 fn block_mutable_expr() {
     check_expr(
@@ -2044,6 +2203,122 @@ fn while_false_shortcut_expr() {
     );
 }
 
+#[test]
+fn while_break_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            mutable x = 0;
+            while true {
+                set x += 1;
+                if x == 5 {
+                    break;
+                }
+            }
+            x
+        }"},
+        &expect!["5"],
+    );
+}
+
+#[test]
+fn while_continue_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            mutable x = 0;
+            mutable evens = 0;
+            while x < 10 {
+                set x += 1;
+                if x % 2 == 1 {
+                    continue;
+                }
+                set evens += 1;
+            }
+            evens
+        }"},
+        &expect!["5"],
+    );
+}
+
+#[test]
+fn for_range_continue_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            mutable sum = 0;
+            for i in 0..10 {
+                if i % 3 != 0 {
+                    continue;
+                }
+                set sum += i;
+            }
+            sum
+        }"},
+        &expect!["18"],
+    );
+}
+
+#[test]
+fn for_array_break_and_continue_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            mutable seen = [];
+            for x in [1, -2, 3, 0, 5] {
+                if x < 0 {
+                    continue;
+                }
+                if x == 0 {
+                    break;
+                }
+                set seen += [x];
+            }
+            seen
+        }"},
+        &expect!["[1, 3]"],
+    );
+}
+
+#[test]
+fn nested_loop_break_only_exits_inner_loop_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            mutable count = 0;
+            for i in 0..2 {
+                for j in 0..10 {
+                    if j == 3 {
+                        break;
+                    }
+                    set count += 1;
+                }
+            }
+            count
+        }"},
+        &expect!["9"],
+    );
+}
+
+#[test]
+fn break_releases_qubits_allocated_in_loop_expr() {
+    check_expr(
+        "",
+        indoc! {"{
+            for i in 0..3 {
+                use q = Qubit();
+                use q1 = Qubit();
+                if i == 1 {
+                    break;
+                }
+            }
+            use q = Qubit();
+            q
+        }"},
+        &expect!["Qubit0"],
+    );
+}
+
 #[test]
 fn cond_expr() {
     check_expr("", "true ? 1 | 0", &expect!["1"]);
@@ -2179,6 +2454,117 @@ fn update_udt_nested_field() {
     );
 }
 
+#[test]
+fn update_udt_field_path() {
+    check_expr(
+        indoc! {"
+            namespace A {
+                newtype Noise = (Rate : Double, Kind : Int);
+                newtype Config = (Noise : Noise, Shots : Int);
+            }
+        "},
+        indoc! {"{
+            open A;
+            let config = Config(Noise(0.5, 1), 100);
+            config w/ Noise::Rate <- 0.1
+        }"},
+        &expect!["((0.1, 1), 100)"],
+    );
+}
+
+#[test]
+fn update_udt_field_then_index() {
+    check_expr(
+        indoc! {"
+            namespace A {
+                newtype Data = (Values : Int[], Label : String);
+            }
+        "},
+        indoc! {"{
+            open A;
+            let data = Data([1, 2, 3], \"a\");
+            data w/ Values[1] <- 7
+        }"},
+        &expect!["([1, 7, 3], a)"],
+    );
+}
+
+#[test]
+fn update_array_index_path() {
+    check_expr(
+        "",
+        "[[1, 2], [3, 4]] w/ [1][0] <- 5",
+        &expect!["[[1, 2], [5, 4]]"],
+    );
+}
+
+#[test]
+fn update_array_index_path_with_locals() {
+    check_expr(
+        "",
+        indoc! {"{
+            let (i, j) = (0, 1);
+            let grid = [[1, 2], [3, 4]];
+            (grid w/ [i][j] <- 5, grid)
+        }"},
+        &expect!["([[1, 5], [3, 4]], [[1, 2], [3, 4]])"],
+    );
+}
+
+#[test]
+fn update_array_index_then_field() {
+    check_expr(
+        indoc! {"
+            namespace A {
+                newtype Pair = (First : Int, Second : Int);
+            }
+        "},
+        indoc! {"{
+            open A;
+            let pairs = [Pair(1, 2), Pair(3, 4)];
+            pairs w/ [1]::First <- 9
+        }"},
+        &expect!["[(1, 2), (9, 4)]"],
+    );
+}
+
+#[test]
+fn update_array_index_from_local_array() {
+    check_expr(
+        "",
+        indoc! {"{
+            let indices = [2, 0];
+            [1, 2, 3] w/ indices[0] <- 4
+        }"},
+        &expect!["[1, 2, 4]"],
+    );
+}
+
+#[test]
+fn update_array_index_path_out_of_range_err() {
+    check_expr(
+        "",
+        "[[1, 2], [3, 4]] w/ [1][2] <- 5",
+        &expect![[r#"
+        (
+            IndexOutOfRange(
+                2,
+                PackageSpan {
+                    package: PackageId(
+                        2,
+                    ),
+                    span: Span {
+                        lo: 0,
+                        hi: 31,
+                    },
+                },
+            ),
+            [],
+        )
+    "#]],
+    );
+}
+
 #[test]
 fn update_range_start() {
     check_expr("", "1..2..3 w/ Start <- 10", &expect!["10..2..3"]);
@@ -2487,6 +2873,38 @@ fn assignupdate_expr_using_field_name() {
     );
 }
 
+#[test]
+fn assignupdate_expr_using_field_path() {
+    check_expr(
+        indoc! {"
+            namespace A {
+                newtype Noise = (Rate : Double, Kind : Int);
+                newtype Config = (Noise : Noise, Shots : Int);
+            }
+        "},
+        indoc! {"{
+            open A;
+            mutable config = Config(Noise(0.5, 1), 100);
+            set config w/= Noise::Kind <- 2;
+            config
+        }"},
+        &expect!["((0.5, 2), 100)"],
+    );
+}
+
+#[test]
+fn assignupdate_expr_using_index_path() {
+    check_expr(
+        "",
+        indoc! {"{
+            mutable grid = [[1, 2], [3, 4]];
+            set grid w/= [1][0] <- 5;
+            grid
+        }"},
+        &expect!["[[1, 2], [5, 4]]"],
+    );
+}
+
 #[test]
 fn assignupdate_expr_using_range() {
     check_expr(
@@ -3025,6 +3443,25 @@ fn call_call_expr() {
     );
 }
 
+#[test]
+fn call_generic_with_class_bound_expr() {
+    check_expr(
+        indoc! {"
+            namespace Test {
+                function Sum<'T : Add>(xs : 'T[], zero : 'T) : 'T {
+                    mutable acc = zero;
+                    for x in xs {
+                        set acc += x;
+                    }
+                    acc
+                }
+            }
+        "},
+        "(Test.Sum([1, 2, 3], 0), Test.Sum([1.5, 2.5], 0.0), Test.Sum([\"a\", \"b\"], \"\"))",
+        &expect!["(6, 4.0, ab)"],
+    );
+}
+
 #[test]
 fn call_adjoint_expr() {
     check_expr(
@@ -3456,6 +3893,163 @@ fn udt_field_nested() {
     );
 }
 
+#[test]
+fn struct_cons() {
+    check_expr(
+        "",
+        "{
+            struct Point { X : Int, Y : Int }
+            let p = new Point { Y = 2, X = 1 };
+            (p::X, p::Y)
+        }",
+        &expect!["(1, 2)"],
+    );
+}
+
+#[test]
+fn struct_cons_evaluates_fields_in_source_order() {
+    check_expr(
+        "",
+        "{
+            struct Pair { First : Int, Second : Int }
+            mutable count = 0;
+            let p = new Pair { Second = { set count += 1; count }, First = { set count += 1; count } };
+            (p::First, p::Second)
+        }",
+        &expect!["(2, 1)"],
+    );
+}
+
+#[test]
+fn struct_copy() {
+    check_expr(
+        "",
+        "{
+            struct Point { X : Int, Y : Int, Z : Int }
+            let p = new Point { X = 1, Y = 2, Z = 3 };
+            let q = new Point { ...p, Z = 4, X = 0 };
+            (p, q::X, q::Y, q::Z)
+        }",
+        &expect!["((1, 2, 3), 0, 2, 4)"],
+    );
+}
+
+#[test]
+fn struct_single_field() {
+    check_expr(
+        "",
+        "{
+            struct Wrapper { Value : Int }
+            let w = new Wrapper { Value = 5 };
+            new Wrapper { ...w, Value = w::Value + 1 }::Value
+        }",
+        &expect!["6"],
+    );
+}
+
+#[test]
+fn enum_match_variant() {
+    check_expr(
+        "",
+        "{
+            enum Shape { Circle(Double), Rect((Double, Double)), Empty }
+            function Area(s : Shape) : Double {
+                match s {
+                    Shape.Circle(r) => 3.0 * r * r,
+                    Shape.Rect((w, h)) => w * h,
+                    Shape.Empty => 0.0,
+                }
+            }
+            (Area(Shape.Circle(1.0)), Area(Shape.Rect((2.0, 3.0))), Area(Shape.Empty))
+        }",
+        &expect!["(3.0, 6.0, 0.0)"],
+    );
+}
+
+#[test]
+fn enum_match_discard() {
+    check_expr(
+        "",
+        "{
+            enum Color { Red, Green, Blue }
+            let colors = [Color.Red, Color.Green, Color.Blue];
+            mutable names = [];
+            for c in colors {
+                let name = match c {
+                    Color.Green => \"green\",
+                    _ => \"other\",
+                };
+                set names += [name];
+            }
+            names
+        }",
+        &expect!["[other, green, other]"],
+    );
+}
+
+#[test]
+fn enum_match_scrutinee_evaluated_once() {
+    check_expr(
+        "",
+        "{
+            enum Count { Some(Int), None }
+            mutable calls = 0;
+            operation Next() : Count {
+                Count.Some(1)
+            }
+            let n = match { set calls += 1; Next() } {
+                Count.None => 0,
+                Count.Some(n) => n,
+            };
+            (n, calls)
+        }",
+        &expect!["(1, 1)"],
+    );
+}
+
+#[test]
+fn enum_variant_as_function() {
+    check_expr(
+        "",
+        "{
+            enum Wrapped { Value(Int), Nothing }
+            let wrap = Wrapped.Value;
+            let values = [wrap(1), Wrapped.Nothing];
+            mutable sum = 0;
+            for v in values {
+                set sum += match v {
+                    Wrapped.Value(x) => x,
+                    Wrapped.Nothing => 100,
+                };
+            }
+            sum
+        }",
+        &expect!["101"],
+    );
+}
+
+#[test]
+fn enum_variants_with_qubit_payloads_in_array() {
+    check_expr(
+        "",
+        "{
+            enum Target { Single(Qubit), Pair((Qubit, Qubit)), Nothing }
+            use qs = Qubit[2];
+            let targets = [Target.Single(qs[0]), Target.Pair((qs[0], qs[1])), Target.Nothing];
+            mutable count = 0;
+            for t in targets {
+                set count += match t {
+                    Target.Single(_) => 1,
+                    Target.Pair(_) => 2,
+                    Target.Nothing => 0,
+                };
+            }
+            count
+        }",
+        &expect!["3"],
+    );
+}
+
 #[test]
 fn lambda_function_empty_closure() {
     check_expr("", "{ let f = x -> x + 1; f(1) }", &expect!["2"]);
@@ -116,6 +116,9 @@ fn derive_callable_input_pattern_elements(
                 ty: pat.ty.clone(),
                 kind: InputPatternElementKind::Discard,
             }],
+            PatKind::Array(..) => {
+                panic!("array patterns in callable inputs should be replaced by bindings")
+            }
         }
     }
 
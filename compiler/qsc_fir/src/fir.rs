@@ -625,7 +625,7 @@ impl PackageLookup for Package {
     fn get_global(&self, id: LocalItemId) -> Option<Global> {
         match &self.items.get(id)?.kind {
             ItemKind::Callable(callable) => Some(Global::Callable(callable)),
-            ItemKind::Namespace(..) => None,
+            ItemKind::Namespace(..) | ItemKind::Export(..) | ItemKind::Const(..) => None,
             ItemKind::Ty(..) => Some(Global::Udt),
         }
     }
@@ -701,6 +701,10 @@ pub enum ItemKind {
     Namespace(Ident, Vec<LocalItemId>),
     /// A `newtype` declaration.
     Ty(Ident, Udt),
+    /// An exported item, visible in the parent namespace under the given name.
+    Export(Ident, ItemId),
+    /// A `const` declaration, whose uses are replaced by its value at compile time.
+    Const(Ident, Ty),
 }
 
 impl Display for ItemKind {
@@ -721,6 +725,8 @@ impl Display for ItemKind {
                 }
             }
             ItemKind::Ty(name, udt) => write!(f, "Type ({name}): {udt}"),
+            ItemKind::Export(name, item) => write!(f, "Export ({name}): {item}"),
+            ItemKind::Const(name, ty) => write!(f, "Const ({name}): {ty}"),
         }
     }
 }
@@ -1035,10 +1041,14 @@ pub enum ExprKind {
     BinOp(BinOp, ExprId, ExprId),
     /// A block: `{ ... }`.
     Block(BlockId),
+    /// A break out of the innermost loop: `break`.
+    Break,
     /// A call: `a(b)`.
     Call(ExprId, ExprId),
     /// A closure that fixes the vector of local variables as arguments to the callable item.
     Closure(Vec<LocalVarId>, LocalItemId),
+    /// A jump to the next iteration of the innermost loop: `continue`.
+    Continue,
     /// A failure: `fail "message"`.
     Fail(ExprId),
     /// A field accessor: `a::F`.
@@ -1091,8 +1101,10 @@ impl Display for ExprKind {
             }
             ExprKind::BinOp(op, lhs, rhs) => display_bin_op(indent, *op, *lhs, *rhs)?,
             ExprKind::Block(block) => write!(indent, "Expr Block: {block}")?,
+            ExprKind::Break => write!(indent, "Break")?,
             ExprKind::Call(callable, arg) => display_call(indent, *callable, *arg)?,
             ExprKind::Closure(args, callable) => display_closure(indent, args, *callable)?,
+            ExprKind::Continue => write!(indent, "Continue")?,
             ExprKind::Fail(e) => write!(indent, "Fail: {e}")?,
             ExprKind::Field(expr, field) => display_field(indent, *expr, field)?,
             ExprKind::Hole => write!(indent, "Hole")?,
@@ -1401,6 +1413,8 @@ pub enum PatKind {
     Discard,
     /// A tuple: `(a, b, c)`.
     Tuple(Vec<PatId>),
+    /// An array with an optional pattern for the remaining items: `[a, b, ...rest]`.
+    Array(Vec<PatId>, Option<PatId>),
 }
 
 impl Display for PatKind {
@@ -1422,6 +1436,16 @@ impl Display for PatKind {
                     }
                 }
             }
+            PatKind::Array(ps, rest) => {
+                write!(indent, "Array:")?;
+                indent = set_indentation(indent, 1);
+                for p in ps {
+                    write!(indent, "\n{p}")?;
+                }
+                if let Some(rest) = rest {
+                    write!(indent, "\nRest: {rest}")?;
+                }
+            }
         }
         Ok(())
     }
@@ -1449,6 +1473,8 @@ impl Display for Ident {
 pub enum Attr {
     /// Indicates that a callable is an entry point to a program.
     EntryPoint,
+    /// Indicates that a callable should not be inlined into its callers when generating code.
+    NoInline,
 }
 
 /// A field.
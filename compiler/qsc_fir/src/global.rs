@@ -91,27 +91,61 @@ impl PackageIter<'_> {
                 .expect("parent should exist")
                 .kind
         });
+
+        match (&item.kind, &parent) {
+            (ItemKind::Export(name, target), Some(ItemKind::Namespace(namespace, _))) => {
+                // Only items from the same package can be exported.
+                if target.package.is_some() {
+                    return None;
+                }
+                let target = self.package.items.get(target.item)?;
+                self.decl_global(&namespace.name, Some(&name.name), item.visibility, target)
+            }
+            (_, Some(ItemKind::Namespace(namespace, _))) => {
+                self.decl_global(&namespace.name, None, item.visibility, item)
+            }
+            (ItemKind::Namespace(ident, _), None) => Some(Global {
+                namespace: "".into(),
+                name: Rc::clone(&ident.name),
+                visibility: Visibility::Public,
+                kind: Kind::Namespace,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Creates the globals for a callable or type declaration in the given namespace, under the
+    /// given name if it is exported under an alias.
+    fn decl_global(
+        &mut self,
+        namespace: &Rc<str>,
+        name: Option<&Rc<str>>,
+        visibility: Visibility,
+        item: &Item,
+    ) -> Option<Global> {
         let id = ItemId {
             package: self.id,
             item: item.id,
         };
 
-        match (&item.kind, &parent) {
-            (ItemKind::Callable(decl), Some(ItemKind::Namespace(namespace, _))) => Some(Global {
-                namespace: Rc::clone(&namespace.name),
-                name: Rc::clone(&decl.name.name),
-                visibility: item.visibility,
+        match &item.kind {
+            ItemKind::Callable(decl) => Some(Global {
+                namespace: Rc::clone(namespace),
+                name: Rc::clone(name.unwrap_or(&decl.name.name)),
+                visibility,
                 kind: Kind::Term(Term {
                     id,
                     scheme: decl
                         .scheme(|key| self.package.pats.get(key).expect("couldn't find pat")),
                 }),
             }),
-            (ItemKind::Ty(name, def), Some(ItemKind::Namespace(namespace, _))) => {
-                self.next = Some(Global {
-                    namespace: Rc::clone(&namespace.name),
-                    name: Rc::clone(&name.name),
-                    visibility: item.visibility,
+            ItemKind::Ty(ty_name, def) => {
+                let name = name.unwrap_or(&ty_name.name);
+                // Enums are constructed through their variants rather than a constructor.
+                self.next = def.variants().is_none().then(|| Global {
+                    namespace: Rc::clone(namespace),
+                    name: Rc::clone(name),
+                    visibility,
                     kind: Kind::Term(Term {
                         id,
                         scheme: def.cons_scheme(id),
@@ -119,19 +153,13 @@ impl PackageIter<'_> {
                 });
 
                 Some(Global {
-                    namespace: Rc::clone(&namespace.name),
-                    name: Rc::clone(&name.name),
-                    visibility: item.visibility,
+                    namespace: Rc::clone(namespace),
+                    name: Rc::clone(name),
+                    visibility,
                     kind: Kind::Ty(Ty { id }),
                 })
             }
-            (ItemKind::Namespace(ident, _), None) => Some(Global {
-                namespace: "".into(),
-                name: Rc::clone(&ident.name),
-                visibility: Visibility::Public,
-                kind: Kind::Namespace,
-            }),
-            _ => None,
+            ItemKind::Namespace(..) | ItemKind::Export(..) | ItemKind::Const(..) => None,
         }
     }
 }
@@ -413,12 +413,23 @@ pub struct Udt {
 }
 
 impl Udt {
+    /// The underlying type wrapped by this user-defined type. Every value of an enum has the same
+    /// underlying type: a tuple of the integer tag of its variant followed by one array per
+    /// variant, in declaration order. The array of the variant of the value holds its payload,
+    /// and every other array is empty.
     #[must_use]
     pub fn get_pure_ty(&self) -> Ty {
         fn get_pure_ty(def: &UdtDef) -> Ty {
             match &def.kind {
                 UdtDefKind::Field(field) => field.ty.clone(),
                 UdtDefKind::Tuple(tup) => Ty::Tuple(tup.iter().map(get_pure_ty).collect()),
+                UdtDefKind::Enum(variants) => Ty::Tuple(
+                    std::iter::once(Ty::Prim(Prim::Int))
+                        .chain(variants.iter().map(|variant| {
+                            Ty::Array(Box::new(variant.ty.clone().unwrap_or(Ty::UNIT)))
+                        }))
+                        .collect(),
+                ),
             }
         }
         get_pure_ty(&self.definition)
@@ -464,6 +475,7 @@ impl Udt {
                     path
                 })
             }),
+            UdtDefKind::Enum(_) => None,
         }
     }
 
@@ -495,6 +507,16 @@ impl Udt {
         let path = self.field_path(name)?;
         self.field_ty(&path)
     }
+
+    /// The variants of this user-defined type in declaration order. Returns [None] if this
+    /// user-defined type is not an enum.
+    #[must_use]
+    pub fn variants(&self) -> Option<&[UdtVariant]> {
+        match &self.definition.kind {
+            UdtDefKind::Enum(variants) => Some(variants),
+            UdtDefKind::Field(_) | UdtDefKind::Tuple(_) => None,
+        }
+    }
 }
 
 impl Display for Udt {
@@ -529,6 +551,8 @@ pub enum UdtDefKind {
     Field(UdtField),
     /// A tuple.
     Tuple(Vec<UdtDef>),
+    /// The variants of an enum.
+    Enum(Vec<UdtVariant>),
 }
 
 impl Display for UdtDefKind {
@@ -551,6 +575,34 @@ impl Display for UdtDefKind {
                     }
                 }
             }
+            UdtDefKind::Enum(variants) => {
+                write!(indent, "Enum:")?;
+                indent = set_indentation(indent, 1);
+                for variant in variants {
+                    write!(indent, "\n{variant}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A variant of an enum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UdtVariant {
+    /// The span of the variant name.
+    pub name_span: Span,
+    /// The variant name.
+    pub name: Rc<str>,
+    /// The type of the payload carried by the variant, if any.
+    pub ty: Option<Ty>,
+}
+
+impl Display for UdtVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Variant {} ({})", self.name_span, self.name)?;
+        if let Some(ty) = &self.ty {
+            write!(f, ": {ty}")?;
         }
         Ok(())
     }
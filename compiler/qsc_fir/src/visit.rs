@@ -63,7 +63,12 @@ pub fn walk_package<'a>(vis: &mut impl Visitor<'a>, package: &'a Package) {
 pub fn walk_item<'a>(vis: &mut impl Visitor<'a>, item: &'a Item) {
     match &item.kind {
         ItemKind::Callable(decl) => vis.visit_callable_decl(decl),
-        ItemKind::Namespace(name, _) | ItemKind::Ty(name, _) => vis.visit_ident(name),
+        ItemKind::Namespace(name, _)
+        | ItemKind::Ty(name, _)
+        | ItemKind::Export(name, _)
+        | ItemKind::Const(name, _) => {
+            vis.visit_ident(name);
+        }
     };
 }
 
@@ -187,7 +192,12 @@ pub fn walk_expr<'a>(vis: &mut impl Visitor<'a>, expr: ExprId) {
             vis.visit_expr(*cond);
             vis.visit_block(*block);
         }
-        ExprKind::Closure(_, _) | ExprKind::Hole | ExprKind::Lit(_) | ExprKind::Var(_, _) => {}
+        ExprKind::Break
+        | ExprKind::Closure(_, _)
+        | ExprKind::Continue
+        | ExprKind::Hole
+        | ExprKind::Lit(_)
+        | ExprKind::Var(_, _) => {}
     }
 }
 
@@ -197,5 +207,6 @@ pub fn walk_pat<'a>(vis: &mut impl Visitor<'a>, pat: PatId) {
         PatKind::Bind(name) => vis.visit_ident(name),
         PatKind::Discard => {}
         PatKind::Tuple(pats) => pats.iter().for_each(|p| vis.visit_pat(*p)),
+        PatKind::Array(pats, rest) => pats.iter().chain(rest).for_each(|p| vis.visit_pat(*p)),
     }
 }
@@ -499,6 +499,7 @@ impl<'a> Formatter<'a> {
         right_kind: &ConcreteTokenKind,
     ) -> (Delimiter, Delimiter) {
         use qsc_frontend::keyword::Keyword;
+        use qsc_frontend::lex::cooked::ClosedBinOp;
         use ConcreteTokenKind::*;
         use TokenKind::*;
 
@@ -538,13 +539,13 @@ impl<'a> Formatter<'a> {
             {
                 self.type_param_state = TypeParameterListState::InTypeParamList;
             }
-            Syntax(AposIdent | Comma | Gt)
+            Syntax(AposIdent | Colon | Ident | ClosedBinOp(ClosedBinOp::Plus) | Comma | Gt)
                 if matches!(
                     self.type_param_state,
                     TypeParameterListState::InTypeParamList
                 ) =>
             {
-                // type param identifiers and commas don't take us out of the type parameter list context
+                // type param identifiers, their class bounds, and commas don't take us out of the type parameter list context
                 // Gt only takes us out of the list once we are past it (it is the left-hand token)
             }
             _ => {
@@ -697,6 +698,8 @@ fn is_newline_keyword_or_ampersat(cooked: &TokenKind) -> bool {
                     | Operation
                     | Function
                     | Newtype
+                    | Struct
+                    | Enum
                     | Namespace
                     | Open
                     | Body
@@ -714,7 +717,10 @@ fn is_newline_keyword_or_ampersat(cooked: &TokenKind) -> bool {
 
 fn is_starter_keyword(keyword: &Keyword) -> bool {
     use Keyword::*;
-    matches!(keyword, For | While | Repeat | If | Within | Return | Fail)
+    matches!(
+        keyword,
+        For | While | Repeat | If | Within | Return | Fail | New | Match
+    )
 }
 
 fn is_newline_after_brace(cooked: &TokenKind, delim_state: Delimiter) -> bool {
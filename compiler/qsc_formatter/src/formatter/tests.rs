@@ -65,6 +65,74 @@ fn namespace_items_begin_on_their_own_lines() {
     );
 }
 
+#[test]
+fn struct_decls_begin_on_their_own_lines() {
+    check(
+        "newtype Foo = Int; struct Bar{A:Int,B:Double} function Baz() : Unit {}",
+        &expect![[r#"
+            newtype Foo = Int;
+            struct Bar { A : Int, B : Double }
+            function Baz() : Unit {}"#]],
+    );
+}
+
+#[test]
+fn struct_decl_fields_on_their_own_lines() {
+    check(
+        indoc! {r#"
+        struct Foo {
+        A : Int,
+                B : Double,
+        }
+        "#},
+        &expect![[r#"
+            struct Foo {
+                A : Int,
+                B : Double,
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn single_space_in_struct_constructor() {
+    check(
+        "let x = new  Foo{A=1,B=2.0}; let y = new Foo{ ...x,B=3.0 };",
+        &expect![[r#"
+            let x = new Foo { A = 1, B = 2.0 };
+            let y = new Foo { ...x, B = 3.0 };"#]],
+    );
+}
+
+#[test]
+fn enum_decls_begin_on_their_own_lines() {
+    check(
+        "struct Bar{A:Int} enum Baz{X(Int),Y} function Qux() : Unit {}",
+        &expect![[r#"
+            struct Bar { A : Int }
+            enum Baz { X(Int), Y }
+            function Qux() : Unit {}"#]],
+    );
+}
+
+#[test]
+fn match_arms_on_their_own_lines() {
+    check(
+        indoc! {r#"
+        let x = match  s {
+        Shape.Circle(r)=>r,
+                Shape.Empty  =>  0.0,
+        };
+        "#},
+        &expect![[r#"
+            let x = match s {
+                Shape.Circle(r) => r,
+                Shape.Empty => 0.0,
+            };
+        "#]],
+    );
+}
+
 // Functor specializations begin on their own lines
 
 #[test]
@@ -863,6 +931,22 @@ fn type_param_lists_have_no_spaces_around_delims() {
     );
 }
 
+#[test]
+fn type_param_class_bounds_have_spaces_around_colon_and_plus() {
+    check(
+        indoc! {r#"
+        {
+            function Foo<'A:Add+Eq,   'B>(a : 'A, b : 'B) : Unit {}
+        }
+    "#},
+        &expect![[r#"
+            {
+                function Foo<'A : Add + Eq, 'B>(a : 'A, b : 'B) : Unit {}
+            }
+        "#]],
+    );
+}
+
 #[test]
 fn greater_than_and_less_than_bin_ops_have_spaces() {
     check(indoc! {r#"x<y>z;"#}, &expect!["x < y > z;"])
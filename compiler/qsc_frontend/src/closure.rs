@@ -129,6 +129,8 @@ pub(super) fn lift(
         },
         generics: Vec::new(),
         input,
+        defaults: Vec::new(),
+        default_items: Vec::new(),
         output: lambda.body.ty.clone(),
         functors: lambda.functors,
         body: SpecDecl {
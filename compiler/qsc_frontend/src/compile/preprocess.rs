@@ -61,6 +61,10 @@ impl MutVisitor for Conditional {
                             name: ident.name.clone(),
                             namespace: namespace.name.name.clone(),
                         }),
+                        ItemKind::Const(decl) => self.included_names.push(TrackedName {
+                            name: decl.name.name.clone(),
+                            namespace: namespace.name.name.clone(),
+                        }),
                         _ => {}
                     }
                     Some(item.clone())
@@ -76,6 +80,10 @@ impl MutVisitor for Conditional {
                             name: ident.name.clone(),
                             namespace: namespace.name.name.clone(),
                         }),
+                        ItemKind::Const(decl) => self.dropped_names.push(TrackedName {
+                            name: decl.name.name.clone(),
+                            namespace: namespace.name.name.clone(),
+                        }),
                         _ => {}
                     }
                     None
@@ -99,6 +107,10 @@ impl MutVisitor for Conditional {
                         name: ident.name.clone(),
                         namespace: Rc::from(""),
                     }),
+                    ItemKind::Const(decl) => self.included_names.push(TrackedName {
+                        name: decl.name.name.clone(),
+                        namespace: Rc::from(""),
+                    }),
                     _ => {}
                 }
             } else {
@@ -113,6 +125,10 @@ impl MutVisitor for Conditional {
                         name: ident.name.clone(),
                         namespace: Rc::from(""),
                     }),
+                    ItemKind::Const(decl) => self.dropped_names.push(TrackedName {
+                        name: decl.name.name.clone(),
+                        namespace: Rc::from(""),
+                    }),
                     _ => {}
                 }
                 stmt.kind = Box::new(StmtKind::Empty);
@@ -7,7 +7,7 @@ mod tests;
 use crate::{
     closure::{self, Lambda, PartialApp},
     resolve::{self, Names},
-    typeck::{self, convert},
+    typeck::{self, convert, OmittedArgs},
 };
 use miette::Diagnostic;
 use qsc_ast::ast;
@@ -15,10 +15,11 @@ use qsc_data_structures::{index_map::IndexMap, span::Span, target::TargetCapabil
 use qsc_hir::{
     assigner::Assigner,
     hir::{self, LocalItemId},
-    mut_visit::MutVisitor,
-    ty::{Arrow, FunctorSetValue, Ty},
+    mut_visit::{self, MutVisitor},
+    ty::{Arrow, FunctorSet, FunctorSetValue, GenericParam, Prim, Ty},
 };
-use std::{clone::Clone, rc::Rc, str::FromStr, vec};
+use rustc_hash::FxHashMap;
+use std::{clone::Clone, rc::Rc, slice, str::FromStr, vec};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Diagnostic, Error)]
@@ -42,6 +43,14 @@ pub(super) enum Error {
     #[error("invalid pattern for specialization declaration")]
     #[diagnostic(code("Qsc.LowerAst.InvalidSpecPat"))]
     InvalidSpecPat(#[label] Span),
+    #[error("break outside of a loop")]
+    #[diagnostic(help("`break` may only be used inside the body of a `for` or `while` loop"))]
+    #[diagnostic(code("Qsc.LowerAst.BreakOutsideLoop"))]
+    BreakOutsideLoop(#[label] Span),
+    #[error("continue outside of a loop")]
+    #[diagnostic(help("`continue` may only be used inside the body of a `for` or `while` loop"))]
+    #[diagnostic(code("Qsc.LowerAst.ContinueOutsideLoop"))]
+    ContinueOutsideLoop(#[label] Span),
 }
 
 #[derive(Clone, Copy)]
@@ -50,12 +59,22 @@ enum ItemScope {
     Local,
 }
 
+/// A level of a nested update: a field of a record, or an index held by a local.
+enum UpdateLevel {
+    Field(hir::Field),
+    Index(hir::NodeId, Ty),
+}
+
 pub(super) struct Lowerer {
     nodes: IndexMap<ast::NodeId, hir::NodeId>,
     locals: IndexMap<hir::NodeId, (hir::Ident, Ty)>,
     parent: Option<LocalItemId>,
     items: Vec<hir::Item>,
+    /// The callables that compute the default values of each callable's parameters, which are
+    /// created by the first call that omits arguments or by the callable's declaration.
+    default_items: FxHashMap<LocalItemId, Vec<LocalItemId>>,
     errors: Vec<Error>,
+    loop_depth: usize,
 }
 
 impl Lowerer {
@@ -65,7 +84,9 @@ impl Lowerer {
             locals: IndexMap::new(),
             parent: None,
             items: Vec::new(),
+            default_items: FxHashMap::default(),
             errors: Vec::new(),
+            loop_depth: 0,
         }
     }
 
@@ -130,11 +151,14 @@ impl With<'_> {
         };
 
         self.lowerer.parent = Some(id);
-        let items = namespace
-            .items
-            .iter()
-            .filter_map(|i| self.lower_item(ItemScope::Global, i))
-            .collect();
+        let mut items = Vec::new();
+        for item in &*namespace.items {
+            if let ast::ItemKind::Export(decl) = &*item.kind {
+                items.extend(self.lower_export_decl(item, decl));
+            } else if let Some(id) = self.lower_item(ItemScope::Global, item) {
+                items.push(id);
+            }
+        }
 
         let name = self.lower_ident(&namespace.name);
         self.lowerer.items.push(hir::Item {
@@ -171,12 +195,15 @@ impl With<'_> {
         };
 
         let (id, kind) = match &*item.kind {
-            ast::ItemKind::Err | ast::ItemKind::Open(..) => return None,
+            // Exports are lowered as part of their namespace, and are an error anywhere else.
+            ast::ItemKind::Err | ast::ItemKind::Open(..) | ast::ItemKind::Export(..) => {
+                return None
+            }
             ast::ItemKind::Callable(callable) => {
                 let id = resolve_id(callable.name.id);
                 let grandparent = self.lowerer.parent;
                 self.lowerer.parent = Some(id.item);
-                let callable = self.lower_callable_decl(callable);
+                let callable = self.outside_loop(|this| this.lower_callable_decl(callable));
                 self.lowerer.parent = grandparent;
                 (id, hir::ItemKind::Callable(callable))
             }
@@ -190,6 +217,38 @@ impl With<'_> {
 
                 (id, hir::ItemKind::Ty(self.lower_ident(name), udt.clone()))
             }
+            ast::ItemKind::Struct(decl) => {
+                let id = resolve_id(decl.name.id);
+                let udt = self
+                    .tys
+                    .udts
+                    .get(&id)
+                    .expect("struct item should have lowered UDT");
+
+                (
+                    id,
+                    hir::ItemKind::Ty(self.lower_ident(&decl.name), udt.clone()),
+                )
+            }
+            ast::ItemKind::Enum(decl) => {
+                let id = resolve_id(decl.name.id);
+                let udt = self
+                    .tys
+                    .udts
+                    .get(&id)
+                    .expect("enum item should have lowered UDT");
+
+                (
+                    id,
+                    hir::ItemKind::Ty(self.lower_ident(&decl.name), udt.clone()),
+                )
+            }
+            ast::ItemKind::Const(decl) => {
+                let id = resolve_id(decl.name.id);
+                let name = self.lower_ident(&decl.name);
+                let value = self.outside_loop(|this| this.lower_expr(&decl.value));
+                (id, hir::ItemKind::Const(name, value))
+            }
         };
 
         self.lowerer.items.push(hir::Item {
@@ -205,6 +264,36 @@ impl With<'_> {
         Some(id.item)
     }
 
+    /// Lowers each successfully resolved entry of an export declaration into its own item.
+    fn lower_export_decl(&mut self, item: &ast::Item, decl: &ast::ExportDecl) -> Vec<LocalItemId> {
+        let visibility = item
+            .visibility
+            .as_ref()
+            .map_or(hir::Visibility::Public, lower_visibility);
+
+        let mut items = Vec::new();
+        for export in &*decl.items {
+            let (Some(&resolve::Res::Item(id, _)), Some(&resolve::Res::Item(target, _))) =
+                (self.names.get(export.id), self.names.get(export.path.id))
+            else {
+                continue;
+            };
+
+            let name = self.lower_ident(export.name());
+            self.lowerer.items.push(hir::Item {
+                id: id.item,
+                span: export.span,
+                parent: self.lowerer.parent,
+                doc: Rc::clone(&item.doc),
+                attrs: Vec::new(),
+                visibility,
+                kind: hir::ItemKind::Export(name, target),
+            });
+            items.push(id.item);
+        }
+        items
+    }
+
     fn lower_attr(&mut self, attr: &ast::Attr) -> Option<hir::Attr> {
         match hir::Attr::from_str(attr.name.name.as_ref()) {
             Ok(hir::Attr::EntryPoint) => match &*attr.arg.kind {
@@ -225,6 +314,15 @@ impl With<'_> {
                     None
                 }
             },
+            Ok(hir::Attr::NoInline) => match &*attr.arg.kind {
+                ast::ExprKind::Tuple(args) if args.is_empty() => Some(hir::Attr::NoInline),
+                _ => {
+                    self.lowerer
+                        .errors
+                        .push(Error::InvalidAttrArgs("()".to_string(), attr.arg.span));
+                    None
+                }
+            },
             Ok(hir::Attr::Config) => {
                 if !matches!(attr.arg.kind.as_ref(), ast::ExprKind::Paren(inner)
                     if matches!(inner.kind.as_ref(), ast::ExprKind::Path(path)
@@ -252,8 +350,10 @@ impl With<'_> {
         let kind = lower_callable_kind(decl.kind);
         let name = self.lower_ident(&decl.name);
         let mut input = self.lower_pat(&decl.input);
+        let defaults: Vec<_> = decl.defaults.iter().map(|d| self.lower_expr(d)).collect();
         let output = convert::ty_from_ast(self.names, &decl.output).0;
         let generics = convert::synthesize_callable_generics(&decl.generics, &mut input);
+        let default_items = self.lower_default_items(&input, &defaults, &generics);
         let functors = convert::ast_callable_functors(decl);
 
         let (body, adj, ctl, ctl_adj) = match decl.body.as_ref() {
@@ -288,6 +388,8 @@ impl With<'_> {
             name,
             generics,
             input,
+            defaults,
+            default_items,
             output,
             functors,
             body,
@@ -297,6 +399,114 @@ impl With<'_> {
         }
     }
 
+    /// Creates the callables that compute the default values of a callable's trailing parameters.
+    /// Each one takes the parameters before its own as a tuple, since a default value can use them.
+    fn lower_default_items(
+        &mut self,
+        input: &hir::Pat,
+        defaults: &[hir::Expr],
+        generics: &[GenericParam],
+    ) -> Vec<LocalItemId> {
+        if defaults.is_empty() {
+            return Vec::new();
+        }
+
+        let item = self.lowerer.parent.expect("callable should have item ID");
+        let kinds = &self
+            .tys
+            .defaults
+            .get(&hir::ItemId {
+                package: None,
+                item,
+            })
+            .expect("callable should have default parameters")
+            .kinds;
+        let items = self.local_default_items(item, defaults.len());
+        let params = match &input.kind {
+            hir::PatKind::Tuple(params) => params.as_slice(),
+            _ => slice::from_ref(input),
+        };
+        let first_default = params.len() - defaults.len();
+
+        for (i, (default, (&id, &kind))) in defaults.iter().zip(items.iter().zip(kinds)).enumerate()
+        {
+            let before = &params[..first_default + i];
+            let mut renumber = Renumber {
+                assigner: self.assigner,
+                locals: FxHashMap::default(),
+            };
+            let mut input = hir::Pat {
+                id: hir::NodeId::default(),
+                span: default.span,
+                ty: Ty::Tuple(before.iter().map(|p| p.ty.clone()).collect()),
+                kind: hir::PatKind::Tuple(before.to_vec()),
+            };
+            renumber.visit_pat(&mut input);
+            let mut body = default.clone();
+            renumber.visit_expr(&mut body);
+
+            let output = params[first_default + i].ty.clone();
+            let callable = hir::CallableDecl {
+                id: self.assigner.next_node(),
+                span: default.span,
+                kind,
+                name: hir::Ident {
+                    id: self.assigner.next_node(),
+                    span: default.span,
+                    name: "default".into(),
+                },
+                generics: generics.to_vec(),
+                input,
+                defaults: Vec::new(),
+                default_items: Vec::new(),
+                output: output.clone(),
+                functors: FunctorSetValue::Empty,
+                body: hir::SpecDecl {
+                    id: self.assigner.next_node(),
+                    span: default.span,
+                    body: hir::SpecBody::Impl(
+                        None,
+                        hir::Block {
+                            id: self.assigner.next_node(),
+                            span: default.span,
+                            ty: output,
+                            stmts: vec![hir::Stmt {
+                                id: self.assigner.next_node(),
+                                span: default.span,
+                                kind: hir::StmtKind::Expr(body),
+                            }],
+                        },
+                    ),
+                },
+                adj: None,
+                ctl: None,
+                ctl_adj: None,
+            };
+            self.lowerer.items.push(hir::Item {
+                id,
+                span: default.span,
+                parent: Some(item),
+                doc: "".into(),
+                attrs: Vec::new(),
+                visibility: hir::Visibility::Internal,
+                kind: hir::ItemKind::Callable(callable),
+            });
+        }
+
+        items
+    }
+
+    /// The IDs of the callables that compute the default values of a callable in this package,
+    /// which are allocated the first time they are needed.
+    fn local_default_items(&mut self, item: LocalItemId, count: usize) -> Vec<LocalItemId> {
+        let assigner = &mut *self.assigner;
+        self.lowerer
+            .default_items
+            .entry(item)
+            .or_insert_with(|| (0..count).map(|_| assigner.next_item()).collect())
+            .clone()
+    }
+
     fn find_spec(
         &mut self,
         specs: &[Box<ast::SpecDecl>],
@@ -428,18 +638,7 @@ impl With<'_> {
                 Box::new(self.lower_expr(rhs)),
             ),
             ast::ExprKind::AssignUpdate(container, index, replace) => {
-                if let Some(field) = resolve::extract_field_name(self.names, index) {
-                    let container = self.lower_expr(container);
-                    let field = self.lower_field(&container.ty, field);
-                    let replace = self.lower_expr(replace);
-                    hir::ExprKind::AssignField(Box::new(container), field, Box::new(replace))
-                } else {
-                    hir::ExprKind::AssignIndex(
-                        Box::new(self.lower_expr(container)),
-                        Box::new(self.lower_expr(index)),
-                        Box::new(self.lower_expr(replace)),
-                    )
-                }
+                self.lower_update(container, index, replace, true, expr.span)
             }
             ast::ExprKind::BinOp(op, lhs, rhs) => hir::ExprKind::BinOp(
                 lower_binop(*op),
@@ -447,17 +646,47 @@ impl With<'_> {
                 Box::new(self.lower_expr(rhs)),
             ),
             ast::ExprKind::Block(block) => hir::ExprKind::Block(self.lower_block(block)),
+            ast::ExprKind::Break => {
+                if self.lowerer.loop_depth == 0 {
+                    self.lowerer.errors.push(Error::BreakOutsideLoop(expr.span));
+                }
+                hir::ExprKind::Break
+            }
             ast::ExprKind::Call(callee, arg) => match &ty {
                 Ty::Arrow(arrow) if is_partial_app(arg) => hir::ExprKind::Block(
                     self.lower_partial_app(callee, arg, (**arrow).clone(), expr.span),
                 ),
+                _ if self.variant_callee(callee).is_some() => {
+                    let (item, tag) = self
+                        .variant_callee(callee)
+                        .expect("callee should be a variant");
+                    let payload = self.lower_expr(arg);
+                    self.lower_variant(item, tag, Some(payload), callee.span)
+                }
+                _ if self.tys.omitted_args(self.names, callee, arg).is_some() => {
+                    let omitted = self
+                        .tys
+                        .omitted_args(self.names, callee, arg)
+                        .expect("call should omit arguments");
+                    let callee = self.lower_expr(callee);
+                    let input = self.lower_defaulted_arg(&callee, arg, omitted);
+                    hir::ExprKind::Call(Box::new(callee), Box::new(input))
+                }
                 _ => hir::ExprKind::Call(
                     Box::new(self.lower_expr(callee)),
                     Box::new(self.lower_expr(arg)),
                 ),
             },
-            ast::ExprKind::Conjugate(within, apply) => {
-                hir::ExprKind::Conjugate(self.lower_block(within), self.lower_block(apply))
+            ast::ExprKind::Conjugate(within, apply) => self.outside_loop(|this| {
+                hir::ExprKind::Conjugate(this.lower_block(within), this.lower_block(apply))
+            }),
+            ast::ExprKind::Continue => {
+                if self.lowerer.loop_depth == 0 {
+                    self.lowerer
+                        .errors
+                        .push(Error::ContinueOutsideLoop(expr.span));
+                }
+                hir::ExprKind::Continue
             }
             ast::ExprKind::Err => hir::ExprKind::Err,
             ast::ExprKind::Fail(message) => hir::ExprKind::Fail(Box::new(self.lower_expr(message))),
@@ -469,7 +698,7 @@ impl With<'_> {
             ast::ExprKind::For(pat, iter, block) => hir::ExprKind::For(
                 self.lower_pat(pat),
                 Box::new(self.lower_expr(iter)),
-                self.lower_block(block),
+                self.lower_loop_body(block),
             ),
             ast::ExprKind::Hole => hir::ExprKind::Hole,
             ast::ExprKind::If(cond, if_true, if_false) => hir::ExprKind::If(
@@ -498,12 +727,19 @@ impl With<'_> {
                     kind: lower_callable_kind(*kind),
                     functors,
                     input: self.lower_pat(input),
-                    body: self.lower_expr(body),
+                    body: self.outside_loop(|this| this.lower_expr(body)),
                 };
                 self.lower_lambda(lambda, expr.span)
             }
             ast::ExprKind::Lit(lit) => lower_lit(lit),
+            ast::ExprKind::Match(scrutinee, arms) => {
+                hir::ExprKind::Block(self.lower_match(&ty, scrutinee, arms, expr.span))
+            }
             ast::ExprKind::Paren(_) => unreachable!("parentheses should be removed earlier"),
+            ast::ExprKind::Path(path) if self.variant_tag(path).is_some() => {
+                let (item, tag) = self.variant_tag(path).expect("path should be a variant");
+                self.lower_variant_path(&ty, item, tag, path.span)
+            }
             ast::ExprKind::Path(path) => {
                 let args = self
                     .tys
@@ -517,12 +753,17 @@ impl With<'_> {
                 step.as_ref().map(|s| Box::new(self.lower_expr(s))),
                 end.as_ref().map(|e| Box::new(self.lower_expr(e))),
             ),
-            ast::ExprKind::Repeat(body, cond, fixup) => hir::ExprKind::Repeat(
-                self.lower_block(body),
-                Box::new(self.lower_expr(cond)),
-                fixup.as_ref().map(|f| self.lower_block(f)),
-            ),
+            ast::ExprKind::Repeat(body, cond, fixup) => self.outside_loop(|this| {
+                hir::ExprKind::Repeat(
+                    this.lower_block(body),
+                    Box::new(this.lower_expr(cond)),
+                    fixup.as_ref().map(|f| this.lower_block(f)),
+                )
+            }),
             ast::ExprKind::Return(expr) => hir::ExprKind::Return(Box::new(self.lower_expr(expr))),
+            ast::ExprKind::Struct(path, copy, fields) => {
+                self.lower_struct(&ty, path.span, copy.as_deref(), fields, expr.span)
+            }
             ast::ExprKind::Interpolate(components) => hir::ExprKind::String(
                 components
                     .iter()
@@ -535,18 +776,7 @@ impl With<'_> {
                 Some(Box::new(self.lower_expr(if_false))),
             ),
             ast::ExprKind::TernOp(ast::TernOp::Update, container, index, replace) => {
-                if let Some(field) = resolve::extract_field_name(self.names, index) {
-                    let record = self.lower_expr(container);
-                    let field = self.lower_field(&record.ty, field);
-                    let replace = self.lower_expr(replace);
-                    hir::ExprKind::UpdateField(Box::new(record), field, Box::new(replace))
-                } else {
-                    hir::ExprKind::UpdateIndex(
-                        Box::new(self.lower_expr(container)),
-                        Box::new(self.lower_expr(index)),
-                        Box::new(self.lower_expr(replace)),
-                    )
-                }
+                self.lower_update(container, index, replace, false, expr.span)
             }
             ast::ExprKind::Tuple(items) => {
                 hir::ExprKind::Tuple(items.iter().map(|i| self.lower_expr(i)).collect())
@@ -555,7 +785,7 @@ impl With<'_> {
                 hir::ExprKind::UnOp(lower_unop(*op), Box::new(self.lower_expr(operand)))
             }
             ast::ExprKind::While(cond, body) => {
-                hir::ExprKind::While(Box::new(self.lower_expr(cond)), self.lower_block(body))
+                hir::ExprKind::While(Box::new(self.lower_expr(cond)), self.lower_loop_body(body))
             }
         };
 
@@ -567,6 +797,23 @@ impl With<'_> {
         }
     }
 
+    /// Lowers the body of a `for` or `while` loop, where `break` and `continue` are allowed.
+    fn lower_loop_body(&mut self, block: &ast::Block) -> hir::Block {
+        self.lowerer.loop_depth += 1;
+        let block = self.lower_block(block);
+        self.lowerer.loop_depth -= 1;
+        block
+    }
+
+    /// Lowers a construct that `break` and `continue` may not jump out of, even when it is
+    /// nested inside a loop.
+    fn outside_loop<T>(&mut self, lower: impl FnOnce(&mut Self) -> T) -> T {
+        let loop_depth = std::mem::take(&mut self.lowerer.loop_depth);
+        let lowered = lower(self);
+        self.lowerer.loop_depth = loop_depth;
+        lowered
+    }
+
     fn lower_partial_app(
         &mut self,
         callee: &ast::Expr,
@@ -574,8 +821,12 @@ impl With<'_> {
         arrow: Arrow,
         span: Span,
     ) -> hir::Block {
+        let omitted = self.tys.omitted_args(self.names, callee, arg);
         let callee = self.lower_expr(callee);
-        let (arg, app) = self.lower_partial_arg(arg);
+        let (arg, app) = match omitted {
+            Some(omitted) => self.lower_partial_defaulted_arg(&callee, arg, omitted),
+            None => self.lower_partial_arg(arg),
+        };
         let close = |mut lambda: Lambda| {
             self.assigner.visit_expr(&mut lambda.body);
             self.lower_lambda(lambda, span)
@@ -607,6 +858,217 @@ impl With<'_> {
         }
     }
 
+    /// Lowers the input of a call that omits arguments for parameters with default values.
+    fn lower_defaulted_arg(
+        &mut self,
+        callee: &hir::Expr,
+        arg: &ast::Expr,
+        omitted: OmittedArgs,
+    ) -> hir::Expr {
+        if omitted.controls > 0 {
+            let (ctls, inner) = controlled_args(arg);
+            let ctls = self.lower_expr(ctls);
+            let inner = self.lower_defaulted_arg(
+                callee,
+                inner,
+                OmittedArgs {
+                    controls: omitted.controls - 1,
+                    ..omitted
+                },
+            );
+            return hir::Expr {
+                id: self.assigner.next_node(),
+                span: arg.span,
+                ty: Ty::Tuple(vec![ctls.ty.clone(), inner.ty.clone()]),
+                kind: hir::ExprKind::Tuple(vec![ctls, inner]),
+            };
+        }
+
+        let args = given_args(arg)
+            .into_iter()
+            .map(|arg| self.lower_expr(arg))
+            .collect();
+        self.defaulted_input(callee, args, omitted, arg.span)
+    }
+
+    /// Lowers the input of a partial application that omits arguments for parameters with default
+    /// values. The default values are computed when the resulting lambda is called, since they can
+    /// depend on the holes.
+    fn lower_partial_defaulted_arg(
+        &mut self,
+        callee: &hir::Expr,
+        arg: &ast::Expr,
+        omitted: OmittedArgs,
+    ) -> (hir::Expr, PartialApp) {
+        if omitted.controls > 0 {
+            let (ctls, inner) = controlled_args(arg);
+            let ctls = self.lower_partial_arg(ctls);
+            let inner = self.lower_partial_defaulted_arg(
+                callee,
+                inner,
+                OmittedArgs {
+                    controls: omitted.controls - 1,
+                    ..omitted
+                },
+            );
+            let (mut arg, mut app) =
+                closure::partial_app_tuple([ctls, inner].into_iter(), arg.span);
+            self.assigner.visit_expr(&mut arg);
+            self.assigner.visit_pat(&mut app.input);
+            return (arg, app);
+        }
+
+        let items: Vec<_> = given_args(arg)
+            .into_iter()
+            .map(|arg| self.lower_partial_arg(arg))
+            .collect();
+        let (args, mut app) = closure::partial_app_tuple(items.into_iter(), arg.span);
+        self.assigner.visit_pat(&mut app.input);
+        let hir::ExprKind::Tuple(args) = args.kind else {
+            panic!("partial application arguments should be a tuple");
+        };
+        (self.defaulted_input(callee, args, omitted, arg.span), app)
+    }
+
+    /// Builds the input of a call that omits arguments from the arguments it gives. The given
+    /// arguments are bound to locals, followed by the default value of each omitted argument, which
+    /// is computed by calling the callable for its parameter with the arguments before it.
+    fn defaulted_input(
+        &mut self,
+        callee: &hir::Expr,
+        args: Vec<hir::Expr>,
+        omitted: OmittedArgs,
+        span: Span,
+    ) -> hir::Expr {
+        let tys = self.tys;
+        let params = tys
+            .defaults
+            .get(&omitted.item)
+            .expect("callee should have default parameters");
+        let items = match omitted.item.package {
+            None => self
+                .local_default_items(omitted.item.item, params.kinds.len())
+                .into_iter()
+                .map(|item| hir::ItemId {
+                    package: None,
+                    item,
+                })
+                .collect(),
+            Some(_) => params.items.clone(),
+        };
+        let callable = base_callee(callee);
+        let generic_args = match &callable.kind {
+            hir::ExprKind::Var(_, args) => args.clone(),
+            _ => Vec::new(),
+        };
+        let param_tys = param_tys(callable, omitted.params);
+
+        let mut stmts = Vec::new();
+        let mut locals = Vec::new();
+        for arg in args {
+            locals.push(self.bind_arg(arg, &mut stmts));
+        }
+
+        let first_default = omitted.params - items.len();
+        for param in omitted.given..omitted.params {
+            let input = self.locals_tuple(&locals, span);
+            let output = param_tys[param].clone();
+            let default_callee = hir::Expr {
+                id: self.assigner.next_node(),
+                span,
+                ty: Ty::Arrow(Box::new(Arrow {
+                    kind: params.kinds[param - first_default],
+                    input: Box::new(input.ty.clone()),
+                    output: Box::new(output.clone()),
+                    functors: FunctorSet::Value(FunctorSetValue::Empty),
+                })),
+                kind: hir::ExprKind::Var(
+                    hir::Res::Item(items[param - first_default]),
+                    generic_args.clone(),
+                ),
+            };
+            let value = hir::Expr {
+                id: self.assigner.next_node(),
+                span,
+                ty: output,
+                kind: hir::ExprKind::Call(Box::new(default_callee), Box::new(input)),
+            };
+            locals.push(self.bind_arg(value, &mut stmts));
+        }
+
+        let input = if omitted.params == 1 {
+            let (id, ty) = locals.pop().expect("callee should have a parameter");
+            self.local_var(id, ty, span)
+        } else {
+            self.locals_tuple(&locals, span)
+        };
+        let ty = input.ty.clone();
+        stmts.push(hir::Stmt {
+            id: self.assigner.next_node(),
+            span,
+            kind: hir::StmtKind::Expr(input),
+        });
+        hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty: ty.clone(),
+            kind: hir::ExprKind::Block(hir::Block {
+                id: self.assigner.next_node(),
+                span,
+                ty,
+                stmts,
+            }),
+        }
+    }
+
+    fn bind_arg(&mut self, arg: hir::Expr, stmts: &mut Vec<hir::Stmt>) -> (hir::NodeId, Ty) {
+        let id = self.assigner.next_node();
+        let ty = arg.ty.clone();
+        let ident = hir::Ident {
+            id,
+            span: arg.span,
+            name: "arg".into(),
+        };
+        self.lowerer.locals.insert(id, (ident.clone(), ty.clone()));
+        stmts.push(hir::Stmt {
+            id: self.assigner.next_node(),
+            span: arg.span,
+            kind: hir::StmtKind::Local(
+                hir::Mutability::Immutable,
+                hir::Pat {
+                    id: self.assigner.next_node(),
+                    span: arg.span,
+                    ty: ty.clone(),
+                    kind: hir::PatKind::Bind(ident),
+                },
+                arg,
+            ),
+        });
+        (id, ty)
+    }
+
+    fn locals_tuple(&mut self, locals: &[(hir::NodeId, Ty)], span: Span) -> hir::Expr {
+        let items: Vec<_> = locals
+            .iter()
+            .map(|(id, ty)| self.local_var(*id, ty.clone(), span))
+            .collect();
+        hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty: Ty::Tuple(items.iter().map(|item| item.ty.clone()).collect()),
+            kind: hir::ExprKind::Tuple(items),
+        }
+    }
+
+    fn local_var(&mut self, id: hir::NodeId, ty: Ty, span: Span) -> hir::Expr {
+        hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty,
+            kind: hir::ExprKind::Var(hir::Res::Local(id), Vec::new()),
+        }
+    }
+
     fn lower_lambda(&mut self, lambda: Lambda, span: Span) -> hir::ExprKind {
         let (args, callable) = closure::lift(self.assigner, &self.lowerer.locals, lambda, span);
 
@@ -624,6 +1086,658 @@ impl With<'_> {
         hir::ExprKind::Closure(args, id)
     }
 
+    /// Lowers a struct expression. A copy becomes a chain of field updates on the copied value.
+    /// Otherwise the struct is built by calling the type's constructor, which takes the field
+    /// values in declaration order, after the field values are evaluated in source order.
+    fn lower_struct(
+        &mut self,
+        ty: &Ty,
+        name_span: Span,
+        copy: Option<&ast::Expr>,
+        fields: &[Box<ast::FieldAssign>],
+        span: Span,
+    ) -> hir::ExprKind {
+        if let Some(copy) = copy {
+            let mut record = self.lower_expr(copy);
+            let Some((last, rest)) = fields.split_last() else {
+                return record.kind;
+            };
+            for field in rest {
+                let kind = self.lower_field_update(ty, record, field);
+                record = hir::Expr {
+                    id: self.assigner.next_node(),
+                    span: Span {
+                        lo: span.lo,
+                        hi: field.span.hi,
+                    },
+                    ty: ty.clone(),
+                    kind,
+                };
+            }
+            return self.lower_field_update(ty, record, last);
+        }
+
+        let tys = self.tys;
+        let Ty::Udt(_, hir::Res::Item(item)) = ty else {
+            return hir::ExprKind::Err;
+        };
+        let udt = tys.udts.get(item).expect("struct type should have UDT");
+        let Some(struct_fields) = udt.struct_fields() else {
+            return hir::ExprKind::Err;
+        };
+
+        let indices = fields
+            .iter()
+            .map(|field| {
+                struct_fields
+                    .iter()
+                    .position(|f| f.name.as_ref() == Some(&field.field.name))
+            })
+            .collect::<Vec<_>>();
+        // Initializers given out of declaration order are bound to locals first, so that they are
+        // evaluated in source order.
+        let in_order = indices.windows(2).all(|pair| pair[0] < pair[1]);
+        let mut stmts = Vec::new();
+        let mut values: Vec<Option<hir::Expr>> = struct_fields.iter().map(|_| None).collect();
+        for (field, index) in fields.iter().zip(indices) {
+            let mut value = self.lower_expr(&field.value);
+            if !in_order {
+                let (id, ty) = self.bind_arg(value, &mut stmts);
+                value = self.local_var(id, ty, field.value.span);
+            }
+            if let Some(index) = index {
+                values[index] = Some(value);
+            }
+        }
+        let Some(mut values) = values.into_iter().collect::<Option<Vec<_>>>() else {
+            return hir::ExprKind::Err;
+        };
+
+        let callee = hir::Expr {
+            id: self.assigner.next_node(),
+            span: name_span,
+            ty: Ty::Arrow(Box::new(
+                udt.cons_scheme(*item)
+                    .instantiate(&[])
+                    .expect("constructor should not be generic"),
+            )),
+            kind: hir::ExprKind::Var(hir::Res::Item(*item), Vec::new()),
+        };
+        let arg = if values.len() == 1 {
+            values.pop().expect("struct should have one field")
+        } else {
+            hir::Expr {
+                id: self.assigner.next_node(),
+                span,
+                ty: Ty::Tuple(values.iter().map(|v| v.ty.clone()).collect()),
+                kind: hir::ExprKind::Tuple(values),
+            }
+        };
+        let call = hir::ExprKind::Call(Box::new(callee), Box::new(arg));
+        if stmts.is_empty() {
+            return call;
+        }
+
+        stmts.push(hir::Stmt {
+            id: self.assigner.next_node(),
+            span,
+            kind: hir::StmtKind::Expr(hir::Expr {
+                id: self.assigner.next_node(),
+                span,
+                ty: ty.clone(),
+                kind: call,
+            }),
+        });
+        hir::ExprKind::Block(hir::Block {
+            id: self.assigner.next_node(),
+            span,
+            ty: ty.clone(),
+            stmts,
+        })
+    }
+
+    /// The enum and the tag of the variant named by the path, if the path is a variant of a known
+    /// enum.
+    fn variant_tag(&self, path: &ast::Path) -> Option<(hir::ItemId, usize)> {
+        let Some(&resolve::Res::Variant(item)) = self.names.get(path.id) else {
+            return None;
+        };
+        let udt = self.tys.udts.get(&item)?;
+        udt.find_variant(&path.name.name)
+            .map(|(tag, _)| (item, tag))
+    }
+
+    fn variant_callee(&self, callee: &ast::Expr) -> Option<(hir::ItemId, usize)> {
+        match callee.kind.as_ref() {
+            ast::ExprKind::Paren(inner) => self.variant_callee(inner),
+            ast::ExprKind::Path(path) => self.variant_tag(path),
+            _ => None,
+        }
+    }
+
+    /// Lowers a variant value to the representation shared by all variants of the enum: a tuple
+    /// of the tag followed by one array per variant, where only the array of the given variant
+    /// holds the payload. See [`hir::ty::Udt::get_pure_ty`].
+    fn lower_variant(
+        &mut self,
+        item: hir::ItemId,
+        tag: usize,
+        payload: Option<hir::Expr>,
+        span: Span,
+    ) -> hir::ExprKind {
+        let Ty::Tuple(slot_tys) = self
+            .tys
+            .udts
+            .get(&item)
+            .expect("enum should have lowered UDT")
+            .get_pure_ty()
+        else {
+            panic!("enum should be represented as a tuple");
+        };
+
+        let mut items = vec![hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty: Ty::Prim(Prim::Int),
+            kind: hir::ExprKind::Lit(hir::Lit::Int(
+                tag.try_into()
+                    .expect("variant tag should fit into an integer"),
+            )),
+        }];
+        let mut payload = payload;
+        for (index, slot_ty) in slot_tys.into_iter().skip(1).enumerate() {
+            let values = if index == tag {
+                payload.take().into_iter().collect()
+            } else {
+                Vec::new()
+            };
+            items.push(hir::Expr {
+                id: self.assigner.next_node(),
+                span,
+                ty: slot_ty,
+                kind: hir::ExprKind::Array(values),
+            });
+        }
+        hir::ExprKind::Tuple(items)
+    }
+
+    /// Lowers a variant used as a value. A variant without a payload is the value itself, and a
+    /// variant with a payload becomes a function from the payload to the value.
+    fn lower_variant_path(
+        &mut self,
+        ty: &Ty,
+        item: hir::ItemId,
+        tag: usize,
+        span: Span,
+    ) -> hir::ExprKind {
+        let Ty::Arrow(arrow) = ty else {
+            return self.lower_variant(item, tag, None, span);
+        };
+
+        let (payload, app) = closure::partial_app_hole(
+            self.assigner,
+            &mut self.lowerer.locals,
+            (*arrow.input).clone(),
+            span,
+        );
+        let body = hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty: (*arrow.output).clone(),
+            kind: self.lower_variant(item, tag, Some(payload), span),
+        };
+        let lambda = Lambda {
+            kind: hir::CallableKind::Function,
+            functors: FunctorSetValue::Empty,
+            input: app.input,
+            body,
+        };
+        self.lower_lambda(lambda, span)
+    }
+
+    /// Lowers a match expression to a chain of conditionals on the tag of the scrutinee. The
+    /// scrutinee is bound to a local first so that it is only evaluated once. The wildcard arm,
+    /// or otherwise the last arm, becomes the final else branch since the match is exhaustive.
+    fn lower_match(
+        &mut self,
+        ty: &Ty,
+        scrutinee: &ast::Expr,
+        arms: &[Box<ast::MatchArm>],
+        span: Span,
+    ) -> hir::Block {
+        let scrutinee = self.lower_expr(scrutinee);
+        let scrutinee_ty = scrutinee.ty.clone();
+        let local_id = self.assigner.next_node();
+        let ident = hir::Ident {
+            id: local_id,
+            span: scrutinee.span,
+            name: "@match".into(),
+        };
+        self.lowerer
+            .locals
+            .insert(local_id, (ident.clone(), scrutinee_ty.clone()));
+        let binding = hir::Stmt {
+            id: self.assigner.next_node(),
+            span: scrutinee.span,
+            kind: hir::StmtKind::Local(
+                hir::Mutability::Immutable,
+                hir::Pat {
+                    id: self.assigner.next_node(),
+                    span: scrutinee.span,
+                    ty: scrutinee_ty.clone(),
+                    kind: hir::PatKind::Bind(ident),
+                },
+                scrutinee,
+            ),
+        };
+
+        let mut branches = Vec::new();
+        for arm in arms {
+            let (tag, payload) = match arm.pat.kind.as_ref() {
+                ast::PatKind::Variant(path, payload) => (
+                    self.variant_tag(path).map(|(_, tag)| tag),
+                    payload.as_deref(),
+                ),
+                _ => (None, None),
+            };
+            let body = self.lower_match_arm_body(arm, tag, payload, local_id, &scrutinee_ty);
+            let is_wildcard = matches!(arm.pat.kind.as_ref(), ast::PatKind::Discard(None));
+            branches.push((tag, arm.pat.span, body));
+            if is_wildcard {
+                break;
+            }
+        }
+
+        let mut chain: Option<hir::Expr> = None;
+        for (tag, pat_span, body) in branches.into_iter().rev() {
+            chain = Some(match (tag, chain) {
+                (Some(tag), Some(otherwise)) => {
+                    let cond = self.lower_match_cond(local_id, &scrutinee_ty, tag, pat_span);
+                    hir::Expr {
+                        id: self.assigner.next_node(),
+                        span: Span {
+                            lo: pat_span.lo,
+                            hi: otherwise.span.hi,
+                        },
+                        ty: ty.clone(),
+                        kind: hir::ExprKind::If(
+                            Box::new(cond),
+                            Box::new(body),
+                            Some(Box::new(otherwise)),
+                        ),
+                    }
+                }
+                // The last reachable arm is taken unconditionally.
+                (_, None) => body,
+                (None, Some(_)) => hir::Expr {
+                    id: self.assigner.next_node(),
+                    span: pat_span,
+                    ty: ty.clone(),
+                    kind: hir::ExprKind::Err,
+                },
+            });
+        }
+
+        let mut stmts = vec![binding];
+        if let Some(chain) = chain {
+            stmts.push(hir::Stmt {
+                id: self.assigner.next_node(),
+                span: chain.span,
+                kind: hir::StmtKind::Expr(chain),
+            });
+        }
+        hir::Block {
+            id: self.assigner.next_node(),
+            span,
+            ty: ty.clone(),
+            stmts,
+        }
+    }
+
+    /// Lowers the body of a match arm. If the arm binds a payload, the body is wrapped in a block
+    /// that first binds the payload of the scrutinee, which is the only item in the array of the
+    /// variant.
+    fn lower_match_arm_body(
+        &mut self,
+        arm: &ast::MatchArm,
+        tag: Option<usize>,
+        payload: Option<&ast::Pat>,
+        local_id: hir::NodeId,
+        scrutinee_ty: &Ty,
+    ) -> hir::Expr {
+        let body = self.lower_expr(&arm.body);
+        let Some(payload) = payload else {
+            return body;
+        };
+
+        let payload = self.lower_pat(payload);
+        let span = arm.pat.span;
+        let value = match tag {
+            Some(tag) => {
+                let slot = self.lower_match_field(
+                    local_id,
+                    scrutinee_ty,
+                    tag + 1,
+                    Ty::Array(Box::new(payload.ty.clone())),
+                    span,
+                );
+                let index = hir::Expr {
+                    id: self.assigner.next_node(),
+                    span,
+                    ty: Ty::Prim(Prim::Int),
+                    kind: hir::ExprKind::Lit(hir::Lit::Int(0)),
+                };
+                hir::Expr {
+                    id: self.assigner.next_node(),
+                    span,
+                    ty: payload.ty.clone(),
+                    kind: hir::ExprKind::Index(Box::new(slot), Box::new(index)),
+                }
+            }
+            None => hir::Expr {
+                id: self.assigner.next_node(),
+                span,
+                ty: payload.ty.clone(),
+                kind: hir::ExprKind::Err,
+            },
+        };
+        let ty = body.ty.clone();
+        let stmts = vec![
+            hir::Stmt {
+                id: self.assigner.next_node(),
+                span: arm.pat.span,
+                kind: hir::StmtKind::Local(hir::Mutability::Immutable, payload, value),
+            },
+            hir::Stmt {
+                id: self.assigner.next_node(),
+                span: body.span,
+                kind: hir::StmtKind::Expr(body),
+            },
+        ];
+        hir::Expr {
+            id: self.assigner.next_node(),
+            span: arm.span,
+            ty: ty.clone(),
+            kind: hir::ExprKind::Block(hir::Block {
+                id: self.assigner.next_node(),
+                span: arm.span,
+                ty,
+                stmts,
+            }),
+        }
+    }
+
+    /// Lowers the condition that the scrutinee has the variant with the given tag.
+    fn lower_match_cond(
+        &mut self,
+        local_id: hir::NodeId,
+        scrutinee_ty: &Ty,
+        tag: usize,
+        span: Span,
+    ) -> hir::Expr {
+        let tag_value =
+            self.lower_match_field(local_id, scrutinee_ty, 0, Ty::Prim(Prim::Int), span);
+        let expected = hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty: Ty::Prim(Prim::Int),
+            kind: hir::ExprKind::Lit(hir::Lit::Int(
+                tag.try_into()
+                    .expect("variant tag should fit into an integer"),
+            )),
+        };
+        hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty: Ty::Prim(Prim::Bool),
+            kind: hir::ExprKind::BinOp(hir::BinOp::Eq, Box::new(tag_value), Box::new(expected)),
+        }
+    }
+
+    fn lower_match_field(
+        &mut self,
+        local_id: hir::NodeId,
+        scrutinee_ty: &Ty,
+        index: usize,
+        ty: Ty,
+        span: Span,
+    ) -> hir::Expr {
+        let scrutinee = hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty: scrutinee_ty.clone(),
+            kind: hir::ExprKind::Var(hir::Res::Local(local_id), Vec::new()),
+        };
+        hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty,
+            kind: hir::ExprKind::Field(
+                Box::new(scrutinee),
+                hir::Field::Path(hir::FieldPath {
+                    indices: vec![index],
+                }),
+            ),
+        }
+    }
+
+    /// Lowers an update operator, or its assignment form if `assign` is true. A nested update
+    /// path becomes a block that binds the container and the indices along the path, then
+    /// updates each level with the updated value of the level below it.
+    fn lower_update(
+        &mut self,
+        container: &ast::Expr,
+        index: &ast::Expr,
+        replace: &ast::Expr,
+        assign: bool,
+        span: Span,
+    ) -> hir::ExprKind {
+        let path = resolve::extract_update_path(self.names, index);
+        let index = match path.as_deref() {
+            Some([(_, resolve::UpdateStep::Field(field))]) => {
+                let container = self.lower_expr(container);
+                let field = self.lower_field(&container.ty, field);
+                let replace = Box::new(self.lower_expr(replace));
+                return if assign {
+                    hir::ExprKind::AssignField(Box::new(container), field, replace)
+                } else {
+                    hir::ExprKind::UpdateField(Box::new(container), field, replace)
+                };
+            }
+            Some([(_, resolve::UpdateStep::Index(index))]) => *index,
+            Some(path) => {
+                let block = self.lower_nested_update(container, path, replace, assign, span);
+                return hir::ExprKind::Block(block);
+            }
+            None => index,
+        };
+
+        let container = Box::new(self.lower_expr(container));
+        let index = Box::new(self.lower_expr(index));
+        let replace = Box::new(self.lower_expr(replace));
+        if assign {
+            hir::ExprKind::AssignIndex(container, index, replace)
+        } else {
+            hir::ExprKind::UpdateIndex(container, index, replace)
+        }
+    }
+
+    fn lower_nested_update(
+        &mut self,
+        container: &ast::Expr,
+        path: &[(&ast::Expr, resolve::UpdateStep)],
+        replace: &ast::Expr,
+        assign: bool,
+        span: Span,
+    ) -> hir::Block {
+        let container = self.lower_expr(container);
+        let container_ty = container.ty.clone();
+        let (target, value) = if assign {
+            // The container is the assigned variable, so the value is read from a fresh copy.
+            let value = hir::Expr {
+                id: self.assigner.next_node(),
+                kind: match &container.kind {
+                    hir::ExprKind::Var(..) => container.kind.clone(),
+                    _ => hir::ExprKind::Err,
+                },
+                ..container.clone()
+            };
+            (Some(container), value)
+        } else {
+            (None, container)
+        };
+        let (value_id, value_stmt) = self.lower_temp("@update", value);
+        let mut stmts = vec![value_stmt];
+
+        // Each level of the path with the type of the value it updates. Indices are bound to
+        // locals so that they are evaluated once, in order.
+        let mut levels = Vec::new();
+        let mut ty = container_ty;
+        for (step_expr, step) in path {
+            let level = match step {
+                resolve::UpdateStep::Field(name) => UpdateLevel::Field(self.lower_field(&ty, name)),
+                resolve::UpdateStep::Index(index) => {
+                    let index = self.lower_expr(index);
+                    let index_ty = index.ty.clone();
+                    let (index_id, index_stmt) = self.lower_temp("@index", index);
+                    stmts.push(index_stmt);
+                    UpdateLevel::Index(index_id, index_ty)
+                }
+            };
+            levels.push((level, ty));
+            ty = self
+                .tys
+                .terms
+                .get(step_expr.id)
+                .map_or(Ty::Err, Clone::clone);
+        }
+
+        let mut item = self.lower_expr(replace);
+        for depth in (0..levels.len()).rev() {
+            let target = target.as_ref().filter(|_| depth == 0);
+            let (record, ty) = match target {
+                Some(target) => (target.clone(), Ty::UNIT),
+                None => (
+                    self.lower_update_value(value_id, &levels[..=depth], span),
+                    levels[depth].1.clone(),
+                ),
+            };
+            let (record, item_box) = (Box::new(record), Box::new(item));
+            let kind = match &levels[depth].0 {
+                UpdateLevel::Field(field) if target.is_some() => {
+                    hir::ExprKind::AssignField(record, field.clone(), item_box)
+                }
+                UpdateLevel::Field(field) => {
+                    hir::ExprKind::UpdateField(record, field.clone(), item_box)
+                }
+                UpdateLevel::Index(index_id, index_ty) => {
+                    let index = Box::new(self.lower_local(*index_id, index_ty.clone(), span));
+                    if target.is_some() {
+                        hir::ExprKind::AssignIndex(record, index, item_box)
+                    } else {
+                        hir::ExprKind::UpdateIndex(record, index, item_box)
+                    }
+                }
+            };
+            item = hir::Expr {
+                id: self.assigner.next_node(),
+                span,
+                ty,
+                kind,
+            };
+        }
+
+        let ty = item.ty.clone();
+        stmts.push(hir::Stmt {
+            id: self.assigner.next_node(),
+            span,
+            kind: hir::StmtKind::Expr(item),
+        });
+        hir::Block {
+            id: self.assigner.next_node(),
+            span,
+            ty,
+            stmts,
+        }
+    }
+
+    /// Reads the value updated by the last of the given levels of a nested update from the local
+    /// holding the value being updated, following each of the levels before it.
+    fn lower_update_value(
+        &mut self,
+        value_id: hir::NodeId,
+        levels: &[(UpdateLevel, Ty)],
+        span: Span,
+    ) -> hir::Expr {
+        let value_ty = levels.first().map_or(Ty::Err, |(_, ty)| ty.clone());
+        let mut value = self.lower_local(value_id, value_ty, span);
+        for window in levels.windows(2) {
+            let (level, ty) = (&window[0].0, window[1].1.clone());
+            let kind = match level {
+                UpdateLevel::Field(field) => hir::ExprKind::Field(Box::new(value), field.clone()),
+                UpdateLevel::Index(index_id, index_ty) => {
+                    let index = self.lower_local(*index_id, index_ty.clone(), span);
+                    hir::ExprKind::Index(Box::new(value), Box::new(index))
+                }
+            };
+            value = hir::Expr {
+                id: self.assigner.next_node(),
+                span,
+                ty,
+                kind,
+            };
+        }
+        value
+    }
+
+    /// Binds a value to a new immutable local with the given name.
+    fn lower_temp(&mut self, name: &str, value: hir::Expr) -> (hir::NodeId, hir::Stmt) {
+        let local_id = self.assigner.next_node();
+        let ident = hir::Ident {
+            id: local_id,
+            span: value.span,
+            name: name.into(),
+        };
+        self.lowerer
+            .locals
+            .insert(local_id, (ident.clone(), value.ty.clone()));
+        let stmt = hir::Stmt {
+            id: self.assigner.next_node(),
+            span: value.span,
+            kind: hir::StmtKind::Local(
+                hir::Mutability::Immutable,
+                hir::Pat {
+                    id: self.assigner.next_node(),
+                    span: value.span,
+                    ty: value.ty.clone(),
+                    kind: hir::PatKind::Bind(ident),
+                },
+                value,
+            ),
+        };
+        (local_id, stmt)
+    }
+
+    fn lower_local(&mut self, local_id: hir::NodeId, ty: Ty, span: Span) -> hir::Expr {
+        hir::Expr {
+            id: self.assigner.next_node(),
+            span,
+            ty,
+            kind: hir::ExprKind::Var(hir::Res::Local(local_id), Vec::new()),
+        }
+    }
+
+    fn lower_field_update(
+        &mut self,
+        ty: &Ty,
+        record: hir::Expr,
+        field: &ast::FieldAssign,
+    ) -> hir::ExprKind {
+        let path = self.lower_field(ty, &field.field.name);
+        let value = self.lower_expr(&field.value);
+        hir::ExprKind::UpdateField(Box::new(record), path, Box::new(value))
+    }
+
     fn lower_field(&mut self, record_ty: &Ty, name: &str) -> hir::Field {
         if let Ty::Udt(_, hir::Res::Item(id)) = record_ty {
             self.tys
@@ -674,7 +1788,14 @@ impl With<'_> {
             ast::PatKind::Tuple(items) => {
                 hir::PatKind::Tuple(items.iter().map(|i| self.lower_pat(i)).collect())
             }
+            ast::PatKind::Array(items, rest) => hir::PatKind::Array(
+                items.iter().map(|i| self.lower_pat(i)).collect(),
+                rest.as_ref().map(|r| Box::new(self.lower_pat(r))),
+            ),
             ast::PatKind::Err => hir::PatKind::Err,
+            ast::PatKind::Variant(..) => {
+                unreachable!("variant patterns should only appear in match arms")
+            }
         };
 
         hir::Pat {
@@ -716,7 +1837,12 @@ impl With<'_> {
         match self.names.get(path.id) {
             Some(&resolve::Res::Item(item, _)) => hir::Res::Item(item),
             Some(&resolve::Res::Local(node)) => hir::Res::Local(self.lower_id(node)),
-            Some(resolve::Res::PrimTy(_) | resolve::Res::UnitTy | resolve::Res::Param(_))
+            Some(
+                resolve::Res::PrimTy(_)
+                | resolve::Res::UnitTy
+                | resolve::Res::Param(_)
+                | resolve::Res::Variant(_),
+            )
             | None => hir::Res::Err,
         }
     }
@@ -823,6 +1949,97 @@ fn lower_functor(functor: ast::Functor) -> hir::Functor {
     }
 }
 
+/// The arguments given by a call, which are the items of its input if it is a tuple.
+fn given_args(arg: &ast::Expr) -> Vec<&ast::Expr> {
+    match arg.kind.as_ref() {
+        ast::ExprKind::Tuple(items) => items.iter().map(AsRef::as_ref).collect(),
+        _ => vec![arg],
+    }
+}
+
+/// The control qubits and the arguments given to a controlled callable.
+fn controlled_args(arg: &ast::Expr) -> (&ast::Expr, &ast::Expr) {
+    match typeck::strip_parens(arg).kind.as_ref() {
+        ast::ExprKind::Tuple(items) if items.len() == 2 => (&items[0], &items[1]),
+        _ => panic!("controlled call should have control and argument tuple"),
+    }
+}
+
+/// The callable underneath any functors applied to a callee.
+fn base_callee(callee: &hir::Expr) -> &hir::Expr {
+    match &callee.kind {
+        hir::ExprKind::UnOp(hir::UnOp::Functor(_), inner) => base_callee(inner),
+        _ => callee,
+    }
+}
+
+/// The types of the parameters of a callable that a call omits arguments for.
+fn param_tys(callable: &hir::Expr, params: usize) -> Vec<Ty> {
+    match &callable.ty {
+        Ty::Arrow(arrow) => match &*arrow.input {
+            Ty::Tuple(items) if params != 1 && items.len() == params => items.clone(),
+            input if params == 1 => vec![input.clone()],
+            _ => vec![Ty::Err; params],
+        },
+        _ => vec![Ty::Err; params],
+    }
+}
+
+/// Gives a copy of lowered nodes fresh IDs. The locals bound by the copy are renamed too, along with
+/// the references to them.
+struct Renumber<'a> {
+    assigner: &'a mut Assigner,
+    locals: FxHashMap<hir::NodeId, hir::NodeId>,
+}
+
+impl MutVisitor for Renumber<'_> {
+    fn visit_block(&mut self, block: &mut hir::Block) {
+        block.id = self.assigner.next_node();
+        mut_visit::walk_block(self, block);
+    }
+
+    fn visit_stmt(&mut self, stmt: &mut hir::Stmt) {
+        stmt.id = self.assigner.next_node();
+        mut_visit::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &mut hir::Expr) {
+        expr.id = self.assigner.next_node();
+        match &mut expr.kind {
+            hir::ExprKind::Var(hir::Res::Local(id), _) => {
+                if let Some(&new_id) = self.locals.get(id) {
+                    *id = new_id;
+                }
+            }
+            hir::ExprKind::Closure(args, _) => {
+                for arg in args {
+                    if let Some(&new_id) = self.locals.get(arg) {
+                        *arg = new_id;
+                    }
+                }
+            }
+            _ => {}
+        }
+        mut_visit::walk_expr(self, expr);
+    }
+
+    fn visit_pat(&mut self, pat: &mut hir::Pat) {
+        pat.id = self.assigner.next_node();
+        mut_visit::walk_pat(self, pat);
+    }
+
+    fn visit_qubit_init(&mut self, init: &mut hir::QubitInit) {
+        init.id = self.assigner.next_node();
+        mut_visit::walk_qubit_init(self, init);
+    }
+
+    fn visit_ident(&mut self, ident: &mut hir::Ident) {
+        let new_id = self.assigner.next_node();
+        self.locals.insert(ident.id, new_id);
+        ident.id = new_id;
+    }
+}
+
 fn is_partial_app(arg: &ast::Expr) -> bool {
     match arg.kind.as_ref() {
         ast::ExprKind::Hole => true,
@@ -616,6 +616,104 @@ fn lift_newtype_from_newtype() {
     );
 }
 
+#[test]
+fn lower_struct_cons() {
+    check_hir(
+        indoc! {"
+            namespace A {
+                struct Pair { First : Int, Second : Double }
+                function Foo() : Pair { new Pair { Second = 2.0, First = 1 } }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-131] (Public):
+                    Namespace (Ident 23 [10-11] "A"): Item 1, Item 2
+                Item 1 [18-62] (Public):
+                    Parent: 0
+                    Type (Ident 0 [25-29] "Pair"): UDT [18-62]:
+                        TyDef [18-62]: Tuple:
+                            TyDef [32-43]: Field:
+                                name: First [32-37]
+                                type: Int
+                            TyDef [45-60]: Field:
+                                name: Second [45-51]
+                                type: Double
+                Item 2 [67-129] (Public):
+                    Parent: 0
+                    Callable 1 [67-129] (function):
+                        name: Ident 2 [76-79] "Foo"
+                        input: Pat 3 [79-81] [Type Unit]: Unit
+                        output: UDT<"Pair": Item 1>
+                        functors: empty set
+                        body: SpecDecl 4 [67-129]: Impl:
+                            Block 5 [89-129] [Type UDT<"Pair": Item 1>]:
+                                Stmt 6 [91-127]: Expr: Expr 7 [91-127] [Type UDT<"Pair": Item 1>]: Expr Block: Block 22 [91-127] [Type UDT<"Pair": Item 1>]:
+                                    Stmt 10 [111-114]: Local (Immutable):
+                                        Pat 11 [111-114] [Type Double]: Bind: Ident 9 [111-114] "arg"
+                                        Expr 8 [111-114] [Type Double]: Lit: Double(2)
+                                    Stmt 15 [124-125]: Local (Immutable):
+                                        Pat 16 [124-125] [Type Int]: Bind: Ident 14 [124-125] "arg"
+                                        Expr 13 [124-125] [Type Int]: Lit: Int(1)
+                                    Stmt 20 [91-127]: Expr: Expr 21 [91-127] [Type UDT<"Pair": Item 1>]: Call:
+                                        Expr 18 [95-99] [Type ((Int, Double) -> UDT<"Pair": Item 1>)]: Var: Item 1
+                                        Expr 19 [91-127] [Type (Int, Double)]: Tuple:
+                                            Expr 17 [124-125] [Type Int]: Var: Local 14
+                                            Expr 12 [111-114] [Type Double]: Var: Local 9
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
+#[test]
+fn lower_struct_copy() {
+    check_hir(
+        indoc! {"
+            namespace A {
+                struct Triple { First : Int, Second : Int, Third : Int }
+                function Foo(t : Triple) : Triple { new Triple { ...t, First = 1, Third = 3 } }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-160] (Public):
+                    Namespace (Ident 13 [10-11] "A"): Item 1, Item 2
+                Item 1 [18-74] (Public):
+                    Parent: 0
+                    Type (Ident 0 [25-31] "Triple"): UDT [18-74]:
+                        TyDef [18-74]: Tuple:
+                            TyDef [34-45]: Field:
+                                name: First [34-39]
+                                type: Int
+                            TyDef [47-59]: Field:
+                                name: Second [47-53]
+                                type: Int
+                            TyDef [61-72]: Field:
+                                name: Third [61-66]
+                                type: Int
+                Item 2 [79-158] (Public):
+                    Parent: 0
+                    Callable 1 [79-158] (function):
+                        name: Ident 2 [88-91] "Foo"
+                        input: Pat 3 [92-102] [Type UDT<"Triple": Item 1>]: Bind: Ident 4 [92-93] "t"
+                        output: UDT<"Triple": Item 1>
+                        functors: empty set
+                        body: SpecDecl 5 [79-158]: Impl:
+                            Block 6 [113-158] [Type UDT<"Triple": Item 1>]:
+                                Stmt 7 [115-156]: Expr: Expr 8 [115-156] [Type UDT<"Triple": Item 1>]: UpdateField:
+                                    Expr 11 [115-143] [Type UDT<"Triple": Item 1>]: UpdateField:
+                                        Expr 9 [131-132] [Type UDT<"Triple": Item 1>]: Var: Local 4
+                                        Path([0])
+                                        Expr 10 [142-143] [Type Int]: Lit: Int(1)
+                                    Path([2])
+                                    Expr 12 [153-154] [Type Int]: Lit: Int(3)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
 #[test]
 fn lambda_function_empty_closure() {
     check_hir(
@@ -2072,6 +2170,38 @@ fn duplicate_commas_in_generics() {
     );
 }
 
+#[test]
+fn generics_with_class_bounds() {
+    check_hir(
+        indoc! {r#"
+            namespace test {
+                function Foo<'T : Eq + Show, 'U>(x : 'T, y : 'U) : Unit {}
+            }
+        "#},
+        &expect![[r#"
+            Package:
+                Item 0 [0-81] (Public):
+                    Namespace (Ident 9 [10-14] "test"): Item 1
+                Item 1 [21-79] (Public):
+                    Parent: 0
+                    Callable 0 [21-79] (function):
+                        name: Ident 1 [30-33] "Foo"
+                        generics:
+                            0: type [34-36] "'T": Eq + Show
+                            1: type [50-52] "'U"
+                        input: Pat 2 [53-69] [Type (Param<"'T": 0>, Param<"'U": 1>)]: Tuple:
+                            Pat 3 [54-60] [Type Param<"'T": 0>]: Bind: Ident 4 [54-55] "x"
+                            Pat 5 [62-68] [Type Param<"'U": 1>]: Bind: Ident 6 [62-63] "y"
+                        output: Unit
+                        functors: empty set
+                        body: SpecDecl 7 [21-79]: Impl:
+                            Block 8 [77-79]: <empty>
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
 #[test]
 fn duplicate_commas_in_pat() {
     check_hir(
@@ -2203,3 +2333,486 @@ fn ignore_item_in_attribute() {
                         ctl-adj: <none>"#]],
     );
 }
+
+#[test]
+fn lower_enum_match() {
+    check_hir(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo() : Double {
+                    match Shape.Circle(1.0) {
+                        Shape.Circle(r) => r,
+                        Shape.Empty => 0.0,
+                    }
+                }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-202] (Public):
+                    Namespace (Ident 35 [10-11] "A"): Item 1, Item 2
+                Item 1 [18-54] (Public):
+                    Parent: 0
+                    Type (Ident 0 [23-28] "Shape"): UDT [18-54]:
+                        TyDef [18-54]: Enum:
+                            Variant [31-37] (Circle): Double
+                            Variant [47-52] (Empty)
+                Item 2 [59-200] (Public):
+                    Parent: 0
+                    Callable 1 [59-200] (function):
+                        name: Ident 2 [68-71] "Foo"
+                        input: Pat 3 [71-73] [Type Unit]: Unit
+                        output: Double
+                        functors: empty set
+                        body: SpecDecl 4 [59-200]: Impl:
+                            Block 5 [83-200] [Type Double]:
+                                Stmt 6 [93-194]: Expr: Expr 7 [93-194] [Type Double]: Expr Block: Block 34 [93-194] [Type Double]:
+                                    Stmt 14 [99-116]: Local (Immutable):
+                                        Pat 15 [99-116] [Type UDT<"Shape": Item 1>]: Bind: Ident 13 [99-116] "@match"
+                                        Expr 8 [99-116] [Type UDT<"Shape": Item 1>]: Tuple:
+                                            Expr 10 [99-111] [Type Int]: Lit: Int(0)
+                                            Expr 11 [99-111] [Type Double[]]: Array:
+                                                Expr 9 [112-115] [Type Double]: Lit: Double(1)
+                                            Expr 12 [99-111] [Type Unit[]]: Array:
+                                    Stmt 33 [131-183]: Expr: Expr 32 [131-183] [Type Double]: If:
+                                        Expr 31 [131-146] [Type Bool]: BinOp (Eq):
+                                            Expr 29 [131-146] [Type Int]: Field:
+                                                Expr 28 [131-146] [Type UDT<"Shape": Item 1>]: Var: Local 13
+                                                Path(FieldPath { indices: [0] })
+                                            Expr 30 [131-146] [Type Int]: Lit: Int(0)
+                                        Expr 25 [131-151] [Type Double]: Expr Block: Block 26 [131-151] [Type Double]:
+                                            Stmt 23 [131-146]: Local (Immutable):
+                                                Pat 18 [144-145] [Type Double]: Bind: Ident 17 [144-145] "r"
+                                                Expr 22 [131-146] [Type Double]: Index:
+                                                    Expr 20 [131-146] [Type Double[]]: Field:
+                                                        Expr 19 [131-146] [Type UDT<"Shape": Item 1>]: Var: Local 13
+                                                        Path(FieldPath { indices: [1] })
+                                                    Expr 21 [131-146] [Type Int]: Lit: Int(0)
+                                            Stmt 24 [150-151]: Expr: Expr 16 [150-151] [Type Double]: Var: Local 17
+                                        Expr 27 [180-183] [Type Double]: Lit: Double(0)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
+#[test]
+fn lower_enum_variant_as_function() {
+    check_hir(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo() : (Double -> Shape) { Shape.Circle }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-112] (Public):
+                    Namespace (Ident 21 [10-11] "A"): Item 1, Item 2
+                Item 1 [18-54] (Public):
+                    Parent: 0
+                    Type (Ident 0 [23-28] "Shape"): UDT [18-54]:
+                        TyDef [18-54]: Enum:
+                            Variant [31-37] (Circle): Double
+                            Variant [47-52] (Empty)
+                Item 2 [59-110] (Public):
+                    Parent: 0
+                    Callable 1 [59-110] (function):
+                        name: Ident 2 [68-71] "Foo"
+                        input: Pat 3 [71-73] [Type Unit]: Unit
+                        output: (Double -> UDT<"Shape": Item 1>)
+                        functors: empty set
+                        body: SpecDecl 4 [59-110]: Impl:
+                            Block 5 [94-110] [Type (Double -> UDT<"Shape": Item 1>)]:
+                                Stmt 6 [96-108]: Expr: Expr 7 [96-108] [Type (Double -> UDT<"Shape": Item 1>)]: Closure([], 3)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 3 [96-108] (Internal):
+                    Parent: 2
+                    Callable 16 [96-108] (function):
+                        name: Ident 17 [0-0] "lambda"
+                        input: Pat 15 [96-108] [Type (Double,)]: Tuple:
+                            Pat 9 [96-108] [Type Double]: Bind: Ident 8 [96-108] "hole"
+                        output: UDT<"Shape": Item 1>
+                        functors: empty set
+                        body: SpecDecl 18 [96-108]: Impl:
+                            Block 19 [96-108] [Type UDT<"Shape": Item 1>]:
+                                Stmt 20 [96-108]: Expr: Expr 11 [96-108] [Type UDT<"Shape": Item 1>]: Tuple:
+                                    Expr 12 [96-108] [Type Int]: Lit: Int(0)
+                                    Expr 13 [96-108] [Type Double[]]: Array:
+                                        Expr 10 [96-108] [Type Double]: Var: Local 8
+                                    Expr 14 [96-108] [Type Unit[]]: Array:
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
+#[test]
+fn break_and_continue_in_loops() {
+    check_errors(
+        indoc! {"
+            namespace A {
+                function Foo(xs : Int[]) : Unit {
+                    for x in xs {
+                        if x == 0 { continue; }
+                        mutable i = 0;
+                        while true {
+                            if i == x { break; }
+                            set i += 1;
+                        }
+                        if x < 0 { break; }
+                    }
+                }
+            }
+        "},
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn break_and_continue_outside_loop() {
+    check_errors(
+        indoc! {"
+            namespace A {
+                function Foo() : Unit {
+                    break;
+                    continue;
+                }
+            }
+        "},
+        &expect![[r#"
+            [
+                BreakOutsideLoop(
+                    Span {
+                        lo: 50,
+                        hi: 55,
+                    },
+                ),
+                ContinueOutsideLoop(
+                    Span {
+                        lo: 65,
+                        hi: 73,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn break_and_continue_cannot_leave_nested_scopes() {
+    check_errors(
+        indoc! {"
+            namespace A {
+                operation Foo(q : Qubit) : Unit {
+                    while true {
+                        let f = () -> { break; };
+                        function Bar() : Unit { continue; }
+                        repeat { break; } until true;
+                        within { continue; } apply { X(q); }
+                    }
+                }
+            }
+        "},
+        &expect![[r#"
+            [
+                BreakOutsideLoop(
+                    Span {
+                        lo: 101,
+                        hi: 106,
+                    },
+                ),
+                ContinueOutsideLoop(
+                    Span {
+                        lo: 147,
+                        hi: 155,
+                    },
+                ),
+                BreakOutsideLoop(
+                    Span {
+                        lo: 180,
+                        hi: 185,
+                    },
+                ),
+                ContinueOutsideLoop(
+                    Span {
+                        lo: 222,
+                        hi: 230,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn omitted_args_are_computed_by_default_items() {
+    check_hir(
+        indoc! {"
+            namespace A {
+                function Foo(x : Int, y : Int = x + 1) : Int { x + y }
+                function Bar() : Int { Foo(1) }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-110] (Public):
+                    Namespace (Ident 52 [10-11] "A"): Item 1, Item 2
+                Item 1 [18-72] (Public):
+                    Parent: 0
+                    Callable 0 [18-72] (function):
+                        name: Ident 1 [27-30] "Foo"
+                        input: Pat 2 [30-56] [Type (Int, Int)]: Tuple:
+                            Pat 3 [31-38] [Type Int]: Bind: Ident 4 [31-32] "x"
+                            Pat 5 [40-47] [Type Int]: Bind: Ident 6 [40-41] "y"
+                        defaults:
+                            Item 3: Expr 7 [50-55] [Type Int]: BinOp (Add):
+                                Expr 8 [50-51] [Type Int]: Var: Local 4
+                                Expr 9 [54-55] [Type Int]: Lit: Int(1)
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 21 [18-72]: Impl:
+                            Block 22 [63-72] [Type Int]:
+                                Stmt 23 [65-70]: Expr: Expr 24 [65-70] [Type Int]: BinOp (Add):
+                                    Expr 25 [65-66] [Type Int]: Var: Local 4
+                                    Expr 26 [69-70] [Type Int]: Var: Local 6
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 2 [77-108] (Public):
+                    Parent: 0
+                    Callable 27 [77-108] (function):
+                        name: Ident 28 [86-89] "Bar"
+                        input: Pat 29 [89-91] [Type Unit]: Unit
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 30 [77-108]: Impl:
+                            Block 31 [98-108] [Type Int]:
+                                Stmt 32 [100-106]: Expr: Expr 33 [100-106] [Type Int]: Call:
+                                    Expr 34 [100-103] [Type ((Int, Int) -> Int)]: Var: Item 1
+                                    Expr 50 [103-106] [Type (Int, Int)]: Expr Block: Block 51 [103-106] [Type (Int, Int)]:
+                                        Stmt 37 [104-105]: Local (Immutable):
+                                            Pat 38 [104-105] [Type Int]: Bind: Ident 36 [104-105] "arg"
+                                            Expr 35 [104-105] [Type Int]: Lit: Int(1)
+                                        Stmt 44 [103-106]: Local (Immutable):
+                                            Pat 45 [103-106] [Type Int]: Bind: Ident 43 [103-106] "arg"
+                                            Expr 42 [103-106] [Type Int]: Call:
+                                                Expr 41 [103-106] [Type ((Int,) -> Int)]: Var: Item 3
+                                                Expr 40 [103-106] [Type (Int,)]: Tuple:
+                                                    Expr 39 [103-106] [Type Int]: Var: Local 36
+                                        Stmt 49 [103-106]: Expr: Expr 48 [103-106] [Type (Int, Int)]: Tuple:
+                                            Expr 46 [103-106] [Type Int]: Var: Local 36
+                                            Expr 47 [103-106] [Type Int]: Var: Local 43
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 3 [50-55] (Internal):
+                    Parent: 1
+                    Callable 16 [50-55] (function):
+                        name: Ident 17 [50-55] "default"
+                        input: Pat 10 [50-55] [Type (Int,)]: Tuple:
+                            Pat 11 [31-38] [Type Int]: Bind: Ident 12 [31-32] "x"
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 18 [50-55]: Impl:
+                            Block 19 [50-55] [Type Int]:
+                                Stmt 20 [50-55]: Expr: Expr 13 [50-55] [Type Int]: BinOp (Add):
+                                    Expr 14 [50-51] [Type Int]: Var: Local 12
+                                    Expr 15 [54-55] [Type Int]: Lit: Int(1)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
+#[test]
+fn controlled_call_omits_defaulted_args() {
+    check_hir(
+        indoc! {"
+            namespace A {
+                operation Foo(q : Qubit, theta : Double = 1.0) : Unit is Ctl {}
+                operation Bar(ctls : Qubit[], q : Qubit) : Unit { Controlled Foo(ctls, q); }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-164] (Public):
+                    Namespace (Ident 51 [10-11] "A"): Item 1, Item 2
+                Item 1 [18-81] (Public):
+                    Parent: 0
+                    Callable 0 [18-81] (operation):
+                        name: Ident 1 [28-31] "Foo"
+                        input: Pat 2 [31-64] [Type (Qubit, Double)]: Tuple:
+                            Pat 3 [32-41] [Type Qubit]: Bind: Ident 4 [32-33] "q"
+                            Pat 5 [43-57] [Type Double]: Bind: Ident 6 [43-48] "theta"
+                        defaults:
+                            Item 3: Expr 7 [60-63] [Type Double]: Lit: Double(1)
+                        output: Unit
+                        functors: Ctl
+                        body: SpecDecl 17 [18-81]: Impl:
+                            Block 18 [79-81]: <empty>
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 2 [86-162] (Public):
+                    Parent: 0
+                    Callable 19 [86-162] (operation):
+                        name: Ident 20 [96-99] "Bar"
+                        input: Pat 21 [99-126] [Type (Qubit[], Qubit)]: Tuple:
+                            Pat 22 [100-114] [Type Qubit[]]: Bind: Ident 23 [100-104] "ctls"
+                            Pat 24 [116-125] [Type Qubit]: Bind: Ident 25 [116-117] "q"
+                        output: Unit
+                        functors: empty set
+                        body: SpecDecl 26 [86-162]: Impl:
+                            Block 27 [134-162] [Type Unit]:
+                                Stmt 28 [136-160]: Semi: Expr 29 [136-159] [Type Unit]: Call:
+                                    Expr 30 [136-150] [Type ((Qubit[], (Qubit, Double)) => Unit is Ctl)]: UnOp (Functor Ctl):
+                                        Expr 31 [147-150] [Type ((Qubit, Double) => Unit is Ctl)]: Var: Item 1
+                                    Expr 50 [150-159] [Type (Qubit[], (Qubit, Double))]: Tuple:
+                                        Expr 32 [151-155] [Type Qubit[]]: Var: Local 23
+                                        Expr 48 [157-158] [Type (Qubit, Double)]: Expr Block: Block 49 [157-158] [Type (Qubit, Double)]:
+                                            Stmt 35 [157-158]: Local (Immutable):
+                                                Pat 36 [157-158] [Type Qubit]: Bind: Ident 34 [157-158] "arg"
+                                                Expr 33 [157-158] [Type Qubit]: Var: Local 25
+                                            Stmt 42 [157-158]: Local (Immutable):
+                                                Pat 43 [157-158] [Type Double]: Bind: Ident 41 [157-158] "arg"
+                                                Expr 40 [157-158] [Type Double]: Call:
+                                                    Expr 39 [157-158] [Type ((Qubit,) -> Double)]: Var: Item 3
+                                                    Expr 38 [157-158] [Type (Qubit,)]: Tuple:
+                                                        Expr 37 [157-158] [Type Qubit]: Var: Local 34
+                                            Stmt 47 [157-158]: Expr: Expr 46 [157-158] [Type (Qubit, Double)]: Tuple:
+                                                Expr 44 [157-158] [Type Qubit]: Var: Local 34
+                                                Expr 45 [157-158] [Type Double]: Var: Local 41
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 3 [60-63] (Internal):
+                    Parent: 1
+                    Callable 12 [60-63] (function):
+                        name: Ident 13 [60-63] "default"
+                        input: Pat 8 [60-63] [Type (Qubit,)]: Tuple:
+                            Pat 9 [32-41] [Type Qubit]: Bind: Ident 10 [32-33] "q"
+                        output: Double
+                        functors: empty set
+                        body: SpecDecl 14 [60-63]: Impl:
+                            Block 15 [60-63] [Type Double]:
+                                Stmt 16 [60-63]: Expr: Expr 11 [60-63] [Type Double]: Lit: Double(1)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
+#[test]
+#[allow(clippy::too_many_lines)]
+fn partial_app_omits_defaulted_args() {
+    check_hir(
+        indoc! {"
+            namespace A {
+                function Foo(x : Int, y : Int, z : Int = x * y) : Int { x + y + z }
+                function Bar() : (Int -> Int) { Foo(_, 2) }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-135] (Public):
+                    Namespace (Ident 83 [10-11] "A"): Item 1, Item 2
+                Item 1 [18-85] (Public):
+                    Parent: 0
+                    Callable 0 [18-85] (function):
+                        name: Ident 1 [27-30] "Foo"
+                        input: Pat 2 [30-65] [Type (Int, Int, Int)]: Tuple:
+                            Pat 3 [31-38] [Type Int]: Bind: Ident 4 [31-32] "x"
+                            Pat 5 [40-47] [Type Int]: Bind: Ident 6 [40-41] "y"
+                            Pat 7 [49-56] [Type Int]: Bind: Ident 8 [49-50] "z"
+                        defaults:
+                            Item 3: Expr 9 [59-64] [Type Int]: BinOp (Mul):
+                                Expr 10 [59-60] [Type Int]: Var: Local 4
+                                Expr 11 [63-64] [Type Int]: Var: Local 6
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 25 [18-85]: Impl:
+                            Block 26 [72-85] [Type Int]:
+                                Stmt 27 [74-83]: Expr: Expr 28 [74-83] [Type Int]: BinOp (Add):
+                                    Expr 29 [74-79] [Type Int]: BinOp (Add):
+                                        Expr 30 [74-75] [Type Int]: Var: Local 4
+                                        Expr 31 [78-79] [Type Int]: Var: Local 6
+                                    Expr 32 [82-83] [Type Int]: Var: Local 8
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 2 [90-133] (Public):
+                    Parent: 0
+                    Callable 33 [90-133] (function):
+                        name: Ident 34 [99-102] "Bar"
+                        input: Pat 35 [102-104] [Type Unit]: Unit
+                        output: (Int -> Int)
+                        functors: empty set
+                        body: SpecDecl 36 [90-133]: Impl:
+                            Block 37 [120-133] [Type (Int -> Int)]:
+                                Stmt 38 [122-131]: Expr: Expr 39 [122-131] [Type (Int -> Int)]: Expr Block: Block 80 [122-131] [Type (Int -> Int)]:
+                                    Stmt 48 [129-130]: Local (Immutable):
+                                        Pat 47 [129-130] [Type Int]: Bind: Ident 45 [129-130] "arg"
+                                        Expr 44 [129-130] [Type Int]: Lit: Int(2)
+                                    Stmt 81 [122-131]: Expr: Expr 82 [122-131] [Type (Int -> Int)]: Closure([45], 4)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 3 [59-64] (Internal):
+                    Parent: 1
+                    Callable 20 [59-64] (function):
+                        name: Ident 21 [59-64] "default"
+                        input: Pat 12 [59-64] [Type (Int, Int)]: Tuple:
+                            Pat 13 [31-38] [Type Int]: Bind: Ident 14 [31-32] "x"
+                            Pat 15 [40-47] [Type Int]: Bind: Ident 16 [40-41] "y"
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 22 [59-64]: Impl:
+                            Block 23 [59-64] [Type Int]:
+                                Stmt 24 [59-64]: Expr: Expr 17 [59-64] [Type Int]: BinOp (Mul):
+                                    Expr 18 [59-60] [Type Int]: Var: Local 14
+                                    Expr 19 [63-64] [Type Int]: Var: Local 16
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 4 [122-131] (Internal):
+                    Parent: 2
+                    Callable 75 [122-131] (function):
+                        name: Ident 76 [0-0] "lambda"
+                        input: Pat 73 [122-131] [Type (Int, Int)]: Tuple:
+                            Pat 74 [129-130] [Type Int]: Bind: Ident 72 [129-130] "arg"
+                            Pat 42 [126-127] [Type Int]: Bind: Ident 41 [126-127] "hole"
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 77 [122-131]: Impl:
+                            Block 78 [122-131] [Type Int]:
+                                Stmt 79 [122-131]: Expr: Expr 71 [122-131] [Type Int]: Call:
+                                    Expr 40 [122-125] [Type ((Int, Int, Int) -> Int)]: Var: Item 1
+                                    Expr 69 [125-131] [Type (Int, Int, Int)]: Expr Block: Block 70 [125-131] [Type (Int, Int, Int)]:
+                                        Stmt 51 [126-127]: Local (Immutable):
+                                            Pat 52 [126-127] [Type Int]: Bind: Ident 50 [126-127] "arg"
+                                            Expr 43 [126-127] [Type Int]: Var: Local 41
+                                        Stmt 54 [129-130]: Local (Immutable):
+                                            Pat 55 [129-130] [Type Int]: Bind: Ident 53 [129-130] "arg"
+                                            Expr 46 [129-130] [Type Int]: Var: Local 72
+                                        Stmt 62 [125-131]: Local (Immutable):
+                                            Pat 63 [125-131] [Type Int]: Bind: Ident 61 [125-131] "arg"
+                                            Expr 60 [125-131] [Type Int]: Call:
+                                                Expr 59 [125-131] [Type ((Int, Int) -> Int)]: Var: Item 3
+                                                Expr 58 [125-131] [Type (Int, Int)]: Tuple:
+                                                    Expr 56 [125-131] [Type Int]: Var: Local 50
+                                                    Expr 57 [125-131] [Type Int]: Var: Local 53
+                                        Stmt 68 [125-131]: Expr: Expr 67 [125-131] [Type (Int, Int, Int)]: Tuple:
+                                            Expr 64 [125-131] [Type Int]: Var: Local 50
+                                            Expr 65 [125-131] [Type Int]: Var: Local 53
+                                            Expr 66 [125-131] [Type Int]: Var: Local 61
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
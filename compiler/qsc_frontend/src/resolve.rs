@@ -47,6 +47,8 @@ pub enum Res {
     PrimTy(Prim),
     /// The unit type.
     UnitTy,
+    /// A variant of the enum with the given item ID.
+    Variant(ItemId),
 }
 
 #[derive(Clone, Debug, Diagnostic, Error)]
@@ -92,6 +94,15 @@ pub(super) enum Error {
     #[diagnostic(code("Qsc.Resolve.DuplicateIntrinsic"))]
     DuplicateIntrinsic(String, #[label] Span),
 
+    #[error("`{0}` cannot be exported because it is declared in another package")]
+    #[diagnostic(help("only items declared in the current package can be exported"))]
+    #[diagnostic(code("Qsc.Resolve.ExportedItemNotLocal"))]
+    ExportedItemNotLocal(String, #[label] Span),
+
+    #[error("export declarations are only allowed in namespaces")]
+    #[diagnostic(code("Qsc.Resolve.ExportOutsideNamespace"))]
+    ExportOutsideNamespace(#[label] Span),
+
     #[error("`{0}` not found")]
     #[diagnostic(code("Qsc.Resolve.NotFound"))]
     NotFound(String, #[label] Span),
@@ -347,6 +358,7 @@ impl Resolver {
     }
 
     pub(super) fn bind_fragments(&mut self, ast: &ast::Package, assigner: &mut Assigner) {
+        let mut namespaces = Vec::new();
         for node in &mut ast.nodes.iter() {
             match node {
                 ast::TopLevelNode::Namespace(namespace) => {
@@ -357,6 +369,7 @@ impl Resolver {
                         assigner,
                         &mut self.errors,
                     );
+                    namespaces.push(namespace);
                 }
                 ast::TopLevelNode::Stmt(stmt) => {
                     if let ast::StmtKind::Item(item) = stmt.kind.as_ref() {
@@ -365,6 +378,13 @@ impl Resolver {
                 }
             }
         }
+        bind_exports(
+            &mut self.names,
+            &mut self.globals,
+            &namespaces,
+            assigner,
+            &mut self.errors,
+        );
     }
 
     fn check_item_status(&mut self, res: Res, name: String, span: Span) {
@@ -406,6 +426,8 @@ impl Resolver {
                 self.check_item_status(res, path.name.name.to_string(), path.span);
                 self.names.insert(path.id, res);
             }
+            Err(Error::NotFound(..))
+                if matches!(kind, NameKind::Term) && self.resolve_variant(path) => {}
             Err(err) => {
                 if let Error::NotFound(name, span) = err {
                     if let Some(dropped_name) =
@@ -426,6 +448,44 @@ impl Resolver {
         }
     }
 
+    /// Resolves a path of the form `A.B`, where `A` is a type, as a reference to the variant `B`
+    /// of the enum `A`. Whether `A` is an enum with a variant `B` is checked during type checking.
+    fn resolve_variant(&mut self, path: &ast::Path) -> bool {
+        let Some(qualifier) = &path.namespace else {
+            return false;
+        };
+        let (namespace, ty_name) = match qualifier.name.rsplit_once('.') {
+            Some((namespace, ty_name)) => (
+                Some(Box::new(Ident {
+                    id: NodeId::default(),
+                    span: qualifier.span,
+                    name: namespace.into(),
+                })),
+                ty_name,
+            ),
+            None => (None, qualifier.name.as_ref()),
+        };
+        let ty_name = Ident {
+            id: qualifier.id,
+            span: qualifier.span,
+            name: ty_name.into(),
+        };
+
+        match resolve(
+            NameKind::Ty,
+            &self.globals,
+            self.locals.get_scopes(&self.curr_scope_chain),
+            &ty_name,
+            &namespace,
+        ) {
+            Ok(Res::Item(item, _)) => {
+                self.names.insert(path.id, Res::Variant(item));
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// # Arguments
     ///
     /// * `pat` - The pattern to bind.
@@ -460,6 +520,15 @@ impl Resolver {
             ast::PatKind::Tuple(pats) => pats
                 .iter()
                 .for_each(|p| self.bind_pat_recursive(p, valid_at, bindings)),
+            ast::PatKind::Array(pats, rest) => pats
+                .iter()
+                .chain(rest)
+                .for_each(|p| self.bind_pat_recursive(p, valid_at, bindings)),
+            ast::PatKind::Variant(_, payload) => {
+                if let Some(payload) = payload {
+                    self.bind_pat_recursive(payload, valid_at, bindings);
+                }
+            }
         }
     }
 
@@ -496,29 +565,58 @@ impl Resolver {
                     .terms
                     .insert(Rc::clone(&decl.name.name), id);
             }
-            ast::ItemKind::Ty(name, _) => {
+            ast::ItemKind::Ty(name, _) => self.bind_local_ty(assigner, item, name, true),
+            ast::ItemKind::Struct(decl) => self.bind_local_ty(assigner, item, &decl.name, true),
+            // Enums are constructed through their variants, so their name is not bound as a term.
+            ast::ItemKind::Enum(decl) => self.bind_local_ty(assigner, item, &decl.name, false),
+            ast::ItemKind::Export(decl) => {
+                self.errors.push(Error::ExportOutsideNamespace(decl.span));
+            }
+            ast::ItemKind::Const(decl) => {
                 let id = intrapackage(assigner.next_item());
                 self.names.insert(
-                    name.id,
+                    decl.name.id,
                     Res::Item(
                         id,
                         ItemStatus::from_attrs(&ast_attrs_as_hir_attrs(&item.attrs)),
                     ),
                 );
-                let scope = self.current_scope_mut();
-                scope.tys.insert(Rc::clone(&name.name), id);
-                scope.terms.insert(Rc::clone(&name.name), id);
+                self.current_scope_mut()
+                    .terms
+                    .insert(Rc::clone(&decl.name.name), id);
             }
             ast::ItemKind::Err => {}
         }
     }
 
+    fn bind_local_ty(
+        &mut self,
+        assigner: &mut Assigner,
+        item: &ast::Item,
+        name: &Ident,
+        is_term: bool,
+    ) {
+        let id = intrapackage(assigner.next_item());
+        self.names.insert(
+            name.id,
+            Res::Item(
+                id,
+                ItemStatus::from_attrs(&ast_attrs_as_hir_attrs(&item.attrs)),
+            ),
+        );
+        let scope = self.current_scope_mut();
+        scope.tys.insert(Rc::clone(&name.name), id);
+        if is_term {
+            scope.terms.insert(Rc::clone(&name.name), id);
+        }
+    }
+
     fn bind_type_parameters(&mut self, decl: &CallableDecl) {
-        decl.generics.iter().enumerate().for_each(|(ix, ident)| {
+        decl.generics.iter().enumerate().for_each(|(ix, param)| {
             self.current_scope_mut()
                 .ty_vars
-                .insert(Rc::clone(&ident.name), ix.into());
-            self.names.insert(ident.id, Res::Param(ix.into()));
+                .insert(Rc::clone(&param.name.name), ix.into());
+            self.names.insert(param.name.id, Res::Param(ix.into()));
         });
     }
 
@@ -589,6 +687,41 @@ impl With<'_> {
             f(visitor);
         });
     }
+
+    /// Visits the index operand of an update operator. The field names in a nested update path
+    /// like `Noise::Rate` or `[i]::Rate` are not resolved, but the indices are.
+    fn visit_update_index(&mut self, index: &ast::Expr) {
+        match &*index.kind {
+            ast::ExprKind::Field(inner, _) if self.is_update_path(inner) => {
+                self.visit_update_index(inner);
+            }
+            ast::ExprKind::Index(inner, item) if self.is_update_path(inner) => {
+                self.visit_update_index(inner);
+                self.visit_expr(item);
+            }
+            ast::ExprKind::Path(_) if self.is_update_path(index) => {}
+            _ => self.visit_expr(index),
+        }
+    }
+
+    /// Whether the expression is an update path, following the same reasoning as
+    /// `extract_update_path`.
+    fn is_update_path(&self, expr: &ast::Expr) -> bool {
+        match &*expr.kind {
+            ast::ExprKind::Path(_) => is_field_update(
+                &self.resolver.globals,
+                self.resolver
+                    .locals
+                    .get_scopes(&self.resolver.curr_scope_chain),
+                expr,
+            ),
+            ast::ExprKind::Array(items) => items.len() == 1,
+            ast::ExprKind::Field(inner, _) | ast::ExprKind::Index(inner, _) => {
+                self.is_update_path(inner)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl AstVisitor<'_> for With<'_> {
@@ -623,6 +756,16 @@ impl AstVisitor<'_> for With<'_> {
                 ast::PatKind::Tuple(pats) => {
                     pats.iter().for_each(|p| collect_param_names(p, names));
                 }
+                ast::PatKind::Array(pats, rest) => {
+                    pats.iter()
+                        .chain(rest)
+                        .for_each(|p| collect_param_names(p, names));
+                }
+                ast::PatKind::Variant(_, payload) => {
+                    if let Some(payload) = payload {
+                        collect_param_names(payload, names);
+                    }
+                }
             }
         }
         let mut param_names = FxHashSet::default();
@@ -630,14 +773,61 @@ impl AstVisitor<'_> for With<'_> {
         let prev_param_names = self.resolver.curr_params.replace(param_names);
         self.with_scope(decl.span, ScopeKind::Callable, |visitor| {
             visitor.resolver.bind_type_parameters(decl);
-            // The parameter bindings are valid after the end of the input pattern.
-            // (More accurately, in the callable body, but we don't have a start offset for that).
-            visitor.resolver.bind_pat(&decl.input, decl.input.span.hi);
-            ast_visit::walk_callable_decl(visitor, decl);
+            if decl.defaults.is_empty() {
+                // The parameter bindings are valid after the end of the input pattern.
+                // (More accurately, in the callable body, but we don't have a start offset for that).
+                visitor.resolver.bind_pat(&decl.input, decl.input.span.hi);
+                ast_visit::walk_callable_decl(visitor, decl);
+                return;
+            }
+
+            // Default values can refer to the parameters before them, so each parameter is bound
+            // after the default value for it has been resolved, and is valid from the start of the
+            // next parameter.
+            visitor.visit_ident(&decl.name);
+            decl.generics.iter().for_each(|p| {
+                visitor.visit_ident(&p.name);
+                p.bounds.iter().for_each(|b| visitor.visit_ident(b));
+            });
+            visitor.visit_pat(&decl.input);
+            let params: Vec<&ast::Pat> = match &*decl.input.kind {
+                ast::PatKind::Tuple(params) => params.iter().map(AsRef::as_ref).collect(),
+                _ => vec![&decl.input],
+            };
+            let first_default = params.len() - decl.defaults.len();
+            let mut bindings = FxHashSet::default();
+            for (i, param) in params.iter().enumerate() {
+                if let Some(default) = i.checked_sub(first_default).map(|i| &decl.defaults[i]) {
+                    visitor.visit_expr(default);
+                }
+                let valid_at = params
+                    .get(i + 1)
+                    .map_or(decl.input.span.hi, |next| next.span.lo);
+                visitor
+                    .resolver
+                    .bind_pat_recursive(param, valid_at, &mut bindings);
+            }
+            visitor.visit_ty(&decl.output);
+            decl.functors
+                .iter()
+                .for_each(|f| visitor.visit_functor_expr(f));
+            match &*decl.body {
+                ast::CallableBody::Block(block) => visitor.visit_block(block),
+                ast::CallableBody::Specs(specs) => {
+                    specs.iter().for_each(|s| visitor.visit_spec_decl(s));
+                }
+            }
         });
         self.resolver.curr_params = prev_param_names;
     }
 
+    fn visit_const_decl(&mut self, decl: &ast::ConstDecl) {
+        // Constant values cannot refer to the locals of an enclosing callable.
+        self.with_scope(decl.span, ScopeKind::Callable, |visitor| {
+            ast_visit::walk_const_decl(visitor, decl);
+        });
+    }
+
     fn visit_spec_decl(&mut self, decl: &ast::SpecDecl) {
         if let ast::SpecBody::Impl(input, block) = &decl.body {
             self.with_spec_pat(block.span, ScopeKind::Block, input, |visitor| {
@@ -713,19 +903,29 @@ impl AstVisitor<'_> for With<'_> {
                     visitor.visit_expr(output);
                 });
             }
+            ast::ExprKind::Match(scrutinee, arms) => {
+                self.visit_expr(scrutinee);
+                for arm in arms {
+                    if let ast::PatKind::Variant(path, _) = &*arm.pat.kind {
+                        self.resolver.resolve_path(NameKind::Term, path);
+                    }
+                    self.with_pat(arm.span, ScopeKind::Block, &arm.pat, |visitor| {
+                        visitor.visit_expr(&arm.body);
+                    });
+                }
+            }
             ast::ExprKind::Path(path) => self.resolver.resolve_path(NameKind::Term, path),
+            ast::ExprKind::Struct(path, copy, fields) => {
+                self.resolver.resolve_path(NameKind::Ty, path);
+                if let Some(copy) = copy {
+                    self.visit_expr(copy);
+                }
+                fields.iter().for_each(|f| self.visit_expr(&f.value));
+            }
             ast::ExprKind::TernOp(ast::TernOp::Update, container, index, replace)
             | ast::ExprKind::AssignUpdate(container, index, replace) => {
                 self.visit_expr(container);
-                if !is_field_update(
-                    &self.resolver.globals,
-                    self.resolver
-                        .locals
-                        .get_scopes(&self.resolver.curr_scope_chain),
-                    index,
-                ) {
-                    self.visit_expr(index);
-                }
+                self.visit_update_index(index);
                 self.visit_expr(replace);
             }
             _ => ast_visit::walk_expr(self, expr),
@@ -776,6 +976,7 @@ impl GlobalTable {
         package: &ast::Package,
     ) -> Vec<Error> {
         let mut errors = Vec::new();
+        let mut namespaces = Vec::new();
         for node in &*package.nodes {
             match node {
                 TopLevelNode::Namespace(namespace) => {
@@ -786,12 +987,20 @@ impl GlobalTable {
                         assigner,
                         &mut errors,
                     );
+                    namespaces.push(namespace);
                 }
                 TopLevelNode::Stmt(_) => {
                     unimplemented!("did not expect top-level statements in the ast")
                 }
             }
         }
+        bind_exports(
+            &mut self.names,
+            &mut self.scope,
+            &namespaces,
+            assigner,
+            &mut errors,
+        );
         errors
     }
 
@@ -820,6 +1029,13 @@ impl GlobalTable {
                         self.scope.intrinsics.insert(global.name);
                     }
                 }
+                (global::Kind::Const(term), hir::Visibility::Public) => {
+                    self.scope
+                        .terms
+                        .entry(global.namespace)
+                        .or_default()
+                        .insert(global.name, Res::Item(term.id, global.status));
+                }
                 (global::Kind::Namespace, hir::Visibility::Public) => {
                     self.scope.namespaces.insert(global.name);
                 }
@@ -856,6 +1072,152 @@ fn bind_global_items(
     }
 }
 
+/// Binds the items listed in the `export` declarations of the given namespaces, making each one
+/// visible in the exporting namespace under its alias or original name. Unqualified names are
+/// resolved in the exporting namespace and the namespaces it opens. Since an export can name an
+/// item exported by another namespace, exports are resolved repeatedly until no more progress
+/// can be made, and only then are the remaining failures reported.
+fn bind_exports(
+    names: &mut Names,
+    scope: &mut GlobalScope,
+    namespaces: &[&ast::Namespace],
+    assigner: &mut Assigner,
+    errors: &mut Vec<Error>,
+) {
+    let mut namespace_scopes = Vec::new();
+    let mut pending = Vec::new();
+    for namespace in namespaces {
+        let mut namespace_scope = Scope::new(
+            ScopeKind::Namespace(Rc::clone(&namespace.name.name)),
+            namespace.span,
+        );
+        for item in &*namespace.items {
+            match &*item.kind {
+                ast::ItemKind::Open(name, alias) if scope.namespaces.contains(&name.name) => {
+                    let alias = alias.as_ref().map_or("".into(), |a| Rc::clone(&a.name));
+                    namespace_scope.opens.entry(alias).or_default().push(Open {
+                        namespace: Rc::clone(&name.name),
+                        span: name.span,
+                    });
+                }
+                ast::ItemKind::Export(decl) => {
+                    for export in &*decl.items {
+                        pending.push((namespace_scopes.len(), &namespace.name.name, export));
+                    }
+                }
+                _ => {}
+            }
+        }
+        namespace_scopes.push(namespace_scope);
+    }
+
+    while !pending.is_empty() {
+        let count = pending.len();
+        let mut unresolved = Vec::new();
+        let mut failures = Vec::new();
+        for (scope_index, namespace, export) in pending {
+            let namespace_scope = &namespace_scopes[scope_index];
+            let term = resolve_export_path(NameKind::Term, scope, namespace_scope, &export.path);
+            let ty = resolve_export_path(NameKind::Ty, scope, namespace_scope, &export.path);
+            let res = match (&term, &ty) {
+                (Ok(res), _) | (Err(_), Ok(res)) => *res,
+                (Err(Error::NotFound(..)), Err(Error::NotFound(..))) => {
+                    unresolved.push((scope_index, namespace, export));
+                    failures.push(term.expect_err("term should not be resolved"));
+                    continue;
+                }
+                (Err(_), Err(_)) => {
+                    errors.push(term.expect_err("term should not be resolved"));
+                    continue;
+                }
+            };
+            bind_export(
+                names,
+                scope,
+                namespace,
+                export,
+                (
+                    res,
+                    term.is_ok_and(|t| t == res),
+                    ty.is_ok_and(|t| t == res),
+                ),
+                assigner,
+                errors,
+            );
+        }
+
+        if unresolved.len() == count {
+            errors.extend(failures);
+            break;
+        }
+        pending = unresolved;
+    }
+}
+
+fn resolve_export_path(
+    kind: NameKind,
+    globals: &GlobalScope,
+    namespace_scope: &Scope,
+    path: &ast::Path,
+) -> Result<Res, Error> {
+    match resolve(
+        kind,
+        globals,
+        std::iter::once(namespace_scope),
+        &path.name,
+        &path.namespace,
+    )? {
+        res @ Res::Item(..) => Ok(res),
+        _ => Err(Error::NotFound(path.name.name.to_string(), path.span)),
+    }
+}
+
+/// Binds a resolved export. The flags record whether the exported item is visible as a term,
+/// as a type, or both.
+fn bind_export(
+    names: &mut Names,
+    scope: &mut GlobalScope,
+    namespace: &Rc<str>,
+    export: &ast::ExportItem,
+    (res, is_term, is_ty): (Res, bool, bool),
+    assigner: &mut Assigner,
+    errors: &mut Vec<Error>,
+) {
+    names.insert(export.path.id, res);
+    let Res::Item(item_id, status) = res else {
+        unreachable!("exports should only resolve to items");
+    };
+    if item_id.package.is_some() {
+        errors.push(Error::ExportedItemNotLocal(
+            export.path.name.name.to_string(),
+            export.path.span,
+        ));
+        return;
+    }
+
+    let name = export.name();
+    let terms = scope.terms.entry(Rc::clone(namespace)).or_default();
+    let tys = scope.tys.entry(Rc::clone(namespace)).or_default();
+    if terms.contains_key(&name.name) || tys.contains_key(&name.name) {
+        errors.push(Error::Duplicate(
+            name.name.to_string(),
+            namespace.to_string(),
+            name.span,
+        ));
+        return;
+    }
+    names.insert(
+        export.id,
+        Res::Item(intrapackage(assigner.next_item()), status),
+    );
+    if is_term {
+        terms.insert(Rc::clone(&name.name), res);
+    }
+    if is_ty {
+        tys.insert(Rc::clone(&name.name), res);
+    }
+}
+
 /// Tries to extract a field name from an expression in cases where it is syntactically ambiguous
 /// whether the expression is a field name or a variable name. This applies to the index operand in
 /// a ternary update operator.
@@ -871,6 +1233,43 @@ pub(super) fn extract_field_name<'a>(names: &Names, expr: &'a ast::Expr) -> Opti
     }
 }
 
+/// A step in the path of an update operator.
+pub(super) enum UpdateStep<'a> {
+    /// A field name: `Rate`.
+    Field(&'a Rc<str>),
+    /// An array index: `[i]`.
+    Index(&'a ast::Expr),
+}
+
+/// Tries to extract the path of a nested update, such as `Noise::Rate`, `[i][j]` or `[i]::Rate`,
+/// from the index operand of an update operator. The path starts with a field name or a bracketed
+/// index, and is followed by any number of field accesses and indices. Each step is paired with
+/// the expression that ends with it.
+pub(super) fn extract_update_path<'a>(
+    names: &Names,
+    expr: &'a ast::Expr,
+) -> Option<Vec<(&'a ast::Expr, UpdateStep<'a>)>> {
+    match &*expr.kind {
+        ast::ExprKind::Path(_) => {
+            extract_field_name(names, expr).map(|name| vec![(expr, UpdateStep::Field(name))])
+        }
+        ast::ExprKind::Array(items) if items.len() == 1 => {
+            Some(vec![(expr, UpdateStep::Index(&items[0]))])
+        }
+        ast::ExprKind::Field(inner, name) => {
+            let mut path = extract_update_path(names, inner)?;
+            path.push((expr, UpdateStep::Field(&name.name)));
+            Some(path)
+        }
+        ast::ExprKind::Index(inner, index) => {
+            let mut path = extract_update_path(names, inner)?;
+            path.push((expr, UpdateStep::Index(index)));
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
 fn is_field_update<'a>(
     globals: &GlobalScope,
     scopes: impl Iterator<Item = &'a Scope>,
@@ -942,35 +1341,79 @@ fn bind_global_item(
             }
         }
         ast::ItemKind::Ty(name, _) => {
+            bind_global_ty(names, scope, namespace, next_id, item, name, true)
+        }
+        ast::ItemKind::Struct(decl) => {
+            bind_global_ty(names, scope, namespace, next_id, item, &decl.name, true)
+        }
+        // Enums are constructed through their variants, so their name is not bound as a term.
+        ast::ItemKind::Enum(decl) => {
+            bind_global_ty(names, scope, namespace, next_id, item, &decl.name, false)
+        }
+        ast::ItemKind::Const(decl) => {
             let item_id = next_id();
             let status = ItemStatus::from_attrs(&ast_attrs_as_hir_attrs(item.attrs.as_ref()));
             let res = Res::Item(item_id, status);
-            names.insert(name.id, res);
-            match (
-                scope
-                    .terms
-                    .entry(Rc::clone(namespace))
-                    .or_default()
-                    .entry(Rc::clone(&name.name)),
-                scope
-                    .tys
-                    .entry(Rc::clone(namespace))
-                    .or_default()
-                    .entry(Rc::clone(&name.name)),
-            ) {
-                (Entry::Occupied(_), _) | (_, Entry::Occupied(_)) => Err(vec![Error::Duplicate(
-                    name.name.to_string(),
+            names.insert(decl.name.id, res);
+            match scope
+                .terms
+                .entry(Rc::clone(namespace))
+                .or_default()
+                .entry(Rc::clone(&decl.name.name))
+            {
+                Entry::Occupied(_) => Err(vec![Error::Duplicate(
+                    decl.name.name.to_string(),
                     namespace.to_string(),
-                    name.span,
+                    decl.name.span,
                 )]),
-                (Entry::Vacant(term_entry), Entry::Vacant(ty_entry)) => {
-                    term_entry.insert(res);
-                    ty_entry.insert(res);
+                Entry::Vacant(entry) => {
+                    entry.insert(res);
                     Ok(())
                 }
             }
         }
-        ast::ItemKind::Err | ast::ItemKind::Open(..) => Ok(()),
+        // Exports are bound once every namespace has been bound, see `bind_exports`.
+        ast::ItemKind::Err | ast::ItemKind::Open(..) | ast::ItemKind::Export(..) => Ok(()),
+    }
+}
+
+fn bind_global_ty(
+    names: &mut Names,
+    scope: &mut GlobalScope,
+    namespace: &Rc<str>,
+    next_id: impl FnOnce() -> ItemId,
+    item: &ast::Item,
+    name: &Ident,
+    is_term: bool,
+) -> Result<(), Vec<Error>> {
+    let item_id = next_id();
+    let status = ItemStatus::from_attrs(&ast_attrs_as_hir_attrs(item.attrs.as_ref()));
+    let res = Res::Item(item_id, status);
+    names.insert(name.id, res);
+    match (
+        scope
+            .terms
+            .entry(Rc::clone(namespace))
+            .or_default()
+            .entry(Rc::clone(&name.name)),
+        scope
+            .tys
+            .entry(Rc::clone(namespace))
+            .or_default()
+            .entry(Rc::clone(&name.name)),
+    ) {
+        (Entry::Occupied(_), _) | (_, Entry::Occupied(_)) => Err(vec![Error::Duplicate(
+            name.name.to_string(),
+            namespace.to_string(),
+            name.span,
+        )]),
+        (Entry::Vacant(term_entry), Entry::Vacant(ty_entry)) => {
+            if is_term {
+                term_entry.insert(res);
+            }
+            ty_entry.insert(res);
+            Ok(())
+        }
     }
 }
 
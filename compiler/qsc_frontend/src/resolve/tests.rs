@@ -36,7 +36,11 @@ impl<'a> Renamer<'a> {
     }
 
     fn rename(&self, input: &mut String) {
-        for (span, res) in self.changes.iter().rev() {
+        // Default values are visited after the whole input pattern, so the changes are sorted to
+        // replace the later spans first.
+        let mut changes: Vec<_> = self.changes.iter().collect();
+        changes.sort_by_key(|(span, _)| span.lo);
+        for (span, res) in changes.into_iter().rev() {
             let name = match res {
                 Res::Item(item, _) => match item.package {
                     None => format!("item{}", item.item),
@@ -46,6 +50,10 @@ impl<'a> Renamer<'a> {
                 Res::PrimTy(prim) => format!("{prim:?}"),
                 Res::UnitTy => "Unit".to_string(),
                 Res::Param(id) => format!("param{id}"),
+                Res::Variant(item) => match item.package {
+                    None => format!("variant_of_item{}", item.item),
+                    Some(package) => format!("variant_of_package{package}_item{}", item.item),
+                },
             };
             input.replace_range((span.lo as usize)..(span.hi as usize), &name);
         }
@@ -935,6 +943,170 @@ fn ty_decl_cons() {
     );
 }
 
+#[test]
+fn struct_decl() {
+    check(
+        indoc! {"
+            namespace Foo {
+                struct A { X : Int }
+                struct B { First : A, Second : Bool }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                struct item1 { X : Int }
+                struct item2 { First : item1, Second : Bool }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn struct_decl_duplicate_error() {
+    check(
+        indoc! {"
+            namespace Foo {
+                newtype A = Unit;
+                struct A { X : Int }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                newtype item1 = Unit;
+                struct item2 { X : Int }
+            }
+
+            // Duplicate("A", "Foo", Span { lo: 49, hi: 50 })
+        "#]],
+    );
+}
+
+#[test]
+fn struct_cons() {
+    check(
+        indoc! {"
+            namespace Foo {
+                struct A { X : Int, Y : Int }
+
+                function B(a : A) : A {
+                    let x = 1;
+                    new A { ...a, X = x }
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                struct item1 { X : Int, Y : Int }
+
+                function item2(local21 : item1) : item1 {
+                    let local31 = 1;
+                    new item1 { ...local21, X = local31 }
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn enum_decl() {
+    check(
+        indoc! {"
+            namespace Foo {
+                struct A { X : Int }
+                enum B { First(A), Second }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                struct item1 { X : Int }
+                enum item2 { First(item1), Second }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn enum_variant_match() {
+    check(
+        indoc! {"
+            namespace Foo {
+                enum A { X(Int), Y }
+
+                function B(a : A) : Int {
+                    match a {
+                        A.X(x) => x,
+                        A.Y => 0,
+                    }
+                }
+
+                function C() : A { A.X(1) }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                enum item1 { X(Int), Y }
+
+                function item2(local18 : item1) : Int {
+                    match local18 {
+                        variant_of_item1(local38) => local38,
+                        variant_of_item1 => 0,
+                    }
+                }
+
+                function item3() : item1 { variant_of_item1(1) }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn enum_name_is_not_term() {
+    check(
+        indoc! {"
+            namespace Foo {
+                enum A { X, Y }
+
+                function B() : Unit {
+                    let a = A;
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                enum item1 { X, Y }
+
+                function item2() : Unit {
+                    let local20 = A;
+                }
+            }
+
+            // NotFound("A", Span { lo: 79, hi: 80 })
+        "#]],
+    );
+}
+
+#[test]
+fn enum_unknown_variant_enum() {
+    check(
+        indoc! {"
+            namespace Foo {
+                function B() : Unit {
+                    let a = C.X;
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                function item1() : Unit {
+                    let local13 = C.X;
+                }
+            }
+
+            // NotFound("X", Span { lo: 60, hi: 61 })
+        "#]],
+    );
+}
+
 #[test]
 fn unknown_term() {
     check(
@@ -1670,6 +1842,38 @@ fn update_udt_known_field_name() {
     );
 }
 
+#[test]
+fn update_field_and_index_path() {
+    check(
+        indoc! {"
+            namespace A {
+                newtype Pair = (First : Int, Second : Int);
+                newtype Pairs = (Items : Pair[], Count : Int);
+
+                function Foo() : () {
+                    let i = 0;
+                    let ps = Pairs([Pair(1, 2)], 1);
+                    let qs = ps w/ Items[i]::First <- 3;
+                    let rs = [ps] w/ [i]::Count <- 2;
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                newtype item1 = (First : Int, Second : Int);
+                newtype item2 = (Items : item1[], Count : Int);
+
+                function item3() : () {
+                    let local38 = 0;
+                    let local42 = item2([item1(1, 2)], 1);
+                    let local59 = local42 w/ Items[local38]::First <- 3;
+                    let local76 = [local42] w/ [local38]::Count <- 2;
+                }
+            }
+        "#]],
+    );
+}
+
 #[test]
 fn update_udt_known_field_name_expr() {
     check(
@@ -1976,6 +2180,77 @@ fn dropped_callable() {
     );
 }
 
+#[test]
+fn dropped_const() {
+    check(
+        indoc! {"
+            namespace A {
+                @Config(Base)
+                const Dropped : Int = 1;
+
+                function B() : Int {
+                    Dropped
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                @Config(Base)
+                const Dropped : Int = 1;
+
+                function item1() : Int {
+                    Dropped
+                }
+            }
+
+            // NotAvailable("Dropped", "A.Dropped", Span { lo: 95, hi: 102 })
+        "#]],
+    );
+}
+
+#[test]
+fn default_value_uses_earlier_param() {
+    check(
+        indoc! {"
+            namespace A {
+                function B(x : Int, y : Int = x + 1, z : Int = y) : Int {
+                    x + y + z
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                function item1(local8 : Int, local13 : Int = local8 + 1, local18 : Int = local13) : Int {
+                    local8 + local13 + local18
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn default_value_cannot_use_own_or_later_param() {
+    check(
+        indoc! {"
+            namespace A {
+                function B(x : Int = x, y : Int = z, z : Int = 1) : Int {
+                    x + y + z
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                function item1(local8 : Int = x, local13 : Int = z, local18 : Int = 1) : Int {
+                    local8 + local13 + local18
+                }
+            }
+
+            // NotFound("x", Span { lo: 39, hi: 40 })
+            // NotFound("z", Span { lo: 52, hi: 53 })
+        "#]],
+    );
+}
+
 #[test]
 fn multiple_definition_dropped_is_not_found() {
     check(
@@ -2655,3 +2930,181 @@ fn newtype_with_tuple_destructuring() {
         "#]],
     );
 }
+
+#[test]
+fn export_callable_from_other_namespace() {
+    check(
+        indoc! {"
+            namespace Implementation {
+                function Helper() : Unit {}
+            }
+            namespace Facade {
+                export Implementation.Helper;
+            }
+            namespace Main {
+                function Main() : Unit {
+                    Facade.Helper();
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                function item1() : Unit {}
+            }
+            namespace item2 {
+                export item1;
+            }
+            namespace item3 {
+                function item4() : Unit {
+                    item1();
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn export_with_alias_from_opened_namespace() {
+    check(
+        indoc! {"
+            namespace Implementation {
+                function Helper() : Unit {}
+                struct Pair { First : Int, Second : Int }
+            }
+            namespace Facade {
+                open Implementation;
+                export Helper as Run, Pair as Tuple;
+            }
+            namespace Main {
+                open Facade;
+                function Main() : Tuple {
+                    Run();
+                    new Tuple { First = 1, Second = 2 }
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                function item1() : Unit {}
+                struct item2 { First : Int, Second : Int }
+            }
+            namespace item3 {
+                open Implementation;
+                export item1 as Run, item2 as Tuple;
+            }
+            namespace item4 {
+                open Facade;
+                function item5() : item2 {
+                    item1();
+                    new item2 { First = 1, Second = 2 }
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn export_of_export_declared_later() {
+    check(
+        indoc! {"
+            namespace A {
+                export B.Bar;
+            }
+            namespace B {
+                export C.Baz as Bar;
+            }
+            namespace C {
+                function Baz() : Unit {}
+            }
+            namespace Main {
+                function Main() : Unit {
+                    A.Bar();
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                export item3;
+            }
+            namespace item1 {
+                export item3 as Bar;
+            }
+            namespace item2 {
+                function item3() : Unit {}
+            }
+            namespace item4 {
+                function item5() : Unit {
+                    item3();
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn export_not_found() {
+    check(
+        indoc! {"
+            namespace Facade {
+                export Implementation.Missing;
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                export Implementation.Missing;
+            }
+
+            // NotFound("Missing", Span { lo: 45, hi: 52 })
+        "#]],
+    );
+}
+
+#[test]
+fn export_conflicts_with_declaration() {
+    check(
+        indoc! {"
+            namespace Implementation {
+                function Helper() : Unit {}
+            }
+            namespace Facade {
+                function Helper() : Unit {}
+                export Implementation.Helper;
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                function item1() : Unit {}
+            }
+            namespace item2 {
+                function item3() : Unit {}
+                export item1;
+            }
+
+            // Duplicate("Helper", "Facade", Span { lo: 138, hi: 144 })
+        "#]],
+    );
+}
+
+#[test]
+fn export_outside_namespace() {
+    check(
+        indoc! {"
+            namespace Implementation {
+                function Helper() : Unit {}
+                function Main() : Unit {
+                    export Helper;
+                }
+            }
+        "},
+        &expect![[r#"
+            namespace item0 {
+                function item1() : Unit {}
+                function item2() : Unit {
+                    export Helper;
+                }
+            }
+
+            // ExportOutsideNamespace(Span { lo: 96, hi: 110 })
+        "#]],
+    );
+}
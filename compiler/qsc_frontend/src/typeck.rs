@@ -8,8 +8,9 @@ mod rules;
 #[cfg(test)]
 mod tests;
 
+use crate::resolve::{Names, Res};
 use miette::Diagnostic;
-use qsc_ast::ast::NodeId;
+use qsc_ast::ast::{self, NodeId};
 use qsc_data_structures::{index_map::IndexMap, span::Span};
 use qsc_hir::{
     hir::{CallableKind, ItemId},
@@ -24,12 +25,114 @@ pub(super) use check::{Checker, GlobalTable};
 #[derive(Debug, Default, Clone)]
 pub struct Table {
     pub udts: FxHashMap<ItemId, Udt>,
+    pub consts: FxHashMap<ItemId, Ty>,
+    pub defaults: FxHashMap<ItemId, DefaultParams>,
 
     // AST nodes that get mapped to types are Expr, Block, Pat, and QubitInit nodes
     pub terms: IndexMap<NodeId, Ty>,
     pub generics: IndexMap<NodeId, Vec<GenericArg>>,
 }
 
+impl Table {
+    /// If a call gives fewer arguments than its callee has parameters, with the missing arguments
+    /// all belonging to parameters with default values, the arguments it omits. The callee can be
+    /// parenthesized or have functors applied, and the call can partially apply it.
+    pub(crate) fn omitted_args(
+        &self,
+        names: &Names,
+        callee: &ast::Expr,
+        input: &ast::Expr,
+    ) -> Option<OmittedArgs> {
+        let mut callee = callee;
+        let mut controls = 0;
+        let item = loop {
+            match callee.kind.as_ref() {
+                ast::ExprKind::Paren(inner)
+                | ast::ExprKind::UnOp(ast::UnOp::Functor(ast::Functor::Adj), inner) => {
+                    callee = inner;
+                }
+                ast::ExprKind::UnOp(ast::UnOp::Functor(ast::Functor::Ctl), inner) => {
+                    controls += 1;
+                    callee = inner;
+                }
+                ast::ExprKind::Path(path) => match names.get(path.id) {
+                    Some(&Res::Item(item, _)) => break item,
+                    _ => return None,
+                },
+                _ => return None,
+            }
+        };
+        let params = self.defaults.get(&item)?;
+
+        let mut input = input;
+        for _ in 0..controls {
+            match strip_parens(input).kind.as_ref() {
+                ast::ExprKind::Tuple(items) if items.len() == 2 => input = &items[1],
+                _ => return None,
+            }
+        }
+        let given = match input.kind.as_ref() {
+            ast::ExprKind::Tuple(items) => items.len(),
+            _ => 1,
+        };
+        (given < params.params && given + params.kinds.len() >= params.params).then_some(
+            OmittedArgs {
+                item,
+                given,
+                params: params.params,
+                controls,
+            },
+        )
+    }
+}
+
+/// The arguments omitted by a call to a callable whose trailing parameters have default values.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct OmittedArgs {
+    /// The callable being called.
+    pub(crate) item: ItemId,
+    /// The number of arguments the call gives.
+    pub(crate) given: usize,
+    /// The number of parameters of the callable.
+    pub(crate) params: usize,
+    /// The number of controlled functors applied to the callable, each of which wraps the arguments
+    /// in a tuple with the control qubits.
+    pub(crate) controls: usize,
+}
+
+/// The parameters of a callable whose trailing parameters have default values.
+#[derive(Clone, Debug)]
+pub struct DefaultParams {
+    /// The number of parameters.
+    pub params: usize,
+    /// The kinds of the callables that compute the default values of the trailing parameters, in
+    /// parameter order. Each one is an operation only if computing its value calls an operation
+    /// or allocates qubits.
+    pub kinds: Vec<CallableKind>,
+    /// The callables that compute the default values, each taking the parameters before its own as
+    /// a tuple. Only known for callables in external packages, since the callables for the package
+    /// being compiled are created when it is lowered.
+    pub items: Vec<ItemId>,
+}
+
+impl DefaultParams {
+    /// Parameters whose defaults are computed by functions, until the defaults are checked.
+    fn new(params: usize, defaults: usize) -> Self {
+        Self {
+            params,
+            kinds: vec![CallableKind::Function; defaults],
+            items: Vec::new(),
+        }
+    }
+}
+
+pub(crate) fn strip_parens(expr: &ast::Expr) -> &ast::Expr {
+    match expr.kind.as_ref() {
+        ast::ExprKind::Paren(inner) => strip_parens(inner),
+        _ => expr,
+    }
+}
+
 #[derive(Clone, Debug, Diagnostic, Error)]
 #[diagnostic(transparent)]
 #[error(transparent)]
@@ -94,6 +197,12 @@ enum ErrorKind {
     #[diagnostic(help("only newtypes support unwrap"))]
     #[diagnostic(code("Qsc.TypeCk.MissingClassUnwrap"))]
     MissingClassUnwrap(String, #[label] Span),
+    #[error("unrecognized class `{0}`")]
+    #[diagnostic(help(
+        "type parameters can only be bounded by the classes Add, Eq, Integral, Num, and Show"
+    ))]
+    #[diagnostic(code("Qsc.TypeCk.UnrecognizedClass"))]
+    UnrecognizedClass(String, #[label] Span),
     #[error("expected superset of {0}, found {1}")]
     #[diagnostic(code("Qsc.TypeCk.MissingFunctor"))]
     MissingFunctor(FunctorSet, FunctorSet, #[label] Span),
@@ -101,6 +210,43 @@ enum ErrorKind {
     #[diagnostic(help("types cannot be inferred for global declarations"))]
     #[diagnostic(code("Qsc.TypeCk.MissingItemTy"))]
     MissingItemTy(#[label] Span),
+    #[error("type {0} is not a struct")]
+    #[diagnostic(help(
+        "only structs and newtypes whose fields are all named can be constructed with `new`"
+    ))]
+    #[diagnostic(code("Qsc.TypeCk.NotStruct"))]
+    NotStruct(String, #[label] Span),
+    #[error("field `{0}` is assigned more than once")]
+    #[diagnostic(code("Qsc.TypeCk.DuplicateField"))]
+    DuplicateField(String, #[label] Span),
+    #[error("struct {0} does not have a field `{1}`")]
+    #[diagnostic(code("Qsc.TypeCk.UnknownStructField"))]
+    UnknownStructField(String, String, #[label] Span),
+    #[error("missing value for field `{0}`")]
+    #[diagnostic(help(
+        "assign every field, or copy the remaining fields from an existing value with `...`"
+    ))]
+    #[diagnostic(code("Qsc.TypeCk.MissingField"))]
+    MissingField(String, #[label] Span),
+    #[error("type {0} has no variant named `{1}`")]
+    #[diagnostic(help("variants can only be referred to through the enum that declares them"))]
+    #[diagnostic(code("Qsc.TypeCk.NotVariant"))]
+    NotVariant(String, String, #[label] Span),
+    #[error("expected an enum variant")]
+    #[diagnostic(help("match arms must name a variant, such as `A.B`, or be the wildcard `_`"))]
+    #[diagnostic(code("Qsc.TypeCk.ExpectedVariant"))]
+    ExpectedVariant(#[label] Span),
+    #[error("variant `{0}` does not have a payload")]
+    #[diagnostic(code("Qsc.TypeCk.NoVariantPayload"))]
+    NoVariantPayload(String, #[label] Span),
+    #[error("non-exhaustive match: {0} not covered")]
+    #[diagnostic(help("add an arm for each missing variant, or a wildcard arm `_`"))]
+    #[diagnostic(code("Qsc.TypeCk.NonExhaustiveMatch"))]
+    NonExhaustiveMatch(String, #[label] Span),
+    #[error("unreachable match arm")]
+    #[diagnostic(help("this arm is already covered by an earlier arm"))]
+    #[diagnostic(code("Qsc.TypeCk.UnreachableArm"))]
+    UnreachableArm(#[label] Span),
     #[error("found hole with type {0}")]
     #[diagnostic(help("replace this hole with an expression of the expected type"))]
     #[diagnostic(code("Qsc.TypeCk.TyHole"))]
@@ -4,7 +4,7 @@
 use super::{
     infer::Inferrer,
     rules::{self, SpecImpl},
-    Error, ErrorKind, Table,
+    DefaultParams, Error, ErrorKind, Table,
 };
 use crate::{
     resolve::{Names, Res},
@@ -16,8 +16,8 @@ use qsc_ast::{
 };
 use qsc_data_structures::index_map::IndexMap;
 use qsc_hir::{
-    hir::{self, ItemId, PackageId},
-    ty::{FunctorSetValue, Scheme, Ty, Udt},
+    hir::{self, CallableKind, ItemId, PackageId},
+    ty::{ClassConstraint, FunctorSetValue, Scheme, Ty, Udt},
 };
 use rustc_hash::FxHashMap;
 use std::vec;
@@ -25,6 +25,8 @@ use std::vec;
 pub(crate) struct GlobalTable {
     udts: FxHashMap<ItemId, Udt>,
     terms: FxHashMap<ItemId, Scheme>,
+    consts: FxHashMap<ItemId, Ty>,
+    defaults: FxHashMap<ItemId, DefaultParams>,
     errors: Vec<Error>,
 }
 
@@ -33,6 +35,8 @@ impl GlobalTable {
         Self {
             udts: FxHashMap::default(),
             terms: FxHashMap::default(),
+            consts: FxHashMap::default(),
+            defaults: FxHashMap::default(),
             errors: Vec::new(),
         }
     }
@@ -46,13 +50,52 @@ impl GlobalTable {
 
             match &item.kind {
                 hir::ItemKind::Callable(decl) => {
+                    if !decl.defaults.is_empty() {
+                        let params = match &decl.input.kind {
+                            hir::PatKind::Tuple(items) => items.len(),
+                            _ => 1,
+                        };
+                        self.defaults.insert(
+                            item_id,
+                            DefaultParams {
+                                params,
+                                kinds: decl
+                                    .default_items
+                                    .iter()
+                                    .map(|item| match package.items.get(*item) {
+                                        Some(hir::Item {
+                                            kind: hir::ItemKind::Callable(default),
+                                            ..
+                                        }) => default.kind,
+                                        _ => panic!("default item should be a callable"),
+                                    })
+                                    .collect(),
+                                items: decl
+                                    .default_items
+                                    .iter()
+                                    .map(|&item| ItemId {
+                                        package: Some(id),
+                                        item,
+                                    })
+                                    .collect(),
+                            },
+                        );
+                    }
                     self.terms.insert(item_id, decl.scheme().with_package(id))
                 }
-                hir::ItemKind::Namespace(..) => None,
+                hir::ItemKind::Namespace(..) | hir::ItemKind::Export(..) => None,
+                hir::ItemKind::Const(_, value) => {
+                    self.consts.insert(item_id, value.ty.clone());
+                    None
+                }
                 hir::ItemKind::Ty(_, udt) => {
                     self.udts.insert(item_id, udt.clone());
-                    self.terms
-                        .insert(item_id, udt.cons_scheme(item_id).with_package(id))
+                    if udt.variants().is_some() {
+                        None
+                    } else {
+                        self.terms
+                            .insert(item_id, udt.cons_scheme(item_id).with_package(id))
+                    }
                 }
             };
         }
@@ -73,6 +116,8 @@ impl Checker {
             globals: globals.terms,
             table: Table {
                 udts: globals.udts,
+                consts: globals.consts,
+                defaults: globals.defaults,
                 terms: IndexMap::new(),
                 generics: IndexMap::new(),
             },
@@ -122,12 +167,15 @@ impl Checker {
 
     fn check_callable_decl(&mut self, names: &Names, decl: &ast::CallableDecl) {
         self.check_callable_signature(names, decl);
+        self.check_callable_defaults(names, decl);
+        let generics = convert::ast_callable_generics(&decl.generics);
         let output = convert::ty_from_ast(names, &decl.output).0;
         match &*decl.body {
             ast::CallableBody::Block(block) => self.check_spec(
                 names,
                 SpecImpl {
                     spec: ast::Spec::Body,
+                    generics: &generics,
                     callable_input: &decl.input,
                     spec_input: None,
                     output: &output,
@@ -142,6 +190,7 @@ impl Checker {
                             names,
                             SpecImpl {
                                 spec: spec.spec,
+                                generics: &generics,
                                 callable_input: &decl.input,
                                 spec_input: Some(input),
                                 output: &output,
@@ -156,6 +205,15 @@ impl Checker {
     }
 
     fn check_callable_signature(&mut self, names: &Names, decl: &ast::CallableDecl) {
+        for bound in decl.generics.iter().flat_map(|param| param.bounds.iter()) {
+            if bound.name.parse::<ClassConstraint>().is_err() {
+                self.errors.push(Error(ErrorKind::UnrecognizedClass(
+                    bound.name.to_string(),
+                    bound.span,
+                )));
+            }
+        }
+
         if convert::ast_callable_functors(decl) != FunctorSetValue::Empty {
             let output = convert::ty_from_ast(names, &decl.output).0;
             match &output {
@@ -169,6 +227,66 @@ impl Checker {
         }
     }
 
+    fn check_callable_defaults(&mut self, names: &Names, decl: &ast::CallableDecl) {
+        if decl.defaults.is_empty() {
+            return;
+        }
+        let params = ast_params(&decl.input);
+        let defaulted = &params[params.len() - decl.defaults.len()..];
+        let defaults: Vec<_> = defaulted
+            .iter()
+            .copied()
+            .zip(decl.defaults.iter().map(AsRef::as_ref))
+            .collect();
+        self.errors.append(&mut rules::defaults(
+            names,
+            &self.globals,
+            &mut self.table,
+            &convert::ast_callable_generics(&decl.generics),
+            &decl.input,
+            &defaults,
+        ));
+
+        let Some(&Res::Item(item, _)) = names.get(decl.name.id) else {
+            panic!("callable should have item ID");
+        };
+        let kinds = decl
+            .defaults
+            .iter()
+            .map(|default| {
+                if is_quantum(&self.table, default) {
+                    CallableKind::Operation
+                } else {
+                    CallableKind::Function
+                }
+            })
+            .collect();
+        self.table
+            .defaults
+            .get_mut(&item)
+            .expect("callable with defaults should have default parameters")
+            .kinds = kinds;
+    }
+
+    fn check_const_decl(&mut self, names: &Names, decl: &ast::ConstDecl) {
+        let Some(&Res::Item(item, _)) = names.get(decl.name.id) else {
+            panic!("const should have item ID");
+        };
+        let ty = self
+            .table
+            .consts
+            .get(&item)
+            .expect("const should have type")
+            .clone();
+        self.errors.append(&mut rules::value(
+            names,
+            &self.globals,
+            &mut self.table,
+            &ty,
+            &decl.value,
+        ));
+    }
+
     fn check_spec(&mut self, names: &Names, spec: SpecImpl) {
         self.errors.append(&mut rules::spec(
             names,
@@ -215,6 +333,11 @@ impl Visitor<'_> for ItemCollector<'_> {
                         .push(Error(ErrorKind::MissingItemTy(span)));
                 }
 
+                if !decl.defaults.is_empty() {
+                    let defaults =
+                        DefaultParams::new(ast_params(&decl.input).len(), decl.defaults.len());
+                    self.checker.table.defaults.insert(item, defaults);
+                }
                 self.checker.globals.insert(item, scheme);
             }
             ast::ItemKind::Ty(name, def) => {
@@ -243,6 +366,64 @@ impl Visitor<'_> for ItemCollector<'_> {
                 );
                 self.checker.globals.insert(item, cons);
             }
+            ast::ItemKind::Struct(decl) => {
+                let span = item.span;
+                let Some(&Res::Item(item, _)) = self.names.get(decl.name.id) else {
+                    panic!("struct should have item ID");
+                };
+
+                let (udt_def, errors) = convert::ast_struct_def(self.names, decl);
+                self.checker.errors.extend(
+                    errors
+                        .into_iter()
+                        .map(|MissingTyError(span)| Error(ErrorKind::MissingItemTy(span))),
+                );
+
+                let udt = Udt {
+                    name: decl.name.name.clone(),
+                    span,
+                    definition: udt_def,
+                };
+                self.checker.globals.insert(item, udt.cons_scheme(item));
+                self.checker.table.udts.insert(item, udt);
+            }
+            ast::ItemKind::Const(decl) => {
+                let Some(&Res::Item(item, _)) = self.names.get(decl.name.id) else {
+                    panic!("const should have item ID");
+                };
+
+                let (ty, errors) = convert::ty_from_ast(self.names, &decl.ty);
+                for MissingTyError(span) in errors {
+                    self.checker
+                        .errors
+                        .push(Error(ErrorKind::MissingItemTy(span)));
+                }
+
+                self.checker.table.consts.insert(item, ty);
+            }
+            ast::ItemKind::Enum(decl) => {
+                let span = item.span;
+                let Some(&Res::Item(item, _)) = self.names.get(decl.name.id) else {
+                    panic!("enum should have item ID");
+                };
+
+                let (udt_def, errors) = convert::ast_enum_def(self.names, decl);
+                self.checker.errors.extend(
+                    errors
+                        .into_iter()
+                        .map(|MissingTyError(span)| Error(ErrorKind::MissingItemTy(span))),
+                );
+
+                // Enums are constructed through their variants, so they have no constructor.
+                self.checker.table.udts.insert(
+                    item,
+                    Udt {
+                        name: decl.name.name.clone(),
+                        span,
+                        definition: udt_def,
+                    },
+                );
+            }
             _ => {}
         }
 
@@ -270,6 +451,60 @@ impl Visitor<'_> for ItemChecker<'_> {
         visit::walk_callable_decl(self, decl);
     }
 
+    fn visit_const_decl(&mut self, decl: &ast::ConstDecl) {
+        self.checker.check_const_decl(self.names, decl);
+        visit::walk_const_decl(self, decl);
+    }
+
     // We do not typecheck attributes, as they are verified during lowering.
     fn visit_attr(&mut self, _: &ast::Attr) {}
 }
+
+/// The parameters in the input pattern of a callable.
+/// Whether evaluating an expression calls an operation or allocates qubits, which makes a callable
+/// that evaluates it an operation.
+fn is_quantum(table: &Table, expr: &ast::Expr) -> bool {
+    struct QuantumFinder<'a> {
+        table: &'a Table,
+        found: bool,
+    }
+
+    impl Visitor<'_> for QuantumFinder<'_> {
+        fn visit_expr(&mut self, expr: &ast::Expr) {
+            match expr.kind.as_ref() {
+                // A lambda's body is evaluated when it is called, not when it is created.
+                ast::ExprKind::Lambda(..) => {}
+                ast::ExprKind::Call(callee, _)
+                    if matches!(self.table.terms.get(callee.id),
+                        Some(Ty::Arrow(arrow)) if arrow.kind == CallableKind::Operation) =>
+                {
+                    self.found = true;
+                }
+                _ => visit::walk_expr(self, expr),
+            }
+        }
+
+        fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+            if let ast::StmtKind::Qubit(..) = stmt.kind.as_ref() {
+                self.found = true;
+            } else {
+                visit::walk_stmt(self, stmt);
+            }
+        }
+    }
+
+    let mut finder = QuantumFinder {
+        table,
+        found: false,
+    };
+    finder.visit_expr(expr);
+    finder.found
+}
+
+fn ast_params(input: &ast::Pat) -> Vec<&ast::Pat> {
+    match input.kind.as_ref() {
+        ast::PatKind::Paren(param) => vec![param],
+        ast::PatKind::Tuple(params) => params.iter().map(AsRef::as_ref).collect(),
+        _ => vec![input],
+    }
+}
@@ -5,15 +5,15 @@ use std::rc::Rc;
 
 use crate::resolve::{self, Names};
 use qsc_ast::ast::{
-    self, CallableBody, CallableDecl, CallableKind, FunctorExpr, FunctorExprKind, Ident, Pat,
-    PatKind, SetOp, Spec, TyDef, TyDefKind, TyKind,
+    self, CallableBody, CallableDecl, CallableKind, EnumDecl, FunctorExpr, FunctorExprKind, Pat,
+    PatKind, SetOp, Spec, StructDecl, TyDef, TyDefKind, TyKind, TypeParameter,
 };
 use qsc_data_structures::span::Span;
 use qsc_hir::{
     hir,
     ty::{
         Arrow, FunctorSet, FunctorSetValue, GenericParam, ParamId, Scheme, Ty, TypeParamName,
-        UdtDef, UdtDefKind, UdtField,
+        UdtDef, UdtDefKind, UdtField, UdtVariant,
     },
 };
 
@@ -57,6 +57,9 @@ pub(crate) fn ty_from_ast(names: &Names, ty: &ast::Ty) -> (Ty, Vec<MissingTyErro
                     "A path should never resolve \
                     to a local or a parameter, as there is syntactic differentiation."
                 ),
+                Some(resolve::Res::Variant(_)) => {
+                    unreachable!("a type path should never resolve to an enum variant")
+                }
                 None => Ty::Err,
             };
             (ty, Vec::new())
@@ -72,7 +75,7 @@ pub(crate) fn ty_from_ast(names: &Names, ty: &ast::Ty) -> (Ty, Vec<MissingTyErro
         TyKind::Tuple(items) => {
             let mut tys = Vec::new();
             let mut errors = Vec::new();
-            for item in items.iter() {
+            for item in items {
                 let (item_ty, item_errors) = ty_from_ast(names, item);
                 tys.push(item_ty);
                 errors.extend(item_errors);
@@ -164,6 +167,58 @@ pub(super) fn ast_ty_def(names: &Names, def: &TyDef) -> (UdtDef, Vec<MissingTyEr
     (def, errors)
 }
 
+pub(super) fn ast_struct_def(names: &Names, decl: &StructDecl) -> (UdtDef, Vec<MissingTyError>) {
+    let mut errors = Vec::new();
+    let mut fields = decl.fields.iter().map(|field| {
+        let (ty, field_errors) = ty_from_ast(names, &field.ty);
+        errors.extend(field_errors);
+        UdtDef {
+            span: field.span,
+            kind: UdtDefKind::Field(UdtField {
+                name_span: Some(field.name.span),
+                name: Some(field.name.name.clone()),
+                ty,
+            }),
+        }
+    });
+
+    // A struct with a single field has the same shape as a newtype with a single named field.
+    let kind = if decl.fields.len() == 1 {
+        fields.next().expect("struct should have one field").kind
+    } else {
+        UdtDefKind::Tuple(fields.collect())
+    };
+
+    let def = UdtDef {
+        span: decl.span,
+        kind,
+    };
+    (def, errors)
+}
+
+pub(super) fn ast_enum_def(names: &Names, decl: &EnumDecl) -> (UdtDef, Vec<MissingTyError>) {
+    let mut errors = Vec::new();
+    let variants = decl
+        .variants
+        .iter()
+        .map(|variant| UdtVariant {
+            name_span: variant.name.span,
+            name: variant.name.name.clone(),
+            ty: variant.ty.as_ref().map(|ty| {
+                let (ty, ty_errors) = ty_from_ast(names, ty);
+                errors.extend(ty_errors);
+                ty
+            }),
+        })
+        .collect();
+
+    let def = UdtDef {
+        span: decl.span,
+        kind: UdtDefKind::Enum(variants),
+    };
+    (def, errors)
+}
+
 pub(super) fn ast_callable_scheme(
     names: &Names,
     callable: &CallableDecl,
@@ -188,7 +243,7 @@ pub(super) fn ast_callable_scheme(
 }
 
 pub(crate) fn synthesize_callable_generics(
-    generics: &[Box<Ident>],
+    generics: &[TypeParameter],
     input: &mut hir::Pat,
 ) -> Vec<GenericParam> {
     let mut params = ast_callable_generics(generics);
@@ -222,9 +277,10 @@ fn synthesize_functor_params_in_pat(
     pat: &mut hir::Pat,
 ) -> Vec<GenericParam> {
     match &mut pat.kind {
-        hir::PatKind::Discard | hir::PatKind::Err | hir::PatKind::Bind(_) => {
-            synthesize_functor_params(next_param, &mut pat.ty)
-        }
+        hir::PatKind::Discard
+        | hir::PatKind::Err
+        | hir::PatKind::Bind(_)
+        | hir::PatKind::Array(..) => synthesize_functor_params(next_param, &mut pat.ty),
         hir::PatKind::Tuple(items) => {
             let mut params = Vec::new();
             for item in &mut *items {
@@ -238,14 +294,20 @@ fn synthesize_functor_params_in_pat(
     }
 }
 
-fn ast_callable_generics(generics: &[Box<Ident>]) -> Vec<GenericParam> {
+pub(super) fn ast_callable_generics(generics: &[TypeParameter]) -> Vec<GenericParam> {
     generics
         .iter()
-        .map(|param| {
-            GenericParam::Ty(TypeParamName {
-                span: param.span,
-                name: param.name.clone(),
-            })
+        .map(|param| GenericParam::Ty {
+            name: TypeParamName {
+                span: param.name.span,
+                name: param.name.name.clone(),
+            },
+            // Unrecognized classes are reported when checking the callable signature.
+            bounds: param
+                .bounds
+                .iter()
+                .filter_map(|bound| bound.name.parse().ok())
+                .collect(),
         })
         .collect()
 }
@@ -267,7 +329,35 @@ pub(crate) fn ast_pat_ty(names: &Names, pat: &Pat) -> (Ty, Vec<MissingTyError>)
             }
             (Ty::Tuple(tys), errors)
         }
+        PatKind::Array(items, rest) => {
+            // Annotations on the items that disagree with the array type are reported when the
+            // pattern is checked.
+            let mut tys = Vec::new();
+            let mut errors = Vec::new();
+            for item in items.iter() {
+                let (item_ty, item_errors) = ast_pat_ty(names, item);
+                tys.push(item_ty);
+                errors.extend(item_errors);
+            }
+            let ty = match rest {
+                Some(rest) => {
+                    let (rest_ty, rest_errors) = ast_pat_ty(names, rest);
+                    errors.extend(rest_errors);
+                    rest_ty
+                }
+                None => {
+                    if let Some(item_ty) = tys.into_iter().next() {
+                        Ty::Array(Box::new(item_ty))
+                    } else {
+                        errors.push(MissingTyError(pat.span));
+                        Ty::Err
+                    }
+                }
+            };
+            (ty, errors)
+        }
         PatKind::Err => (Ty::Err, Vec::new()),
+        PatKind::Variant(..) => unreachable!("variant patterns should only appear in match arms"),
     }
 }
 
@@ -6,8 +6,8 @@ use qsc_data_structures::{index_map::IndexMap, span::Span};
 use qsc_hir::{
     hir::{ItemId, PrimField, Res},
     ty::{
-        Arrow, FunctorSet, FunctorSetValue, GenericArg, GenericParam, InferFunctorId, InferTyId,
-        Prim, Scheme, Ty, Udt,
+        Arrow, ClassConstraint, FunctorSet, FunctorSetValue, GenericArg, GenericParam,
+        InferFunctorId, InferTyId, ParamId, Prim, Scheme, Ty, Udt,
     },
 };
 use rustc_hash::FxHashMap;
@@ -18,6 +18,9 @@ use std::{
 
 const MAX_TY_RECURSION_DEPTH: i8 = 100;
 
+/// The classes that each generic type parameter in scope is bounded by.
+type ParamBounds = FxHashMap<ParamId, Vec<ClassConstraint>>;
+
 #[derive(Debug, Default)]
 struct Solution {
     tys: IndexMap<InferTyId, Ty>,
@@ -138,9 +141,16 @@ impl Class {
         }
     }
 
-    fn check(self, udts: &FxHashMap<ItemId, Udt>, span: Span) -> (Vec<Constraint>, Vec<Error>) {
+    fn check(
+        self,
+        udts: &FxHashMap<ItemId, Udt>,
+        params: &ParamBounds,
+        span: Span,
+    ) -> (Vec<Constraint>, Vec<Error>) {
         match self {
-            Class::Add(ty) if check_add(&ty) => (Vec::new(), Vec::new()),
+            Class::Add(ty) if check_add(&ty) || has_bound(params, &ty, ClassConstraint::Add) => {
+                (Vec::new(), Vec::new())
+            }
             Class::Add(ty) => (
                 Vec::new(),
                 vec![Error(ErrorKind::MissingClassAdd(ty.display(), span))],
@@ -152,6 +162,9 @@ impl Class {
                 output,
             } => check_call(callee, &input, output, span),
             Class::Ctl { op, with_ctls } => check_ctl(op, with_ctls, span),
+            Class::Eq(ty) if has_bound(params, &ty, ClassConstraint::Eq) => {
+                (Vec::new(), Vec::new())
+            }
             Class::Eq(ty) => check_eq(ty, span),
             Class::Exp { base, power } => check_exp(base, power, span),
             Class::HasField { record, name, item } => {
@@ -162,17 +175,26 @@ impl Class {
                 index,
                 item,
             } => check_has_index(container, index, item, span),
-            Class::Integral(ty) if check_integral(&ty) => (Vec::new(), Vec::new()),
+            Class::Integral(ty)
+                if check_integral(&ty) || has_bound(params, &ty, ClassConstraint::Integral) =>
+            {
+                (Vec::new(), Vec::new())
+            }
             Class::Integral(ty) => (
                 Vec::new(),
                 vec![Error(ErrorKind::MissingClassInteger(ty.display(), span))],
             ),
             Class::Iterable { container, item } => check_iterable(container, item, span),
-            Class::Num(ty) if check_num(&ty) => (Vec::new(), Vec::new()),
+            Class::Num(ty) if check_num(&ty) || has_bound(params, &ty, ClassConstraint::Num) => {
+                (Vec::new(), Vec::new())
+            }
             Class::Num(ty) => (
                 Vec::new(),
                 vec![Error(ErrorKind::MissingClassNum(ty.display(), span))],
             ),
+            Class::Show(ty) if has_bound(params, &ty, ClassConstraint::Show) => {
+                (Vec::new(), Vec::new())
+            }
             Class::Show(ty) => check_show(ty, span),
             Class::Unwrap { wrapper, base } => check_unwrap(udts, &wrapper, base, span),
         }
@@ -337,8 +359,20 @@ pub(super) struct Inferrer {
 
 impl Inferrer {
     pub(super) fn new() -> Self {
+        Self::with_params(&[])
+    }
+
+    /// Creates an inferrer for a callable body, where the given generic parameters are in scope.
+    pub(super) fn with_params(params: &[GenericParam]) -> Self {
+        let mut solver = Solver::new();
+        for (ix, param) in params.iter().enumerate() {
+            if let GenericParam::Ty { bounds, .. } = param {
+                solver.param_bounds.insert(ix.into(), bounds.clone());
+            }
+        }
+
         Self {
-            solver: Solver::new(),
+            solver,
             constraints: VecDeque::new(),
             next_ty: InferTyId::default(),
             next_functor: InferFunctorId::default(),
@@ -381,7 +415,13 @@ impl Inferrer {
             .params()
             .iter()
             .map(|param| match param {
-                GenericParam::Ty(_) => GenericArg::Ty(self.fresh_ty(TySource::not_divergent(span))),
+                GenericParam::Ty { bounds, .. } => {
+                    let ty = self.fresh_ty(TySource::not_divergent(span));
+                    for &bound in bounds {
+                        self.class(span, bound_class(bound, ty.clone()));
+                    }
+                    GenericArg::Ty(ty)
+                }
                 GenericParam::Functor(expected) => {
                     let actual = self.fresh_functor();
                     self.constraints.push_back(Constraint::Superset {
@@ -451,6 +491,7 @@ impl Inferrer {
 #[derive(Debug)]
 struct Solver {
     solution: Solution,
+    param_bounds: ParamBounds,
     pending_tys: FxHashMap<InferTyId, Vec<Class>>,
     pending_functors: FxHashMap<InferFunctorId, FunctorSetValue>,
     errors: Vec<Error>,
@@ -460,6 +501,7 @@ impl Solver {
     fn new() -> Self {
         Self {
             solution: Solution::default(),
+            param_bounds: ParamBounds::default(),
             pending_tys: FxHashMap::default(),
             pending_functors: FxHashMap::default(),
             errors: Vec::new(),
@@ -514,7 +556,7 @@ impl Solver {
         } else {
             let (constraints, mut errors) = class
                 .map(|ty| substituted_ty(&self.solution, ty))
-                .check(udts, span);
+                .check(udts, &self.param_bounds, span);
             self.errors.append(&mut errors);
             constraints
         }
@@ -733,6 +775,45 @@ fn contains_infer_ty(id: InferTyId, ty: &Ty) -> bool {
     }
 }
 
+/// The class that a type argument must belong to in order to satisfy a bound.
+fn bound_class(bound: ClassConstraint, ty: Ty) -> Class {
+    match bound {
+        ClassConstraint::Add => Class::Add(ty),
+        ClassConstraint::Eq => Class::Eq(ty),
+        ClassConstraint::Integral => Class::Integral(ty),
+        ClassConstraint::Num => Class::Num(ty),
+        ClassConstraint::Show => Class::Show(ty),
+    }
+}
+
+/// Checks if the type is a type parameter whose bounds imply the given class.
+fn has_bound(params: &ParamBounds, ty: &Ty, class: ClassConstraint) -> bool {
+    let Ty::Param(_, id) = ty else {
+        return false;
+    };
+    params
+        .get(id)
+        .is_some_and(|bounds| bounds.iter().any(|&bound| implies(bound, class)))
+}
+
+/// Checks if every type that belongs to the bound class also belongs to the given class.
+fn implies(bound: ClassConstraint, class: ClassConstraint) -> bool {
+    bound == class
+        || matches!(
+            (bound, class),
+            (
+                ClassConstraint::Integral,
+                ClassConstraint::Add
+                    | ClassConstraint::Eq
+                    | ClassConstraint::Num
+                    | ClassConstraint::Show
+            ) | (
+                ClassConstraint::Num,
+                ClassConstraint::Add | ClassConstraint::Eq | ClassConstraint::Show
+            )
+        )
+}
+
 fn check_add(ty: &Ty) -> bool {
     matches!(
         ty,
@@ -1032,7 +1113,8 @@ fn check_unwrap(
     span: Span,
 ) -> (Vec<Constraint>, Vec<Error>) {
     if let Ty::Udt(_, Res::Item(id)) = wrapper {
-        if let Some(udt) = udts.get(id) {
+        // Enums do not wrap a single value, so they cannot be unwrapped.
+        if let Some(udt) = udts.get(id).filter(|udt| udt.variants().is_none()) {
             return (
                 vec![Constraint::Eq {
                     expected: base,
@@ -4,20 +4,20 @@
 use super::{
     convert,
     infer::{ArgTy, Class, Inferrer, TySource},
-    Error, Table,
+    strip_parens, Error, ErrorKind, OmittedArgs, Table,
 };
 use crate::resolve::{self, Names, Res};
 use qsc_ast::ast::{
-    self, BinOp, Block, Expr, ExprKind, Functor, Lit, NodeId, Pat, PatKind, QubitInit,
-    QubitInitKind, Spec, Stmt, StmtKind, StringComponent, TernOp, TyKind, UnOp,
+    self, BinOp, Block, Expr, ExprKind, FieldAssign, Functor, Lit, MatchArm, NodeId, Pat, PatKind,
+    QubitInit, QubitInitKind, Spec, Stmt, StmtKind, StringComponent, TernOp, TyKind, UnOp,
 };
 use qsc_data_structures::span::Span;
 use qsc_hir::{
     hir::{self, ItemId},
-    ty::{Arrow, FunctorSet, FunctorSetValue, GenericArg, Prim, Scheme, Ty},
+    ty::{Arrow, FunctorSet, FunctorSetValue, GenericArg, GenericParam, Prim, Scheme, Ty},
 };
-use rustc_hash::FxHashMap;
-use std::convert::identity;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{convert::identity, rc::Rc};
 
 /// An inferred partial term has a type, but may be the result of a diverging (non-terminating)
 /// computation.
@@ -35,6 +35,10 @@ impl<T> Partial<T> {
     }
 }
 
+/// The enum matched by the arms of a match expression, and for each of its variants, whether an
+/// arm covers it.
+type VariantCoverage = (ItemId, Vec<(Rc<str>, bool)>);
+
 struct Context<'a> {
     names: &'a Names,
     globals: &'a FxHashMap<ItemId, Scheme>,
@@ -43,6 +47,7 @@ struct Context<'a> {
     typed_holes: Vec<(NodeId, Span)>,
     new: Vec<NodeId>,
     inferrer: &'a mut Inferrer,
+    errors: Vec<Error>,
 }
 
 impl<'a> Context<'a> {
@@ -61,6 +66,7 @@ impl<'a> Context<'a> {
             typed_holes: Vec::new(),
             new,
             inferrer,
+            errors: Vec::new(),
         }
     }
 
@@ -118,6 +124,9 @@ impl<'a> Context<'a> {
                     "A path should never resolve \
                     to a local or a parameter, as there is syntactic differentiation."
                 ),
+                Some(resolve::Res::Variant(_)) => {
+                    unreachable!("a type path should never resolve to an enum variant")
+                }
             },
             TyKind::Param(name) => match self.names.get(name.id) {
                 Some(Res::Param(id)) => Ty::Param(name.name.clone(), *id),
@@ -227,15 +236,21 @@ impl<'a> Context<'a> {
             }
             ExprKind::BinOp(op, lhs, rhs) => self.infer_binop(expr.span, *op, lhs, rhs),
             ExprKind::Block(block) => self.infer_block(block),
-            ExprKind::Call(callee, input) => {
+            ExprKind::Break | ExprKind::Continue => self.diverge(),
+            ExprKind::Call(callee, input_expr) => {
+                let omitted = self.table.omitted_args(self.names, callee, input_expr);
                 let callee = self.infer_expr(callee);
-                let input = self.infer_hole_tuple(
+                let mut input = self.infer_hole_tuple(
                     ArgTy::Hole,
                     ArgTy::Given,
                     ArgTy::Tuple,
                     ArgTy::to_ty,
-                    input,
+                    input_expr,
                 );
+                if let Some(omitted) = omitted {
+                    // The omitted arguments take the types of the parameters they are for.
+                    input.ty = self.add_omitted_args(expr.span, input_expr, input.ty, &omitted);
+                }
                 let output_ty = self.inferrer.fresh_ty(TySource::not_divergent(expr.span));
                 self.inferrer.class(
                     expr.span,
@@ -374,6 +389,7 @@ impl<'a> Context<'a> {
                     functors: self.inferrer.fresh_functor(),
                 })))
             }
+            ExprKind::Match(scrutinee, arms) => self.infer_match(expr.span, scrutinee, arms),
             ExprKind::Lit(lit) => match lit.as_ref() {
                 Lit::BigInt(_) => converge(Ty::Prim(Prim::BigInt)),
                 Lit::Bool(_) => converge(Ty::Prim(Prim::Bool)),
@@ -386,6 +402,9 @@ impl<'a> Context<'a> {
             ExprKind::Paren(expr) => self.infer_expr(expr),
             ExprKind::Path(path) => match self.names.get(path.id) {
                 None => converge(Ty::Err),
+                Some(Res::Item(item, _)) if self.table.consts.contains_key(item) => {
+                    converge(self.table.consts[item].clone())
+                }
                 Some(Res::Item(item, _)) => {
                     let scheme = self.globals.get(item).expect("item should have scheme");
                     let (ty, args) = self.inferrer.instantiate(scheme, expr.span);
@@ -399,6 +418,16 @@ impl<'a> Context<'a> {
                         .expect("local should have type")
                         .clone(),
                 ),
+                Some(&Res::Variant(item)) => match self.variant(item, &path.name) {
+                    Some((ty, None)) => converge(ty),
+                    Some((ty, Some(payload))) => converge(Ty::Arrow(Box::new(Arrow {
+                        kind: hir::CallableKind::Function,
+                        input: Box::new(payload),
+                        output: Box::new(ty),
+                        functors: FunctorSet::Value(FunctorSetValue::Empty),
+                    }))),
+                    None => converge(Ty::Err),
+                },
                 Some(Res::PrimTy(_) | Res::UnitTy | Res::Param(_)) => {
                     panic!("expression resolves to type")
                 }
@@ -437,6 +466,9 @@ impl<'a> Context<'a> {
                     converge(Ty::UNIT),
                 )
             }
+            ExprKind::Struct(path, copy, fields) => {
+                self.infer_struct(path, copy.as_deref(), fields)
+            }
             ExprKind::Return(expr) => {
                 let ty = self.infer_expr(expr).ty;
                 if let Some(return_ty) = &self.return_ty {
@@ -647,16 +679,42 @@ impl<'a> Context<'a> {
     ) -> Partial<Ty> {
         let container = self.infer_expr(container);
         let item = self.infer_expr(item);
-        if let Some(field) = resolve::extract_field_name(self.names, index) {
-            self.inferrer.class(
-                span,
-                Class::HasField {
-                    record: container.ty.clone(),
-                    name: field.to_string(),
-                    item: item.ty.clone(),
-                },
-            );
-            self.diverge_if(item.diverges, container)
+        if let Some(path) = resolve::extract_update_path(self.names, index) {
+            // Each step updates an item of the value at the previous step. The types of the
+            // intermediate values are recorded on the expressions that end with their steps.
+            let mut record = container.ty.clone();
+            let mut diverges = item.diverges;
+            let last = path.len() - 1;
+            for (i, (step_expr, step)) in path.into_iter().enumerate() {
+                let step_item = if i == last {
+                    item.ty.clone()
+                } else {
+                    let ty = self
+                        .inferrer
+                        .fresh_ty(TySource::not_divergent(step_expr.span));
+                    self.record(step_expr.id, ty.clone());
+                    ty
+                };
+                let class = match step {
+                    resolve::UpdateStep::Field(name) => Class::HasField {
+                        record,
+                        name: name.to_string(),
+                        item: step_item.clone(),
+                    },
+                    resolve::UpdateStep::Index(index) => {
+                        let index = self.infer_expr(index);
+                        diverges = diverges || index.diverges;
+                        Class::HasIndex {
+                            container: record,
+                            index: index.ty,
+                            item: step_item.clone(),
+                        }
+                    }
+                };
+                self.inferrer.class(span, class);
+                record = step_item;
+            }
+            self.diverge_if(diverges, container)
         } else {
             let index = self.infer_expr(index);
             self.inferrer.class(
@@ -671,6 +729,132 @@ impl<'a> Context<'a> {
         }
     }
 
+    fn infer_struct(
+        &mut self,
+        path: &ast::Path,
+        copy: Option<&Expr>,
+        fields: &[Box<FieldAssign>],
+    ) -> Partial<Ty> {
+        let (ty, struct_fields) = match self.names.get(path.id) {
+            Some(&Res::Item(item, _)) => {
+                let udt = self.table.udts.get(&item).expect("item should be a type");
+                let ty = Ty::Udt(udt.name.clone(), hir::Res::Item(item));
+                let struct_fields: Option<Vec<_>> = udt.struct_fields().map(|fields| {
+                    fields
+                        .into_iter()
+                        .map(|field| {
+                            let name = field.name.clone().expect("struct field should be named");
+                            (name, field.ty.clone())
+                        })
+                        .collect()
+                });
+                if struct_fields.is_none() {
+                    self.errors
+                        .push(Error(ErrorKind::NotStruct(ty.display(), path.span)));
+                }
+                (ty, struct_fields)
+            }
+            Some(&Res::PrimTy(prim)) => {
+                self.errors.push(Error(ErrorKind::NotStruct(
+                    Ty::Prim(prim).display(),
+                    path.span,
+                )));
+                (Ty::Err, None)
+            }
+            Some(Res::UnitTy) => {
+                self.errors.push(Error(ErrorKind::NotStruct(
+                    Ty::Tuple(Vec::new()).display(),
+                    path.span,
+                )));
+                (Ty::Err, None)
+            }
+            Some(Res::Local(_) | Res::Param(_) | Res::Variant(_)) | None => (Ty::Err, None),
+        };
+
+        let mut diverges = false;
+        if let Some(copy) = copy {
+            let copy_ty = self.infer_expr(copy);
+            diverges = diverges || copy_ty.diverges;
+            self.inferrer.eq(copy.span, ty.clone(), copy_ty.ty);
+        }
+
+        let mut assigned: FxHashSet<Rc<str>> = FxHashSet::default();
+        for field in fields {
+            let value = self.infer_expr(&field.value);
+            diverges = diverges || value.diverges;
+            let Some(struct_fields) = &struct_fields else {
+                continue;
+            };
+            let name = &field.field.name;
+            match struct_fields.iter().find(|(n, _)| n == name) {
+                Some((_, field_ty)) => {
+                    if !assigned.insert(Rc::clone(name)) {
+                        self.errors.push(Error(ErrorKind::DuplicateField(
+                            name.to_string(),
+                            field.field.span,
+                        )));
+                    }
+                    self.inferrer
+                        .eq(field.value.span, field_ty.clone(), value.ty);
+                }
+                None => self.errors.push(Error(ErrorKind::UnknownStructField(
+                    ty.display(),
+                    name.to_string(),
+                    field.field.span,
+                ))),
+            }
+        }
+
+        if copy.is_none() {
+            for (name, _) in struct_fields.iter().flatten() {
+                if !assigned.contains(name) {
+                    self.errors
+                        .push(Error(ErrorKind::MissingField(name.to_string(), path.span)));
+                }
+            }
+        }
+
+        self.diverge_if(diverges, converge(ty))
+    }
+
+    fn add_omitted_args(
+        &mut self,
+        span: Span,
+        input_expr: &Expr,
+        input: ArgTy,
+        omitted: &OmittedArgs,
+    ) -> ArgTy {
+        if omitted.controls > 0 {
+            let inner_omitted = OmittedArgs {
+                controls: omitted.controls - 1,
+                ..*omitted
+            };
+            let (ExprKind::Tuple(exprs), ArgTy::Tuple(mut tys)) =
+                (strip_parens(input_expr).kind.as_ref(), input)
+            else {
+                panic!("controlled call should have control and argument tuple");
+            };
+            let inner = tys.pop().expect("controlled call should have arguments");
+            let inner = self.add_omitted_args(span, &exprs[1], inner, &inner_omitted);
+            tys.push(inner);
+            return ArgTy::Tuple(tys);
+        }
+
+        let mut args = match (input_expr.kind.as_ref(), input) {
+            (ExprKind::Tuple(_), ArgTy::Tuple(args)) => args,
+            (_, arg) => vec![arg],
+        };
+        args.extend(
+            (omitted.given..omitted.params)
+                .map(|_| ArgTy::Given(self.inferrer.fresh_ty(TySource::not_divergent(span)))),
+        );
+        if omitted.params == 1 {
+            args.pop().expect("callee should have a parameter")
+        } else {
+            ArgTy::Tuple(args)
+        }
+    }
+
     fn infer_pat(&mut self, pat: &Pat) -> Ty {
         let ty = match &*pat.kind {
             PatKind::Bind(name, None) => {
@@ -691,13 +875,191 @@ impl<'a> Context<'a> {
             PatKind::Tuple(items) => {
                 Ty::Tuple(items.iter().map(|item| self.infer_pat(item)).collect())
             }
+            PatKind::Array(items, rest) => {
+                let item_ty = self.inferrer.fresh_ty(TySource::not_divergent(pat.span));
+                for item in items {
+                    let ty = self.infer_pat(item);
+                    self.inferrer.eq(item.span, item_ty.clone(), ty);
+                }
+                let ty = Ty::Array(Box::new(item_ty));
+                if let Some(rest) = rest {
+                    let rest_ty = self.infer_pat(rest);
+                    self.inferrer.eq(rest.span, ty.clone(), rest_ty);
+                }
+                ty
+            }
             PatKind::Err => Ty::Err,
+            PatKind::Variant(..) => {
+                unreachable!("variant patterns should only appear in match arms")
+            }
         };
 
         self.record(pat.id, ty.clone());
         ty
     }
 
+    /// The enum type and payload type of the variant of the enum `item` with the given name.
+    /// Returns [None] and records an error if the enum has no such variant.
+    fn variant(&mut self, item: ItemId, name: &ast::Ident) -> Option<(Ty, Option<Ty>)> {
+        let udt = self.table.udts.get(&item).expect("item should be a type");
+        let ty = Ty::Udt(udt.name.clone(), hir::Res::Item(item));
+        if let Some((_, variant)) = udt.find_variant(&name.name) {
+            let payload = variant.ty.clone().map(|payload| match item.package {
+                Some(package) => payload.with_package(package),
+                None => payload,
+            });
+            Some((ty, payload))
+        } else {
+            self.errors.push(Error(ErrorKind::NotVariant(
+                ty.display(),
+                name.name.to_string(),
+                name.span,
+            )));
+            None
+        }
+    }
+
+    fn infer_match(&mut self, span: Span, scrutinee: &Expr, arms: &[Box<MatchArm>]) -> Partial<Ty> {
+        let scrutinee = self.infer_expr(scrutinee);
+        let ty = self.inferrer.fresh_ty(TySource::not_divergent(span));
+        if arms.is_empty() {
+            self.inferrer.eq(span, Ty::UNIT, ty.clone());
+        }
+
+        let mut variants: Option<VariantCoverage> = None;
+        let mut has_wildcard = false;
+        let mut arms_diverge = !arms.is_empty();
+        for arm in arms {
+            let covers_new = match &*arm.pat.kind {
+                PatKind::Discard(None) => {
+                    self.record(arm.pat.id, scrutinee.ty.clone());
+                    let covers_new = !has_wildcard
+                        && variants
+                            .as_ref()
+                            .is_none_or(|(_, vs)| vs.iter().any(|(_, covered)| !covered));
+                    has_wildcard = true;
+                    covers_new
+                }
+                PatKind::Variant(path, payload) => {
+                    let covers_new = self.infer_variant_pat(
+                        arm.pat.id,
+                        &scrutinee.ty,
+                        path,
+                        payload.as_deref(),
+                        &mut variants,
+                    );
+                    covers_new && !has_wildcard
+                }
+                _ => {
+                    self.errors
+                        .push(Error(ErrorKind::ExpectedVariant(arm.pat.span)));
+                    true
+                }
+            };
+            if !covers_new {
+                self.errors
+                    .push(Error(ErrorKind::UnreachableArm(arm.pat.span)));
+            }
+
+            let body_span = arm.body.span;
+            let body = self.infer_expr(&arm.body);
+            self.inferrer.eq(body_span, ty.clone(), body.ty);
+            arms_diverge = arms_diverge && body.diverges;
+        }
+
+        if !has_wildcard {
+            let missing: Vec<_> = match &variants {
+                Some((_, vs)) => vs
+                    .iter()
+                    .filter(|(_, covered)| !covered)
+                    .map(|(name, _)| format!("`{name}`"))
+                    .collect(),
+                None => vec!["`_`".to_string()],
+            };
+            if !missing.is_empty() {
+                self.errors.push(Error(ErrorKind::NonExhaustiveMatch(
+                    missing.join(", "),
+                    span,
+                )));
+            }
+        }
+
+        self.diverge_if(
+            scrutinee.diverges,
+            Partial {
+                ty,
+                diverges: arms_diverge,
+            },
+        )
+    }
+
+    /// Infers the type of a variant pattern in a match arm and marks the variant as covered.
+    /// Returns false if the variant was already covered by an earlier arm.
+    fn infer_variant_pat(
+        &mut self,
+        pat_id: NodeId,
+        scrutinee_ty: &Ty,
+        path: &ast::Path,
+        payload: Option<&Pat>,
+        variants: &mut Option<VariantCoverage>,
+    ) -> bool {
+        let payload_ty = if let Some(&Res::Variant(item)) = self.names.get(path.id) {
+            self.variant(item, &path.name).map(|(ty, payload_ty)| {
+                self.inferrer.eq(path.span, scrutinee_ty.clone(), ty);
+                (item, payload_ty)
+            })
+        } else {
+            if self.names.get(path.id).is_some() {
+                self.errors
+                    .push(Error(ErrorKind::ExpectedVariant(path.span)));
+            }
+            None
+        };
+        self.record(pat_id, scrutinee_ty.clone());
+
+        let payload_pat_ty = payload.map(|payload| self.infer_pat(payload));
+        let Some((item, payload_ty)) = payload_ty else {
+            return true;
+        };
+        match (payload_pat_ty, payload_ty) {
+            (Some(pat_ty), Some(payload_ty)) => {
+                let payload = payload.expect("payload pattern should be present");
+                self.inferrer.eq(payload.span, payload_ty, pat_ty);
+            }
+            (Some(_), None) => self.errors.push(Error(ErrorKind::NoVariantPayload(
+                path.name.name.to_string(),
+                payload.expect("payload pattern should be present").span,
+            ))),
+            (None, _) => {}
+        }
+
+        let (enum_item, covered) = variants.get_or_insert_with(|| {
+            let udt = self.table.udts.get(&item).expect("item should be a type");
+            let names = udt
+                .variants()
+                .expect("type should be an enum")
+                .iter()
+                .map(|v| (v.name.clone(), false))
+                .collect();
+            (item, names)
+        });
+        if *enum_item != item {
+            // The type mismatch is reported by the equality constraint with the scrutinee.
+            return true;
+        }
+        match covered
+            .iter_mut()
+            .find(|(name, _)| name.as_ref() == path.name.name.as_ref())
+        {
+            Some((_, true)) => false,
+            Some((_, covered)) => {
+                *covered = true;
+                true
+            }
+            None => true,
+        }
+    }
+
     fn infer_qubit_init(&mut self, init: &QubitInit) -> Partial<Ty> {
         let ty = match &*init.kind {
             QubitInitKind::Array(length) => {
@@ -759,7 +1121,8 @@ impl<'a> Context<'a> {
     }
 
     pub(crate) fn solve(self) -> Vec<Error> {
-        let mut errs = self.inferrer.solve(&self.table.udts);
+        let mut errs = self.errors;
+        errs.extend(self.inferrer.solve(&self.table.udts));
 
         for id in self.new {
             let ty = self.table.terms.get_mut(id).expect("node should have type");
@@ -779,7 +1142,7 @@ impl<'a> Context<'a> {
 
         for (id, span) in self.typed_holes {
             let ty = self.table.terms.get_mut(id).expect("node should have type");
-            errs.push(Error(super::ErrorKind::TyHole(ty.display(), span)));
+            errs.push(Error(ErrorKind::TyHole(ty.display(), span)));
         }
 
         errs
@@ -789,6 +1152,7 @@ impl<'a> Context<'a> {
 #[derive(Clone, Copy)]
 pub(super) struct SpecImpl<'a> {
     pub(super) spec: Spec,
+    pub(super) generics: &'a [GenericParam],
     pub(super) callable_input: &'a Pat,
     pub(super) spec_input: Option<&'a Pat>,
     pub(super) output: &'a Ty,
@@ -802,12 +1166,38 @@ pub(super) fn spec(
     table: &mut Table,
     spec: SpecImpl,
 ) -> Vec<Error> {
-    let mut inferrer = Inferrer::new();
+    let mut inferrer = Inferrer::with_params(spec.generics);
     let mut context = Context::new(names, globals, table, &mut inferrer, Vec::new());
     context.infer_spec(spec);
     context.solve()
 }
 
+/// Checks the default values of a callable's trailing parameters. The parameters are in scope for
+/// the default values, so the callable's input is inferred first.
+pub(super) fn defaults(
+    names: &Names,
+    globals: &FxHashMap<ItemId, Scheme>,
+    table: &mut Table,
+    generics: &[GenericParam],
+    input: &Pat,
+    defaults: &[(&Pat, &Expr)],
+) -> Vec<Error> {
+    let mut inferrer = Inferrer::with_params(generics);
+    let mut context = Context::new(names, globals, table, &mut inferrer, Vec::new());
+    context.infer_pat(input);
+    for &(param, default) in defaults {
+        let expected = context
+            .table
+            .terms
+            .get(param.id)
+            .expect("parameter should have type")
+            .clone();
+        let actual = context.infer_expr(default).ty;
+        context.inferrer.eq(default.span, expected, actual);
+    }
+    context.solve()
+}
+
 pub(super) fn expr(
     names: &Names,
     globals: &FxHashMap<ItemId, Scheme>,
@@ -820,6 +1210,20 @@ pub(super) fn expr(
     context.solve()
 }
 
+pub(super) fn value(
+    names: &Names,
+    globals: &FxHashMap<ItemId, Scheme>,
+    table: &mut Table,
+    ty: &Ty,
+    value: &Expr,
+) -> Vec<Error> {
+    let mut inferrer = Inferrer::new();
+    let mut context = Context::new(names, globals, table, &mut inferrer, Vec::new());
+    let actual = context.infer_expr(value).ty;
+    context.inferrer.eq(value.span, ty.clone(), actual);
+    context.solve()
+}
+
 pub(super) fn stmt(
     names: &Names,
     globals: &FxHashMap<ItemId, Scheme>,
@@ -792,6 +792,80 @@ fn qubit_tuple_arity_error() {
     );
 }
 
+#[test]
+fn let_array_pattern() {
+    check(
+        "",
+        "{ let [x, ...rest] = [0, 1]; }",
+        &expect![[r##"
+        #1 0-30 "{ let [x, ...rest] = [0, 1]; }" : Unit
+        #2 0-30 "{ let [x, ...rest] = [0, 1]; }" : Unit
+        #4 6-18 "[x, ...rest]" : Int[]
+        #5 7-8 "x" : Int
+        #7 13-17 "rest" : Int[]
+        #9 21-27 "[0, 1]" : Int[]
+        #10 22-23 "0" : Int
+        #11 25-26 "1" : Int
+    "##]],
+    );
+}
+
+#[test]
+fn let_array_pattern_not_array_error() {
+    check(
+        "",
+        "{ let [x, y] = (0, 1); }",
+        &expect![[r##"
+        #1 0-24 "{ let [x, y] = (0, 1); }" : Unit
+        #2 0-24 "{ let [x, y] = (0, 1); }" : Unit
+        #4 6-12 "[x, y]" : ?2[]
+        #5 7-8 "x" : ?2
+        #7 10-11 "y" : ?2
+        #9 15-21 "(0, 1)" : (Int, Int)
+        #10 16-17 "0" : Int
+        #11 19-20 "1" : Int
+        Error(Type(Error(TyMismatch("?[]", "(Int, Int)", Span { lo: 15, hi: 21 }))))
+        Error(Type(Error(AmbiguousTy(Span { lo: 10, hi: 11 }))))
+    "##]],
+    );
+}
+
+#[test]
+fn callable_array_param() {
+    check(
+        indoc! {"
+            namespace A {
+                function Foo([x : Int, ...rest : Int[]]) : Int { x + Length(rest) }
+                function Length(xs : Int[]) : Int { 0 }
+            }
+        "},
+        "A.Foo([1, 2])",
+        &expect![[r##"
+            #6 30-58 "([x : Int, ...rest : Int[]])" : Int[]
+            #7 31-57 "[x : Int, ...rest : Int[]]" : Int[]
+            #8 32-39 "x : Int" : Int
+            #13 44-56 "rest : Int[]" : Int[]
+            #22 65-85 "{ x + Length(rest) }" : Int
+            #24 67-83 "x + Length(rest)" : Int
+            #25 67-68 "x" : Int
+            #28 71-83 "Length(rest)" : Int
+            #29 71-77 "Length" : (Int[] -> Int)
+            #32 77-83 "(rest)" : Int[]
+            #33 78-82 "rest" : Int[]
+            #39 105-117 "(xs : Int[])" : Int[]
+            #40 106-116 "xs : Int[]" : Int[]
+            #49 124-129 "{ 0 }" : Int
+            #51 126-127 "0" : Int
+            #52 132-145 "A.Foo([1, 2])" : Int
+            #53 132-137 "A.Foo" : (Int[] -> Int)
+            #57 137-145 "([1, 2])" : Int[]
+            #58 138-144 "[1, 2]" : Int[]
+            #59 139-140 "1" : Int
+            #60 142-143 "2" : Int
+        "##]],
+    );
+}
+
 #[test]
 fn for_loop_not_iterable() {
     check(
@@ -1655,6 +1729,50 @@ fn return_diverges_stmt_after() {
     );
 }
 
+#[test]
+fn break_diverges() {
+    check(
+        indoc! {"
+            namespace A {
+                function Foo(xs : Int[]) : Int {
+                    mutable sum = 0;
+                    for x in xs {
+                        let y = if x < 0 { break } else { x };
+                        set sum += y;
+                    }
+                    sum
+                }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #6 30-42 "(xs : Int[])" : Int[]
+            #7 31-41 "xs : Int[]" : Int[]
+            #16 49-202 "{\n        mutable sum = 0;\n        for x in xs {\n            let y = if x < 0 { break } else { x };\n            set sum += y;\n        }\n        sum\n    }" : Int
+            #18 67-70 "sum" : Int
+            #20 73-74 "0" : Int
+            #22 84-184 "for x in xs {\n            let y = if x < 0 { break } else { x };\n            set sum += y;\n        }" : Unit
+            #23 88-89 "x" : Int
+            #25 93-95 "xs" : Int[]
+            #28 96-184 "{\n            let y = if x < 0 { break } else { x };\n            set sum += y;\n        }" : Unit
+            #30 114-115 "y" : Int
+            #32 118-147 "if x < 0 { break } else { x }" : Int
+            #33 121-126 "x < 0" : Bool
+            #34 121-122 "x" : Int
+            #37 125-126 "0" : Int
+            #38 127-136 "{ break }" : Int
+            #40 129-134 "break" : Int
+            #41 137-147 "else { x }" : Int
+            #42 142-147 "{ x }" : Int
+            #44 144-145 "x" : Int
+            #48 161-173 "set sum += y" : Unit
+            #49 165-168 "sum" : Int
+            #52 172-173 "y" : Int
+            #56 193-196 "sum" : Int
+        "##]],
+    );
+}
+
 #[test]
 fn return_mismatch() {
     check(
@@ -2307,6 +2425,515 @@ fn newtype_field_invalid() {
     );
 }
 
+#[test]
+fn struct_cons() {
+    check(
+        indoc! {"
+            namespace A {
+                struct Pair { First : Int, Second : Double }
+                function Foo() : Pair { new Pair { Second = 2.0, First = 1 } }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #19 79-81 "()" : Unit
+            #23 89-129 "{ new Pair { Second = 2.0, First = 1 } }" : UDT<"Pair": Item 1>
+            #25 91-127 "new Pair { Second = 2.0, First = 1 }" : UDT<"Pair": Item 1>
+            #30 111-114 "2.0" : Double
+            #33 124-125 "1" : Int
+        "##]],
+    );
+}
+
+#[test]
+fn struct_cons_wrong_field_ty() {
+    check(
+        indoc! {"
+            namespace A {
+                struct Pair { First : Int, Second : Double }
+                function Foo() : Pair { new Pair { First = 1.0, Second = 2.0 } }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #19 79-81 "()" : Unit
+            #23 89-131 "{ new Pair { First = 1.0, Second = 2.0 } }" : UDT<"Pair": Item 1>
+            #25 91-129 "new Pair { First = 1.0, Second = 2.0 }" : UDT<"Pair": Item 1>
+            #30 110-113 "1.0" : Double
+            #33 124-127 "2.0" : Double
+            Error(Type(Error(TyMismatch("Int", "Double", Span { lo: 110, hi: 113 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn struct_cons_missing_field() {
+    check(
+        indoc! {"
+            namespace A {
+                struct Pair { First : Int, Second : Double }
+                function Foo() : Pair { new Pair { First = 1 } }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #19 79-81 "()" : Unit
+            #23 89-115 "{ new Pair { First = 1 } }" : UDT<"Pair": Item 1>
+            #25 91-113 "new Pair { First = 1 }" : UDT<"Pair": Item 1>
+            #30 110-111 "1" : Int
+            Error(Type(Error(MissingField("Second", Span { lo: 95, hi: 99 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn struct_cons_duplicate_field() {
+    check(
+        indoc! {"
+            namespace A {
+                struct Pair { First : Int, Second : Double }
+                function Foo() : Pair { new Pair { First = 1, First = 2, Second = 2.0 } }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #19 79-81 "()" : Unit
+            #23 89-140 "{ new Pair { First = 1, First = 2, Second = 2.0 } }" : UDT<"Pair": Item 1>
+            #25 91-138 "new Pair { First = 1, First = 2, Second = 2.0 }" : UDT<"Pair": Item 1>
+            #30 110-111 "1" : Int
+            #33 121-122 "2" : Int
+            #36 133-136 "2.0" : Double
+            Error(Type(Error(DuplicateField("First", Span { lo: 113, hi: 118 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn struct_cons_unknown_field() {
+    check(
+        indoc! {"
+            namespace A {
+                struct Pair { First : Int, Second : Double }
+                function Foo() : Pair { new Pair { First = 1, Second = 2.0, Third = 3 } }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #19 79-81 "()" : Unit
+            #23 89-140 "{ new Pair { First = 1, Second = 2.0, Third = 3 } }" : UDT<"Pair": Item 1>
+            #25 91-138 "new Pair { First = 1, Second = 2.0, Third = 3 }" : UDT<"Pair": Item 1>
+            #30 110-111 "1" : Int
+            #33 122-125 "2.0" : Double
+            #36 135-136 "3" : Int
+            Error(Type(Error(UnknownStructField("Pair", "Third", Span { lo: 127, hi: 132 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn struct_copy() {
+    check(
+        indoc! {"
+            namespace A {
+                struct Pair { First : Int, Second : Double }
+                function Foo(p : Pair) : Pair { new Pair { ...p, Second = 2.0 } }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #19 79-89 "(p : Pair)" : UDT<"Pair": Item 1>
+            #20 80-88 "p : Pair" : UDT<"Pair": Item 1>
+            #28 97-132 "{ new Pair { ...p, Second = 2.0 } }" : UDT<"Pair": Item 1>
+            #30 99-130 "new Pair { ...p, Second = 2.0 }" : UDT<"Pair": Item 1>
+            #33 113-114 "p" : UDT<"Pair": Item 1>
+            #38 125-128 "2.0" : Double
+        "##]],
+    );
+}
+
+#[test]
+fn struct_copy_wrong_ty() {
+    check(
+        indoc! {"
+            namespace A {
+                struct Pair { First : Int, Second : Double }
+                function Foo() : Pair { new Pair { ...(1, 2.0) } }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #19 79-81 "()" : Unit
+            #23 89-117 "{ new Pair { ...(1, 2.0) } }" : UDT<"Pair": Item 1>
+            #25 91-115 "new Pair { ...(1, 2.0) }" : UDT<"Pair": Item 1>
+            #28 105-113 "(1, 2.0)" : (Int, Double)
+            #29 106-107 "1" : Int
+            #30 109-112 "2.0" : Double
+            Error(Type(Error(TyMismatch("Pair", "(Int, Double)", Span { lo: 105, hi: 113 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn struct_cons_newtype_with_unnamed_items() {
+    check(
+        indoc! {"
+            namespace A {
+                newtype Pair = (Int, Second : Double);
+                function Foo() : Pair { new Pair { Second = 2.0 } }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #18 73-75 "()" : Unit
+            #22 83-112 "{ new Pair { Second = 2.0 } }" : UDT<"Pair": Item 1>
+            #24 85-110 "new Pair { Second = 2.0 }" : UDT<"Pair": Item 1>
+            #29 105-108 "2.0" : Double
+            Error(Type(Error(NotStruct("Pair", Span { lo: 89, hi: 93 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn struct_cons_prim_ty() {
+    check(
+        "",
+        "new Int { First = 1 }",
+        &expect![[r##"
+        #1 0-21 "new Int { First = 1 }" : ?
+        #6 18-19 "1" : Int
+        Error(Type(Error(NotStruct("Int", Span { lo: 4, hi: 7 }))))
+    "##]],
+    );
+}
+
+#[test]
+fn struct_field_access() {
+    check(
+        indoc! {"
+            namespace A {
+                struct Pair { First : Int, Second : Double }
+                function Foo(p : Pair) : Double { p::Second }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #19 79-89 "(p : Pair)" : UDT<"Pair": Item 1>
+            #20 80-88 "p : Pair" : UDT<"Pair": Item 1>
+            #28 99-112 "{ p::Second }" : Double
+            #30 101-110 "p::Second" : Double
+            #31 101-102 "p" : UDT<"Pair": Item 1>
+        "##]],
+    );
+}
+
+#[test]
+fn enum_variant_cons() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo() : (Shape, Shape, Double -> Shape) { (Shape.Circle(1.0), Shape.Empty, Shape.Circle) }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #16 71-73 "()" : Unit
+            #31 108-158 "{ (Shape.Circle(1.0), Shape.Empty, Shape.Circle) }" : (UDT<"Shape": Item 1>, UDT<"Shape": Item 1>, (Double -> UDT<"Shape": Item 1>))
+            #33 110-156 "(Shape.Circle(1.0), Shape.Empty, Shape.Circle)" : (UDT<"Shape": Item 1>, UDT<"Shape": Item 1>, (Double -> UDT<"Shape": Item 1>))
+            #34 111-128 "Shape.Circle(1.0)" : UDT<"Shape": Item 1>
+            #35 111-123 "Shape.Circle" : (Double -> UDT<"Shape": Item 1>)
+            #39 123-128 "(1.0)" : Double
+            #40 124-127 "1.0" : Double
+            #41 130-141 "Shape.Empty" : UDT<"Shape": Item 1>
+            #45 143-155 "Shape.Circle" : (Double -> UDT<"Shape": Item 1>)
+        "##]],
+    );
+}
+
+#[test]
+fn enum_variant_cons_wrong_payload_ty() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo() : Shape { Shape.Circle(1) }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #16 71-73 "()" : Unit
+            #20 82-101 "{ Shape.Circle(1) }" : UDT<"Shape": Item 1>
+            #22 84-99 "Shape.Circle(1)" : UDT<"Shape": Item 1>
+            #23 84-96 "Shape.Circle" : (Double -> UDT<"Shape": Item 1>)
+            #27 96-99 "(1)" : Int
+            #28 97-98 "1" : Int
+            Error(Type(Error(TyMismatch("Double", "Int", Span { lo: 84, hi: 99 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn enum_unknown_variant() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo() : Shape { Shape.Square }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #16 71-73 "()" : Unit
+            #20 82-98 "{ Shape.Square }" : ?
+            #22 84-96 "Shape.Square" : ?
+            Error(Type(Error(NotVariant("Shape", "Square", Span { lo: 90, hi: 96 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn enum_match() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo(s : Shape) : Double {
+                    match s {
+                        Shape.Circle(r) => r,
+                        Shape.Empty => 0.0,
+                    }
+                }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #16 71-82 "(s : Shape)" : UDT<"Shape": Item 1>
+            #17 72-81 "s : Shape" : UDT<"Shape": Item 1>
+            #25 92-193 "{\n        match s {\n            Shape.Circle(r) => r,\n            Shape.Empty => 0.0,\n        }\n    }" : Double
+            #27 102-187 "match s {\n            Shape.Circle(r) => r,\n            Shape.Empty => 0.0,\n        }" : Double
+            #28 108-109 "s" : UDT<"Shape": Item 1>
+            #32 124-139 "Shape.Circle(r)" : UDT<"Shape": Item 1>
+            #36 136-139 "(r)" : Double
+            #37 137-138 "r" : Double
+            #39 143-144 "r" : Double
+            #43 158-169 "Shape.Empty" : UDT<"Shape": Item 1>
+            #47 173-176 "0.0" : Double
+        "##]],
+    );
+}
+
+#[test]
+fn enum_match_non_exhaustive() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Square(Double), Empty }
+                function Foo(s : Shape) : Double {
+                    match s {
+                        Shape.Circle(r) => r,
+                    }
+                }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #21 87-98 "(s : Shape)" : UDT<"Shape": Item 1>
+            #22 88-97 "s : Shape" : UDT<"Shape": Item 1>
+            #30 108-177 "{\n        match s {\n            Shape.Circle(r) => r,\n        }\n    }" : Double
+            #32 118-171 "match s {\n            Shape.Circle(r) => r,\n        }" : Double
+            #33 124-125 "s" : UDT<"Shape": Item 1>
+            #37 140-155 "Shape.Circle(r)" : UDT<"Shape": Item 1>
+            #41 152-155 "(r)" : Double
+            #42 153-154 "r" : Double
+            #44 159-160 "r" : Double
+            Error(Type(Error(NonExhaustiveMatch("`Square`, `Empty`", Span { lo: 118, hi: 171 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn enum_match_duplicate_arm() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo(s : Shape) : Double {
+                    match s {
+                        Shape.Empty => 0.0,
+                        Shape.Circle(r) => r,
+                        Shape.Empty => 1.0,
+                    }
+                }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #16 71-82 "(s : Shape)" : UDT<"Shape": Item 1>
+            #17 72-81 "s : Shape" : UDT<"Shape": Item 1>
+            #25 92-225 "{\n        match s {\n            Shape.Empty => 0.0,\n            Shape.Circle(r) => r,\n            Shape.Empty => 1.0,\n        }\n    }" : Double
+            #27 102-219 "match s {\n            Shape.Empty => 0.0,\n            Shape.Circle(r) => r,\n            Shape.Empty => 1.0,\n        }" : Double
+            #28 108-109 "s" : UDT<"Shape": Item 1>
+            #32 124-135 "Shape.Empty" : UDT<"Shape": Item 1>
+            #36 139-142 "0.0" : Double
+            #38 156-171 "Shape.Circle(r)" : UDT<"Shape": Item 1>
+            #42 168-171 "(r)" : Double
+            #43 169-170 "r" : Double
+            #45 175-176 "r" : Double
+            #49 190-201 "Shape.Empty" : UDT<"Shape": Item 1>
+            #53 205-208 "1.0" : Double
+            Error(Type(Error(UnreachableArm(Span { lo: 190, hi: 201 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn enum_match_arm_after_discard() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo(s : Shape) : Double {
+                    match s {
+                        _ => 0.0,
+                        Shape.Empty => 1.0,
+                    }
+                }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #16 71-82 "(s : Shape)" : UDT<"Shape": Item 1>
+            #17 72-81 "s : Shape" : UDT<"Shape": Item 1>
+            #25 92-181 "{\n        match s {\n            _ => 0.0,\n            Shape.Empty => 1.0,\n        }\n    }" : Double
+            #27 102-175 "match s {\n            _ => 0.0,\n            Shape.Empty => 1.0,\n        }" : Double
+            #28 108-109 "s" : UDT<"Shape": Item 1>
+            #32 124-125 "_" : UDT<"Shape": Item 1>
+            #33 129-132 "0.0" : Double
+            #35 146-157 "Shape.Empty" : UDT<"Shape": Item 1>
+            #39 161-164 "1.0" : Double
+            Error(Type(Error(UnreachableArm(Span { lo: 146, hi: 157 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn enum_match_wrong_enum() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                enum Color { Red, Green }
+                function Foo(s : Shape) : Double {
+                    match s {
+                        Color.Red => 0.0,
+                        _ => 1.0,
+                    }
+                }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #23 101-112 "(s : Shape)" : UDT<"Shape": Item 1>
+            #24 102-111 "s : Shape" : UDT<"Shape": Item 1>
+            #32 122-209 "{\n        match s {\n            Color.Red => 0.0,\n            _ => 1.0,\n        }\n    }" : Double
+            #34 132-203 "match s {\n            Color.Red => 0.0,\n            _ => 1.0,\n        }" : Double
+            #35 138-139 "s" : UDT<"Shape": Item 1>
+            #39 154-163 "Color.Red" : UDT<"Shape": Item 1>
+            #43 167-170 "0.0" : Double
+            #45 184-185 "_" : UDT<"Shape": Item 1>
+            #46 189-192 "1.0" : Double
+            Error(Type(Error(TyMismatch("Shape", "Color", Span { lo: 154, hi: 163 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn enum_match_payload_on_nullary_variant() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo(s : Shape) : Double {
+                    match s {
+                        Shape.Circle(r) => r,
+                        Shape.Empty(x) => x,
+                    }
+                }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #16 71-82 "(s : Shape)" : UDT<"Shape": Item 1>
+            #17 72-81 "s : Shape" : UDT<"Shape": Item 1>
+            #25 92-194 "{\n        match s {\n            Shape.Circle(r) => r,\n            Shape.Empty(x) => x,\n        }\n    }" : Double
+            #27 102-188 "match s {\n            Shape.Circle(r) => r,\n            Shape.Empty(x) => x,\n        }" : Double
+            #28 108-109 "s" : UDT<"Shape": Item 1>
+            #32 124-139 "Shape.Circle(r)" : UDT<"Shape": Item 1>
+            #36 136-139 "(r)" : Double
+            #37 137-138 "r" : Double
+            #39 143-144 "r" : Double
+            #43 158-172 "Shape.Empty(x)" : UDT<"Shape": Item 1>
+            #47 169-172 "(x)" : Double
+            #48 170-171 "x" : Double
+            #50 176-177 "x" : Double
+            Error(Type(Error(NoVariantPayload("Empty", Span { lo: 169, hi: 172 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn enum_match_arm_body_ty_mismatch() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo(s : Shape) : Double {
+                    match s {
+                        Shape.Circle(r) => r,
+                        Shape.Empty => 0,
+                    }
+                }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #16 71-82 "(s : Shape)" : UDT<"Shape": Item 1>
+            #17 72-81 "s : Shape" : UDT<"Shape": Item 1>
+            #25 92-191 "{\n        match s {\n            Shape.Circle(r) => r,\n            Shape.Empty => 0,\n        }\n    }" : Double
+            #27 102-185 "match s {\n            Shape.Circle(r) => r,\n            Shape.Empty => 0,\n        }" : Double
+            #28 108-109 "s" : UDT<"Shape": Item 1>
+            #32 124-139 "Shape.Circle(r)" : UDT<"Shape": Item 1>
+            #36 136-139 "(r)" : Double
+            #37 137-138 "r" : Double
+            #39 143-144 "r" : Double
+            #43 158-169 "Shape.Empty" : UDT<"Shape": Item 1>
+            #47 173-174 "0" : Int
+            Error(Type(Error(TyMismatch("Double", "Int", Span { lo: 173, hi: 174 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn enum_unwrap() {
+    check(
+        indoc! {"
+            namespace A {
+                enum Shape { Circle(Double), Empty }
+                function Foo(s : Shape) : Double { s! }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #16 71-82 "(s : Shape)" : UDT<"Shape": Item 1>
+            #17 72-81 "s : Shape" : UDT<"Shape": Item 1>
+            #25 92-98 "{ s! }" : Double
+            #27 94-96 "s!" : Double
+            #28 94-95 "s" : UDT<"Shape": Item 1>
+            Error(Type(Error(MissingClassUnwrap("Shape", Span { lo: 94, hi: 95 }))))
+        "##]],
+    );
+}
+
 #[test]
 fn unknown_name_fits_any_ty() {
     check(
@@ -3781,3 +4408,445 @@ fn lambda_on_array_where_item_used_in_call_should_be_inferred() {
         "##]],
     );
 }
+
+#[test]
+fn ty_param_with_class_bound_supports_class_operation() {
+    check(
+        indoc! {"
+            namespace A {
+                function Sum<'T : Add>(xs : 'T[], zero : 'T) : 'T {
+                    mutable acc = zero;
+                    for x in xs {
+                        set acc += x;
+                    }
+                    acc
+                }
+            }
+        "},
+        "A.Sum([1.0, 2.0], 0.0)",
+        &expect![[r##"
+            #8 40-62 "(xs : 'T[], zero : 'T)" : (Param<"'T": 0>[], Param<"'T": 0>)
+            #9 41-50 "xs : 'T[]" : Param<"'T": 0>[]
+            #14 52-61 "zero : 'T" : Param<"'T": 0>
+            #20 68-173 "{\n        mutable acc = zero;\n        for x in xs {\n            set acc += x;\n        }\n        acc\n    }" : Param<"'T": 0>
+            #22 86-89 "acc" : Param<"'T": 0>
+            #24 92-96 "zero" : Param<"'T": 0>
+            #28 106-155 "for x in xs {\n            set acc += x;\n        }" : Unit
+            #29 110-111 "x" : Param<"'T": 0>
+            #31 115-117 "xs" : Param<"'T": 0>[]
+            #34 118-155 "{\n            set acc += x;\n        }" : Unit
+            #36 132-144 "set acc += x" : Unit
+            #37 136-139 "acc" : Param<"'T": 0>
+            #40 143-144 "x" : Param<"'T": 0>
+            #44 164-167 "acc" : Param<"'T": 0>
+            #47 176-198 "A.Sum([1.0, 2.0], 0.0)" : Double
+            #48 176-181 "A.Sum" : ((Double[], Double) -> Double)
+            #52 181-198 "([1.0, 2.0], 0.0)" : (Double[], Double)
+            #53 182-192 "[1.0, 2.0]" : Double[]
+            #54 183-186 "1.0" : Double
+            #55 188-191 "2.0" : Double
+            #56 194-197 "0.0" : Double
+        "##]],
+    );
+}
+
+#[test]
+fn ty_param_without_class_bound_does_not_support_class_operation() {
+    check(
+        indoc! {"
+            namespace A {
+                function Double<'T>(x : 'T) : 'T { x + x }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #7 37-45 "(x : 'T)" : Param<"'T": 0>
+            #8 38-44 "x : 'T" : Param<"'T": 0>
+            #14 51-60 "{ x + x }" : Param<"'T": 0>
+            #16 53-58 "x + x" : Param<"'T": 0>
+            #17 53-54 "x" : Param<"'T": 0>
+            #20 57-58 "x" : Param<"'T": 0>
+            Error(Type(Error(MissingClassAdd("'T", Span { lo: 53, hi: 54 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn ty_param_class_bound_checked_at_call() {
+    check(
+        indoc! {"
+            namespace A {
+                function Same<'T : Eq>(x : 'T, y : 'T) : Bool { x == y }
+                function F() : Unit {}
+            }
+        "},
+        "A.Same(Zero, One) or A.Same(A.F, A.F)",
+        &expect![[r##"
+            #8 40-56 "(x : 'T, y : 'T)" : (Param<"'T": 0>, Param<"'T": 0>)
+            #9 41-47 "x : 'T" : Param<"'T": 0>
+            #13 49-55 "y : 'T" : Param<"'T": 0>
+            #20 64-74 "{ x == y }" : Bool
+            #22 66-72 "x == y" : Bool
+            #23 66-67 "x" : Param<"'T": 0>
+            #26 71-72 "y" : Param<"'T": 0>
+            #32 89-91 "()" : Unit
+            #36 99-101 "{}" : Unit
+            #37 104-141 "A.Same(Zero, One) or A.Same(A.F, A.F)" : Bool
+            #38 104-121 "A.Same(Zero, One)" : Bool
+            #39 104-110 "A.Same" : ((Result, Result) -> Bool)
+            #43 110-121 "(Zero, One)" : (Result, Result)
+            #44 111-115 "Zero" : Result
+            #45 117-120 "One" : Result
+            #46 125-141 "A.Same(A.F, A.F)" : Bool
+            #47 125-131 "A.Same" : (((Unit -> Unit), (Unit -> Unit)) -> Bool)
+            #51 131-141 "(A.F, A.F)" : ((Unit -> Unit), (Unit -> Unit))
+            #52 132-135 "A.F" : (Unit -> Unit)
+            #56 137-140 "A.F" : (Unit -> Unit)
+            Error(Type(Error(MissingClassEq("(Unit -> Unit)", Span { lo: 125, hi: 141 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn ty_param_class_bound_implies_weaker_classes() {
+    check(
+        indoc! {"
+            namespace A {
+                function Average<'T : Num>(x : 'T, y : 'T) : String {
+                    $\"{(x + y) / y}\"
+                }
+                function Twice<'T : Integral>(x : 'T) : String { Average(x, x) }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #8 44-60 "(x : 'T, y : 'T)" : (Param<"'T": 0>, Param<"'T": 0>)
+            #9 45-51 "x : 'T" : Param<"'T": 0>
+            #13 53-59 "y : 'T" : Param<"'T": 0>
+            #20 70-102 "{\n        $\"{(x + y) / y}\"\n    }" : String
+            #22 80-96 "$\"{(x + y) / y}\"" : String
+            #23 83-94 "(x + y) / y" : Param<"'T": 0>
+            #24 83-90 "(x + y)" : Param<"'T": 0>
+            #25 84-89 "x + y" : Param<"'T": 0>
+            #26 84-85 "x" : Param<"'T": 0>
+            #29 88-89 "y" : Param<"'T": 0>
+            #32 93-94 "y" : Param<"'T": 0>
+            #40 136-144 "(x : 'T)" : Param<"'T": 0>
+            #41 137-143 "x : 'T" : Param<"'T": 0>
+            #48 154-171 "{ Average(x, x) }" : String
+            #50 156-169 "Average(x, x)" : String
+            #51 156-163 "Average" : ((Param<"'T": 0>, Param<"'T": 0>) -> String)
+            #54 163-169 "(x, x)" : (Param<"'T": 0>, Param<"'T": 0>)
+            #55 164-165 "x" : Param<"'T": 0>
+            #58 167-168 "x" : Param<"'T": 0>
+        "##]],
+    );
+}
+
+#[test]
+fn ty_param_class_bound_not_implied_by_other_bound() {
+    check(
+        indoc! {"
+            namespace A {
+                function Plus<'T : Add>(x : 'T) : 'T { x + x }
+                function Same<'T : Eq>(x : 'T) : 'T { Plus(x) }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #8 41-49 "(x : 'T)" : Param<"'T": 0>
+            #9 42-48 "x : 'T" : Param<"'T": 0>
+            #15 55-64 "{ x + x }" : Param<"'T": 0>
+            #17 57-62 "x + x" : Param<"'T": 0>
+            #18 57-58 "x" : Param<"'T": 0>
+            #21 61-62 "x" : Param<"'T": 0>
+            #29 91-99 "(x : 'T)" : Param<"'T": 0>
+            #30 92-98 "x : 'T" : Param<"'T": 0>
+            #36 105-116 "{ Plus(x) }" : Param<"'T": 0>
+            #38 107-114 "Plus(x)" : Param<"'T": 0>
+            #39 107-111 "Plus" : (Param<"'T": 0> -> Param<"'T": 0>)
+            #42 111-114 "(x)" : Param<"'T": 0>
+            #43 112-113 "x" : Param<"'T": 0>
+            Error(Type(Error(MissingClassAdd("'T", Span { lo: 107, hi: 114 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn ty_param_unrecognized_class_bound() {
+    check(
+        indoc! {"
+            namespace A {
+                function Foo<'T : Ord + Eq>(x : 'T) : Bool { x == x }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #9 45-53 "(x : 'T)" : Param<"'T": 0>
+            #10 46-52 "x : 'T" : Param<"'T": 0>
+            #17 61-71 "{ x == x }" : Bool
+            #19 63-69 "x == x" : Bool
+            #20 63-64 "x" : Param<"'T": 0>
+            #23 68-69 "x" : Param<"'T": 0>
+            Error(Type(Error(UnrecognizedClass("Ord", Span { lo: 36, hi: 39 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn const_use() {
+    check(
+        indoc! {"
+            namespace A {
+                const Size : Int = 2 ^ 3;
+                function Foo() : Double[] { [0.0, size = Size] }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #9 37-42 "2 ^ 3" : Int
+            #10 37-38 "2" : Int
+            #11 41-42 "3" : Int
+            #15 60-62 "()" : Unit
+            #20 74-96 "{ [0.0, size = Size] }" : Double[]
+            #22 76-94 "[0.0, size = Size]" : Double[]
+            #23 77-80 "0.0" : Double
+            #24 89-93 "Size" : Int
+        "##]],
+    );
+}
+
+#[test]
+fn const_value_ty_mismatch() {
+    check(
+        indoc! {"
+            namespace A {
+                const Size : Int = 1.0;
+            }
+        "},
+        "",
+        &expect![[r##"
+            #9 37-40 "1.0" : Double
+            Error(Type(Error(TyMismatch("Int", "Double", Span { lo: 37, hi: 40 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn call_omits_defaulted_args() {
+    check(
+        indoc! {"
+            namespace A {
+                function Foo(x : Int, y : Int = 2, z : Double = 0.5) : Int { x + y }
+                function Bar() : Int { Foo(1) + Foo(1, 3) + Foo(1, 3, 1.5) }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #6 30-70 "(x : Int, y : Int = 2, z : Double = 0.5)" : (Int, Int, Double)
+            #7 31-38 "x : Int" : Int
+            #12 40-47 "y : Int" : Int
+            #17 53-63 "z : Double" : Double
+            #22 50-51 "2" : Int
+            #23 66-69 "0.5" : Double
+            #27 77-86 "{ x + y }" : Int
+            #29 79-84 "x + y" : Int
+            #30 79-80 "x" : Int
+            #33 83-84 "y" : Int
+            #39 103-105 "()" : Unit
+            #43 112-151 "{ Foo(1) + Foo(1, 3) + Foo(1, 3, 1.5) }" : Int
+            #45 114-149 "Foo(1) + Foo(1, 3) + Foo(1, 3, 1.5)" : Int
+            #46 114-132 "Foo(1) + Foo(1, 3)" : Int
+            #47 114-120 "Foo(1)" : Int
+            #48 114-117 "Foo" : ((Int, Int, Double) -> Int)
+            #51 117-120 "(1)" : Int
+            #52 118-119 "1" : Int
+            #53 123-132 "Foo(1, 3)" : Int
+            #54 123-126 "Foo" : ((Int, Int, Double) -> Int)
+            #57 126-132 "(1, 3)" : (Int, Int)
+            #58 127-128 "1" : Int
+            #59 130-131 "3" : Int
+            #60 135-149 "Foo(1, 3, 1.5)" : Int
+            #61 135-138 "Foo" : ((Int, Int, Double) -> Int)
+            #64 138-149 "(1, 3, 1.5)" : (Int, Int, Double)
+            #65 139-140 "1" : Int
+            #66 142-143 "3" : Int
+            #67 145-148 "1.5" : Double
+        "##]],
+    );
+}
+
+#[test]
+fn call_omits_required_arg_error() {
+    check(
+        indoc! {"
+            namespace A {
+                function Foo(x : Int, y : Int = 2) : Int { x + y }
+                function Bar() : Int { Foo() }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #6 30-52 "(x : Int, y : Int = 2)" : (Int, Int)
+            #7 31-38 "x : Int" : Int
+            #12 40-47 "y : Int" : Int
+            #17 50-51 "2" : Int
+            #21 59-68 "{ x + y }" : Int
+            #23 61-66 "x + y" : Int
+            #24 61-62 "x" : Int
+            #27 65-66 "y" : Int
+            #33 85-87 "()" : Unit
+            #37 94-103 "{ Foo() }" : Int
+            #39 96-101 "Foo()" : Int
+            #40 96-99 "Foo" : ((Int, Int) -> Int)
+            #43 99-101 "()" : Unit
+            Error(Type(Error(TyMismatch("(Int, Int)", "Unit", Span { lo: 96, hi: 101 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn default_value_ty_mismatch() {
+    check(
+        indoc! {"
+            namespace A {
+                function Foo(x : Int, y : Int = 2.0) : Int { x + y }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #6 30-54 "(x : Int, y : Int = 2.0)" : (Int, Int)
+            #7 31-38 "x : Int" : Int
+            #12 40-47 "y : Int" : Int
+            #17 50-53 "2.0" : Double
+            #21 61-70 "{ x + y }" : Int
+            #23 63-68 "x + y" : Int
+            #24 63-64 "x" : Int
+            #27 67-68 "y" : Int
+            Error(Type(Error(TyMismatch("Int", "Double", Span { lo: 50, hi: 53 }))))
+        "##]],
+    );
+}
+
+#[test]
+fn default_value_uses_earlier_param() {
+    check(
+        indoc! {"
+            namespace A {
+                function Foo<'T>(xs : 'T[], n : Int = Length(xs)) : Int { n }
+                function Length<'T>(xs : 'T[]) : Int { 0 }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #7 34-67 "(xs : 'T[], n : Int = Length(xs))" : (Param<"'T": 0>[], Int)
+            #8 35-44 "xs : 'T[]" : Param<"'T": 0>[]
+            #13 46-53 "n : Int" : Int
+            #18 56-66 "Length(xs)" : Int
+            #19 56-62 "Length" : (Param<"'T": 0>[] -> Int)
+            #22 62-66 "(xs)" : Param<"'T": 0>[]
+            #23 63-65 "xs" : Param<"'T": 0>[]
+            #29 74-79 "{ n }" : Int
+            #31 76-77 "n" : Int
+            #38 103-114 "(xs : 'T[])" : Param<"'T": 0>[]
+            #39 104-113 "xs : 'T[]" : Param<"'T": 0>[]
+            #47 121-126 "{ 0 }" : Int
+            #49 123-124 "0" : Int
+        "##]],
+    );
+}
+
+#[test]
+fn functor_and_partial_app_calls_omit_defaulted_args() {
+    check(
+        indoc! {"
+            namespace A {
+                operation Foo(q : Qubit, theta : Double = 1.0) : Unit is Adj + Ctl {}
+                operation Bar(qs : Qubit[]) : Unit {
+                    Controlled Adjoint Foo(qs, qs[0]);
+                    let f = Foo(_);
+                    f(qs[0]);
+                }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #6 31-64 "(q : Qubit, theta : Double = 1.0)" : (Qubit, Double)
+            #7 32-41 "q : Qubit" : Qubit
+            #12 43-57 "theta : Double" : Double
+            #17 60-63 "1.0" : Double
+            #24 85-87 "{}" : Unit
+            #28 105-119 "(qs : Qubit[])" : Qubit[]
+            #29 106-118 "qs : Qubit[]" : Qubit[]
+            #38 127-219 "{\n        Controlled Adjoint Foo(qs, qs[0]);\n        let f = Foo(_);\n        f(qs[0]);\n    }" : Unit
+            #40 137-170 "Controlled Adjoint Foo(qs, qs[0])" : Unit
+            #41 137-159 "Controlled Adjoint Foo" : ((Qubit[], (Qubit, Double)) => Unit is Adj + Ctl)
+            #42 148-159 "Adjoint Foo" : ((Qubit, Double) => Unit is Adj + Ctl)
+            #43 156-159 "Foo" : ((Qubit, Double) => Unit is Adj + Ctl)
+            #46 159-170 "(qs, qs[0])" : (Qubit[], Qubit)
+            #47 160-162 "qs" : Qubit[]
+            #50 164-169 "qs[0]" : Qubit
+            #51 164-166 "qs" : Qubit[]
+            #54 167-168 "0" : Int
+            #56 184-185 "f" : (Qubit => Unit is Adj + Ctl)
+            #58 188-194 "Foo(_)" : (Qubit => Unit is Adj + Ctl)
+            #59 188-191 "Foo" : ((Qubit, Double) => Unit is Adj + Ctl)
+            #62 191-194 "(_)" : Qubit
+            #63 192-193 "_" : Qubit
+            #65 204-212 "f(qs[0])" : Unit
+            #66 204-205 "f" : (Qubit => Unit is Adj + Ctl)
+            #69 205-212 "(qs[0])" : Qubit
+            #70 206-211 "qs[0]" : Qubit
+            #71 206-208 "qs" : Qubit[]
+            #74 209-210 "0" : Int
+        "##]],
+    );
+}
+
+#[test]
+fn update_field_path() {
+    check(
+        indoc! {"
+            namespace A {
+                newtype Noise = (Rate : Double, Kind : Int);
+                newtype Config = (Noise : Noise, Shots : Int);
+                function Foo(config : Config) : Config { config w/ Noise::Rate <- 0.1 }
+            }
+        "},
+        "",
+        &expect![[r##"
+            #32 130-147 "(config : Config)" : UDT<"Config": Item 2>
+            #33 131-146 "config : Config" : UDT<"Config": Item 2>
+            #41 157-189 "{ config w/ Noise::Rate <- 0.1 }" : UDT<"Config": Item 2>
+            #43 159-187 "config w/ Noise::Rate <- 0.1" : UDT<"Config": Item 2>
+            #44 159-165 "config" : UDT<"Config": Item 2>
+            #47 169-180 "Noise::Rate" : ?
+            #48 169-174 "Noise" : UDT<"Noise": Item 1>
+            #52 184-187 "0.1" : Double
+        "##]],
+    );
+}
+
+#[test]
+fn update_index_path_ty_mismatch() {
+    check(
+        "",
+        "[[1, 2], [3, 4]] w/ [1][0] <- 0.5",
+        &expect![[r##"
+        #1 0-33 "[[1, 2], [3, 4]] w/ [1][0] <- 0.5" : Int[][]
+        #2 0-16 "[[1, 2], [3, 4]]" : Int[][]
+        #3 1-7 "[1, 2]" : Int[]
+        #4 2-3 "1" : Int
+        #5 5-6 "2" : Int
+        #6 9-15 "[3, 4]" : Int[]
+        #7 10-11 "3" : Int
+        #8 13-14 "4" : Int
+        #9 20-26 "[1][0]" : ?
+        #10 20-23 "[1]" : Int[]
+        #11 21-22 "1" : Int
+        #12 24-25 "0" : Int
+        #13 30-33 "0.5" : Double
+        Error(Type(Error(TyMismatch("Int", "Double", Span { lo: 0, hi: 33 }))))
+    "##]],
+    );
+}
@@ -21,6 +21,7 @@ pub enum Kind {
     Namespace,
     Ty(Ty),
     Term(Term),
+    Const(Const),
 }
 
 pub struct Ty {
@@ -33,6 +34,10 @@ pub struct Term {
     pub intrinsic: bool,
 }
 
+pub struct Const {
+    pub id: ItemId,
+}
+
 #[derive(Default)]
 pub struct Table {
     tys: FxHashMap<Rc<str>, FxHashMap<Rc<str>, Ty>>,
@@ -68,7 +73,7 @@ impl FromIterator<Global> for Table {
                         .or_default()
                         .insert(global.name, term);
                 }
-                Kind::Namespace => {}
+                Kind::Namespace | Kind::Const(_) => {}
             }
         }
 
@@ -93,17 +98,50 @@ impl PackageIter<'_> {
                 .expect("parent should exist")
                 .kind
         });
+
+        match (&item.kind, &parent) {
+            (ItemKind::Export(name, target), Some(ItemKind::Namespace(namespace, _))) => {
+                // Only items from the same package can be exported.
+                if target.package.is_some() {
+                    return None;
+                }
+                let target = self.package.items.get(target.item)?;
+                self.decl_global(&namespace.name, Some(&name.name), item.visibility, target)
+            }
+            (_, Some(ItemKind::Namespace(namespace, _))) => {
+                self.decl_global(&namespace.name, None, item.visibility, item)
+            }
+            (ItemKind::Namespace(ident, _), None) => Some(Global {
+                namespace: "".into(),
+                name: Rc::clone(&ident.name),
+                visibility: Visibility::Public,
+                status: ItemStatus::from_attrs(item.attrs.as_ref()),
+                kind: Kind::Namespace,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Creates the globals for a callable or type declaration in the given namespace, under the
+    /// given name if it is exported under an alias.
+    fn decl_global(
+        &mut self,
+        namespace: &Rc<str>,
+        name: Option<&Rc<str>>,
+        visibility: Visibility,
+        item: &Item,
+    ) -> Option<Global> {
         let id = ItemId {
             package: self.id,
             item: item.id,
         };
         let status = ItemStatus::from_attrs(item.attrs.as_ref());
 
-        match (&item.kind, &parent) {
-            (ItemKind::Callable(decl), Some(ItemKind::Namespace(namespace, _))) => Some(Global {
-                namespace: Rc::clone(&namespace.name),
-                name: Rc::clone(&decl.name.name),
-                visibility: item.visibility,
+        match &item.kind {
+            ItemKind::Callable(decl) => Some(Global {
+                namespace: Rc::clone(namespace),
+                name: Rc::clone(name.unwrap_or(&decl.name.name)),
+                visibility,
                 status,
                 kind: Kind::Term(Term {
                     id,
@@ -111,11 +149,13 @@ impl PackageIter<'_> {
                     intrinsic: decl.body.body == SpecBody::Gen(SpecGen::Intrinsic),
                 }),
             }),
-            (ItemKind::Ty(name, def), Some(ItemKind::Namespace(namespace, _))) => {
-                self.next = Some(Global {
-                    namespace: Rc::clone(&namespace.name),
-                    name: Rc::clone(&name.name),
-                    visibility: item.visibility,
+            ItemKind::Ty(ty_name, def) => {
+                let name = name.unwrap_or(&ty_name.name);
+                // Enums are constructed through their variants rather than a constructor.
+                self.next = def.variants().is_none().then(|| Global {
+                    namespace: Rc::clone(namespace),
+                    name: Rc::clone(name),
+                    visibility,
                     status,
                     kind: Kind::Term(Term {
                         id,
@@ -125,21 +165,21 @@ impl PackageIter<'_> {
                 });
 
                 Some(Global {
-                    namespace: Rc::clone(&namespace.name),
-                    name: Rc::clone(&name.name),
-                    visibility: item.visibility,
+                    namespace: Rc::clone(namespace),
+                    name: Rc::clone(name),
+                    visibility,
                     status,
                     kind: Kind::Ty(Ty { id }),
                 })
             }
-            (ItemKind::Namespace(ident, _), None) => Some(Global {
-                namespace: "".into(),
-                name: Rc::clone(&ident.name),
-                visibility: Visibility::Public,
+            ItemKind::Const(const_name, _) => Some(Global {
+                namespace: Rc::clone(namespace),
+                name: Rc::clone(name.unwrap_or(&const_name.name)),
+                visibility,
                 status,
-                kind: Kind::Namespace,
+                kind: Kind::Const(Const { id }),
             }),
-            _ => None,
+            ItemKind::Namespace(..) | ItemKind::Export(..) => None,
         }
     }
 }
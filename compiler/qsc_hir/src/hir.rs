@@ -336,6 +336,10 @@ pub enum ItemKind {
     Namespace(Ident, Vec<LocalItemId>),
     /// A `newtype` declaration.
     Ty(Ident, Udt),
+    /// An exported item, visible in the parent namespace under the given name.
+    Export(Ident, ItemId),
+    /// A `const` declaration with its value, which is replaced by a literal once evaluated.
+    Const(Ident, Expr),
 }
 
 impl Display for ItemKind {
@@ -356,6 +360,8 @@ impl Display for ItemKind {
                 }
             }
             ItemKind::Ty(name, udt) => write!(f, "Type ({name}): {udt}"),
+            ItemKind::Export(name, item) => write!(f, "Export ({name}): {item}"),
+            ItemKind::Const(name, value) => write!(f, "Const ({name}): {value}"),
         }
     }
 }
@@ -375,6 +381,11 @@ pub struct CallableDecl {
     pub generics: Vec<GenericParam>,
     /// The input to the callable.
     pub input: Pat,
+    /// The default values of the trailing parameters in the input.
+    pub defaults: Vec<Expr>,
+    /// The callables that compute the default values, each taking the parameters before its own as
+    /// a tuple. Calls that omit arguments call these instead of using the default values directly.
+    pub default_items: Vec<LocalItemId>,
     /// The return type of the callable.
     pub output: Ty,
     /// The functors supported by the callable.
@@ -424,6 +435,14 @@ impl Display for CallableDecl {
             indent = set_indentation(indent, 1);
         }
         write!(indent, "\ninput: {}", self.input)?;
+        if !self.defaults.is_empty() {
+            write!(indent, "\ndefaults:")?;
+            indent = set_indentation(indent, 2);
+            for (default, item) in self.defaults.iter().zip(&self.default_items) {
+                write!(indent, "\nItem {item}: {default}")?;
+            }
+            indent = set_indentation(indent, 1);
+        }
         write!(indent, "\noutput: {}", self.output)?;
         write!(indent, "\nfunctors: {}", self.functors)?;
         write!(indent, "\nbody: {}", self.body)?;
@@ -621,12 +640,16 @@ pub enum ExprKind {
     BinOp(BinOp, Box<Expr>, Box<Expr>),
     /// A block: `{ ... }`.
     Block(Block),
+    /// A break out of the innermost loop: `break`.
+    Break,
     /// A call: `a(b)`.
     Call(Box<Expr>, Box<Expr>),
     /// A closure that fixes the vector of local variables as arguments to the callable item.
     Closure(Vec<NodeId>, LocalItemId),
     /// A conjugation: `within { ... } apply { ... }`.
     Conjugate(Block, Block),
+    /// A jump to the next iteration of the innermost loop: `continue`.
+    Continue,
     /// A failure: `fail "message"`.
     Fail(Box<Expr>),
     /// A field accessor: `a::F`.
@@ -686,9 +709,11 @@ impl Display for ExprKind {
             }
             ExprKind::BinOp(op, lhs, rhs) => display_bin_op(indent, *op, lhs, rhs)?,
             ExprKind::Block(block) => write!(indent, "Expr Block: {block}")?,
+            ExprKind::Break => write!(indent, "Break")?,
             ExprKind::Call(callable, arg) => display_call(indent, callable, arg)?,
             ExprKind::Closure(args, callable) => display_closure(indent, args, *callable)?,
             ExprKind::Conjugate(within, apply) => display_conjugate(indent, within, apply)?,
+            ExprKind::Continue => write!(indent, "Continue")?,
             ExprKind::Err => write!(indent, "Err")?,
             ExprKind::Fail(e) => write!(indent, "Fail: {e}")?,
             ExprKind::Field(expr, field) => display_field(indent, expr, field)?,
@@ -1043,6 +1068,8 @@ pub enum PatKind {
     Discard,
     /// A tuple: `(a, b, c)`.
     Tuple(Vec<Pat>),
+    /// An array with an optional pattern for the remaining items: `[a, b, ...rest]`.
+    Array(Vec<Pat>, Option<Box<Pat>>),
     /// An invalid pattern.
     Err,
 }
@@ -1066,6 +1093,16 @@ impl Display for PatKind {
                     }
                 }
             }
+            PatKind::Array(ps, rest) => {
+                write!(indent, "Array:")?;
+                indent = set_indentation(indent, 1);
+                for p in ps {
+                    write!(indent, "\n{p}")?;
+                }
+                if let Some(rest) = rest {
+                    write!(indent, "\nRest: {rest}")?;
+                }
+            }
             PatKind::Err => write!(indent, "Err")?,
         }
         Ok(())
@@ -1161,6 +1198,8 @@ pub enum Attr {
     EntryPoint,
     /// Indicates that an item does not have an implementation available for use.
     Unimplemented,
+    /// Indicates that a callable should not be inlined into its callers when generating code.
+    NoInline,
 }
 
 impl FromStr for Attr {
@@ -1171,6 +1210,7 @@ impl FromStr for Attr {
             "Config" => Ok(Self::Config),
             "EntryPoint" => Ok(Self::EntryPoint),
             "Unimplemented" => Ok(Self::Unimplemented),
+            "NoInline" => Ok(Self::NoInline),
             _ => Err(()),
         }
     }
@@ -62,7 +62,13 @@ pub fn walk_item(vis: &mut impl MutVisitor, item: &mut Item) {
 
     match &mut item.kind {
         ItemKind::Callable(decl) => vis.visit_callable_decl(decl),
-        ItemKind::Namespace(name, _) | ItemKind::Ty(name, _) => vis.visit_ident(name),
+        ItemKind::Namespace(name, _) | ItemKind::Ty(name, _) | ItemKind::Export(name, _) => {
+            vis.visit_ident(name);
+        }
+        ItemKind::Const(name, value) => {
+            vis.visit_ident(name);
+            vis.visit_expr(value);
+        }
     }
 }
 
@@ -70,6 +76,7 @@ pub fn walk_callable_decl(vis: &mut impl MutVisitor, decl: &mut CallableDecl) {
     vis.visit_span(&mut decl.span);
     vis.visit_ident(&mut decl.name);
     vis.visit_pat(&mut decl.input);
+    decl.defaults.iter_mut().for_each(|d| vis.visit_expr(d));
     vis.visit_spec_decl(&mut decl.body);
     decl.adj
         .iter_mut()
@@ -197,7 +204,9 @@ pub fn walk_expr(vis: &mut impl MutVisitor, expr: &mut Expr) {
             vis.visit_expr(cond);
             vis.visit_block(block);
         }
-        ExprKind::Closure(_, _)
+        ExprKind::Break
+        | ExprKind::Closure(_, _)
+        | ExprKind::Continue
         | ExprKind::Err
         | ExprKind::Hole
         | ExprKind::Lit(_)
@@ -212,6 +221,10 @@ pub fn walk_pat(vis: &mut impl MutVisitor, pat: &mut Pat) {
         PatKind::Bind(name) => vis.visit_ident(name),
         PatKind::Discard | PatKind::Err => {}
         PatKind::Tuple(pats) => pats.iter_mut().for_each(|p| vis.visit_pat(p)),
+        PatKind::Array(pats, rest) => pats
+            .iter_mut()
+            .chain(rest.as_deref_mut())
+            .for_each(|p| vis.visit_pat(p)),
     }
 }
 
@@ -9,6 +9,7 @@ use crate::hir::{CallableKind, FieldPath, Functor, ItemId, PackageId, Res};
 use std::{
     fmt::{self, Debug, Display, Formatter, Write},
     rc::Rc,
+    str::FromStr,
 };
 
 fn set_indentation<'a, 'b>(
@@ -258,7 +259,16 @@ fn instantiate_arrow_ty<'a>(
 impl Display for GenericParam {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
-            GenericParam::Ty(name) => write!(f, "type {name}"),
+            GenericParam::Ty { name, bounds } => {
+                write!(f, "type {name}")?;
+                if let Some((first, rest)) = bounds.split_first() {
+                    write!(f, ": {first}")?;
+                    for bound in rest {
+                        write!(f, " + {bound}")?;
+                    }
+                }
+                Ok(())
+            }
             GenericParam::Functor(min) => write!(f, "functor ({min})"),
         }
     }
@@ -267,12 +277,59 @@ impl Display for GenericParam {
 /// The kind of a generic parameter.
 #[derive(Clone, Debug, PartialEq)]
 pub enum GenericParam {
-    /// A type parameter.
-    Ty(TypeParamName),
+    /// A type parameter with the classes it is bounded by.
+    Ty {
+        /// The name of the type parameter.
+        name: TypeParamName,
+        /// The classes that any type argument must belong to.
+        bounds: Vec<ClassConstraint>,
+    },
     /// A functor parameter with a lower bound.
     Functor(FunctorSetValue),
 }
 
+/// A class that bounds a generic type parameter.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ClassConstraint {
+    /// Types that support the plus operator.
+    Add,
+    /// Types that support equality comparison.
+    Eq,
+    /// Integer types.
+    Integral,
+    /// Number types.
+    Num,
+    /// Types that can be converted into a string.
+    Show,
+}
+
+impl Display for ClassConstraint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ClassConstraint::Add => f.write_str("Add"),
+            ClassConstraint::Eq => f.write_str("Eq"),
+            ClassConstraint::Integral => f.write_str("Integral"),
+            ClassConstraint::Num => f.write_str("Num"),
+            ClassConstraint::Show => f.write_str("Show"),
+        }
+    }
+}
+
+impl FromStr for ClassConstraint {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Add" => Ok(Self::Add),
+            "Eq" => Ok(Self::Eq),
+            "Integral" => Ok(Self::Integral),
+            "Num" => Ok(Self::Num),
+            "Show" => Ok(Self::Show),
+            _ => Err(()),
+        }
+    }
+}
+
 /// The name of a generic type parameter.
 #[derive(Clone, Debug, PartialEq)]
 pub struct TypeParamName {
@@ -534,12 +591,23 @@ pub struct Udt {
 }
 
 impl Udt {
+    /// The underlying type wrapped by this user-defined type. Every value of an enum has the same
+    /// underlying type: a tuple of the integer tag of its variant followed by one array per
+    /// variant, in declaration order. The array of the variant of the value holds its payload,
+    /// and every other array is empty.
     #[must_use]
     pub fn get_pure_ty(&self) -> Ty {
         fn get_pure_ty(def: &UdtDef) -> Ty {
             match &def.kind {
                 UdtDefKind::Field(field) => field.ty.clone(),
                 UdtDefKind::Tuple(tup) => Ty::Tuple(tup.iter().map(get_pure_ty).collect()),
+                UdtDefKind::Enum(variants) => Ty::Tuple(
+                    std::iter::once(Ty::Prim(Prim::Int))
+                        .chain(variants.iter().map(|variant| {
+                            Ty::Array(Box::new(variant.ty.clone().unwrap_or(Ty::UNIT)))
+                        }))
+                        .collect(),
+                ),
             }
         }
         get_pure_ty(&self.definition)
@@ -585,6 +653,7 @@ impl Udt {
                     path
                 })
             }),
+            UdtDefKind::Enum(_) => None,
         }
     }
 
@@ -621,6 +690,7 @@ impl Udt {
             UdtDefKind::Tuple(defs) => defs
                 .iter()
                 .find_map(|def| Self::find_field_by_name_rec(def, name)),
+            UdtDefKind::Enum(_) => None,
         }
     }
 
@@ -637,6 +707,43 @@ impl Udt {
     pub fn field_ty_by_name(&self, name: &str) -> Option<&Ty> {
         self.find_field_by_name(name).map(|field| &field.ty)
     }
+
+    /// The top-level fields of this user-defined type in declaration order. Returns [None] unless
+    /// every top-level field is named, which is the shape required for struct construction.
+    #[must_use]
+    pub fn struct_fields(&self) -> Option<Vec<&UdtField>> {
+        match &self.definition.kind {
+            UdtDefKind::Field(field) => field.name.is_some().then(|| vec![field]),
+            UdtDefKind::Tuple(defs) => defs
+                .iter()
+                .map(|def| match &def.kind {
+                    UdtDefKind::Field(field) if field.name.is_some() => Some(field),
+                    _ => None,
+                })
+                .collect(),
+            UdtDefKind::Enum(_) => None,
+        }
+    }
+
+    /// The variants of this user-defined type in declaration order. Returns [None] if this
+    /// user-defined type is not an enum.
+    #[must_use]
+    pub fn variants(&self) -> Option<&[UdtVariant]> {
+        match &self.definition.kind {
+            UdtDefKind::Enum(variants) => Some(variants),
+            UdtDefKind::Field(_) | UdtDefKind::Tuple(_) => None,
+        }
+    }
+
+    /// The tag and definition of the variant with the given name. Returns [None] if this
+    /// user-defined type is not an enum or does not have a variant with the given name.
+    #[must_use]
+    pub fn find_variant(&self, name: &str) -> Option<(usize, &UdtVariant)> {
+        self.variants()?
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| variant.name.as_ref() == name)
+    }
 }
 
 impl Display for Udt {
@@ -671,6 +778,8 @@ pub enum UdtDefKind {
     Field(UdtField),
     /// A tuple.
     Tuple(Vec<UdtDef>),
+    /// The variants of an enum.
+    Enum(Vec<UdtVariant>),
 }
 
 impl Display for UdtDefKind {
@@ -693,6 +802,34 @@ impl Display for UdtDefKind {
                     }
                 }
             }
+            UdtDefKind::Enum(variants) => {
+                write!(indent, "Enum:")?;
+                indent = set_indentation(indent, 1);
+                for variant in variants {
+                    write!(indent, "\n{variant}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A variant of an enum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UdtVariant {
+    /// The span of the variant name.
+    pub name_span: Span,
+    /// The variant name.
+    pub name: Rc<str>,
+    /// The type of the payload carried by the variant, if any.
+    pub ty: Option<Ty>,
+}
+
+impl Display for UdtVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Variant {} ({})", self.name_span, self.name)?;
+        if let Some(ty) = &self.ty {
+            write!(f, ": {ty}")?;
         }
         Ok(())
     }
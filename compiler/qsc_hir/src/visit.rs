@@ -55,13 +55,20 @@ pub fn walk_package<'a>(vis: &mut impl Visitor<'a>, package: &'a Package) {
 pub fn walk_item<'a>(vis: &mut impl Visitor<'a>, item: &'a Item) {
     match &item.kind {
         ItemKind::Callable(decl) => vis.visit_callable_decl(decl),
-        ItemKind::Namespace(name, _) | ItemKind::Ty(name, _) => vis.visit_ident(name),
+        ItemKind::Namespace(name, _) | ItemKind::Ty(name, _) | ItemKind::Export(name, _) => {
+            vis.visit_ident(name);
+        }
+        ItemKind::Const(name, value) => {
+            vis.visit_ident(name);
+            vis.visit_expr(value);
+        }
     }
 }
 
 pub fn walk_callable_decl<'a>(vis: &mut impl Visitor<'a>, decl: &'a CallableDecl) {
     vis.visit_ident(&decl.name);
     vis.visit_pat(&decl.input);
+    decl.defaults.iter().for_each(|d| vis.visit_expr(d));
     vis.visit_spec_decl(&decl.body);
     decl.adj.iter().for_each(|spec| vis.visit_spec_decl(spec));
     decl.ctl.iter().for_each(|spec| vis.visit_spec_decl(spec));
@@ -178,7 +185,9 @@ pub fn walk_expr<'a>(vis: &mut impl Visitor<'a>, expr: &'a Expr) {
             vis.visit_expr(cond);
             vis.visit_block(block);
         }
-        ExprKind::Closure(_, _)
+        ExprKind::Break
+        | ExprKind::Closure(_, _)
+        | ExprKind::Continue
         | ExprKind::Err
         | ExprKind::Hole
         | ExprKind::Lit(_)
@@ -191,6 +200,10 @@ pub fn walk_pat<'a>(vis: &mut impl Visitor<'a>, pat: &'a Pat) {
         PatKind::Bind(name) => vis.visit_ident(name),
         PatKind::Discard | PatKind::Err => {}
         PatKind::Tuple(pats) => pats.iter().for_each(|p| vis.visit_pat(p)),
+        PatKind::Array(pats, rest) => pats
+            .iter()
+            .chain(rest.as_deref())
+            .for_each(|p| vis.visit_pat(p)),
     }
 }
 
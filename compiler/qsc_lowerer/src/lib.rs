@@ -32,6 +32,19 @@ pub struct Lowerer {
     assigner: Assigner,
     exec_graph: Vec<ExecGraphNode>,
     enable_debug: bool,
+    scope_depth: usize,
+    loops: Vec<Loop>,
+}
+
+/// The execution graph positions of an enclosing while-loop, used to lower `break` and `continue`.
+struct Loop {
+    /// The index of the start of the loop condition, which `continue` jumps to.
+    cond_idx: usize,
+    /// The indices of the placeholder jumps emitted for `break`, which are updated to jump past
+    /// the loop once its end is known.
+    breaks: Vec<usize>,
+    /// The number of debug scopes open outside of the loop body.
+    scope_depth: usize,
 }
 
 impl Default for Lowerer {
@@ -53,6 +66,8 @@ impl Lowerer {
             assigner: Assigner::new(),
             exec_graph: Vec::new(),
             enable_debug: false,
+            scope_depth: 0,
+            loops: Vec::new(),
         }
     }
 
@@ -171,6 +186,16 @@ impl Lowerer {
 
                 fir::ItemKind::Ty(name, udt)
             }
+            hir::ItemKind::Export(name, item) => {
+                let name = self.lower_ident(name);
+
+                fir::ItemKind::Export(name, lower_item_id(item))
+            }
+            hir::ItemKind::Const(name, value) => {
+                let name = self.lower_ident(name);
+
+                fir::ItemKind::Const(name, self.lower_ty(&value.ty))
+            }
         };
         let attrs = lower_attrs(&item.attrs);
         fir::Item {
@@ -191,7 +216,8 @@ impl Lowerer {
         let id = self.lower_id(decl.id);
         let kind = lower_callable_kind(decl.kind);
         let name = self.lower_ident(&decl.name);
-        let input = self.lower_pat(&decl.input);
+        let mut array_params = Vec::new();
+        let input = self.lower_callable_input(&decl.input, &mut array_params);
         let generics = lower_generics(&decl.generics);
         let output = self.lower_ty(&decl.output);
         let functors = lower_functors(decl.functors);
@@ -202,10 +228,19 @@ impl Lowerer {
             );
             CallableImpl::Intrinsic
         } else {
-            let body = self.lower_spec_decl(&decl.body);
-            let adj = decl.adj.as_ref().map(|f| self.lower_spec_decl(f));
-            let ctl = decl.ctl.as_ref().map(|f| self.lower_spec_decl(f));
-            let ctl_adj = decl.ctl_adj.as_ref().map(|f| self.lower_spec_decl(f));
+            let body = self.lower_spec_decl(&decl.body, &array_params);
+            let adj = decl
+                .adj
+                .as_ref()
+                .map(|f| self.lower_spec_decl(f, &array_params));
+            let ctl = decl
+                .ctl
+                .as_ref()
+                .map(|f| self.lower_spec_decl(f, &array_params));
+            let ctl_adj = decl
+                .ctl_adj
+                .as_ref()
+                .map(|f| self.lower_spec_decl(f, &array_params));
             let specialized_implementation = SpecImpl {
                 body,
                 adj,
@@ -234,12 +269,54 @@ impl Lowerer {
         }
     }
 
-    fn lower_spec_decl(&mut self, decl: &hir::SpecDecl) -> fir::SpecDecl {
+    /// Lowers the input pattern of a callable, replacing any array patterns with a binding to a
+    /// fresh local. The array patterns are collected into `array_params` so that each
+    /// specialization can destructure the bound array at the start of its body.
+    fn lower_callable_input(
+        &mut self,
+        pat: &hir::Pat,
+        array_params: &mut Vec<(fir::LocalVarId, hir::Pat)>,
+    ) -> PatId {
+        let kind = match &pat.kind {
+            hir::PatKind::Array(..) => {
+                let local = self.assigner.next_local();
+                array_params.push((local, pat.clone()));
+                fir::PatKind::Bind(fir::Ident {
+                    id: local,
+                    span: pat.span,
+                    name: "@array_param".into(),
+                })
+            }
+            hir::PatKind::Tuple(items) => fir::PatKind::Tuple(
+                items
+                    .iter()
+                    .map(|i| self.lower_callable_input(i, array_params))
+                    .collect(),
+            ),
+            _ => return self.lower_pat(pat),
+        };
+
+        let id = self.assigner.next_pat();
+        let pat = fir::Pat {
+            id,
+            span: pat.span,
+            ty: self.lower_ty(&pat.ty),
+            kind,
+        };
+        self.pats.insert(id, pat);
+        id
+    }
+
+    fn lower_spec_decl(
+        &mut self,
+        decl: &hir::SpecDecl,
+        array_params: &[(fir::LocalVarId, hir::Pat)],
+    ) -> fir::SpecDecl {
         let SpecBody::Impl(pat, block) = &decl.body else {
             panic!("if a SpecDecl is some, then it must be an implementation");
         };
         let input = pat.as_ref().map(|p| self.lower_spec_decl_pat(p));
-        let block = self.lower_block(block);
+        let block = self.lower_block_with_params(block, array_params);
         fir::SpecDecl {
             id: self.lower_id(decl.id),
             span: decl.span,
@@ -264,6 +341,10 @@ impl Lowerer {
             hir::PatKind::Tuple(elems) => {
                 fir::PatKind::Tuple(elems.iter().map(|pat| self.lower_pat(pat)).collect())
             }
+            hir::PatKind::Array(elems, rest) => fir::PatKind::Array(
+                elems.iter().map(|pat| self.lower_pat(pat)).collect(),
+                rest.as_ref().map(|pat| self.lower_pat(pat)),
+            ),
             hir::PatKind::Err => unreachable!("error pat should not be present"),
         };
 
@@ -273,6 +354,14 @@ impl Lowerer {
     }
 
     fn lower_block(&mut self, block: &hir::Block) -> BlockId {
+        self.lower_block_with_params(block, &[])
+    }
+
+    fn lower_block_with_params(
+        &mut self,
+        block: &hir::Block,
+        array_params: &[(fir::LocalVarId, hir::Pat)],
+    ) -> BlockId {
         let id = self.assigner.next_block();
         // When lowering for debugging, we need to be more strict about scoping for variables
         // otherwise variables that are not in scope will be visible in the locals view.
@@ -285,28 +374,65 @@ impl Lowerer {
         // exit.
         if self.enable_debug {
             self.exec_graph.push(ExecGraphNode::PushScope);
+            self.scope_depth += 1;
         }
         let set_unit = block.stmts.is_empty()
             || !matches!(
                 block.stmts.last().expect("block should be non-empty").kind,
                 hir::StmtKind::Expr(..)
             );
+        let mut stmts = array_params
+            .iter()
+            .map(|(local, pat)| self.lower_array_param(*local, pat))
+            .collect::<Vec<_>>();
+        stmts.extend(block.stmts.iter().map(|s| self.lower_stmt(s)));
         let block = fir::Block {
             id,
             span: block.span,
             ty: self.lower_ty(&block.ty),
-            stmts: block.stmts.iter().map(|s| self.lower_stmt(s)).collect(),
+            stmts,
         };
         if set_unit {
             self.exec_graph.push(ExecGraphNode::Unit);
         }
         if self.enable_debug {
             self.exec_graph.push(ExecGraphNode::PopScope);
+            self.scope_depth -= 1;
         }
         self.blocks.insert(id, block);
         id
     }
 
+    /// Creates the statement `let <pat> = <local>;` that destructures an array parameter.
+    fn lower_array_param(&mut self, local: fir::LocalVarId, pat: &hir::Pat) -> fir::StmtId {
+        let id = self.assigner.next_stmt();
+        let graph_start_idx = self.exec_graph.len();
+        if self.enable_debug {
+            self.exec_graph.push(ExecGraphNode::Stmt(id));
+        }
+        let pat_id = self.lower_pat(pat);
+        let expr_id = self.assigner.next_expr();
+        let expr_graph_start_idx = self.exec_graph.len();
+        self.exec_graph.push(ExecGraphNode::Expr(expr_id));
+        let expr = fir::Expr {
+            id: expr_id,
+            span: pat.span,
+            ty: self.lower_ty(&pat.ty),
+            kind: fir::ExprKind::Var(fir::Res::Local(local), Vec::new()),
+            exec_graph_range: expr_graph_start_idx..self.exec_graph.len(),
+        };
+        self.exprs.insert(expr_id, expr);
+        self.exec_graph.push(ExecGraphNode::Bind(pat_id));
+        let stmt = fir::Stmt {
+            id,
+            span: pat.span,
+            kind: fir::StmtKind::Local(fir::Mutability::Immutable, pat_id, expr_id),
+            exec_graph_range: graph_start_idx..self.exec_graph.len(),
+        };
+        self.stmts.insert(id, stmt);
+        id
+    }
+
     fn lower_stmt(&mut self, stmt: &hir::Stmt) -> fir::StmtId {
         let id = self.assigner.next_stmt();
         let graph_start_idx = self.exec_graph.len();
@@ -568,22 +694,55 @@ impl Lowerer {
                 let idx = self.exec_graph.len();
                 // Put a placeholder in the execution graph for the jump past the loop
                 self.exec_graph.push(ExecGraphNode::Jump(0));
+                self.loops.push(Loop {
+                    cond_idx,
+                    breaks: Vec::new(),
+                    scope_depth: self.scope_depth,
+                });
                 let body = self.lower_block(body);
+                let lp = self.loops.pop().expect("loop should be present");
                 self.exec_graph.push(ExecGraphNode::Jump(
                     cond_idx.try_into().expect("nodes should fit into u32"),
                 ));
-                // Update the placeholder to skip the loop if the condition is false
-                self.exec_graph[idx] = ExecGraphNode::JumpIfNot(
-                    self.exec_graph
-                        .len()
-                        .try_into()
-                        .expect("nodes should fit into u32"),
-                );
+                // Update the placeholder to skip the loop if the condition is false, and any
+                // breaks to jump to the same place.
+                let end_idx = self
+                    .exec_graph
+                    .len()
+                    .try_into()
+                    .expect("nodes should fit into u32");
+                self.exec_graph[idx] = ExecGraphNode::JumpIfNot(end_idx);
+                for break_idx in lp.breaks {
+                    self.exec_graph[break_idx] = ExecGraphNode::Jump(end_idx);
+                }
                 // While-exprs never have a return value, so we need to insert a no-op to ensure
                 // a Unit value is returned for the expr.
                 self.exec_graph.push(ExecGraphNode::Unit);
                 fir::ExprKind::While(cond, body)
             }
+            hir::ExprKind::Break => {
+                self.pop_loop_scopes();
+                self.exec_graph.push(ExecGraphNode::Jump(0));
+                let idx = self.exec_graph.len() - 1;
+                self.loops
+                    .last_mut()
+                    .expect("break should be inside a loop")
+                    .breaks
+                    .push(idx);
+                fir::ExprKind::Break
+            }
+            hir::ExprKind::Continue => {
+                self.pop_loop_scopes();
+                let cond_idx = self
+                    .loops
+                    .last()
+                    .expect("continue should be inside a loop")
+                    .cond_idx;
+                self.exec_graph.push(ExecGraphNode::Jump(
+                    cond_idx.try_into().expect("nodes should fit into u32"),
+                ));
+                fir::ExprKind::Continue
+            }
             hir::ExprKind::Closure(ids, id) => {
                 let ids = ids.iter().map(|id| self.lower_local_id(*id)).collect();
                 fir::ExprKind::Closure(ids, lower_local_item_id(*id))
@@ -625,6 +784,8 @@ impl Lowerer {
             // These expressions express specific control flow that is handled above.
             fir::ExprKind::BinOp(fir::BinOp::AndL | fir::BinOp::OrL, _, _)
             | fir::ExprKind::Block(..)
+            | fir::ExprKind::Break
+            | fir::ExprKind::Continue
             | fir::ExprKind::If(..)
             | fir::ExprKind::Return(..)
             | fir::ExprKind::While(..) => {}
@@ -644,6 +805,18 @@ impl Lowerer {
         id
     }
 
+    /// Closes the debug scopes opened inside the innermost loop body before jumping out of it.
+    fn pop_loop_scopes(&mut self) {
+        let loop_depth = self
+            .loops
+            .last()
+            .expect("loop jump should be inside a loop")
+            .scope_depth;
+        for _ in loop_depth..self.scope_depth {
+            self.exec_graph.push(ExecGraphNode::PopScope);
+        }
+    }
+
     fn lower_string_component(&mut self, component: &hir::StringComponent) -> fir::StringComponent {
         match component {
             hir::StringComponent::Expr(expr) => {
@@ -667,6 +840,10 @@ impl Lowerer {
             hir::PatKind::Tuple(items) => {
                 fir::PatKind::Tuple(items.iter().map(|i| self.lower_pat(i)).collect())
             }
+            hir::PatKind::Array(items, rest) => fir::PatKind::Array(
+                items.iter().map(|i| self.lower_pat(i)).collect(),
+                rest.as_ref().map(|r| self.lower_pat(r)),
+            ),
             hir::PatKind::Err => unreachable!("error pat should not be present"),
         };
 
@@ -741,6 +918,12 @@ impl Lowerer {
             qsc_hir::ty::UdtDefKind::Tuple(tup) => qsc_fir::ty::UdtDefKind::Tuple(
                 tup.iter().map(|def| self.lower_udt_defn(def)).collect(),
             ),
+            qsc_hir::ty::UdtDefKind::Enum(variants) => qsc_fir::ty::UdtDefKind::Enum(
+                variants
+                    .iter()
+                    .map(|variant| self.lower_udt_variant(variant))
+                    .collect(),
+            ),
         };
         qsc_fir::ty::UdtDef { span, kind }
     }
@@ -782,6 +965,14 @@ impl Lowerer {
             name_span: field.name_span,
         }
     }
+
+    fn lower_udt_variant(&mut self, variant: &qsc_hir::ty::UdtVariant) -> qsc_fir::ty::UdtVariant {
+        qsc_fir::ty::UdtVariant {
+            name_span: variant.name_span,
+            name: variant.name.clone(),
+            ty: variant.ty.as_ref().map(|ty| self.lower_ty(ty)),
+        }
+    }
 }
 
 fn lower_generics(generics: &[qsc_hir::ty::GenericParam]) -> Vec<qsc_fir::ty::GenericParam> {
@@ -789,7 +980,14 @@ fn lower_generics(generics: &[qsc_hir::ty::GenericParam]) -> Vec<qsc_fir::ty::Ge
 }
 
 fn lower_attrs(attrs: &[hir::Attr]) -> Vec<fir::Attr> {
-    attrs.iter().map(|_| fir::Attr::EntryPoint).collect()
+    attrs
+        .iter()
+        .filter_map(|attr| match attr {
+            hir::Attr::EntryPoint => Some(fir::Attr::EntryPoint),
+            hir::Attr::NoInline => Some(fir::Attr::NoInline),
+            hir::Attr::Config | hir::Attr::Unimplemented => None,
+        })
+        .collect()
 }
 
 fn lower_functors(functors: qsc_hir::ty::FunctorSetValue) -> qsc_fir::ty::FunctorSetValue {
@@ -798,7 +996,7 @@ fn lower_functors(functors: qsc_hir::ty::FunctorSetValue) -> qsc_fir::ty::Functo
 
 fn lower_generic_param(g: &qsc_hir::ty::GenericParam) -> qsc_fir::ty::GenericParam {
     match g {
-        qsc_hir::ty::GenericParam::Ty(_) => qsc_fir::ty::GenericParam::Ty,
+        qsc_hir::ty::GenericParam::Ty { .. } => qsc_fir::ty::GenericParam::Ty,
         qsc_hir::ty::GenericParam::Functor(value) => {
             qsc_fir::ty::GenericParam::Functor(lower_functor_set_value(*value))
         }
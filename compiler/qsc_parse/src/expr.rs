@@ -20,8 +20,8 @@ use crate::{
 use num_bigint::BigInt;
 use num_traits::Num;
 use qsc_ast::ast::{
-    self, BinOp, CallableKind, Expr, ExprKind, Functor, Lit, NodeId, Pat, PatKind, Pauli,
-    StringComponent, TernOp, UnOp,
+    self, BinOp, CallableKind, Expr, ExprKind, FieldAssign, Functor, Lit, MatchArm, NodeId, Pat,
+    PatKind, Pauli, StringComponent, TernOp, UnOp,
 };
 use qsc_data_structures::span::Span;
 use std::{result, str::FromStr};
@@ -91,6 +91,7 @@ pub(super) fn is_stmt_final(kind: &ExprKind) -> bool {
             | ExprKind::Conjugate(..)
             | ExprKind::For(..)
             | ExprKind::If(..)
+            | ExprKind::Match(..)
             | ExprKind::Repeat(..)
             | ExprKind::While(..)
     )
@@ -163,6 +164,10 @@ fn expr_base(s: &mut ParserContext) -> Result<Box<Expr>> {
         expr_range_prefix(s)
     } else if token(s, TokenKind::Keyword(Keyword::Underscore)).is_ok() {
         Ok(Box::new(ExprKind::Hole))
+    } else if token(s, TokenKind::Keyword(Keyword::Break)).is_ok() {
+        Ok(Box::new(ExprKind::Break))
+    } else if token(s, TokenKind::Keyword(Keyword::Continue)).is_ok() {
+        Ok(Box::new(ExprKind::Continue))
     } else if token(s, TokenKind::Keyword(Keyword::Fail)).is_ok() {
         Ok(Box::new(ExprKind::Fail(expr(s)?)))
     } else if token(s, TokenKind::Keyword(Keyword::For)).is_ok() {
@@ -187,6 +192,10 @@ fn expr_base(s: &mut ParserContext) -> Result<Box<Expr>> {
             None
         };
         Ok(Box::new(ExprKind::Repeat(body, cond, fixup)))
+    } else if token(s, TokenKind::Keyword(Keyword::Match)).is_ok() {
+        expr_match(s)
+    } else if token(s, TokenKind::Keyword(Keyword::New)).is_ok() {
+        expr_struct(s)
     } else if token(s, TokenKind::Keyword(Keyword::Return)).is_ok() {
         Ok(Box::new(ExprKind::Return(expr(s)?)))
     } else if token(s, TokenKind::Keyword(Keyword::Set)).is_ok() {
@@ -244,6 +253,98 @@ fn expr_if(s: &mut ParserContext) -> Result<Box<ExprKind>> {
     Ok(Box::new(ExprKind::If(cond, body, otherwise)))
 }
 
+fn expr_match(s: &mut ParserContext) -> Result<Box<ExprKind>> {
+    let scrutinee = expr(s)?;
+    token(s, TokenKind::Open(Delim::Brace))?;
+    let mut arms = Vec::new();
+    while s.peek().kind != TokenKind::Close(Delim::Brace) {
+        let arm = expr_match_arm(s)?;
+        let block_body = matches!(*arm.body.kind, ExprKind::Block(..));
+        arms.push(arm);
+        // Arms with a block body do not need a separating comma.
+        if token(s, TokenKind::Comma).is_err() && !block_body {
+            break;
+        }
+    }
+    token(s, TokenKind::Close(Delim::Brace))?;
+    Ok(Box::new(ExprKind::Match(
+        scrutinee,
+        arms.into_boxed_slice(),
+    )))
+}
+
+fn expr_match_arm(s: &mut ParserContext) -> Result<Box<MatchArm>> {
+    let lo = s.peek().span.lo;
+    let pat = match_pat(s)?;
+    token(s, TokenKind::FatArrow)?;
+    let body = expr(s)?;
+    Ok(Box::new(MatchArm {
+        id: NodeId::default(),
+        span: s.span(lo),
+        pat,
+        body,
+    }))
+}
+
+fn match_pat(s: &mut ParserContext) -> Result<Box<Pat>> {
+    let lo = s.peek().span.lo;
+    let kind = if token(s, TokenKind::Keyword(Keyword::Underscore)).is_ok() {
+        PatKind::Discard(None)
+    } else {
+        let variant = path(s)?;
+        let payload = if s.peek().kind == TokenKind::Open(Delim::Paren) {
+            Some(pat(s)?)
+        } else {
+            None
+        };
+        PatKind::Variant(variant, payload)
+    };
+    Ok(Box::new(Pat {
+        id: NodeId::default(),
+        span: s.span(lo),
+        kind: Box::new(kind),
+    }))
+}
+
+fn expr_struct(s: &mut ParserContext) -> Result<Box<ExprKind>> {
+    let name = path(s)?;
+    token(s, TokenKind::Open(Delim::Brace))?;
+    let copy = if token(s, TokenKind::DotDotDot).is_ok() {
+        let copy = expr(s)?;
+        if token(s, TokenKind::Comma).is_err() {
+            token(s, TokenKind::Close(Delim::Brace))?;
+            return Ok(Box::new(ExprKind::Struct(
+                name,
+                Some(copy),
+                Vec::new().into_boxed_slice(),
+            )));
+        }
+        Some(copy)
+    } else {
+        None
+    };
+    let (fields, _) = seq(s, expr_field_assign)?;
+    token(s, TokenKind::Close(Delim::Brace))?;
+    Ok(Box::new(ExprKind::Struct(
+        name,
+        copy,
+        fields.into_boxed_slice(),
+    )))
+}
+
+fn expr_field_assign(s: &mut ParserContext) -> Result<Box<FieldAssign>> {
+    let lo = s.peek().span.lo;
+    let field = ident(s)?;
+    token(s, TokenKind::Eq)?;
+    let value = expr(s)?;
+    Ok(Box::new(FieldAssign {
+        id: NodeId::default(),
+        span: s.span(lo),
+        field,
+        value,
+    }))
+}
+
 fn expr_set(s: &mut ParserContext) -> Result<Box<ExprKind>> {
     let lhs = expr(s)?;
     if token(s, TokenKind::Eq).is_ok() {
@@ -693,6 +794,20 @@ fn expr_as_pat(expr: Expr) -> Result<Box<Pat>> {
                 .collect::<Result<_>>()?;
             Ok(PatKind::Tuple(pats))
         }
+        ExprKind::Array(exprs) => {
+            let len = exprs.len();
+            let mut pats = Vec::new();
+            let mut rest = None;
+            for (i, expr) in exprs.into_vec().into_iter().enumerate() {
+                match *expr.kind {
+                    ExprKind::Range(None, None, Some(inner)) if i + 1 == len => {
+                        rest = Some(expr_as_pat(*inner)?);
+                    }
+                    _ => pats.push(expr_as_pat(*expr)?),
+                }
+            }
+            Ok(PatKind::Array(pats.into_boxed_slice(), rest))
+        }
         _ => Err(Error(ErrorKind::Convert(
             "pattern",
             "expression",
@@ -896,6 +896,33 @@ fn while_expr() {
     );
 }
 
+#[test]
+fn while_break_continue() {
+    check(
+        expr,
+        "while c { if x { break; } continue; }",
+        &expect![[r#"
+            Expr _id_ [0-37]: While:
+                Expr _id_ [6-7]: Path: Path _id_ [6-7] (Ident _id_ [6-7] "c")
+                Block _id_ [8-37]:
+                    Stmt _id_ [10-25]: Expr: Expr _id_ [10-25]: If:
+                        Expr _id_ [13-14]: Path: Path _id_ [13-14] (Ident _id_ [13-14] "x")
+                        Block _id_ [15-25]:
+                            Stmt _id_ [17-23]: Semi: Expr _id_ [17-22]: Break
+                    Stmt _id_ [26-35]: Semi: Expr _id_ [26-34]: Continue"#]],
+    );
+}
+
+#[test]
+fn break_expr() {
+    check(expr, "break", &expect!["Expr _id_ [0-5]: Break"]);
+}
+
+#[test]
+fn continue_expr() {
+    check(expr, "continue", &expect!["Expr _id_ [0-8]: Continue"]);
+}
+
 #[test]
 fn within_apply() {
     check(
@@ -1076,6 +1103,78 @@ fn array_repeat_two_items() {
     );
 }
 
+#[test]
+fn struct_new() {
+    check(
+        expr,
+        "new Foo { A = 1, B = 2.0 }",
+        &expect![[r#"
+        Expr _id_ [0-26]: Struct (Path _id_ [4-7] (Ident _id_ [4-7] "Foo")):
+            FieldAssign _id_ [10-15] (Ident _id_ [10-11] "A"):
+                Expr _id_ [14-15]: Lit: Int(1)
+            FieldAssign _id_ [17-24] (Ident _id_ [17-18] "B"):
+                Expr _id_ [21-24]: Lit: Double(2)"#]],
+    );
+}
+
+#[test]
+fn struct_new_qualified() {
+    check(
+        expr,
+        "new Bar.Foo { A = 1 }",
+        &expect![[r#"
+        Expr _id_ [0-21]: Struct (Path _id_ [4-11] (Ident _id_ [4-7] "Bar") (Ident _id_ [8-11] "Foo")):
+            FieldAssign _id_ [14-19] (Ident _id_ [14-15] "A"):
+                Expr _id_ [18-19]: Lit: Int(1)"#]],
+    );
+}
+
+#[test]
+fn struct_new_copy() {
+    check(
+        expr,
+        "new Foo { ...a }",
+        &expect![[r#"
+        Expr _id_ [0-16]: Struct (Path _id_ [4-7] (Ident _id_ [4-7] "Foo")):
+            Copy: Expr _id_ [13-14]: Path: Path _id_ [13-14] (Ident _id_ [13-14] "a")"#]],
+    );
+}
+
+#[test]
+fn struct_new_copy_with_fields() {
+    check(
+        expr,
+        "new Foo { ...a, B = 2.0, }",
+        &expect![[r#"
+        Expr _id_ [0-26]: Struct (Path _id_ [4-7] (Ident _id_ [4-7] "Foo")):
+            Copy: Expr _id_ [13-14]: Path: Path _id_ [13-14] (Ident _id_ [13-14] "a")
+            FieldAssign _id_ [16-23] (Ident _id_ [16-17] "B"):
+                Expr _id_ [20-23]: Lit: Double(2)"#]],
+    );
+}
+
+#[test]
+fn struct_new_missing_value() {
+    check(
+        expr,
+        "new Foo { A }",
+        &expect![[r#"
+        Error(
+            Token(
+                Eq,
+                Close(
+                    Brace,
+                ),
+                Span {
+                    lo: 12,
+                    hi: 13,
+                },
+            ),
+        )
+    "#]],
+    );
+}
+
 #[test]
 fn array_concat() {
     check(
@@ -1976,6 +2075,22 @@ fn lambda_tuple_input() {
     );
 }
 
+#[test]
+fn lambda_array_input() {
+    check(
+        expr,
+        "[x, ...rest] -> x",
+        &expect![[r#"
+        Expr _id_ [0-17]: Lambda (Function):
+            Pat _id_ [0-12]: Array:
+                Pat _id_ [1-2]: Bind:
+                    Ident _id_ [1-2] "x"
+                Rest: Pat _id_ [7-11]: Bind:
+                    Ident _id_ [7-11] "rest"
+            Expr _id_ [16-17]: Path: Path _id_ [16-17] (Ident _id_ [16-17] "x")"#]],
+    );
+}
+
 #[test]
 fn lambda_invalid_input() {
     check(
@@ -2435,3 +2550,127 @@ fn invalid_initial_commas_in_pattern() {
             ]"#]],
     );
 }
+
+#[test]
+fn match_expr() {
+    check(
+        expr,
+        "match s { Shape.Circle(r) => r, Shape.Rect(w, h) => w * h, Shape.Empty => 0.0 }",
+        &expect![[r#"
+            Expr _id_ [0-79]: Match:
+                Expr _id_ [6-7]: Path: Path _id_ [6-7] (Ident _id_ [6-7] "s")
+                MatchArm _id_ [10-30]:
+                    Pat _id_ [10-25]: Variant (Path _id_ [10-22] (Ident _id_ [10-15] "Shape") (Ident _id_ [16-22] "Circle")):
+                        Pat _id_ [22-25]: Paren:
+                            Pat _id_ [23-24]: Bind:
+                                Ident _id_ [23-24] "r"
+                    Expr _id_ [29-30]: Path: Path _id_ [29-30] (Ident _id_ [29-30] "r")
+                MatchArm _id_ [32-57]:
+                    Pat _id_ [32-48]: Variant (Path _id_ [32-42] (Ident _id_ [32-37] "Shape") (Ident _id_ [38-42] "Rect")):
+                        Pat _id_ [42-48]: Tuple:
+                            Pat _id_ [43-44]: Bind:
+                                Ident _id_ [43-44] "w"
+                            Pat _id_ [46-47]: Bind:
+                                Ident _id_ [46-47] "h"
+                    Expr _id_ [52-57]: BinOp (Mul):
+                        Expr _id_ [52-53]: Path: Path _id_ [52-53] (Ident _id_ [52-53] "w")
+                        Expr _id_ [56-57]: Path: Path _id_ [56-57] (Ident _id_ [56-57] "h")
+                MatchArm _id_ [59-77]:
+                    Pat _id_ [59-70]: Variant (Path _id_ [59-70] (Ident _id_ [59-64] "Shape") (Ident _id_ [65-70] "Empty"))
+                    Expr _id_ [74-77]: Lit: Double(0)"#]],
+    );
+}
+
+#[test]
+fn match_expr_discard() {
+    check(
+        expr,
+        "match s { Shape.Circle(_) => 1, _ => 0, }",
+        &expect![[r#"
+            Expr _id_ [0-41]: Match:
+                Expr _id_ [6-7]: Path: Path _id_ [6-7] (Ident _id_ [6-7] "s")
+                MatchArm _id_ [10-30]:
+                    Pat _id_ [10-25]: Variant (Path _id_ [10-22] (Ident _id_ [10-15] "Shape") (Ident _id_ [16-22] "Circle")):
+                        Pat _id_ [22-25]: Paren:
+                            Pat _id_ [23-24]: Discard
+                    Expr _id_ [29-30]: Lit: Int(1)
+                MatchArm _id_ [32-38]:
+                    Pat _id_ [32-33]: Discard
+                    Expr _id_ [37-38]: Lit: Int(0)"#]],
+    );
+}
+
+#[test]
+fn match_expr_block_arms() {
+    check(
+        expr,
+        "match s { Shape.Circle(r) => { r } Shape.Empty => { 0.0 } }",
+        &expect![[r#"
+            Expr _id_ [0-59]: Match:
+                Expr _id_ [6-7]: Path: Path _id_ [6-7] (Ident _id_ [6-7] "s")
+                MatchArm _id_ [10-34]:
+                    Pat _id_ [10-25]: Variant (Path _id_ [10-22] (Ident _id_ [10-15] "Shape") (Ident _id_ [16-22] "Circle")):
+                        Pat _id_ [22-25]: Paren:
+                            Pat _id_ [23-24]: Bind:
+                                Ident _id_ [23-24] "r"
+                    Expr _id_ [29-34]: Expr Block: Block _id_ [29-34]:
+                        Stmt _id_ [31-32]: Expr: Expr _id_ [31-32]: Path: Path _id_ [31-32] (Ident _id_ [31-32] "r")
+                MatchArm _id_ [35-57]:
+                    Pat _id_ [35-46]: Variant (Path _id_ [35-46] (Ident _id_ [35-40] "Shape") (Ident _id_ [41-46] "Empty"))
+                    Expr _id_ [50-57]: Expr Block: Block _id_ [50-57]:
+                        Stmt _id_ [52-55]: Expr: Expr _id_ [52-55]: Lit: Double(0)"#]],
+    );
+}
+
+#[test]
+fn match_expr_empty() {
+    check(
+        expr,
+        "match s {}",
+        &expect![[r#"
+        Expr _id_ [0-10]: Match:
+            Expr _id_ [6-7]: Path: Path _id_ [6-7] (Ident _id_ [6-7] "s")"#]],
+    );
+}
+
+#[test]
+fn match_expr_missing_comma() {
+    check(
+        expr,
+        "match s { Shape.Circle(r) => r Shape.Empty => 0.0 }",
+        &expect![[r#"
+            Error(
+                Token(
+                    Close(
+                        Brace,
+                    ),
+                    Ident,
+                    Span {
+                        lo: 31,
+                        hi: 36,
+                    },
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn match_expr_missing_arrow() {
+    check(
+        expr,
+        "match s { Shape.Empty 0.0 }",
+        &expect![[r#"
+        Error(
+            Token(
+                FatArrow,
+                Float,
+                Span {
+                    lo: 22,
+                    hi: 25,
+                },
+            ),
+        )
+    "#]],
+    );
+}
@@ -10,7 +10,7 @@ mod tests;
 use super::{
     expr::expr,
     keyword::Keyword,
-    prim::{dot_ident, ident, many, opt, pat, seq, token},
+    prim::{dot_ident, ident, many, opt, pat, path, seq, token},
     scan::ParserContext,
     stmt,
     ty::{self, ty},
@@ -18,15 +18,16 @@ use super::{
 };
 use crate::{
     lex::{Delim, TokenKind},
-    prim::{barrier, recovering, recovering_token, shorten},
+    prim::{barrier, recovering, recovering_token, shorten, FinalSep},
     stmt::check_semis,
     ty::array_or_arrow,
     ErrorKind,
 };
 use qsc_ast::ast::{
-    Attr, Block, CallableBody, CallableDecl, CallableKind, Ident, Item, ItemKind, Namespace,
-    NodeId, Pat, PatKind, Path, Spec, SpecBody, SpecDecl, SpecGen, StmtKind, TopLevelNode, Ty,
-    TyDef, TyDefKind, TyKind, Visibility, VisibilityKind,
+    Attr, Block, CallableBody, CallableDecl, CallableKind, ConstDecl, EnumDecl, ExportDecl,
+    ExportItem, Expr, FieldDef, Ident, Item, ItemKind, Namespace, NodeId, Pat, PatKind, Path, Spec,
+    SpecBody, SpecDecl, SpecGen, StmtKind, StructDecl, TopLevelNode, Ty, TyDef, TyDefKind, TyKind,
+    VariantDef, Visibility, VisibilityKind,
 };
 use qsc_data_structures::span::Span;
 
@@ -39,6 +40,14 @@ pub(super) fn parse(s: &mut ParserContext) -> Result<Box<Item>> {
         open
     } else if let Some(ty) = opt(s, parse_newtype)? {
         ty
+    } else if let Some(decl) = opt(s, parse_struct)? {
+        Box::new(ItemKind::Struct(decl))
+    } else if let Some(decl) = opt(s, parse_enum)? {
+        Box::new(ItemKind::Enum(decl))
+    } else if let Some(decl) = opt(s, parse_export)? {
+        Box::new(ItemKind::Export(decl))
+    } else if let Some(decl) = opt(s, parse_const)? {
+        Box::new(ItemKind::Const(decl))
     } else if let Some(callable) = opt(s, parse_callable_decl)? {
         Box::new(ItemKind::Callable(callable))
     } else if visibility.is_some() {
@@ -75,6 +84,10 @@ fn parse_many(s: &mut ParserContext) -> Result<Vec<Box<Item>>> {
         TokenKind::Keyword(Keyword::Internal),
         TokenKind::Keyword(Keyword::Open),
         TokenKind::Keyword(Keyword::Newtype),
+        TokenKind::Keyword(Keyword::Struct),
+        TokenKind::Keyword(Keyword::Enum),
+        TokenKind::Keyword(Keyword::Export),
+        TokenKind::Keyword(Keyword::Const),
         TokenKind::Keyword(Keyword::Operation),
         TokenKind::Keyword(Keyword::Function),
     ];
@@ -230,6 +243,117 @@ fn parse_newtype(s: &mut ParserContext) -> Result<Box<ItemKind>> {
     Ok(Box::new(ItemKind::Ty(name, def)))
 }
 
+fn parse_struct(s: &mut ParserContext) -> Result<Box<StructDecl>> {
+    let lo = s.peek().span.lo;
+    token(s, TokenKind::Keyword(Keyword::Struct))?;
+    let name = ident(s)?;
+    token(s, TokenKind::Open(Delim::Brace))?;
+    let (fields, _) = seq(s, parse_field_def)?;
+    recovering_token(s, TokenKind::Close(Delim::Brace));
+    Ok(Box::new(StructDecl {
+        id: NodeId::default(),
+        span: s.span(lo),
+        name,
+        fields: fields.into_boxed_slice(),
+    }))
+}
+
+fn parse_field_def(s: &mut ParserContext) -> Result<Box<FieldDef>> {
+    let lo = s.peek().span.lo;
+    let name = ident(s)?;
+    token(s, TokenKind::Colon)?;
+    let field_ty = ty(s)?;
+    Ok(Box::new(FieldDef {
+        id: NodeId::default(),
+        span: s.span(lo),
+        name,
+        ty: Box::new(field_ty),
+    }))
+}
+
+fn parse_enum(s: &mut ParserContext) -> Result<Box<EnumDecl>> {
+    let lo = s.peek().span.lo;
+    token(s, TokenKind::Keyword(Keyword::Enum))?;
+    let name = ident(s)?;
+    token(s, TokenKind::Open(Delim::Brace))?;
+    let (variants, _) = seq(s, parse_variant_def)?;
+    recovering_token(s, TokenKind::Close(Delim::Brace));
+    Ok(Box::new(EnumDecl {
+        id: NodeId::default(),
+        span: s.span(lo),
+        name,
+        variants: variants.into_boxed_slice(),
+    }))
+}
+
+fn parse_variant_def(s: &mut ParserContext) -> Result<Box<VariantDef>> {
+    let lo = s.peek().span.lo;
+    let name = ident(s)?;
+    let payload_ty = if token(s, TokenKind::Open(Delim::Paren)).is_ok() {
+        let payload_ty = ty(s)?;
+        token(s, TokenKind::Close(Delim::Paren))?;
+        Some(Box::new(payload_ty))
+    } else {
+        None
+    };
+    Ok(Box::new(VariantDef {
+        id: NodeId::default(),
+        span: s.span(lo),
+        name,
+        ty: payload_ty,
+    }))
+}
+
+fn parse_export(s: &mut ParserContext) -> Result<Box<ExportDecl>> {
+    let lo = s.peek().span.lo;
+    token(s, TokenKind::Keyword(Keyword::Export))?;
+    let (items, _) = seq(s, parse_export_item)?;
+    if items.is_empty() {
+        let p = s.peek();
+        return Err(Error(ErrorKind::Rule("export item", p.kind, p.span)));
+    }
+    token(s, TokenKind::Semi)?;
+    Ok(Box::new(ExportDecl {
+        id: NodeId::default(),
+        span: s.span(lo),
+        items: items.into_boxed_slice(),
+    }))
+}
+
+fn parse_export_item(s: &mut ParserContext) -> Result<Box<ExportItem>> {
+    let lo = s.peek().span.lo;
+    let path = path(s)?;
+    let alias = if token(s, TokenKind::Keyword(Keyword::As)).is_ok() {
+        Some(ident(s)?)
+    } else {
+        None
+    };
+    Ok(Box::new(ExportItem {
+        id: NodeId::default(),
+        span: s.span(lo),
+        path,
+        alias,
+    }))
+}
+
+fn parse_const(s: &mut ParserContext) -> Result<Box<ConstDecl>> {
+    let lo = s.peek().span.lo;
+    token(s, TokenKind::Keyword(Keyword::Const))?;
+    let name = ident(s)?;
+    token(s, TokenKind::Colon)?;
+    let const_ty = ty(s)?;
+    token(s, TokenKind::Eq)?;
+    let value = expr(s)?;
+    token(s, TokenKind::Semi)?;
+    Ok(Box::new(ConstDecl {
+        id: NodeId::default(),
+        span: s.span(lo),
+        name,
+        ty: Box::new(const_ty),
+        value,
+    }))
+}
+
 fn try_tydef_as_ty(tydef: &TyDef) -> Option<Ty> {
     match tydef.kind.as_ref() {
         TyDefKind::Field(Some(_), _) | TyDefKind::Err => None,
@@ -309,15 +433,14 @@ fn parse_callable_decl(s: &mut ParserContext) -> Result<Box<CallableDecl>> {
     let name = ident(s)?;
     let generics = if token(s, TokenKind::Lt).is_ok() {
         throw_away_doc(s);
-        let params = seq(s, ty::param)?.0;
+        let params = seq(s, ty::type_parameter)?.0;
         token(s, TokenKind::Gt)?;
         params
     } else {
         Vec::new()
     };
 
-    let input = pat(s)?;
-    check_input_parens(&input)?;
+    let (input, defaults) = parse_callable_input(s)?;
     token(s, TokenKind::Colon)?;
     throw_away_doc(s);
     let output = ty(s)?;
@@ -336,12 +459,53 @@ fn parse_callable_decl(s: &mut ParserContext) -> Result<Box<CallableDecl>> {
         name,
         generics: generics.into_boxed_slice(),
         input,
+        defaults,
         output: Box::new(output),
         functors,
         body: Box::new(body),
     }))
 }
 
+/// The input pattern of a callable and the default values of its trailing parameters.
+type CallableInput = (Box<Pat>, Box<[Box<Expr>]>);
+
+/// Parses the parenthesized input of a callable, where the trailing parameters may have default
+/// values: `(a : Int, b : Double = 1.0)`.
+fn parse_callable_input(s: &mut ParserContext) -> Result<CallableInput> {
+    throw_away_doc(s);
+    let lo = s.peek().span.lo;
+    if token(s, TokenKind::Open(Delim::Paren)).is_err() {
+        let input = pat(s)?;
+        return Err(Error(ErrorKind::MissingParens(input.span)));
+    }
+
+    let mut params = Vec::new();
+    let mut defaults = Vec::new();
+    let mut final_sep = FinalSep::Missing;
+    while let Some(param) = opt(s, pat)? {
+        if token(s, TokenKind::Eq).is_ok() {
+            defaults.push(expr(s)?);
+        } else if !defaults.is_empty() {
+            s.push_error(Error(ErrorKind::MissingDefault(param.span)));
+        }
+        params.push(param);
+        if token(s, TokenKind::Comma).is_ok() {
+            final_sep = FinalSep::Present;
+        } else {
+            final_sep = FinalSep::Missing;
+            break;
+        }
+    }
+    token(s, TokenKind::Close(Delim::Paren))?;
+
+    let input = Box::new(Pat {
+        id: NodeId::default(),
+        span: s.span(lo),
+        kind: Box::new(final_sep.reify(params, PatKind::Paren, PatKind::Tuple)),
+    });
+    Ok((input, defaults.into_boxed_slice()))
+}
+
 fn parse_callable_body(s: &mut ParserContext) -> Result<CallableBody> {
     let lo = s.peek().span.lo;
     token(s, TokenKind::Open(Delim::Brace))?;
@@ -417,11 +581,3 @@ fn parse_spec_gen(s: &mut ParserContext) -> Result<SpecGen> {
         )))
     }
 }
-/// Checks that the inputs of the callable are surrounded by parens
-pub(super) fn check_input_parens(inputs: &Pat) -> Result<()> {
-    if matches!(*inputs.kind, PatKind::Paren(_) | PatKind::Tuple(_)) {
-        Ok(())
-    } else {
-        Err(Error(ErrorKind::MissingParens(inputs.span)))
-    }
-}
@@ -510,6 +510,278 @@ fn ty_def_named_duplicate_comma() {
     );
 }
 
+#[test]
+fn struct_decl() {
+    check(
+        parse,
+        "struct Foo { A : Int, B : Double }",
+        &expect![[r#"
+        Item _id_ [0-34]:
+            Struct _id_ [0-34] (Ident _id_ [7-10] "Foo"):
+                FieldDef _id_ [13-20] (Ident _id_ [13-14] "A"): Type _id_ [17-20]: Path: Path _id_ [17-20] (Ident _id_ [17-20] "Int")
+                FieldDef _id_ [22-32] (Ident _id_ [22-23] "B"): Type _id_ [26-32]: Path: Path _id_ [26-32] (Ident _id_ [26-32] "Double")"#]],
+    );
+}
+
+#[test]
+fn struct_decl_empty() {
+    check(
+        parse,
+        "struct Foo {}",
+        &expect![[r#"
+        Item _id_ [0-13]:
+            Struct _id_ [0-13] (Ident _id_ [7-10] "Foo"): <empty>"#]],
+    );
+}
+
+#[test]
+fn struct_decl_trailing_comma() {
+    check(
+        parse,
+        "struct Foo { A : Int, }",
+        &expect![[r#"
+        Item _id_ [0-23]:
+            Struct _id_ [0-23] (Ident _id_ [7-10] "Foo"):
+                FieldDef _id_ [13-20] (Ident _id_ [13-14] "A"): Type _id_ [17-20]: Path: Path _id_ [17-20] (Ident _id_ [17-20] "Int")"#]],
+    );
+}
+
+#[test]
+fn struct_decl_missing_ty() {
+    check(
+        parse,
+        "struct Foo { A }",
+        &expect![[r#"
+        Error(
+            Token(
+                Colon,
+                Close(
+                    Brace,
+                ),
+                Span {
+                    lo: 15,
+                    hi: 16,
+                },
+            ),
+        )
+    "#]],
+    );
+}
+
+#[test]
+fn enum_decl() {
+    check(
+        parse,
+        "enum Shape { Circle(Double), Rect((Double, Double)), Empty }",
+        &expect![[r#"
+            Item _id_ [0-60]:
+                Enum _id_ [0-60] (Ident _id_ [5-10] "Shape"):
+                    VariantDef _id_ [13-27] (Ident _id_ [13-19] "Circle"): Type _id_ [20-26]: Path: Path _id_ [20-26] (Ident _id_ [20-26] "Double")
+                    VariantDef _id_ [29-51] (Ident _id_ [29-33] "Rect"): Type _id_ [34-50]: Tuple:
+                        Type _id_ [35-41]: Path: Path _id_ [35-41] (Ident _id_ [35-41] "Double")
+                        Type _id_ [43-49]: Path: Path _id_ [43-49] (Ident _id_ [43-49] "Double")
+                    VariantDef _id_ [53-58] (Ident _id_ [53-58] "Empty")"#]],
+    );
+}
+
+#[test]
+fn enum_decl_empty() {
+    check(
+        parse,
+        "enum Foo {}",
+        &expect![[r#"
+        Item _id_ [0-11]:
+            Enum _id_ [0-11] (Ident _id_ [5-8] "Foo"): <empty>"#]],
+    );
+}
+
+#[test]
+fn enum_decl_trailing_comma() {
+    check(
+        parse,
+        "enum Foo { A, B(Int), }",
+        &expect![[r#"
+        Item _id_ [0-23]:
+            Enum _id_ [0-23] (Ident _id_ [5-8] "Foo"):
+                VariantDef _id_ [11-12] (Ident _id_ [11-12] "A")
+                VariantDef _id_ [14-20] (Ident _id_ [14-15] "B"): Type _id_ [16-19]: Path: Path _id_ [16-19] (Ident _id_ [16-19] "Int")"#]],
+    );
+}
+
+#[test]
+fn enum_decl_missing_payload_close() {
+    check(
+        parse,
+        "enum Foo { A(Int }",
+        &expect![[r#"
+        Error(
+            Token(
+                Close(
+                    Paren,
+                ),
+                Close(
+                    Brace,
+                ),
+                Span {
+                    lo: 17,
+                    hi: 18,
+                },
+            ),
+        )
+    "#]],
+    );
+}
+
+#[test]
+fn export_decl() {
+    check(
+        parse,
+        "export Foo.Bar, Baz;",
+        &expect![[r#"
+        Item _id_ [0-20]:
+            Export _id_ [0-20]:
+                ExportItem _id_ [7-14]: Path _id_ [7-14] (Ident _id_ [7-10] "Foo") (Ident _id_ [11-14] "Bar")
+                ExportItem _id_ [16-19]: Path _id_ [16-19] (Ident _id_ [16-19] "Baz")"#]],
+    );
+}
+
+#[test]
+fn export_decl_with_alias() {
+    check(
+        parse,
+        "internal export Foo.Bar as Qux;",
+        &expect![[r#"
+        Item _id_ [0-31]:
+            Visibility _id_ [0-8] (Internal)
+            Export _id_ [9-31]:
+                ExportItem _id_ [16-30]: Path _id_ [16-23] (Ident _id_ [16-19] "Foo") (Ident _id_ [20-23] "Bar") as Ident _id_ [27-30] "Qux""#]],
+    );
+}
+
+#[test]
+fn const_decl() {
+    check(
+        parse,
+        "const NumIterations : Int = 2 ^ 10;",
+        &expect![[r#"
+        Item _id_ [0-35]:
+            Const _id_ [0-35] (Ident _id_ [6-19] "NumIterations"):
+                type: Type _id_ [22-25]: Path: Path _id_ [22-25] (Ident _id_ [22-25] "Int")
+                value: Expr _id_ [28-34]: BinOp (Exp):
+                    Expr _id_ [28-29]: Lit: Int(2)
+                    Expr _id_ [32-34]: Lit: Int(10)"#]],
+    );
+}
+
+#[test]
+fn const_decl_missing_ty() {
+    check(
+        parse,
+        "const NumIterations = 2 ^ 10;",
+        &expect![[r#"
+        Error(
+            Token(
+                Colon,
+                Eq,
+                Span {
+                    lo: 20,
+                    hi: 21,
+                },
+            ),
+        )
+    "#]],
+    );
+}
+
+#[test]
+fn callable_decl_defaults() {
+    check(
+        parse,
+        "function Foo(x : Int, y : Int = 2, z : Double = 1.0 / 3.0) : Int { x + y }",
+        &expect![[r#"
+            Item _id_ [0-74]:
+                Callable _id_ [0-74] (Function):
+                    name: Ident _id_ [9-12] "Foo"
+                    input: Pat _id_ [12-58]: Tuple:
+                        Pat _id_ [13-20]: Bind:
+                            Ident _id_ [13-14] "x"
+                            Type _id_ [17-20]: Path: Path _id_ [17-20] (Ident _id_ [17-20] "Int")
+                        Pat _id_ [22-29]: Bind:
+                            Ident _id_ [22-23] "y"
+                            Type _id_ [26-29]: Path: Path _id_ [26-29] (Ident _id_ [26-29] "Int")
+                        Pat _id_ [35-45]: Bind:
+                            Ident _id_ [35-36] "z"
+                            Type _id_ [39-45]: Path: Path _id_ [39-45] (Ident _id_ [39-45] "Double")
+                    defaults:
+                        Expr _id_ [32-33]: Lit: Int(2)
+                        Expr _id_ [48-57]: BinOp (Div):
+                            Expr _id_ [48-51]: Lit: Double(1)
+                            Expr _id_ [54-57]: Lit: Double(3)
+                    output: Type _id_ [61-64]: Path: Path _id_ [61-64] (Ident _id_ [61-64] "Int")
+                    body: Block: Block _id_ [65-74]:
+                        Stmt _id_ [67-72]: Expr: Expr _id_ [67-72]: BinOp (Add):
+                            Expr _id_ [67-68]: Path: Path _id_ [67-68] (Ident _id_ [67-68] "x")
+                            Expr _id_ [71-72]: Path: Path _id_ [71-72] (Ident _id_ [71-72] "y")"#]],
+    );
+}
+
+#[test]
+fn callable_decl_default_before_required() {
+    check(
+        parse,
+        "function Foo(x : Int = 1, y : Int) : Int { x + y }",
+        &expect![[r#"
+            Item _id_ [0-50]:
+                Callable _id_ [0-50] (Function):
+                    name: Ident _id_ [9-12] "Foo"
+                    input: Pat _id_ [12-34]: Tuple:
+                        Pat _id_ [13-20]: Bind:
+                            Ident _id_ [13-14] "x"
+                            Type _id_ [17-20]: Path: Path _id_ [17-20] (Ident _id_ [17-20] "Int")
+                        Pat _id_ [26-33]: Bind:
+                            Ident _id_ [26-27] "y"
+                            Type _id_ [30-33]: Path: Path _id_ [30-33] (Ident _id_ [30-33] "Int")
+                    defaults:
+                        Expr _id_ [23-24]: Lit: Int(1)
+                    output: Type _id_ [37-40]: Path: Path _id_ [37-40] (Ident _id_ [37-40] "Int")
+                    body: Block: Block _id_ [41-50]:
+                        Stmt _id_ [43-48]: Expr: Expr _id_ [43-48]: BinOp (Add):
+                            Expr _id_ [43-44]: Path: Path _id_ [43-44] (Ident _id_ [43-44] "x")
+                            Expr _id_ [47-48]: Path: Path _id_ [47-48] (Ident _id_ [47-48] "y")
+
+            [
+                Error(
+                    MissingDefault(
+                        Span {
+                            lo: 26,
+                            hi: 33,
+                        },
+                    ),
+                ),
+            ]"#]],
+    );
+}
+
+#[test]
+fn export_decl_empty() {
+    check(
+        parse,
+        "export;",
+        &expect![[r#"
+        Error(
+            Rule(
+                "export item",
+                Semi,
+                Span {
+                    lo: 6,
+                    hi: 7,
+                },
+            ),
+        )
+    "#]],
+    );
+}
+
 #[test]
 fn function_decl() {
     check(
@@ -652,7 +924,7 @@ fn function_one_ty_param() {
                 Callable _id_ [0-45] (Function):
                     name: Ident _id_ [9-12] "Foo"
                     generics:
-                        Ident _id_ [13-15] "'T"
+                        TypeParameter [13-15]: Ident _id_ [13-15] "'T"
                     input: Pat _id_ [16-18]: Unit
                     output: Type _id_ [21-25]: Path: Path _id_ [21-25] (Ident _id_ [21-25] "Unit")
                     body: Specializations:
@@ -670,8 +942,8 @@ fn function_two_ty_params() {
                 Callable _id_ [0-49] (Function):
                     name: Ident _id_ [9-12] "Foo"
                     generics:
-                        Ident _id_ [13-15] "'T"
-                        Ident _id_ [17-19] "'U"
+                        TypeParameter [13-15]: Ident _id_ [13-15] "'T"
+                        TypeParameter [17-19]: Ident _id_ [17-19] "'U"
                     input: Pat _id_ [20-22]: Unit
                     output: Type _id_ [25-29]: Path: Path _id_ [25-29] (Ident _id_ [25-29] "Unit")
                     body: Specializations:
@@ -689,8 +961,8 @@ fn function_duplicate_comma_in_ty_param() {
                 Callable _id_ [0-47] (Function):
                     name: Ident _id_ [9-12] "Foo"
                     generics:
-                        Ident _id_ [13-15] "'T"
-                        Ident _id_ [16-16] ""
+                        TypeParameter [13-15]: Ident _id_ [13-15] "'T"
+                        TypeParameter [16-16]: Ident _id_ [16-16] ""
                     input: Pat _id_ [18-20]: Unit
                     output: Type _id_ [23-27]: Path: Path _id_ [23-27] (Ident _id_ [23-27] "Unit")
                     body: Specializations:
@@ -709,6 +981,68 @@ fn function_duplicate_comma_in_ty_param() {
     );
 }
 
+#[test]
+fn function_ty_param_with_class_bound() {
+    check(
+        parse,
+        "function Foo<'T : Eq>() : Unit { body intrinsic; }",
+        &expect![[r#"
+            Item _id_ [0-50]:
+                Callable _id_ [0-50] (Function):
+                    name: Ident _id_ [9-12] "Foo"
+                    generics:
+                        TypeParameter [13-20]: Ident _id_ [13-15] "'T"
+                            bounds:
+                                Ident _id_ [18-20] "Eq"
+                    input: Pat _id_ [21-23]: Unit
+                    output: Type _id_ [26-30]: Path: Path _id_ [26-30] (Ident _id_ [26-30] "Unit")
+                    body: Specializations:
+                        SpecDecl _id_ [33-48] (Body): Gen: Intrinsic"#]],
+    );
+}
+
+#[test]
+fn function_ty_params_with_multiple_class_bounds() {
+    check(
+        parse,
+        "function Foo<'T : Add + Eq, 'U>() : Unit { body intrinsic; }",
+        &expect![[r#"
+            Item _id_ [0-60]:
+                Callable _id_ [0-60] (Function):
+                    name: Ident _id_ [9-12] "Foo"
+                    generics:
+                        TypeParameter [13-26]: Ident _id_ [13-15] "'T"
+                            bounds:
+                                Ident _id_ [18-21] "Add"
+                                Ident _id_ [24-26] "Eq"
+                        TypeParameter [28-30]: Ident _id_ [28-30] "'U"
+                    input: Pat _id_ [31-33]: Unit
+                    output: Type _id_ [36-40]: Path: Path _id_ [36-40] (Ident _id_ [36-40] "Unit")
+                    body: Specializations:
+                        SpecDecl _id_ [43-58] (Body): Gen: Intrinsic"#]],
+    );
+}
+
+#[test]
+fn function_ty_param_missing_class_bound() {
+    check(
+        parse,
+        "function Foo<'T : >() : Unit { body intrinsic; }",
+        &expect![[r#"
+            Error(
+                Rule(
+                    "identifier",
+                    Gt,
+                    Span {
+                        lo: 18,
+                        hi: 19,
+                    },
+                ),
+            )
+        "#]],
+    );
+}
+
 #[test]
 fn function_single_impl() {
     check(
@@ -18,12 +18,17 @@ pub enum Keyword {
     Auto,
     Body,
     Borrow,
+    Break,
+    Const,
+    Continue,
     Controlled,
     ControlledUpper,
     Ctl,
     Distribute,
     Elif,
     Else,
+    Enum,
+    Export,
     Fail,
     False,
     Fixup,
@@ -36,8 +41,10 @@ pub enum Keyword {
     Invert,
     Is,
     Let,
+    Match,
     Mutable,
     Namespace,
+    New,
     Newtype,
     Not,
     One,
@@ -52,6 +59,7 @@ pub enum Keyword {
     Return,
     Slf,
     Set,
+    Struct,
     True,
     Underscore,
     Until,
@@ -73,12 +81,17 @@ impl Keyword {
             Self::Auto => "auto",
             Self::Body => "body",
             Self::Borrow => "borrow",
+            Self::Break => "break",
+            Self::Const => "const",
+            Self::Continue => "continue",
             Self::Controlled => "controlled",
             Self::ControlledUpper => "Controlled",
             Self::Ctl => "Ctl",
             Self::Distribute => "distribute",
             Self::Elif => "elif",
             Self::Else => "else",
+            Self::Enum => "enum",
+            Self::Export => "export",
             Self::Fail => "fail",
             Self::False => "false",
             Self::Fixup => "fixup",
@@ -91,8 +104,10 @@ impl Keyword {
             Self::Invert => "invert",
             Self::Is => "is",
             Self::Let => "let",
+            Self::Match => "match",
             Self::Mutable => "mutable",
             Self::Namespace => "namespace",
+            Self::New => "new",
             Self::Newtype => "newtype",
             Self::Not => "not",
             Self::One => "One",
@@ -107,6 +122,7 @@ impl Keyword {
             Self::Return => "return",
             Self::Slf => "self",
             Self::Set => "set",
+            Self::Struct => "struct",
             Self::True => "true",
             Self::Underscore => "_",
             Self::Until => "until",
@@ -142,6 +158,9 @@ impl FromStr for Keyword {
             "open" => Ok(Self::Open),
             "if" => Ok(Self::If),
             "return" => Ok(Self::Return),
+            "break" => Ok(Self::Break),
+            "const" => Ok(Self::Const),
+            "continue" => Ok(Self::Continue),
             "Adj" => Ok(Self::Adj),
             "Controlled" => Ok(Self::ControlledUpper),
             "controlled" => Ok(Self::Controlled),
@@ -171,6 +190,11 @@ impl FromStr for Keyword {
             "within" => Ok(Self::Within),
             "body" => Ok(Self::Body),
             "newtype" => Ok(Self::Newtype),
+            "new" => Ok(Self::New),
+            "struct" => Ok(Self::Struct),
+            "enum" => Ok(Self::Enum),
+            "export" => Ok(Self::Export),
+            "match" => Ok(Self::Match),
             "invert" => Ok(Self::Invert),
             "distribute" => Ok(Self::Distribute),
             "auto" => Ok(Self::Auto),
@@ -74,6 +74,12 @@ enum ErrorKind {
     #[error("missing entry in sequence")]
     #[diagnostic(code("Qsc.Parse.MissingSeqEntry"))]
     MissingSeqEntry(#[label] Span),
+    #[error("expected parameter to have a default value")]
+    #[diagnostic(help(
+        "parameters with default values must come after all parameters without default values"
+    ))]
+    #[diagnostic(code("Qsc.Parse.MissingDefault"))]
+    MissingDefault(#[label] Span),
 }
 
 impl ErrorKind {
@@ -91,6 +97,7 @@ impl ErrorKind {
             Self::FloatingAttr(span) => Self::FloatingAttr(span + offset),
             Self::FloatingVisibility(span) => Self::FloatingVisibility(span + offset),
             Self::MissingSeqEntry(span) => Self::MissingSeqEntry(span + offset),
+            Self::MissingDefault(span) => Self::MissingDefault(span + offset),
         }
     }
 }
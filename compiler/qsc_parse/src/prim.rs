@@ -143,6 +143,21 @@ pub(super) fn pat(s: &mut ParserContext) -> Result<Box<Pat>> {
         let (pats, final_sep) = seq(s, pat)?;
         token(s, TokenKind::Close(Delim::Paren))?;
         Ok(final_sep.reify(pats, PatKind::Paren, PatKind::Tuple))
+    } else if token(s, TokenKind::Open(Delim::Bracket)).is_ok() {
+        let mut pats = Vec::new();
+        let mut rest = None;
+        while rest.is_none() && s.peek().kind != TokenKind::Close(Delim::Bracket) {
+            if token(s, TokenKind::DotDotDot).is_ok() {
+                rest = Some(pat(s)?);
+            } else {
+                pats.push(pat(s)?);
+            }
+            if token(s, TokenKind::Comma).is_err() {
+                break;
+            }
+        }
+        token(s, TokenKind::Close(Delim::Bracket))?;
+        Ok(PatKind::Array(pats.into_boxed_slice(), rest))
     } else {
         let name = ident(s).map_err(|e| map_rule_name("pattern", e))?;
         let ty = if token(s, TokenKind::Colon).is_ok() {
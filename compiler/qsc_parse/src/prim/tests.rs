@@ -215,6 +215,94 @@ fn pat_tuple_ty_discard() {
     );
 }
 
+#[test]
+fn pat_array() {
+    check(
+        pat,
+        "[foo, bar]",
+        &expect![[r#"
+        Pat _id_ [0-10]: Array:
+            Pat _id_ [1-4]: Bind:
+                Ident _id_ [1-4] "foo"
+            Pat _id_ [6-9]: Bind:
+                Ident _id_ [6-9] "bar""#]],
+    );
+}
+
+#[test]
+fn pat_array_empty() {
+    check(pat, "[]", &expect!["Pat _id_ [0-2]: Array:"]);
+}
+
+#[test]
+fn pat_array_rest() {
+    check(
+        pat,
+        "[foo : Qubit, ...rest : Qubit[]]",
+        &expect![[r#"
+        Pat _id_ [0-32]: Array:
+            Pat _id_ [1-12]: Bind:
+                Ident _id_ [1-4] "foo"
+                Type _id_ [7-12]: Path: Path _id_ [7-12] (Ident _id_ [7-12] "Qubit")
+            Rest: Pat _id_ [17-31]: Bind:
+                Ident _id_ [17-21] "rest"
+                Type _id_ [24-31]: Array: Type _id_ [24-29]: Path: Path _id_ [24-29] (Ident _id_ [24-29] "Qubit")"#]],
+    );
+}
+
+#[test]
+fn pat_array_only_rest() {
+    check(
+        pat,
+        "[...rest]",
+        &expect![[r#"
+        Pat _id_ [0-9]: Array:
+            Rest: Pat _id_ [4-8]: Bind:
+                Ident _id_ [4-8] "rest""#]],
+    );
+}
+
+#[test]
+fn pat_array_nested() {
+    check(
+        pat,
+        "[(a, b), [c, ..._]]",
+        &expect![[r#"
+        Pat _id_ [0-19]: Array:
+            Pat _id_ [1-7]: Tuple:
+                Pat _id_ [2-3]: Bind:
+                    Ident _id_ [2-3] "a"
+                Pat _id_ [5-6]: Bind:
+                    Ident _id_ [5-6] "b"
+            Pat _id_ [9-18]: Array:
+                Pat _id_ [10-11]: Bind:
+                    Ident _id_ [10-11] "c"
+                Rest: Pat _id_ [16-17]: Discard"#]],
+    );
+}
+
+#[test]
+fn pat_array_rest_not_last() {
+    check(
+        pat,
+        "[...rest, foo]",
+        &expect![[r"
+        Error(
+            Token(
+                Close(
+                    Bracket,
+                ),
+                Ident,
+                Span {
+                    lo: 10,
+                    hi: 13,
+                },
+            ),
+        )
+    "]],
+    );
+}
+
 #[test]
 fn pat_invalid() {
     check(
@@ -853,3 +853,22 @@ fn recover_missing_semicolon() {
             ]"#]],
     );
 }
+
+#[test]
+fn match_stmt_no_semi() {
+    check(
+        parse_block,
+        "{ match s { Shape.Empty => {} } let x = 1; }",
+        &expect![[r#"
+            Block _id_ [0-44]:
+                Stmt _id_ [2-31]: Expr: Expr _id_ [2-31]: Match:
+                    Expr _id_ [8-9]: Path: Path _id_ [8-9] (Ident _id_ [8-9] "s")
+                    MatchArm _id_ [12-29]:
+                        Pat _id_ [12-23]: Variant (Path _id_ [12-23] (Ident _id_ [12-17] "Shape") (Ident _id_ [18-23] "Empty"))
+                        Expr _id_ [27-29]: Expr Block: Block _id_ [27-29]: <empty>
+                Stmt _id_ [32-42]: Local (Immutable):
+                    Pat _id_ [36-37]: Bind:
+                        Ident _id_ [36-37] "x"
+                    Expr _id_ [40-41]: Lit: Int(1)"#]],
+    );
+}
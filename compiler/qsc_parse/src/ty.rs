@@ -6,7 +6,7 @@ mod tests;
 
 use super::{
     keyword::Keyword,
-    prim::{apos_ident, opt, path, seq, token},
+    prim::{apos_ident, ident, opt, path, seq, token},
     scan::ParserContext,
     Error, Parser, Result,
 };
@@ -17,6 +17,7 @@ use crate::{
 };
 use qsc_ast::ast::{
     CallableKind, Functor, FunctorExpr, FunctorExprKind, Ident, NodeId, SetOp, Ty, TyKind,
+    TypeParameter,
 };
 
 pub(super) fn ty(s: &mut ParserContext) -> Result<Ty> {
@@ -62,6 +63,30 @@ pub(super) fn param(s: &mut ParserContext) -> Result<Box<Ident>> {
     apos_ident(s)
 }
 
+pub(super) fn type_parameter(s: &mut ParserContext) -> Result<TypeParameter> {
+    throw_away_doc(s);
+    let lo = s.peek().span.lo;
+    let name = apos_ident(s)?;
+    let bounds = if token(s, TokenKind::Colon).is_ok() {
+        class_bounds(s)?
+    } else {
+        Box::default()
+    };
+    Ok(TypeParameter {
+        span: s.span(lo),
+        name,
+        bounds,
+    })
+}
+
+fn class_bounds(s: &mut ParserContext) -> Result<Box<[Box<Ident>]>> {
+    let mut bounds = vec![ident(s)?];
+    while token(s, TokenKind::ClosedBinOp(ClosedBinOp::Plus)).is_ok() {
+        bounds.push(ident(s)?);
+    }
+    Ok(bounds.into_boxed_slice())
+}
+
 fn array(s: &mut ParserContext) -> Result<()> {
     token(s, TokenKind::Open(Delim::Bracket))?;
     token(s, TokenKind::Close(Delim::Bracket))?;
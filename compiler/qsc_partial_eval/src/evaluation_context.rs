@@ -3,12 +3,12 @@
 
 use qsc_data_structures::functors::FunctorApp;
 use qsc_eval::{
-    val::{Result, Value},
+    val::{Result, Value, Var},
     Env, Variable,
 };
 use qsc_fir::fir::{LocalItemId, LocalVarId, PackageId};
 use qsc_rca::{RuntimeKind, ValueKind};
-use qsc_rir::rir::BlockId;
+use qsc_rir::rir::{BlockId, Variable as RirVariable};
 use rustc_hash::FxHashMap;
 
 /// Struct that keeps track of the active RIR blocks (where RIR instructions are added) and the active scopes (which
@@ -21,7 +21,7 @@ pub struct EvaluationContext {
 impl EvaluationContext {
     /// Creates a new evaluation context.
     pub fn new(package_id: PackageId, initial_block: BlockId) -> Self {
-        let entry_callable_scope = Scope::new(package_id, None, Vec::new());
+        let entry_callable_scope = Scope::new(package_id, None, None, Vec::new());
         Self {
             active_blocks: vec![BlockNode {
                 id: initial_block,
@@ -50,6 +50,12 @@ impl EvaluationContext {
             .expect("the evaluation context does not have a current scope")
     }
 
+    /// Determines whether instructions are currently being added to a block that is only reached at runtime depending
+    /// on dynamic values, which is the case within dynamic branches, dynamic loops and outlined callables.
+    pub fn is_within_dynamic_scope(&self) -> bool {
+        self.active_blocks.len() > 1
+    }
+
     /// Pops the currently active block.
     pub fn pop_block_node(&mut self) -> BlockNode {
         self.get_current_scope_mut().active_block_count -= 1;
@@ -58,6 +64,14 @@ impl EvaluationContext {
             .expect("there are no active blocks in the evaluation context")
     }
 
+    /// Pops the currently active (call) scope along with the entry block of the callable it was generating.
+    pub fn pop_callable_scope(&mut self) -> Scope {
+        self.active_blocks
+            .pop()
+            .expect("there are no active blocks in the evaluation context");
+        self.pop_scope()
+    }
+
     /// Pops the currently active (call) scope.
     pub fn pop_scope(&mut self) -> Scope {
         self.scopes
@@ -71,6 +85,16 @@ impl EvaluationContext {
         self.get_current_scope_mut().active_block_count += 1;
     }
 
+    /// Pushes a new (call) scope whose instructions are generated into the body of a separate callable, starting at
+    /// the specified entry block.
+    pub fn push_callable_scope(&mut self, s: Scope, entry_block: BlockId) {
+        self.scopes.push(s);
+        self.active_blocks.push(BlockNode {
+            id: entry_block,
+            successor: None,
+        });
+    }
+
     /// Pushes a new (call) scope.
     pub fn push_scope(&mut self, s: Scope) {
         self.scopes.push(s);
@@ -85,6 +109,16 @@ pub struct BlockNode {
     pub successor: Option<BlockId>,
 }
 
+/// Struct that represents a loop whose iterations are decided at runtime, so it is generated as RIR blocks with a
+/// back edge instead of being unrolled.
+#[derive(Clone, Copy)]
+pub struct LoopNode {
+    /// The block that evaluates the loop condition, where continue expressions jump to.
+    pub header: BlockId,
+    /// The block that follows the loop, where break expressions jump to.
+    pub exit: BlockId,
+}
+
 /// A call scope.
 pub struct Scope {
     /// The package ID of the callable.
@@ -99,13 +133,21 @@ pub struct Scope {
     active_block_count: usize,
     /// Map that holds the values of local variables.
     hybrid_vars: FxHashMap<LocalVarId, Value>,
+    /// Map from mutable local variables whose value is only known at runtime to the RIR variables that hold them.
+    mutable_vars: FxHashMap<LocalVarId, RirVariable>,
+    /// The loops that are currently being evaluated, from outermost to innermost, where loops that are unrolled have
+    /// no node.
+    active_loops: Vec<Option<LoopNode>>,
 }
 
 impl Scope {
     /// Creates a new call scope.
+    /// The controls of a controlled specialization are bound like any other argument, but are not part of the
+    /// argument value kinds since RCA does not model them as input parameters.
     pub fn new(
         package_id: PackageId,
         callable: Option<(LocalItemId, FunctorApp)>,
+        ctls: Option<Arg>,
         args: Vec<Arg>,
     ) -> Self {
         // Create the environment for the classical evaluator.
@@ -131,7 +173,15 @@ impl Scope {
         // Add the values to either the environment or the hybrid variables depending on whether the value is static or
         // dynamic.
         let mut hybrid_vars = FxHashMap::default();
-        let arg_runtime_kind_tuple = args.into_iter().zip(args_value_kind.iter());
+        let ctls_value_kind = ctls.as_ref().map(|ctls| match ctls {
+            Arg::Discard(value) | Arg::Var(_, Variable { value, .. }) => {
+                map_eval_value_to_value_kind(value)
+            }
+        });
+        let arg_runtime_kind_tuple = ctls
+            .into_iter()
+            .zip(ctls_value_kind.iter())
+            .chain(args.into_iter().zip(args_value_kind.iter()));
         for (arg, value_kind) in arg_runtime_kind_tuple {
             let Arg::Var(local_var_id, var) = arg else {
                 continue;
@@ -152,16 +202,43 @@ impl Scope {
             env,
             active_block_count: 1,
             hybrid_vars,
+            mutable_vars: FxHashMap::default(),
+            active_loops: Vec::new(),
         }
     }
 
+    /// Gets the innermost loop that is currently being evaluated, if any.
+    pub fn get_innermost_loop_node(&self) -> Option<LoopNode> {
+        self.active_loops.last().copied().flatten()
+    }
+
     /// Gets the value of a (hybrid) local variable.
+    /// Falls back to the classical environment for locals that were bound before becoming dynamic.
     pub fn get_local_var_value(&self, local_var_id: LocalVarId) -> &Value {
         self.hybrid_vars
             .get(&local_var_id)
+            .or_else(|| self.env.get(local_var_id).map(|var| &var.value))
             .expect("local variable value does not exist")
     }
 
+    /// Gets the RIR variable that holds the value of a mutable local variable, if any.
+    pub fn get_mutable_var(&self, local_var_id: LocalVarId) -> Option<RirVariable> {
+        self.mutable_vars.get(&local_var_id).copied()
+    }
+
+    /// Gets all the mutable local variables held in RIR variables.
+    pub fn get_mutable_vars(&self) -> Vec<(LocalVarId, RirVariable)> {
+        self.mutable_vars
+            .iter()
+            .map(|(local_var_id, var)| (*local_var_id, *var))
+            .collect()
+    }
+
+    /// Determines whether a local variable has been bound to a value.
+    pub fn is_local_var_bound(&self, local_var_id: LocalVarId) -> bool {
+        self.hybrid_vars.contains_key(&local_var_id) || self.env.get(local_var_id).is_some()
+    }
+
     /// Determines whether we are currently evaluating a branch within the scope.
     pub fn is_currently_evaluating_branch(&self) -> bool {
         self.active_block_count > 1
@@ -178,6 +255,52 @@ impl Scope {
     pub fn insert_local_var_value(&mut self, local_var_id: LocalVarId, value: Value) {
         self.hybrid_vars.insert(local_var_id, value);
     }
+
+    /// Updates the value of a local variable in both the hybrid variables map and the classical environment (if it is
+    /// bound there), so the classical evaluator reads the updated value.
+    pub fn update_local_var_value(&mut self, local_var_id: LocalVarId, value: Value) {
+        if let Some(env_var) = self.env.get_mut(local_var_id) {
+            env_var.value = value.clone();
+        }
+        self.hybrid_vars.insert(local_var_id, value);
+    }
+
+    /// Inserts the RIR variable that holds the value of a mutable local variable.
+    /// The local variable is bound to the RIR variable in both the hybrid variables map and the classical environment
+    /// (if it is bound there), so assignments made by the classical evaluator can be detected.
+    pub fn insert_mutable_var(&mut self, local_var_id: LocalVarId, var: RirVariable) {
+        let value = Value::Var(Var(var.variable_id.into()));
+        if let Some(env_var) = self.env.get_mut(local_var_id) {
+            env_var.value = value.clone();
+        }
+        self.hybrid_vars.insert(local_var_id, value);
+        self.mutable_vars.insert(local_var_id, var);
+    }
+
+    /// Pops the innermost loop that is currently being evaluated.
+    pub fn pop_loop_node(&mut self) {
+        self.active_loops
+            .pop()
+            .expect("there are no active loops in the scope");
+    }
+
+    /// Pushes a new loop, which has a node only if it is not unrolled.
+    pub fn push_loop_node(&mut self, loop_node: Option<LoopNode>) {
+        self.active_loops.push(loop_node);
+    }
+
+    /// Resets the classical environment value of a mutable local variable to the RIR variable that holds it.
+    /// Returns the value that the classical evaluator assigned to it, if it changed.
+    pub fn take_assigned_mutable_var_value(&mut self, local_var_id: LocalVarId) -> Option<Value> {
+        let var = self.mutable_vars.get(&local_var_id)?;
+        let var_value = Value::Var(Var(var.variable_id.into()));
+        let env_var = self.env.get_mut(local_var_id)?;
+        if env_var.value == var_value {
+            None
+        } else {
+            Some(std::mem::replace(&mut env_var.value, var_value))
+        }
+    }
 }
 
 /// A call argument.
@@ -194,6 +317,22 @@ impl Arg {
             Self::Var(_, var) => var.value,
         }
     }
+
+    /// Gets the underlying value of the argument.
+    pub fn value(&self) -> &Value {
+        match self {
+            Self::Discard(value) => value,
+            Self::Var(_, var) => &var.value,
+        }
+    }
+
+    /// Replaces the underlying value of the argument.
+    pub fn with_value(self, value: Value) -> Self {
+        match self {
+            Self::Discard(_) => Self::Discard(value),
+            Self::Var(local_var_id, var) => Self::Var(local_var_id, Variable { value, ..var }),
+        }
+    }
 }
 
 /// Represents the possible control flow options that can result from a branch.
@@ -208,6 +347,10 @@ pub enum BranchControlFlow {
 pub enum EvalControlFlow {
     Continue(Value),
     Return(Value),
+    /// A break out of the innermost loop.
+    LoopBreak,
+    /// A jump to the next iteration of the innermost loop.
+    LoopContinue,
 }
 
 impl EvalControlFlow {
@@ -215,16 +358,24 @@ impl EvalControlFlow {
     pub fn into_value(self) -> Value {
         match self {
             EvalControlFlow::Continue(value) | EvalControlFlow::Return(value) => value,
+            EvalControlFlow::LoopBreak | EvalControlFlow::LoopContinue => {
+                panic!("a loop jump does not have a value")
+            }
         }
     }
 
     /// Whether this evaluation control flow is a return.
     pub fn is_return(&self) -> bool {
         match self {
-            Self::Continue(_) => false,
+            Self::Continue(_) | Self::LoopBreak | Self::LoopContinue => false,
             Self::Return(_) => true,
         }
     }
+
+    /// Whether this evaluation control flow is a break or continue of the innermost loop.
+    pub fn is_loop_jump(&self) -> bool {
+        matches!(self, Self::LoopBreak | Self::LoopContinue)
+    }
 }
 
 fn map_eval_value_to_value_kind(value: &Value) -> ValueKind {
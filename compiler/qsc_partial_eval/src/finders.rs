@@ -0,0 +1,130 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use qsc_fir::{
+    fir::{
+        Block, BlockId, Expr, ExprId, ExprKind, LocalVarId, Package, PackageLookup, Pat, PatId,
+        Res, Stmt, StmtId,
+    },
+    ty::Ty,
+    visit::{walk_expr, Visitor},
+};
+
+/// Finds the local variables that are assigned to within the specified expressions and blocks, along with their types.
+pub fn find_assigned_locals(
+    package: &Package,
+    expr_ids: &[ExprId],
+    block_ids: &[BlockId],
+) -> Vec<(LocalVarId, Ty)> {
+    let mut finder = AssignedLocalsFinder {
+        package,
+        locals: Vec::new(),
+    };
+    for expr_id in expr_ids {
+        finder.visit_expr(*expr_id);
+    }
+    for block_id in block_ids {
+        finder.visit_block(*block_id);
+    }
+    finder.locals
+}
+
+/// Finds the break and continue expressions within a loop block that jump to the loop itself, which excludes the ones
+/// that belong to nested loops.
+pub fn find_loop_jumps(package: &Package, block_id: BlockId) -> Vec<ExprId> {
+    let mut finder = LoopJumpsFinder {
+        package,
+        jumps: Vec::new(),
+    };
+    finder.visit_block(block_id);
+    finder.jumps
+}
+
+struct AssignedLocalsFinder<'a> {
+    package: &'a Package,
+    locals: Vec<(LocalVarId, Ty)>,
+}
+
+impl AssignedLocalsFinder<'_> {
+    fn add_assignee(&mut self, assignee_expr_id: ExprId) {
+        let assignee_expr = self.package.get_expr(assignee_expr_id);
+        match &assignee_expr.kind {
+            ExprKind::Var(Res::Local(local_var_id), _)
+                if !self.locals.iter().any(|(id, _)| id == local_var_id) =>
+            {
+                self.locals.push((*local_var_id, assignee_expr.ty.clone()));
+            }
+            ExprKind::Tuple(exprs) => {
+                for expr_id in exprs {
+                    self.add_assignee(*expr_id);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for AssignedLocalsFinder<'a> {
+    fn visit_expr(&mut self, expr_id: ExprId) {
+        let expr = self.get_expr(expr_id);
+        match &expr.kind {
+            ExprKind::Assign(assignee_expr_id, _)
+            | ExprKind::AssignOp(_, assignee_expr_id, _)
+            | ExprKind::AssignField(assignee_expr_id, _, _)
+            | ExprKind::AssignIndex(assignee_expr_id, _, _) => {
+                self.add_assignee(*assignee_expr_id);
+            }
+            _ => {}
+        }
+        walk_expr(self, expr_id);
+    }
+
+    fn get_block(&self, id: BlockId) -> &'a Block {
+        self.package.get_block(id)
+    }
+
+    fn get_expr(&self, id: ExprId) -> &'a Expr {
+        self.package.get_expr(id)
+    }
+
+    fn get_pat(&self, id: PatId) -> &'a Pat {
+        self.package.get_pat(id)
+    }
+
+    fn get_stmt(&self, id: StmtId) -> &'a Stmt {
+        self.package.get_stmt(id)
+    }
+}
+
+struct LoopJumpsFinder<'a> {
+    package: &'a Package,
+    jumps: Vec<ExprId>,
+}
+
+impl<'a> Visitor<'a> for LoopJumpsFinder<'a> {
+    fn visit_expr(&mut self, expr_id: ExprId) {
+        let expr = self.get_expr(expr_id);
+        match &expr.kind {
+            ExprKind::Break | ExprKind::Continue => self.jumps.push(expr_id),
+            // Jumps within a nested loop only affect the nested loop.
+            ExprKind::While(..) => {}
+            _ => walk_expr(self, expr_id),
+        }
+    }
+
+    fn get_block(&self, id: BlockId) -> &'a Block {
+        self.package.get_block(id)
+    }
+
+    fn get_expr(&self, id: ExprId) -> &'a Expr {
+        self.package.get_expr(id)
+    }
+
+    fn get_pat(&self, id: PatId) -> &'a Pat {
+        self.package.get_pat(id)
+    }
+
+    fn get_stmt(&self, id: StmtId) -> &'a Stmt {
+        self.package.get_stmt(id)
+    }
+}
@@ -6,11 +6,13 @@
 //! not purely classical.
 
 mod evaluation_context;
+mod finders;
 mod management;
 
 use evaluation_context::{
-    Arg, BlockNode, BranchControlFlow, EvalControlFlow, EvaluationContext, Scope,
+    Arg, BlockNode, BranchControlFlow, EvalControlFlow, EvaluationContext, LoopNode, Scope,
 };
+use finders::{find_assigned_locals, find_loop_jumps};
 use management::{QuantumIntrinsicsChecker, ResourceManager};
 use miette::Diagnostic;
 use qsc_data_structures::span::Span;
@@ -24,9 +26,9 @@ use qsc_eval::{
 use qsc_fir::{
     fir::{
         self, BinOp, Block, BlockId, CallableDecl, CallableImpl, ExecGraphNode, Expr, ExprId,
-        ExprKind, Global, Ident, PackageId, PackageStore, PackageStoreLookup, Pat, PatId, PatKind,
-        Res, SpecDecl, SpecImpl, Stmt, StmtId, StmtKind, StoreBlockId, StoreExprId, StoreItemId,
-        StorePatId, StoreStmtId,
+        ExprKind, Field, Global, Ident, LocalVarId, Mutability, PackageId, PackageLookup,
+        PackageStore, PackageStoreLookup, Pat, PatId, PatKind, Res, SpecDecl, SpecImpl, Stmt,
+        StmtId, StmtKind, StoreBlockId, StoreExprId, StoreItemId, StorePatId, StoreStmtId, UnOp,
     },
     ty::{Prim, Ty},
 };
@@ -34,12 +36,12 @@ use qsc_rca::{ComputeKind, ComputePropertiesLookup, PackageStoreComputePropertie
 use qsc_rir::{
     builder,
     rir::{
-        self, Callable, CallableId, CallableType, ConditionCode, Instruction, Literal, Operand,
-        Program,
+        self, Callable, CallableId, CallableType, ConditionCode, FcmpConditionCode, Instruction,
+        Literal, Operand, Program,
     },
 };
 use rustc_hash::FxHashMap;
-use std::{collections::hash_map::Entry, rc::Rc, result::Result};
+use std::{collections::hash_map::Entry, ops, rc::Rc, result::Result};
 use thiserror::Error;
 
 /// Partially evaluates a program with the specified entry expression.
@@ -80,6 +82,11 @@ pub enum Error {
     Unimplemented(String, #[label] Span),
 }
 
+enum OutputRecordingError {
+    ResultLiteral,
+    DynamicResultVariable,
+}
+
 /// An entry to the program to be partially evaluated.
 pub struct ProgramEntry {
     /// The execution graph that corresponds to the entry expression.
@@ -97,6 +104,20 @@ struct PartialEvaluator<'a> {
     eval_context: EvaluationContext,
     program: Program,
     entry: &'a ProgramEntry,
+    outlined_callables: Vec<(OutlinedCallableKey, CallableId)>,
+    outlined_callable_depth: usize,
+    /// The qubit arrays allocated at runtime that have not been released yet, with the variable that holds each of them.
+    runtime_qubit_arrays: Vec<(Rc<Vec<Value>>, rir::Variable)>,
+}
+
+/// Identifies a callable generated with its own body, which is reused by all the calls to the same specialization with
+/// the same static arguments.
+#[derive(PartialEq)]
+struct OutlinedCallableKey {
+    store_item_id: StoreItemId,
+    functor_app: FunctorApp,
+    /// The value of each argument that is specialized into the body, or `None` for arguments passed as parameters.
+    static_args: Vec<Option<Value>>,
 }
 
 impl<'a> PartialEvaluator<'a> {
@@ -116,6 +137,7 @@ impl<'a> PartialEvaluator<'a> {
         let entry_point = rir::Callable {
             name: "main".into(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: None,
             body: Some(entry_block_id),
             call_type: CallableType::Regular,
@@ -134,10 +156,13 @@ impl<'a> PartialEvaluator<'a> {
             callables_map: FxHashMap::default(),
             program,
             entry,
+            outlined_callables: Vec::new(),
+            outlined_callable_depth: 0,
+            runtime_qubit_arrays: Vec::new(),
         }
     }
 
-    fn bind_value_to_pat(&mut self, pat_id: PatId, value: Value) {
+    fn bind_value_to_pat(&mut self, pat_id: PatId, value: Value) -> Result<(), Error> {
         let pat = self.get_pat(pat_id);
         match &pat.kind {
             PatKind::Bind(ident) => {
@@ -147,13 +172,36 @@ impl<'a> PartialEvaluator<'a> {
                 let tup = value.unwrap_tuple();
                 assert!(pats.len() == tup.len());
                 for (pat_id, value) in pats.iter().zip(tup.iter()) {
-                    self.bind_value_to_pat(*pat_id, value.clone());
+                    self.bind_value_to_pat(*pat_id, value.clone())?;
+                }
+            }
+            PatKind::Array(pats, rest) => {
+                let array = value.unwrap_array();
+                let expected = match rest {
+                    Some(_) if array.len() < pats.len() => Some(format!("at least {}", pats.len())),
+                    None if array.len() != pats.len() => Some(pats.len().to_string()),
+                    _ => None,
+                };
+                if let Some(expected) = expected {
+                    let message = format!(
+                        "array pattern expects {expected} items, found {}",
+                        array.len()
+                    );
+                    return Err(Error::EvaluationFailed(message, pat.span));
+                }
+                for (pat_id, value) in pats.iter().zip(array.iter()) {
+                    self.bind_value_to_pat(*pat_id, value.clone())?;
+                }
+                if let Some(rest) = rest {
+                    let rest_value = Value::Array(array[pats.len()..].to_vec().into());
+                    self.bind_value_to_pat(*rest, rest_value)?;
                 }
             }
             PatKind::Discard => {
                 // Nothing to bind to.
             }
         }
+        Ok(())
     }
 
     fn bind_value_to_ident(&mut self, ident: &Ident, value: Value) {
@@ -188,6 +236,7 @@ impl<'a> PartialEvaluator<'a> {
         Callable {
             name,
             input_type,
+            params: Vec::new(),
             output_type,
             body,
             call_type,
@@ -203,12 +252,16 @@ impl<'a> PartialEvaluator<'a> {
     fn eval(mut self) -> Result<Program, Error> {
         // Evaluate the entry-point expression.
         let ret_val = self.try_eval_expr(self.entry.expr.expr)?.into_value();
+        let entry_expr = self.get_expr(self.entry.expr.expr);
         let output_recording: Vec<Instruction> = self
-            .generate_output_recording_instructions(
-                ret_val,
-                &self.get_expr(self.entry.expr.expr).ty,
-            )
-            .map_err(|()| Error::OutputResultLiteral(self.get_expr(self.entry.expr.expr).span))?;
+            .generate_output_recording_instructions(ret_val, &entry_expr.ty)
+            .map_err(|error| match error {
+                OutputRecordingError::ResultLiteral => Error::OutputResultLiteral(entry_expr.span),
+                OutputRecordingError::DynamicResultVariable => Error::Unimplemented(
+                    "Output Recording of Dynamic Result Variable".to_string(),
+                    entry_expr.span,
+                ),
+            })?;
 
         // Insert the return expression and return the generated program.
         let current_block = self.get_current_rir_block_mut();
@@ -251,6 +304,7 @@ impl<'a> PartialEvaluator<'a> {
                 let StepResult::Return(value) = step_result else {
                     panic!("evaluating a classical expression should always return a value");
                 };
+                self.store_assigned_mutable_vars();
 
                 // Figure out the control flow kind.
                 let scope = self.eval_context.get_current_scope();
@@ -285,6 +339,7 @@ impl<'a> PartialEvaluator<'a> {
                 let StepResult::Return(value) = step_result else {
                     panic!("evaluating a classical expression should always return a value");
                 };
+                self.store_assigned_mutable_vars();
 
                 // Figure out the control flow kind.
                 let scope = self.eval_context.get_current_scope();
@@ -309,34 +364,33 @@ impl<'a> PartialEvaluator<'a> {
             ExprKind::ArrayRepeat(_, _) => {
                 Err(Error::Unimplemented("Array Repeat".to_string(), expr.span))
             }
-            ExprKind::Assign(_, _) => Err(Error::Unimplemented(
-                "Assignment Expr".to_string(),
-                expr.span,
-            )),
-            ExprKind::AssignField(_, _, _) => Err(Error::Unimplemented(
-                "Field Assignment Expr".to_string(),
-                expr.span,
-            )),
-            ExprKind::AssignIndex(_, _, _) => Err(Error::Unimplemented(
-                "Assignment Index Expr".to_string(),
-                expr.span,
-            )),
-            ExprKind::AssignOp(_, _, _) => Err(Error::Unimplemented(
-                "Assignment Op Expr".to_string(),
-                expr.span,
-            )),
+            ExprKind::Assign(lhs_expr_id, rhs_expr_id) => {
+                self.eval_expr_assign(expr_id, *lhs_expr_id, *rhs_expr_id)
+            }
+            ExprKind::AssignField(record_expr_id, field, replace_expr_id) => {
+                self.eval_expr_assign_field(expr_id, *record_expr_id, field, *replace_expr_id)
+            }
+            ExprKind::AssignIndex(array_expr_id, index_expr_id, replace_expr_id) => self
+                .eval_expr_assign_index(expr_id, *array_expr_id, *index_expr_id, *replace_expr_id),
+            ExprKind::AssignOp(bin_op, lhs_expr_id, rhs_expr_id) => {
+                self.eval_expr_assign_op(expr_id, *bin_op, *lhs_expr_id, *rhs_expr_id)
+            }
             ExprKind::BinOp(bin_op, lhs_expr_id, rhs_expr_id) => {
                 self.eval_expr_bin_op(expr_id, *bin_op, *lhs_expr_id, *rhs_expr_id)
             }
             ExprKind::Block(block_id) => self.try_eval_block(*block_id),
+            ExprKind::Break => Ok(EvalControlFlow::LoopBreak),
             ExprKind::Call(callee_expr_id, args_expr_id) => {
                 self.eval_expr_call(*callee_expr_id, *args_expr_id)
             }
             ExprKind::Closure(_, _) => {
                 panic!("instruction generation for closure expressions is unsupported")
             }
+            ExprKind::Continue => Ok(EvalControlFlow::LoopContinue),
             ExprKind::Fail(_) => panic!("instruction generation for fail expression is invalid"),
-            ExprKind::Field(_, _) => Err(Error::Unimplemented("Field Expr".to_string(), expr.span)),
+            ExprKind::Field(record_expr_id, field) => {
+                self.eval_expr_field(expr_id, *record_expr_id, field)
+            }
             ExprKind::Hole => panic!("instruction generation for hole expressions is invalid"),
             ExprKind::If(condition_expr_id, body_expr_id, otherwise_expr_id) => self.eval_expr_if(
                 expr_id,
@@ -344,7 +398,9 @@ impl<'a> PartialEvaluator<'a> {
                 *body_expr_id,
                 *otherwise_expr_id,
             ),
-            ExprKind::Index(_, _) => Err(Error::Unimplemented("Index Expr".to_string(), expr.span)),
+            ExprKind::Index(array_expr_id, index_expr_id) => {
+                self.eval_expr_index(expr_id, *array_expr_id, *index_expr_id)
+            }
             ExprKind::Lit(_) => panic!("instruction generation for literal expressions is invalid"),
             ExprKind::Range(_, _, _) => {
                 panic!("instruction generation for range expressions is invalid")
@@ -354,18 +410,17 @@ impl<'a> PartialEvaluator<'a> {
                 panic!("instruction generation for string expressions is invalid")
             }
             ExprKind::Tuple(exprs) => self.eval_expr_tuple(exprs),
-            ExprKind::UnOp(_, _) => Err(Error::Unimplemented("Unary Expr".to_string(), expr.span)),
-            ExprKind::UpdateField(_, _, _) => Err(Error::Unimplemented(
-                "Updated Field Expr".to_string(),
-                expr.span,
-            )),
-            ExprKind::UpdateIndex(_, _, _) => Err(Error::Unimplemented(
-                "Update Index Expr".to_string(),
-                expr.span,
-            )),
+            ExprKind::UnOp(un_op, operand_expr_id) => {
+                self.eval_expr_un_op(expr_id, *un_op, *operand_expr_id)
+            }
+            ExprKind::UpdateField(record_expr_id, field, replace_expr_id) => {
+                self.eval_expr_update_field(expr_id, *record_expr_id, field, *replace_expr_id)
+            }
+            ExprKind::UpdateIndex(array_expr_id, index_expr_id, replace_expr_id) => self
+                .eval_expr_update_index(expr_id, *array_expr_id, *index_expr_id, *replace_expr_id),
             ExprKind::Var(res, _) => Ok(EvalControlFlow::Continue(self.eval_expr_var(res))),
             ExprKind::While(condition_expr_id, body_block_id) => {
-                self.eval_expr_while(*condition_expr_id, *body_block_id)
+                self.eval_expr_while(expr_id, *condition_expr_id, *body_block_id)
             }
         }
     }
@@ -378,17 +433,24 @@ impl<'a> PartialEvaluator<'a> {
                 let control_flow = self.try_eval_expr(expr_id)?;
                 match control_flow {
                     EvalControlFlow::Continue(_) => Ok(EvalControlFlow::Continue(Value::unit())),
-                    EvalControlFlow::Return(_) => Ok(control_flow),
+                    EvalControlFlow::Return(_)
+                    | EvalControlFlow::LoopBreak
+                    | EvalControlFlow::LoopContinue => Ok(control_flow),
                 }
             }
-            StmtKind::Local(_, pat_id, expr_id) => {
+            StmtKind::Local(mutability, pat_id, expr_id) => {
                 let control_flow = self.try_eval_expr(expr_id)?;
                 match control_flow {
                     EvalControlFlow::Continue(value) => {
-                        self.bind_value_to_pat(pat_id, value);
+                        self.bind_value_to_pat(pat_id, value)?;
+                        if matches!(mutability, Mutability::Mutable) {
+                            self.promote_dynamic_mutable_bindings(pat_id);
+                        }
                         Ok(EvalControlFlow::Continue(Value::unit()))
                     }
-                    EvalControlFlow::Return(_) => Ok(control_flow),
+                    EvalControlFlow::Return(_)
+                    | EvalControlFlow::LoopBreak
+                    | EvalControlFlow::LoopContinue => Ok(control_flow),
                 }
             }
             StmtKind::Item(_) => {
@@ -398,6 +460,120 @@ impl<'a> PartialEvaluator<'a> {
         }
     }
 
+    fn eval_expr_assign(
+        &mut self,
+        assign_expr_id: ExprId,
+        lhs_expr_id: ExprId,
+        rhs_expr_id: ExprId,
+    ) -> Result<EvalControlFlow, Error> {
+        if !self.supports_mutable_vars() {
+            let assign_expr = self.get_expr(assign_expr_id);
+            return Err(Error::Unimplemented(
+                "Assignment Expr".to_string(),
+                assign_expr.span,
+            ));
+        }
+
+        let rhs_control_flow = self.try_eval_expr(rhs_expr_id)?;
+        if rhs_control_flow.is_return() {
+            let rhs_expr = self.get_expr(rhs_expr_id);
+            return Err(Error::Unexpected(
+                "embedded return in assignment".to_string(),
+                rhs_expr.span,
+            ));
+        }
+
+        self.update_bindings(lhs_expr_id, rhs_control_flow.into_value())?;
+        Ok(EvalControlFlow::Continue(Value::unit()))
+    }
+
+    fn eval_expr_assign_op(
+        &mut self,
+        assign_op_expr_id: ExprId,
+        bin_op: BinOp,
+        lhs_expr_id: ExprId,
+        rhs_expr_id: ExprId,
+    ) -> Result<EvalControlFlow, Error> {
+        // Only mutable local variables whose value can be held in a RIR variable can be updated at runtime.
+        let assign_op_expr = self.get_expr(assign_op_expr_id);
+        let lhs_expr = self.get_expr(lhs_expr_id);
+        let ExprKind::Var(Res::Local(local_var_id), _) = &lhs_expr.kind else {
+            return Err(Error::Unimplemented(
+                "Assignment Op Expr".to_string(),
+                assign_op_expr.span,
+            ));
+        };
+        if matches!(bin_op, BinOp::Add) && matches!(lhs_expr.ty, Ty::Array(_)) {
+            return self.eval_array_append(*local_var_id, rhs_expr_id);
+        }
+        if !self.supports_mutable_vars() {
+            return Err(Error::Unimplemented(
+                "Assignment Op Expr".to_string(),
+                assign_op_expr.span,
+            ));
+        }
+        let Some(lhs_var) = self.get_or_promote_mutable_var(*local_var_id, &lhs_expr.ty) else {
+            return Err(Error::Unimplemented(
+                "Assignment Op Expr".to_string(),
+                assign_op_expr.span,
+            ));
+        };
+
+        let rhs_control_flow = self.try_eval_expr(rhs_expr_id)?;
+        if rhs_control_flow.is_return() {
+            let rhs_expr = self.get_expr(rhs_expr_id);
+            return Err(Error::Unexpected(
+                "embedded return in assignment".to_string(),
+                rhs_expr.span,
+            ));
+        }
+        let rhs_ty = map_fir_type_to_rir_variable_type(&self.get_expr(rhs_expr_id).ty);
+        let rhs_operand =
+            self.map_eval_value_to_stored_operand(&rhs_control_flow.into_value(), rhs_ty);
+
+        // Compute the updated value and store it back into the variable that holds the local.
+        let variable = self.eval_bin_op(
+            bin_op,
+            Operand::Variable(lhs_var),
+            rhs_operand,
+            lhs_var.ty,
+            assign_op_expr.span,
+        )?;
+        let store_ins = Instruction::Store(Operand::Variable(variable), lhs_var);
+        self.get_current_rir_block_mut().0.push(store_ins);
+        Ok(EvalControlFlow::Continue(Value::unit()))
+    }
+
+    /// Appends to a mutable array local. Arrays cannot be held in RIR variables, but the value bound to the local is
+    /// known even when its items are dynamic, such as the qubits of arrays allocated at runtime.
+    fn eval_array_append(
+        &mut self,
+        local_var_id: LocalVarId,
+        rhs_expr_id: ExprId,
+    ) -> Result<EvalControlFlow, Error> {
+        let rhs_control_flow = self.try_eval_expr(rhs_expr_id)?;
+        if rhs_control_flow.is_return() {
+            let rhs_expr = self.get_expr(rhs_expr_id);
+            return Err(Error::Unexpected(
+                "embedded return in assignment".to_string(),
+                rhs_expr.span,
+            ));
+        }
+        let scope = self.eval_context.get_current_scope_mut();
+        let lhs_array = scope
+            .get_local_var_value(local_var_id)
+            .clone()
+            .unwrap_array();
+        let rhs_array = rhs_control_flow.into_value().unwrap_array();
+        let array = lhs_array
+            .iter()
+            .chain(rhs_array.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        scope.update_local_var_value(local_var_id, Value::Array(array.into()));
+        Ok(EvalControlFlow::Continue(Value::unit()))
+    }
+
     #[allow(clippy::similar_names)]
     fn eval_expr_bin_op(
         &mut self,
@@ -425,47 +601,153 @@ impl<'a> PartialEvaluator<'a> {
             ));
         }
 
+        // Operations on static values, such as comparisons against the length of an array of dynamic qubits, are
+        // computed directly.
+        let lhs_value = lhs_control_flow.into_value();
+        let rhs_value = rhs_control_flow.into_value();
+        if let Some(value) = eval_static_bin_op(bin_op, &lhs_value, &rhs_value) {
+            return Ok(EvalControlFlow::Continue(value));
+        }
+
         // Get the operands to use when generating the binary operation instruction depending on the type of the
         // expression's value.
-        let lhs_value = lhs_control_flow.into_value();
         let lhs_operand = if let Value::Result(result) = lhs_value {
             self.eval_result_as_bool_operand(result)
         } else {
-            map_eval_value_to_rir_operand(&lhs_value)
+            let lhs_ty = map_fir_type_to_rir_variable_type(&self.get_expr(lhs_expr_id).ty);
+            map_eval_value_to_typed_rir_operand(&lhs_value, lhs_ty)
         };
-        let rhs_value = rhs_control_flow.into_value();
         let rhs_operand = if let Value::Result(result) = rhs_value {
             self.eval_result_as_bool_operand(result)
         } else {
-            map_eval_value_to_rir_operand(&rhs_value)
+            let rhs_ty = map_fir_type_to_rir_variable_type(&self.get_expr(rhs_expr_id).ty);
+            map_eval_value_to_typed_rir_operand(&rhs_value, rhs_ty)
         };
 
-        // Create a variable to store the result of the expression.
+        // Generate the instruction and return the variable that holds its result as a value.
         let bin_op_expr = self.get_expr(bin_op_expr_id);
-        let variable_id = self.resource_manager.next_var();
         let variable_ty = map_fir_type_to_rir_type(&bin_op_expr.ty);
+        let variable = self.eval_bin_op(
+            bin_op,
+            lhs_operand,
+            rhs_operand,
+            variable_ty,
+            bin_op_expr.span,
+        )?;
+        let value = Value::Var(Var(variable.variable_id.into()));
+        Ok(EvalControlFlow::Continue(value))
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn eval_bin_op(
+        &mut self,
+        bin_op: BinOp,
+        lhs_operand: Operand,
+        rhs_operand: Operand,
+        variable_ty: rir::Ty,
+        span: Span,
+    ) -> Result<rir::Variable, Error> {
+        // Create a variable to store the result of the operation.
+        let variable_id = self.resource_manager.next_var();
         let variable = rir::Variable {
             variable_id,
             ty: variable_ty,
         };
 
-        // Create the binary operation instruction and add it to the current block.
-        let instruction = match bin_op {
-            BinOp::Eq => Instruction::Icmp(ConditionCode::Eq, lhs_operand, rhs_operand, variable),
-            BinOp::Neq => Instruction::Icmp(ConditionCode::Ne, lhs_operand, rhs_operand, variable),
+        // Create the binary operation instruction and add it to the current block. Double operands use the
+        // floating-point instructions, where inequality is unordered so that it holds for NaN operands.
+        let instruction = match (bin_op, lhs_operand.get_type()) {
+            (BinOp::Add, rir::Ty::Double) => Instruction::Fadd(lhs_operand, rhs_operand, variable),
+            (BinOp::Sub, rir::Ty::Double) => Instruction::Fsub(lhs_operand, rhs_operand, variable),
+            (BinOp::Mul, rir::Ty::Double) => Instruction::Fmul(lhs_operand, rhs_operand, variable),
+            (BinOp::Div, rir::Ty::Double) => Instruction::Fdiv(lhs_operand, rhs_operand, variable),
+            (BinOp::Eq, rir::Ty::Double) => Instruction::Fcmp(
+                FcmpConditionCode::OrderedAndEqual,
+                lhs_operand,
+                rhs_operand,
+                variable,
+            ),
+            (BinOp::Neq, rir::Ty::Double) => Instruction::Fcmp(
+                FcmpConditionCode::UnorderedOrNotEqual,
+                lhs_operand,
+                rhs_operand,
+                variable,
+            ),
+            (BinOp::Gt, rir::Ty::Double) => Instruction::Fcmp(
+                FcmpConditionCode::OrderedAndGreaterThan,
+                lhs_operand,
+                rhs_operand,
+                variable,
+            ),
+            (BinOp::Gte, rir::Ty::Double) => Instruction::Fcmp(
+                FcmpConditionCode::OrderedAndGreaterThanOrEqual,
+                lhs_operand,
+                rhs_operand,
+                variable,
+            ),
+            (BinOp::Lt, rir::Ty::Double) => Instruction::Fcmp(
+                FcmpConditionCode::OrderedAndLessThan,
+                lhs_operand,
+                rhs_operand,
+                variable,
+            ),
+            (BinOp::Lte, rir::Ty::Double) => Instruction::Fcmp(
+                FcmpConditionCode::OrderedAndLessThanOrEqual,
+                lhs_operand,
+                rhs_operand,
+                variable,
+            ),
+            (BinOp::Eq, _) => {
+                Instruction::Icmp(ConditionCode::Eq, lhs_operand, rhs_operand, variable)
+            }
+            (BinOp::Neq, _) => {
+                Instruction::Icmp(ConditionCode::Ne, lhs_operand, rhs_operand, variable)
+            }
+            (BinOp::Add, rir::Ty::Integer) => Instruction::Add(lhs_operand, rhs_operand, variable),
+            (BinOp::Sub, rir::Ty::Integer) => Instruction::Sub(lhs_operand, rhs_operand, variable),
+            (BinOp::Mul, rir::Ty::Integer) => Instruction::Mul(lhs_operand, rhs_operand, variable),
+            (BinOp::Div, rir::Ty::Integer) => Instruction::Sdiv(lhs_operand, rhs_operand, variable),
+            (BinOp::Mod, rir::Ty::Integer) => Instruction::Srem(lhs_operand, rhs_operand, variable),
+            (BinOp::Shl, rir::Ty::Integer) => Instruction::Shl(lhs_operand, rhs_operand, variable),
+            (BinOp::Shr, rir::Ty::Integer) => Instruction::Ashr(lhs_operand, rhs_operand, variable),
+            (BinOp::AndB, rir::Ty::Integer) => {
+                Instruction::BitwiseAnd(lhs_operand, rhs_operand, variable)
+            }
+            (BinOp::OrB, rir::Ty::Integer) => {
+                Instruction::BitwiseOr(lhs_operand, rhs_operand, variable)
+            }
+            (BinOp::XorB, rir::Ty::Integer) => {
+                Instruction::BitwiseXor(lhs_operand, rhs_operand, variable)
+            }
+            (BinOp::Gt, rir::Ty::Integer) => {
+                Instruction::Icmp(ConditionCode::Sgt, lhs_operand, rhs_operand, variable)
+            }
+            (BinOp::Gte, rir::Ty::Integer) => {
+                Instruction::Icmp(ConditionCode::Sge, lhs_operand, rhs_operand, variable)
+            }
+            (BinOp::Lt, rir::Ty::Integer) => {
+                Instruction::Icmp(ConditionCode::Slt, lhs_operand, rhs_operand, variable)
+            }
+            (BinOp::Lte, rir::Ty::Integer) => {
+                Instruction::Icmp(ConditionCode::Sle, lhs_operand, rhs_operand, variable)
+            }
+            // Both operands have already been evaluated at this point, so logical operations do not short-circuit.
+            (BinOp::AndL, rir::Ty::Boolean) => {
+                Instruction::LogicalAnd(lhs_operand, rhs_operand, variable)
+            }
+            (BinOp::OrL, rir::Ty::Boolean) => {
+                Instruction::LogicalOr(lhs_operand, rhs_operand, variable)
+            }
             _ => {
                 return Err(Error::Unimplemented(
                     format!("BinOp Expr ({bin_op:?})"),
-                    bin_op_expr.span,
+                    span,
                 ))
             }
         };
         let current_block = self.get_current_rir_block_mut();
         current_block.0.push(instruction);
-
-        // Return the variable as a value.
-        let value = Value::Var(Var(variable_id.into()));
-        Ok(EvalControlFlow::Continue(value))
+        Ok(variable)
     }
 
     fn eval_expr_call(
@@ -511,13 +793,37 @@ impl<'a> PartialEvaluator<'a> {
                 callable_decl,
                 args_control_flow.into_value(),
             ),
-            CallableImpl::Spec(spec_impl) => self.eval_expr_call_to_spec(
-                store_item_id,
-                functor_app,
-                spec_impl,
-                callable_decl.input,
-                args_control_flow.into_value(),
-            )?,
+            CallableImpl::Spec(spec_impl) => {
+                let args_value = args_control_flow.into_value();
+                if let Some(value) = self.try_eval_runtime_qubit_array_call(
+                    store_item_id,
+                    callable_decl,
+                    &args_value,
+                ) {
+                    value
+                } else if self.should_outline_call(
+                    store_item_id,
+                    callable_decl,
+                    functor_app,
+                    &args_value,
+                ) {
+                    self.eval_expr_call_to_outlined_spec(
+                        store_item_id,
+                        functor_app,
+                        callable_decl,
+                        spec_impl,
+                        args_value,
+                    )?
+                } else {
+                    self.eval_expr_call_to_spec(
+                        store_item_id,
+                        functor_app,
+                        spec_impl,
+                        callable_decl.input,
+                        args_value,
+                    )?
+                }
+            }
         };
         Ok(EvalControlFlow::Continue(value))
     }
@@ -533,9 +839,9 @@ impl<'a> PartialEvaluator<'a> {
             // Qubit allocations and measurements have special handling.
             "__quantum__rt__qubit_allocate" => self.allocate_qubit(),
             "__quantum__rt__qubit_release" => self.release_qubit(args_value),
-            "__quantum__qis__m__body" => self.measure_qubit(builder::mz_decl(), args_value),
+            "__quantum__qis__m__body" => self.measure_qubit(builder::mz_decl(), &args_value),
             "__quantum__qis__mresetz__body" => {
-                self.measure_qubit(builder::mresetz_decl(), args_value)
+                self.measure_qubit(builder::mresetz_decl(), &args_value)
             }
             // The following operations should be conditionally compiled out for all targets for which QIR generation is
             // supported.
@@ -543,6 +849,27 @@ impl<'a> PartialEvaluator<'a> {
                 "`{}` is not a supported by partial evaluation",
                 callable_decl.name.name
             ),
+            // Conversions between integers and doubles have dedicated instructions.
+            "IntAsDouble" => self.eval_conversion(
+                &args_value,
+                rir::Ty::Integer,
+                rir::Ty::Double,
+                Instruction::Sitofp,
+            ),
+            "Truncate" => self.eval_conversion(
+                &args_value,
+                rir::Ty::Double,
+                rir::Ty::Integer,
+                Instruction::Fptosi,
+            ),
+            // The length of an array is known even if its items are dynamic.
+            "Length" => Value::Int(
+                args_value
+                    .unwrap_array()
+                    .len()
+                    .try_into()
+                    .expect("array length should fit in an integer"),
+            ),
             // The following intrinsic operations and functions are no-ops.
             "BeginEstimateCaching" => Value::Bool(true),
             "DumpRegister"
@@ -564,6 +891,7 @@ impl<'a> PartialEvaluator<'a> {
 
         // Check if the callable is already in the program, and if not add it.
         let callable = self.create_intrinsic_callable(store_item_id, callable_decl);
+        let input_type = callable.input_type.clone();
         let callable_id = self.get_or_insert_callable(callable);
 
         // Resove the call arguments, create the call instruction and insert it to the current block.
@@ -573,7 +901,8 @@ impl<'a> PartialEvaluator<'a> {
         );
         let args_operands = args
             .into_iter()
-            .map(|arg| map_eval_value_to_rir_operand(&arg.into_value()))
+            .zip(input_type)
+            .map(|(arg, ty)| map_eval_value_to_typed_rir_operand(&arg.into_value(), ty))
             .collect();
 
         let instruction = Instruction::Call(callable_id, args_operands, None);
@@ -593,10 +922,33 @@ impl<'a> PartialEvaluator<'a> {
         let spec_decl = get_spec_decl(spec_impl, functor_app);
 
         // Create new call scope.
+        // Each controlled functor wraps the arguments in a tuple with its controls, which are collected into the
+        // single array bound by the input of the controlled specialization.
+        let mut args_value = args_value;
+        let ctls = spec_decl.input.map(|spec_pat| {
+            let mut ctls = Vec::new();
+            for _ in 0..functor_app.controlled {
+                let [ctl, rest] = &*args_value.clone().unwrap_tuple() else {
+                    panic!("controlled arguments should be a tuple of arity 2");
+                };
+                ctls.extend_from_slice(&ctl.clone().unwrap_array());
+                args_value = rest.clone();
+            }
+            let mut ctls_args = self.resolve_args(
+                (global_callable_id.package, spec_pat).into(),
+                Value::Array(ctls.into()),
+            );
+            assert!(
+                ctls_args.len() == 1,
+                "controls should be bound to a single argument"
+            );
+            ctls_args.remove(0)
+        });
         let args = self.resolve_args((global_callable_id.package, args_pat).into(), args_value);
         let call_scope = Scope::new(
             global_callable_id.package,
             Some((global_callable_id.item, functor_app)),
+            ctls,
             args,
         );
         self.eval_context.push_scope(call_scope);
@@ -617,6 +969,150 @@ impl<'a> PartialEvaluator<'a> {
         Ok(block_value)
     }
 
+    fn eval_expr_call_to_outlined_spec(
+        &mut self,
+        global_callable_id: StoreItemId,
+        functor_app: FunctorApp,
+        callable_decl: &CallableDecl,
+        spec_impl: &SpecImpl,
+        args_value: Value,
+    ) -> Result<Value, Error> {
+        // Arguments that are qubits or dynamic scalars are passed as parameters, while the rest are specialized into the
+        // body of the callable.
+        let args = self.resolve_args(
+            (global_callable_id.package, callable_decl.input).into(),
+            args_value,
+        );
+        let input_params = self
+            .package_store
+            .get(global_callable_id.package)
+            .derive_callable_input_params(callable_decl);
+        let mut static_args = Vec::new();
+        let mut params_ty = Vec::new();
+        let mut args_operands = Vec::new();
+        for (arg, input_param) in args.iter().zip(input_params) {
+            let value = arg.value();
+            if is_outlined_param_value(value) {
+                let ty = map_fir_type_to_rir_variable_type(&input_param.ty);
+                args_operands.push(map_eval_value_to_typed_rir_operand(value, ty));
+                params_ty.push(ty);
+                static_args.push(None);
+            } else {
+                static_args.push(Some(value.clone()));
+            }
+        }
+
+        // Generate the callable the first time this specialization is called with these static arguments.
+        let key = OutlinedCallableKey {
+            store_item_id: global_callable_id,
+            functor_app,
+            static_args,
+        };
+        let callable_id = if let Some((_, callable_id)) = self
+            .outlined_callables
+            .iter()
+            .find(|(outlined_key, _)| *outlined_key == key)
+        {
+            *callable_id
+        } else {
+            self.create_outlined_callable(key, callable_decl, spec_impl, args, params_ty)?
+        };
+
+        // Call the outlined callable from the current block.
+        let instruction = Instruction::Call(callable_id, args_operands, None);
+        self.get_current_rir_block_mut().0.push(instruction);
+        Ok(Value::unit())
+    }
+
+    fn create_outlined_callable(
+        &mut self,
+        key: OutlinedCallableKey,
+        callable_decl: &CallableDecl,
+        spec_impl: &SpecImpl,
+        args: Vec<Arg>,
+        input_type: Vec<rir::Ty>,
+    ) -> Result<CallableId, Error> {
+        let global_callable_id = key.store_item_id;
+        let functor_app = key.functor_app;
+
+        // Add the callable to the program before evaluating its body so recursive calls can refer to it.
+        let params: Vec<rir::Variable> = input_type
+            .iter()
+            .map(|ty| rir::Variable {
+                variable_id: self.resource_manager.next_var(),
+                ty: *ty,
+            })
+            .collect();
+        let body_block_id = self.create_program_block();
+        let name = self.get_outlined_callable_name(&callable_decl.name.name, functor_app);
+        let callable = Callable {
+            name: name.clone(),
+            input_type,
+            params: params.clone(),
+            output_type: None,
+            body: Some(body_block_id),
+            call_type: CallableType::Regular,
+        };
+        let callable_id = self.resource_manager.next_callable();
+        self.program.callables.insert(callable_id, callable);
+        self.callables_map.insert(name.into(), callable_id);
+        self.outlined_callables.push((key, callable_id));
+
+        // Within the body, the arguments passed as parameters are bound to the variables that hold them.
+        let mut params_iter = params.into_iter();
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                if is_outlined_param_value(arg.value()) {
+                    let param = params_iter.next().expect("parameter should exist");
+                    arg.with_value(Value::Var(Var(param.variable_id.into())))
+                } else {
+                    arg
+                }
+            })
+            .collect();
+
+        // Evaluate the body of the specialization into its own blocks.
+        let spec_decl = get_spec_decl(spec_impl, functor_app);
+        let call_scope = Scope::new(
+            global_callable_id.package,
+            Some((global_callable_id.item, functor_app)),
+            None,
+            args,
+        );
+        self.eval_context
+            .push_callable_scope(call_scope, body_block_id);
+        self.outlined_callable_depth += 1;
+        let body_control_flow = self.try_eval_block(spec_decl.block);
+        self.outlined_callable_depth -= 1;
+        let _ = body_control_flow?;
+        self.get_current_rir_block_mut().0.push(Instruction::Return);
+        let popped_scope = self.eval_context.pop_callable_scope();
+        assert!(
+            popped_scope.package_id == global_callable_id.package,
+            "scope package ID mismatch"
+        );
+        Ok(callable_id)
+    }
+
+    fn eval_conversion(
+        &mut self,
+        value: &Value,
+        input_ty: rir::Ty,
+        output_ty: rir::Ty,
+        conversion: fn(Operand, rir::Variable) -> Instruction,
+    ) -> Value {
+        let operand = map_eval_value_to_typed_rir_operand(value, input_ty);
+        let variable_id = self.resource_manager.next_var();
+        let variable = rir::Variable {
+            variable_id,
+            ty: output_ty,
+        };
+        let instruction = conversion(operand, variable);
+        self.get_current_rir_block_mut().0.push(instruction);
+        Value::Var(Var(variable_id.into()))
+    }
+
     fn eval_expr_if(
         &mut self,
         if_expr_id: ExprId,
@@ -645,8 +1141,24 @@ impl<'a> PartialEvaluator<'a> {
             );
         }
 
-        // At this point the condition value is not classical, so we need to generate a branching instruction.
-        // First, we pop the current block node and generate a new one which the new branches will jump to when their
+        // At this point the condition value is not classical, so the value of the if expression is dynamic. Only
+        // values that fit in a variable are supported, which excludes tuples, arrays and user-defined types.
+        let if_expr = self.get_expr(if_expr_id);
+        if !matches!(if_expr.ty, Ty::Prim(_)) && if_expr.ty != Ty::UNIT {
+            return Err(Error::Unimplemented(
+                "If Expr with dynamic non-primitive value".to_string(),
+                if_expr.span,
+            ));
+        }
+
+        // We need to generate a branching instruction. Locals assigned within the branches hold different values depending on the branch taken at runtime, so they
+        // need to be held in variables before branching.
+        let branch_expr_ids: Vec<ExprId> = std::iter::once(body_expr_id)
+            .chain(otherwise_expr_id)
+            .collect();
+        self.promote_assigned_locals(&branch_expr_ids, &[]);
+
+        // Then, we pop the current block node and generate a new one which the new branches will jump to when their
         // instructions end.
         let current_block_node = self.eval_context.pop_block_node();
         let continuation_block_node_id = self.create_program_block();
@@ -663,7 +1175,7 @@ impl<'a> PartialEvaluator<'a> {
             None
         } else {
             let variable_id = self.resource_manager.next_var();
-            let variable_ty = map_fir_type_to_rir_type(&if_expr.ty);
+            let variable_ty = map_fir_type_to_rir_variable_type(&if_expr.ty);
             Some(rir::Variable {
                 variable_id,
                 ty: variable_ty,
@@ -733,10 +1245,36 @@ impl<'a> PartialEvaluator<'a> {
         if body_control.is_return() {
             return Ok(BranchControlFlow::Return(body_control.into_value()));
         }
+        if body_control.is_loop_jump() {
+            // A loop jump within a dynamic branch is only possible if the loop is not unrolled, in which case the
+            // branch jumps directly to the corresponding block of the loop.
+            let Some(loop_node) = self
+                .eval_context
+                .get_current_scope()
+                .get_innermost_loop_node()
+            else {
+                let body_expr = self.get_expr(branch_body_expr_id);
+                return Err(Error::Unimplemented(
+                    "Loop Jump in Dynamic Branch".to_string(),
+                    body_expr.span,
+                ));
+            };
+            let target_block_id = if matches!(body_control, EvalControlFlow::LoopBreak) {
+                loop_node.exit
+            } else {
+                loop_node.header
+            };
+            self.get_current_rir_block_mut()
+                .0
+                .push(Instruction::Jump(target_block_id));
+            let _ = self.eval_context.pop_block_node();
+            return Ok(BranchControlFlow::Block(block_node_id));
+        }
 
         // If there is a variable to save the value of the if expression to, add a store instruction.
         if let Some(if_expr_var) = if_expr_var {
-            let body_operand = map_eval_value_to_rir_operand(&body_control.into_value());
+            let body_operand =
+                self.map_eval_value_to_stored_operand(&body_control.into_value(), if_expr_var.ty);
             let store_ins = Instruction::Store(body_operand, if_expr_var);
             self.get_current_rir_block_mut().0.push(store_ins);
         }
@@ -766,34 +1304,239 @@ impl<'a> PartialEvaluator<'a> {
         }
     }
 
-    fn eval_expr_return(&mut self, expr_id: ExprId) -> Result<EvalControlFlow, Error> {
-        let control_flow = self.try_eval_expr(expr_id)?;
-        Ok(EvalControlFlow::Return(control_flow.into_value()))
+    fn eval_expr_index(
+        &mut self,
+        expr_id: ExprId,
+        array_expr_id: ExprId,
+        index_expr_id: ExprId,
+    ) -> Result<EvalControlFlow, Error> {
+        let array_control_flow = self.try_eval_expr(array_expr_id)?;
+        if array_control_flow.is_return() {
+            let array_expr = self.get_expr(array_expr_id);
+            return Err(Error::Unexpected(
+                "embedded return in index expression".to_string(),
+                array_expr.span,
+            ));
+        }
+        let index_control_flow = self.try_eval_expr(index_expr_id)?;
+        if index_control_flow.is_return() {
+            let index_expr = self.get_expr(index_expr_id);
+            return Err(Error::Unexpected(
+                "embedded return in index expression".to_string(),
+                index_expr.span,
+            ));
+        }
+
+        // Only indexing with a static integer is supported, in which case the item is known even if its value is
+        // dynamic.
+        let expr = self.get_expr(expr_id);
+        let Value::Int(index) = index_control_flow.into_value() else {
+            return Err(Error::Unimplemented("Index Expr".to_string(), expr.span));
+        };
+        let array = array_control_flow.into_value().unwrap_array();
+        let item = usize::try_from(index)
+            .ok()
+            .and_then(|index| array.get(index))
+            .ok_or_else(|| {
+                Error::EvaluationFailed(format!("index out of range: {index}"), expr.span)
+            })?;
+        Ok(EvalControlFlow::Continue(item.clone()))
     }
 
-    fn eval_expr_array(&mut self, exprs: &Vec<ExprId>) -> Result<EvalControlFlow, Error> {
-        let mut values = Vec::with_capacity(exprs.len());
-        for expr_id in exprs {
-            let control_flow = self.try_eval_expr(*expr_id)?;
-            if control_flow.is_return() {
-                let expr = self.get_expr(*expr_id);
-                return Err(Error::Unexpected(
-                    "embedded return in array".to_string(),
-                    expr.span,
-                ));
-            }
-            values.push(control_flow.into_value());
+    fn eval_expr_field(
+        &mut self,
+        expr_id: ExprId,
+        record_expr_id: ExprId,
+        field: &Field,
+    ) -> Result<EvalControlFlow, Error> {
+        let record_control_flow = self.try_eval_expr(record_expr_id)?;
+        if record_control_flow.is_return() {
+            let record_expr = self.get_expr(record_expr_id);
+            return Err(Error::Unexpected(
+                "embedded return in field expression".to_string(),
+                record_expr.span,
+            ));
         }
-        Ok(EvalControlFlow::Continue(Value::Array(values.into())))
+
+        // Only field paths are supported, in which case the item is known even if its value is dynamic. Fields of
+        // ranges are always classical.
+        let expr = self.get_expr(expr_id);
+        let Field::Path(path) = field else {
+            return Err(Error::Unimplemented("Field Expr".to_string(), expr.span));
+        };
+        let mut value = record_control_flow.into_value();
+        for &index in &path.indices {
+            value = value.unwrap_tuple()[index].clone();
+        }
+        Ok(EvalControlFlow::Continue(value))
     }
 
-    fn eval_expr_tuple(&mut self, exprs: &Vec<ExprId>) -> Result<EvalControlFlow, Error> {
-        let mut values = Vec::with_capacity(exprs.len());
-        for expr_id in exprs {
-            let control_flow = self.try_eval_expr(*expr_id)?;
-            if control_flow.is_return() {
-                let expr = self.get_expr(*expr_id);
-                return Err(Error::Unexpected(
+    fn eval_expr_update_field(
+        &mut self,
+        expr_id: ExprId,
+        record_expr_id: ExprId,
+        field: &Field,
+        replace_expr_id: ExprId,
+    ) -> Result<EvalControlFlow, Error> {
+        let record = self.eval_update_operand(record_expr_id, "update field expression")?;
+        let replace = self.eval_update_operand(replace_expr_id, "update field expression")?;
+
+        // Only field paths are supported, in which case the updated item is known even if its value is dynamic.
+        let expr = self.get_expr(expr_id);
+        let Field::Path(path) = field else {
+            return Err(Error::Unimplemented(
+                "Updated Field Expr".to_string(),
+                expr.span,
+            ));
+        };
+        Ok(EvalControlFlow::Continue(update_field_path(
+            &record,
+            &path.indices,
+            replace,
+        )))
+    }
+
+    fn eval_expr_update_index(
+        &mut self,
+        expr_id: ExprId,
+        array_expr_id: ExprId,
+        index_expr_id: ExprId,
+        replace_expr_id: ExprId,
+    ) -> Result<EvalControlFlow, Error> {
+        let array = self.eval_update_operand(array_expr_id, "update index expression")?;
+        let index = self.eval_update_operand(index_expr_id, "update index expression")?;
+        let replace = self.eval_update_operand(replace_expr_id, "update index expression")?;
+
+        // Only updating with a static integer index is supported, in which case the updated item is known even if its
+        // value is dynamic.
+        let expr = self.get_expr(expr_id);
+        let Value::Int(index) = index else {
+            return Err(Error::Unimplemented(
+                "Update Index Expr".to_string(),
+                expr.span,
+            ));
+        };
+        let array = update_array_index(array, index, replace, expr.span)?;
+        Ok(EvalControlFlow::Continue(array))
+    }
+
+    fn eval_expr_assign_field(
+        &mut self,
+        expr_id: ExprId,
+        record_expr_id: ExprId,
+        field: &Field,
+        replace_expr_id: ExprId,
+    ) -> Result<EvalControlFlow, Error> {
+        // Only records held by local variables outside of dynamic scopes can be updated, since the value of the local
+        // is only known at compile time.
+        let expr = self.get_expr(expr_id);
+        let record_expr = self.get_expr(record_expr_id);
+        let (ExprKind::Var(Res::Local(local_var_id), _), Field::Path(path)) =
+            (&record_expr.kind, field)
+        else {
+            return Err(Error::Unimplemented(
+                "Field Assignment Expr".to_string(),
+                expr.span,
+            ));
+        };
+        if self.eval_context.is_within_dynamic_scope() {
+            return Err(Error::Unimplemented(
+                "Field Assignment Expr".to_string(),
+                expr.span,
+            ));
+        }
+
+        let replace = self.eval_update_operand(replace_expr_id, "field assignment")?;
+        let scope = self.eval_context.get_current_scope_mut();
+        let record = update_field_path(
+            scope.get_local_var_value(*local_var_id),
+            &path.indices,
+            replace,
+        );
+        scope.update_local_var_value(*local_var_id, record);
+        Ok(EvalControlFlow::Continue(Value::unit()))
+    }
+
+    fn eval_expr_assign_index(
+        &mut self,
+        expr_id: ExprId,
+        array_expr_id: ExprId,
+        index_expr_id: ExprId,
+        replace_expr_id: ExprId,
+    ) -> Result<EvalControlFlow, Error> {
+        // Only arrays held by local variables outside of dynamic scopes can be updated, since the value of the local is
+        // only known at compile time.
+        let expr = self.get_expr(expr_id);
+        let array_expr = self.get_expr(array_expr_id);
+        let ExprKind::Var(Res::Local(local_var_id), _) = &array_expr.kind else {
+            return Err(Error::Unimplemented(
+                "Assignment Index Expr".to_string(),
+                expr.span,
+            ));
+        };
+        if self.eval_context.is_within_dynamic_scope() {
+            return Err(Error::Unimplemented(
+                "Assignment Index Expr".to_string(),
+                expr.span,
+            ));
+        }
+
+        let index = self.eval_update_operand(index_expr_id, "index assignment")?;
+        let replace = self.eval_update_operand(replace_expr_id, "index assignment")?;
+        let Value::Int(index) = index else {
+            return Err(Error::Unimplemented(
+                "Assignment Index Expr".to_string(),
+                expr.span,
+            ));
+        };
+        let scope = self.eval_context.get_current_scope_mut();
+        let array = scope.get_local_var_value(*local_var_id).clone();
+        let array = update_array_index(array, index, replace, expr.span)?;
+        scope.update_local_var_value(*local_var_id, array);
+        Ok(EvalControlFlow::Continue(Value::unit()))
+    }
+
+    /// Evaluates an operand of an update or assignment expression, which cannot contain a return.
+    fn eval_update_operand(&mut self, expr_id: ExprId, context: &str) -> Result<Value, Error> {
+        let control_flow = self.try_eval_expr(expr_id)?;
+        if control_flow.is_return() {
+            let expr = self.get_expr(expr_id);
+            return Err(Error::Unexpected(
+                format!("embedded return in {context}"),
+                expr.span,
+            ));
+        }
+        Ok(control_flow.into_value())
+    }
+
+    fn eval_expr_return(&mut self, expr_id: ExprId) -> Result<EvalControlFlow, Error> {
+        let control_flow = self.try_eval_expr(expr_id)?;
+        Ok(EvalControlFlow::Return(control_flow.into_value()))
+    }
+
+    fn eval_expr_array(&mut self, exprs: &Vec<ExprId>) -> Result<EvalControlFlow, Error> {
+        let mut values = Vec::with_capacity(exprs.len());
+        for expr_id in exprs {
+            let control_flow = self.try_eval_expr(*expr_id)?;
+            if control_flow.is_return() {
+                let expr = self.get_expr(*expr_id);
+                return Err(Error::Unexpected(
+                    "embedded return in array".to_string(),
+                    expr.span,
+                ));
+            }
+            values.push(control_flow.into_value());
+        }
+        Ok(EvalControlFlow::Continue(Value::Array(values.into())))
+    }
+
+    fn eval_expr_tuple(&mut self, exprs: &Vec<ExprId>) -> Result<EvalControlFlow, Error> {
+        let mut values = Vec::with_capacity(exprs.len());
+        for expr_id in exprs {
+            let control_flow = self.try_eval_expr(*expr_id)?;
+            if control_flow.is_return() {
+                let expr = self.get_expr(*expr_id);
+                return Err(Error::Unexpected(
                     "embedded return in tuple".to_string(),
                     expr.span,
                 ));
@@ -803,6 +1546,65 @@ impl<'a> PartialEvaluator<'a> {
         Ok(EvalControlFlow::Continue(Value::Tuple(values.into())))
     }
 
+    fn eval_expr_un_op(
+        &mut self,
+        un_op_expr_id: ExprId,
+        un_op: UnOp,
+        operand_expr_id: ExprId,
+    ) -> Result<EvalControlFlow, Error> {
+        if !self.supports_mutable_vars() {
+            let un_op_expr = self.get_expr(un_op_expr_id);
+            return Err(Error::Unimplemented(
+                "Unary Expr".to_string(),
+                un_op_expr.span,
+            ));
+        }
+
+        let operand_control_flow = self.try_eval_expr(operand_expr_id)?;
+        if operand_control_flow.is_return() {
+            let operand_expr = self.get_expr(operand_expr_id);
+            return Err(Error::Unexpected(
+                "embedded return in unary operation".to_string(),
+                operand_expr.span,
+            ));
+        }
+
+        // A leading plus does not change the value of its operand.
+        let operand_value = operand_control_flow.into_value();
+        if matches!(un_op, UnOp::Pos) {
+            return Ok(EvalControlFlow::Continue(operand_value));
+        }
+
+        let un_op_expr = self.get_expr(un_op_expr_id);
+        let operand_ty = map_fir_type_to_rir_variable_type(&self.get_expr(operand_expr_id).ty);
+        let operand = self.map_eval_value_to_stored_operand(&operand_value, operand_ty);
+        let variable_id = self.resource_manager.next_var();
+        let variable = rir::Variable {
+            variable_id,
+            ty: operand_ty,
+        };
+        let instruction = match (un_op, operand_ty) {
+            (UnOp::NotL, rir::Ty::Boolean) => Instruction::LogicalNot(operand, variable),
+            (UnOp::NotB, rir::Ty::Integer) => Instruction::BitwiseNot(operand, variable),
+            (UnOp::Neg, rir::Ty::Integer) => {
+                Instruction::Sub(Operand::Literal(Literal::Integer(0)), operand, variable)
+            }
+            (UnOp::Neg, rir::Ty::Double) => {
+                Instruction::Fmul(Operand::Literal(Literal::Double(-1.0)), operand, variable)
+            }
+            _ => {
+                return Err(Error::Unimplemented(
+                    format!("Unary Expr ({un_op})"),
+                    un_op_expr.span,
+                ))
+            }
+        };
+        self.get_current_rir_block_mut().0.push(instruction);
+        Ok(EvalControlFlow::Continue(Value::Var(Var(
+            variable_id.into()
+        ))))
+    }
+
     fn eval_expr_var(&mut self, res: &Res) -> Value {
         match res {
             Res::Err => panic!("resolution error"),
@@ -813,24 +1615,33 @@ impl<'a> PartialEvaluator<'a> {
                 },
                 FunctorApp::default(),
             ),
-            Res::Local(local_var_id) => self
-                .eval_context
-                .get_current_scope()
-                .get_local_var_value(*local_var_id)
-                .clone(),
+            Res::Local(local_var_id) => {
+                let scope = self.eval_context.get_current_scope();
+                if let Some(var) = scope.get_mutable_var(*local_var_id) {
+                    // The variable that holds a mutable local can be updated later on, so read its current value into
+                    // a new variable.
+                    let variable_id = self.resource_manager.next_var();
+                    let variable = rir::Variable {
+                        variable_id,
+                        ty: var.ty,
+                    };
+                    let store_ins = Instruction::Store(Operand::Variable(var), variable);
+                    self.get_current_rir_block_mut().0.push(store_ins);
+                    Value::Var(Var(variable_id.into()))
+                } else {
+                    scope.get_local_var_value(*local_var_id).clone()
+                }
+            }
         }
     }
 
     fn eval_expr_while(
         &mut self,
+        while_expr_id: ExprId,
         condition_expr_id: ExprId,
         body_block_id: BlockId,
     ) -> Result<EvalControlFlow, Error> {
         // Verify assumptions.
-        assert!(
-            self.is_classical_expr(condition_expr_id),
-            "loop conditions must be purely classical"
-        );
         let body_block = self.get_block(body_block_id);
         assert_eq!(
             body_block.ty,
@@ -838,6 +1649,21 @@ impl<'a> PartialEvaluator<'a> {
             "the type of a loop block is expected to be Unit"
         );
 
+        // Loops whose condition or jumps depend on dynamic values cannot be unrolled. Conditions that are not classical
+        // but have a static value, such as the length of an array of dynamic qubits, can still be unrolled.
+        let package = self.package_store.get(self.get_current_package_id());
+        let has_dynamic_loop_jump = find_loop_jumps(package, body_block_id)
+            .into_iter()
+            .any(|jump_expr_id| !self.is_classical_expr(jump_expr_id));
+        let has_dynamic_condition = self.get_expr_compute_kind(condition_expr_id).is_dynamic();
+        if has_dynamic_loop_jump || has_dynamic_condition {
+            return self.eval_expr_while_with_dynamic_condition(
+                while_expr_id,
+                condition_expr_id,
+                body_block_id,
+            );
+        }
+
         // Evaluate the block until the loop condition is false.
         let mut condition_control_flow = self.try_eval_expr(condition_expr_id)?;
         if condition_control_flow.is_return() {
@@ -850,9 +1676,15 @@ impl<'a> PartialEvaluator<'a> {
         let mut condition_boolean = condition_control_flow.into_value().unwrap_bool();
         while condition_boolean {
             // Evaluate the loop block.
-            let block_control_flow = self.try_eval_block(body_block_id)?;
-            if block_control_flow.is_return() {
-                return Ok(block_control_flow);
+            self.eval_context
+                .get_current_scope_mut()
+                .push_loop_node(None);
+            let body_control_flow = self.try_eval_block(body_block_id);
+            self.eval_context.get_current_scope_mut().pop_loop_node();
+            match body_control_flow? {
+                control_flow @ EvalControlFlow::Return(_) => return Ok(control_flow),
+                EvalControlFlow::LoopBreak => break,
+                EvalControlFlow::Continue(_) | EvalControlFlow::LoopContinue => {}
             }
 
             // Re-evaluate the condition now that the block evaluation is done
@@ -871,6 +1703,123 @@ impl<'a> PartialEvaluator<'a> {
         Ok(EvalControlFlow::Continue(Value::unit()))
     }
 
+    fn eval_expr_while_with_dynamic_condition(
+        &mut self,
+        while_expr_id: ExprId,
+        condition_expr_id: ExprId,
+        body_block_id: BlockId,
+    ) -> Result<EvalControlFlow, Error> {
+        // Generating a loop requires the target to support branching backwards. Since qubits are reused across
+        // iterations, it also requires them to be reset.
+        let while_expr = self.get_expr(while_expr_id);
+        let required_capabilities =
+            TargetCapabilityFlags::BackwardsBranching | TargetCapabilityFlags::QubitReset;
+        if !self
+            .program
+            .config
+            .capabilities
+            .contains(required_capabilities)
+        {
+            return Err(Error::Unimplemented(
+                "Loop With Dynamic Condition".to_string(),
+                while_expr.span,
+            ));
+        }
+
+        // Locals assigned within the loop carry their values across iterations, so they need to be held in variables
+        // before the loop starts.
+        self.promote_assigned_locals(&[condition_expr_id], &[body_block_id]);
+
+        // The current block jumps to the loop header, which evaluates the condition. Evaluation continues after the
+        // loop in the exit block, which takes the place of the current block.
+        let current_block_node = self.eval_context.pop_block_node();
+        let header_block_id = self.create_program_block();
+        let exit_block_id = self.create_program_block();
+        self.get_program_block_mut(current_block_node.id)
+            .0
+            .push(Instruction::Jump(header_block_id));
+        self.eval_context.push_block_node(BlockNode {
+            id: exit_block_id,
+            successor: current_block_node.successor,
+        });
+
+        // Evaluate the condition in the header block and branch to either the loop body or the exit block.
+        self.eval_context.push_block_node(BlockNode {
+            id: header_block_id,
+            successor: None,
+        });
+        let condition_control_flow = self.try_eval_expr(condition_expr_id)?;
+        if condition_control_flow.is_return() {
+            let condition_expr = self.get_expr(condition_expr_id);
+            return Err(Error::Unexpected(
+                "embedded return in loop condition".to_string(),
+                condition_expr.span,
+            ));
+        }
+        let condition_value = condition_control_flow.into_value();
+        if let Value::Bool(false) = condition_value {
+            // The loop never runs, so there is no body to generate.
+            self.get_current_rir_block_mut()
+                .0
+                .push(Instruction::Jump(exit_block_id));
+            let _ = self.eval_context.pop_block_node();
+            return Ok(EvalControlFlow::Continue(Value::unit()));
+        }
+        let loop_body_block_id = self.create_program_block();
+        let branch_ins = if let Value::Bool(true) = condition_value {
+            Instruction::Jump(loop_body_block_id)
+        } else {
+            let condition_rir_var = rir::Variable {
+                variable_id: condition_value.unwrap_var().0.into(),
+                ty: rir::Ty::Boolean,
+            };
+            Instruction::Branch(condition_rir_var, loop_body_block_id, exit_block_id)
+        };
+        self.get_current_rir_block_mut().0.push(branch_ins);
+        let _ = self.eval_context.pop_block_node();
+
+        // Evaluate the body, which jumps back to the header unless it breaks out of the loop.
+        self.eval_context.push_block_node(BlockNode {
+            id: loop_body_block_id,
+            successor: Some(header_block_id),
+        });
+        self.eval_context
+            .get_current_scope_mut()
+            .push_loop_node(Some(LoopNode {
+                header: header_block_id,
+                exit: exit_block_id,
+            }));
+        let body_control_flow = self.try_eval_block(body_block_id);
+        self.eval_context.get_current_scope_mut().pop_loop_node();
+        let jump_ins = match body_control_flow? {
+            EvalControlFlow::Return(_) => {
+                let while_expr = self.get_expr(while_expr_id);
+                return Err(Error::Unimplemented(
+                    "Return in Loop With Dynamic Condition".to_string(),
+                    while_expr.span,
+                ));
+            }
+            EvalControlFlow::LoopBreak => Instruction::Jump(exit_block_id),
+            EvalControlFlow::Continue(_) | EvalControlFlow::LoopContinue => {
+                Instruction::Jump(header_block_id)
+            }
+        };
+        self.get_current_rir_block_mut().0.push(jump_ins);
+        let _ = self.eval_context.pop_block_node();
+
+        // The loop does not have a value, so just return unit.
+        Ok(EvalControlFlow::Continue(Value::unit()))
+    }
+
+    fn map_eval_value_to_stored_operand(&mut self, value: &Value, ty: rir::Ty) -> Operand {
+        // Dynamic results are held in variables as Booleans, so read them before storing them.
+        if let Value::Result(result) = value {
+            self.eval_result_as_bool_operand(*result)
+        } else {
+            map_eval_value_to_typed_rir_operand(value, ty)
+        }
+    }
+
     fn eval_result_as_bool_operand(&mut self, result: val::Result) -> Operand {
         match result {
             val::Result::Id(id) => {
@@ -986,6 +1935,24 @@ impl<'a> PartialEvaluator<'a> {
             .expect("callable not present")
     }
 
+    fn get_outlined_callable_name(&self, callable_name: &str, functor_app: FunctorApp) -> String {
+        let functor_suffix = if functor_app.adjoint { "adj" } else { "body" };
+        let name = format!("{callable_name}__{functor_suffix}");
+        if !self.callables_map.contains_key(name.as_str()) {
+            return name;
+        }
+
+        // Different callables with the same name, or calls with different static arguments, need unique names.
+        let mut index = 1;
+        loop {
+            let unique_name = format!("{name}__{index}");
+            if !self.callables_map.contains_key(unique_name.as_str()) {
+                return unique_name;
+            }
+            index += 1;
+        }
+    }
+
     fn get_program_block_mut(&mut self, id: rir::BlockId) -> &mut rir::Block {
         self.program
             .blocks
@@ -998,21 +1965,194 @@ impl<'a> PartialEvaluator<'a> {
         matches!(compute_kind, ComputeKind::Classical)
     }
 
+    fn has_escaping_jump(&self, exec_graph_range: ops::Range<usize>) -> bool {
+        let exec_graph = self.get_current_scope_exec_graph();
+        exec_graph[exec_graph_range.clone()]
+            .iter()
+            .any(|node| match node {
+                ExecGraphNode::Jump(idx)
+                | ExecGraphNode::JumpIf(idx)
+                | ExecGraphNode::JumpIfNot(idx) => {
+                    let idx = *idx as usize;
+                    idx < exec_graph_range.start || idx > exec_graph_range.end
+                }
+                _ => false,
+            })
+    }
+
+    /// Determines whether a call is generated as a call instruction to a separate callable instead of being inlined.
+    /// This happens for non-controlled calls to operations marked with the `@NoInline()` attribute when the target
+    /// supports higher-level constructs, as long as their arguments can be passed as parameters or specialized.
+    fn should_outline_call(
+        &self,
+        store_item_id: StoreItemId,
+        callable_decl: &CallableDecl,
+        functor_app: FunctorApp,
+        args_value: &Value,
+    ) -> bool {
+        // Qubits allocated within outlined callables are not reused, so targets need to support qubit reset.
+        let required_capabilities =
+            TargetCapabilityFlags::HigherLevelConstructs | TargetCapabilityFlags::QubitReset;
+        if !self
+            .program
+            .config
+            .capabilities
+            .contains(required_capabilities)
+            || functor_app.controlled > 0
+            || callable_decl.output != Ty::UNIT
+        {
+            return false;
+        }
+
+        let package = self.package_store.get(store_item_id.package);
+        let item = package.get_item(store_item_id.item);
+        if !item.attrs.contains(&fir::Attr::NoInline) {
+            return false;
+        }
+
+        // Dynamic values nested in aggregates cannot be passed as parameters, and parameters need primitive types.
+        let args = self.resolve_args(
+            (store_item_id.package, callable_decl.input).into(),
+            args_value.clone(),
+        );
+        let input_params = package.derive_callable_input_params(callable_decl);
+        args.iter().zip(input_params).all(|(arg, input_param)| {
+            let value = arg.value();
+            if is_outlined_param_value(value) {
+                matches!(input_param.ty, Ty::Prim(_))
+            } else {
+                !contains_dynamic_var(value)
+            }
+        })
+    }
+
     fn is_classical_stmt(&self, stmt_id: StmtId) -> bool {
         let compute_kind = self.get_stmt_compute_kind(stmt_id);
         matches!(compute_kind, ComputeKind::Classical)
     }
 
+    /// Whether qubits are allocated at runtime, which is the case within dynamic scopes when the target supports it,
+    /// since the number of qubits that get allocated can depend on measurement results.
+    fn allocates_qubits_at_runtime(&self) -> bool {
+        self.program
+            .config
+            .capabilities
+            .contains(TargetCapabilityFlags::DynamicQubitManagement)
+            && self.eval_context.is_within_dynamic_scope()
+    }
+
     fn allocate_qubit(&mut self) -> Value {
+        if self.allocates_qubits_at_runtime() {
+            let allocate_callable_id = self.get_or_insert_callable(builder::qubit_allocate_decl());
+            let variable_id = self.resource_manager.next_var();
+            let variable = rir::Variable {
+                variable_id,
+                ty: rir::Ty::Qubit,
+            };
+            let instruction = Instruction::Call(allocate_callable_id, Vec::new(), Some(variable));
+            self.get_current_rir_block_mut().0.push(instruction);
+            return Value::Var(Var(variable_id.into()));
+        }
+
         let qubit = self.resource_manager.allocate_qubit();
         Value::Qubit(qubit)
     }
 
-    fn measure_qubit(&mut self, measure_callable: Callable, args_value: Value) -> Value {
+    /// Allocates and releases qubit arrays through the array runtime functions rather than one qubit at a time, returning
+    /// `None` for calls to anything else, which are evaluated as usual.
+    fn try_eval_runtime_qubit_array_call(
+        &mut self,
+        store_item_id: StoreItemId,
+        callable_decl: &CallableDecl,
+        args_value: &Value,
+    ) -> Option<Value> {
+        if store_item_id.package != PackageId::CORE {
+            return None;
+        }
+        match (callable_decl.name.name.as_ref(), args_value) {
+            // Empty arrays and negative lengths are left to the library implementation.
+            ("AllocateQubitArray", Value::Int(size @ 1..))
+                if self.allocates_qubits_at_runtime() =>
+            {
+                Some(self.allocate_qubit_array(*size))
+            }
+            ("ReleaseQubitArray", Value::Array(qubits)) => self.release_qubit_array(qubits),
+            _ => None,
+        }
+    }
+
+    fn allocate_qubit_array(&mut self, size: i64) -> Value {
+        let allocate_callable_id =
+            self.get_or_insert_callable(builder::qubit_allocate_array_decl());
+        let array_variable = rir::Variable {
+            variable_id: self.resource_manager.next_var(),
+            ty: rir::Ty::Pointer,
+        };
+        let size_operand = Operand::Literal(Literal::Integer(size));
+        let instruction = Instruction::Call(
+            allocate_callable_id,
+            vec![size_operand],
+            Some(array_variable),
+        );
+        self.get_current_rir_block_mut().0.push(instruction);
+
+        // Each qubit is loaded from a pointer to its item in the array.
+        let get_element_callable_id =
+            self.get_or_insert_callable(builder::array_get_element_ptr_1d_decl());
+        let mut qubits = Vec::new();
+        for index in 0..size {
+            let element_variable = rir::Variable {
+                variable_id: self.resource_manager.next_var(),
+                ty: rir::Ty::Pointer,
+            };
+            let qubit_variable = rir::Variable {
+                variable_id: self.resource_manager.next_var(),
+                ty: rir::Ty::Qubit,
+            };
+            let args = vec![
+                Operand::Variable(array_variable),
+                Operand::Literal(Literal::Integer(index)),
+            ];
+            let current_block = self.get_current_rir_block_mut();
+            current_block.0.push(Instruction::Call(
+                get_element_callable_id,
+                args,
+                Some(element_variable),
+            ));
+            current_block.0.push(Instruction::Load(
+                Operand::Variable(element_variable),
+                qubit_variable,
+            ));
+            qubits.push(Value::Var(Var(qubit_variable.variable_id.into())));
+        }
+        let qubits = Rc::new(qubits);
+        self.runtime_qubit_arrays
+            .push((qubits.clone(), array_variable));
+        Value::Array(qubits)
+    }
+
+    /// Releases a qubit array allocated at runtime, returning `None` if the array was not allocated at runtime as a
+    /// whole, in which case its qubits are released one at a time.
+    fn release_qubit_array(&mut self, qubits: &Rc<Vec<Value>>) -> Option<Value> {
+        let index = self
+            .runtime_qubit_arrays
+            .iter()
+            .position(|(array, _)| array == qubits)?;
+        let (_, array_variable) = self.runtime_qubit_arrays.swap_remove(index);
+        let release_callable_id = self.get_or_insert_callable(builder::qubit_release_array_decl());
+        let instruction = Instruction::Call(
+            release_callable_id,
+            vec![Operand::Variable(array_variable)],
+            None,
+        );
+        self.get_current_rir_block_mut().0.push(instruction);
+        Some(Value::unit())
+    }
+
+    fn measure_qubit(&mut self, measure_callable: Callable, args_value: &Value) -> Value {
         // Get the qubit and result IDs to use in the qubit measure instruction.
-        let qubit = args_value.unwrap_qubit();
-        let qubit_value = Value::Qubit(qubit);
-        let qubit_operand = map_eval_value_to_rir_operand(&qubit_value);
+        // Qubits passed as parameters to outlined callables are held in variables.
+        let qubit_operand = map_eval_value_to_typed_rir_operand(args_value, rir::Ty::Qubit);
         let result_value = Value::Result(self.resource_manager.next_result_register());
         let result_operand = map_eval_value_to_rir_operand(&result_value);
 
@@ -1028,13 +2168,141 @@ impl<'a> PartialEvaluator<'a> {
     }
 
     fn release_qubit(&mut self, args_value: Value) -> Value {
-        let qubit = args_value.unwrap_qubit();
-        self.resource_manager.release_qubit(qubit);
+        // Qubits allocated at runtime are also released at runtime.
+        if let Value::Var(var) = args_value {
+            let release_callable_id = self.get_or_insert_callable(builder::qubit_release_decl());
+            let qubit_operand = Operand::Variable(rir::Variable {
+                variable_id: var.0.into(),
+                ty: rir::Ty::Qubit,
+            });
+            let instruction = Instruction::Call(release_callable_id, vec![qubit_operand], None);
+            self.get_current_rir_block_mut().0.push(instruction);
+            return Value::unit();
+        }
+
+        // Qubits allocated within outlined callables stay reserved, since the same body can be called while other qubits
+        // are allocated.
+        if self.outlined_callable_depth == 0 {
+            let qubit = args_value.unwrap_qubit();
+            self.resource_manager.release_qubit(qubit);
+        }
 
         // The value of a qubit release is unit.
         Value::unit()
     }
 
+    /// Whether locals can be held in RIR variables that are updated at runtime, which is only supported for targets
+    /// with backwards branching. Unary operations on dynamic values are only generated for these targets too.
+    fn supports_mutable_vars(&self) -> bool {
+        self.program
+            .config
+            .capabilities
+            .contains(TargetCapabilityFlags::BackwardsBranching)
+    }
+
+    /// Gets the RIR variable that holds the value of a mutable local variable, creating it and storing the current
+    /// value of the local into it if needed. Returns `None` if the type of the local cannot be held in a variable.
+    fn get_or_promote_mutable_var(
+        &mut self,
+        local_var_id: LocalVarId,
+        ty: &Ty,
+    ) -> Option<rir::Variable> {
+        let scope = self.eval_context.get_current_scope();
+        if let Some(var) = scope.get_mutable_var(local_var_id) {
+            return Some(var);
+        }
+        if !matches!(
+            ty,
+            Ty::Prim(Prim::Bool | Prim::Int | Prim::Double | Prim::Result)
+        ) {
+            return None;
+        }
+
+        let value = scope.get_local_var_value(local_var_id).clone();
+        let variable_ty = map_fir_type_to_rir_variable_type(ty);
+        let operand = self.map_eval_value_to_stored_operand(&value, variable_ty);
+        let variable_id = self.resource_manager.next_var();
+        let variable = rir::Variable {
+            variable_id,
+            ty: variable_ty,
+        };
+        let store_ins = Instruction::Store(operand, variable);
+        self.get_current_rir_block_mut().0.push(store_ins);
+        self.eval_context
+            .get_current_scope_mut()
+            .insert_mutable_var(local_var_id, variable);
+        Some(variable)
+    }
+
+    /// Holds the mutable local variables declared by a pattern in RIR variables when their values are dynamic.
+    fn promote_dynamic_mutable_bindings(&mut self, pat_id: PatId) {
+        if !self.supports_mutable_vars() {
+            return;
+        }
+        let pat = self.get_pat(pat_id);
+        match &pat.kind {
+            PatKind::Bind(ident) => {
+                let scope = self.eval_context.get_current_scope();
+                let value = scope.get_local_var_value(ident.id);
+                if !matches!(value, Value::Var(_) | Value::Result(val::Result::Id(_))) {
+                    return;
+                }
+                if let Some(var) = self.get_or_promote_mutable_var(ident.id, &pat.ty) {
+                    // Bind the local in the classical environment too, so it can be assigned by classical statements.
+                    let variable = Variable {
+                        name: ident.name.clone(),
+                        value: Value::Var(Var(var.variable_id.into())),
+                        span: ident.span,
+                    };
+                    self.eval_context
+                        .get_current_scope_mut()
+                        .env
+                        .bind_variable_in_top_frame(ident.id, variable);
+                }
+            }
+            PatKind::Tuple(pats) => {
+                for pat_id in pats {
+                    self.promote_dynamic_mutable_bindings(*pat_id);
+                }
+            }
+            PatKind::Array(..) | PatKind::Discard => {}
+        }
+    }
+
+    /// Holds the local variables assigned within the specified expressions and blocks in RIR variables, so their values can be
+    /// updated at runtime.
+    fn promote_assigned_locals(&mut self, expr_ids: &[ExprId], block_ids: &[BlockId]) {
+        if !self.supports_mutable_vars() {
+            return;
+        }
+        let package = self.package_store.get(self.get_current_package_id());
+        for (local_var_id, ty) in find_assigned_locals(package, expr_ids, block_ids) {
+            // Locals declared within the expressions are not bound yet.
+            let scope = self.eval_context.get_current_scope();
+            if scope.is_local_var_bound(local_var_id) {
+                let _ = self.get_or_promote_mutable_var(local_var_id, &ty);
+            }
+        }
+    }
+
+    /// Stores the values that the classical evaluator assigned to mutable local variables into the RIR variables that
+    /// hold them.
+    fn store_assigned_mutable_vars(&mut self) {
+        let mutable_vars = self.eval_context.get_current_scope().get_mutable_vars();
+        for (local_var_id, var) in mutable_vars {
+            let Some(value) = self
+                .eval_context
+                .get_current_scope_mut()
+                .take_assigned_mutable_var_value(local_var_id)
+            else {
+                continue;
+            };
+            let operand = self.map_eval_value_to_stored_operand(&value, var.ty);
+            let store_ins = Instruction::Store(operand, var);
+            self.get_current_rir_block_mut().0.push(store_ins);
+        }
+    }
+
     fn resolve_args(&self, store_pat_id: StorePatId, value: Value) -> Vec<Arg> {
         let pat = self.package_store.get_pat(store_pat_id);
         match &pat.kind {
@@ -1063,6 +2331,9 @@ impl<'a> PartialEvaluator<'a> {
                 }
                 args
             }
+            PatKind::Array(..) => {
+                panic!("array patterns in callable inputs should be replaced by bindings")
+            }
         }
     }
 
@@ -1075,7 +2346,7 @@ impl<'a> PartialEvaluator<'a> {
         let mut stmts_iter = block.stmts.iter();
         for stmt_id in stmts_iter.by_ref() {
             last_control_flow = self.try_eval_stmt(*stmt_id)?;
-            if last_control_flow.is_return() {
+            if last_control_flow.is_return() || last_control_flow.is_loop_jump() {
                 return_stmt_id = Some(*stmt_id);
                 break;
             }
@@ -1085,7 +2356,10 @@ impl<'a> PartialEvaluator<'a> {
         // unprocessed when we are evaluating a branch within a callable scope.
         let remaining_stmt_count = stmts_iter.count();
         let current_scope = self.eval_context.get_current_scope();
-        if remaining_stmt_count > 0 && current_scope.is_currently_evaluating_branch() {
+        if remaining_stmt_count > 0
+            && last_control_flow.is_return()
+            && current_scope.is_currently_evaluating_branch()
+        {
             let return_stmt =
                 self.get_stmt(return_stmt_id.expect("a return statement ID must have been set"));
             Err(Error::Unexpected(
@@ -1099,7 +2373,10 @@ impl<'a> PartialEvaluator<'a> {
 
     fn try_eval_expr(&mut self, expr_id: ExprId) -> Result<EvalControlFlow, Error> {
         // An expression is evaluated differently depending on whether it is purely classical or hybrid.
-        if self.is_classical_expr(expr_id) {
+        // Classical expressions that break or continue an enclosing loop are evaluated as hybrid so the jump can be
+        // propagated to the loop.
+        let exec_graph_range = self.get_expr(expr_id).exec_graph_range.clone();
+        if self.is_classical_expr(expr_id) && !self.has_escaping_jump(exec_graph_range) {
             self.eval_classical_expr(expr_id)
         } else {
             self.eval_hybrid_expr(expr_id)
@@ -1107,23 +2384,65 @@ impl<'a> PartialEvaluator<'a> {
     }
 
     fn try_eval_stmt(&mut self, stmt_id: StmtId) -> Result<EvalControlFlow, Error> {
-        // If the statement is classical, we can just evaluate it.
-        if self.is_classical_stmt(stmt_id) {
+        // If the statement is classical and does not jump out of it, we can just evaluate it.
+        let exec_graph_range = self.get_stmt(stmt_id).exec_graph_range.clone();
+        if self.is_classical_stmt(stmt_id) && !self.has_escaping_jump(exec_graph_range) {
             self.eval_classical_stmt(stmt_id)
         } else {
             self.eval_hybrid_stmt(stmt_id)
         }
     }
 
+    fn update_bindings(&mut self, lhs_expr_id: ExprId, value: Value) -> Result<(), Error> {
+        let lhs_expr = self.get_expr(lhs_expr_id);
+        match &lhs_expr.kind {
+            ExprKind::Hole => {}
+            ExprKind::Var(Res::Local(local_var_id), _) => {
+                let Some(var) = self.get_or_promote_mutable_var(*local_var_id, &lhs_expr.ty) else {
+                    return Err(Error::Unimplemented(
+                        "Assignment Expr".to_string(),
+                        lhs_expr.span,
+                    ));
+                };
+                let operand = self.map_eval_value_to_stored_operand(&value, var.ty);
+                let store_ins = Instruction::Store(operand, var);
+                self.get_current_rir_block_mut().0.push(store_ins);
+            }
+            ExprKind::Tuple(exprs) => {
+                let values = value.unwrap_tuple();
+                assert_eq!(
+                    exprs.len(),
+                    values.len(),
+                    "assignee tuple and value tuple have different arity"
+                );
+                for (expr_id, value) in exprs.iter().zip(values.iter()) {
+                    self.update_bindings(*expr_id, value.clone())?;
+                }
+            }
+            _ => {
+                return Err(Error::Unimplemented(
+                    "Assignment Expr".to_string(),
+                    lhs_expr.span,
+                ))
+            }
+        }
+        Ok(())
+    }
+
     fn generate_output_recording_instructions(
         &mut self,
         ret_val: Value,
         ty: &Ty,
-    ) -> Result<Vec<Instruction>, ()> {
+    ) -> Result<Vec<Instruction>, OutputRecordingError> {
         let mut instrs = Vec::new();
 
         match ret_val {
-            Value::Result(val::Result::Val(_)) => return Err(()),
+            Value::Result(val::Result::Val(_)) => return Err(OutputRecordingError::ResultLiteral),
+            // Dynamic results stored in mutable variables are tracked as Booleans, so the result they came from is no
+            // longer known.
+            Value::Var(_) if *ty == Ty::Prim(Prim::Result) => {
+                return Err(OutputRecordingError::DynamicResultVariable)
+            }
 
             Value::Array(vals) => self.record_array(ty, &mut instrs, &vals)?,
             Value::Tuple(vals) => self.record_tuple(ty, &mut instrs, &vals)?,
@@ -1131,10 +2450,10 @@ impl<'a> PartialEvaluator<'a> {
             Value::Var(var) => self.record_variable(ty, &mut instrs, var),
             Value::Bool(val) => self.record_bool(&mut instrs, val),
             Value::Int(val) => self.record_int(&mut instrs, val),
+            Value::Double(val) => self.record_double(&mut instrs, val),
 
             Value::BigInt(_)
             | Value::Closure(_)
-            | Value::Double(_)
             | Value::Global(_, _)
             | Value::Pauli(_)
             | Value::Qubit(_)
@@ -1157,6 +2476,18 @@ impl<'a> PartialEvaluator<'a> {
         ));
     }
 
+    fn record_double(&mut self, instrs: &mut Vec<Instruction>, val: f64) {
+        let double_record_callable_id = self.get_double_record_callable();
+        instrs.push(Instruction::Call(
+            double_record_callable_id,
+            vec![
+                Operand::Literal(Literal::Double(val)),
+                Operand::Literal(Literal::Pointer),
+            ],
+            None,
+        ));
+    }
+
     fn record_bool(&mut self, instrs: &mut Vec<Instruction>, val: bool) {
         let bool_record_callable_id = self.get_bool_record_callable();
         instrs.push(Instruction::Call(
@@ -1173,6 +2504,7 @@ impl<'a> PartialEvaluator<'a> {
         let (record_callable_id, record_ty) = match ty {
             Ty::Prim(Prim::Bool) => (self.get_bool_record_callable(), rir::Ty::Boolean),
             Ty::Prim(Prim::Int) => (self.get_int_record_callable(), rir::Ty::Integer),
+            Ty::Prim(Prim::Double) => (self.get_double_record_callable(), rir::Ty::Double),
             _ => panic!("unsupported variable type in output recording"),
         };
         instrs.push(Instruction::Call(
@@ -1209,7 +2541,7 @@ impl<'a> PartialEvaluator<'a> {
         ty: &Ty,
         instrs: &mut Vec<Instruction>,
         vals: &Rc<[Value]>,
-    ) -> Result<(), ()> {
+    ) -> Result<(), OutputRecordingError> {
         let Ty::Tuple(elem_tys) = ty else {
             panic!("expected tuple type for tuple value");
         };
@@ -1238,7 +2570,7 @@ impl<'a> PartialEvaluator<'a> {
         ty: &Ty,
         instrs: &mut Vec<Instruction>,
         vals: &Rc<Vec<Value>>,
-    ) -> Result<(), ()> {
+    ) -> Result<(), OutputRecordingError> {
         let Ty::Array(elem_ty) = ty else {
             panic!("expected array type for array value");
         };
@@ -1317,6 +2649,22 @@ impl<'a> PartialEvaluator<'a> {
         callable_id
     }
 
+    fn get_double_record_callable(&mut self) -> CallableId {
+        if let Some(id) = self
+            .callables_map
+            .get("__quantum__rt__double_record_output")
+        {
+            return *id;
+        }
+
+        let callable = builder::double_record_decl();
+        let callable_id = self.resource_manager.next_callable();
+        self.callables_map
+            .insert("__quantum__rt__double_record_output".into(), callable_id);
+        self.program.callables.insert(callable_id, callable);
+        callable_id
+    }
+
     fn get_int_record_callable(&mut self) -> CallableId {
         if let Some(id) = self.callables_map.get("__quantum__rt__int_record_output") {
             return *id;
@@ -1352,6 +2700,46 @@ fn get_spec_decl(spec_impl: &SpecImpl, functor_app: FunctorApp) -> &SpecDecl {
     }
 }
 
+/// Whether a value is passed as a parameter to an outlined callable rather than being specialized into its body.
+fn update_array_index(
+    array: Value,
+    index: i64,
+    replace: Value,
+    span: Span,
+) -> Result<Value, Error> {
+    let mut items = array.unwrap_array().as_ref().clone();
+    let item = usize::try_from(index)
+        .ok()
+        .and_then(|index| items.get_mut(index))
+        .ok_or_else(|| Error::EvaluationFailed(format!("index out of range: {index}"), span))?;
+    *item = replace;
+    Ok(Value::Array(items.into()))
+}
+
+fn update_field_path(record: &Value, path: &[usize], replace: Value) -> Value {
+    match path {
+        [] => replace,
+        [index, rest @ ..] => {
+            let mut items = record.clone().unwrap_tuple().to_vec();
+            items[*index] = update_field_path(&items[*index], rest, replace);
+            Value::Tuple(items.into())
+        }
+    }
+}
+
+fn is_outlined_param_value(value: &Value) -> bool {
+    matches!(value, Value::Qubit(_) | Value::Var(_))
+}
+
+fn contains_dynamic_var(value: &Value) -> bool {
+    match value {
+        Value::Var(_) => true,
+        Value::Array(elements) => elements.iter().any(contains_dynamic_var),
+        Value::Tuple(elements) => elements.iter().any(contains_dynamic_var),
+        _ => false,
+    }
+}
+
 fn map_eval_value_to_rir_operand(value: &Value) -> Operand {
     match value {
         Value::Bool(b) => Operand::Literal(Literal::Bool(*b)),
@@ -1372,6 +2760,56 @@ fn map_eval_value_to_rir_operand(value: &Value) -> Operand {
     }
 }
 
+/// Computes a binary operation whose operands are both static, returning `None` for operations that are left to the
+/// generated program, such as those that can fail or overflow.
+fn eval_static_bin_op(bin_op: BinOp, lhs: &Value, rhs: &Value) -> Option<Value> {
+    let value = match (bin_op, lhs, rhs) {
+        (BinOp::Add, Value::Int(lhs), Value::Int(rhs)) => Value::Int(lhs.checked_add(*rhs)?),
+        (BinOp::Sub, Value::Int(lhs), Value::Int(rhs)) => Value::Int(lhs.checked_sub(*rhs)?),
+        (BinOp::Mul, Value::Int(lhs), Value::Int(rhs)) => Value::Int(lhs.checked_mul(*rhs)?),
+        (BinOp::Div, Value::Int(lhs), Value::Int(rhs)) => Value::Int(lhs.checked_div(*rhs)?),
+        (BinOp::Mod, Value::Int(lhs), Value::Int(rhs)) => Value::Int(lhs.checked_rem(*rhs)?),
+        // Concatenating arrays, such as the controls of nested controlled calls, only rearranges their elements.
+        (BinOp::Add, Value::Array(lhs), Value::Array(rhs)) => Value::Array(
+            lhs.iter()
+                .chain(rhs.iter())
+                .cloned()
+                .collect::<Vec<_>>()
+                .into(),
+        ),
+        (BinOp::Eq, Value::Int(lhs), Value::Int(rhs)) => Value::Bool(lhs == rhs),
+        (BinOp::Neq, Value::Int(lhs), Value::Int(rhs)) => Value::Bool(lhs != rhs),
+        (BinOp::Gt, Value::Int(lhs), Value::Int(rhs)) => Value::Bool(lhs > rhs),
+        (BinOp::Gte, Value::Int(lhs), Value::Int(rhs)) => Value::Bool(lhs >= rhs),
+        (BinOp::Lt, Value::Int(lhs), Value::Int(rhs)) => Value::Bool(lhs < rhs),
+        (BinOp::Lte, Value::Int(lhs), Value::Int(rhs)) => Value::Bool(lhs <= rhs),
+        (BinOp::Eq, Value::Bool(lhs), Value::Bool(rhs)) => Value::Bool(lhs == rhs),
+        (BinOp::Neq, Value::Bool(lhs), Value::Bool(rhs)) => Value::Bool(lhs != rhs),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Maps a value to a RIR operand, where dynamic variables are given the specified type.
+fn map_eval_value_to_typed_rir_operand(value: &Value, ty: rir::Ty) -> Operand {
+    if let Value::Var(var) = value {
+        Operand::Variable(rir::Variable {
+            variable_id: var.0.into(),
+            ty,
+        })
+    } else {
+        map_eval_value_to_rir_operand(value)
+    }
+}
+
+/// Maps a FIR type to the type of the RIR variables that hold dynamic values of it, where results are held as Booleans.
+fn map_fir_type_to_rir_variable_type(ty: &Ty) -> rir::Ty {
+    match map_fir_type_to_rir_type(ty) {
+        rir::Ty::Result => rir::Ty::Boolean,
+        ty => ty,
+    }
+}
+
 fn map_fir_type_to_rir_type(ty: &Ty) -> rir::Ty {
     let Ty::Prim(prim) = ty else {
         panic!("only some primitive types are supported");
@@ -11,8 +11,12 @@ pub mod test_utils;
 
 use expect_test::expect;
 use indoc::indoc;
+use qsc_data_structures::target::TargetCapabilityFlags;
 use qsc_rir::rir::{BlockId, CallableId};
-use test_utils::{assert_block_instructions, assert_blocks, assert_callable, get_rir_program};
+use test_utils::{
+    assert_block_instructions, assert_blocks, assert_callable, get_rir_program,
+    get_rir_program_with_capabilities,
+};
 
 #[test]
 fn call_to_single_qubit_unitary_with_two_calls_to_the_same_intrinsic() {
@@ -705,3 +709,326 @@ fn call_to_boolean_function_using_dynamic_result_as_argument_generates_branches(
                 Jump(1)"#]],
     );
 }
+
+#[test]
+fn calls_to_no_inline_operation_with_qubit_arguments_generate_calls_to_single_callable() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {r#"
+        namespace Test {
+            operation Op(q : Qubit) : Unit { body intrinsic; }
+            @NoInline()
+            operation OpSquared(q : Qubit) : Unit {
+                Op(q);
+                Op(q);
+            }
+            @EntryPoint()
+            operation Main() : Unit {
+                use (q0, q1) = (Qubit(), Qubit());
+                OpSquared(q0);
+                OpSquared(q1);
+            }
+        }
+    "#},
+        TargetCapabilityFlags::all(),
+    );
+    assert_callable(
+        &program,
+        CallableId(1),
+        &expect![[r#"
+            Callable:
+                name: OpSquared__body
+                call_type: Regular
+                input_type:
+                    [0]: Qubit
+                params:
+                    [0]: Variable(0, Qubit)
+                output_type: <VOID>
+                body: 1"#]],
+    );
+    assert_blocks(
+        &program,
+        &expect![[r#"
+            Blocks:
+            Block 0:Block:
+                Call id(1), args( Qubit(0), )
+                Call id(1), args( Qubit(1), )
+                Call id(3), args( Integer(0), Pointer, )
+                Return
+            Block 1:Block:
+                Call id(2), args( Variable(0, Qubit), )
+                Call id(2), args( Variable(0, Qubit), )
+                Return"#]],
+    );
+}
+
+#[test]
+fn call_to_no_inline_operation_with_dynamic_bool_argument_generates_branches_within_callable() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {r#"
+        namespace Test {
+            operation Op(q : Qubit) : Unit { body intrinsic; }
+            @NoInline()
+            operation ConditionalOp(b : Bool, q : Qubit) : Unit {
+                if b {
+                    Op(q);
+                }
+            }
+            @EntryPoint()
+            operation Main() : Unit {
+                use (q0, q1) = (Qubit(), Qubit());
+                let b = MResetZ(q0) == One;
+                ConditionalOp(b, q1);
+            }
+        }
+    "#},
+        TargetCapabilityFlags::all(),
+    );
+    assert_callable(
+        &program,
+        CallableId(3),
+        &expect![[r#"
+            Callable:
+                name: ConditionalOp__body
+                call_type: Regular
+                input_type:
+                    [0]: Boolean
+                    [1]: Qubit
+                params:
+                    [0]: Variable(2, Boolean)
+                    [1]: Variable(3, Qubit)
+                output_type: <VOID>
+                body: 1"#]],
+    );
+    assert_blocks(
+        &program,
+        &expect![[r#"
+            Blocks:
+            Block 0:Block:
+                Call id(1), args( Qubit(0), Result(0), )
+                Variable(0, Boolean) = Call id(2), args( Result(0), )
+                Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+                Call id(3), args( Variable(1, Boolean), Qubit(1), )
+                Call id(5), args( Integer(0), Pointer, )
+                Return
+            Block 1:Block:
+                Branch Variable(2, Boolean), 3, 2
+            Block 2:Block:
+                Return
+            Block 3:Block:
+                Call id(4), args( Variable(3, Qubit), )
+                Jump(2)"#]],
+    );
+}
+
+#[test]
+fn calls_to_no_inline_operation_with_different_static_arguments_generate_separate_callables() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {r#"
+        namespace Test {
+            operation Op(q : Qubit) : Unit { body intrinsic; }
+            @NoInline()
+            operation OpRepeated(q : Qubit, n : Int) : Unit {
+                for _ in 1..n {
+                    Op(q);
+                }
+            }
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                OpRepeated(q, 1);
+                OpRepeated(q, 2);
+                OpRepeated(q, 1);
+            }
+        }
+    "#},
+        TargetCapabilityFlags::all(),
+    );
+    assert_callable(
+        &program,
+        CallableId(1),
+        &expect![[r#"
+            Callable:
+                name: OpRepeated__body
+                call_type: Regular
+                input_type:
+                    [0]: Qubit
+                params:
+                    [0]: Variable(0, Qubit)
+                output_type: <VOID>
+                body: 1"#]],
+    );
+    assert_callable(
+        &program,
+        CallableId(3),
+        &expect![[r#"
+            Callable:
+                name: OpRepeated__body__1
+                call_type: Regular
+                input_type:
+                    [0]: Qubit
+                params:
+                    [0]: Variable(1, Qubit)
+                output_type: <VOID>
+                body: 2"#]],
+    );
+    assert_blocks(
+        &program,
+        &expect![[r#"
+            Blocks:
+            Block 0:Block:
+                Call id(1), args( Qubit(0), )
+                Call id(3), args( Qubit(0), )
+                Call id(1), args( Qubit(0), )
+                Call id(4), args( Integer(0), Pointer, )
+                Return
+            Block 1:Block:
+                Call id(2), args( Variable(0, Qubit), )
+                Return
+            Block 2:Block:
+                Call id(2), args( Variable(1, Qubit), )
+                Call id(2), args( Variable(1, Qubit), )
+                Return"#]],
+    );
+}
+
+#[test]
+fn call_to_adjoint_of_no_inline_operation_generates_adjoint_callable() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {r#"
+        namespace Test {
+            @NoInline()
+            operation OpAdj(q : Qubit) : Unit is Adj {
+                S(q);
+            }
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                Adjoint OpAdj(q);
+            }
+        }
+    "#},
+        TargetCapabilityFlags::all(),
+    );
+    assert_callable(
+        &program,
+        CallableId(1),
+        &expect![[r#"
+            Callable:
+                name: OpAdj__adj
+                call_type: Regular
+                input_type:
+                    [0]: Qubit
+                params:
+                    [0]: Variable(0, Qubit)
+                output_type: <VOID>
+                body: 1"#]],
+    );
+    assert_blocks(
+        &program,
+        &expect![[r#"
+            Blocks:
+            Block 0:Block:
+                Call id(1), args( Qubit(0), )
+                Call id(3), args( Integer(0), Pointer, )
+                Return
+            Block 1:Block:
+                Call id(2), args( Variable(0, Qubit), )
+                Return"#]],
+    );
+}
+
+#[test]
+fn call_to_no_inline_operation_without_higher_level_constructs_is_inlined() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {r#"
+        namespace Test {
+            operation Op(q : Qubit) : Unit { body intrinsic; }
+            @NoInline()
+            operation OpSquared(q : Qubit) : Unit {
+                Op(q);
+                Op(q);
+            }
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                OpSquared(q);
+            }
+        }
+    "#},
+        TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::QubitReset,
+    );
+    assert_block_instructions(
+        &program,
+        BlockId(0),
+        &expect![[r#"
+            Block:
+                Call id(1), args( Qubit(0), )
+                Call id(1), args( Qubit(0), )
+                Call id(2), args( Integer(0), Pointer, )
+                Return"#]],
+    );
+}
+
+#[test]
+fn calls_to_controlled_specialization_bind_controls_and_arguments() {
+    let program = get_rir_program(indoc! {r#"
+        namespace Test {
+            operation Op(q : Qubit) : Unit { body intrinsic; }
+            operation CtlOp(ctl : Qubit, q : Qubit) : Unit { body intrinsic; }
+            operation Flip(q : Qubit) : Unit is Ctl {
+                body ... {
+                    Op(q);
+                }
+                controlled (cs, ...) {
+                    for c in cs {
+                        CtlOp(c, q);
+                    }
+                }
+            }
+            @EntryPoint()
+            operation Main() : Unit {
+                use (a, b, c) = (Qubit(), Qubit(), Qubit());
+                Controlled Flip([a], c);
+                Controlled Controlled Flip([a], ([b], c));
+            }
+        }
+    "#});
+    assert_block_instructions(
+        &program,
+        BlockId(0),
+        &expect![[r#"
+        Block:
+            Call id(1), args( Qubit(0), Qubit(2), )
+            Call id(1), args( Qubit(0), Qubit(2), )
+            Call id(1), args( Qubit(1), Qubit(2), )
+            Call id(2), args( Integer(0), Pointer, )
+            Return"#]],
+    );
+}
+
+#[test]
+fn call_to_controlled_intrinsic_with_many_controls_computes_control_count_statically() {
+    let program = get_rir_program(indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use (ctls, target) = (Qubit[3], Qubit());
+                Controlled CNOT(ctls, (ctls[0], target));
+            }
+        }
+    "#});
+    assert_block_instructions(
+        &program,
+        BlockId(0),
+        &expect![[r#"
+        Block:
+            Call id(1), args( Qubit(0), Qubit(1), Qubit(4), )
+            Call id(1), args( Qubit(2), Qubit(0), Qubit(5), )
+            Call id(1), args( Qubit(5), Qubit(4), Qubit(3), )
+            Call id(2), args( Qubit(5), )
+            Call id(3), args( Qubit(5), Result(0), )
+            Variable(0, Boolean) = Call id(4), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+            Branch Variable(1, Boolean), 2, 1"#]],
+    );
+}
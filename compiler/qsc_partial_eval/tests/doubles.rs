@@ -0,0 +1,162 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+pub mod test_utils;
+
+use expect_test::expect;
+use indoc::indoc;
+use test_utils::{assert_blocks, assert_error, get_partial_evaluation_error, get_rir_program};
+
+#[test]
+fn dynamic_double_arithmetic_generates_floating_point_instructions() {
+    let program = get_rir_program(indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                let r = QIR.Intrinsic.__quantum__qis__mresetz__body(q);
+                let theta = if r == One { 1.0 } else { 0.0 };
+                let angle = (theta * 2.0 + 0.5 - theta) / 3.0;
+                QIR.Intrinsic.__quantum__qis__rx__body(angle, q);
+            }
+        }
+    "#});
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+            Branch Variable(1, Boolean), 2, 3
+        Block 1:Block:
+            Variable(3, Double) = Fmul Variable(2, Double), Double(2)
+            Variable(4, Double) = Fadd Variable(3, Double), Double(0.5)
+            Variable(5, Double) = Fsub Variable(4, Double), Variable(2, Double)
+            Variable(6, Double) = Fdiv Variable(5, Double), Double(3)
+            Call id(3), args( Variable(6, Double), Qubit(0), )
+            Call id(4), args( Integer(0), Pointer, )
+            Return
+        Block 2:Block:
+            Variable(2, Double) = Store Double(1)
+            Jump(1)
+        Block 3:Block:
+            Variable(2, Double) = Store Double(0)
+            Jump(1)"#]],
+    );
+}
+
+#[test]
+fn dynamic_double_comparisons_generate_fcmp_instructions() {
+    let program = get_rir_program(indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                let r = QIR.Intrinsic.__quantum__qis__mresetz__body(q);
+                let theta = if r == One { 1.0 } else { 0.0 };
+                let eq = theta == 1.0;
+                let neq = theta != 1.0;
+                let lt = theta < 0.5;
+                let gte = theta >= 0.5;
+                if gte {
+                    QIR.Intrinsic.__quantum__qis__x__body(q);
+                }
+            }
+        }
+    "#});
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+            Branch Variable(1, Boolean), 2, 3
+        Block 1:Block:
+            Variable(3, Boolean) = Fcmp Oeq, Variable(2, Double), Double(1)
+            Variable(4, Boolean) = Fcmp Une, Variable(2, Double), Double(1)
+            Variable(5, Boolean) = Fcmp Olt, Variable(2, Double), Double(0.5)
+            Variable(6, Boolean) = Fcmp Oge, Variable(2, Double), Double(0.5)
+            Branch Variable(6, Boolean), 5, 4
+        Block 2:Block:
+            Variable(2, Double) = Store Double(1)
+            Jump(1)
+        Block 3:Block:
+            Variable(2, Double) = Store Double(0)
+            Jump(1)
+        Block 4:Block:
+            Call id(4), args( Integer(0), Pointer, )
+            Return
+        Block 5:Block:
+            Call id(3), args( Qubit(0), )
+            Jump(4)"#]],
+    );
+}
+
+#[test]
+fn dynamic_int_and_double_conversions_generate_instructions() {
+    let program = get_rir_program(indoc! {r#"
+        namespace Test {
+            open Microsoft.Quantum.Convert;
+            open Microsoft.Quantum.Math;
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                let r = QIR.Intrinsic.__quantum__qis__mresetz__body(q);
+                let i = if r == One { 3 } else { 1 };
+                let theta = IntAsDouble(i) * 0.5;
+                let j = Truncate(theta);
+                let b = j == 1;
+                QIR.Intrinsic.__quantum__qis__rx__body(theta, q);
+            }
+        }
+    "#});
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+            Branch Variable(1, Boolean), 2, 3
+        Block 1:Block:
+            Variable(3, Double) = Sitofp Variable(2, Integer)
+            Variable(4, Double) = Fmul Variable(3, Double), Double(0.5)
+            Variable(5, Integer) = Fptosi Variable(4, Double)
+            Variable(6, Boolean) = Icmp Eq, Variable(5, Integer), Integer(1)
+            Call id(3), args( Variable(4, Double), Qubit(0), )
+            Call id(4), args( Integer(0), Pointer, )
+            Return
+        Block 2:Block:
+            Variable(2, Integer) = Store Integer(3)
+            Jump(1)
+        Block 3:Block:
+            Variable(2, Integer) = Store Integer(1)
+            Jump(1)"#]],
+    );
+}
+
+#[test]
+fn dynamic_double_exponentiation_yields_error() {
+    let error = get_partial_evaluation_error(indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                let r = QIR.Intrinsic.__quantum__qis__mresetz__body(q);
+                let theta = if r == One { 1.0 } else { 0.0 };
+                let angle = theta ^ 2.0;
+            }
+        }
+    "#});
+    assert_error(
+        &error,
+        &expect![[r#"Unimplemented("BinOp Expr (Exp)", Span { lo: 229, hi: 240 })"#]],
+    );
+}
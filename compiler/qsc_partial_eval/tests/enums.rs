@@ -0,0 +1,110 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+pub mod test_utils;
+
+use expect_test::expect;
+use indoc::indoc;
+use qsc_rir::rir::BlockId;
+use test_utils::{
+    assert_block_instructions, assert_blocks, assert_error, get_partial_evaluation_error,
+    get_rir_program,
+};
+
+#[test]
+fn match_on_enum_with_classical_variant() {
+    let program = get_rir_program(indoc! {r#"
+        namespace Test {
+            enum Rotation { X(Double), Z(Double), None }
+            operation opX(d : Double, q : Qubit) : Unit { body intrinsic; }
+            operation opZ(d : Double, q : Qubit) : Unit { body intrinsic; }
+            operation Apply(r : Rotation, q : Qubit) : Unit {
+                match r {
+                    Rotation.X(angle) => opX(angle, q),
+                    Rotation.Z(angle) => opZ(angle, q),
+                    Rotation.None => {},
+                }
+            }
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                let rotations = [Rotation.Z(1.0), Rotation.None, Rotation.X(2.0)];
+                for r in rotations {
+                    Apply(r, q);
+                }
+            }
+        }
+    "#});
+    assert_block_instructions(
+        &program,
+        BlockId(0),
+        &expect![[r#"
+        Block:
+            Call id(1), args( Double(1), Qubit(0), )
+            Call id(2), args( Double(2), Qubit(0), )
+            Call id(3), args( Integer(0), Pointer, )
+            Return"#]],
+    );
+}
+
+#[test]
+fn match_on_enum_with_classical_variant_and_dynamic_payload() {
+    let program = get_rir_program(indoc! {r#"
+        namespace Test {
+            enum Outcome { Measured(Result), Skipped }
+            operation opX(q : Qubit) : Unit { body intrinsic; }
+            @EntryPoint()
+            operation Main() : Unit {
+                use (q0, q1) = (Qubit(), Qubit());
+                let outcome = Outcome.Measured(M(q0));
+                match outcome {
+                    Outcome.Measured(r) => if r == One { opX(q1); },
+                    Outcome.Skipped => {},
+                }
+            }
+        }
+    "#});
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+            Branch Variable(1, Boolean), 2, 1
+        Block 1:Block:
+            Call id(4), args( Integer(0), Pointer, )
+            Return
+        Block 2:Block:
+            Call id(3), args( Qubit(1), )
+            Jump(1)"#]],
+    );
+}
+
+#[test]
+fn match_on_enum_with_dynamic_variant_yields_error() {
+    let error = get_partial_evaluation_error(indoc! {r#"
+        namespace Test {
+            enum Outcome { Flipped, Kept }
+            operation opX(q : Qubit) : Unit { body intrinsic; }
+            @EntryPoint()
+            operation Main() : Unit {
+                use (q0, q1) = (Qubit(), Qubit());
+                let outcome = M(q0) == One ? Outcome.Flipped | Outcome.Kept;
+                match outcome {
+                    Outcome.Flipped => opX(q1),
+                    Outcome.Kept => {},
+                }
+            }
+        }
+    "#});
+    assert_error(
+        &error,
+        &expect![[
+            r#"Unimplemented("If Expr with dynamic non-primitive value", Span { lo: 222, hi: 267 })"#
+        ]],
+    );
+}
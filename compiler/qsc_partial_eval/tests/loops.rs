@@ -7,8 +7,13 @@ pub mod test_utils;
 
 use expect_test::expect;
 use indoc::indoc;
+use qsc_data_structures::target::TargetCapabilityFlags;
 use qsc_rir::rir::{BlockId, CallableId};
-use test_utils::{assert_block_instructions, assert_callable, get_rir_program};
+use test_utils::{
+    assert_block_instructions, assert_blocks, assert_callable, assert_error,
+    get_partial_evaluation_error_with_capabilities, get_rir_program,
+    get_rir_program_with_capabilities,
+};
 
 #[test]
 fn unitary_call_within_a_for_loop() {
@@ -280,3 +285,272 @@ fn rotation_call_within_a_repeat_until_loop() {
                 Return"#]],
     );
 }
+
+#[test]
+fn unitary_call_within_a_for_loop_with_break() {
+    let program = get_rir_program(indoc! {
+        r#"
+        namespace Test {
+            operation op(q : Qubit) : Unit { body intrinsic; }
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                for i in 0..5 {
+                    if i == 2 {
+                        break;
+                    }
+                    op(q);
+                }
+            }
+        }
+        "#,
+    });
+
+    assert_block_instructions(
+        &program,
+        BlockId(0),
+        &expect![[r#"
+        Block:
+            Call id(1), args( Qubit(0), )
+            Call id(1), args( Qubit(0), )
+            Call id(2), args( Integer(0), Pointer, )
+            Return"#]],
+    );
+}
+
+#[test]
+fn unitary_call_within_a_while_loop_with_continue() {
+    let program = get_rir_program(indoc! {
+        r#"
+        namespace Test {
+            operation op(q : Qubit) : Unit { body intrinsic; }
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                mutable idx = 0;
+                while idx < 4 {
+                    set idx += 1;
+                    if idx % 2 == 0 {
+                        continue;
+                    }
+                    op(q);
+                }
+            }
+        }
+        "#,
+    });
+
+    assert_block_instructions(
+        &program,
+        BlockId(0),
+        &expect![[r#"
+        Block:
+            Call id(1), args( Qubit(0), )
+            Call id(1), args( Qubit(0), )
+            Call id(2), args( Integer(0), Pointer, )
+            Return"#]],
+    );
+}
+
+#[test]
+fn repeat_until_loop_with_dynamic_condition_generates_back_edge() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {
+            r#"
+            namespace Test {
+                operation op(q : Qubit) : Unit { body intrinsic; }
+                @EntryPoint()
+                operation Main() : Unit {
+                    use q = Qubit();
+                    mutable r = Zero;
+                    repeat {
+                        op(q);
+                        set r = M(q);
+                    } until r == Zero;
+                }
+            }
+            "#,
+        },
+        TargetCapabilityFlags::all(),
+    );
+
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Variable(0, Boolean) = Store Bool(false)
+            Variable(1, Boolean) = Store Bool(true)
+            Jump(1)
+        Block 1:Block:
+            Variable(2, Boolean) = Store Variable(1, Boolean)
+            Branch Variable(2, Boolean), 3, 2
+        Block 2:Block:
+            Call id(4), args( Integer(0), Pointer, )
+            Return
+        Block 3:Block:
+            Call id(1), args( Qubit(0), )
+            Call id(2), args( Qubit(0), Result(0), )
+            Variable(3, Boolean) = Call id(3), args( Result(0), )
+            Variable(0, Boolean) = Store Variable(3, Boolean)
+            Variable(4, Boolean) = Store Variable(0, Boolean)
+            Variable(5, Boolean) = Icmp Eq, Variable(4, Boolean), Bool(false)
+            Variable(6, Boolean) = LogicalNot Variable(5, Boolean)
+            Variable(1, Boolean) = Store Variable(6, Boolean)
+            Jump(1)"#]],
+    );
+}
+
+#[test]
+fn while_loop_with_dynamic_condition_updates_counter() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {
+            r#"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : Int {
+                    use q = Qubit();
+                    mutable count = 0;
+                    while MResetZ(q) == One {
+                        set count += 1;
+                    }
+                    count
+                }
+            }
+            "#,
+        },
+        TargetCapabilityFlags::all(),
+    );
+
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Variable(0, Integer) = Store Integer(0)
+            Jump(1)
+        Block 1:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(1, Boolean) = Call id(2), args( Result(0), )
+            Variable(2, Boolean) = Icmp Eq, Variable(1, Boolean), Bool(true)
+            Branch Variable(2, Boolean), 3, 2
+        Block 2:Block:
+            Variable(4, Integer) = Store Variable(0, Integer)
+            Call id(3), args( Variable(4, Integer), Pointer, )
+            Return
+        Block 3:Block:
+            Variable(3, Integer) = Add Variable(0, Integer), Integer(1)
+            Variable(0, Integer) = Store Variable(3, Integer)
+            Jump(1)"#]],
+    );
+}
+
+#[test]
+fn for_loop_with_dynamic_break_generates_back_edge() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {
+            r#"
+            namespace Test {
+                operation op(q : Qubit) : Unit { body intrinsic; }
+                @EntryPoint()
+                operation Main() : Unit {
+                    use q = Qubit();
+                    for _ in 0..2 {
+                        if MResetZ(q) == One {
+                            break;
+                        }
+                        op(q);
+                    }
+                }
+            }
+            "#,
+        },
+        TargetCapabilityFlags::all(),
+    );
+
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Variable(0, Integer) = Store Integer(0)
+            Jump(1)
+        Block 1:Block:
+            Variable(1, Integer) = Store Variable(0, Integer)
+            Variable(2, Boolean) = Icmp Sle, Variable(1, Integer), Integer(2)
+            Variable(3, Boolean) = LogicalAnd Bool(true), Variable(2, Boolean)
+            Variable(4, Integer) = Store Variable(0, Integer)
+            Variable(5, Boolean) = Icmp Sge, Variable(4, Integer), Integer(2)
+            Variable(6, Boolean) = LogicalAnd Bool(false), Variable(5, Boolean)
+            Variable(7, Boolean) = LogicalOr Variable(3, Boolean), Variable(6, Boolean)
+            Branch Variable(7, Boolean), 3, 2
+        Block 2:Block:
+            Call id(4), args( Integer(0), Pointer, )
+            Return
+        Block 3:Block:
+            Variable(8, Integer) = Store Variable(0, Integer)
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(9, Boolean) = Call id(2), args( Result(0), )
+            Variable(10, Boolean) = Icmp Eq, Variable(9, Boolean), Bool(true)
+            Branch Variable(10, Boolean), 5, 4
+        Block 4:Block:
+            Call id(3), args( Qubit(0), )
+            Variable(11, Integer) = Add Variable(0, Integer), Integer(1)
+            Variable(0, Integer) = Store Variable(11, Integer)
+            Jump(1)
+        Block 5:Block:
+            Jump(2)"#]],
+    );
+}
+
+#[test]
+fn while_loop_with_dynamic_condition_without_backwards_branching_yields_error() {
+    let error = get_partial_evaluation_error_with_capabilities(
+        indoc! {
+            r#"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : Unit {
+                    use q = Qubit();
+                    while MResetZ(q) == One {}
+                }
+            }
+            "#,
+        },
+        TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::QubitReset,
+    );
+
+    assert_error(
+        &error,
+        &expect![[r#"Unimplemented("Loop With Dynamic Condition", Span { lo: 99, hi: 125 })"#]],
+    );
+}
+
+#[test]
+fn returning_result_updated_in_loop_with_dynamic_condition_yields_error() {
+    let error = get_partial_evaluation_error_with_capabilities(
+        indoc! {
+            r#"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : Result {
+                    use q = Qubit();
+                    mutable r = Zero;
+                    repeat {
+                        set r = M(q);
+                    } until r == Zero;
+                    r
+                }
+            }
+            "#,
+        },
+        TargetCapabilityFlags::all(),
+    );
+
+    assert_error(
+        &error,
+        &expect![[
+            r#"Unimplemented("Output Recording of Dynamic Result Variable", Span { lo: 50, hi: 54 })"#
+        ]],
+    );
+}
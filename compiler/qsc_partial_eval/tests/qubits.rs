@@ -7,8 +7,12 @@ pub mod test_utils;
 
 use expect_test::expect;
 use indoc::indoc;
+use qsc_data_structures::target::TargetCapabilityFlags;
 use qsc_rir::rir::{BlockId, CallableId};
-use test_utils::{assert_block_instructions, assert_callable, get_rir_program};
+use test_utils::{
+    assert_block_instructions, assert_blocks, assert_callable, get_rir_program,
+    get_rir_program_with_capabilities,
+};
 
 #[test]
 fn qubit_ids_are_correct_for_allocate_use_release_one_qubit() {
@@ -248,3 +252,194 @@ fn qubit_ids_are_correct_for_allocate_use_release_multiple_qubits_interleaved()
     assert_eq!(program.num_qubits, 4);
     assert_eq!(program.num_results, 0);
 }
+
+#[test]
+fn qubit_array_split_with_array_patterns() {
+    let program = get_rir_program(indoc! {
+        r#"
+        namespace Test {
+            operation op(q : Qubit) : Unit { body intrinsic; }
+            operation ApplyToFirst([first : Qubit, ..._ : Qubit[]]) : Unit {
+                op(first);
+            }
+            @EntryPoint()
+            operation Main() : Unit {
+                let qs = [
+                    QIR.Runtime.__quantum__rt__qubit_allocate(),
+                    QIR.Runtime.__quantum__rt__qubit_allocate(),
+                    QIR.Runtime.__quantum__rt__qubit_allocate()
+                ];
+                let [q0, ...rest] = qs;
+                op(q0);
+                ApplyToFirst(rest);
+            }
+        }
+        "#,
+    });
+    assert_block_instructions(
+        &program,
+        BlockId(0),
+        &expect![[r#"
+        Block:
+            Call id(1), args( Qubit(0), )
+            Call id(1), args( Qubit(1), )
+            Call id(2), args( Integer(0), Pointer, )
+            Return"#]],
+    );
+}
+
+#[test]
+fn qubit_ids_are_correct_for_allocate_use_release_qubit_array() {
+    let program = get_rir_program(indoc! {
+        r#"
+        namespace Test {
+            operation op(q : Qubit) : Unit { body intrinsic; }
+            @EntryPoint()
+            operation Main() : Unit {
+                use qs = Qubit[2];
+                op(qs[0]);
+                op(qs[1]);
+            }
+        }
+        "#,
+    });
+    assert_block_instructions(
+        &program,
+        BlockId(0),
+        &expect![[r#"
+        Block:
+            Call id(1), args( Qubit(0), )
+            Call id(1), args( Qubit(1), )
+            Call id(2), args( Integer(0), Pointer, )
+            Return"#]],
+    );
+    assert_eq!(program.num_qubits, 2);
+}
+
+#[test]
+fn qubit_allocated_within_dynamic_branch_uses_runtime_allocation_with_dynamic_qubit_management() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {
+            r#"
+            namespace Test {
+                operation op(q : Qubit) : Unit { body intrinsic; }
+                @EntryPoint()
+                operation Main() : Unit {
+                    use control = Qubit();
+                    if M(control) == One {
+                        use q = Qubit();
+                        op(q);
+                    }
+                }
+            }
+            "#,
+        },
+        TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::DynamicQubitManagement,
+    );
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+            Branch Variable(1, Boolean), 2, 1
+        Block 1:Block:
+            Call id(6), args( Integer(0), Pointer, )
+            Return
+        Block 2:Block:
+            Variable(2, Qubit) = Call id(3), args( )
+            Call id(4), args( Variable(2, Qubit), )
+            Call id(5), args( Variable(2, Qubit), )
+            Jump(1)"#]],
+    );
+    assert_eq!(program.num_qubits, 1);
+}
+
+#[test]
+fn qubit_array_allocated_within_dynamic_branch_uses_array_runtime_functions_with_dynamic_qubit_management(
+) {
+    let program = get_rir_program_with_capabilities(
+        indoc! {
+            r#"
+            namespace Test {
+                operation op(q : Qubit) : Unit { body intrinsic; }
+                @EntryPoint()
+                operation Main() : Unit {
+                    use control = Qubit();
+                    if M(control) == One {
+                        use qs = Qubit[2];
+                        op(qs[0]);
+                        op(qs[1]);
+                    }
+                }
+            }
+            "#,
+        },
+        TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::DynamicQubitManagement,
+    );
+    assert_blocks(
+        &program,
+        &expect![[r#"
+            Blocks:
+            Block 0:Block:
+                Call id(1), args( Qubit(0), Result(0), )
+                Variable(0, Boolean) = Call id(2), args( Result(0), )
+                Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+                Branch Variable(1, Boolean), 2, 1
+            Block 1:Block:
+                Call id(7), args( Integer(0), Pointer, )
+                Return
+            Block 2:Block:
+                Variable(2, Pointer) = Call id(3), args( Integer(2), )
+                Variable(3, Pointer) = Call id(4), args( Variable(2, Pointer), Integer(0), )
+                Variable(4, Qubit) = Load Variable(3, Pointer)
+                Variable(5, Pointer) = Call id(4), args( Variable(2, Pointer), Integer(1), )
+                Variable(6, Qubit) = Load Variable(5, Pointer)
+                Call id(5), args( Variable(4, Qubit), )
+                Call id(5), args( Variable(6, Qubit), )
+                Call id(6), args( Variable(2, Pointer), )
+                Jump(1)"#]],
+    );
+    assert_eq!(program.num_qubits, 1);
+}
+
+#[test]
+fn qubit_allocated_within_dynamic_branch_uses_static_id_without_dynamic_qubit_management() {
+    let program = get_rir_program_with_capabilities(
+        indoc! {
+            r#"
+            namespace Test {
+                operation op(q : Qubit) : Unit { body intrinsic; }
+                @EntryPoint()
+                operation Main() : Unit {
+                    use control = Qubit();
+                    if M(control) == One {
+                        use q = Qubit();
+                        op(q);
+                    }
+                }
+            }
+            "#,
+        },
+        TargetCapabilityFlags::Adaptive,
+    );
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(true)
+            Branch Variable(1, Boolean), 2, 1
+        Block 1:Block:
+            Call id(4), args( Integer(0), Pointer, )
+            Return
+        Block 2:Block:
+            Call id(3), args( Qubit(1), )
+            Jump(1)"#]],
+    );
+    assert_eq!(program.num_qubits, 2);
+}
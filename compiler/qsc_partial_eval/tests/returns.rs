@@ -890,10 +890,11 @@ fn explicit_return_embedded_in_assign_field_expr_yields_error() {
         }
     }
     "#});
-    // The type of error will change once this kind of hybrid expression is supported.
     assert_error(
         &error,
-        &expect![[r#"Unimplemented("Updated Field Expr", Span { lo: 217, hi: 243 })"#]],
+        &expect![[
+            r#"Unexpected("embedded return in update field expression", Span { lo: 231, hi: 243 })"#
+        ]],
     );
 }
 
@@ -910,10 +911,11 @@ fn explicit_return_embedded_in_assign_index_expr_yields_error() {
         }
     }
     "#});
-    // The type of error will change once this kind of hybrid expression is supported.
     assert_error(
         &error,
-        &expect![[r#"Unimplemented("Update Index Expr", Span { lo: 170, hi: 193 })"#]],
+        &expect![[
+            r#"Unexpected("embedded return in update index expression", Span { lo: 181, hi: 193 })"#
+        ]],
     );
 }
 
@@ -1010,10 +1012,11 @@ fn explicit_return_embedded_in_index_expr_yields_error() {
         }
     }
     "#});
-    // The type of error will change once this kind of hybrid expression is supported.
     assert_error(
         &error,
-        &expect![[r#"Unimplemented("Index Expr", Span { lo: 168, hi: 195 })"#]],
+        &expect![[
+            r#"Unexpected("embedded return in index expression", Span { lo: 170, hi: 194 })"#
+        ]],
     );
 }
 
@@ -1068,10 +1071,11 @@ fn explicit_return_embedded_in_update_field_expr_yields_error() {
         }
     }
     "#});
-    // The type of error will change once this kind of hybrid expression is supported.
     assert_error(
         &error,
-        &expect![[r#"Unimplemented("Field Assignment Expr", Span { lo: 211, hi: 241 })"#]],
+        &expect![[
+            r#"Unexpected("embedded return in field assignment", Span { lo: 229, hi: 241 })"#
+        ]],
     );
 }
 
@@ -1088,9 +1092,10 @@ fn explicit_return_embedded_in_update_index_expr_yields_error() {
         }
     }
     "#});
-    // The type of error will change once this kind of hybrid expression is supported.
     assert_error(
         &error,
-        &expect![[r#"Unimplemented("Assignment Index Expr", Span { lo: 164, hi: 191 })"#]],
+        &expect![[
+            r#"Unexpected("embedded return in index assignment", Span { lo: 179, hi: 191 })"#
+        ]],
     );
 }
@@ -37,7 +37,15 @@ pub fn assert_error(error: &Error, expected_error: &Expect) {
 
 #[must_use]
 pub fn get_partial_evaluation_error(source: &str) -> Error {
-    let maybe_program = compile_and_partially_evaluate(source);
+    get_partial_evaluation_error_with_capabilities(source, TargetCapabilityFlags::empty())
+}
+
+#[must_use]
+pub fn get_partial_evaluation_error_with_capabilities(
+    source: &str,
+    capabilities: TargetCapabilityFlags,
+) -> Error {
+    let maybe_program = compile_and_partially_evaluate(source, capabilities);
     match maybe_program {
         Ok(_) => panic!("partial evaluation succeeded"),
         Err(error) => error,
@@ -46,20 +54,31 @@ pub fn get_partial_evaluation_error(source: &str) -> Error {
 
 #[must_use]
 pub fn get_rir_program(source: &str) -> Program {
-    let maybe_program = compile_and_partially_evaluate(source);
+    get_rir_program_with_capabilities(source, TargetCapabilityFlags::empty())
+}
+
+#[must_use]
+pub fn get_rir_program_with_capabilities(
+    source: &str,
+    capabilities: TargetCapabilityFlags,
+) -> Program {
+    let maybe_program = compile_and_partially_evaluate(source, capabilities);
     match maybe_program {
         Ok(program) => program,
         Err(error) => panic!("partial evaluation failed: {error:?}"),
     }
 }
 
-fn compile_and_partially_evaluate(source: &str) -> Result<Program, Error> {
+fn compile_and_partially_evaluate(
+    source: &str,
+    capabilities: TargetCapabilityFlags,
+) -> Result<Program, Error> {
     let compilation_context = CompilationContext::new(source);
     partially_evaluate(
         &compilation_context.fir_store,
         &compilation_context.compute_properties,
         &compilation_context.entry,
-        TargetCapabilityFlags::empty(),
+        capabilities,
     )
 }
 
@@ -0,0 +1,176 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+pub mod test_utils;
+
+use expect_test::expect;
+use indoc::indoc;
+use test_utils::{assert_blocks, assert_error, get_partial_evaluation_error, get_rir_program};
+
+#[test]
+fn nested_array_update_with_dynamic_value() {
+    let program = get_rir_program(indoc! {
+        r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                use q = Qubit();
+                let r = QIR.Intrinsic.__quantum__qis__mresetz__body(q);
+                let value = if r == Zero { 3 } else { 4 };
+                let grid = [[0, 1], [2, 5]];
+                let updated = grid w/ [1][0] <- value;
+                updated[1][0] + updated[1][1]
+            }
+        }
+        "#,
+    });
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(false)
+            Branch Variable(1, Boolean), 2, 3
+        Block 1:Block:
+            Variable(3, Integer) = Add Variable(2, Integer), Integer(5)
+            Call id(3), args( Variable(3, Integer), Pointer, )
+            Return
+        Block 2:Block:
+            Variable(2, Integer) = Store Integer(3)
+            Jump(1)
+        Block 3:Block:
+            Variable(2, Integer) = Store Integer(4)
+            Jump(1)"#]],
+    );
+}
+
+#[test]
+fn nested_array_assign_update_with_dynamic_value() {
+    let program = get_rir_program(indoc! {
+        r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                use q = Qubit();
+                let r = QIR.Intrinsic.__quantum__qis__mresetz__body(q);
+                let value = if r == Zero { 3 } else { 4 };
+                mutable grid = [[0, 1], [2, 5]];
+                set grid w/= [1][0] <- value;
+                grid[1][0] + grid[1][1]
+            }
+        }
+        "#,
+    });
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(false)
+            Branch Variable(1, Boolean), 2, 3
+        Block 1:Block:
+            Variable(3, Integer) = Add Variable(2, Integer), Integer(5)
+            Call id(3), args( Variable(3, Integer), Pointer, )
+            Return
+        Block 2:Block:
+            Variable(2, Integer) = Store Integer(3)
+            Jump(1)
+        Block 3:Block:
+            Variable(2, Integer) = Store Integer(4)
+            Jump(1)"#]],
+    );
+}
+
+#[test]
+fn nested_field_update_with_dynamic_value() {
+    let program = get_rir_program(indoc! {
+        r#"
+        namespace Test {
+            newtype Pair = (First : Int, Second : Int);
+            @EntryPoint()
+            operation Main() : Int {
+                use q = Qubit();
+                let r = QIR.Intrinsic.__quantum__qis__mresetz__body(q);
+                let value = if r == Zero { 3 } else { 4 };
+                mutable pairs = [Pair(0, 1), Pair(2, 5)];
+                set pairs w/= [1]::Second <- value;
+                pairs[1]::First + pairs[1]::Second
+            }
+        }
+        "#,
+    });
+    assert_blocks(
+        &program,
+        &expect![[r#"
+        Blocks:
+        Block 0:Block:
+            Call id(1), args( Qubit(0), Result(0), )
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Boolean) = Icmp Eq, Variable(0, Boolean), Bool(false)
+            Branch Variable(1, Boolean), 2, 3
+        Block 1:Block:
+            Variable(3, Integer) = Add Integer(2), Variable(2, Integer)
+            Call id(3), args( Variable(3, Integer), Pointer, )
+            Return
+        Block 2:Block:
+            Variable(2, Integer) = Store Integer(3)
+            Jump(1)
+        Block 3:Block:
+            Variable(2, Integer) = Store Integer(4)
+            Jump(1)"#]],
+    );
+}
+
+#[test]
+fn nested_array_update_with_dynamic_index_yields_error() {
+    let error = get_partial_evaluation_error(indoc! {
+        r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                use q = Qubit();
+                let r = QIR.Intrinsic.__quantum__qis__mresetz__body(q);
+                let index = if r == Zero { 0 } else { 1 };
+                let grid = [[0, 1], [2, 5]];
+                let updated = grid w/ [1][index] <- 3;
+                updated[1][0]
+            }
+        }
+        "#,
+    });
+    assert_error(
+        &error,
+        &expect![[r#"Unimplemented("Update Index Expr", Span { lo: 264, hi: 287 })"#]],
+    );
+}
+
+#[test]
+fn nested_array_assign_update_within_dynamic_branch_yields_error() {
+    let error = get_partial_evaluation_error(indoc! {
+        r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Int {
+                use q = Qubit();
+                let r = QIR.Intrinsic.__quantum__qis__mresetz__body(q);
+                let value = if r == Zero { 3 } else { 4 };
+                mutable grid = [[0, 1], [2, 5]];
+                if r == Zero {
+                    set grid w/= [1][0] <- value;
+                }
+                grid[1][0]
+            }
+        }
+        "#,
+    });
+    assert_error(
+        &error,
+        &expect![[r#"Unimplemented("Assignment Index Expr", Span { lo: 281, hi: 309 })"#]],
+    );
+}
@@ -50,6 +50,11 @@ impl Checker {
                     self.track_pat(pat);
                 }
             }
+            PatKind::Array(items, rest) => {
+                for pat in items.iter().chain(rest.as_deref()) {
+                    self.track_pat(pat);
+                }
+            }
         }
     }
 
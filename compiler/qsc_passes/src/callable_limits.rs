@@ -7,7 +7,7 @@ mod tests;
 use miette::Diagnostic;
 use qsc_data_structures::span::Span;
 use qsc_hir::{
-    hir::{CallableDecl, CallableKind, Expr, ExprKind, Package, Stmt, StmtKind},
+    hir::{CallableDecl, CallableKind, Expr, ExprKind, Item, ItemKind, Package, Stmt, StmtKind},
     ty::{FunctorSetValue, Ty},
     visit::{self, Visitor},
 };
@@ -50,6 +50,13 @@ impl Visitor<'_> for CallableLimits {
         package.items.values().for_each(|i| self.visit_item(i));
     }
 
+    fn visit_item(&mut self, item: &Item) {
+        // The values of constants are checked when they are evaluated.
+        if !matches!(item.kind, ItemKind::Const(..)) {
+            visit::walk_item(self, item);
+        }
+    }
+
     fn visit_callable_decl(&mut self, decl: &CallableDecl) {
         if decl.kind == CallableKind::Function {
             if decl.adj.is_some() || decl.ctl.is_some() || decl.ctl_adj.is_some() {
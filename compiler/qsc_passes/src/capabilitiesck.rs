@@ -619,6 +619,13 @@ fn get_missing_runtime_features(
     runtime_features: RuntimeFeatureFlags,
     target_capabilities: TargetCapabilityFlags,
 ) -> RuntimeFeatureFlags {
+    // Dynamic qubits need higher level constructs, unless the target manages qubits dynamically.
+    let runtime_features =
+        if target_capabilities.contains(TargetCapabilityFlags::DynamicQubitManagement) {
+            runtime_features - RuntimeFeatureFlags::UseOfDynamicQubit
+        } else {
+            runtime_features
+        };
     let missing_capabilities = !target_capabilities & runtime_features.target_capabilities();
     runtime_features.contributing_features(missing_capabilities)
 }
@@ -146,6 +146,28 @@ fn use_of_dynamic_qubit_yields_errors() {
     );
 }
 
+#[test]
+fn use_of_dynamic_qubit_with_dynamic_qubit_management_yields_no_errors() {
+    check(
+        USE_DYNAMIC_QUBIT,
+        &expect![[r#"
+            []
+        "#]],
+        TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::DynamicQubitManagement,
+    );
+}
+
+#[test]
+fn use_of_dynamic_qubit_with_higher_level_constructs_yields_no_errors() {
+    check(
+        USE_DYNAMIC_QUBIT,
+        &expect![[r#"
+            []
+        "#]],
+        TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::HigherLevelConstructs,
+    );
+}
+
 #[test]
 fn use_of_dynamic_big_int_yields_errors() {
     check_profile(
@@ -0,0 +1,570 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use miette::Diagnostic;
+use qsc_data_structures::{index_map::IndexMap, span::Span};
+use qsc_eval::{
+    backend::SparseSim,
+    limits::Limits,
+    output::GenericReceiver,
+    val::{self, Value},
+    Env, State, StepAction, StepResult,
+};
+use qsc_fir::fir;
+use qsc_frontend::compile::PackageStore;
+use qsc_hir::{
+    assigner::Assigner,
+    hir::{
+        CallableKind, Expr, ExprKind, ItemId, ItemKind, Lit, LocalItemId, Package, PackageId,
+        Pauli, Res, Result, Stmt, StmtKind, StringComponent,
+    },
+    mut_visit::{self, MutVisitor},
+    ty::{Prim, Ty},
+    visit::{self, Visitor},
+};
+use qsc_lowerer::{map_hir_package_to_fir, Lowerer};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{iter::once, rc::Rc};
+use thiserror::Error;
+
+/// The number of execution steps the evaluation of a single constant may take.
+const STEP_LIMIT: u64 = 1_000_000;
+
+#[derive(Clone, Debug, Diagnostic, Error)]
+pub enum Error {
+    #[error("constant `{0}` depends on its own value")]
+    #[diagnostic(code("Qsc.ConstEval.Cycle"))]
+    Cycle(String, #[label] Span),
+
+    #[error("constant evaluation failed: {0}")]
+    #[diagnostic(code("Qsc.ConstEval.EvalFailed"))]
+    EvalFailed(String, #[label] Span),
+
+    #[error("constants cannot call operations")]
+    #[diagnostic(code("Qsc.ConstEval.OpCall"))]
+    OpCall(#[label] Span),
+
+    #[error("constants cannot allocate qubits")]
+    #[diagnostic(code("Qsc.ConstEval.QubitAlloc"))]
+    QubitAlloc(#[label] Span),
+
+    #[error("constant has no value")]
+    #[diagnostic(help("the evaluation of this constant failed when it was declared"))]
+    #[diagnostic(code("Qsc.ConstEval.MissingValue"))]
+    MissingValue(#[label] Span),
+
+    #[error("constants of type {0} are not supported")]
+    #[diagnostic(help(
+        "constants can have any primitive type other than Qubit, or be arrays or tuples of such types"
+    ))]
+    #[diagnostic(code("Qsc.ConstEval.UnsupportedTy"))]
+    UnsupportedTy(String, #[label] Span),
+}
+
+/// Evaluates the `const` items in the package, then replaces every use of a constant, including the
+/// constants of dependencies, with a literal of its value. If the package is an increment of a
+/// package in the store, `open` is the ID of that package.
+pub(super) fn fold_consts(
+    store: &PackageStore,
+    open: Option<PackageId>,
+    package: &mut Package,
+    assigner: &mut Assigner,
+) -> Vec<Error> {
+    let mut errors = Vec::new();
+    let uses = const_uses(package);
+    let order = eval_order(package, &uses, &mut errors);
+    let mut failed: FxHashSet<_> = uses
+        .keys()
+        .filter(|item| !order.contains(item))
+        .copied()
+        .collect();
+    let mut substitute = Substitute::new(store, open, package, assigner);
+    let mut substituted = FxHashSet::default();
+
+    if !order.is_empty() {
+        let mut evaluator = Evaluator::new(store, open, package);
+        for item in order {
+            if uses[&item].consts.iter().any(|dep| failed.contains(dep)) {
+                failed.insert(item);
+                continue;
+            }
+
+            // The item and the callables it reaches only use constants evaluated so far, so their
+            // uses are replaced before they are lowered and evaluation never reaches a constant
+            // that has no value yet. Each item is substituted only once.
+            let mut changed = Vec::new();
+            for id in uses[&item].callables.iter().copied().chain(once(item)) {
+                if substituted.insert(id) {
+                    let local = package
+                        .items
+                        .get_mut(id)
+                        .expect("item should be in package");
+                    substitute.visit_item(local);
+                    changed.push(id);
+                }
+            }
+            evaluator.update(package, &changed);
+
+            let result = match package.items.get(item).map(|i| &i.kind) {
+                Some(ItemKind::Const(_, value)) if !is_const_ty(&value.ty) => {
+                    Err(Error::UnsupportedTy(value.ty.display(), value.span))
+                }
+                Some(ItemKind::Const(_, value)) => evaluator.eval(value),
+                _ => panic!("item should be a constant"),
+            };
+
+            match result {
+                Ok((value, ty, span)) => {
+                    let literal = value_to_expr(&value, &ty, span, substitute.assigner);
+                    if let Some(ItemKind::Const(_, value)) =
+                        package.items.get_mut(item).map(|i| &mut i.kind)
+                    {
+                        *value = literal.clone();
+                    }
+                    substitute.values.insert(item, literal);
+                }
+                Err(error) => {
+                    errors.push(error);
+                    failed.insert(item);
+                }
+            }
+        }
+    }
+
+    for item in package.items.values_mut() {
+        if !substituted.contains(&item.id) {
+            substitute.visit_item(item);
+        }
+    }
+    package
+        .stmts
+        .iter_mut()
+        .for_each(|stmt| substitute.visit_stmt(stmt));
+    if let Some(entry) = &mut package.entry {
+        substitute.visit_expr(entry);
+    }
+    errors.extend(substitute.errors);
+    errors
+}
+
+/// The items that a constant uses either directly or through the callables it calls.
+#[derive(Default)]
+struct Uses {
+    /// The local constants.
+    consts: FxHashSet<LocalItemId>,
+    /// The local callables.
+    callables: FxHashSet<LocalItemId>,
+}
+
+/// Reports the operation calls and qubit allocations in the values of constants, which cannot be
+/// evaluated at compile time. This runs before qubit allocations are replaced with calls.
+pub(super) fn check_purity(package: &Package) -> Vec<Error> {
+    let mut purity = Purity::default();
+    for item in package.items.values() {
+        if let ItemKind::Const(_, value) = &item.kind {
+            purity.visit_expr(value);
+        }
+    }
+    purity.errors
+}
+
+/// Collects the uses of each constant in the package.
+fn const_uses(package: &Package) -> FxHashMap<LocalItemId, Uses> {
+    package
+        .items
+        .values()
+        .filter_map(|item| {
+            let ItemKind::Const(_, value) = &item.kind else {
+                return None;
+            };
+            let mut refs = ConstRefs {
+                package,
+                uses: Uses::default(),
+            };
+            refs.visit_expr(value);
+            Some((item.id, refs.uses))
+        })
+        .collect()
+}
+
+/// Orders the constants so that each one comes after the constants it uses. Constants that use
+/// their own value are reported and left out.
+fn eval_order(
+    package: &Package,
+    uses: &FxHashMap<LocalItemId, Uses>,
+    errors: &mut Vec<Error>,
+) -> Vec<LocalItemId> {
+    fn visit(
+        package: &Package,
+        uses: &FxHashMap<LocalItemId, Uses>,
+        item: LocalItemId,
+        visiting: &mut FxHashSet<LocalItemId>,
+        visited: &mut FxHashSet<LocalItemId>,
+        order: &mut Vec<LocalItemId>,
+        errors: &mut Vec<Error>,
+    ) {
+        if visited.contains(&item) {
+            return;
+        }
+        if !visiting.insert(item) {
+            if let Some(ItemKind::Const(name, _)) = package.items.get(item).map(|i| &i.kind) {
+                errors.push(Error::Cycle(name.name.to_string(), name.span));
+            }
+            visited.insert(item);
+            return;
+        }
+
+        for &dep in &uses[&item].consts {
+            visit(package, uses, dep, visiting, visited, order, errors);
+        }
+
+        visiting.remove(&item);
+        if visited.insert(item) {
+            order.push(item);
+        }
+    }
+
+    let mut visiting = FxHashSet::default();
+    let mut visited = FxHashSet::default();
+    let mut order = Vec::new();
+    for item in package.items.values() {
+        if uses.contains_key(&item.id) {
+            visit(
+                package,
+                uses,
+                item.id,
+                &mut visiting,
+                &mut visited,
+                &mut order,
+                errors,
+            );
+        }
+    }
+    order
+}
+
+/// Evaluates values with the store lowered once. The package is lowered together with the earlier
+/// increments of it if there are any, and afterwards only the items that change are lowered again.
+struct Evaluator {
+    fir_store: fir::PackageStore,
+    fir_id: fir::PackageId,
+    lowerer: Lowerer,
+}
+
+impl Evaluator {
+    fn new(store: &PackageStore, open: Option<PackageId>, package: &Package) -> Self {
+        let mut fir_store = fir::PackageStore::new();
+        for (id, unit) in store {
+            if Some(id) != open {
+                let fir_package = Lowerer::new().lower_package(&unit.package);
+                fir_store.insert(map_hir_package_to_fir(id), fir_package);
+            }
+        }
+
+        let mut lowerer = Lowerer::new();
+        let (fir_id, fir_package) = if let Some(open) = open {
+            let mut open_package = store
+                .get(open)
+                .expect("open package should be in store")
+                .package
+                .clone();
+            for (id, item) in package.items.iter() {
+                open_package.items.insert(id, item.clone());
+            }
+            (
+                map_hir_package_to_fir(open),
+                lowerer.lower_package(&open_package),
+            )
+        } else {
+            let next = store.iter().map(|(id, _)| usize::from(id) + 1).max();
+            (
+                fir::PackageId::from(next.unwrap_or_default()),
+                lowerer.lower_package(package),
+            )
+        };
+        // The top-level statements are never evaluated.
+        lowerer.take_exec_graph();
+        fir_store.insert(fir_id, fir_package);
+
+        Self {
+            fir_store,
+            fir_id,
+            lowerer,
+        }
+    }
+
+    /// Lowers the items of the package that changed since they were last lowered.
+    fn update(&mut self, package: &Package, changed: &[LocalItemId]) {
+        let changed = Package {
+            items: changed
+                .iter()
+                .filter_map(|&id| Some((id, package.items.get(id)?.clone())))
+                .collect(),
+            stmts: Vec::new(),
+            entry: None,
+        };
+        self.lowerer
+            .lower_and_update_package(self.fir_store.get_mut(self.fir_id), &changed);
+    }
+
+    /// Evaluates a value as the entry expression of the package.
+    fn eval(&mut self, value: &Expr) -> std::result::Result<(Value, Ty, Span), Error> {
+        let entry = Package {
+            items: IndexMap::new(),
+            stmts: Vec::new(),
+            entry: Some(value.clone()),
+        };
+        self.lowerer
+            .lower_and_update_package(self.fir_store.get_mut(self.fir_id), &entry);
+        let exec_graph = self.lowerer.take_exec_graph().into();
+
+        let mut state = State::new(self.fir_id, exec_graph, None).with_limits(Limits {
+            max_steps: Some(STEP_LIMIT),
+            ..Limits::default()
+        });
+        let mut out = std::io::sink();
+        match state.eval(
+            &self.fir_store,
+            &mut Env::default(),
+            &mut SparseSim::new(),
+            &mut GenericReceiver::new(&mut out),
+            &[],
+            StepAction::Continue,
+        ) {
+            Ok(StepResult::Return(result)) => Ok((result, value.ty.clone(), value.span)),
+            Ok(_) => panic!("evaluation should return a value"),
+            Err((error, _)) => {
+                // Errors inside dependencies are reported on the value of the constant.
+                let span = if map_hir_package_to_fir(error.span().package) == self.fir_id {
+                    error.span().span
+                } else {
+                    value.span
+                };
+                Err(Error::EvalFailed(error.to_string(), span))
+            }
+        }
+    }
+}
+
+fn is_const_ty(ty: &Ty) -> bool {
+    match ty {
+        Ty::Prim(prim) => *prim != Prim::Qubit,
+        Ty::Array(item) => is_const_ty(item),
+        Ty::Tuple(items) => items.iter().all(is_const_ty),
+        _ => false,
+    }
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Lit(_) => true,
+        ExprKind::Array(items) | ExprKind::Tuple(items) => items.iter().all(is_literal),
+        ExprKind::Range(start, step, end) => [start, step, end]
+            .into_iter()
+            .all(|e| e.as_deref().is_none_or(is_literal)),
+        ExprKind::String(components) => components
+            .iter()
+            .all(|c| matches!(c, StringComponent::Lit(_))),
+        _ => false,
+    }
+}
+
+fn value_to_expr(value: &Value, ty: &Ty, span: Span, assigner: &mut Assigner) -> Expr {
+    let kind = match (value, ty) {
+        (Value::Array(items), Ty::Array(item_ty)) => ExprKind::Array(
+            items
+                .iter()
+                .map(|item| value_to_expr(item, item_ty, span, assigner))
+                .collect(),
+        ),
+        (Value::Tuple(items), Ty::Tuple(item_tys)) => ExprKind::Tuple(
+            items
+                .iter()
+                .zip(item_tys)
+                .map(|(item, item_ty)| value_to_expr(item, item_ty, span, assigner))
+                .collect(),
+        ),
+        (Value::BigInt(v), _) => ExprKind::Lit(Lit::BigInt(v.clone())),
+        (Value::Bool(v), _) => ExprKind::Lit(Lit::Bool(*v)),
+        (Value::Double(v), _) => ExprKind::Lit(Lit::Double(*v)),
+        (Value::Int(v), _) => ExprKind::Lit(Lit::Int(*v)),
+        (Value::Pauli(p), _) => ExprKind::Lit(Lit::Pauli(match p {
+            fir::Pauli::I => Pauli::I,
+            fir::Pauli::X => Pauli::X,
+            fir::Pauli::Y => Pauli::Y,
+            fir::Pauli::Z => Pauli::Z,
+        })),
+        (Value::Result(val::Result::Val(v)), _) => {
+            ExprKind::Lit(Lit::Result(if *v { Result::One } else { Result::Zero }))
+        }
+        (Value::Range(range), _) => {
+            let int = Ty::Prim(Prim::Int);
+            let mut bound = |v: i64| Box::new(value_to_expr(&Value::Int(v), &int, span, assigner));
+            ExprKind::Range(
+                range.start.map(&mut bound),
+                Some(bound(range.step)),
+                range.end.map(&mut bound),
+            )
+        }
+        (Value::String(v), _) => ExprKind::String(vec![StringComponent::Lit(Rc::clone(v))]),
+        _ => panic!(
+            "constant of type {} should not have value {value}",
+            ty.display()
+        ),
+    };
+
+    Expr {
+        id: assigner.next_node(),
+        span,
+        ty: ty.clone(),
+        kind,
+    }
+}
+
+struct ConstRefs<'a> {
+    package: &'a Package,
+    uses: Uses,
+}
+
+impl<'a> Visitor<'a> for ConstRefs<'a> {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let ExprKind::Var(
+            Res::Item(ItemId {
+                package: None,
+                item,
+            }),
+            _,
+        ) = &expr.kind
+        {
+            match self.package.items.get(*item).map(|i| &i.kind) {
+                Some(ItemKind::Const(..)) => {
+                    self.uses.consts.insert(*item);
+                }
+                Some(ItemKind::Callable(decl)) if self.uses.callables.insert(*item) => {
+                    self.visit_callable_decl(decl);
+                }
+                _ => {}
+            }
+        }
+        visit::walk_expr(self, expr);
+    }
+}
+
+/// Finds the operation calls and qubit allocations in a value, which cannot be evaluated at compile
+/// time. Functions cannot do either, so the callables the value calls are not visited.
+#[derive(Default)]
+struct Purity {
+    errors: Vec<Error>,
+}
+
+impl Visitor<'_> for Purity {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        if let StmtKind::Qubit(..) = &stmt.kind {
+            self.errors.push(Error::QubitAlloc(stmt.span));
+        }
+        visit::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let ExprKind::Call(callee, _) = &expr.kind {
+            if matches!(&callee.ty, Ty::Arrow(arrow) if arrow.kind == CallableKind::Operation) {
+                self.errors.push(Error::OpCall(expr.span));
+            }
+        }
+        visit::walk_expr(self, expr);
+    }
+}
+
+struct Substitute<'a> {
+    store: &'a PackageStore,
+    open: Option<PackageId>,
+    local_items: FxHashSet<LocalItemId>,
+    values: FxHashMap<LocalItemId, Expr>,
+    assigner: &'a mut Assigner,
+    errors: Vec<Error>,
+}
+
+impl<'a> Substitute<'a> {
+    fn new(
+        store: &'a PackageStore,
+        open: Option<PackageId>,
+        package: &Package,
+        assigner: &'a mut Assigner,
+    ) -> Self {
+        Self {
+            store,
+            open,
+            local_items: package.items.iter().map(|(id, _)| id).collect(),
+            values: FxHashMap::default(),
+            assigner,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Whether a non-callable item exists nowhere, which is the case for a constant declared by an
+    /// earlier increment whose evaluation failed.
+    fn is_missing(&self, id: ItemId, ty: &Ty) -> bool {
+        !matches!(ty, Ty::Arrow(_))
+            && id.package.is_none()
+            && !self.local_items.contains(&id.item)
+            && self.open.is_none_or(|open| {
+                self.store
+                    .get(open)
+                    .is_none_or(|unit| unit.package.items.get(id.item).is_none())
+            })
+    }
+
+    fn value(&self, id: ItemId) -> Option<&Expr> {
+        let stored_value =
+            |package| match &self.store.get(package)?.package.items.get(id.item)?.kind {
+                ItemKind::Const(_, value) if is_literal(value) => Some(value),
+                _ => None,
+            };
+
+        match id.package {
+            None => self
+                .values
+                .get(&id.item)
+                .or_else(|| self.open.and_then(stored_value)),
+            Some(package) => stored_value(package),
+        }
+    }
+}
+
+impl MutVisitor for Substitute<'_> {
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        if let ExprKind::Var(Res::Item(id), _) = &expr.kind {
+            if let Some(value) = self.value(*id) {
+                let mut value = value.clone();
+                Refresh {
+                    assigner: self.assigner,
+                    span: expr.span,
+                }
+                .visit_expr(&mut value);
+                *expr = value;
+                return;
+            }
+            if self.is_missing(*id, &expr.ty) {
+                self.errors.push(Error::MissingValue(expr.span));
+            }
+        }
+        mut_visit::walk_expr(self, expr);
+    }
+}
+
+/// Gives a copy of a literal fresh node IDs and the span of the use it replaces.
+struct Refresh<'a> {
+    assigner: &'a mut Assigner,
+    span: Span,
+}
+
+impl MutVisitor for Refresh<'_> {
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        expr.id = self.assigner.next_node();
+        expr.span = self.span;
+        mut_visit::walk_expr(self, expr);
+    }
+}
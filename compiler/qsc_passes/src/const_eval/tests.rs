@@ -0,0 +1,455 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes, clippy::too_many_lines)]
+
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{self, compile, PackageStore, SourceMap};
+
+use crate::{fold_consts, run_core_passes, run_default_passes, PackageType};
+
+fn check(file: &str, expect: &Expect) {
+    let mut core = compile::core();
+    assert!(run_core_passes(&mut core).is_empty());
+    let store = PackageStore::new(core);
+    let sources = SourceMap::new([("test".into(), file.into())], None);
+    let mut unit = compile(
+        &store,
+        &[],
+        sources,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(unit.errors.is_empty(), "{:?}", unit.errors);
+    let mut errors = run_default_passes(
+        store.core(),
+        &mut unit,
+        PackageType::Lib,
+        TargetCapabilityFlags::all(),
+    );
+
+    if errors.is_empty() {
+        errors = fold_consts(&store, None, &mut unit.package, &mut unit.assigner);
+    }
+    if errors.is_empty() {
+        expect.assert_eq(&unit.package.to_string());
+    } else {
+        expect.assert_debug_eq(&errors);
+    }
+}
+
+#[test]
+fn const_uses_are_replaced_with_values() {
+    check(
+        indoc! {"
+            namespace Test {
+                const NumIterations : Int = 2 ^ 10;
+                function Foo() : Int {
+                    NumIterations + 1
+                }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-117] (Public):
+                    Namespace (Ident 13 [10-14] "Test"): Item 1, Item 2
+                Item 1 [21-56] (Public):
+                    Parent: 0
+                    Const (Ident 0 [27-40] "NumIterations"): Expr 14 [49-55] [Type Int]: Lit: Int(1024)
+                Item 2 [61-115] (Public):
+                    Parent: 0
+                    Callable 4 [61-115] (function):
+                        name: Ident 5 [70-73] "Foo"
+                        input: Pat 6 [73-75] [Type Unit]: Unit
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 7 [61-115]: Impl:
+                            Block 8 [82-115] [Type Int]:
+                                Stmt 9 [92-109]: Expr: Expr 10 [92-109] [Type Int]: BinOp (Add):
+                                    Expr 15 [92-105] [Type Int]: Lit: Int(1024)
+                                    Expr 12 [108-109] [Type Int]: Lit: Int(1)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
+#[test]
+fn const_can_use_other_consts_and_functions() {
+    check(
+        indoc! {"
+            namespace Test {
+                const Sizes : Int[] = [Size, Double(Size)];
+                const Size : Int = 3;
+                function Double(x : Int) : Int {
+                    2 * x
+                }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-149] (Public):
+                    Namespace (Ident 18 [10-14] "Test"): Item 1, Item 2, Item 3
+                Item 1 [21-64] (Public):
+                    Parent: 0
+                    Const (Ident 0 [27-32] "Sizes"): Expr 24 [43-63] [Type Int[]]: Array:
+                        Expr 22 [43-63] [Type Int]: Lit: Int(3)
+                        Expr 23 [43-63] [Type Int]: Lit: Int(6)
+                Item 2 [69-90] (Public):
+                    Parent: 0
+                    Const (Ident 6 [75-79] "Size"): Expr 19 [88-89] [Type Int]: Lit: Int(3)
+                Item 3 [95-147] (Public):
+                    Parent: 0
+                    Callable 8 [95-147] (function):
+                        name: Ident 9 [104-110] "Double"
+                        input: Pat 10 [111-118] [Type Int]: Bind: Ident 11 [111-112] "x"
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 12 [95-147]: Impl:
+                            Block 13 [126-147] [Type Int]:
+                                Stmt 14 [136-141]: Expr: Expr 15 [136-141] [Type Int]: BinOp (Mul):
+                                    Expr 16 [136-137] [Type Int]: Lit: Int(2)
+                                    Expr 17 [140-141] [Type Int]: Var: Local 11
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
+#[test]
+fn const_used_as_array_size() {
+    check(
+        indoc! {"
+            namespace Test {
+                const Size : Int = 2;
+                const Zeros : (Bool, Double[]) = (true, [0.0, size = Size]);
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-109] (Public):
+                    Namespace (Ident 8 [10-14] "Test"): Item 1, Item 2
+                Item 1 [21-42] (Public):
+                    Parent: 0
+                    Const (Ident 0 [27-31] "Size"): Expr 9 [40-41] [Type Int]: Lit: Int(2)
+                Item 2 [47-107] (Public):
+                    Parent: 0
+                    Const (Ident 2 [53-58] "Zeros"): Expr 15 [80-106] [Type (Bool, Double[])]: Tuple:
+                        Expr 11 [80-106] [Type Bool]: Lit: Bool(true)
+                        Expr 14 [80-106] [Type Double[]]: Array:
+                            Expr 12 [80-106] [Type Double]: Lit: Double(0)
+                            Expr 13 [80-106] [Type Double]: Lit: Double(0)"#]],
+    );
+}
+
+#[test]
+fn const_div_by_zero_is_error() {
+    check(
+        indoc! {"
+            namespace Test {
+                const Divisor : Int = 0;
+                const Bad : Int = 1 / Divisor;
+                const AlsoBad : Int = Bad + 1;
+            }
+        "},
+        &expect![[r#"
+            [
+                ConstEval(
+                    EvalFailed(
+                        "division by zero",
+                        Span {
+                            lo: 72,
+                            hi: 79,
+                        },
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn const_overflow_is_error() {
+    check(
+        indoc! {"
+            namespace Test {
+                const Big : Int = 2 ^ 64;
+            }
+        "},
+        &expect![[r#"
+            [
+                ConstEval(
+                    EvalFailed(
+                        "integer too large for operation",
+                        Span {
+                            lo: 43,
+                            hi: 45,
+                        },
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn const_cycle_is_error() {
+    check(
+        indoc! {"
+            namespace Test {
+                const A : Int = B + 1;
+                const B : Int = Foo();
+                function Foo() : Int {
+                    A
+                }
+            }
+        "},
+        &expect![[r#"
+            [
+                ConstEval(
+                    Cycle(
+                        "A",
+                        Span {
+                            lo: 27,
+                            hi: 28,
+                        },
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn const_of_callable_type_is_error() {
+    check(
+        indoc! {"
+            namespace Test {
+                const F : Int -> Int = Foo;
+                function Foo(x : Int) : Int {
+                    x
+                }
+            }
+        "},
+        &expect![[r#"
+            [
+                ConstEval(
+                    UnsupportedTy(
+                        "(Int -> Int)",
+                        Span {
+                            lo: 44,
+                            hi: 47,
+                        },
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn const_calling_operation_is_error() {
+    check(
+        indoc! {"
+            namespace Test {
+                const R : Result = Measure();
+                operation Measure() : Result {
+                    body intrinsic;
+                }
+            }
+        "},
+        &expect![[r#"
+            [
+                ConstEval(
+                    OpCall(
+                        Span {
+                            lo: 40,
+                            hi: 49,
+                        },
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn const_allocating_qubits_is_error() {
+    check(
+        indoc! {"
+            namespace Test {
+                const N : Int = {
+                    use q = Qubit();
+                    1
+                };
+            }
+        "},
+        &expect![[r#"
+            [
+                ConstEval(
+                    QubitAlloc(
+                        Span {
+                            lo: 47,
+                            hi: 63,
+                        },
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn const_is_selected_by_config() {
+    check(
+        indoc! {"
+            namespace Test {
+                @Config(Adaptive)
+                const Shots : Int = 10;
+                @Config(Base)
+                const Shots : Int = 1;
+                function Foo() : Int {
+                    Shots
+                }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-160] (Public):
+                    Namespace (Ident 9 [10-14] "Test"): Item 1, Item 2
+                Item 1 [21-66] (Public):
+                    Parent: 0
+                    Const (Ident 0 [49-54] "Shots"): Expr 10 [63-65] [Type Int]: Lit: Int(10)
+                Item 2 [116-158] (Public):
+                    Parent: 0
+                    Callable 2 [116-158] (function):
+                        name: Ident 3 [125-128] "Foo"
+                        input: Pat 4 [128-130] [Type Unit]: Unit
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 5 [116-158]: Impl:
+                            Block 6 [137-158] [Type Int]:
+                                Stmt 7 [147-152]: Expr: Expr 11 [147-152] [Type Int]: Lit: Int(10)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
+#[test]
+fn consts_in_default_values_are_folded() {
+    check(
+        indoc! {"
+            namespace Test {
+                const Step : Int = 5;
+                function Foo(x : Int, y : Int = 2 ^ 3, z : Int = 2 * Step) : Int {
+                    (x + y) * z
+                }
+                function Bar() : Int {
+                    Foo(1, 2)
+                }
+            }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-192] (Public):
+                    Namespace (Ident 80 [10-14] "Test"): Item 1, Item 2, Item 3
+                Item 1 [21-42] (Public):
+                    Parent: 0
+                    Const (Ident 0 [27-31] "Step"): Expr 81 [40-41] [Type Int]: Lit: Int(5)
+                Item 2 [47-139] (Public):
+                    Parent: 0
+                    Callable 2 [47-139] (function):
+                        name: Ident 3 [56-59] "Foo"
+                        input: Pat 4 [59-105] [Type (Int, Int, Int)]: Tuple:
+                            Pat 5 [60-67] [Type Int]: Bind: Ident 6 [60-61] "x"
+                            Pat 7 [69-76] [Type Int]: Bind: Ident 8 [69-70] "y"
+                            Pat 9 [86-93] [Type Int]: Bind: Ident 10 [86-87] "z"
+                        defaults:
+                            Item 4: Expr 11 [79-84] [Type Int]: BinOp (Exp):
+                                Expr 12 [79-80] [Type Int]: Lit: Int(2)
+                                Expr 13 [83-84] [Type Int]: Lit: Int(3)
+                            Item 5: Expr 14 [96-104] [Type Int]: BinOp (Mul):
+                                Expr 15 [96-97] [Type Int]: Lit: Int(2)
+                                Expr 82 [100-104] [Type Int]: Lit: Int(5)
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 41 [47-139]: Impl:
+                            Block 42 [112-139] [Type Int]:
+                                Stmt 43 [122-133]: Expr: Expr 44 [122-133] [Type Int]: BinOp (Mul):
+                                    Expr 45 [123-128] [Type Int]: BinOp (Add):
+                                        Expr 46 [123-124] [Type Int]: Var: Local 6
+                                        Expr 47 [127-128] [Type Int]: Var: Local 8
+                                    Expr 48 [132-133] [Type Int]: Var: Local 10
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 3 [144-190] (Public):
+                    Parent: 0
+                    Callable 49 [144-190] (function):
+                        name: Ident 50 [153-156] "Bar"
+                        input: Pat 51 [156-158] [Type Unit]: Unit
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 52 [144-190]: Impl:
+                            Block 53 [165-190] [Type Int]:
+                                Stmt 54 [175-184]: Expr: Expr 55 [175-184] [Type Int]: Call:
+                                    Expr 56 [175-178] [Type ((Int, Int, Int) -> Int)]: Var: Item 2
+                                    Expr 78 [178-184] [Type (Int, Int, Int)]: Expr Block: Block 79 [178-184] [Type (Int, Int, Int)]:
+                                        Stmt 60 [179-180]: Local (Immutable):
+                                            Pat 61 [179-180] [Type Int]: Bind: Ident 59 [179-180] "arg"
+                                            Expr 57 [179-180] [Type Int]: Lit: Int(1)
+                                        Stmt 63 [182-183]: Local (Immutable):
+                                            Pat 64 [182-183] [Type Int]: Bind: Ident 62 [182-183] "arg"
+                                            Expr 58 [182-183] [Type Int]: Lit: Int(2)
+                                        Stmt 71 [178-184]: Local (Immutable):
+                                            Pat 72 [178-184] [Type Int]: Bind: Ident 70 [178-184] "arg"
+                                            Expr 69 [178-184] [Type Int]: Call:
+                                                Expr 68 [178-184] [Type ((Int, Int) -> Int)]: Var: Item 5
+                                                Expr 67 [178-184] [Type (Int, Int)]: Tuple:
+                                                    Expr 65 [178-184] [Type Int]: Var: Local 59
+                                                    Expr 66 [178-184] [Type Int]: Var: Local 62
+                                        Stmt 77 [178-184]: Expr: Expr 76 [178-184] [Type (Int, Int, Int)]: Tuple:
+                                            Expr 73 [178-184] [Type Int]: Var: Local 59
+                                            Expr 74 [178-184] [Type Int]: Var: Local 62
+                                            Expr 75 [178-184] [Type Int]: Var: Local 70
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 4 [79-84] (Internal):
+                    Parent: 2
+                    Callable 23 [79-84] (function):
+                        name: Ident 24 [79-84] "default"
+                        input: Pat 17 [79-84] [Type (Int,)]: Tuple:
+                            Pat 18 [60-67] [Type Int]: Bind: Ident 19 [60-61] "x"
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 25 [79-84]: Impl:
+                            Block 26 [79-84] [Type Int]:
+                                Stmt 27 [79-84]: Expr: Expr 20 [79-84] [Type Int]: BinOp (Exp):
+                                    Expr 21 [79-80] [Type Int]: Lit: Int(2)
+                                    Expr 22 [83-84] [Type Int]: Lit: Int(3)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>
+                Item 5 [96-104] (Internal):
+                    Parent: 2
+                    Callable 36 [96-104] (function):
+                        name: Ident 37 [96-104] "default"
+                        input: Pat 28 [96-104] [Type (Int, Int)]: Tuple:
+                            Pat 29 [60-67] [Type Int]: Bind: Ident 30 [60-61] "x"
+                            Pat 31 [69-76] [Type Int]: Bind: Ident 32 [69-70] "y"
+                        output: Int
+                        functors: empty set
+                        body: SpecDecl 38 [96-104]: Impl:
+                            Block 39 [96-104] [Type Int]:
+                                Stmt 40 [96-104]: Expr: Expr 33 [96-104] [Type Int]: BinOp (Mul):
+                                    Expr 34 [96-97] [Type Int]: Lit: Int(2)
+                                    Expr 83 [100-104] [Type Int]: Lit: Int(5)
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
@@ -7,6 +7,7 @@ mod callable_limits;
 mod capabilitiesck;
 mod common;
 mod conjugate_invert;
+mod const_eval;
 mod entry_point;
 mod id_update;
 mod invert_block;
@@ -22,11 +23,11 @@ use loop_unification::LoopUni;
 use miette::Diagnostic;
 use qsc_data_structures::target::TargetCapabilityFlags;
 use qsc_fir::fir;
-use qsc_frontend::compile::CompileUnit;
+use qsc_frontend::compile::{CompileUnit, PackageStore};
 use qsc_hir::{
     assigner::Assigner,
     global::{self, Table},
-    hir::Package,
+    hir::{Package, PackageId},
     mut_visit::MutVisitor,
     validate::Validator,
     visit::Visitor,
@@ -45,6 +46,7 @@ pub enum Error {
     CallableLimits(callable_limits::Error),
     CapabilitiesCk(capabilitiesck::Error),
     ConjInvert(conjugate_invert::Error),
+    ConstEval(const_eval::Error),
     EntryPoint(entry_point::Error),
     SpecGen(spec_gen::Error),
 }
@@ -90,6 +92,7 @@ impl PassContext {
         let mut call_limits = CallableLimits::default();
         call_limits.visit_package(package);
         let callable_errors = call_limits.errors;
+        let purity_errors = const_eval::check_purity(package);
 
         self.borrow_check.visit_package(package);
         let borrow_errors = &mut self.borrow_check.errors;
@@ -123,6 +126,7 @@ impl PassContext {
         callable_errors
             .into_iter()
             .map(Error::CallableLimits)
+            .chain(purity_errors.into_iter().map(Error::ConstEval))
             .chain(borrow_errors.drain(..).map(Error::BorrowCk))
             .chain(spec_errors.into_iter().map(Error::SpecGen))
             .chain(conjugate_errors.into_iter().map(Error::ConjInvert))
@@ -155,6 +159,20 @@ pub fn run_default_passes(
     )
 }
 
+/// Evaluates the `const` items of the package and replaces every use of a constant with its value.
+/// This runs after the default passes, as the constants are evaluated with the lowered package. If
+/// the package is an increment of a package in the store, `open` is the ID of that package.
+pub fn fold_consts(
+    store: &PackageStore,
+    open: Option<PackageId>,
+    package: &mut Package,
+    assigner: &mut Assigner,
+) -> Vec<Error> {
+    let errors = const_eval::fold_consts(store, open, package, assigner);
+    Validator::default().visit_package(package);
+    errors.into_iter().map(Error::ConstEval).collect()
+}
+
 pub fn run_core_passes(core: &mut CompileUnit) -> Vec<Error> {
     let mut borrow_check = borrowck::Checker::default();
     borrow_check.visit_package(&core.package);
@@ -202,6 +202,8 @@ impl SepCheck {
             | ExprKind::AssignOp(..)
             | ExprKind::AssignField(..)
             | ExprKind::AssignIndex(..)
+            | ExprKind::Break
+            | ExprKind::Continue
             | ExprKind::Repeat(..)
             | ExprKind::Return(..)
             | ExprKind::While(..) => {
@@ -10,6 +10,7 @@ use qsc_hir::{
     hir::{BinOp, Block, Expr, ExprKind, Lit, Mutability, Pat, PrimField, Stmt, StmtKind, UnOp},
     mut_visit::{walk_expr, MutVisitor},
     ty::{GenericArg, Prim, Ty},
+    visit::{self, Visitor},
 };
 
 use crate::common::{create_gen_core_ref, generated_name, IdentTemplate};
@@ -194,8 +195,7 @@ impl LoopUni<'_> {
         };
         let update_index = gen_id_add_update(self.assigner, &index_id, update_expr);
 
-        block.stmts.insert(0, pat_init);
-        block.stmts.push(update_index);
+        insert_loop_stmts(&mut block, pat_init, update_index);
 
         let cond = Expr {
             id: self.assigner.next_node(),
@@ -278,8 +278,7 @@ impl LoopUni<'_> {
         let update_expr = step_id.gen_local_ref(self.assigner);
         let update_index = gen_id_add_update(self.assigner, &index_id, update_expr);
 
-        block.stmts.insert(0, pat_init);
-        block.stmts.push(update_index);
+        insert_loop_stmts(&mut block, pat_init, update_index);
 
         let cond = gen_range_cond(self.assigner, &index_id, &step_id, &end_id, iterable_span);
 
@@ -342,6 +341,36 @@ impl MutVisitor for LoopUni<'_> {
     }
 }
 
+/// Inserts the binding of the loop variable at the start of the loop body and the update of the
+/// loop index at its end. If the body can skip to the next iteration with `continue`, the index
+/// is instead updated right after the loop variable is bound so that the update is not skipped.
+fn insert_loop_stmts(block: &mut Block, pat_init: Stmt, update_index: Stmt) {
+    let mut finder = ContinueFinder { found: false };
+    finder.visit_block(block);
+    if finder.found {
+        block.stmts.splice(0..0, [pat_init, update_index]);
+    } else {
+        block.stmts.insert(0, pat_init);
+        block.stmts.push(update_index);
+    }
+}
+
+/// Finds a `continue` that targets the loop whose body is visited.
+struct ContinueFinder {
+    found: bool,
+}
+
+impl<'a> Visitor<'a> for ContinueFinder {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match &expr.kind {
+            ExprKind::Continue => self.found = true,
+            // A `continue` in the body of a nested loop targets that loop instead.
+            ExprKind::While(cond, _) => self.visit_expr(cond),
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+}
+
 fn gen_range_cond(
     assigner: &mut Assigner,
     index: &IdentTemplate,
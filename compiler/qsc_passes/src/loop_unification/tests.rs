@@ -594,3 +594,91 @@ fn convert_repeat_nested() {
                         ctl-adj: <none>"#]],
     );
 }
+
+#[test]
+fn convert_for_range_with_continue() {
+    check(
+        indoc! {"
+        namespace test {
+            operation Main() : Unit {
+                for i in 0..4 {
+                    if i == 2 {
+                        continue;
+                    }
+                    let x = i;
+                }
+            }
+        }
+        "},
+        &expect![[r#"
+            Package:
+                Item 0 [0-175] (Public):
+                    Namespace (Ident 26 [10-14] "test"): Item 1
+                Item 1 [21-173] (Public):
+                    Parent: 0
+                    Callable 0 [21-173] (operation):
+                        name: Ident 1 [31-35] "Main"
+                        input: Pat 2 [35-37] [Type Unit]: Unit
+                        output: Unit
+                        functors: empty set
+                        body: SpecDecl 3 [21-173]: Impl:
+                            Block 4 [45-173] [Type Unit]:
+                                Stmt 5 [55-167]: Expr: Expr 68 [55-167] [Type Unit]: Expr Block: Block 69 [55-167] [Type Unit]:
+                                    Stmt 28 [0-0]: Local (Immutable):
+                                        Pat 29 [64-68] [Type Range]: Bind: Ident 27 [64-68] "@range_id_27"
+                                        Expr 9 [64-68] [Type Range]: Range:
+                                            Expr 10 [64-65] [Type Int]: Lit: Int(0)
+                                            <no step>
+                                            Expr 11 [67-68] [Type Int]: Lit: Int(4)
+                                    Stmt 33 [64-68]: Local (Mutable):
+                                        Pat 34 [64-68] [Type Int]: Bind: Ident 30 [64-68] "@index_id_30"
+                                        Expr 31 [64-68] [Type Int]: Field:
+                                            Expr 32 [64-68] [Type Range]: Var: Local 27
+                                            Prim(Start)
+                                    Stmt 38 [0-0]: Local (Immutable):
+                                        Pat 39 [64-68] [Type Int]: Bind: Ident 35 [64-68] "@step_id_35"
+                                        Expr 36 [64-68] [Type Int]: Field:
+                                            Expr 37 [64-68] [Type Range]: Var: Local 27
+                                            Prim(Step)
+                                    Stmt 43 [0-0]: Local (Immutable):
+                                        Pat 44 [64-68] [Type Int]: Bind: Ident 40 [64-68] "@end_id_40"
+                                        Expr 41 [64-68] [Type Int]: Field:
+                                            Expr 42 [64-68] [Type Range]: Var: Local 27
+                                            Prim(End)
+                                    Stmt 66 [0-0]: Expr: Expr 67 [55-167] [Type Unit]: While:
+                                        Expr 51 [64-68] [Type Bool]: BinOp (OrL):
+                                            Expr 52 [64-68] [Type Bool]: BinOp (AndL):
+                                                Expr 53 [64-68] [Type Bool]: BinOp (Gt):
+                                                    Expr 54 [64-68] [Type Int]: Var: Local 35
+                                                    Expr 55 [64-68] [Type Int]: Lit: Int(0)
+                                                Expr 56 [64-68] [Type Bool]: BinOp (Lte):
+                                                    Expr 57 [64-68] [Type Int]: Var: Local 30
+                                                    Expr 58 [64-68] [Type Int]: Var: Local 40
+                                            Expr 59 [64-68] [Type Bool]: BinOp (AndL):
+                                                Expr 60 [64-68] [Type Bool]: BinOp (Lt):
+                                                    Expr 61 [64-68] [Type Int]: Var: Local 35
+                                                    Expr 62 [64-68] [Type Int]: Lit: Int(0)
+                                                Expr 63 [64-68] [Type Bool]: BinOp (Gte):
+                                                    Expr 64 [64-68] [Type Int]: Var: Local 30
+                                                    Expr 65 [64-68] [Type Int]: Var: Local 40
+                                        Block 12 [69-167] [Type Unit]:
+                                            Stmt 45 [59-60]: Local (Immutable):
+                                                Pat 7 [59-60] [Type Int]: Bind: Ident 8 [59-60] "i"
+                                                Expr 46 [64-68] [Type Int]: Var: Local 30
+                                            Stmt 48 [64-68]: Semi: Expr 49 [64-68] [Type Unit]: AssignOp (Add):
+                                                Expr 50 [64-68] [Type Int]: Var: Local 30
+                                                Expr 47 [64-68] [Type Int]: Var: Local 35
+                                            Stmt 13 [83-134]: Expr: Expr 14 [83-134] [Type Unit]: If:
+                                                Expr 15 [86-92] [Type Bool]: BinOp (Eq):
+                                                    Expr 16 [86-87] [Type Int]: Var: Local 8
+                                                    Expr 17 [91-92] [Type Int]: Lit: Int(2)
+                                                Expr 18 [93-134] [Type Unit]: Expr Block: Block 19 [93-134] [Type Unit]:
+                                                    Stmt 20 [111-120]: Semi: Expr 21 [111-119] [Type Unit]: Continue
+                                            Stmt 22 [147-157]: Local (Immutable):
+                                                Pat 23 [151-152] [Type Int]: Bind: Ident 24 [151-152] "x"
+                                                Expr 25 [155-156] [Type Int]: Var: Local 8
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
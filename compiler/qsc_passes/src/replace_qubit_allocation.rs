@@ -30,6 +30,8 @@ pub(crate) struct ReplaceQubitAllocation<'a> {
     qubits_curr_callable: Vec<Vec<QubitIdent>>,
     qubits_curr_block: Vec<QubitIdent>,
     prefix_qubits: Vec<QubitIdent>,
+    /// The length of `qubits_curr_callable` outside of the body of each enclosing loop.
+    loops: Vec<usize>,
 }
 
 impl<'a> ReplaceQubitAllocation<'a> {
@@ -40,6 +42,7 @@ impl<'a> ReplaceQubitAllocation<'a> {
             qubits_curr_callable: Vec::new(),
             qubits_curr_block: Vec::new(),
             prefix_qubits: Vec::new(),
+            loops: Vec::new(),
         }
     }
 
@@ -237,6 +240,22 @@ impl<'a> ReplaceQubitAllocation<'a> {
         stmts
     }
 
+    fn get_dealloc_stmts_for_loop(&mut self) -> Vec<Stmt> {
+        let loop_depth = *self
+            .loops
+            .last()
+            .expect("loop jump should be inside a loop");
+        let mut stmts = self.get_dealloc_stmts(&self.qubits_curr_block.clone());
+        stmts.extend(
+            self.qubits_curr_callable[loop_depth + 1..]
+                .to_vec()
+                .iter()
+                .rev()
+                .flat_map(|q| self.get_dealloc_stmts(q)),
+        );
+        stmts
+    }
+
     fn create_alloc_stmt(&mut self, ident: &IdentTemplate) -> Stmt {
         let mut call_expr = create_gen_core_ref(
             self.core,
@@ -380,6 +399,39 @@ impl MutVisitor for ReplaceQubitAllocation<'_> {
                     *expr = new_expr;
                 }
             }
+            ExprKind::While(cond, body) => {
+                self.visit_expr(cond);
+                self.loops.push(self.qubits_curr_callable.len());
+                self.visit_block(body);
+                self.loops.pop();
+            }
+            ExprKind::Break | ExprKind::Continue => {
+                // Release the qubits allocated inside the loop body before jumping out of it.
+                let mut stmts = self.get_dealloc_stmts_for_loop();
+                if !stmts.is_empty() {
+                    stmts.push(Stmt {
+                        id: self.assigner.next_node(),
+                        span: expr.span,
+                        kind: StmtKind::Semi(Expr {
+                            id: self.assigner.next_node(),
+                            span: expr.span,
+                            ty: expr.ty.clone(),
+                            kind: take(&mut expr.kind),
+                        }),
+                    });
+                    *expr = Expr {
+                        id: self.assigner.next_node(),
+                        span: expr.span,
+                        ty: expr.ty.clone(),
+                        kind: ExprKind::Block(Block {
+                            id: self.assigner.next_node(),
+                            span: expr.span,
+                            ty: expr.ty.clone(),
+                            stmts,
+                        }),
+                    };
+                }
+            }
             _ => walk_expr(self, expr),
         }
     }
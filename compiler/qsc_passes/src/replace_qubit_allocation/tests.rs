@@ -786,6 +786,87 @@ fn test_rtrn_expr() {
     );
 }
 
+#[test]
+fn test_loop_jump_releases_loop_qubits() {
+    check(
+        indoc! { "namespace input {
+            operation Foo() : Unit {
+                use a = Qubit();
+                while true {
+                    use b = Qubit();
+                    if true {
+                        use c = Qubit();
+                        break;
+                    }
+                    continue;
+                }
+            }
+        }" },
+        &expect![[r#"
+            Package:
+                Item 0 [0-253] (Public):
+                    Namespace (Ident 30 [10-15] "input"): Item 1
+                Item 1 [22-251] (Public):
+                    Parent: 0
+                    Callable 0 [22-251] (operation):
+                        name: Ident 1 [32-35] "Foo"
+                        input: Pat 2 [35-37] [Type Unit]: Unit
+                        output: Unit
+                        functors: empty set
+                        body: SpecDecl 3 [22-251]: Impl:
+                            Block 4 [45-251] [Type Unit]:
+                                Stmt 34 [55-71]: Local (Immutable):
+                                    Pat 35 [55-71] [Type Qubit]: Bind: Ident 7 [55-71] "a"
+                                    Expr 32 [55-71] [Type Qubit]: Call:
+                                        Expr 31 [55-71] [Type (Unit => Qubit)]: Var: Item 4 (Package 0)
+                                        Expr 33 [55-71] [Type Unit]: Unit
+                                Stmt 75 [80-245]: Local (Immutable):
+                                    Pat 76 [80-245] [Type Unit]: Bind: Ident 74 [80-245] "@generated_ident_74"
+                                    Expr 10 [80-245] [Type Unit]: While:
+                                        Expr 11 [86-90] [Type Bool]: Lit: Bool(true)
+                                        Block 12 [91-245] [Type Unit]:
+                                            Stmt 39 [105-121]: Local (Immutable):
+                                                Pat 40 [105-121] [Type Qubit]: Bind: Ident 15 [105-121] "b"
+                                                Expr 37 [105-121] [Type Qubit]: Call:
+                                                    Expr 36 [105-121] [Type (Unit => Qubit)]: Var: Item 4 (Package 0)
+                                                    Expr 38 [105-121] [Type Unit]: Unit
+                                            Stmt 17 [134-213]: Expr: Expr 18 [134-213] [Type Unit]: If:
+                                                Expr 19 [137-141] [Type Bool]: Lit: Bool(true)
+                                                Expr 20 [142-213] [Type Unit]: Expr Block: Block 21 [142-213] [Type Unit]:
+                                                    Stmt 44 [160-176]: Local (Immutable):
+                                                        Pat 45 [160-176] [Type Qubit]: Bind: Ident 24 [160-176] "c"
+                                                        Expr 42 [160-176] [Type Qubit]: Call:
+                                                            Expr 41 [160-176] [Type (Unit => Qubit)]: Var: Item 4 (Package 0)
+                                                            Expr 43 [160-176] [Type Unit]: Unit
+                                                    Stmt 26 [193-199]: Semi: Expr 56 [193-198] [Type Unit]: Expr Block: Block 57 [193-198] [Type Unit]:
+                                                        Stmt 47 [0-0]: Semi: Expr 48 [0-0] [Type Unit]: Call:
+                                                            Expr 46 [160-176] [Type (Qubit => Unit)]: Var: Item 5 (Package 0)
+                                                            Expr 49 [160-176] [Type Qubit]: Var: Local 24
+                                                        Stmt 51 [0-0]: Semi: Expr 52 [0-0] [Type Unit]: Call:
+                                                            Expr 50 [105-121] [Type (Qubit => Unit)]: Var: Item 5 (Package 0)
+                                                            Expr 53 [105-121] [Type Qubit]: Var: Local 15
+                                                        Stmt 54 [193-198]: Semi: Expr 55 [193-198] [Type Unit]: Break
+                                                    Stmt 59 [0-0]: Semi: Expr 60 [0-0] [Type Unit]: Call:
+                                                        Expr 58 [160-176] [Type (Qubit => Unit)]: Var: Item 5 (Package 0)
+                                                        Expr 61 [160-176] [Type Qubit]: Var: Local 24
+                                            Stmt 28 [226-235]: Semi: Expr 68 [226-234] [Type Unit]: Expr Block: Block 69 [226-234] [Type Unit]:
+                                                Stmt 63 [0-0]: Semi: Expr 64 [0-0] [Type Unit]: Call:
+                                                    Expr 62 [105-121] [Type (Qubit => Unit)]: Var: Item 5 (Package 0)
+                                                    Expr 65 [105-121] [Type Qubit]: Var: Local 15
+                                                Stmt 66 [226-234]: Semi: Expr 67 [226-234] [Type Unit]: Continue
+                                            Stmt 71 [0-0]: Semi: Expr 72 [0-0] [Type Unit]: Call:
+                                                Expr 70 [105-121] [Type (Qubit => Unit)]: Var: Item 5 (Package 0)
+                                                Expr 73 [105-121] [Type Qubit]: Var: Local 15
+                                Stmt 80 [0-0]: Semi: Expr 81 [0-0] [Type Unit]: Call:
+                                    Expr 79 [55-71] [Type (Qubit => Unit)]: Var: Item 5 (Package 0)
+                                    Expr 82 [55-71] [Type Qubit]: Var: Local 7
+                                Stmt 77 [0-0]: Expr: Expr 78 [80-245] [Type Unit]: Var: Local 74
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
 #[test]
 fn test_unrelated_unaffected() {
     check(
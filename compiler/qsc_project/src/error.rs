@@ -12,4 +12,6 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("failed to construct regular expression from excluded file item: {0}")]
     RegexError(#[from] regex_lite::Error),
+    #[error("circular dependency on package `{0}`")]
+    CircularDependency(String),
 }
@@ -16,7 +16,7 @@ pub use error::Error;
 #[cfg(feature = "fs")]
 pub use fs::StdFs;
 pub use js::{JSFileEntry, ProjectSystemCallbacks};
-pub use manifest::{Manifest, ManifestDescriptor, MANIFEST_FILE_NAME};
+pub use manifest::{Dependency, Manifest, ManifestDescriptor, MANIFEST_FILE_NAME};
 #[cfg(feature = "async")]
 pub use project::FileSystemAsync;
-pub use project::{DirEntry, EntryType, FileSystem, Project};
+pub use project::{DependencyPackage, DirEntry, EntryType, FileSystem, Project};
@@ -11,7 +11,7 @@ use std::{
 
 pub use qsc_linter::LintConfig;
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 
 pub const MANIFEST_FILE_NAME: &str = "qsharp.json";
 
@@ -25,6 +25,16 @@ pub struct Manifest {
     pub language_features: Vec<String>,
     #[serde(default)]
     pub lints: Vec<LintConfig>,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, Dependency>,
+}
+
+/// A package that a Q# project depends on, as listed in its manifest.
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct Dependency {
+    /// The directory containing the package's manifest, relative to the directory of the
+    /// manifest that lists it.
+    pub path: String,
 }
 
 /// Describes the contents and location of a Q# manifest file.
@@ -1,7 +1,14 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::manifest::ManifestDescriptor;
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    manifest::{Manifest, ManifestDescriptor},
+    Error, MANIFEST_FILE_NAME,
+};
+use miette::Context;
 use std::{
     path::{Path, PathBuf},
     sync::Arc,
@@ -12,6 +19,22 @@ use std::{
 pub struct Project {
     pub sources: Vec<(Arc<str>, Arc<str>)>,
     pub manifest: crate::Manifest,
+    /// The packages this project depends on, directly or through other packages,
+    /// ordered so that every package comes after the packages it depends on.
+    pub dependencies: Vec<DependencyPackage>,
+}
+
+/// A package that a Q# project depends on, loaded from the directory named in a manifest.
+#[derive(Default, Debug, Clone)]
+pub struct DependencyPackage {
+    /// The name the package was first listed under.
+    pub name: Arc<str>,
+    pub sources: Vec<(Arc<str>, Arc<str>)>,
+    pub manifest: crate::Manifest,
+    /// Indices into [`Project::dependencies`] of the packages this package depends on directly.
+    pub dependencies: Vec<usize>,
+    /// Whether the project itself lists this package in its manifest.
+    pub direct: bool,
 }
 
 /// This enum represents a filesystem object type. It is analogous to [`std::fs::FileType`].
@@ -97,10 +120,34 @@ pub trait FileSystemAsync {
         }
         Ok(files)
     }
-    /// Given a [ManifestDescriptor], load project sources.
+    /// Given a [ManifestDescriptor], load project sources and the packages it depends on.
     async fn load_project(&self, manifest: &ManifestDescriptor) -> miette::Result<Project> {
-        let project_path = manifest.manifest_dir.clone();
-        let qs_files = self.collect_project_sources(&project_path).await?;
+        let sources = self.load_sources(&manifest.manifest_dir).await?;
+
+        let mut dependencies = Vec::new();
+        let direct = self
+            .load_dependencies(
+                &manifest.manifest_dir,
+                &manifest.manifest,
+                &mut dependencies,
+                &mut Vec::new(),
+                &mut vec![resolve_dependency_dir(&manifest.manifest_dir, ".")],
+            )
+            .await?;
+        for index in direct {
+            dependencies[index].direct = true;
+        }
+
+        Ok(Project {
+            manifest: manifest.manifest.clone(),
+            sources,
+            dependencies,
+        })
+    }
+
+    /// Given the directory of a manifest, load the sources of its project.
+    async fn load_sources(&self, project_path: &Path) -> miette::Result<Vec<(Arc<str>, Arc<str>)>> {
+        let qs_files = self.collect_project_sources(project_path).await?;
 
         let qs_files = qs_files.into_iter().map(|file| file.path());
 
@@ -108,11 +155,62 @@ pub trait FileSystemAsync {
         for path in qs_files {
             sources.push(self.read_file(&path).await?);
         }
+        Ok(sources)
+    }
 
-        Ok(Project {
-            manifest: manifest.manifest.clone(),
-            sources,
-        })
+    /// Read and parse the manifest in the given directory.
+    async fn read_manifest(&self, manifest_dir: &Path) -> miette::Result<Manifest> {
+        let (_, contents) = self
+            .read_file(&manifest_dir.join(MANIFEST_FILE_NAME))
+            .await?;
+        Ok(serde_json::from_str(&contents).map_err(Error::from)?)
+    }
+
+    /// Load the dependencies listed in `manifest`, appending each package to `packages` after
+    /// the packages it depends on. `loaded` holds the directory of each package in `packages`,
+    /// and `stack` the directories of the packages whose dependencies are being loaded.
+    /// Returns the indices into `packages` of the dependencies listed in `manifest`.
+    async fn load_dependencies(
+        &self,
+        manifest_dir: &Path,
+        manifest: &Manifest,
+        packages: &mut Vec<DependencyPackage>,
+        loaded: &mut Vec<PathBuf>,
+        stack: &mut Vec<PathBuf>,
+    ) -> miette::Result<Vec<usize>> {
+        let mut indices = Vec::with_capacity(manifest.dependencies.len());
+        for (name, dependency) in &manifest.dependencies {
+            let dir = resolve_dependency_dir(manifest_dir, &dependency.path);
+            if stack.contains(&dir) {
+                return Err(Error::CircularDependency(name.clone()).into());
+            }
+
+            let index = if let Some(index) = loaded.iter().position(|loaded| *loaded == dir) {
+                index
+            } else {
+                let dependency_manifest = self
+                    .read_manifest(&dir)
+                    .await
+                    .with_context(|| format!("could not load dependency `{name}`"))?;
+                stack.push(dir.clone());
+                let dependencies = self
+                    .load_dependencies(&dir, &dependency_manifest, packages, loaded, stack)
+                    .await?;
+                stack.pop();
+                let sources = self.load_sources(&dir).await?;
+                packages.push(DependencyPackage {
+                    name: name.as_str().into(),
+                    sources,
+                    manifest: dependency_manifest,
+                    dependencies,
+                    direct: false,
+                });
+                loaded.push(dir);
+                packages.len() - 1
+            };
+            indices.push(index);
+        }
+        Ok(indices)
     }
 }
 
@@ -170,19 +268,126 @@ pub trait FileSystem {
         Ok(files)
     }
 
-    /// Given a [`ManifestDescriptor`], load project sources.
+    /// Given a [`ManifestDescriptor`], load project sources and the packages it depends on.
     fn load_project(&self, manifest: &ManifestDescriptor) -> miette::Result<Project> {
-        let project_path = manifest.manifest_dir.clone();
-        let qs_files = self.collect_project_sources(&project_path)?;
+        let sources = self.load_sources(&manifest.manifest_dir)?;
 
-        let qs_files = qs_files.into_iter().map(|file| file.path());
-
-        let qs_sources = qs_files.map(|path| self.read_file(&path));
+        let mut dependencies = Vec::new();
+        let direct = self.load_dependencies(
+            &manifest.manifest_dir,
+            &manifest.manifest,
+            &mut dependencies,
+            &mut Vec::new(),
+            &mut vec![resolve_dependency_dir(&manifest.manifest_dir, ".")],
+        )?;
+        for index in direct {
+            dependencies[index].direct = true;
+        }
 
-        let sources = qs_sources.collect::<miette::Result<_>>()?;
         Ok(Project {
             manifest: manifest.manifest.clone(),
             sources,
+            dependencies,
         })
     }
+
+    /// Given the directory of a manifest, load the sources of its project.
+    fn load_sources(&self, project_path: &Path) -> miette::Result<Vec<(Arc<str>, Arc<str>)>> {
+        let qs_files = self.collect_project_sources(project_path)?;
+
+        let qs_files = qs_files.into_iter().map(|file| file.path());
+
+        let qs_sources = qs_files.map(|path| self.read_file(&path));
+
+        qs_sources.collect()
+    }
+
+    /// Read and parse the manifest in the given directory.
+    fn read_manifest(&self, manifest_dir: &Path) -> miette::Result<Manifest> {
+        let (_, contents) = self.read_file(&manifest_dir.join(MANIFEST_FILE_NAME))?;
+        Ok(serde_json::from_str(&contents).map_err(Error::from)?)
+    }
+
+    /// Load the dependencies listed in `manifest`, appending each package to `packages` after
+    /// the packages it depends on. `loaded` holds the directory of each package in `packages`,
+    /// and `stack` the directories of the packages whose dependencies are being loaded.
+    /// Returns the indices into `packages` of the dependencies listed in `manifest`.
+    fn load_dependencies(
+        &self,
+        manifest_dir: &Path,
+        manifest: &Manifest,
+        packages: &mut Vec<DependencyPackage>,
+        loaded: &mut Vec<PathBuf>,
+        stack: &mut Vec<PathBuf>,
+    ) -> miette::Result<Vec<usize>> {
+        let mut indices = Vec::with_capacity(manifest.dependencies.len());
+        for (name, dependency) in &manifest.dependencies {
+            let dir = resolve_dependency_dir(manifest_dir, &dependency.path);
+            if stack.contains(&dir) {
+                return Err(Error::CircularDependency(name.clone()).into());
+            }
+
+            let index = if let Some(index) = loaded.iter().position(|loaded| *loaded == dir) {
+                index
+            } else {
+                let dependency_manifest = self
+                    .read_manifest(&dir)
+                    .with_context(|| format!("could not load dependency `{name}`"))?;
+                stack.push(dir.clone());
+                let dependencies =
+                    self.load_dependencies(&dir, &dependency_manifest, packages, loaded, stack)?;
+                stack.pop();
+                let sources = self.load_sources(&dir)?;
+                packages.push(DependencyPackage {
+                    name: name.as_str().into(),
+                    sources,
+                    manifest: dependency_manifest,
+                    dependencies,
+                    direct: false,
+                });
+                loaded.push(dir);
+                packages.len() - 1
+            };
+            indices.push(index);
+        }
+        Ok(indices)
+    }
+}
+
+/// Resolves the path of a dependency against the directory of the manifest that lists it.
+/// Paths are treated as `/`-separated strings rather than OS paths, since the filesystem
+/// may be a virtual one whose paths are URIs. An absolute path replaces the manifest directory,
+/// and resolving `.` normalizes the manifest directory itself.
+fn resolve_dependency_dir(manifest_dir: &Path, path: &str) -> PathBuf {
+    let manifest_dir = manifest_dir.to_string_lossy();
+    let (base, path) = if is_absolute_path(path) {
+        (path, "")
+    } else {
+        (manifest_dir.as_ref(), path)
+    };
+    let trimmed = base.trim_end_matches(['/', '\\']);
+    let base = if trimmed.is_empty() { base } else { trimmed };
+
+    // Empty segments of the base are kept, since they are part of its root, as in `/` or `file:///`.
+    let mut segments = Vec::new();
+    let base = base.split(['/', '\\']).map(|segment| (segment, true));
+    let path = path.split(['/', '\\']).map(|segment| (segment, false));
+    for (segment, in_base) in base.chain(path) {
+        match segment {
+            "" if in_base => segments.push(segment),
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    PathBuf::from(segments.join("/"))
+}
+
+/// Whether a dependency path is absolute: rooted, a Windows drive path or a URI.
+fn is_absolute_path(path: &str) -> bool {
+    path.starts_with(['/', '\\'])
+        || path.contains("://")
+        || matches!(path.as_bytes(), [drive, b':', ..] if drive.is_ascii_alphabetic())
 }
@@ -0,0 +1,61 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::resolve_dependency_dir;
+use std::path::{Path, PathBuf};
+
+fn resolve(manifest_dir: &str, path: &str) -> PathBuf {
+    resolve_dependency_dir(Path::new(manifest_dir), path)
+}
+
+#[test]
+fn relative_path_is_resolved_against_manifest_dir() {
+    assert_eq!(
+        resolve("/home/app", "../libs/./oracles"),
+        PathBuf::from("/home/libs/oracles")
+    );
+}
+
+#[test]
+fn relative_path_is_resolved_against_uri() {
+    assert_eq!(
+        resolve("file:///home/app/", "../libs"),
+        PathBuf::from("file:///home/libs")
+    );
+}
+
+#[test]
+fn absolute_path_replaces_manifest_dir() {
+    assert_eq!(
+        resolve("/home/app", "/libs/oracles"),
+        PathBuf::from("/libs/oracles")
+    );
+}
+
+#[test]
+fn windows_drive_path_replaces_manifest_dir() {
+    assert_eq!(
+        resolve("C:\\app", "D:\\libs\\oracles\\"),
+        PathBuf::from("D:/libs/oracles")
+    );
+}
+
+#[test]
+fn uri_replaces_manifest_dir() {
+    assert_eq!(
+        resolve("file:///home/app", "file:///libs/oracles"),
+        PathBuf::from("file:///libs/oracles")
+    );
+}
+
+#[test]
+fn path_back_to_manifest_dir_matches_normalized_manifest_dir() {
+    assert_eq!(
+        resolve("C:\\projects\\app\\", "..\\app"),
+        resolve("C:\\projects\\app\\", ".")
+    );
+    assert_eq!(
+        resolve("/projects/app/", "../app"),
+        resolve("/projects/app/", ".")
+    );
+}
@@ -1,34 +1,64 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use expect_test::Expect;
-use qsc_project::{FileSystem, Manifest, StdFs};
+use qsc_project::{FileSystem, Manifest, ManifestDescriptor, StdFs};
 
 pub fn check(project_path: &PathBuf, expect: &Expect) {
+    let root_path = root_path();
+    let manifest = load_manifest(&root_path, project_path);
+    let fs = StdFs;
+    let mut project = fs.load_project(&manifest).expect("project should load");
+
+    // remove the prefix absolute path
+    strip_root_path(&root_path, &mut project.sources);
+    for dependency in &mut project.dependencies {
+        strip_root_path(&root_path, &mut dependency.sources);
+    }
+
+    expect.assert_eq(&format!("{project:#?}"));
+}
+
+pub fn check_error(project_path: &PathBuf, expect: &Expect) {
+    let root_path = root_path();
+    let manifest = load_manifest(&root_path, project_path);
+    let fs = StdFs;
+    let error = fs
+        .load_project(&manifest)
+        .expect_err("project should fail to load");
+
+    expect.assert_eq(&error.to_string());
+}
+
+fn root_path() -> PathBuf {
     let mut root_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     root_path.push(PathBuf::from("tests/projects"));
-    let mut absolute_project_path = root_path.clone();
+    root_path
+}
+
+fn load_manifest(root_path: &Path, project_path: impl AsRef<Path>) -> ManifestDescriptor {
+    let mut absolute_project_path = root_path.to_path_buf();
     absolute_project_path.push(project_path);
-    let manifest = Manifest::load_from_path(absolute_project_path)
+    Manifest::load_from_path(absolute_project_path)
         .expect("manifest should load")
-        .expect("manifest should contain descriptor");
-    let fs = StdFs;
-    let mut project = fs.load_project(&manifest).expect("project should load");
+        .expect("manifest should contain descriptor")
+}
 
-    // remove the prefix absolute path
-    for (path, _contents) in &mut project.sources {
+fn strip_root_path(root_path: &Path, sources: &mut [(Arc<str>, Arc<str>)]) {
+    for (path, _contents) in sources.iter_mut() {
         let new_path = PathBuf::from(path.to_string());
         let new_path = new_path
-            .strip_prefix(&root_path)
+            .strip_prefix(root_path)
             .expect("prefix should be present")
             .to_string_lossy();
         let new_path = new_path.replace(std::path::MAIN_SEPARATOR, "/");
         *path = Arc::from(new_path);
     }
 
-    project.sources.sort();
-
-    expect.assert_eq(&format!("{project:#?}"));
+    sources.sort();
 }
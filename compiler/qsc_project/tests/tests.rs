@@ -8,7 +8,7 @@
 mod harness;
 
 use expect_test::expect;
-use harness::check;
+use harness::{check, check_error};
 
 #[test]
 fn basic_manifest() {
@@ -37,7 +37,9 @@ fn basic_manifest() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    dependencies: {},
                 },
+                dependencies: [],
             }"#]],
     );
 }
@@ -69,7 +71,9 @@ fn circular_imports() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    dependencies: {},
                 },
+                dependencies: [],
             }"#]],
     );
 }
@@ -101,7 +105,9 @@ fn different_files_same_manifest() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    dependencies: {},
                 },
+                dependencies: [],
             }"#]],
     );
 }
@@ -123,7 +129,9 @@ fn empty_manifest() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    dependencies: {},
                 },
+                dependencies: [],
             }"#]],
     );
 }
@@ -157,7 +165,9 @@ fn folder_structure() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    dependencies: {},
                 },
+                dependencies: [],
             }"#]],
     );
 }
@@ -186,7 +196,9 @@ fn hidden_files() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    dependencies: {},
                 },
+                dependencies: [],
             }"#]],
     );
 }
@@ -219,7 +231,9 @@ fn peer_file() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    dependencies: {},
                 },
+                dependencies: [],
             }"#]],
     );
 }
@@ -243,7 +257,91 @@ fn language_feature() {
                         "v2-preview-syntax",
                     ],
                     lints: [],
+                    dependencies: {},
                 },
+                dependencies: [],
             }"#]],
     );
 }
+
+#[test]
+fn package_dependencies() {
+    check(
+        &"package_dependencies/app".into(),
+        &expect![[r#"
+        Project {
+            sources: [
+                (
+                    "package_dependencies/app/src/Main.qs",
+                    "namespace Main {\n    open Geometry;\n    open Units;\n    @EntryPoint()\n    operation Main() : Double {\n        ToMeters(Perimeter(1.0, 2.0))\n    }\n}\n",
+                ),
+            ],
+            manifest: Manifest {
+                author: None,
+                license: None,
+                language_features: [],
+                lints: [],
+                dependencies: {
+                    "Geometry": Dependency {
+                        path: "../geometry",
+                    },
+                    "Units": Dependency {
+                        path: "../units",
+                    },
+                },
+            },
+            dependencies: [
+                DependencyPackage {
+                    name: "Units",
+                    sources: [
+                        (
+                            "package_dependencies/units/src/Units.qs",
+                            "namespace Units {\n    function ToMeters(feet : Double) : Double {\n        feet * FeetPerMeter()\n    }\n    internal function FeetPerMeter() : Double {\n        0.3048\n    }\n}\n",
+                        ),
+                    ],
+                    manifest: Manifest {
+                        author: None,
+                        license: None,
+                        language_features: [],
+                        lints: [],
+                        dependencies: {},
+                    },
+                    dependencies: [],
+                    direct: true,
+                },
+                DependencyPackage {
+                    name: "Geometry",
+                    sources: [
+                        (
+                            "package_dependencies/geometry/src/Geometry.qs",
+                            "namespace Geometry {\n    function Perimeter(width : Double, height : Double) : Double {\n        Double(2) * (width + height)\n    }\n}\n",
+                        ),
+                    ],
+                    manifest: Manifest {
+                        author: None,
+                        license: None,
+                        language_features: [],
+                        lints: [],
+                        dependencies: {
+                            "Units": Dependency {
+                                path: "../units/",
+                            },
+                        },
+                    },
+                    dependencies: [
+                        0,
+                    ],
+                    direct: true,
+                },
+            ],
+        }"#]],
+    );
+}
+
+#[test]
+fn circular_dependencies() {
+    check_error(
+        &"circular_dependencies/first".into(),
+        &expect!["circular dependency on package `First`"],
+    );
+}
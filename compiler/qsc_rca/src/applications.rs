@@ -387,6 +387,15 @@ impl GeneratorSetsBuilder {
     }
 }
 
+/// A loop whose body is being analyzed.
+#[derive(Debug)]
+pub struct ActiveLoop {
+    /// The number of active dynamic scopes at the start of the loop body.
+    pub dynamic_scopes_count: usize,
+    /// Whether a break or continue within a dynamic scope opened inside the loop body has been found.
+    pub has_dynamic_jump: bool,
+}
+
 /// An instance of a callable application.
 #[derive(Debug, Default)]
 pub struct ApplicationInstance {
@@ -394,6 +403,8 @@ pub struct ApplicationInstance {
     pub locals_map: LocalsComputeKindMap,
     /// The currently active dynamic scopes in the application instance.
     pub active_dynamic_scopes: Vec<ExprId>,
+    /// The loops whose bodies are currently being analyzed, from outermost to innermost.
+    pub active_loops: Vec<ActiveLoop>,
     /// The return expressions throughout the application instance.
     /// The first ID in the tuple represents the return expression itself.
     /// The second ID in the tuple represents the returned value expression.
@@ -500,6 +511,7 @@ impl ApplicationInstance {
         Self {
             locals_map,
             active_dynamic_scopes: Vec::new(),
+            active_loops: Vec::new(),
             return_expressions: Vec::new(),
             return_type: return_type.clone(),
             blocks: FxHashMap::default(),
@@ -587,6 +599,16 @@ impl LocalsComputeKindMap {
         local_compute_kind.compute_kind = local_compute_kind.compute_kind.aggregate(delta);
     }
 
+    /// Gets the compute kind of every local currently in the map.
+    pub fn compute_kinds(&self) -> Vec<(LocalVarId, ComputeKind)> {
+        self.0
+            .iter()
+            .map(|(local_var_id, local_compute_kind)| {
+                (local_var_id, local_compute_kind.compute_kind)
+            })
+            .collect()
+    }
+
     pub fn find_local_compute_kind(&self, local_var_id: LocalVarId) -> Option<&LocalComputeKind> {
         self.0.get(local_var_id)
     }
@@ -2,7 +2,7 @@
 // Licensed under the MIT License.
 
 use crate::{
-    applications::{ApplicationInstance, GeneratorSetsBuilder, LocalComputeKind},
+    applications::{ActiveLoop, ApplicationInstance, GeneratorSetsBuilder, LocalComputeKind},
     common::{try_resolve_callee, Callee, FunctorAppExt, GlobalSpecId, Local, LocalKind, TyExt},
     scaffolding::{InternalItemComputeProperties, InternalPackageStoreComputeProperties},
     ApplicationGeneratorSet, ArrayParamApplication, ComputeKind, ComputePropertiesLookup,
@@ -674,6 +674,27 @@ impl<'a> Analyzer<'a> {
         compute_kind
     }
 
+    fn analyze_expr_loop_jump(&mut self) -> ComputeKind {
+        // A break or continue that happens within a dynamic scope opened inside the loop body makes the number of
+        // iterations of the loop dynamic.
+        // Top-level statements are analyzed in isolation before their enclosing loop, so there might not be an active
+        // loop yet, in which case no dynamic scope can be open either.
+        let application_instance = self.get_current_application_instance_mut();
+        let active_dynamic_scopes_count = application_instance.active_dynamic_scopes.len();
+        let Some(active_loop) = application_instance.active_loops.last_mut() else {
+            return ComputeKind::Classical;
+        };
+        if active_dynamic_scopes_count > active_loop.dynamic_scopes_count {
+            active_loop.has_dynamic_jump = true;
+            ComputeKind::Quantum(QuantumProperties {
+                runtime_features: RuntimeFeatureFlags::LoopWithDynamicCondition,
+                value_kind: ValueKind::Element(RuntimeKind::Static),
+            })
+        } else {
+            ComputeKind::Classical
+        }
+    }
+
     fn analyze_expr_range(
         &mut self,
         start_expr_id: Option<ExprId>,
@@ -903,31 +924,37 @@ impl<'a> Analyzer<'a> {
     }
 
     fn analyze_expr_while(&mut self, condition_expr_id: ExprId, block_id: BlockId) -> ComputeKind {
-        // Visit the condition expression to determine its compute kind.
-        self.visit_expr(condition_expr_id);
-
-        // If the condition expression is dynamic, we push a new dynamic scope before visiting the block.
-        let application_instance = self.get_current_application_instance_mut();
-        let condition_expr_compute_kind =
-            *application_instance.get_expr_compute_kind(condition_expr_id);
-        let within_dynamic_scope = condition_expr_compute_kind.is_dynamic();
-        if within_dynamic_scope {
-            application_instance
-                .active_dynamic_scopes
-                .push(condition_expr_id);
-        }
-        self.visit_block(block_id);
-        if within_dynamic_scope {
-            let application_instance = self.get_current_application_instance_mut();
-            let dynamic_scope_expr_id = application_instance
-                .active_dynamic_scopes
-                .pop()
-                .expect("at least one dynamic scope should exist");
-            assert!(dynamic_scope_expr_id == condition_expr_id);
+        // Locals updated by the block flow back into the condition expression and the block itself on the next
+        // iteration, so visit both until the compute kind of the locals in scope before the loop no longer changes.
+        // A break or continue within a dynamic scope makes the number of iterations dynamic, so once one is found the
+        // whole loop is visited as a dynamic scope. Compute kinds only ever become more dynamic, so this always
+        // terminates.
+        let mut has_dynamic_jump = false;
+        loop {
+            let locals_before = self
+                .get_current_application_instance()
+                .locals_map
+                .compute_kinds();
+            let found_dynamic_jump =
+                self.analyze_expr_while_iteration(condition_expr_id, block_id, has_dynamic_jump);
+            let locals_map = &self.get_current_application_instance().locals_map;
+            if found_dynamic_jump == has_dynamic_jump
+                && locals_before.iter().all(|(local_var_id, compute_kind)| {
+                    locals_map
+                        .get_local_compute_kind(*local_var_id)
+                        .compute_kind
+                        == *compute_kind
+                })
+            {
+                break;
+            }
+            has_dynamic_jump = found_dynamic_jump;
         }
 
         // Return the aggregated runtime features of the condition expression and the block.
         let application_instance = self.get_current_application_instance();
+        let condition_expr_compute_kind =
+            *application_instance.get_expr_compute_kind(condition_expr_id);
         let block_compute_kind = *application_instance.get_block_compute_kind(block_id);
         let default_value_kind = ValueKind::Element(RuntimeKind::Static);
         let mut compute_kind = ComputeKind::Classical;
@@ -947,6 +974,49 @@ impl<'a> Analyzer<'a> {
         compute_kind
     }
 
+    /// Visits the condition expression and the block of a loop once, returning whether the loop has a break or continue
+    /// within a dynamic scope.
+    fn analyze_expr_while_iteration(
+        &mut self,
+        condition_expr_id: ExprId,
+        block_id: BlockId,
+        has_dynamic_jump: bool,
+    ) -> bool {
+        // Visit the condition expression to determine its compute kind.
+        self.visit_expr(condition_expr_id);
+
+        // If the condition expression is dynamic or the loop has dynamic jumps, we push a new dynamic scope before
+        // visiting the block.
+        let application_instance = self.get_current_application_instance_mut();
+        let condition_expr_compute_kind =
+            *application_instance.get_expr_compute_kind(condition_expr_id);
+        let within_dynamic_scope = condition_expr_compute_kind.is_dynamic() || has_dynamic_jump;
+        if within_dynamic_scope {
+            application_instance
+                .active_dynamic_scopes
+                .push(condition_expr_id);
+        }
+        let dynamic_scopes_count = application_instance.active_dynamic_scopes.len();
+        application_instance.active_loops.push(ActiveLoop {
+            dynamic_scopes_count,
+            has_dynamic_jump,
+        });
+        self.visit_block(block_id);
+        let application_instance = self.get_current_application_instance_mut();
+        let active_loop = application_instance
+            .active_loops
+            .pop()
+            .expect("at least one active loop should exist");
+        if within_dynamic_scope {
+            let dynamic_scope_expr_id = application_instance
+                .active_dynamic_scopes
+                .pop()
+                .expect("at least one dynamic scope should exist");
+            assert!(dynamic_scope_expr_id == condition_expr_id);
+        }
+        active_loop.has_dynamic_jump
+    }
+
     // Analyzes the currently active callable assuming it is intrinsic.
     fn analyze_intrinsic_callable(&mut self) {
         // Check whether the callable has already been analyzed.
@@ -1164,6 +1234,9 @@ impl<'a> Analyzer<'a> {
                     self.bind_fixed_expr_compute_kind_to_pattern(mutability, pat_id, expr_id);
                 }
             },
+            PatKind::Array(..) => {
+                self.bind_fixed_expr_compute_kind_to_pattern(mutability, pat_id, expr_id);
+            }
             PatKind::Discard => {
                 // Nothing to bind to.
             }
@@ -1192,6 +1265,62 @@ impl<'a> Analyzer<'a> {
                     self.bind_fixed_expr_compute_kind_to_pattern(mutability, *pat_id, expr_id);
                 }
             }
+            PatKind::Array(..) => {
+                let application_instance = self.get_current_application_instance();
+                let compute_kind = *application_instance.get_expr_compute_kind(expr_id);
+                self.bind_compute_kind_to_pattern(mutability, pat_id, expr_id, compute_kind);
+            }
+            PatKind::Discard => {
+                // Nothing to bind to.
+            }
+        }
+    }
+
+    fn bind_compute_kind_to_pattern(
+        &mut self,
+        mutability: Mutability,
+        pat_id: PatId,
+        expr_id: ExprId,
+        compute_kind: ComputeKind,
+    ) {
+        let pat = self.get_pat(pat_id);
+        match &pat.kind {
+            PatKind::Bind(ident) => {
+                let local_kind = match mutability {
+                    Mutability::Immutable => LocalKind::Immutable(expr_id),
+                    Mutability::Mutable => LocalKind::Mutable,
+                };
+                self.bind_compute_kind_to_ident(pat, ident, local_kind, compute_kind);
+            }
+            PatKind::Tuple(pats) => {
+                for pat_id in pats {
+                    let item_compute_kind =
+                        derive_item_compute_kind(compute_kind, &self.get_pat(*pat_id).ty);
+                    self.bind_compute_kind_to_pattern(
+                        mutability,
+                        *pat_id,
+                        expr_id,
+                        item_compute_kind,
+                    );
+                }
+            }
+            PatKind::Array(pats, rest) => {
+                for pat_id in pats {
+                    let item_compute_kind =
+                        derive_item_compute_kind(compute_kind, &self.get_pat(*pat_id).ty);
+                    self.bind_compute_kind_to_pattern(
+                        mutability,
+                        *pat_id,
+                        expr_id,
+                        item_compute_kind,
+                    );
+                }
+
+                // The rest of the array has the same compute kind as the whole array.
+                if let Some(rest) = rest {
+                    self.bind_compute_kind_to_pattern(mutability, *rest, expr_id, compute_kind);
+                }
+            }
             PatKind::Discard => {
                 // Nothing to bind to.
             }
@@ -1563,6 +1692,7 @@ impl<'a> Visitor<'a> for Analyzer<'a> {
                 self.analyze_expr_bin_op(*lhs_expr_id, *rhs_expr_id, &expr.ty)
             }
             ExprKind::Block(block_id) => self.analyze_expr_block(*block_id),
+            ExprKind::Break | ExprKind::Continue => self.analyze_expr_loop_jump(),
             ExprKind::Call(callee_expr_id, args_expr_id) => {
                 self.analyze_expr_call(*callee_expr_id, *args_expr_id, &expr.ty)
             }
@@ -1643,7 +1773,10 @@ impl<'a> Visitor<'a> for Analyzer<'a> {
     fn visit_item(&mut self, item: &'a Item) {
         let current_item_context = self.get_current_item_context();
         match &item.kind {
-            ItemKind::Namespace(_, _) | ItemKind::Ty(_, _) => {
+            ItemKind::Namespace(_, _)
+            | ItemKind::Ty(_, _)
+            | ItemKind::Export(_, _)
+            | ItemKind::Const(_, _) => {
                 self.package_store_compute_properties.insert_item(
                     current_item_context.id,
                     InternalItemComputeProperties::NonCallable,
@@ -2184,6 +2317,16 @@ fn derive_runtime_features_for_value_kind_associated_to_type(
     }
 }
 
+/// Derives the compute kind of an item of a tuple or an array from the compute kind of the whole.
+fn derive_item_compute_kind(compute_kind: ComputeKind, item_ty: &Ty) -> ComputeKind {
+    let mut item_compute_kind = ComputeKind::Classical
+        .aggregate_runtime_features(compute_kind, ValueKind::new_static_from_type(item_ty));
+    if compute_kind.is_dynamic() {
+        item_compute_kind.aggregate_value_kind(ValueKind::new_dynamic_from_type(item_ty));
+    }
+    item_compute_kind
+}
+
 fn derive_specialization_controls(
     spec_decl: &SpecDecl,
     pats: &IndexMap<PatId, Pat>,
@@ -2198,7 +2341,9 @@ fn derive_specialization_controls(
                 kind: LocalKind::SpecInput,
             }),
             PatKind::Discard => None, // Nothing to bind to.
-            PatKind::Tuple(_) => panic!("expected specialization input pattern"),
+            PatKind::Tuple(_) | PatKind::Array(..) => {
+                panic!("expected specialization input pattern")
+            }
         }
     })
 }
@@ -2233,6 +2378,9 @@ fn map_input_pattern_to_input_expressions(
                 _ => panic!("expected tuple expression"),
             }
         }
+        PatKind::Array(..) => {
+            panic!("array patterns in callable inputs should be replaced by bindings")
+        }
     }
 }
 
@@ -71,7 +71,7 @@ impl<'a> CycleDetector<'a> {
                     }
                 }
             }
-            PatKind::Discard => {}
+            PatKind::Array(..) | PatKind::Discard => {}
         }
     }
 
@@ -122,6 +122,8 @@ impl<'a> CycleDetector<'a> {
                     callable_decl,
                 ),
                 ItemKind::Namespace(_, _) => panic!("calls to namespaces are invalid"),
+                ItemKind::Export(_, _) => panic!("calls should refer to the exported item"),
+                ItemKind::Const(_, _) => panic!("uses of constants should be replaced by values"),
                 ItemKind::Ty(_, _) => {
                     // Ignore "calls" to types.
                 }
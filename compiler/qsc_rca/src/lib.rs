@@ -433,7 +433,7 @@ impl Display for ArrayParamApplication {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ComputeKind {
     Classical,
     Quantum(QuantumProperties),
@@ -529,7 +529,8 @@ impl ComputeKind {
         quantum_properties.value_kind = quantum_properties.value_kind.aggregate(value);
     }
 
-    pub(crate) fn is_dynamic(self) -> bool {
+    #[must_use]
+    pub fn is_dynamic(self) -> bool {
         match self {
             Self::Classical => false,
             Self::Quantum(quantum_properties) => quantum_properties.value_kind.is_dynamic(),
@@ -552,7 +553,7 @@ impl ComputeKind {
 }
 
 /// The quantum properties of a program element.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct QuantumProperties {
     /// The runtime features used by the program element.
     pub runtime_features: RuntimeFeatureFlags,
@@ -571,7 +572,7 @@ impl Display for QuantumProperties {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ValueKind {
     /// The first runtime kind corresponds to the content of the array while the second corresponds to the size.
     Array(RuntimeKind, RuntimeKind),
@@ -679,7 +680,7 @@ impl ValueKind {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RuntimeKind {
     Static,
     Dynamic,
@@ -383,3 +383,70 @@ fn check_rca_for_array_with_static_size_bound_through_dynamic_tuple() {
                 dynamic_param_applications: <empty>"#]],
     );
 }
+
+#[test]
+fn check_rca_for_nested_array_update_with_dynamic_value() {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(
+        r#"
+        use q = Qubit();
+        let grid = [[0, 0], [0, 0]];
+        let value = M(q) == Zero ? 0 | 1;
+        grid w/ [1][0] <- value"#,
+    );
+    let package_store_compute_properties = compilation_context.get_compute_properties();
+    check_last_statement_compute_properties(
+        package_store_compute_properties,
+        &expect![[r#"
+        ApplicationsGeneratorSet:
+            inherent: Quantum: QuantumProperties:
+                runtime_features: RuntimeFeatureFlags(UseOfDynamicBool | UseOfDynamicInt)
+                value_kind: Array(Content: Dynamic, Size: Static)
+            dynamic_param_applications: <empty>"#]],
+    );
+}
+
+#[test]
+fn check_rca_for_nested_array_update_with_dynamic_index() {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(
+        r#"
+        use q = Qubit();
+        let grid = [[0, 0], [0, 0]];
+        let index = M(q) == Zero ? 0 | 1;
+        grid w/ [1][index] <- 5"#,
+    );
+    let package_store_compute_properties = compilation_context.get_compute_properties();
+    check_last_statement_compute_properties(
+        package_store_compute_properties,
+        &expect![[r#"
+        ApplicationsGeneratorSet:
+            inherent: Quantum: QuantumProperties:
+                runtime_features: RuntimeFeatureFlags(UseOfDynamicBool | UseOfDynamicInt | UseOfDynamicIndex)
+                value_kind: Array(Content: Dynamic, Size: Static)
+            dynamic_param_applications: <empty>"#]],
+    );
+}
+
+#[test]
+fn check_rca_for_nested_array_assign_update_with_dynamic_index() {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(
+        r#"
+        use q = Qubit();
+        mutable grid = [[0, 0], [0, 0]];
+        let index = M(q) == Zero ? 0 | 1;
+        set grid w/= [index][1] <- 5;
+        grid"#,
+    );
+    let package_store_compute_properties = compilation_context.get_compute_properties();
+    check_last_statement_compute_properties(
+        package_store_compute_properties,
+        &expect![[r#"
+        ApplicationsGeneratorSet:
+            inherent: Quantum: QuantumProperties:
+                runtime_features: RuntimeFeatureFlags(UseOfDynamicBool | UseOfDynamicInt | UseOfDynamicIndex)
+                value_kind: Array(Content: Dynamic, Size: Static)
+            dynamic_param_applications: <empty>"#]],
+    );
+}
@@ -0,0 +1,134 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+pub mod test_utils;
+
+use expect_test::expect;
+use test_utils::{check_last_statement_compute_properties, CompilationContext};
+
+#[test]
+fn check_rca_for_enum_variant_with_classical_payload() {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(
+        r#"
+        enum Shape { Circle(Double), Empty }
+        Shape.Circle(1.0)"#,
+    );
+    let package_store_compute_properties = compilation_context.get_compute_properties();
+    check_last_statement_compute_properties(
+        package_store_compute_properties,
+        &expect![
+            r#"
+            ApplicationsGeneratorSet:
+                inherent: Classical
+                dynamic_param_applications: <empty>"#
+        ],
+    );
+}
+
+#[test]
+fn check_rca_for_match_on_enum_with_dynamic_payload() {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(
+        r#"
+        enum Shape { Circle(Double), Empty }
+        use q = Qubit();
+        let r = M(q) == Zero ? 0.0 | 1.0;
+        let s = Shape.Circle(r);
+        match s {
+            Shape.Circle(r) => r,
+            Shape.Empty => 0.0,
+        }"#,
+    );
+    let package_store_compute_properties = compilation_context.get_compute_properties();
+    check_last_statement_compute_properties(
+        package_store_compute_properties,
+        &expect![
+            r#"
+            ApplicationsGeneratorSet:
+                inherent: Quantum: QuantumProperties:
+                    runtime_features: RuntimeFeatureFlags(UseOfDynamicBool | UseOfDynamicDouble)
+                    value_kind: Element(Dynamic)
+                dynamic_param_applications: <empty>"#
+        ],
+    );
+}
+
+#[test]
+fn check_rca_for_array_of_enums() {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(
+        r#"
+        enum Shape { Circle(Double), Rect((Double, Double)), Empty }
+        let shapes = [Shape.Circle(1.0), Shape.Rect((1.0, 2.0)), Shape.Empty];
+        shapes"#,
+    );
+    let package_store_compute_properties = compilation_context.get_compute_properties();
+    check_last_statement_compute_properties(
+        package_store_compute_properties,
+        &expect![
+            r#"
+            ApplicationsGeneratorSet:
+                inherent: Classical
+                dynamic_param_applications: <empty>"#
+        ],
+    );
+}
+
+#[test]
+fn check_rca_for_match_on_enum_with_dynamic_variant() {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(
+        r#"
+        enum Shape { Circle(Double), Empty }
+        use q = Qubit();
+        let s = M(q) == Zero ? Shape.Circle(1.0) | Shape.Empty;
+        match s {
+            Shape.Circle(r) => r,
+            Shape.Empty => 0.0,
+        }"#,
+    );
+    let package_store_compute_properties = compilation_context.get_compute_properties();
+    check_last_statement_compute_properties(
+        package_store_compute_properties,
+        &expect![
+            r#"
+            ApplicationsGeneratorSet:
+                inherent: Quantum: QuantumProperties:
+                    runtime_features: RuntimeFeatureFlags(UseOfDynamicBool | UseOfDynamicDouble | UseOfDynamicUdt)
+                    value_kind: Element(Dynamic)
+                dynamic_param_applications: <empty>"#
+        ],
+    );
+}
+
+#[test]
+fn check_rca_for_function_with_enum_param() {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(
+        r#"
+        enum Shape { Circle(Double), Empty }
+        function Area(s : Shape) : Double {
+            match s {
+                Shape.Circle(r) => 3.0 * r * r,
+                Shape.Empty => 0.0,
+            }
+        }
+        let wrap = Shape.Circle;
+        Area(wrap(2.0))"#,
+    );
+    let package_store_compute_properties = compilation_context.get_compute_properties();
+    check_last_statement_compute_properties(
+        package_store_compute_properties,
+        &expect![
+            r#"
+            ApplicationsGeneratorSet:
+                inherent: Quantum: QuantumProperties:
+                    runtime_features: RuntimeFeatureFlags(UseOfDynamicBool | UseOfDynamicDouble | UseOfDynamicUdt | CallToDynamicCallee | UseOfClosure)
+                    value_kind: Element(Dynamic)
+                dynamic_param_applications: <empty>"#
+        ],
+    );
+}
@@ -99,3 +99,26 @@ fn check_rca_for_udt_field_update_with_dynamic_value() {
         ],
     );
 }
+
+#[test]
+fn check_rca_for_nested_udt_field_update_with_dynamic_value() {
+    let mut compilation_context = CompilationContext::default();
+    compilation_context.update(
+        r#"
+        open Microsoft.Quantum.Math;
+        let cs = [Complex(0.0, 0.0), Complex(1.0, 0.0)];
+        use q = Qubit();
+        let i = M(q) == Zero ? 0.0 | 1.0;
+        cs w/ [1]::Imag <- i"#,
+    );
+    let package_store_compute_properties = compilation_context.get_compute_properties();
+    check_last_statement_compute_properties(
+        package_store_compute_properties,
+        &expect![[r#"
+        ApplicationsGeneratorSet:
+            inherent: Quantum: QuantumProperties:
+                runtime_features: RuntimeFeatureFlags(UseOfDynamicBool | UseOfDynamicDouble)
+                value_kind: Array(Content: Dynamic, Size: Static)
+            dynamic_param_applications: <empty>"#]],
+    );
+}
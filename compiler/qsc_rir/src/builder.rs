@@ -13,6 +13,7 @@ pub fn x_decl() -> Callable {
     Callable {
         name: "__quantum__qis__x__body".to_string(),
         input_type: vec![Ty::Qubit],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::Regular,
@@ -24,6 +25,7 @@ pub fn z_decl() -> Callable {
     Callable {
         name: "__quantum__qis__z__body".to_string(),
         input_type: vec![Ty::Qubit],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::Regular,
@@ -35,6 +37,7 @@ pub fn h_decl() -> Callable {
     Callable {
         name: "__quantum__qis__h__body".to_string(),
         input_type: vec![Ty::Qubit],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::Regular,
@@ -46,6 +49,7 @@ pub fn cx_decl() -> Callable {
     Callable {
         name: "__quantum__qis__cx__body".to_string(),
         input_type: vec![Ty::Qubit, Ty::Qubit],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::Regular,
@@ -57,6 +61,7 @@ pub fn rx_decl() -> Callable {
     Callable {
         name: "__quantum__qis__rx__body".to_string(),
         input_type: vec![Ty::Double, Ty::Qubit],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::Regular,
@@ -68,6 +73,7 @@ pub fn mz_decl() -> Callable {
     Callable {
         name: "__quantum__qis__mz__body".to_string(),
         input_type: vec![Ty::Qubit, Ty::Result],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::Measurement,
@@ -79,6 +85,7 @@ pub fn mresetz_decl() -> Callable {
     Callable {
         name: "__quantum__qis__mresetz__body".to_string(),
         input_type: vec![Ty::Qubit, Ty::Result],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::Measurement,
@@ -90,17 +97,79 @@ pub fn reset_decl() -> Callable {
     Callable {
         name: "__quantum__qis__reset__body".to_string(),
         input_type: vec![Ty::Qubit],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::Reset,
     }
 }
 
+#[must_use]
+pub fn qubit_allocate_decl() -> Callable {
+    Callable {
+        name: "__quantum__rt__qubit_allocate".to_string(),
+        input_type: Vec::new(),
+        params: Vec::new(),
+        output_type: Some(Ty::Qubit),
+        body: None,
+        call_type: CallableType::Regular,
+    }
+}
+
+#[must_use]
+pub fn qubit_release_decl() -> Callable {
+    Callable {
+        name: "__quantum__rt__qubit_release".to_string(),
+        input_type: vec![Ty::Qubit],
+        params: Vec::new(),
+        output_type: None,
+        body: None,
+        call_type: CallableType::Regular,
+    }
+}
+
+#[must_use]
+pub fn qubit_allocate_array_decl() -> Callable {
+    Callable {
+        name: "__quantum__rt__qubit_allocate_array".to_string(),
+        input_type: vec![Ty::Integer],
+        params: Vec::new(),
+        output_type: Some(Ty::Pointer),
+        body: None,
+        call_type: CallableType::Regular,
+    }
+}
+
+#[must_use]
+pub fn qubit_release_array_decl() -> Callable {
+    Callable {
+        name: "__quantum__rt__qubit_release_array".to_string(),
+        input_type: vec![Ty::Pointer],
+        params: Vec::new(),
+        output_type: None,
+        body: None,
+        call_type: CallableType::Regular,
+    }
+}
+
+#[must_use]
+pub fn array_get_element_ptr_1d_decl() -> Callable {
+    Callable {
+        name: "__quantum__rt__array_get_element_ptr_1d".to_string(),
+        input_type: vec![Ty::Pointer, Ty::Integer],
+        params: Vec::new(),
+        output_type: Some(Ty::Pointer),
+        body: None,
+        call_type: CallableType::Regular,
+    }
+}
+
 #[must_use]
 pub fn read_result_decl() -> Callable {
     Callable {
         name: "__quantum__qis__read_result__body".to_string(),
         input_type: vec![Ty::Result],
+        params: Vec::new(),
         output_type: Some(Ty::Boolean),
         body: None,
         call_type: CallableType::Readout,
@@ -112,6 +181,7 @@ pub fn result_record_decl() -> Callable {
     Callable {
         name: "__quantum__rt__result_record_output".to_string(),
         input_type: vec![Ty::Result, Ty::Pointer],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::OutputRecording,
@@ -123,6 +193,19 @@ pub fn int_record_decl() -> Callable {
     Callable {
         name: "__quantum__rt__integer_record_output".to_string(),
         input_type: vec![Ty::Integer, Ty::Pointer],
+        params: Vec::new(),
+        output_type: None,
+        body: None,
+        call_type: CallableType::OutputRecording,
+    }
+}
+
+#[must_use]
+pub fn double_record_decl() -> Callable {
+    Callable {
+        name: "__quantum__rt__double_record_output".to_string(),
+        input_type: vec![Ty::Double, Ty::Pointer],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::OutputRecording,
@@ -134,6 +217,7 @@ pub fn bool_record_decl() -> Callable {
     Callable {
         name: "__quantum__rt__bool_record_output".to_string(),
         input_type: vec![Ty::Boolean, Ty::Pointer],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::OutputRecording,
@@ -145,6 +229,7 @@ pub fn array_record_decl() -> Callable {
     Callable {
         name: "__quantum__rt__array_record_output".to_string(),
         input_type: vec![Ty::Integer, Ty::Pointer],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::OutputRecording,
@@ -156,6 +241,7 @@ pub fn tuple_record_decl() -> Callable {
     Callable {
         name: "__quantum__rt__tuple_record_output".to_string(),
         input_type: vec![Ty::Integer, Ty::Pointer],
+        params: Vec::new(),
         output_type: None,
         body: None,
         call_type: CallableType::OutputRecording,
@@ -172,6 +258,7 @@ pub fn new_program() -> Program {
         Callable {
             name: "main".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -195,6 +282,7 @@ pub fn bell_program() -> Program {
         Callable {
             name: "main".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -284,6 +372,7 @@ pub fn teleport_program() -> Program {
         Callable {
             name: "main".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 pub mod builder;
+pub mod parse;
 pub mod passes;
 pub mod rir;
 pub mod utils;
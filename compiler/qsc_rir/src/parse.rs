@@ -0,0 +1,545 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Parsing of the textual form of the RIR, as produced by the `Display` implementations in [`crate::rir`].
+//! Parsing the display of a program produces a program with the same display, so programs can be written as text,
+//! parsed, transformed and displayed again.
+
+#[cfg(test)]
+mod tests;
+
+use std::str::FromStr;
+
+use miette::Diagnostic;
+use qsc_data_structures::{span::Span, target::TargetCapabilityFlags};
+use thiserror::Error;
+
+use crate::rir::{
+    Block, BlockId, Callable, CallableId, CallableType, ConditionCode, Config, FcmpConditionCode,
+    Instruction, Literal, Operand, OptimizationLevel, Program, Ty, Variable, VariableId,
+};
+
+/// An error in the textual form of the RIR.
+#[derive(Clone, Debug, Diagnostic, Eq, Error, PartialEq)]
+pub enum Error {
+    #[error("expected {0}, found {1}")]
+    #[diagnostic(code("Qsc.Rir.Parse.Token"))]
+    Token(String, String, #[label] Span),
+
+    #[error("invalid {0} `{1}`")]
+    #[diagnostic(code("Qsc.Rir.Parse.Invalid"))]
+    Invalid(&'static str, String, #[label] Span),
+
+    #[error("duplicate {0} id {1}")]
+    #[diagnostic(code("Qsc.Rir.Parse.DuplicateId"))]
+    DuplicateId(&'static str, u32, #[label] Span),
+}
+
+/// Parses a program from the text produced by its `Display` implementation.
+/// # Errors
+/// Returns an error if the input is not a well-formed program.
+pub fn parse_program(input: &str) -> Result<Program, Error> {
+    let mut parser = Parser::new(input);
+    let program = parser.program()?;
+    parser.end()?;
+    Ok(program)
+}
+
+/// Parses a block from the text produced by its `Display` implementation.
+/// # Errors
+/// Returns an error if the input is not a well-formed block.
+pub fn parse_block(input: &str) -> Result<Block, Error> {
+    let mut parser = Parser::new(input);
+    let block = parser.block()?;
+    parser.end()?;
+    Ok(block)
+}
+
+/// The keywords that can start an instruction.
+const INSTRUCTION_STARTS: [&str; 5] = ["Variable(", "Call ", "Jump(", "Branch ", "Return"];
+
+struct Parser<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, offset: 0 }
+    }
+
+    fn program(&mut self) -> Result<Program, Error> {
+        let mut program = Program::new();
+        self.expect("Program:")?;
+        self.expect("entry:")?;
+        program.entry = CallableId(self.value("callable id")?);
+
+        self.expect("callables:")?;
+        while self.peek("Callable ") {
+            self.expect("Callable")?;
+            self.skip_whitespace();
+            let lo = self.offset;
+            let id = self.value("callable id")?;
+            let id_span = self.span_from(lo);
+            self.expect(":")?;
+            let callable = self.callable()?;
+            if program.callables.contains_key(CallableId(id)) {
+                return Err(Error::DuplicateId("callable", id, id_span));
+            }
+            program.callables.insert(CallableId(id), callable);
+        }
+
+        self.expect("blocks:")?;
+        while self.peek("Block ") {
+            self.expect("Block")?;
+            self.skip_whitespace();
+            let lo = self.offset;
+            let id = self.value("block id")?;
+            let id_span = self.span_from(lo);
+            self.expect(":")?;
+            let block = self.block()?;
+            if program.blocks.contains_key(BlockId(id)) {
+                return Err(Error::DuplicateId("block", id, id_span));
+            }
+            program.blocks.insert(BlockId(id), block);
+        }
+
+        self.expect("config:")?;
+        program.config = self.config()?;
+        self.expect("num_qubits:")?;
+        program.num_qubits = self.value("number of qubits")?;
+        self.expect("num_results:")?;
+        program.num_results = self.value("number of results")?;
+        Ok(program)
+    }
+
+    fn callable(&mut self) -> Result<Callable, Error> {
+        self.expect("Callable:")?;
+        self.expect("name:")?;
+        let name = self.rest_of_line("callable name")?;
+
+        self.expect("call_type:")?;
+        let (call_type, span) = self.word("call type")?;
+        let call_type = match call_type {
+            "Measurement" => CallableType::Measurement,
+            "Reset" => CallableType::Reset,
+            "Readout" => CallableType::Readout,
+            "OutputRecording" => CallableType::OutputRecording,
+            "Regular" => CallableType::Regular,
+            _ => return Err(Error::Invalid("call type", call_type.to_string(), span)),
+        };
+
+        self.expect("input_type:")?;
+        let mut input_type = Vec::new();
+        if !self.eat("<VOID>") {
+            while self.eat("[") {
+                self.skip_whitespace();
+                let lo = self.offset;
+                let index = self.value::<usize>("input index")?;
+                if index != input_type.len() {
+                    return Err(Error::Invalid(
+                        "input index",
+                        index.to_string(),
+                        self.span_from(lo),
+                    ));
+                }
+                self.expect("]")?;
+                self.expect(":")?;
+                input_type.push(self.ty()?);
+            }
+        }
+
+        let mut params = Vec::new();
+        if self.eat("params:") {
+            while self.eat("[") {
+                self.skip_whitespace();
+                let lo = self.offset;
+                let index = self.value::<usize>("param index")?;
+                if index != params.len() {
+                    return Err(Error::Invalid(
+                        "param index",
+                        index.to_string(),
+                        self.span_from(lo),
+                    ));
+                }
+                self.expect("]")?;
+                self.expect(":")?;
+                params.push(self.variable()?);
+            }
+        }
+
+        self.expect("output_type:")?;
+        let output_type = if self.eat("<VOID>") {
+            None
+        } else {
+            Some(self.ty()?)
+        };
+
+        self.expect("body:")?;
+        let body = if self.eat("<NONE>") {
+            None
+        } else {
+            Some(BlockId(self.value("block id")?))
+        };
+
+        Ok(Callable {
+            name,
+            input_type,
+            params,
+            output_type,
+            body,
+            call_type,
+        })
+    }
+
+    fn config(&mut self) -> Result<Config, Error> {
+        let mut config = Config::default();
+        self.expect("Config:")?;
+        self.expect("capabilities:")?;
+        if !self.eat("Base") {
+            self.expect("TargetCapabilityFlags(")?;
+            loop {
+                let (flag, span) = self.word("capability")?;
+                config.capabilities |= TargetCapabilityFlags::from_str(flag)
+                    .map_err(|()| Error::Invalid("capability", flag.to_string(), span))?;
+                if !self.eat("|") {
+                    break;
+                }
+            }
+            self.expect(")")?;
+        }
+        if self.eat("optimization_level:") {
+            let (level, span) = self.word("optimization level")?;
+            config.optimization_level = match level {
+                "None" => OptimizationLevel::None,
+                "Basic" => OptimizationLevel::Basic,
+                "Full" => OptimizationLevel::Full,
+                _ => {
+                    return Err(Error::Invalid(
+                        "optimization level",
+                        level.to_string(),
+                        span,
+                    ))
+                }
+            };
+        }
+        Ok(config)
+    }
+
+    fn block(&mut self) -> Result<Block, Error> {
+        self.expect("Block:")?;
+        let mut instrs = Vec::new();
+        if !self.eat("<EMPTY>") {
+            while INSTRUCTION_STARTS.iter().any(|start| self.peek(start)) {
+                instrs.push(self.instruction()?);
+            }
+        }
+        Ok(Block(instrs))
+    }
+
+    fn instruction(&mut self) -> Result<Instruction, Error> {
+        if self.eat("Jump(") {
+            let block_id = BlockId(self.value("block id")?);
+            self.expect(")")?;
+            return Ok(Instruction::Jump(block_id));
+        }
+        if self.eat("Branch") {
+            let condition = self.variable()?;
+            self.expect(",")?;
+            let if_true = BlockId(self.value("block id")?);
+            self.expect(",")?;
+            let if_false = BlockId(self.value("block id")?);
+            return Ok(Instruction::Branch(condition, if_true, if_false));
+        }
+        if self.eat("Return") {
+            return Ok(Instruction::Return);
+        }
+        if self.eat("Call") {
+            return self.call(None);
+        }
+
+        let variable = self.variable()?;
+        self.expect("=")?;
+        let (name, span) = self.word("instruction")?;
+        let instr = match name {
+            "Call" => return self.call(Some(variable)),
+            "Store" => Instruction::Store(self.operand()?, variable),
+            "LogicalNot" => Instruction::LogicalNot(self.operand()?, variable),
+            "BitwiseNot" => Instruction::BitwiseNot(self.operand()?, variable),
+            "Sitofp" => Instruction::Sitofp(self.operand()?, variable),
+            "Fptosi" => Instruction::Fptosi(self.operand()?, variable),
+            "Load" => Instruction::Load(self.operand()?, variable),
+            "Icmp" => {
+                let (cond, span) = self.word("condition code")?;
+                let cond = condition_code(cond)
+                    .ok_or_else(|| Error::Invalid("condition code", cond.to_string(), span))?;
+                self.expect(",")?;
+                let (lhs, rhs) = self.operand_pair()?;
+                Instruction::Icmp(cond, lhs, rhs, variable)
+            }
+            "Fcmp" => {
+                let (cond, span) = self.word("condition code")?;
+                let cond = fcmp_condition_code(cond)
+                    .ok_or_else(|| Error::Invalid("condition code", cond.to_string(), span))?;
+                self.expect(",")?;
+                let (lhs, rhs) = self.operand_pair()?;
+                Instruction::Fcmp(cond, lhs, rhs, variable)
+            }
+            "Phi" => {
+                self.expect("(")?;
+                let mut args = Vec::new();
+                while self.eat("[") {
+                    let operand = self.operand()?;
+                    self.expect(",")?;
+                    let block_id = BlockId(self.value("block id")?);
+                    self.expect("]")?;
+                    self.expect(",")?;
+                    args.push((operand, block_id));
+                }
+                self.expect(")")?;
+                Instruction::Phi(args, variable)
+            }
+            _ => {
+                let Some(binary) = binary_instruction(name) else {
+                    return Err(Error::Invalid("instruction", name.to_string(), span));
+                };
+                let (lhs, rhs) = self.operand_pair()?;
+                binary(lhs, rhs, variable)
+            }
+        };
+        Ok(instr)
+    }
+
+    fn call(&mut self, variable: Option<Variable>) -> Result<Instruction, Error> {
+        self.expect("id(")?;
+        let callable_id = CallableId(self.value("callable id")?);
+        self.expect(")")?;
+        self.expect(",")?;
+        self.expect("args(")?;
+        let mut args = Vec::new();
+        while !self.eat(")") {
+            args.push(self.operand()?);
+            self.expect(",")?;
+        }
+        Ok(Instruction::Call(callable_id, args, variable))
+    }
+
+    fn operand_pair(&mut self) -> Result<(Operand, Operand), Error> {
+        let lhs = self.operand()?;
+        self.expect(",")?;
+        let rhs = self.operand()?;
+        Ok((lhs, rhs))
+    }
+
+    fn operand(&mut self) -> Result<Operand, Error> {
+        if self.peek("Variable(") {
+            return Ok(Operand::Variable(self.variable()?));
+        }
+
+        let (kind, span) = self.word("operand")?;
+        if kind == "Pointer" {
+            return Ok(Operand::Literal(Literal::Pointer));
+        }
+        self.expect("(")?;
+        let literal = match kind {
+            "Qubit" => Literal::Qubit(self.value("qubit id")?),
+            "Result" => Literal::Result(self.value("result id")?),
+            "Bool" => Literal::Bool(self.value("boolean")?),
+            "Integer" => Literal::Integer(self.value("integer")?),
+            "Double" => Literal::Double(self.value("double")?),
+            _ => return Err(Error::Invalid("operand", kind.to_string(), span)),
+        };
+        self.expect(")")?;
+        Ok(Operand::Literal(literal))
+    }
+
+    fn variable(&mut self) -> Result<Variable, Error> {
+        self.expect("Variable(")?;
+        let variable_id = VariableId(self.value("variable id")?);
+        self.expect(",")?;
+        let ty = self.ty()?;
+        self.expect(")")?;
+        Ok(Variable { variable_id, ty })
+    }
+
+    fn ty(&mut self) -> Result<Ty, Error> {
+        let (ty, span) = self.word("type")?;
+        Ok(match ty {
+            "Qubit" => Ty::Qubit,
+            "Result" => Ty::Result,
+            "Boolean" => Ty::Boolean,
+            "Integer" => Ty::Integer,
+            "Double" => Ty::Double,
+            "Pointer" => Ty::Pointer,
+            _ => return Err(Error::Invalid("type", ty.to_string(), span)),
+        })
+    }
+
+    /// Parses a value that runs until the next delimiter, such as a number or boolean.
+    fn value<T: FromStr>(&mut self, what: &'static str) -> Result<T, Error> {
+        self.skip_whitespace();
+        let lo = self.offset;
+        let len = self.input[lo..]
+            .find(|c: char| c.is_whitespace() || matches!(c, ',' | '(' | ')' | '[' | ']' | ':'))
+            .unwrap_or(self.input.len() - lo);
+        if len == 0 {
+            return Err(self.unexpected(what));
+        }
+        self.offset += len;
+        let text = &self.input[lo..self.offset];
+        text.parse()
+            .map_err(|_| Error::Invalid(what, text.to_string(), self.span_from(lo)))
+    }
+
+    fn word(&mut self, what: &'static str) -> Result<(&'a str, Span), Error> {
+        self.skip_whitespace();
+        let lo = self.offset;
+        let len = self.input[lo..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.input.len() - lo);
+        if len == 0 {
+            return Err(self.unexpected(what));
+        }
+        self.offset += len;
+        Ok((&self.input[lo..self.offset], self.span_from(lo)))
+    }
+
+    fn rest_of_line(&mut self, what: &'static str) -> Result<String, Error> {
+        let rest = &self.input[self.offset..];
+        let start = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        let len = rest.find('\n').unwrap_or(rest.len());
+        let text = rest[start.min(len)..len].trim_end();
+        if text.is_empty() {
+            self.offset += start;
+            return Err(self.unexpected(what));
+        }
+        self.offset += len;
+        Ok(text.to_string())
+    }
+
+    fn peek(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        self.input[self.offset..].starts_with(token)
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.peek(token) {
+            self.offset += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), Error> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{token}`")))
+        }
+    }
+
+    fn end(&mut self) -> Result<(), Error> {
+        self.skip_whitespace();
+        if self.offset == self.input.len() {
+            Ok(())
+        } else {
+            Err(self.unexpected("end of input"))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.offset..];
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    /// Creates an error for the token at the current offset, which is not the expected one.
+    fn unexpected(&mut self, expected: &str) -> Error {
+        self.skip_whitespace();
+        let rest = &self.input[self.offset..];
+        let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let found = if len == 0 {
+            "end of input".to_string()
+        } else {
+            format!("`{}`", &rest[..len])
+        };
+        let lo = self.offset;
+        Error::Token(
+            expected.to_string(),
+            found,
+            Span {
+                lo: offset_to_u32(lo),
+                hi: offset_to_u32(lo + len),
+            },
+        )
+    }
+
+    fn span_from(&self, lo: usize) -> Span {
+        Span {
+            lo: offset_to_u32(lo),
+            hi: offset_to_u32(self.offset),
+        }
+    }
+}
+
+fn offset_to_u32(offset: usize) -> u32 {
+    offset.try_into().expect("offset should fit into u32")
+}
+
+type BinaryInstruction = fn(Operand, Operand, Variable) -> Instruction;
+
+fn binary_instruction(name: &str) -> Option<BinaryInstruction> {
+    Some(match name {
+        "Add" => Instruction::Add,
+        "Sub" => Instruction::Sub,
+        "Mul" => Instruction::Mul,
+        "Sdiv" => Instruction::Sdiv,
+        "Srem" => Instruction::Srem,
+        "Shl" => Instruction::Shl,
+        "Ashr" => Instruction::Ashr,
+        "Fadd" => Instruction::Fadd,
+        "Fsub" => Instruction::Fsub,
+        "Fmul" => Instruction::Fmul,
+        "Fdiv" => Instruction::Fdiv,
+        "LogicalAnd" => Instruction::LogicalAnd,
+        "LogicalOr" => Instruction::LogicalOr,
+        "BitwiseAnd" => Instruction::BitwiseAnd,
+        "BitwiseOr" => Instruction::BitwiseOr,
+        "BitwiseXor" => Instruction::BitwiseXor,
+        _ => return None,
+    })
+}
+
+fn condition_code(name: &str) -> Option<ConditionCode> {
+    Some(match name {
+        "Eq" => ConditionCode::Eq,
+        "Ne" => ConditionCode::Ne,
+        "Slt" => ConditionCode::Slt,
+        "Sle" => ConditionCode::Sle,
+        "Sgt" => ConditionCode::Sgt,
+        "Sge" => ConditionCode::Sge,
+        _ => return None,
+    })
+}
+
+fn fcmp_condition_code(name: &str) -> Option<FcmpConditionCode> {
+    Some(match name {
+        "False" => FcmpConditionCode::False,
+        "Oeq" => FcmpConditionCode::OrderedAndEqual,
+        "Ogt" => FcmpConditionCode::OrderedAndGreaterThan,
+        "Oge" => FcmpConditionCode::OrderedAndGreaterThanOrEqual,
+        "Olt" => FcmpConditionCode::OrderedAndLessThan,
+        "Ole" => FcmpConditionCode::OrderedAndLessThanOrEqual,
+        "One" => FcmpConditionCode::OrderedAndNotEqual,
+        "Ord" => FcmpConditionCode::Ordered,
+        "Ueq" => FcmpConditionCode::UnorderedOrEqual,
+        "Ugt" => FcmpConditionCode::UnorderedOrGreaterThan,
+        "Uge" => FcmpConditionCode::UnorderedOrGreaterThanOrEqual,
+        "Ult" => FcmpConditionCode::UnorderedOrLessThan,
+        "Ule" => FcmpConditionCode::UnorderedOrLessThanOrEqual,
+        "Une" => FcmpConditionCode::UnorderedOrNotEqual,
+        "Uno" => FcmpConditionCode::Unordered,
+        "True" => FcmpConditionCode::True,
+        _ => return None,
+    })
+}
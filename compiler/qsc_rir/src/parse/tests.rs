@@ -0,0 +1,310 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::expect;
+
+use super::{parse_block, parse_program};
+use crate::{
+    builder::{bell_program, teleport_program},
+    rir::{Block, BlockId},
+};
+
+fn check_round_trip(input: &str) {
+    let program = parse_program(input).expect("program should parse");
+    assert_eq!(program.to_string(), input);
+}
+
+#[test]
+fn bell_program_round_trips() {
+    check_round_trip(&bell_program().to_string());
+}
+
+#[test]
+fn teleport_program_round_trips() {
+    check_round_trip(&teleport_program().to_string());
+}
+
+#[test]
+fn program_with_every_instruction_round_trips() {
+    check_round_trip(
+        r#"Program:
+    entry: 0
+    callables:
+        Callable 0: Callable:
+            name: main
+            call_type: Regular
+            input_type: <VOID>
+            output_type: <VOID>
+            body: 0
+        Callable 1: Callable:
+            name: __quantum__qis__rz__body
+            call_type: Regular
+            input_type:
+                [0]: Double
+                [1]: Qubit
+            output_type: <VOID>
+            body: <NONE>
+        Callable 2: Callable:
+            name: __quantum__qis__read_result__body
+            call_type: Readout
+            input_type:
+                [0]: Result
+            output_type: Boolean
+            body: <NONE>
+        Callable 3: Callable:
+            name: __quantum__rt__tuple_record_output
+            call_type: OutputRecording
+            input_type:
+                [0]: Integer
+                [1]: Pointer
+            output_type: <VOID>
+            body: <NONE>
+    blocks:
+        Block 0: Block:
+            Variable(0, Boolean) = Call id(2), args( Result(0), )
+            Variable(1, Integer) = Store Integer(-3)
+            Variable(2, Integer) = Add Variable(1, Integer), Integer(1)
+            Variable(3, Integer) = Sub Variable(2, Integer), Integer(1)
+            Variable(4, Integer) = Mul Variable(3, Integer), Integer(2)
+            Variable(5, Integer) = Sdiv Variable(4, Integer), Integer(2)
+            Variable(6, Integer) = Srem Variable(5, Integer), Integer(2)
+            Variable(7, Integer) = Shl Variable(6, Integer), Integer(1)
+            Variable(8, Integer) = Ashr Variable(7, Integer), Integer(1)
+            Variable(9, Integer) = BitwiseNot Variable(8, Integer)
+            Variable(10, Integer) = BitwiseAnd Variable(9, Integer), Integer(7)
+            Variable(11, Integer) = BitwiseOr Variable(10, Integer), Integer(8)
+            Variable(12, Integer) = BitwiseXor Variable(11, Integer), Integer(15)
+            Variable(13, Boolean) = Icmp Sge, Variable(12, Integer), Integer(0)
+            Variable(14, Boolean) = LogicalNot Variable(13, Boolean)
+            Variable(15, Boolean) = LogicalAnd Variable(14, Boolean), Bool(true)
+            Variable(16, Boolean) = LogicalOr Variable(15, Boolean), Variable(0, Boolean)
+            Branch Variable(16, Boolean), 1, 2
+        Block 1: Block:
+            Variable(17, Double) = Sitofp Variable(12, Integer)
+            Variable(18, Double) = Fadd Variable(17, Double), Double(0.5)
+            Variable(19, Double) = Fsub Variable(18, Double), Double(-1)
+            Variable(20, Double) = Fmul Variable(19, Double), Double(NaN)
+            Variable(21, Double) = Fdiv Variable(20, Double), Double(inf)
+            Variable(22, Boolean) = Fcmp Uno, Variable(21, Double), Double(3.141592653589793)
+            Variable(23, Integer) = Fptosi Variable(21, Double)
+            Call id(1), args( Variable(21, Double), Qubit(0), )
+            Jump(2)
+        Block 2: Block:
+            Variable(24, Integer) = Phi ( [Integer(0), 0], [Variable(23, Integer), 1], )
+            Call id(3), args( Variable(24, Integer), Pointer, )
+            Return
+        Block 3: Block: <EMPTY>
+    config: Config:
+        capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations)
+        optimization_level: Basic
+    num_qubits: 1
+    num_results: 1"#,
+    );
+}
+
+#[test]
+fn program_with_callable_params_round_trips() {
+    check_round_trip(
+        r#"Program:
+    entry: 0
+    callables:
+        Callable 0: Callable:
+            name: main
+            call_type: Regular
+            input_type: <VOID>
+            output_type: <VOID>
+            body: 0
+        Callable 1: Callable:
+            name: Rotate__body
+            call_type: Regular
+            input_type:
+                [0]: Double
+                [1]: Qubit
+            params:
+                [0]: Variable(0, Double)
+                [1]: Variable(1, Qubit)
+            output_type: <VOID>
+            body: 1
+        Callable 2: Callable:
+            name: __quantum__qis__rz__body
+            call_type: Regular
+            input_type:
+                [0]: Double
+                [1]: Qubit
+            output_type: <VOID>
+            body: <NONE>
+    blocks:
+        Block 0: Block:
+            Call id(1), args( Double(0.5), Qubit(0), )
+            Return
+        Block 1: Block:
+            Call id(2), args( Variable(0, Double), Variable(1, Qubit), )
+            Return
+    config: Config:
+        capabilities: TargetCapabilityFlags(Adaptive | FloatingPointComputations | HigherLevelConstructs)
+    num_qubits: 1
+    num_results: 0"#,
+    );
+}
+
+#[test]
+fn block_parses() {
+    let block = parse_block(
+        r#"Block:
+    Variable(0, Integer) = Add Integer(1), Integer(2)
+    Jump(1)"#,
+    )
+    .expect("block should parse");
+    expect![[r#"
+        Block:
+            Variable(0, Integer) = Add Integer(1), Integer(2)
+            Jump(1)"#]]
+    .assert_eq(&block.to_string());
+}
+
+#[test]
+fn block_parses_regardless_of_whitespace() {
+    let block = parse_block("Block: Variable(0, Boolean) = Call id(1), args(Qubit(0),) Return")
+        .expect("block should parse");
+    expect![[r#"
+        Block:
+            Variable(0, Boolean) = Call id(1), args( Qubit(0), )
+            Return"#]]
+    .assert_eq(&block.to_string());
+}
+
+#[test]
+fn unknown_instruction_is_error() {
+    let Err(error) = parse_block("Block:\n    Variable(0, Integer) = Pow Integer(1), Integer(2)")
+    else {
+        panic!("block should not parse");
+    };
+    expect![[r#"
+        Invalid(
+            "instruction",
+            "Pow",
+            Span {
+                lo: 34,
+                hi: 37,
+            },
+        )
+    "#]]
+    .assert_debug_eq(&error);
+}
+
+#[test]
+fn missing_token_is_error() {
+    let Err(error) = parse_block("Block:\n    Variable(0, Integer) = Add Integer(1) Integer(2)")
+    else {
+        panic!("block should not parse");
+    };
+    expect![[r#"
+        Token(
+            "`,`",
+            "`Integer(2)`",
+            Span {
+                lo: 49,
+                hi: 59,
+            },
+        )
+    "#]]
+    .assert_debug_eq(&error);
+}
+
+#[test]
+fn invalid_literal_is_error() {
+    let Err(error) = parse_block("Block:\n    Variable(0, Boolean) = Store Bool(maybe)") else {
+        panic!("block should not parse");
+    };
+    expect![[r#"
+        Invalid(
+            "boolean",
+            "maybe",
+            Span {
+                lo: 45,
+                hi: 50,
+            },
+        )
+    "#]]
+    .assert_debug_eq(&error);
+}
+
+#[test]
+fn unknown_type_is_error() {
+    let Err(error) = parse_block("Block:\n    Variable(0, Float) = Store Double(1.5)") else {
+        panic!("block should not parse");
+    };
+    expect![[r#"
+        Invalid(
+            "type",
+            "Float",
+            Span {
+                lo: 23,
+                hi: 28,
+            },
+        )
+    "#]]
+    .assert_debug_eq(&error);
+}
+
+#[test]
+fn trailing_input_is_error() {
+    let Err(error) = parse_block("Block:\n    Return\n    Goto(1)") else {
+        panic!("block should not parse");
+    };
+    expect![[r#"
+        Token(
+            "end of input",
+            "`Goto(1)`",
+            Span {
+                lo: 22,
+                hi: 29,
+            },
+        )
+    "#]]
+    .assert_debug_eq(&error);
+}
+
+#[test]
+fn duplicate_block_is_error() {
+    let mut program = bell_program();
+    program.blocks.insert(BlockId(1), Block(Vec::new()));
+    let input = program
+        .to_string()
+        .replace("Block 1: Block: <EMPTY>", "Block 0: Block: <EMPTY>");
+    let Err(error) = parse_program(&input) else {
+        panic!("program should not parse");
+    };
+    expect![[r#"
+        DuplicateId(
+            "block",
+            0,
+            Span {
+                lo: 1846,
+                hi: 1847,
+            },
+        )
+    "#]]
+    .assert_debug_eq(&error);
+}
+
+#[test]
+fn missing_program_field_is_error() {
+    let input = bell_program().to_string().replace("    num_results: 2", "");
+    let Err(error) = parse_program(&input) else {
+        panic!("program should not parse");
+    };
+    expect![[r#"
+        Token(
+            "`num_results:`",
+            "end of input",
+            Span {
+                lo: 1897,
+                hi: 1897,
+            },
+        )
+    "#]]
+    .assert_debug_eq(&error);
+}
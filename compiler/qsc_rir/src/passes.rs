@@ -3,8 +3,12 @@
 
 mod build_dominator_graph;
 mod defer_meas;
+mod fold_constants;
+mod optimize_gates;
+mod propagate_copies;
 mod reindex_qubits;
 mod remap_block_ids;
+mod remove_dead_code;
 mod simplify_control_flow;
 mod ssa_check;
 mod ssa_transform;
@@ -13,16 +17,23 @@ mod unreachable_code_check;
 
 use build_dominator_graph::build_dominator_graph;
 use defer_meas::defer_measurements;
+use fold_constants::fold_constants;
+pub use optimize_gates::{optimize_gates, GateOptimizationStats};
+use propagate_copies::propagate_copies;
 use qsc_data_structures::target::TargetCapabilityFlags;
 use reindex_qubits::reindex_qubits;
 use remap_block_ids::remap_block_ids;
+use remove_dead_code::remove_dead_code;
 use simplify_control_flow::simplify_control_flow;
 use ssa_check::check_ssa_form;
 use ssa_transform::transform_to_ssa;
 pub use type_check::check_types;
 pub use unreachable_code_check::check_unreachable_code;
 
-use crate::{rir::Program, utils::build_predecessors_map};
+use crate::{
+    rir::{OptimizationLevel, Program},
+    utils::build_predecessors_map,
+};
 
 /// Run the default set of RIR check and transformation passes.
 /// This includes:
@@ -32,6 +43,8 @@ use crate::{rir::Program, utils::build_predecessors_map};
 /// - Remapping block IDs
 /// - Transforming the program to SSA form
 /// - Checking that the program is in SSA form
+/// - If the program has an optimization level, running the optimization passes for that level and checking the
+///   optimized program again.
 /// - If the target has no reset capability, reindexing qubit IDs and removing resets.
 /// - If the target has no mid-program measurement capability, deferring measurements to the end of the program.
 pub fn check_and_transform(program: &mut Program) {
@@ -46,6 +59,16 @@ pub fn check_and_transform(program: &mut Program) {
     check_unreachable_code(program);
     check_types(program);
 
+    if program.config.optimization_level != OptimizationLevel::None {
+        optimize(program);
+        remap_block_ids(program);
+        let preds = build_predecessors_map(program);
+        let doms = build_dominator_graph(program, &preds);
+        check_ssa_form(program, &preds, &doms);
+        check_unreachable_code(program);
+        check_types(program);
+    }
+
     // Run the RIR passes that are necessary for targets with no mid-program measurement.
     // This requires that qubits are not reused after measurement or reset, so qubit ids must be reindexed.
     // This also requires that the program has no loops and block ids form a topological ordering on a
@@ -61,3 +84,24 @@ pub fn check_and_transform(program: &mut Program) {
         defer_measurements(program);
     }
 }
+
+/// Run the optimization passes for the program's optimization level until none of them change the program.
+/// For `OptimizationLevel::Basic` and `OptimizationLevel::Full`, this includes:
+/// - Folding constants and branches on constant conditions
+/// - Propagating copies
+/// - Removing dead code
+///
+/// The program is expected to be in SSA form.
+pub fn optimize(program: &mut Program) {
+    if program.config.optimization_level == OptimizationLevel::None {
+        return;
+    }
+    loop {
+        let mut changed = fold_constants(program);
+        changed |= propagate_copies(program);
+        changed |= remove_dead_code(program);
+        if !changed {
+            break;
+        }
+    }
+}
@@ -15,26 +15,32 @@ mod tests;
 /// by Cooper, Harvey, and Kennedy, with two notable differences:
 /// - Blocks are assumed to be sequentially numbered starting from 0 in reverse postorder rather than depth first order.
 /// - Given that reversal, intersection between nodes uses the lesser of the two nodes rather than the greater.
+///
+/// Every callable with a body is treated as a separate graph rooted at its entry block.
 #[must_use]
 pub fn build_dominator_graph(
     program: &Program,
     preds: &IndexMap<BlockId, Vec<BlockId>>,
 ) -> IndexMap<BlockId, BlockId> {
     let mut doms = IndexMap::default();
-    let entry_block_id = program
-        .get_callable(program.entry)
-        .body
-        .expect("entry point should have a body");
 
-    // The entry block dominates itself.
-    doms.insert(entry_block_id, entry_block_id);
+    // The entry block of each callable dominates itself.
+    for (_, callable) in program.callables.iter() {
+        if let Some(entry_block_id) = callable.body {
+            doms.insert(entry_block_id, entry_block_id);
+        }
+    }
 
     // The algorithm needs to run until the dominance map stabilizes, ie: no block's immediate dominator changes.
     let mut changed = true;
     while changed {
         changed = false;
-        // Always skip the entry block, as it is the only block that by definition dominates itself.
-        for (block_id, _) in program.blocks.iter().skip(1) {
+        for (block_id, _) in program.blocks.iter() {
+            // Always skip the entry blocks, as they are the only blocks that by definition dominate themselves.
+            if doms.get(block_id) == Some(&block_id) {
+                continue;
+            }
+
             // The immediate dominator of a block is the intersection of the dominators of its predecessors.
             // Start from an assumption that the first predecessor is the dominator, and intersect with the rest.
             let (first_pred, rest_preds) = preds
@@ -81,6 +81,7 @@ fn dominator_graph_branching_blocks_dominated_by_common_predecessor() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -157,6 +158,7 @@ fn dominator_graph_branch_and_loop() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -203,8 +205,8 @@ fn dominator_graph_branch_and_loop() {
         Block 0 dominated by block 0,
         Block 1 dominated by block 0,
         Block 2 dominated by block 1,
-        Block 3 dominated by block 1,
-        Block 4 dominated by block 2,
+        Block 3 dominated by block 2,
+        Block 4 dominated by block 1,
     "#]]
     .assert_eq(&display_dominator_graph(&doms));
 }
@@ -220,6 +222,7 @@ fn dominator_graph_complex_structure_only_dominated_by_entry() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -305,6 +308,7 @@ fn dominator_graph_with_node_having_many_predicates() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -384,3 +388,60 @@ fn dominator_graph_with_node_having_many_predicates() {
     "#]]
     .assert_eq(&display_dominator_graph(&doms));
 }
+
+#[test]
+fn dominator_graph_callable_body_is_separate_graph() {
+    let mut program = new_program();
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "op".to_string(),
+            input_type: vec![Ty::Boolean],
+            params: vec![Variable {
+                variable_id: VariableId(0),
+                ty: Ty::Boolean,
+            }],
+            output_type: None,
+            body: Some(BlockId(3)),
+            call_type: CallableType::Regular,
+        },
+    );
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), None),
+            Instruction::Jump(BlockId(1)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(1), Block(vec![Instruction::Return]));
+    program.blocks.insert(
+        BlockId(3),
+        Block(vec![Instruction::Branch(
+            Variable {
+                variable_id: VariableId(0),
+                ty: Ty::Boolean,
+            },
+            BlockId(4),
+            BlockId(2),
+        )]),
+    );
+    program
+        .blocks
+        .insert(BlockId(4), Block(vec![Instruction::Jump(BlockId(2))]));
+    program
+        .blocks
+        .insert(BlockId(2), Block(vec![Instruction::Return]));
+
+    let doms = build_doms(&mut program);
+
+    expect![[r#"
+        Block 0 dominated by block 0,
+        Block 1 dominated by block 0,
+        Block 2 dominated by block 2,
+        Block 3 dominated by block 2,
+        Block 4 dominated by block 2,
+    "#]]
+    .assert_eq(&display_dominator_graph(&doms));
+}
@@ -0,0 +1,244 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    rir::{ConditionCode, FcmpConditionCode, Instruction, Literal, Operand, Program, VariableId},
+    utils::{get_assigned_variable, replace_variable_uses},
+};
+
+/// Fold instructions whose operands are all literals into those literals, replacing every use of the
+/// variable they assign. Branches on a constant condition are folded into jumps to the taken block.
+/// Returns true if the program was changed.
+/// The program is expected to be in SSA form.
+pub fn fold_constants(program: &mut Program) -> bool {
+    let mut constants = FxHashMap::default();
+    for block in program.blocks.values() {
+        for instr in &block.0 {
+            if let Some((variable_id, literal)) = fold_instr(instr) {
+                constants.insert(variable_id, Operand::Literal(literal));
+            }
+        }
+    }
+
+    let mut changed = !constants.is_empty();
+    changed |= fold_branches(program, &constants);
+
+    // Every use of the folded variables can be replaced by the literal, so the instructions
+    // that assign them are no longer needed.
+    replace_variable_uses(program, &constants);
+    for block in program.blocks.values_mut() {
+        block.0.retain(|instr| {
+            get_assigned_variable(instr).is_none_or(|var| !constants.contains_key(&var.variable_id))
+        });
+    }
+
+    changed
+}
+
+fn fold_branches(program: &mut Program, constants: &FxHashMap<VariableId, Operand>) -> bool {
+    let mut changed = false;
+    for block_id in program.blocks.iter().map(|(id, _)| id).collect::<Vec<_>>() {
+        let Some(Instruction::Branch(cond, if_true, if_false)) =
+            program.get_block(block_id).0.last().cloned()
+        else {
+            continue;
+        };
+        let (target, removed) = match constants.get(&cond.variable_id) {
+            Some(Operand::Literal(Literal::Bool(true))) => (if_true, if_false),
+            Some(Operand::Literal(Literal::Bool(false))) => (if_false, if_true),
+            _ if if_true == if_false => (if_true, if_false),
+            _ => continue,
+        };
+
+        let block = program.get_block_mut(block_id);
+        block.0.pop();
+        block.0.push(Instruction::Jump(target));
+        changed = true;
+
+        // The block is no longer a predecessor of the block that is not taken, so remove it from any phi nodes there.
+        if removed != target {
+            for instr in &mut program.get_block_mut(removed).0 {
+                if let Instruction::Phi(args, _) = instr {
+                    args.retain(|(_, pred)| *pred != block_id);
+                }
+            }
+        }
+    }
+    changed
+}
+
+/// Returns the variable assigned by the instruction with the literal it folds to, if its operands are literals
+/// and the result can be computed without changing the behavior of the program.
+#[allow(clippy::cast_precision_loss, clippy::too_many_lines)]
+fn fold_instr(instr: &Instruction) -> Option<(VariableId, Literal)> {
+    let (var, literal) = match instr {
+        Instruction::Add(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_int(lhs, rhs, |l, r| Some(l.wrapping_add(r)))?)
+        }
+        Instruction::Sub(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_int(lhs, rhs, |l, r| Some(l.wrapping_sub(r)))?)
+        }
+        Instruction::Mul(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_int(lhs, rhs, |l, r| Some(l.wrapping_mul(r)))?)
+        }
+        // Division by zero and overflowing division are left for the target to handle.
+        Instruction::Sdiv(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_int(lhs, rhs, i64::checked_div)?)
+        }
+        Instruction::Srem(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_int(lhs, rhs, i64::checked_rem)?)
+        }
+        Instruction::Shl(Operand::Literal(lhs), Operand::Literal(rhs), var) => (
+            var,
+            fold_int(lhs, rhs, |l, r| l.checked_shl(r.try_into().ok()?))?,
+        ),
+        Instruction::Ashr(Operand::Literal(lhs), Operand::Literal(rhs), var) => (
+            var,
+            fold_int(lhs, rhs, |l, r| l.checked_shr(r.try_into().ok()?))?,
+        ),
+        Instruction::BitwiseAnd(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_int(lhs, rhs, |l, r| Some(l & r))?)
+        }
+        Instruction::BitwiseOr(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_int(lhs, rhs, |l, r| Some(l | r))?)
+        }
+        Instruction::BitwiseXor(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_int(lhs, rhs, |l, r| Some(l ^ r))?)
+        }
+        Instruction::BitwiseNot(Operand::Literal(Literal::Integer(value)), var) => {
+            (var, Literal::Integer(!value))
+        }
+        Instruction::LogicalAnd(
+            Operand::Literal(Literal::Bool(lhs)),
+            Operand::Literal(Literal::Bool(rhs)),
+            var,
+        ) => (var, Literal::Bool(*lhs && *rhs)),
+        Instruction::LogicalOr(
+            Operand::Literal(Literal::Bool(lhs)),
+            Operand::Literal(Literal::Bool(rhs)),
+            var,
+        ) => (var, Literal::Bool(*lhs || *rhs)),
+        Instruction::LogicalNot(Operand::Literal(Literal::Bool(value)), var) => {
+            (var, Literal::Bool(!value))
+        }
+        Instruction::Icmp(cond, Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, Literal::Bool(fold_icmp(*cond, lhs, rhs)?))
+        }
+        Instruction::Fadd(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_double(lhs, rhs, |l, r| l + r)?)
+        }
+        Instruction::Fsub(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_double(lhs, rhs, |l, r| l - r)?)
+        }
+        Instruction::Fmul(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_double(lhs, rhs, |l, r| l * r)?)
+        }
+        Instruction::Fdiv(Operand::Literal(lhs), Operand::Literal(rhs), var) => {
+            (var, fold_double(lhs, rhs, |l, r| l / r)?)
+        }
+        Instruction::Fcmp(
+            cond,
+            Operand::Literal(Literal::Double(lhs)),
+            Operand::Literal(Literal::Double(rhs)),
+            var,
+        ) => (var, Literal::Bool(fold_fcmp(*cond, *lhs, *rhs))),
+        Instruction::Sitofp(Operand::Literal(Literal::Integer(value)), var) => {
+            (var, Literal::Double(*value as f64))
+        }
+        Instruction::Fptosi(Operand::Literal(Literal::Double(value)), var) => {
+            (var, Literal::Integer(fold_fptosi(*value)?))
+        }
+        // A phi node whose arguments are all the same literal, ignoring references to itself, is that literal.
+        Instruction::Phi(args, var) => {
+            let mut args = args
+                .iter()
+                .map(|(arg, _)| *arg)
+                .filter(|arg| *arg != Operand::Variable(*var));
+            let Some(Operand::Literal(first)) = args.next() else {
+                return None;
+            };
+            if args.any(|arg| arg != Operand::Literal(first)) {
+                return None;
+            }
+            (var, first)
+        }
+        _ => return None,
+    };
+    Some((var.variable_id, literal))
+}
+
+fn fold_int(lhs: &Literal, rhs: &Literal, op: impl Fn(i64, i64) -> Option<i64>) -> Option<Literal> {
+    let (Literal::Integer(lhs), Literal::Integer(rhs)) = (lhs, rhs) else {
+        return None;
+    };
+    op(*lhs, *rhs).map(Literal::Integer)
+}
+
+/// Computes the result of a floating-point operation, unless it is not finite, in which case the operation is left
+/// for the target to handle since infinities and NaN cannot be written as literals.
+fn fold_double(lhs: &Literal, rhs: &Literal, op: impl Fn(f64, f64) -> f64) -> Option<Literal> {
+    let (Literal::Double(lhs), Literal::Double(rhs)) = (lhs, rhs) else {
+        return None;
+    };
+    let value = op(*lhs, *rhs);
+    value.is_finite().then_some(Literal::Double(value))
+}
+
+fn fold_icmp(cond: ConditionCode, lhs: &Literal, rhs: &Literal) -> Option<bool> {
+    match (lhs, rhs) {
+        (Literal::Integer(lhs), Literal::Integer(rhs)) => Some(match cond {
+            ConditionCode::Eq => lhs == rhs,
+            ConditionCode::Ne => lhs != rhs,
+            ConditionCode::Slt => lhs < rhs,
+            ConditionCode::Sle => lhs <= rhs,
+            ConditionCode::Sgt => lhs > rhs,
+            ConditionCode::Sge => lhs >= rhs,
+        }),
+        (Literal::Bool(lhs), Literal::Bool(rhs)) => match cond {
+            ConditionCode::Eq => Some(lhs == rhs),
+            ConditionCode::Ne => Some(lhs != rhs),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[allow(clippy::float_cmp)]
+fn fold_fcmp(cond: FcmpConditionCode, lhs: f64, rhs: f64) -> bool {
+    let unordered = lhs.is_nan() || rhs.is_nan();
+    match cond {
+        FcmpConditionCode::False => false,
+        FcmpConditionCode::OrderedAndEqual => !unordered && lhs == rhs,
+        FcmpConditionCode::OrderedAndGreaterThan => !unordered && lhs > rhs,
+        FcmpConditionCode::OrderedAndGreaterThanOrEqual => !unordered && lhs >= rhs,
+        FcmpConditionCode::OrderedAndLessThan => !unordered && lhs < rhs,
+        FcmpConditionCode::OrderedAndLessThanOrEqual => !unordered && lhs <= rhs,
+        FcmpConditionCode::OrderedAndNotEqual => !unordered && lhs != rhs,
+        FcmpConditionCode::Ordered => !unordered,
+        FcmpConditionCode::UnorderedOrEqual => unordered || lhs == rhs,
+        FcmpConditionCode::UnorderedOrGreaterThan => unordered || lhs > rhs,
+        FcmpConditionCode::UnorderedOrGreaterThanOrEqual => unordered || lhs >= rhs,
+        FcmpConditionCode::UnorderedOrLessThan => unordered || lhs < rhs,
+        FcmpConditionCode::UnorderedOrLessThanOrEqual => unordered || lhs <= rhs,
+        FcmpConditionCode::UnorderedOrNotEqual => unordered || lhs != rhs,
+        FcmpConditionCode::Unordered => unordered,
+        FcmpConditionCode::True => true,
+    }
+}
+
+/// Truncates the value to an integer, unless it is out of the range of integers, in which case the conversion
+/// is left for the target to handle.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn fold_fptosi(value: f64) -> Option<i64> {
+    let truncated = value.trunc();
+    if truncated.is_finite() && truncated >= i64::MIN as f64 && truncated < i64::MAX as f64 {
+        Some(truncated as i64)
+    } else {
+        None
+    }
+}
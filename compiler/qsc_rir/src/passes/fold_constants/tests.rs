@@ -0,0 +1,242 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::too_many_lines, clippy::needless_raw_string_hashes)]
+
+use expect_test::expect;
+
+use crate::{
+    builder::new_program,
+    passes::fold_constants::fold_constants,
+    rir::{
+        Block, BlockId, Callable, CallableId, CallableType, ConditionCode, Instruction, Literal,
+        Operand, Ty, Variable, VariableId,
+    },
+};
+
+fn int_var(id: u32) -> Variable {
+    Variable {
+        variable_id: VariableId(id),
+        ty: Ty::Integer,
+    }
+}
+
+fn bool_var(id: u32) -> Variable {
+    Variable {
+        variable_id: VariableId(id),
+        ty: Ty::Boolean,
+    }
+}
+
+fn double_var(id: u32) -> Variable {
+    Variable {
+        variable_id: VariableId(id),
+        ty: Ty::Double,
+    }
+}
+
+fn record_int_decl() -> Callable {
+    Callable {
+        name: "record_int".to_string(),
+        input_type: vec![Ty::Integer],
+        params: Vec::new(),
+        output_type: None,
+        body: None,
+        call_type: CallableType::Regular,
+    }
+}
+
+fn record_double_decl() -> Callable {
+    Callable {
+        name: "record_double".to_string(),
+        input_type: vec![Ty::Double],
+        params: Vec::new(),
+        output_type: None,
+        body: None,
+        call_type: CallableType::Regular,
+    }
+}
+
+#[test]
+fn fold_constants_folds_chain_of_literal_arithmetic() {
+    let mut program = new_program();
+    program.callables.insert(CallableId(1), record_int_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Add(
+                Operand::Literal(Literal::Integer(2)),
+                Operand::Literal(Literal::Integer(3)),
+                int_var(0),
+            ),
+            Instruction::Mul(
+                Operand::Variable(int_var(0)),
+                Operand::Literal(Literal::Integer(4)),
+                int_var(1),
+            ),
+            Instruction::Call(CallableId(1), vec![Operand::Variable(int_var(1))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    assert!(fold_constants(&mut program));
+    expect![[r#"
+        Block:
+            Variable(1, Integer) = Mul Integer(5), Integer(4)
+            Call id(1), args( Variable(1, Integer), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+
+    assert!(fold_constants(&mut program));
+    expect![[r#"
+        Block:
+            Call id(1), args( Integer(20), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+
+    assert!(!fold_constants(&mut program));
+}
+
+#[test]
+fn fold_constants_leaves_division_by_zero_unfolded() {
+    let mut program = new_program();
+    program.callables.insert(CallableId(1), record_int_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Sdiv(
+                Operand::Literal(Literal::Integer(1)),
+                Operand::Literal(Literal::Integer(0)),
+                int_var(0),
+            ),
+            Instruction::Call(CallableId(1), vec![Operand::Variable(int_var(0))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    assert!(!fold_constants(&mut program));
+    expect![[r#"
+        Block:
+            Variable(0, Integer) = Sdiv Integer(1), Integer(0)
+            Call id(1), args( Variable(0, Integer), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+}
+
+#[test]
+fn fold_constants_leaves_non_finite_double_results_unfolded() {
+    let mut program = new_program();
+    program
+        .callables
+        .insert(CallableId(1), record_double_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Fdiv(
+                Operand::Literal(Literal::Double(1.0)),
+                Operand::Literal(Literal::Double(0.0)),
+                double_var(0),
+            ),
+            Instruction::Fsub(
+                Operand::Literal(Literal::Double(0.0)),
+                Operand::Literal(Literal::Double(0.0)),
+                double_var(1),
+            ),
+            Instruction::Fdiv(
+                Operand::Variable(double_var(1)),
+                Operand::Literal(Literal::Double(0.0)),
+                double_var(2),
+            ),
+            Instruction::Call(CallableId(1), vec![Operand::Variable(double_var(0))], None),
+            Instruction::Call(CallableId(1), vec![Operand::Variable(double_var(2))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    assert!(fold_constants(&mut program));
+    assert!(!fold_constants(&mut program));
+    expect![[r#"
+        Block:
+            Variable(0, Double) = Fdiv Double(1), Double(0)
+            Variable(2, Double) = Fdiv Double(0), Double(0)
+            Call id(1), args( Variable(0, Double), )
+            Call id(1), args( Variable(2, Double), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+}
+
+#[test]
+fn fold_constants_folds_branch_on_constant_condition_into_jump() {
+    let mut program = new_program();
+    program.callables.insert(CallableId(1), record_int_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Icmp(
+                ConditionCode::Slt,
+                Operand::Literal(Literal::Integer(1)),
+                Operand::Literal(Literal::Integer(2)),
+                bool_var(0),
+            ),
+            Instruction::Branch(bool_var(0), BlockId(1), BlockId(2)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(1), Block(vec![Instruction::Jump(BlockId(2))]));
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![
+            Instruction::Phi(
+                vec![
+                    (Operand::Literal(Literal::Integer(5)), BlockId(1)),
+                    (Operand::Literal(Literal::Integer(7)), BlockId(0)),
+                ],
+                int_var(1),
+            ),
+            Instruction::Call(CallableId(1), vec![Operand::Variable(int_var(1))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    assert!(fold_constants(&mut program));
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: record_int
+                    call_type: Regular
+                    input_type:
+                        [0]: Integer
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Jump(1)
+                Block 1: Block:
+                    Jump(2)
+                Block 2: Block:
+                    Variable(1, Integer) = Phi ( [Integer(5), 1], )
+                    Call id(1), args( Variable(1, Integer), )
+                    Return
+            config: Config:
+                capabilities: Base
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+
+    // With the branch removed, the phi node has a single literal argument and can be folded.
+    assert!(fold_constants(&mut program));
+    expect![[r#"
+        Block:
+            Call id(1), args( Integer(5), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(2)).to_string());
+}
@@ -0,0 +1,494 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use std::fmt::{self, Display, Formatter};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::rir::{
+    BlockId, Callable, CallableId, CallableType, Instruction, Literal, Operand, OptimizationLevel,
+    Program, Ty,
+};
+
+/// Statistics about the gates removed by the gate optimization pass.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GateOptimizationStats {
+    /// The number of gates acting on a single qubit that were removed.
+    pub removed_single_qubit_gates: usize,
+    /// The number of gates acting on two or more qubits that were removed.
+    pub removed_multi_qubit_gates: usize,
+    /// The number of times two gates were merged into one. The gate merged away is also counted as removed.
+    pub merged_gates: usize,
+}
+
+impl Display for GateOptimizationStats {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "removed single-qubit gates: {}, removed multi-qubit gates: {}, merged gates: {}",
+            self.removed_single_qubit_gates, self.removed_multi_qubit_gates, self.merged_gates
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Gate {
+    H,
+    X,
+    Y,
+    Z,
+    S,
+    SAdj,
+    T,
+    TAdj,
+    Cx,
+    Cy,
+    Cz,
+    Swap,
+    Ccx,
+    Rx,
+    Ry,
+    Rz,
+    Rxx,
+    Ryy,
+    Rzz,
+}
+
+impl Gate {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "__quantum__qis__h__body" => Self::H,
+            "__quantum__qis__x__body" => Self::X,
+            "__quantum__qis__y__body" => Self::Y,
+            "__quantum__qis__z__body" => Self::Z,
+            "__quantum__qis__s__body" => Self::S,
+            "__quantum__qis__s__adj" => Self::SAdj,
+            "__quantum__qis__t__body" => Self::T,
+            "__quantum__qis__t__adj" => Self::TAdj,
+            "__quantum__qis__cx__body" => Self::Cx,
+            "__quantum__qis__cy__body" => Self::Cy,
+            "__quantum__qis__cz__body" => Self::Cz,
+            "__quantum__qis__swap__body" => Self::Swap,
+            "__quantum__qis__ccx__body" => Self::Ccx,
+            "__quantum__qis__rx__body" => Self::Rx,
+            "__quantum__qis__ry__body" => Self::Ry,
+            "__quantum__qis__rz__body" => Self::Rz,
+            "__quantum__qis__rxx__body" => Self::Rxx,
+            "__quantum__qis__ryy__body" => Self::Ryy,
+            "__quantum__qis__rzz__body" => Self::Rzz,
+            _ => return None,
+        })
+    }
+
+    /// The name of the intrinsic for a gate that can be produced by merging two other gates.
+    fn merged_name(self) -> &'static str {
+        match self {
+            Self::Z => "__quantum__qis__z__body",
+            Self::S => "__quantum__qis__s__body",
+            Self::SAdj => "__quantum__qis__s__adj",
+            _ => panic!("{self:?} is not produced by merging gates"),
+        }
+    }
+
+    fn num_qubits(self) -> usize {
+        match self {
+            Self::H
+            | Self::X
+            | Self::Y
+            | Self::Z
+            | Self::S
+            | Self::SAdj
+            | Self::T
+            | Self::TAdj
+            | Self::Rx
+            | Self::Ry
+            | Self::Rz => 1,
+            Self::Cx | Self::Cy | Self::Cz | Self::Swap | Self::Rxx | Self::Ryy | Self::Rzz => 2,
+            Self::Ccx => 3,
+        }
+    }
+
+    fn is_rotation(self) -> bool {
+        matches!(
+            self,
+            Self::Rx | Self::Ry | Self::Rz | Self::Rxx | Self::Ryy | Self::Rzz
+        )
+    }
+
+    /// Whether the gate acts the same regardless of the order of its qubits.
+    fn is_symmetric(self) -> bool {
+        matches!(
+            self,
+            Self::Cz | Self::Swap | Self::Rxx | Self::Ryy | Self::Rzz
+        )
+    }
+
+    fn is_inverse_of(self, other: Self) -> bool {
+        match self {
+            Self::H
+            | Self::X
+            | Self::Y
+            | Self::Z
+            | Self::Cx
+            | Self::Cy
+            | Self::Cz
+            | Self::Swap
+            | Self::Ccx => self == other,
+            Self::S => other == Self::SAdj,
+            Self::SAdj => other == Self::S,
+            Self::T => other == Self::TAdj,
+            Self::TAdj => other == Self::T,
+            Self::Rx | Self::Ry | Self::Rz | Self::Rxx | Self::Ryy | Self::Rzz => false,
+        }
+    }
+
+    /// The single gate equivalent to applying this gate twice, if there is one.
+    fn squared(self) -> Option<Self> {
+        match self {
+            Self::S | Self::SAdj => Some(Self::Z),
+            Self::T => Some(Self::S),
+            Self::TAdj => Some(Self::SAdj),
+            _ => None,
+        }
+    }
+}
+
+/// A recognized gate call, with its angle if it is a rotation by a known angle.
+#[derive(Clone, Debug)]
+struct GateCall {
+    gate: Gate,
+    angle: Option<f64>,
+    qubits: Vec<u32>,
+}
+
+impl GateCall {
+    fn acts_on_same_qubits(&self, other: &GateCall) -> bool {
+        if self.gate.is_symmetric() {
+            let mut lhs = self.qubits.clone();
+            let mut rhs = other.qubits.clone();
+            lhs.sort_unstable();
+            rhs.sort_unstable();
+            lhs == rhs
+        } else {
+            self.qubits == other.qubits
+        }
+    }
+}
+
+/// How an instruction interacts with the gates around it.
+enum Effect {
+    /// A recognized gate that may be cancelled or merged.
+    Gate(GateCall),
+    /// A call that acts on the given qubits in an unknown way, so no gate on those qubits can be moved across it.
+    Barrier(Vec<u32>),
+    /// A call that may act on any qubit, so no gate can be moved across it.
+    FullBarrier,
+    /// An instruction that does not act on qubits.
+    None,
+}
+
+enum Combination {
+    /// The gates cancel each other out.
+    Cancel,
+    /// The gates can be replaced by the given gate.
+    Merge(Gate, Option<f64>),
+    /// The gates cannot be combined.
+    None,
+}
+
+/// Cancel and merge gates within each block of the program. Calls to intrinsics are recognized by the names of their
+/// callables. Gates are only combined when every gate between them acts on other qubits, so that they commute.
+/// Pairs of gates that are inverses of each other are removed, `s` and `t` gates applied twice are replaced by
+/// the equivalent single gate, and consecutive rotations around the same axis by known angles are merged into
+/// one rotation by the sum of the angles.
+/// Declarations of intrinsics that are no longer called are removed.
+/// The pass only runs for `OptimizationLevel::Full`. Returns statistics about the gates removed.
+pub fn optimize_gates(program: &mut Program) -> GateOptimizationStats {
+    if program.config.optimization_level != OptimizationLevel::Full {
+        return GateOptimizationStats::default();
+    }
+    let mut pass = GateOptimizationPass {
+        gates: program
+            .callables
+            .iter()
+            .filter_map(|(id, callable)| {
+                let gate = Gate::from_name(&callable.name)?;
+                (callable.body.is_none() && callable.output_type.is_none()).then_some((id, gate))
+            })
+            .collect(),
+        stats: GateOptimizationStats::default(),
+    };
+    if pass.gates.is_empty() {
+        return pass.stats;
+    }
+
+    for block_id in program.blocks.iter().map(|(id, _)| id).collect::<Vec<_>>() {
+        pass.optimize_block(program, block_id);
+    }
+
+    if pass.stats != GateOptimizationStats::default() {
+        remove_unused_intrinsics(program);
+    }
+    pass.stats
+}
+
+struct GateOptimizationPass {
+    gates: FxHashMap<CallableId, Gate>,
+    stats: GateOptimizationStats,
+}
+
+/// The state of a single block during the pass.
+struct BlockState {
+    instrs: Vec<Option<Instruction>>,
+    gate_calls: Vec<Option<GateCall>>,
+    /// For each qubit, the indices of the instructions still in the block that act on it, in order.
+    qubit_instrs: FxHashMap<u32, Vec<usize>>,
+}
+
+impl GateOptimizationPass {
+    fn optimize_block(&mut self, program: &mut Program, block_id: BlockId) {
+        let instrs = std::mem::take(&mut program.get_block_mut(block_id).0);
+        let mut state = BlockState {
+            gate_calls: vec![None; instrs.len()],
+            instrs: instrs.into_iter().map(Some).collect(),
+            qubit_instrs: FxHashMap::default(),
+        };
+
+        for idx in 0..state.instrs.len() {
+            let effect = self.get_effect(
+                program,
+                state.instrs[idx]
+                    .as_ref()
+                    .expect("instruction should not be removed before it is visited"),
+            );
+            match effect {
+                Effect::Gate(gate_call) => {
+                    state.gate_calls[idx] = Some(gate_call);
+                    self.place_gate(program, &mut state, idx);
+                }
+                Effect::Barrier(qubits) => {
+                    for qubit in qubits {
+                        state.qubit_instrs.entry(qubit).or_default().push(idx);
+                    }
+                }
+                Effect::FullBarrier => state.qubit_instrs.clear(),
+                Effect::None => {}
+            }
+        }
+
+        program.get_block_mut(block_id).0 = state.instrs.into_iter().flatten().collect();
+    }
+
+    fn get_effect(&self, program: &Program, instr: &Instruction) -> Effect {
+        let Instruction::Call(callable_id, args, _) = instr else {
+            return Effect::None;
+        };
+        if program.get_callable(*callable_id).body.is_some() {
+            return Effect::FullBarrier;
+        }
+
+        let mut qubits = Vec::new();
+        for arg in args {
+            match arg {
+                Operand::Literal(Literal::Qubit(q)) => qubits.push(*q),
+                // The qubit is not known, so it may be any qubit.
+                Operand::Variable(var) if var.ty == Ty::Qubit => return Effect::FullBarrier,
+                _ => {}
+            }
+        }
+
+        let Some(gate) = self.gates.get(callable_id).copied() else {
+            return Effect::Barrier(qubits);
+        };
+        let angle = match args.first() {
+            Some(Operand::Literal(Literal::Double(angle))) if gate.is_rotation() => Some(*angle),
+            _ => None,
+        };
+        if qubits.len() != gate.num_qubits() {
+            return Effect::Barrier(qubits);
+        }
+        Effect::Gate(GateCall {
+            gate,
+            angle,
+            qubits,
+        })
+    }
+
+    /// Combines the gate at the given index with the gate before it on the same qubits for as long as possible.
+    fn place_gate(&mut self, program: &mut Program, state: &mut BlockState, idx: usize) {
+        let mut idx = idx;
+        loop {
+            let gate_call = state.gate_calls[idx]
+                .as_ref()
+                .expect("instruction should be a gate call");
+            let Some(prev_idx) = get_previous_gate(state, gate_call) else {
+                break;
+            };
+            let prev_gate_call = state.gate_calls[prev_idx]
+                .as_ref()
+                .expect("previous instruction should be a gate call");
+
+            match combine(prev_gate_call, gate_call) {
+                Combination::Cancel => {
+                    self.count_removed(gate_call, 2);
+                    for qubit in &gate_call.qubits {
+                        state.qubit_instrs.entry(*qubit).or_default().pop();
+                    }
+                    state.instrs[prev_idx] = None;
+                    state.instrs[idx] = None;
+                    return;
+                }
+                Combination::Merge(gate, angle) => {
+                    self.count_removed(gate_call, 1);
+                    self.stats.merged_gates += 1;
+                    for qubit in &gate_call.qubits {
+                        state.qubit_instrs.entry(*qubit).or_default().pop();
+                    }
+                    let qubits = prev_gate_call.qubits.clone();
+                    state.instrs[prev_idx] = Some(self.build_call(program, gate, angle, &qubits));
+                    state.gate_calls[prev_idx] = Some(GateCall {
+                        gate,
+                        angle,
+                        qubits,
+                    });
+                    state.instrs[idx] = None;
+
+                    // The merged gate may in turn combine with the gate before it.
+                    idx = prev_idx;
+                }
+                Combination::None => break,
+            }
+        }
+
+        let gate_call = state.gate_calls[idx]
+            .as_ref()
+            .expect("instruction should be a gate call");
+        for qubit in &gate_call.qubits {
+            state.qubit_instrs.entry(*qubit).or_default().push(idx);
+        }
+    }
+
+    fn count_removed(&mut self, gate_call: &GateCall, count: usize) {
+        if gate_call.qubits.len() == 1 {
+            self.stats.removed_single_qubit_gates += count;
+        } else {
+            self.stats.removed_multi_qubit_gates += count;
+        }
+    }
+
+    fn build_call(
+        &mut self,
+        program: &mut Program,
+        gate: Gate,
+        angle: Option<f64>,
+        qubits: &[u32],
+    ) -> Instruction {
+        let callable_id = if let Some((id, _)) = self.gates.iter().find(|(_, g)| **g == gate) {
+            *id
+        } else {
+            let id = add_intrinsic(program, gate.merged_name());
+            self.gates.insert(id, gate);
+            id
+        };
+        let mut args = Vec::new();
+        if let Some(angle) = angle {
+            args.push(Operand::Literal(Literal::Double(angle)));
+        }
+        args.extend(qubits.iter().map(|q| Operand::Literal(Literal::Qubit(*q))));
+        Instruction::Call(callable_id, args, None)
+    }
+}
+
+/// Returns the index of the instruction before the gate if it is the last instruction on every qubit the gate acts on.
+fn get_previous_gate(state: &BlockState, gate_call: &GateCall) -> Option<usize> {
+    let mut prev_idx = None;
+    for qubit in &gate_call.qubits {
+        let last = *state.qubit_instrs.get(qubit)?.last()?;
+        if prev_idx.is_some_and(|idx| idx != last) {
+            return None;
+        }
+        prev_idx = Some(last);
+    }
+    let prev_idx = prev_idx?;
+    let prev_gate_call = state.gate_calls[prev_idx].as_ref()?;
+    prev_gate_call
+        .acts_on_same_qubits(gate_call)
+        .then_some(prev_idx)
+}
+
+fn combine(prev: &GateCall, next: &GateCall) -> Combination {
+    if prev.gate.is_inverse_of(next.gate) {
+        return Combination::Cancel;
+    }
+    if prev.gate != next.gate {
+        return Combination::None;
+    }
+    if prev.gate.is_rotation() {
+        return match (prev.angle, next.angle) {
+            (Some(prev_angle), Some(next_angle)) => {
+                let angle = prev_angle + next_angle;
+                if angle == 0.0 {
+                    Combination::Cancel
+                } else {
+                    Combination::Merge(prev.gate, Some(angle))
+                }
+            }
+            _ => Combination::None,
+        };
+    }
+    match prev.gate.squared() {
+        Some(gate) => Combination::Merge(gate, None),
+        None => Combination::None,
+    }
+}
+
+fn add_intrinsic(program: &mut Program, name: &str) -> CallableId {
+    let id = CallableId(
+        program
+            .callables
+            .iter()
+            .map(|(id, _)| id.0 + 1)
+            .max()
+            .unwrap_or_default(),
+    );
+    program.callables.insert(
+        id,
+        Callable {
+            name: name.to_string(),
+            input_type: vec![Ty::Qubit],
+            params: Vec::new(),
+            output_type: None,
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+    id
+}
+
+fn remove_unused_intrinsics(program: &mut Program) {
+    let mut used = FxHashSet::default();
+    used.insert(program.entry);
+    for block in program.blocks.values() {
+        for instr in &block.0 {
+            if let Instruction::Call(callable_id, _, _) = instr {
+                used.insert(*callable_id);
+            }
+        }
+    }
+    let unused = program
+        .callables
+        .iter()
+        .filter(|(id, callable)| {
+            !used.contains(id)
+                && callable.body.is_none()
+                && Gate::from_name(&callable.name).is_some()
+        })
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    for id in unused {
+        program.callables.remove(id);
+    }
+}
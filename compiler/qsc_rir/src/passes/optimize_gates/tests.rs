@@ -0,0 +1,304 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::too_many_lines, clippy::needless_raw_string_hashes)]
+
+use expect_test::expect;
+
+use crate::{
+    builder::{cx_decl, h_decl, mz_decl, new_program, rx_decl, x_decl},
+    passes::optimize_gates::{optimize_gates, GateOptimizationStats},
+    rir::{
+        Block, BlockId, Callable, CallableId, CallableType, Instruction, Literal, Operand,
+        OptimizationLevel, Program, Ty,
+    },
+};
+
+fn qubit(q: u32) -> Operand {
+    Operand::Literal(Literal::Qubit(q))
+}
+
+fn new_full_program() -> Program {
+    let mut program = new_program();
+    program.config.optimization_level = OptimizationLevel::Full;
+    program
+}
+
+fn s_decl() -> Callable {
+    Callable {
+        name: "__quantum__qis__s__body".to_string(),
+        input_type: vec![Ty::Qubit],
+        params: Vec::new(),
+        output_type: None,
+        body: None,
+        call_type: CallableType::Regular,
+    }
+}
+
+#[test]
+fn optimize_gates_cancels_self_inverse_gates_across_gates_on_other_qubits() {
+    let mut program = new_full_program();
+    program.callables.insert(CallableId(1), h_decl());
+    program.callables.insert(CallableId(2), x_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Call(CallableId(2), vec![qubit(1)], None),
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Return,
+        ]),
+    );
+
+    let stats = optimize_gates(&mut program);
+    assert_eq!(
+        stats,
+        GateOptimizationStats {
+            removed_single_qubit_gates: 2,
+            removed_multi_qubit_gates: 0,
+            merged_gates: 0,
+        }
+    );
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 2: Callable:
+                    name: __quantum__qis__x__body
+                    call_type: Regular
+                    input_type:
+                        [0]: Qubit
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Call id(2), args( Qubit(1), )
+                    Return
+            config: Config:
+                capabilities: Base
+                optimization_level: Full
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
+
+#[test]
+fn optimize_gates_cancels_nested_pairs_of_gates() {
+    let mut program = new_full_program();
+    program.callables.insert(CallableId(1), x_decl());
+    program.callables.insert(CallableId(2), cx_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Call(CallableId(2), vec![qubit(0), qubit(1)], None),
+            Instruction::Call(CallableId(2), vec![qubit(0), qubit(1)], None),
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Return,
+        ]),
+    );
+
+    let stats = optimize_gates(&mut program);
+    assert_eq!(
+        stats,
+        GateOptimizationStats {
+            removed_single_qubit_gates: 2,
+            removed_multi_qubit_gates: 2,
+            merged_gates: 0,
+        }
+    );
+    expect![[r#"
+        Block:
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+}
+
+#[test]
+fn optimize_gates_does_not_cancel_gates_across_gates_on_shared_qubits() {
+    let mut program = new_full_program();
+    program.callables.insert(CallableId(1), h_decl());
+    program.callables.insert(CallableId(2), cx_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(2), vec![qubit(0), qubit(1)], None),
+            Instruction::Call(CallableId(1), vec![qubit(1)], None),
+            Instruction::Call(CallableId(2), vec![qubit(0), qubit(1)], None),
+            Instruction::Call(CallableId(2), vec![qubit(1), qubit(0)], None),
+            Instruction::Return,
+        ]),
+    );
+
+    let stats = optimize_gates(&mut program);
+    assert_eq!(stats, GateOptimizationStats::default());
+    expect![[r#"
+        Block:
+            Call id(2), args( Qubit(0), Qubit(1), )
+            Call id(1), args( Qubit(1), )
+            Call id(2), args( Qubit(0), Qubit(1), )
+            Call id(2), args( Qubit(1), Qubit(0), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+}
+
+#[test]
+fn optimize_gates_does_not_cancel_gates_across_measurement() {
+    let mut program = new_full_program();
+    program.callables.insert(CallableId(1), h_decl());
+    program.callables.insert(CallableId(2), mz_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Call(
+                CallableId(2),
+                vec![qubit(0), Operand::Literal(Literal::Result(0))],
+                None,
+            ),
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Return,
+        ]),
+    );
+
+    let stats = optimize_gates(&mut program);
+    assert_eq!(stats, GateOptimizationStats::default());
+    expect![[r#"
+        Block:
+            Call id(1), args( Qubit(0), )
+            Call id(2), args( Qubit(0), Result(0), )
+            Call id(1), args( Qubit(0), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+}
+
+#[test]
+fn optimize_gates_merges_s_gates_into_z_and_cancels_result() {
+    let mut program = new_full_program();
+    program.callables.insert(CallableId(1), s_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Call(CallableId(1), vec![qubit(1)], None),
+            Instruction::Call(CallableId(1), vec![qubit(1)], None),
+            Instruction::Call(CallableId(1), vec![qubit(1)], None),
+            Instruction::Call(CallableId(1), vec![qubit(1)], None),
+            Instruction::Return,
+        ]),
+    );
+
+    let stats = optimize_gates(&mut program);
+    assert_eq!(
+        stats,
+        GateOptimizationStats {
+            removed_single_qubit_gates: 5,
+            removed_multi_qubit_gates: 0,
+            merged_gates: 3,
+        }
+    );
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 2: Callable:
+                    name: __quantum__qis__z__body
+                    call_type: Regular
+                    input_type:
+                        [0]: Qubit
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Call id(2), args( Qubit(0), )
+                    Return
+            config: Config:
+                capabilities: Base
+                optimization_level: Full
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
+
+#[test]
+fn optimize_gates_merges_rotations_with_known_angles() {
+    let mut program = new_full_program();
+    program.callables.insert(CallableId(1), rx_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Double(1.0)), qubit(0)],
+                None,
+            ),
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Double(2.0)), qubit(0)],
+                None,
+            ),
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Double(1.5)), qubit(1)],
+                None,
+            ),
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Double(-1.5)), qubit(1)],
+                None,
+            ),
+            Instruction::Return,
+        ]),
+    );
+
+    let stats = optimize_gates(&mut program);
+    assert_eq!(
+        stats,
+        GateOptimizationStats {
+            removed_single_qubit_gates: 3,
+            removed_multi_qubit_gates: 0,
+            merged_gates: 1,
+        }
+    );
+    expect![[r#"
+        Block:
+            Call id(1), args( Double(3), Qubit(0), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+}
+
+#[test]
+fn optimize_gates_leaves_program_unchanged_below_full_optimization_level() {
+    let mut program = new_program();
+    program.config.optimization_level = OptimizationLevel::Basic;
+    program.callables.insert(CallableId(1), x_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Call(CallableId(1), vec![qubit(0)], None),
+            Instruction::Return,
+        ]),
+    );
+
+    let stats = optimize_gates(&mut program);
+    assert_eq!(stats, GateOptimizationStats::default());
+    expect![[r#"
+        Block:
+            Call id(1), args( Qubit(0), )
+            Call id(1), args( Qubit(0), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+}
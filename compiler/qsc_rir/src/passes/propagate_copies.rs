@@ -0,0 +1,83 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    rir::{Instruction, Operand, Program, VariableId},
+    utils::{get_assigned_variable, replace_variable_uses},
+};
+
+/// Propagate copies of variables to their uses.
+/// Stores are already propagated by the transformation to SSA form, so the remaining copies are phi nodes whose
+/// arguments, ignoring references to the phi node itself, are all the same variable.
+/// Returns true if the program was changed.
+/// The program is expected to be in SSA form.
+pub fn propagate_copies(program: &mut Program) -> bool {
+    let mut copies = FxHashMap::default();
+    for block in program.blocks.values() {
+        for instr in &block.0 {
+            if let Some((variable_id, copied)) = get_copy(instr) {
+                copies.insert(variable_id, copied);
+            }
+        }
+    }
+    if copies.is_empty() {
+        return false;
+    }
+
+    // A copy may be of another copy, so resolve each copy to the operand it ultimately copies.
+    let resolved = copies
+        .keys()
+        .filter_map(|variable_id| Some((*variable_id, resolve_copy(&copies, *variable_id)?)))
+        .collect::<FxHashMap<_, _>>();
+    if resolved.is_empty() {
+        return false;
+    }
+
+    replace_variable_uses(program, &resolved);
+    for block in program.blocks.values_mut() {
+        block.0.retain(|instr| {
+            get_assigned_variable(instr).is_none_or(|var| !resolved.contains_key(&var.variable_id))
+        });
+    }
+    true
+}
+
+fn get_copy(instr: &Instruction) -> Option<(VariableId, Operand)> {
+    let Instruction::Phi(args, var) = instr else {
+        return None;
+    };
+    let mut args = args
+        .iter()
+        .map(|(arg, _)| *arg)
+        .filter(|arg| *arg != Operand::Variable(*var));
+    let first @ Operand::Variable(_) = args.next()? else {
+        return None;
+    };
+    if args.any(|arg| arg != first) {
+        return None;
+    }
+    Some((var.variable_id, first))
+}
+
+/// Follows a chain of copies to the operand it starts from. Copies that form a cycle have no such operand.
+fn resolve_copy(
+    copies: &FxHashMap<VariableId, Operand>,
+    variable_id: VariableId,
+) -> Option<Operand> {
+    let mut operand = copies[&variable_id];
+    for _ in 0..copies.len() {
+        match operand {
+            Operand::Variable(var) if var.variable_id == variable_id => return None,
+            Operand::Variable(var) if copies.contains_key(&var.variable_id) => {
+                operand = copies[&var.variable_id];
+            }
+            _ => return Some(operand),
+        }
+    }
+    None
+}
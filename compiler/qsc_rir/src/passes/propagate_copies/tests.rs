@@ -0,0 +1,145 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::too_many_lines, clippy::needless_raw_string_hashes)]
+
+use expect_test::expect;
+
+use crate::{
+    builder::new_program,
+    passes::propagate_copies::propagate_copies,
+    rir::{
+        Block, BlockId, Callable, CallableId, CallableType, ConditionCode, Instruction, Literal,
+        Operand, Program, Ty, Variable, VariableId,
+    },
+};
+
+fn int_var(id: u32) -> Variable {
+    Variable {
+        variable_id: VariableId(id),
+        ty: Ty::Integer,
+    }
+}
+
+fn bool_var(id: u32) -> Variable {
+    Variable {
+        variable_id: VariableId(id),
+        ty: Ty::Boolean,
+    }
+}
+
+fn add_decls(program: &mut Program) {
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "dynamic_int".to_string(),
+            input_type: Vec::new(),
+            params: Vec::new(),
+            output_type: Some(Ty::Integer),
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+    program.callables.insert(
+        CallableId(2),
+        Callable {
+            name: "record_int".to_string(),
+            input_type: vec![Ty::Integer],
+            params: Vec::new(),
+            output_type: None,
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+}
+
+#[test]
+fn propagate_copies_replaces_uses_of_phi_with_single_incoming_variable() {
+    let mut program = new_program();
+    add_decls(&mut program);
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(int_var(0))),
+            Instruction::Icmp(
+                ConditionCode::Eq,
+                Operand::Variable(int_var(0)),
+                Operand::Literal(Literal::Integer(0)),
+                bool_var(1),
+            ),
+            Instruction::Branch(bool_var(1), BlockId(1), BlockId(2)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(1), Block(vec![Instruction::Jump(BlockId(2))]));
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![
+            Instruction::Phi(
+                vec![
+                    (Operand::Variable(int_var(0)), BlockId(1)),
+                    (Operand::Variable(int_var(0)), BlockId(0)),
+                ],
+                int_var(2),
+            ),
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var(2))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    assert!(propagate_copies(&mut program));
+    expect![[r#"
+        Block:
+            Call id(2), args( Variable(0, Integer), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(2)).to_string());
+
+    assert!(!propagate_copies(&mut program));
+}
+
+#[test]
+fn propagate_copies_resolves_loop_phi_referencing_itself() {
+    let mut program = new_program();
+    add_decls(&mut program);
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(int_var(0))),
+            Instruction::Jump(BlockId(1)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Phi(
+                vec![
+                    (Operand::Variable(int_var(0)), BlockId(0)),
+                    (Operand::Variable(int_var(1)), BlockId(1)),
+                ],
+                int_var(1),
+            ),
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var(1))], None),
+            Instruction::Call(CallableId(1), Vec::new(), Some(int_var(2))),
+            Instruction::Icmp(
+                ConditionCode::Eq,
+                Operand::Variable(int_var(2)),
+                Operand::Literal(Literal::Integer(0)),
+                bool_var(3),
+            ),
+            Instruction::Branch(bool_var(3), BlockId(1), BlockId(2)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(2), Block(vec![Instruction::Return]));
+
+    assert!(propagate_copies(&mut program));
+    expect![[r#"
+        Block:
+            Call id(2), args( Variable(0, Integer), )
+            Variable(2, Integer) = Call id(1), args( )
+            Variable(3, Boolean) = Icmp Eq, Variable(2, Integer), Integer(0)
+            Branch Variable(3, Boolean), 1, 2"#]]
+    .assert_eq(&program.get_block(BlockId(1)).to_string());
+}
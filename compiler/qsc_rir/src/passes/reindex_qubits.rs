@@ -31,6 +31,10 @@ struct BlockQubitMap {
 /// The pass will panic if the input program violates any of these assumptions.
 pub fn reindex_qubits(program: &mut Program) {
     validate_assumptions(program);
+    if program.num_qubits == 0 {
+        // A purely classical program has no qubits to reindex.
+        return;
+    }
 
     let (used_mz, mz_id) = match find_callable(program, "__quantum__qis__mz__body") {
         Some(id) => (true, id),
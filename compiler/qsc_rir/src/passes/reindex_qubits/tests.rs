@@ -902,3 +902,19 @@ fn qubit_reindex_fails_across_branches_with_one_branch_longer_different_usage_in
     // After
     reindex_qubits(&mut program);
 }
+
+#[test]
+fn program_without_qubits_is_unchanged() {
+    let mut program = Program::new();
+    program
+        .blocks
+        .insert(BlockId(0), Block(vec![Instruction::Return]));
+
+    reindex_qubits(&mut program);
+    expect![[r#"
+        Block:
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+    assert_eq!(program.num_qubits, 0);
+    assert!(program.callables.is_empty());
+}
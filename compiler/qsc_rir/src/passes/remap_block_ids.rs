@@ -3,7 +3,7 @@
 
 use std::collections::VecDeque;
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
     rir::{BlockId, Instruction, Program},
@@ -16,34 +16,36 @@ mod tests;
 /// Remaps block IDs in the given program to be contiguous, starting from 0,
 /// and in a topological ordering if the program is Directed Acyclic Graph (DAG).
 /// Toplogical ordering is useful for passes that assume each block's successors
-/// have higher IDs than the block itself. This is best effort; if the program has a cycle,
-/// the function will still remap block IDs but the ordering may not be topological.
+/// have higher IDs than the block itself. If the program has a cycle, the blocks are
+/// remapped in reverse postorder instead, where the only successors with lower or equal IDs
+/// than a block are the headers of the loops it belongs to.
+/// The blocks of the entry point come first, followed by the blocks of each other callable with a body, so the
+/// entry block of every callable has a lower ID than the rest of its blocks.
 pub fn remap_block_ids(program: &mut Program) {
-    // Check if the program is acyclic, which lets us construct a topological ordering.
-    let is_acyclic = check_acyclic(program);
-
-    // Only update the entry point.
     let entry_block_id = program
         .get_callable(program.entry)
         .body
         .expect("entry point should have a body block");
+    let mut body_block_ids = vec![(program.entry, entry_block_id)];
+    body_block_ids.extend(
+        program
+            .callables
+            .iter()
+            .filter_map(|(callable_id, callable)| match callable.body {
+                Some(block_id) if callable_id != program.entry => Some((callable_id, block_id)),
+                _ => None,
+            }),
+    );
 
-    // Because we know the program is acyclic, we can keep a list as the map from old block IDs to new block IDs, where
-    // the new block ID is the index in the list.
+    // Keep a list as the map from old block IDs to new block IDs, where the new block ID is the index in the list.
+    let is_acyclic = check_acyclic(program);
     let mut block_id_map = Vec::new();
-    let mut blocks_to_visit: VecDeque<BlockId> = vec![entry_block_id].into();
-    while let Some(block_id) = blocks_to_visit.pop_front() {
-        // If we've already visited this block, remove it from the previous ordering so that we can insert it at the end.
-        // This effectively remaps all the blocks in the list and updates the mapped id of the current block.
-        // This is only safe without cycles, so on a cyclic graph the node is skipped and not remapped.
+    for (_, body_block_id) in &body_block_ids {
         if is_acyclic {
-            block_id_map.retain_mut(|id| *id != block_id);
-        } else if block_id_map.contains(&block_id) {
-            continue;
+            block_id_map.extend(get_topological_order(program, *body_block_id));
+        } else {
+            block_id_map.extend(get_reverse_postorder(program, *body_block_id));
         }
-        block_id_map.push(block_id);
-
-        blocks_to_visit.extend(get_block_successors(program.get_block(block_id)));
     }
 
     let block_id_map = block_id_map
@@ -65,11 +67,13 @@ pub fn remap_block_ids(program: &mut Program) {
         );
         program.blocks.insert(new_block_id.into(), block);
     }
-    program
-        .callables
-        .get_mut(program.entry)
-        .expect("entry should exist")
-        .body = Some(block_id_map[&entry_block_id].into());
+    for (callable_id, body_block_id) in body_block_ids {
+        program
+            .callables
+            .get_mut(callable_id)
+            .expect("callable should exist")
+            .body = Some(block_id_map[&body_block_id].into());
+    }
 }
 
 fn check_acyclic(program: &Program) -> bool {
@@ -81,6 +85,46 @@ fn check_acyclic(program: &Program) -> bool {
     true
 }
 
+fn get_topological_order(program: &Program, entry_block_id: BlockId) -> Vec<BlockId> {
+    let mut order = Vec::new();
+    let mut blocks_to_visit: VecDeque<BlockId> = vec![entry_block_id].into();
+    while let Some(block_id) = blocks_to_visit.pop_front() {
+        // If we've already visited this block, remove it from the previous ordering so that we can insert it at the end.
+        // This effectively remaps all the blocks in the list and updates the mapped id of the current block.
+        // This is only safe without cycles.
+        order.retain_mut(|id| *id != block_id);
+        order.push(block_id);
+
+        blocks_to_visit.extend(get_block_successors(program.get_block(block_id)));
+    }
+    order
+}
+
+fn get_reverse_postorder(program: &Program, entry_block_id: BlockId) -> Vec<BlockId> {
+    // Do a depth-first traversal that visits the successors of a block starting from the last one, so that the first
+    // successor comes right after the block once the postorder is reversed.
+    let mut postorder = Vec::new();
+    let mut visited = FxHashSet::default();
+    visited.insert(entry_block_id);
+    let mut stack = vec![(
+        entry_block_id,
+        get_block_successors(program.get_block(entry_block_id)),
+    )];
+    while let Some((block_id, successors)) = stack.last_mut() {
+        if let Some(successor_id) = successors.pop() {
+            if visited.insert(successor_id) {
+                let successor_successors = get_block_successors(program.get_block(successor_id));
+                stack.push((successor_id, successor_successors));
+            }
+        } else {
+            postorder.push(*block_id);
+            stack.pop();
+        }
+    }
+    postorder.reverse();
+    postorder
+}
+
 fn update_phi_nodes(block_id_map: &FxHashMap<BlockId, usize>, instrs: &mut [Instruction]) {
     for instr in instrs.iter_mut() {
         if let Instruction::Phi(args, _) = instr {
@@ -20,6 +20,7 @@ fn remap_block_ids_no_changes() {
         Callable {
             name: "main".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -92,6 +93,7 @@ fn remap_block_ids_out_of_order_no_branches() {
         Callable {
             name: "main".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(5)),
             call_type: CallableType::Regular,
@@ -164,6 +166,7 @@ fn remap_block_ids_out_of_order_with_one_branch() {
         Callable {
             name: "main".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(2)),
             call_type: CallableType::Regular,
@@ -252,6 +255,7 @@ fn remap_block_ids_simple_loop() {
         Callable {
             name: "main".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(4)),
             call_type: CallableType::Regular,
@@ -333,6 +337,7 @@ fn remap_block_ids_infinite_loop() {
         Callable {
             name: "main".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(4)),
             call_type: CallableType::Regular,
@@ -399,6 +404,7 @@ fn remap_block_ids_nested_branching_loops() {
         Callable {
             name: "main".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(4)),
             call_type: CallableType::Regular,
@@ -488,6 +494,7 @@ fn remap_block_ids_ensures_acyclic_program_gets_topological_ordering() {
         Callable {
             name: "main".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(4)),
             call_type: CallableType::Regular,
@@ -619,3 +626,94 @@ fn remap_block_ids_ensures_acyclic_program_gets_topological_ordering() {
             num_results: 0"#]]
     .assert_eq(&program.to_string());
 }
+
+#[test]
+fn remap_block_ids_loop_with_uneven_branches_gets_reverse_postorder() {
+    let mut program = Program::new();
+    program.callables.insert(
+        CallableId(0),
+        Callable {
+            name: "main".to_string(),
+            input_type: Vec::new(),
+            params: Vec::new(),
+            output_type: None,
+            body: Some(BlockId(9)),
+            call_type: CallableType::Regular,
+        },
+    );
+    program
+        .blocks
+        .insert(BlockId(9), Block(vec![Instruction::Jump(BlockId(7))]));
+    program.blocks.insert(
+        BlockId(7),
+        Block(vec![Instruction::Branch(
+            Variable {
+                variable_id: VariableId(0),
+                ty: Ty::Boolean,
+            },
+            BlockId(5),
+            BlockId(1),
+        )]),
+    );
+    program.blocks.insert(
+        BlockId(5),
+        Block(vec![Instruction::Branch(
+            Variable {
+                variable_id: VariableId(1),
+                ty: Ty::Boolean,
+            },
+            BlockId(3),
+            BlockId(4),
+        )]),
+    );
+    program
+        .blocks
+        .insert(BlockId(3), Block(vec![Instruction::Jump(BlockId(8))]));
+    program
+        .blocks
+        .insert(BlockId(4), Block(vec![Instruction::Jump(BlockId(2))]));
+    program
+        .blocks
+        .insert(BlockId(2), Block(vec![Instruction::Jump(BlockId(8))]));
+    program
+        .blocks
+        .insert(BlockId(8), Block(vec![Instruction::Jump(BlockId(7))]));
+    program
+        .blocks
+        .insert(BlockId(1), Block(vec![Instruction::Return]));
+
+    // After
+    remap_block_ids(&mut program);
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+            blocks:
+                Block 0: Block:
+                    Jump(1)
+                Block 1: Block:
+                    Branch Variable(0, Boolean), 2, 7
+                Block 2: Block:
+                    Branch Variable(1, Boolean), 3, 4
+                Block 3: Block:
+                    Jump(6)
+                Block 4: Block:
+                    Jump(5)
+                Block 5: Block:
+                    Jump(6)
+                Block 6: Block:
+                    Jump(1)
+                Block 7: Block:
+                    Return
+            config: Config:
+                capabilities: Base
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
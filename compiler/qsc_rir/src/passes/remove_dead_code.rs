@@ -0,0 +1,138 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use rustc_hash::FxHashSet;
+use std::iter::once;
+
+use crate::{
+    rir::{BlockId, CallableId, Instruction, Operand, Program, VariableId},
+    utils::{
+        get_all_block_successors, get_assigned_variable, get_operands, get_variable_assignments,
+    },
+};
+
+/// Remove code that has no effect on the program: callables that are no longer called from the entry point, blocks
+/// that cannot be reached from any callable, and instructions other than calls that assign variables which are never
+/// used, including phi nodes that are only used by each other.
+/// Returns true if the program was changed.
+/// The program is expected to be in SSA form.
+pub fn remove_dead_code(program: &mut Program) -> bool {
+    let mut changed = remove_unreachable_callables(program);
+    changed |= remove_unreachable_blocks(program);
+    remove_unused_variables(program) || changed
+}
+
+// Folding a branch can remove the only calls to a callable, which must then be removed too, along with its body.
+fn remove_unreachable_callables(program: &mut Program) -> bool {
+    let mut reachable = FxHashSet::default();
+    let mut to_visit = vec![program.entry];
+    while let Some(callable_id) = to_visit.pop() {
+        if !reachable.insert(callable_id) {
+            continue;
+        }
+        let Some(body) = program.get_callable(callable_id).body else {
+            continue;
+        };
+        for block_id in once(body).chain(get_all_block_successors(body, program)) {
+            for instr in &program.get_block(block_id).0 {
+                if let Instruction::Call(callable_id, ..) = instr {
+                    to_visit.push(*callable_id);
+                }
+            }
+        }
+    }
+
+    let unreachable = program
+        .callables
+        .iter()
+        .map(|(callable_id, _)| callable_id)
+        .filter(|callable_id| !reachable.contains(callable_id))
+        .collect::<Vec<CallableId>>();
+    for callable_id in &unreachable {
+        program.callables.remove(*callable_id);
+    }
+    !unreachable.is_empty()
+}
+
+fn remove_unreachable_blocks(program: &mut Program) -> bool {
+    let mut reachable = FxHashSet::default();
+    for (_, callable) in program.callables.iter() {
+        if let Some(entry_id) = callable.body {
+            reachable.insert(entry_id);
+            reachable.extend(get_all_block_successors(entry_id, program));
+        }
+    }
+
+    let unreachable = program
+        .blocks
+        .iter()
+        .map(|(block_id, _)| block_id)
+        .filter(|block_id| !reachable.contains(block_id))
+        .collect::<Vec<BlockId>>();
+    if unreachable.is_empty() {
+        return false;
+    }
+    for block_id in unreachable {
+        program.blocks.remove(block_id);
+    }
+
+    // Phi nodes can no longer be reached from the removed blocks.
+    for block in program.blocks.values_mut() {
+        for instr in &mut block.0 {
+            if let Instruction::Phi(args, _) = instr {
+                args.retain(|(_, pred)| reachable.contains(pred));
+            }
+        }
+    }
+    true
+}
+
+fn remove_unused_variables(program: &mut Program) -> bool {
+    // Calls may have side effects and branches determine control flow, so the variables they use are live.
+    // Any variable used to compute a live variable is also live.
+    let assignments = get_variable_assignments(program);
+    let mut live = FxHashSet::default();
+    let mut to_visit = Vec::new();
+    for block in program.blocks.values() {
+        for instr in &block.0 {
+            match instr {
+                Instruction::Call(..) => to_visit.extend(get_used_variables(instr)),
+                Instruction::Branch(cond, _, _) => to_visit.push(cond.variable_id),
+                _ => {}
+            }
+        }
+    }
+    while let Some(variable_id) = to_visit.pop() {
+        if !live.insert(variable_id) {
+            continue;
+        }
+        if let Some((block_id, idx)) = assignments.get(variable_id) {
+            let instr = &program.get_block(*block_id).0[*idx];
+            to_visit.extend(get_used_variables(instr));
+        }
+    }
+
+    let mut changed = false;
+    for block in program.blocks.values_mut() {
+        let len = block.0.len();
+        block.0.retain(|instr| {
+            matches!(instr, Instruction::Call(..))
+                || get_assigned_variable(instr).is_none_or(|var| live.contains(&var.variable_id))
+        });
+        changed |= block.0.len() != len;
+    }
+    changed
+}
+
+fn get_used_variables(instr: &Instruction) -> Vec<VariableId> {
+    get_operands(instr)
+        .into_iter()
+        .filter_map(|opr| match opr {
+            Operand::Variable(var) => Some(var.variable_id),
+            Operand::Literal(_) => None,
+        })
+        .collect()
+}
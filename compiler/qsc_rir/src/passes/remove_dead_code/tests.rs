@@ -0,0 +1,258 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::too_many_lines, clippy::needless_raw_string_hashes)]
+
+use expect_test::expect;
+
+use crate::{
+    builder::new_program,
+    passes::remove_dead_code::remove_dead_code,
+    rir::{
+        Block, BlockId, Callable, CallableId, CallableType, ConditionCode, Instruction, Literal,
+        Operand, Program, Ty, Variable, VariableId,
+    },
+};
+
+fn int_var(id: u32) -> Variable {
+    Variable {
+        variable_id: VariableId(id),
+        ty: Ty::Integer,
+    }
+}
+
+fn bool_var(id: u32) -> Variable {
+    Variable {
+        variable_id: VariableId(id),
+        ty: Ty::Boolean,
+    }
+}
+
+fn add_decls(program: &mut Program) {
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "dynamic_int".to_string(),
+            input_type: Vec::new(),
+            params: Vec::new(),
+            output_type: Some(Ty::Integer),
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+    program.callables.insert(
+        CallableId(2),
+        Callable {
+            name: "record_int".to_string(),
+            input_type: vec![Ty::Integer],
+            params: Vec::new(),
+            output_type: None,
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+}
+
+#[test]
+fn remove_dead_code_removes_unused_variables_but_keeps_calls() {
+    let mut program = new_program();
+    add_decls(&mut program);
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(int_var(0))),
+            Instruction::Call(CallableId(1), Vec::new(), Some(int_var(1))),
+            Instruction::Add(
+                Operand::Variable(int_var(0)),
+                Operand::Literal(Literal::Integer(1)),
+                int_var(2),
+            ),
+            Instruction::Mul(
+                Operand::Variable(int_var(1)),
+                Operand::Literal(Literal::Integer(2)),
+                int_var(3),
+            ),
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var(2))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    assert!(remove_dead_code(&mut program));
+    expect![[r#"
+        Block:
+            Variable(0, Integer) = Call id(1), args( )
+            Variable(1, Integer) = Call id(1), args( )
+            Variable(2, Integer) = Add Variable(0, Integer), Integer(1)
+            Call id(2), args( Variable(2, Integer), )
+            Return"#]]
+    .assert_eq(&program.get_block(BlockId(0)).to_string());
+
+    assert!(!remove_dead_code(&mut program));
+}
+
+#[test]
+fn remove_dead_code_removes_phi_nodes_only_used_by_each_other() {
+    let mut program = new_program();
+    add_decls(&mut program);
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(int_var(0))),
+            Instruction::Jump(BlockId(1)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Phi(
+                vec![
+                    (Operand::Variable(int_var(0)), BlockId(0)),
+                    (Operand::Variable(int_var(2)), BlockId(1)),
+                ],
+                int_var(1),
+            ),
+            Instruction::Add(
+                Operand::Variable(int_var(1)),
+                Operand::Literal(Literal::Integer(1)),
+                int_var(2),
+            ),
+            Instruction::Icmp(
+                ConditionCode::Eq,
+                Operand::Variable(int_var(0)),
+                Operand::Literal(Literal::Integer(0)),
+                bool_var(3),
+            ),
+            Instruction::Branch(bool_var(3), BlockId(1), BlockId(2)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(2), Block(vec![Instruction::Return]));
+
+    assert!(remove_dead_code(&mut program));
+    expect![[r#"
+        Block:
+            Variable(3, Boolean) = Icmp Eq, Variable(0, Integer), Integer(0)
+            Branch Variable(3, Boolean), 1, 2"#]]
+    .assert_eq(&program.get_block(BlockId(1)).to_string());
+}
+
+#[test]
+fn remove_dead_code_removes_unreachable_blocks_and_their_phi_arguments() {
+    let mut program = new_program();
+    add_decls(&mut program);
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(int_var(0))),
+            Instruction::Jump(BlockId(2)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(1), Block(vec![Instruction::Jump(BlockId(2))]));
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![
+            Instruction::Phi(
+                vec![
+                    (Operand::Literal(Literal::Integer(1)), BlockId(1)),
+                    (Operand::Variable(int_var(0)), BlockId(0)),
+                ],
+                int_var(1),
+            ),
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var(1))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    assert!(remove_dead_code(&mut program));
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: dynamic_int
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: Integer
+                    body: <NONE>
+                Callable 2: Callable:
+                    name: record_int
+                    call_type: Regular
+                    input_type:
+                        [0]: Integer
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Variable(0, Integer) = Call id(1), args( )
+                    Jump(2)
+                Block 2: Block:
+                    Variable(1, Integer) = Phi ( [Variable(0, Integer), 0], )
+                    Call id(2), args( Variable(1, Integer), )
+                    Return
+            config: Config:
+                capabilities: Base
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
+
+#[test]
+fn remove_dead_code_removes_callables_only_called_from_unreachable_blocks() {
+    let mut program = new_program();
+    add_decls(&mut program);
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(int_var(0))),
+            Instruction::Jump(BlockId(2)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var(0))], None),
+            Instruction::Jump(BlockId(2)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(2), Block(vec![Instruction::Return]));
+
+    assert!(remove_dead_code(&mut program));
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: dynamic_int
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: Integer
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Variable(0, Integer) = Call id(1), args( )
+                    Jump(2)
+                Block 2: Block:
+                    Return
+            config: Config:
+                capabilities: Base
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
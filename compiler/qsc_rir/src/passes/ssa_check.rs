@@ -16,24 +16,29 @@ mod tests;
 /// - Each variable is assigned exactly once.
 /// - Each variable is used after it is assigned.
 /// - Each variable is used in a block that is dominated by the block in which it is assigned.
+/// - Each callable parameter is only used within the body of its callable.
 /// - Each phi node references only its predecessors.
 pub fn check_ssa_form(
     program: &Program,
     preds: &IndexMap<BlockId, Vec<BlockId>>,
     doms: &IndexMap<BlockId, BlockId>,
 ) {
-    check_phi_nodes(program, preds);
+    check_phi_nodes(program, preds, doms);
     let variable_assignments = get_variable_assignments(program);
     let variable_uses = get_variable_uses(program);
+    let params = get_callable_params(program, &variable_assignments);
 
     for (var_id, uses) in variable_uses.iter() {
-        let Some((def_block_id, def_idx)) = variable_assignments.get(var_id) else {
-            panic!("{var_id:?} is used but not assigned");
+        // Parameters are assigned on entry to the body of their callable, before any of its instructions.
+        let (def_block_id, def_idx) = match (variable_assignments.get(var_id), params.get(var_id)) {
+            (Some((def_block_id, def_idx)), _) => (def_block_id, Some(def_idx)),
+            (None, Some(def_block_id)) => (def_block_id, None),
+            (None, None) => panic!("{var_id:?} is used but not assigned"),
         };
         for (use_block_id, use_idx) in uses {
             if use_block_id == def_block_id {
                 assert!(
-                    use_idx > def_idx,
+                    !matches!(def_idx, Some(def_idx) if use_idx <= def_idx),
                     "{var_id:?} is used before it is assigned in {use_block_id:?}, instruction {use_idx:?}"
                 );
             } else {
@@ -53,7 +58,33 @@ pub fn check_ssa_form(
     }
 }
 
-fn check_phi_nodes(program: &Program, preds: &IndexMap<BlockId, Vec<BlockId>>) {
+fn get_callable_params(
+    program: &Program,
+    variable_assignments: &IndexMap<VariableId, (BlockId, usize)>,
+) -> IndexMap<VariableId, BlockId> {
+    let mut params = IndexMap::default();
+    for (_, callable) in program.callables.iter() {
+        let Some(body_block_id) = callable.body else {
+            continue;
+        };
+        for param in &callable.params {
+            assert!(
+                !variable_assignments.contains_key(param.variable_id)
+                    && !params.contains_key(param.variable_id),
+                "Duplicate assignment to parameter {:?}",
+                param.variable_id
+            );
+            params.insert(param.variable_id, body_block_id);
+        }
+    }
+    params
+}
+
+fn check_phi_nodes(
+    program: &Program,
+    preds: &IndexMap<BlockId, Vec<BlockId>>,
+    doms: &IndexMap<BlockId, BlockId>,
+) {
     for (block_id, block) in program.blocks.iter() {
         let Some(block_preds) = preds.get(block_id) else {
             // Block with no predecessors cannot have phi nodes.
@@ -82,9 +113,12 @@ fn check_phi_nodes(program: &Program, preds: &IndexMap<BlockId, Vec<BlockId>>) {
                             .contains(pred_block_id),
                         "Phi node in {block_id:?} references a non-predecessor {pred_block_id:?}"
                     );
+                    // A phi node can only refer to itself through the back edge of a loop, where its value is carried
+                    // over from the previous iteration. An edge is a back edge when its target dominates its source.
                     if let Operand::Variable(var) = val {
                         assert!(
-                            var.variable_id.0 != res.variable_id.0,
+                            var.variable_id.0 != res.variable_id.0
+                                || dominates(doms, block_id, *pred_block_id),
                             "Phi node in {block_id:?} assigns to {:?} to itself",
                             res.variable_id
                         );
@@ -95,6 +129,28 @@ fn check_phi_nodes(program: &Program, preds: &IndexMap<BlockId, Vec<BlockId>>) {
     }
 }
 
+/// Whether the given block dominates the other block, walking up the immediate dominators of the other block.
+fn dominates(
+    doms: &IndexMap<BlockId, BlockId>,
+    block_id: BlockId,
+    other_block_id: BlockId,
+) -> bool {
+    let mut dominator = other_block_id;
+    loop {
+        if dominator == block_id {
+            return true;
+        }
+        let new_dom = *doms
+            .get(dominator)
+            .expect("all blocks should have dominator");
+        if new_dom == dominator {
+            return false;
+        }
+        dominator = new_dom;
+    }
+}
+
+#[allow(clippy::too_many_lines)]
 fn get_variable_uses(program: &Program) -> IndexMap<VariableId, Vec<(BlockId, usize)>> {
     let mut uses: IndexMap<VariableId, Vec<(BlockId, usize)>> = IndexMap::default();
     let mut add_use = |var_id, block_id, idx| {
@@ -124,6 +180,19 @@ fn get_variable_uses(program: &Program) -> IndexMap<VariableId, Vec<(BlockId, us
                 | Instruction::Ashr(Operand::Literal(_), Operand::Variable(var), _)
                 | Instruction::Icmp(_, Operand::Variable(var), Operand::Literal(_), _)
                 | Instruction::Icmp(_, Operand::Literal(_), Operand::Variable(var), _)
+                | Instruction::Fadd(Operand::Variable(var), Operand::Literal(_), _)
+                | Instruction::Fadd(Operand::Literal(_), Operand::Variable(var), _)
+                | Instruction::Fsub(Operand::Variable(var), Operand::Literal(_), _)
+                | Instruction::Fsub(Operand::Literal(_), Operand::Variable(var), _)
+                | Instruction::Fmul(Operand::Variable(var), Operand::Literal(_), _)
+                | Instruction::Fmul(Operand::Literal(_), Operand::Variable(var), _)
+                | Instruction::Fdiv(Operand::Variable(var), Operand::Literal(_), _)
+                | Instruction::Fdiv(Operand::Literal(_), Operand::Variable(var), _)
+                | Instruction::Fcmp(_, Operand::Variable(var), Operand::Literal(_), _)
+                | Instruction::Fcmp(_, Operand::Literal(_), Operand::Variable(var), _)
+                | Instruction::Sitofp(Operand::Variable(var), _)
+                | Instruction::Fptosi(Operand::Variable(var), _)
+                | Instruction::Load(Operand::Variable(var), _)
                 | Instruction::LogicalNot(Operand::Variable(var), _)
                 | Instruction::LogicalAnd(Operand::Variable(var), Operand::Literal(_), _)
                 | Instruction::LogicalAnd(Operand::Literal(_), Operand::Variable(var), _)
@@ -149,6 +218,11 @@ fn get_variable_uses(program: &Program) -> IndexMap<VariableId, Vec<(BlockId, us
                 | Instruction::Shl(Operand::Variable(var1), Operand::Variable(var2), _)
                 | Instruction::Ashr(Operand::Variable(var1), Operand::Variable(var2), _)
                 | Instruction::Icmp(_, Operand::Variable(var1), Operand::Variable(var2), _)
+                | Instruction::Fadd(Operand::Variable(var1), Operand::Variable(var2), _)
+                | Instruction::Fsub(Operand::Variable(var1), Operand::Variable(var2), _)
+                | Instruction::Fmul(Operand::Variable(var1), Operand::Variable(var2), _)
+                | Instruction::Fdiv(Operand::Variable(var1), Operand::Variable(var2), _)
+                | Instruction::Fcmp(_, Operand::Variable(var1), Operand::Variable(var2), _)
                 | Instruction::LogicalAnd(Operand::Variable(var1), Operand::Variable(var2), _)
                 | Instruction::LogicalOr(Operand::Variable(var1), Operand::Variable(var2), _)
                 | Instruction::BitwiseAnd(Operand::Variable(var1), Operand::Variable(var2), _)
@@ -186,6 +260,14 @@ fn get_variable_uses(program: &Program) -> IndexMap<VariableId, Vec<(BlockId, us
                 | Instruction::Shl(Operand::Literal(_), Operand::Literal(_), _)
                 | Instruction::Ashr(Operand::Literal(_), Operand::Literal(_), _)
                 | Instruction::Icmp(_, Operand::Literal(_), Operand::Literal(_), _)
+                | Instruction::Fadd(Operand::Literal(_), Operand::Literal(_), _)
+                | Instruction::Fsub(Operand::Literal(_), Operand::Literal(_), _)
+                | Instruction::Fmul(Operand::Literal(_), Operand::Literal(_), _)
+                | Instruction::Fdiv(Operand::Literal(_), Operand::Literal(_), _)
+                | Instruction::Fcmp(_, Operand::Literal(_), Operand::Literal(_), _)
+                | Instruction::Sitofp(Operand::Literal(_), _)
+                | Instruction::Fptosi(Operand::Literal(_), _)
+                | Instruction::Load(Operand::Literal(_), _)
                 | Instruction::LogicalNot(Operand::Literal(_), _)
                 | Instruction::LogicalAnd(Operand::Literal(_), Operand::Literal(_), _)
                 | Instruction::LogicalOr(Operand::Literal(_), Operand::Literal(_), _)
@@ -219,6 +219,7 @@ fn ssa_check_passes_for_variable_that_dominates_usage() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -299,6 +300,7 @@ fn ssa_check_fails_when_definition_does_not_dominates_usage() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -389,6 +391,7 @@ fn ssa_check_succeeds_when_phi_handles_multiple_values_from_branches() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -501,6 +504,7 @@ fn ssa_check_succeeds_when_phi_handles_value_from_dominator_of_predecessor() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -620,6 +624,7 @@ fn ssa_check_fails_when_phi_handles_value_from_non_dominator_of_predecessor() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -765,6 +770,7 @@ fn ssa_check_fails_when_phi_lists_non_predecessor_block() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -878,6 +884,7 @@ fn ssa_check_fails_when_phi_assigns_to_itself() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -981,6 +988,132 @@ fn ssa_check_fails_when_phi_assigns_to_itself() {
     perform_ssa_check(&mut program);
 }
 
+fn insert_dynamic_bool_callable(program: &mut Program) {
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "dynamic_bool".to_string(),
+            input_type: Vec::new(),
+            params: Vec::new(),
+            output_type: Some(Ty::Boolean),
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+}
+
+/// Inserts a block that assigns itself a phi node of `Bool(true)` from `BlockId(0)` and of itself from `back_pred`.
+fn insert_self_referencing_phi_block(
+    program: &mut Program,
+    block_id: BlockId,
+    back_pred: BlockId,
+    terminator: Instruction,
+) {
+    program.blocks.insert(
+        block_id,
+        Block(vec![
+            Instruction::Phi(
+                vec![
+                    (Operand::Literal(Literal::Bool(true)), BlockId(0)),
+                    (
+                        Operand::Variable(Variable {
+                            variable_id: VariableId(1),
+                            ty: Ty::Boolean,
+                        }),
+                        back_pred,
+                    ),
+                ],
+                Variable {
+                    variable_id: VariableId(1),
+                    ty: Ty::Boolean,
+                },
+            ),
+            terminator,
+        ]),
+    );
+}
+
+#[test]
+fn ssa_check_passes_when_phi_refers_to_itself_through_loop_back_edge() {
+    let mut program = new_program();
+    insert_dynamic_bool_callable(&mut program);
+    let condition = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Boolean,
+    };
+
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(condition)),
+            Instruction::Jump(BlockId(1)),
+        ]),
+    );
+    insert_self_referencing_phi_block(
+        &mut program,
+        BlockId(1),
+        BlockId(2),
+        Instruction::Branch(condition, BlockId(2), BlockId(3)),
+    );
+    program
+        .blocks
+        .insert(BlockId(2), Block(vec![Instruction::Jump(BlockId(1))]));
+    program.blocks.insert(
+        BlockId(3),
+        Block(vec![
+            Instruction::LogicalNot(
+                Operand::Variable(Variable {
+                    variable_id: VariableId(1),
+                    ty: Ty::Boolean,
+                }),
+                Variable {
+                    variable_id: VariableId(2),
+                    ty: Ty::Boolean,
+                },
+            ),
+            Instruction::Return,
+        ]),
+    );
+
+    perform_ssa_check(&mut program);
+}
+
+#[test]
+#[should_panic(expected = "assigns to VariableId(1) to itself")]
+fn ssa_check_fails_when_phi_refers_to_itself_through_cycle_it_does_not_dominate() {
+    let mut program = new_program();
+    insert_dynamic_bool_callable(&mut program);
+    let condition = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Boolean,
+    };
+
+    // Both blocks of the cycle can be entered from the entry block, so neither dominates the other and the edge into
+    // the block with the phi node is not a back edge.
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(condition)),
+            Instruction::Branch(condition, BlockId(1), BlockId(2)),
+        ]),
+    );
+    insert_self_referencing_phi_block(
+        &mut program,
+        BlockId(1),
+        BlockId(2),
+        Instruction::Jump(BlockId(2)),
+    );
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![Instruction::Branch(condition, BlockId(1), BlockId(3))]),
+    );
+    program
+        .blocks
+        .insert(BlockId(3), Block(vec![Instruction::Return]));
+
+    perform_ssa_check(&mut program);
+}
+
 #[test]
 #[should_panic(expected = "Phi node in BlockId(3) has 1 arguments but 2 predecessors")]
 fn ssa_check_fails_when_phi_blocks_have_different_predecessors() {
@@ -990,6 +1123,7 @@ fn ssa_check_fails_when_phi_blocks_have_different_predecessors() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -1084,3 +1218,132 @@ fn ssa_check_fails_when_phi_blocks_have_different_predecessors() {
 
     perform_ssa_check(&mut program);
 }
+
+fn insert_callable_with_param(program: &mut Program, body: BlockId, param: Variable) {
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "op".to_string(),
+            input_type: vec![param.ty],
+            params: vec![param],
+            output_type: None,
+            body: Some(body),
+            call_type: CallableType::Regular,
+        },
+    );
+}
+
+#[test]
+fn ssa_check_passes_for_param_used_within_its_callable() {
+    let mut program = new_program();
+    let param = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Boolean,
+    };
+    insert_callable_with_param(&mut program, BlockId(1), param);
+
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Bool(true))],
+                None,
+            ),
+            Instruction::Return,
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![Instruction::Branch(param, BlockId(2), BlockId(3))]),
+    );
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![
+            Instruction::LogicalNot(
+                Operand::Variable(param),
+                Variable {
+                    variable_id: VariableId(1),
+                    ty: Ty::Boolean,
+                },
+            ),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(3), Block(vec![Instruction::Return]));
+
+    perform_ssa_check(&mut program);
+}
+
+#[test]
+#[should_panic(
+    expected = "Definition of VariableId(0) in BlockId(1) does not dominate use in BlockId(0), instruction 0"
+)]
+fn ssa_check_fails_for_param_used_outside_its_callable() {
+    let mut program = new_program();
+    let param = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Boolean,
+    };
+    insert_callable_with_param(&mut program, BlockId(1), param);
+
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), vec![Operand::Variable(param)], None),
+            Instruction::Return,
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(1), Block(vec![Instruction::Return]));
+
+    perform_ssa_check(&mut program);
+}
+
+#[test]
+#[should_panic(expected = "Duplicate assignment to parameter VariableId(0)")]
+fn ssa_check_fails_for_assignment_to_param() {
+    let mut program = new_program();
+    let param = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Boolean,
+    };
+    insert_callable_with_param(&mut program, BlockId(1), param);
+
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Bool(true))],
+                None,
+            ),
+            Instruction::Return,
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::LogicalNot(
+                Operand::Variable(param),
+                Variable {
+                    variable_id: VariableId(1),
+                    ty: Ty::Boolean,
+                },
+            ),
+            Instruction::LogicalNot(
+                Operand::Variable(Variable {
+                    variable_id: VariableId(1),
+                    ty: Ty::Boolean,
+                }),
+                param,
+            ),
+            Instruction::Return,
+        ]),
+    );
+
+    perform_ssa_check(&mut program);
+}
@@ -6,17 +6,16 @@ mod tests;
 
 use crate::{
     rir::{Block, BlockId, Instruction, Operand, Program, Variable, VariableId},
-    utils::get_variable_assignments,
+    utils::{get_assigned_variable, get_variable_assignments, replace_variable_uses},
 };
 use qsc_data_structures::index_map::IndexMap;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 /// Transforms the program into Single Static Assignment (SSA) form by inserting phi nodes
 /// at the beginning of blocks where necessary, allowing the removal of store instructions.
+/// Block IDs are expected to be in reverse postorder, so a predecessor with an ID greater than or equal to
+/// that of a block is a back edge into a loop header.
 pub fn transform_to_ssa(program: &mut Program, preds: &IndexMap<BlockId, Vec<BlockId>>) {
-    // Ensure that the graph is acyclic before proceeding. Current approach does not support cycles.
-    ensure_acyclic(preds);
-
     // First, remove store instructions and propagate variables through individual blocks.
     // This produces a per-block map of dynamic variables to their values.
     // Orphan variables may be left behind where a variable is defined in one block and used in another, which
@@ -32,7 +31,10 @@ pub fn transform_to_ssa(program: &mut Program, preds: &IndexMap<BlockId, Vec<Blo
 
     // Insert phi nodes where necessary, mapping any remaining orphaned uses to the new variable
     // created by the phi node.
-    // This can be done in one pass because the graph is assumed to be acyclic.
+    // Predecessors reached through a forward edge are always processed before the block itself, so this can be
+    // done in one pass. Loop headers get a phi node for every variable live on entry to the loop, whose values
+    // from the back edges are only known once the whole loop has been processed.
+    let mut loop_phi_nodes = Vec::new();
     for (block_id, block) in program.blocks.iter_mut() {
         let Some(block_preds) = preds.get(block_id) else {
             // The block with no predecessors is the entry block and has no phi nodes.
@@ -43,84 +45,188 @@ pub fn transform_to_ssa(program: &mut Program, preds: &IndexMap<BlockId, Vec<Blo
         // any phi nodes are inserted and will replace any orphaned variables.
         let mut var_map_updates = FxHashMap::default();
 
-        let (first_pred, rest_preds) = block_preds
-            .split_first()
-            .expect("block should have at least one predecessor");
+        let (forward_preds, back_edge_preds): (Vec<BlockId>, Vec<BlockId>) =
+            block_preds.iter().partition(|pred| **pred < block_id);
+        let Some((first_pred, rest_preds)) = forward_preds.split_first() else {
+            // A block only reachable through back edges has no values flowing into it.
+            continue;
+        };
 
-        // The block is only a candidate for phi nodes if it has multiple predecessors.
-        if !rest_preds.is_empty() {
+        if rest_preds.is_empty() && back_edge_preds.is_empty() {
+            // A block with a single predecessor inherits any values its predecessor gained from phi nodes,
+            // which were inserted after the block's variable map was first built.
+            var_map_updates.extend(
+                block_var_map
+                    .get(*first_pred)
+                    .expect("block should have variable map")
+                    .iter()
+                    .map(|(var_id, operand)| (*var_id, *operand)),
+            );
+        } else {
+            // The block is only a candidate for phi nodes if it has multiple predecessors.
             // Check each variable in the first predecessor's variable map, and if any other
             // predecessor has a different value for the variable, a phi node is needed.
+            // A variable missing from another forward predecessor is not live in the block.
+            let mut phi_nodes = Vec::new();
             for (var_id, operand) in block_var_map
                 .get(*first_pred)
                 .expect("block should have variable map")
             {
-                let mut phi_nodes = FxHashMap::default();
-
-                if rest_preds.iter().any(|pred| {
-                    block_var_map
+                let mut phi_args = vec![(*operand, *first_pred)];
+                for pred in rest_preds {
+                    if let Some(pred_operand) = block_var_map
                         .get(*pred)
                         .expect("block should have variable map")
                         .get(var_id)
-                        != Some(operand)
-                }) {
-                    // Some predecessors have different values for this variable, so a phi node is needed.
-                    // Start with the first predecessor's value and block id, then add the values from the other predecessors.
-                    let mut phi_args = vec![(*operand, *first_pred)];
-                    for pred in rest_preds {
-                        phi_args.push((
-                            block_var_map
-                                .get(*pred)
-                                .expect("block should have variable map")
-                                .get(var_id)
-                                .copied()
-                                .expect("variable should be defined in all predecessors"),
-                            *pred,
-                        ));
+                    {
+                        phi_args.push((*pred_operand, *pred));
                     }
-                    phi_nodes.insert(*var_id, phi_args);
-                } else {
+                }
+                if phi_args.len() <= rest_preds.len() {
+                    continue;
+                }
+
+                if back_edge_preds.is_empty()
+                    && matches!(operand, Operand::Variable(_))
+                    && phi_args.iter().all(|(arg, _)| arg == operand)
+                {
                     // If all predecessors have the same value for this variable, the value can be propagated.
                     // Update the block variable map with the common operand.
+                    // A common literal still gets a phi node, since propagating it could leave instructions
+                    // with only literal operands or a branch on a literal condition.
                     var_map_updates.insert(*var_id, *operand);
+                } else {
+                    // Some predecessors have different values for this variable, so a phi node is needed.
+                    phi_nodes.push((*var_id, operand.get_type(), phi_args));
                 }
+            }
+
+            // For any phi nodes that need to be inserted, create a new variable and insert
+            // the phi node at the beginning of the block. The new variable will be used to replace
+            // the original variable in the block's variable map, which will take care of any orphaned uses.
+            // The values from back edges start as the phi node itself and are filled in once known.
+            for (variable_id, ty, mut args) in phi_nodes {
+                let new_var = Variable {
+                    variable_id: next_var_id,
+                    ty,
+                };
+                for pred in &back_edge_preds {
+                    args.push((Operand::Variable(new_var), *pred));
+                    loop_phi_nodes.push((block_id, new_var, variable_id, *pred));
+                }
+                let phi_node = Instruction::Phi(args, new_var);
+                block.0.insert(0, phi_node);
+                var_map_updates.insert(variable_id, Operand::Variable(new_var));
+                next_var_id = next_var_id.successor();
+            }
+        }
 
-                // For any phi nodes that need to be inserted, create a new variable and insert
-                // the phi node at the beginning of the block. The new variable will be used to replace
-                // the original variable in the block's variable map, which will take care of any orphaned uses.
-                for (variable_id, args) in phi_nodes {
-                    let new_var = Variable {
-                        variable_id: next_var_id,
-                        ty: operand.get_type(),
-                    };
-                    let phi_node = Instruction::Phi(args, new_var);
-                    block.0.insert(0, phi_node);
-                    var_map_updates.insert(variable_id, Operand::Variable(new_var));
-                    next_var_id = next_var_id.successor();
+        // Now that the block has finished processing, apply any updates to the block and
+        // merge those updates into the stored variable map to propagate to successors.
+        // Values stored in the block may themselves refer to variables that were just updated, so they
+        // are mapped to the new values too.
+        map_variable_use_in_block(block, &mut var_map_updates);
+        let var_map = block_var_map
+            .get_mut(block_id)
+            .expect("block should have variable map");
+        for operand in var_map.values_mut() {
+            if let Operand::Variable(var) = operand {
+                if let Some(updated) = var_map_updates.get(&var.variable_id) {
+                    *operand = *updated;
                 }
             }
+        }
+        for (var_id, operand) in var_map_updates {
+            var_map.entry(var_id).or_insert(operand);
+        }
+    }
 
-            // Now that the block has finished processing, apply any updates to the block and
-            // merge those updates into the stored variable map to propagate to successors.
-            map_variable_use_in_block(block, &mut var_map_updates);
-            for (var_id, operand) in var_map_updates {
-                let var_map = block_var_map
-                    .get_mut(block_id)
-                    .expect("block should have variable map");
-                var_map.entry(var_id).or_insert(operand);
+    if !loop_phi_nodes.is_empty() {
+        fill_loop_phi_nodes(program, &block_var_map, &loop_phi_nodes);
+        remove_trivial_phi_nodes(program);
+    }
+}
+
+// Fill in the values that flow into loop header phi nodes from back edges, which are the values the variables have at
+// the end of the predecessor block.
+fn fill_loop_phi_nodes(
+    program: &mut Program,
+    block_var_map: &IndexMap<BlockId, FxHashMap<VariableId, Operand>>,
+    loop_phi_nodes: &[(BlockId, Variable, VariableId, BlockId)],
+) {
+    for (block_id, phi_var, variable_id, pred) in loop_phi_nodes {
+        let value = block_var_map
+            .get(*pred)
+            .expect("block should have variable map")
+            .get(variable_id)
+            .copied()
+            .unwrap_or(Operand::Variable(*phi_var));
+        for instr in &mut program.get_block_mut(*block_id).0 {
+            if let Instruction::Phi(args, var) = instr {
+                if var == phi_var {
+                    for (arg, arg_pred) in args.iter_mut() {
+                        if arg_pred == pred {
+                            *arg = value;
+                        }
+                    }
+                }
             }
         }
     }
 }
 
-// For now, SSA transform assumes the graph is acyclic, so verify that no block has a predecessor with
-// a block id less than itself, which would indicate a cycle.
-fn ensure_acyclic(preds: &IndexMap<BlockId, Vec<BlockId>>) {
-    for (block_id, block_preds) in preds.iter() {
-        assert!(
-            !block_preds.iter().any(|pred| *pred >= block_id),
-            "block {block_id:?} has a cycle in its predecessors"
-        );
+// Loop header phi nodes are inserted for every variable live on entry to the loop, so remove the ones whose value
+// does not change within the loop, where all arguments other than the phi node itself are the same value. Removing
+// some of them can make others trivial, so repeat until no more are found.
+fn remove_trivial_phi_nodes(program: &mut Program) {
+    loop {
+        let branch_conditions = program
+            .blocks
+            .values()
+            .filter_map(|block| match block.0.last() {
+                Some(Instruction::Branch(cond, _, _)) => Some(cond.variable_id),
+                _ => None,
+            })
+            .collect::<FxHashSet<_>>();
+        let mut replacements = FxHashMap::default();
+        for block in program.blocks.values() {
+            // Since phi nodes are always at the top of the block, stop at the first other instruction.
+            for instr in &block.0 {
+                let Instruction::Phi(args, var) = instr else {
+                    break;
+                };
+                let mut values = args
+                    .iter()
+                    .map(|(arg, _)| *arg)
+                    .filter(|arg| *arg != Operand::Variable(*var));
+                let Some(value) = values.next() else {
+                    continue;
+                };
+                // Branch conditions can only be replaced by variables.
+                if values.all(|arg| arg == value)
+                    && (matches!(value, Operand::Variable(_))
+                        || !branch_conditions.contains(&var.variable_id))
+                {
+                    replacements.insert(var.variable_id, value);
+                }
+            }
+        }
+
+        // A phi node whose value is another one being removed is left for the next round, once that value is known.
+        let removed = replacements.keys().copied().collect::<FxHashSet<_>>();
+        replacements.retain(|_, value| {
+            !matches!(value, Operand::Variable(var) if removed.contains(&var.variable_id))
+        });
+        if replacements.is_empty() {
+            return;
+        }
+        replace_variable_uses(program, &replacements);
+        for block in program.blocks.values_mut() {
+            block.0.retain(|instr| {
+                get_assigned_variable(instr)
+                    .is_none_or(|var| !replacements.contains_key(&var.variable_id))
+            });
+        }
     }
 }
 
@@ -158,8 +264,10 @@ fn map_variable_use_in_block(block: &mut Block, var_map: &mut impl VariableMappe
     for mut instr in instrs {
         match &mut instr {
             // Track the new value of the variable and omit the store instruction.
+            // The stored operand may itself be a variable stored earlier, so it is mapped first.
             Instruction::Store(operand, var) => {
-                var_map.insert(*var, *operand);
+                let operand = operand.mapped(var_map);
+                var_map.insert(*var, operand);
                 continue;
             }
 
@@ -188,6 +296,11 @@ fn map_variable_use_in_block(block: &mut Block, var_map: &mut impl VariableMappe
             | Instruction::Shl(lhs, rhs, _)
             | Instruction::Ashr(lhs, rhs, _)
             | Instruction::Icmp(_, lhs, rhs, _)
+            | Instruction::Fadd(lhs, rhs, _)
+            | Instruction::Fsub(lhs, rhs, _)
+            | Instruction::Fmul(lhs, rhs, _)
+            | Instruction::Fdiv(lhs, rhs, _)
+            | Instruction::Fcmp(_, lhs, rhs, _)
             | Instruction::LogicalAnd(lhs, rhs, _)
             | Instruction::LogicalOr(lhs, rhs, _)
             | Instruction::BitwiseAnd(lhs, rhs, _)
@@ -198,7 +311,11 @@ fn map_variable_use_in_block(block: &mut Block, var_map: &mut impl VariableMappe
             }
 
             // Single variable instructions, replace operand with new value.
-            Instruction::BitwiseNot(operand, _) | Instruction::LogicalNot(operand, _) => {
+            Instruction::BitwiseNot(operand, _)
+            | Instruction::LogicalNot(operand, _)
+            | Instruction::Sitofp(operand, _)
+            | Instruction::Fptosi(operand, _)
+            | Instruction::Load(operand, _) => {
                 *operand = operand.mapped(var_map);
             }
 
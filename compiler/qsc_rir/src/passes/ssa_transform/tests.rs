@@ -10,8 +10,8 @@ use crate::{
     builder::{bell_program, new_program, teleport_program},
     passes::check_and_transform,
     rir::{
-        Block, BlockId, Callable, CallableId, CallableType, Instruction, Operand, Program, Ty,
-        Variable, VariableId,
+        Block, BlockId, Callable, CallableId, CallableType, Instruction, Literal, Operand, Program,
+        Ty, Variable, VariableId,
     },
 };
 fn transform_program(program: &mut Program) {
@@ -47,6 +47,7 @@ fn ssa_transform_removes_store_in_single_block_program() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -141,7 +142,7 @@ fn ssa_transform_removes_store_in_single_block_program() {
                     Variable(2, Boolean) = LogicalNot Variable(0, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
             num_results: 0"#]]
     .assert_eq(&program.to_string());
@@ -155,6 +156,7 @@ fn ssa_transform_removes_multiple_stores_in_single_block_program() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -295,7 +297,7 @@ fn ssa_transform_removes_multiple_stores_in_single_block_program() {
                     Variable(4, Boolean) = LogicalNot Variable(3, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
             num_results: 0"#]]
     .assert_eq(&program.to_string());
@@ -309,6 +311,7 @@ fn ssa_transform_store_dominating_usage_propagates_to_successor_blocks() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -464,7 +467,7 @@ fn ssa_transform_store_dominating_usage_propagates_to_successor_blocks() {
                     Variable(4, Boolean) = LogicalNot Variable(0, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
             num_results: 0"#]]
     .assert_eq(&program.to_string());
@@ -479,6 +482,7 @@ fn ssa_transform_store_dominating_usage_propagates_to_successor_blocks_without_i
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -604,7 +608,7 @@ fn ssa_transform_store_dominating_usage_propagates_to_successor_blocks_without_i
                     Variable(4, Boolean) = LogicalNot Variable(0, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
             num_results: 0"#]]
     .assert_eq(&program.to_string());
@@ -618,6 +622,7 @@ fn ssa_transform_inserts_phi_for_store_not_dominating_usage() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -796,9 +801,10 @@ fn ssa_transform_inserts_phi_for_store_not_dominating_usage() {
                     Variable(4, Boolean) = LogicalNot Variable(5, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
-            num_results: 0"#]].assert_eq(&program.to_string());
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
 }
 
 #[test]
@@ -809,6 +815,7 @@ fn ssa_transform_inserts_phi_for_store_not_dominating_usage_in_one_branch() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -961,9 +968,10 @@ fn ssa_transform_inserts_phi_for_store_not_dominating_usage_in_one_branch() {
                     Variable(4, Boolean) = LogicalNot Variable(5, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
-            num_results: 0"#]].assert_eq(&program.to_string());
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
 }
 
 #[test]
@@ -974,6 +982,7 @@ fn ssa_transform_inserts_phi_for_node_with_many_predecessors() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -1220,9 +1229,10 @@ fn ssa_transform_inserts_phi_for_node_with_many_predecessors() {
                     Variable(5, Boolean) = LogicalNot Variable(6, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
-            num_results: 0"#]].assert_eq(&program.to_string());
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
 }
 
 #[test]
@@ -1233,6 +1243,7 @@ fn ssa_transform_inserts_phi_for_multiple_stored_values() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -1435,9 +1446,10 @@ fn ssa_transform_inserts_phi_for_multiple_stored_values() {
                     Variable(6, Boolean) = LogicalNot Variable(8, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
-            num_results: 0"#]].assert_eq(&program.to_string());
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
 }
 
 #[test]
@@ -1448,6 +1460,7 @@ fn ssa_transform_inserts_phi_nodes_in_successive_blocks_for_chained_branches() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -1764,9 +1777,10 @@ fn ssa_transform_inserts_phi_nodes_in_successive_blocks_for_chained_branches() {
                     Variable(8, Boolean) = LogicalNot Variable(10, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
-            num_results: 0"#]].assert_eq(&program.to_string());
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
 }
 
 #[test]
@@ -1777,6 +1791,7 @@ fn ssa_transform_inerts_phi_nodes_for_early_return_graph_pattern() {
         Callable {
             name: "dynamic_bool".to_string(),
             input_type: Vec::new(),
+            params: Vec::new(),
             output_type: Some(Ty::Boolean),
             body: None,
             call_type: CallableType::Regular,
@@ -2051,7 +2066,644 @@ fn ssa_transform_inerts_phi_nodes_for_early_return_graph_pattern() {
                     Variable(4, Boolean) = LogicalNot Variable(9, Boolean)
                     Return
             config: Config:
-                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset)
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
+
+#[test]
+fn ssa_transform_propagates_phi_nodes_through_blocks_with_single_predecessor() {
+    let mut program = new_program();
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "dynamic_bool".to_string(),
+            input_type: Vec::new(),
+            params: Vec::new(),
+            output_type: Some(Ty::Boolean),
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+    program.callables.insert(
+        CallableId(2),
+        Callable {
+            name: "use_int".to_string(),
+            input_type: vec![Ty::Integer],
+            params: Vec::new(),
+            output_type: None,
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+
+    let cond = |variable_id| Variable {
+        variable_id: VariableId(variable_id),
+        ty: Ty::Boolean,
+    };
+    let int_var = Variable {
+        variable_id: VariableId(1),
+        ty: Ty::Integer,
+    };
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(cond(0))),
+            Instruction::Branch(cond(0), BlockId(1), BlockId(2)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Store(Operand::Literal(Literal::Integer(4)), int_var),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![
+            Instruction::Store(Operand::Literal(Literal::Integer(2)), int_var),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(3),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(cond(2))),
+            Instruction::Branch(cond(2), BlockId(4), BlockId(5)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(4),
+        Block(vec![
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var)], None),
+            Instruction::Jump(BlockId(5)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(5),
+        Block(vec![
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var)], None),
+            Instruction::Return,
+        ]),
+    );
+
+    transform_program(&mut program);
+
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: dynamic_bool
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: Boolean
+                    body: <NONE>
+                Callable 2: Callable:
+                    name: use_int
+                    call_type: Regular
+                    input_type:
+                        [0]: Integer
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Variable(0, Boolean) = Call id(1), args( )
+                    Branch Variable(0, Boolean), 1, 2
+                Block 1: Block:
+                    Jump(3)
+                Block 2: Block:
+                    Jump(3)
+                Block 3: Block:
+                    Variable(3, Integer) = Phi ( [Integer(4), 1], [Integer(2), 2], )
+                    Variable(2, Boolean) = Call id(1), args( )
+                    Branch Variable(2, Boolean), 4, 5
+                Block 4: Block:
+                    Call id(2), args( Variable(3, Integer), )
+                    Jump(5)
+                Block 5: Block:
+                    Call id(2), args( Variable(3, Integer), )
+                    Return
+            config: Config:
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
+
+#[test]
+fn ssa_transform_maps_stored_variables_replaced_by_phi_nodes() {
+    let mut program = new_program();
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "dynamic_bool".to_string(),
+            input_type: Vec::new(),
+            params: Vec::new(),
+            output_type: Some(Ty::Boolean),
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+    program.callables.insert(
+        CallableId(2),
+        Callable {
+            name: "use_int".to_string(),
+            input_type: vec![Ty::Integer],
+            params: Vec::new(),
+            output_type: None,
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+
+    let cond = |variable_id| Variable {
+        variable_id: VariableId(variable_id),
+        ty: Ty::Boolean,
+    };
+    let int_var = |variable_id| Variable {
+        variable_id: VariableId(variable_id),
+        ty: Ty::Integer,
+    };
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(cond(0))),
+            Instruction::Branch(cond(0), BlockId(1), BlockId(2)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Store(Operand::Literal(Literal::Integer(0)), int_var(1)),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![
+            Instruction::Store(Operand::Literal(Literal::Integer(2)), int_var(1)),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(3),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(cond(2))),
+            Instruction::Branch(cond(2), BlockId(4), BlockId(5)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(4),
+        Block(vec![
+            Instruction::Store(Operand::Variable(int_var(1)), int_var(3)),
+            Instruction::Jump(BlockId(6)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(5),
+        Block(vec![
+            Instruction::Store(Operand::Literal(Literal::Integer(3)), int_var(3)),
+            Instruction::Jump(BlockId(6)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(6),
+        Block(vec![
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var(3))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    transform_program(&mut program);
+
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: dynamic_bool
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: Boolean
+                    body: <NONE>
+                Callable 2: Callable:
+                    name: use_int
+                    call_type: Regular
+                    input_type:
+                        [0]: Integer
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Variable(0, Boolean) = Call id(1), args( )
+                    Branch Variable(0, Boolean), 1, 2
+                Block 1: Block:
+                    Jump(3)
+                Block 2: Block:
+                    Jump(3)
+                Block 3: Block:
+                    Variable(3, Integer) = Phi ( [Integer(0), 1], [Integer(2), 2], )
+                    Variable(2, Boolean) = Call id(1), args( )
+                    Branch Variable(2, Boolean), 4, 5
+                Block 4: Block:
+                    Jump(6)
+                Block 5: Block:
+                    Jump(6)
+                Block 6: Block:
+                    Variable(4, Integer) = Phi ( [Variable(3, Integer), 4], [Integer(3), 5], )
+                    Call id(2), args( Variable(4, Integer), )
+                    Return
+            config: Config:
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
+
+#[test]
+fn ssa_transform_inserts_phi_nodes_for_variables_updated_in_loop() {
+    let mut program = new_program();
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "dynamic_bool".to_string(),
+            input_type: Vec::new(),
+            params: Vec::new(),
+            output_type: Some(Ty::Boolean),
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+    program.callables.insert(
+        CallableId(2),
+        Callable {
+            name: "use_int".to_string(),
+            input_type: vec![Ty::Integer],
+            params: Vec::new(),
+            output_type: None,
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+
+    let cond = |variable_id| Variable {
+        variable_id: VariableId(variable_id),
+        ty: Ty::Boolean,
+    };
+    let int_var = |variable_id| Variable {
+        variable_id: VariableId(variable_id),
+        ty: Ty::Integer,
+    };
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Store(Operand::Literal(Literal::Integer(0)), int_var(0)),
+            Instruction::Store(Operand::Literal(Literal::Integer(5)), int_var(1)),
+            Instruction::Jump(BlockId(1)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(cond(2))),
+            Instruction::Branch(cond(2), BlockId(2), BlockId(3)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![
+            Instruction::Store(Operand::Variable(int_var(0)), int_var(3)),
+            Instruction::Add(
+                Operand::Variable(int_var(3)),
+                Operand::Variable(int_var(1)),
+                int_var(4),
+            ),
+            Instruction::Store(Operand::Variable(int_var(4)), int_var(0)),
+            Instruction::Jump(BlockId(1)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(3),
+        Block(vec![
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var(0))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    transform_program(&mut program);
+
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: dynamic_bool
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: Boolean
+                    body: <NONE>
+                Callable 2: Callable:
+                    name: use_int
+                    call_type: Regular
+                    input_type:
+                        [0]: Integer
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Jump(1)
+                Block 1: Block:
+                    Variable(5, Integer) = Phi ( [Integer(0), 0], [Variable(4, Integer), 2], )
+                    Variable(2, Boolean) = Call id(1), args( )
+                    Branch Variable(2, Boolean), 2, 3
+                Block 2: Block:
+                    Variable(4, Integer) = Add Variable(5, Integer), Integer(5)
+                    Jump(1)
+                Block 3: Block:
+                    Call id(2), args( Variable(5, Integer), )
+                    Return
+            config: Config:
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
+
+#[test]
+fn ssa_transform_inserts_phi_nodes_for_variables_updated_in_nested_loops() {
+    let mut program = new_program();
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "dynamic_bool".to_string(),
+            input_type: Vec::new(),
+            params: Vec::new(),
+            output_type: Some(Ty::Boolean),
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+    program.callables.insert(
+        CallableId(2),
+        Callable {
+            name: "use_int".to_string(),
+            input_type: vec![Ty::Integer],
+            params: Vec::new(),
+            output_type: None,
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+
+    let cond = |variable_id| Variable {
+        variable_id: VariableId(variable_id),
+        ty: Ty::Boolean,
+    };
+    let int_var = |variable_id| Variable {
+        variable_id: VariableId(variable_id),
+        ty: Ty::Integer,
+    };
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Store(Operand::Literal(Literal::Integer(0)), int_var(0)),
+            Instruction::Jump(BlockId(1)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(cond(1))),
+            Instruction::Branch(cond(1), BlockId(2), BlockId(5)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(2), Block(vec![Instruction::Jump(BlockId(3))]));
+    program.blocks.insert(
+        BlockId(3),
+        Block(vec![
+            Instruction::Call(CallableId(1), Vec::new(), Some(cond(2))),
+            Instruction::Branch(cond(2), BlockId(4), BlockId(1)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(4),
+        Block(vec![
+            Instruction::Add(
+                Operand::Variable(int_var(0)),
+                Operand::Literal(Literal::Integer(1)),
+                int_var(3),
+            ),
+            Instruction::Store(Operand::Variable(int_var(3)), int_var(0)),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(5),
+        Block(vec![
+            Instruction::Call(CallableId(2), vec![Operand::Variable(int_var(0))], None),
+            Instruction::Return,
+        ]),
+    );
+
+    transform_program(&mut program);
+
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: dynamic_bool
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: Boolean
+                    body: <NONE>
+                Callable 2: Callable:
+                    name: use_int
+                    call_type: Regular
+                    input_type:
+                        [0]: Integer
+                    output_type: <VOID>
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Jump(1)
+                Block 1: Block:
+                    Variable(4, Integer) = Phi ( [Integer(0), 0], [Variable(5, Integer), 3], )
+                    Variable(1, Boolean) = Call id(1), args( )
+                    Branch Variable(1, Boolean), 2, 5
+                Block 2: Block:
+                    Jump(3)
+                Block 3: Block:
+                    Variable(5, Integer) = Phi ( [Variable(4, Integer), 2], [Variable(3, Integer), 4], )
+                    Variable(2, Boolean) = Call id(1), args( )
+                    Branch Variable(2, Boolean), 4, 1
+                Block 4: Block:
+                    Variable(3, Integer) = Add Variable(5, Integer), Integer(1)
+                    Jump(3)
+                Block 5: Block:
+                    Call id(2), args( Variable(4, Integer), )
+                    Return
+            config: Config:
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
+            num_qubits: 0
+            num_results: 0"#]]
+    .assert_eq(&program.to_string());
+}
+
+#[test]
+fn ssa_transform_inserts_phi_for_same_literal_stored_in_all_branches() {
+    let mut program = new_program();
+    program.callables.insert(
+        CallableId(1),
+        Callable {
+            name: "dynamic_bool".to_string(),
+            input_type: Vec::new(),
+            params: Vec::new(),
+            output_type: Some(Ty::Boolean),
+            body: None,
+            call_type: CallableType::Regular,
+        },
+    );
+
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(
+                CallableId(1),
+                Vec::new(),
+                Some(Variable {
+                    variable_id: VariableId(0),
+                    ty: Ty::Boolean,
+                }),
+            ),
+            Instruction::Branch(
+                Variable {
+                    variable_id: VariableId(0),
+                    ty: Ty::Boolean,
+                },
+                BlockId(1),
+                BlockId(2),
+            ),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Store(
+                Operand::Literal(Literal::Bool(true)),
+                Variable {
+                    variable_id: VariableId(1),
+                    ty: Ty::Boolean,
+                },
+            ),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![
+            Instruction::Store(
+                Operand::Literal(Literal::Bool(true)),
+                Variable {
+                    variable_id: VariableId(1),
+                    ty: Ty::Boolean,
+                },
+            ),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(3),
+        Block(vec![
+            Instruction::LogicalAnd(
+                Operand::Variable(Variable {
+                    variable_id: VariableId(1),
+                    ty: Ty::Boolean,
+                }),
+                Operand::Literal(Literal::Bool(false)),
+                Variable {
+                    variable_id: VariableId(2),
+                    ty: Ty::Boolean,
+                },
+            ),
+            Instruction::Branch(
+                Variable {
+                    variable_id: VariableId(1),
+                    ty: Ty::Boolean,
+                },
+                BlockId(4),
+                BlockId(4),
+            ),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(4), Block(vec![Instruction::Return]));
+
+    // Propagating the literal would leave an instruction with only literal operands and a branch on a literal, so
+    // the literal is assigned by a phi node like any other value.
+    transform_program(&mut program);
+    expect![[r#"
+        Program:
+            entry: 0
+            callables:
+                Callable 0: Callable:
+                    name: main
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: <VOID>
+                    body: 0
+                Callable 1: Callable:
+                    name: dynamic_bool
+                    call_type: Regular
+                    input_type: <VOID>
+                    output_type: Boolean
+                    body: <NONE>
+            blocks:
+                Block 0: Block:
+                    Variable(0, Boolean) = Call id(1), args( )
+                    Branch Variable(0, Boolean), 1, 2
+                Block 1: Block:
+                    Jump(3)
+                Block 2: Block:
+                    Jump(3)
+                Block 3: Block:
+                    Variable(3, Boolean) = Phi ( [Bool(true), 1], [Bool(true), 2], )
+                    Variable(2, Boolean) = LogicalAnd Variable(3, Boolean), Bool(false)
+                    Branch Variable(3, Boolean), 4, 4
+                Block 4: Block:
+                    Return
+            config: Config:
+                capabilities: TargetCapabilityFlags(Adaptive | IntegerComputations | FloatingPointComputations | BackwardsBranching | HigherLevelConstructs | QubitReset | DynamicQubitManagement)
             num_qubits: 0
             num_results: 0"#]].assert_eq(&program.to_string());
 }
@@ -7,6 +7,9 @@ use crate::rir::{Callable, Instruction, Operand, Program, Ty, Variable};
 mod tests;
 
 pub fn check_types(program: &Program) {
+    for (_, callable) in program.callables.iter() {
+        check_callable_param_types(callable);
+    }
     for (_, block) in program.blocks.iter() {
         for instr in &block.0 {
             check_instr_types(program, instr);
@@ -31,12 +34,45 @@ fn check_instr_types(program: &Program, instr: &Instruction) {
         | Instruction::LogicalOr(opr1, opr2, var)
         | Instruction::BitwiseAnd(opr1, opr2, var)
         | Instruction::BitwiseOr(opr1, opr2, var)
-        | Instruction::BitwiseXor(opr1, opr2, var)
-        | Instruction::Icmp(_, opr1, opr2, var) => {
+        | Instruction::BitwiseXor(opr1, opr2, var) => {
             assert_eq!(opr1.get_type(), opr2.get_type());
             assert_eq!(opr1.get_type(), var.ty);
         }
 
+        Instruction::Fadd(opr1, opr2, var)
+        | Instruction::Fsub(opr1, opr2, var)
+        | Instruction::Fmul(opr1, opr2, var)
+        | Instruction::Fdiv(opr1, opr2, var) => {
+            assert_eq!(opr1.get_type(), Ty::Double);
+            assert_eq!(opr2.get_type(), Ty::Double);
+            assert_eq!(var.ty, Ty::Double);
+        }
+
+        Instruction::Icmp(_, opr1, opr2, var) => {
+            assert_eq!(opr1.get_type(), opr2.get_type());
+            assert_eq!(var.ty, Ty::Boolean);
+        }
+
+        Instruction::Fcmp(_, opr1, opr2, var) => {
+            assert_eq!(opr1.get_type(), Ty::Double);
+            assert_eq!(opr2.get_type(), Ty::Double);
+            assert_eq!(var.ty, Ty::Boolean);
+        }
+
+        Instruction::Sitofp(opr, var) => {
+            assert_eq!(opr.get_type(), Ty::Integer);
+            assert_eq!(var.ty, Ty::Double);
+        }
+
+        Instruction::Fptosi(opr, var) => {
+            assert_eq!(opr.get_type(), Ty::Double);
+            assert_eq!(var.ty, Ty::Integer);
+        }
+
+        Instruction::Load(opr, _) => {
+            assert_eq!(opr.get_type(), Ty::Pointer);
+        }
+
         Instruction::Store(opr, var)
         | Instruction::LogicalNot(opr, var)
         | Instruction::BitwiseNot(opr, var) => {
@@ -53,6 +89,24 @@ fn check_instr_types(program: &Program, instr: &Instruction) {
     }
 }
 
+fn check_callable_param_types(callable: &Callable) {
+    if callable.body.is_none() {
+        assert!(
+            callable.params.is_empty(),
+            "intrinsics should not have params"
+        );
+        return;
+    }
+    assert_eq!(
+        callable.input_type.len(),
+        callable.params.len(),
+        "incorrect number of params"
+    );
+    for (param, ty) in callable.params.iter().zip(callable.input_type.iter()) {
+        assert_eq!(param.ty, *ty);
+    }
+}
+
 fn check_call_types(callable: &Callable, args: &[Operand], var: Option<Variable>) {
     assert_eq!(
         callable.input_type.len(),
@@ -2,11 +2,11 @@
 // Licensed under the MIT License.
 
 use crate::rir::{
-    BlockId, Callable, CallableId, CallableType, Instruction, Literal, Operand, Program, Ty,
-    Variable, VariableId,
+    BlockId, Callable, CallableId, CallableType, ConditionCode, FcmpConditionCode, Instruction,
+    Literal, Operand, Program, Ty, Variable, VariableId,
 };
 
-use super::check_instr_types;
+use super::{check_callable_param_types, check_instr_types};
 
 #[test]
 fn binop_instr_matching_types_passes_check() {
@@ -65,6 +65,116 @@ fn unop_instr_mismatching_types_fails_check() {
     );
 }
 
+#[test]
+fn double_binop_instr_matching_types_passes_check() {
+    let var = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Double,
+    };
+    let opr1 = Operand::Variable(var);
+    let opr2 = Operand::Literal(Literal::Double(1.0));
+
+    check_instr_types(&Program::new(), &Instruction::Fmul(opr1, opr2, var));
+}
+
+#[test]
+#[should_panic(expected = "assertion `left == right` failed")]
+fn double_binop_instr_integer_operands_fails_check() {
+    let var = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Integer,
+    };
+    let opr1 = Operand::Variable(var);
+    let opr2 = Operand::Literal(Literal::Integer(1));
+
+    check_instr_types(&Program::new(), &Instruction::Fadd(opr1, opr2, var));
+}
+
+#[test]
+fn icmp_instr_boolean_output_passes_check() {
+    let opr1 = Operand::Variable(Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Integer,
+    });
+    let opr2 = Operand::Literal(Literal::Integer(0));
+    let var = Variable {
+        variable_id: VariableId(1),
+        ty: Ty::Boolean,
+    };
+
+    check_instr_types(
+        &Program::new(),
+        &Instruction::Icmp(ConditionCode::Slt, opr1, opr2, var),
+    );
+}
+
+#[test]
+fn fcmp_instr_boolean_output_passes_check() {
+    let opr1 = Operand::Variable(Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Double,
+    });
+    let opr2 = Operand::Literal(Literal::Double(0.0));
+    let var = Variable {
+        variable_id: VariableId(1),
+        ty: Ty::Boolean,
+    };
+
+    check_instr_types(
+        &Program::new(),
+        &Instruction::Fcmp(FcmpConditionCode::OrderedAndLessThan, opr1, opr2, var),
+    );
+}
+
+#[test]
+#[should_panic(expected = "assertion `left == right` failed")]
+fn fcmp_instr_double_output_fails_check() {
+    let var = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Double,
+    };
+    let opr1 = Operand::Variable(var);
+    let opr2 = Operand::Literal(Literal::Double(0.0));
+
+    check_instr_types(
+        &Program::new(),
+        &Instruction::Fcmp(FcmpConditionCode::OrderedAndEqual, opr1, opr2, var),
+    );
+}
+
+#[test]
+fn conversion_instrs_matching_types_passes_check() {
+    let int_var = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Integer,
+    };
+    let double_var = Variable {
+        variable_id: VariableId(1),
+        ty: Ty::Double,
+    };
+
+    check_instr_types(
+        &Program::new(),
+        &Instruction::Sitofp(Operand::Variable(int_var), double_var),
+    );
+    check_instr_types(
+        &Program::new(),
+        &Instruction::Fptosi(Operand::Variable(double_var), int_var),
+    );
+}
+
+#[test]
+#[should_panic(expected = "assertion `left == right` failed")]
+fn sitofp_instr_double_operand_fails_check() {
+    let var = Variable {
+        variable_id: VariableId(0),
+        ty: Ty::Double,
+    };
+    let opr = Operand::Variable(var);
+
+    check_instr_types(&Program::new(), &Instruction::Sitofp(opr, var));
+}
+
 #[test]
 fn phi_instr_matching_types_passes_check() {
     let var = Variable {
@@ -114,6 +224,7 @@ fn call_instr_matching_types_passes_check() {
         Callable {
             name: "foo".to_string(),
             input_type: vec![Ty::Integer],
+            params: Vec::new(),
             output_type: Some(Ty::Integer),
             call_type: CallableType::Regular,
             body: None,
@@ -148,6 +259,7 @@ fn call_instr_mismatching_output_types_fails_check() {
         Callable {
             name: "foo".to_string(),
             input_type: vec![Ty::Integer],
+            params: Vec::new(),
             output_type: Some(Ty::Integer),
             call_type: CallableType::Regular,
             body: None,
@@ -176,6 +288,7 @@ fn call_instr_mismatching_input_types_fails_check() {
         Callable {
             name: "foo".to_string(),
             input_type: vec![Ty::Integer],
+            params: Vec::new(),
             output_type: Some(Ty::Integer),
             call_type: CallableType::Regular,
             body: None,
@@ -210,6 +323,7 @@ fn call_instr_too_many_args_fails_check() {
         Callable {
             name: "foo".to_string(),
             input_type: vec![Ty::Integer],
+            params: Vec::new(),
             output_type: Some(Ty::Integer),
             call_type: CallableType::Regular,
             body: None,
@@ -243,6 +357,7 @@ fn call_instr_no_return_type_no_output_var_passes_check() {
         Callable {
             name: "foo".to_string(),
             input_type: vec![Ty::Integer],
+            params: Vec::new(),
             output_type: None,
             call_type: CallableType::Regular,
             body: None,
@@ -269,6 +384,7 @@ fn call_instr_return_type_without_output_var_fails() {
         Callable {
             name: "foo".to_string(),
             input_type: vec![Ty::Integer],
+            params: Vec::new(),
             output_type: Some(Ty::Integer),
             call_type: CallableType::Regular,
             body: None,
@@ -295,6 +411,7 @@ fn call_instr_output_var_without_return_type_fails() {
         Callable {
             name: "foo".to_string(),
             input_type: vec![Ty::Integer],
+            params: Vec::new(),
             output_type: None,
             call_type: CallableType::Regular,
             body: None,
@@ -313,3 +430,40 @@ fn call_instr_output_var_without_return_type_fails() {
         ),
     );
 }
+
+#[test]
+fn callable_with_matching_param_types_passes_check() {
+    check_callable_param_types(&Callable {
+        name: "foo".to_string(),
+        input_type: vec![Ty::Qubit, Ty::Integer],
+        params: vec![
+            Variable {
+                variable_id: VariableId(0),
+                ty: Ty::Qubit,
+            },
+            Variable {
+                variable_id: VariableId(1),
+                ty: Ty::Integer,
+            },
+        ],
+        output_type: None,
+        call_type: CallableType::Regular,
+        body: Some(BlockId(1)),
+    });
+}
+
+#[test]
+#[should_panic(expected = "assertion `left == right` failed")]
+fn callable_with_mismatching_param_types_fails_check() {
+    check_callable_param_types(&Callable {
+        name: "foo".to_string(),
+        input_type: vec![Ty::Qubit],
+        params: vec![Variable {
+            variable_id: VariableId(0),
+            ty: Ty::Integer,
+        }],
+        output_type: None,
+        call_type: CallableType::Regular,
+        body: Some(BlockId(1)),
+    });
+}
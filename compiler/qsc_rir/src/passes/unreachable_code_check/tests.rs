@@ -81,6 +81,7 @@ fn test_check_unreachable_blocks_succeeds_on_no_unreachable_blocks() {
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -101,6 +102,7 @@ fn test_check_unreachable_blocks_panics_on_unreachable_block() {
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -123,6 +125,7 @@ fn test_check_unreachable_blocks_succeeds_on_no_unreachable_blocks_with_branch()
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -156,6 +159,7 @@ fn test_check_unreachable_blocks_succeeds_on_no_unreachable_blocks_with_jump() {
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -179,6 +183,7 @@ fn test_check_unreachable_blocks_panics_on_unreachable_block_with_branch() {
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -213,6 +218,7 @@ fn test_check_unreachable_callable_succeeds_on_no_unreachable_callables() {
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -234,6 +240,7 @@ fn test_check_unreachable_callable_panics_on_unreachable_callable() {
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -244,6 +251,7 @@ fn test_check_unreachable_callable_panics_on_unreachable_callable() {
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(1)),
             call_type: CallableType::Regular,
@@ -267,6 +275,7 @@ fn test_check_unreachable_callable_succeeds_on_no_unreachable_callables_with_cal
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -277,6 +286,7 @@ fn test_check_unreachable_callable_succeeds_on_no_unreachable_callables_with_cal
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(1)),
             call_type: CallableType::Regular,
@@ -301,6 +311,7 @@ fn test_check_unreachable_callable_succeeds_on_no_unreachable_callables_with_nes
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -311,6 +322,7 @@ fn test_check_unreachable_callable_succeeds_on_no_unreachable_callables_with_nes
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(1)),
             call_type: CallableType::Regular,
@@ -321,6 +333,7 @@ fn test_check_unreachable_callable_succeeds_on_no_unreachable_callables_with_nes
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: None,
             call_type: CallableType::Regular,
@@ -347,6 +360,7 @@ fn test_check_unreachable_callable_panics_on_unreachable_callable_with_nested_ca
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -357,6 +371,7 @@ fn test_check_unreachable_callable_panics_on_unreachable_callable_with_nested_ca
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(1)),
             call_type: CallableType::Regular,
@@ -367,6 +382,7 @@ fn test_check_unreachable_callable_panics_on_unreachable_callable_with_nested_ca
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: None,
             call_type: CallableType::Regular,
@@ -392,6 +408,7 @@ fn test_check_unreachable_callable_succeeds_on_no_unreachable_callables_with_cal
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: Some(BlockId(0)),
             call_type: CallableType::Regular,
@@ -402,6 +419,7 @@ fn test_check_unreachable_callable_succeeds_on_no_unreachable_callables_with_cal
         Callable {
             name: "test".to_string(),
             input_type: vec![],
+            params: Vec::new(),
             output_type: None,
             body: None,
             call_type: CallableType::Regular,
@@ -3,7 +3,10 @@
 
 use indenter::{indented, Indented};
 use qsc_data_structures::{index_map::IndexMap, target::TargetCapabilityFlags};
-use std::fmt::{self, Display, Formatter, Write};
+use std::{
+    fmt::{self, Display, Formatter, Write},
+    str::FromStr,
+};
 
 /// The root of the RIR.
 #[derive(Default)]
@@ -66,6 +69,7 @@ impl Program {
 #[derive(Default)]
 pub struct Config {
     pub capabilities: TargetCapabilityFlags,
+    pub optimization_level: OptimizationLevel,
 }
 
 impl Display for Config {
@@ -78,6 +82,9 @@ impl Display for Config {
         } else {
             write!(indent, "\ncapabilities: {:?}", self.capabilities)?;
         }
+        if self.optimization_level != OptimizationLevel::None {
+            write!(indent, "\noptimization_level: {}", self.optimization_level)?;
+        }
         Ok(())
     }
 }
@@ -89,6 +96,41 @@ impl Config {
     }
 }
 
+/// The set of optimization passes to run over a program.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OptimizationLevel {
+    /// No optimizations.
+    #[default]
+    None,
+    /// Classical optimizations: constant folding, copy propagation and dead-code elimination.
+    Basic,
+    /// Classical optimizations followed by peephole optimizations that cancel and merge adjacent gates.
+    Full,
+}
+
+impl Display for OptimizationLevel {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::Basic => write!(f, "Basic"),
+            Self::Full => write!(f, "Full"),
+        }
+    }
+}
+
+impl FromStr for OptimizationLevel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "None" | "none" => Ok(Self::None),
+            "Basic" | "basic" => Ok(Self::Basic),
+            "Full" | "full" => Ok(Self::Full),
+            _ => Err(()),
+        }
+    }
+}
+
 /// A unique identifier for a block in a RIR program.
 #[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, PartialOrd, Ord)]
 pub struct BlockId(pub u32);
@@ -162,6 +204,9 @@ pub struct Callable {
     pub name: String,
     /// The input type of the callable.
     pub input_type: Vec<Ty>,
+    /// The variables that hold the arguments within the callable body.
+    /// N.B. Intrinsics have no params.
+    pub params: Vec<Variable>,
     /// The output type of the callable.
     pub output_type: Option<Ty>,
     /// The callable body.
@@ -188,6 +233,14 @@ impl Display for Callable {
             }
             indent = set_indentation(indent, 1);
         }
+        if !self.params.is_empty() {
+            write!(indent, "\nparams:")?;
+            indent = set_indentation(indent, 2);
+            for (index, param) in self.params.iter().enumerate() {
+                write!(indent, "\n[{index}]: {param}")?;
+            }
+            indent = set_indentation(indent, 1);
+        }
         write!(indent, "\noutput_type:")?;
         if let Some(output_type) = &self.output_type {
             write!(indent, " {output_type}")?;
@@ -238,6 +291,50 @@ impl Display for ConditionCode {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FcmpConditionCode {
+    False,
+    OrderedAndEqual,
+    OrderedAndGreaterThan,
+    OrderedAndGreaterThanOrEqual,
+    OrderedAndLessThan,
+    OrderedAndLessThanOrEqual,
+    OrderedAndNotEqual,
+    Ordered,
+    UnorderedOrEqual,
+    UnorderedOrGreaterThan,
+    UnorderedOrGreaterThanOrEqual,
+    UnorderedOrLessThan,
+    UnorderedOrLessThanOrEqual,
+    UnorderedOrNotEqual,
+    Unordered,
+    True,
+}
+
+impl Display for FcmpConditionCode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self {
+            Self::False => write!(f, "False")?,
+            Self::OrderedAndEqual => write!(f, "Oeq")?,
+            Self::OrderedAndGreaterThan => write!(f, "Ogt")?,
+            Self::OrderedAndGreaterThanOrEqual => write!(f, "Oge")?,
+            Self::OrderedAndLessThan => write!(f, "Olt")?,
+            Self::OrderedAndLessThanOrEqual => write!(f, "Ole")?,
+            Self::OrderedAndNotEqual => write!(f, "One")?,
+            Self::Ordered => write!(f, "Ord")?,
+            Self::UnorderedOrEqual => write!(f, "Ueq")?,
+            Self::UnorderedOrGreaterThan => write!(f, "Ugt")?,
+            Self::UnorderedOrGreaterThanOrEqual => write!(f, "Uge")?,
+            Self::UnorderedOrLessThan => write!(f, "Ult")?,
+            Self::UnorderedOrLessThanOrEqual => write!(f, "Ule")?,
+            Self::UnorderedOrNotEqual => write!(f, "Une")?,
+            Self::Unordered => write!(f, "Uno")?,
+            Self::True => write!(f, "True")?,
+        };
+        Ok(())
+    }
+}
+
 impl Display for CallableType {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match &self {
@@ -265,6 +362,14 @@ pub enum Instruction {
     Shl(Operand, Operand, Variable),
     Ashr(Operand, Operand, Variable),
     Icmp(ConditionCode, Operand, Operand, Variable),
+    Fadd(Operand, Operand, Variable),
+    Fsub(Operand, Operand, Variable),
+    Fmul(Operand, Operand, Variable),
+    Fdiv(Operand, Operand, Variable),
+    Fcmp(FcmpConditionCode, Operand, Operand, Variable),
+    Sitofp(Operand, Variable),
+    Fptosi(Operand, Variable),
+    Load(Operand, Variable),
     LogicalNot(Operand, Variable),
     LogicalAnd(Operand, Operand, Variable),
     LogicalOr(Operand, Operand, Variable),
@@ -343,6 +448,18 @@ impl Display for Instruction {
             Ok(())
         }
 
+        fn write_fcmp_instruction(
+            f: &mut Formatter,
+            condition: FcmpConditionCode,
+            lhs: &Operand,
+            rhs: &Operand,
+            variable: Variable,
+        ) -> fmt::Result {
+            let mut indent = set_indentation(indented(f), 0);
+            write!(indent, "{variable} = Fcmp {condition}, {lhs}, {rhs}")?;
+            Ok(())
+        }
+
         fn write_phi_instruction(
             f: &mut Formatter,
             args: &[(Operand, BlockId)],
@@ -411,6 +528,30 @@ impl Display for Instruction {
             Self::Icmp(op, lhs, rhs, variable) => {
                 write_icmp_instruction(f, *op, lhs, rhs, *variable)?;
             }
+            Self::Fadd(lhs, rhs, variable) => {
+                write_binary_instruction(f, "Fadd", lhs, rhs, *variable)?;
+            }
+            Self::Fsub(lhs, rhs, variable) => {
+                write_binary_instruction(f, "Fsub", lhs, rhs, *variable)?;
+            }
+            Self::Fmul(lhs, rhs, variable) => {
+                write_binary_instruction(f, "Fmul", lhs, rhs, *variable)?;
+            }
+            Self::Fdiv(lhs, rhs, variable) => {
+                write_binary_instruction(f, "Fdiv", lhs, rhs, *variable)?;
+            }
+            Self::Fcmp(op, lhs, rhs, variable) => {
+                write_fcmp_instruction(f, *op, lhs, rhs, *variable)?;
+            }
+            Self::Sitofp(value, variable) => {
+                write_unary_instruction(f, "Sitofp", value, *variable)?;
+            }
+            Self::Fptosi(value, variable) => {
+                write_unary_instruction(f, "Fptosi", value, *variable)?;
+            }
+            Self::Load(value, variable) => {
+                write_unary_instruction(f, "Load", value, *variable)?;
+            }
             Self::Phi(args, variable) => {
                 write_phi_instruction(f, args, *variable)?;
             }
@@ -1,9 +1,9 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::rir::{Block, BlockId, Instruction, Program, VariableId};
+use crate::rir::{Block, BlockId, Instruction, Operand, Program, Variable, VariableId};
 use qsc_data_structures::index_map::IndexMap;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 /// Given a block, return the block IDs of its successors.
 #[must_use]
@@ -74,41 +74,149 @@ pub fn get_variable_assignments(program: &Program) -> IndexMap<VariableId, (Bloc
     let mut assignments = IndexMap::default();
     for (block_id, block) in program.blocks.iter() {
         for (idx, instr) in block.0.iter().enumerate() {
-            match instr {
-                Instruction::Call(_, _, Some(var))
-                | Instruction::Add(_, _, var)
-                | Instruction::Sub(_, _, var)
-                | Instruction::Mul(_, _, var)
-                | Instruction::Sdiv(_, _, var)
-                | Instruction::Srem(_, _, var)
-                | Instruction::Shl(_, _, var)
-                | Instruction::Ashr(_, _, var)
-                | Instruction::Icmp(_, _, _, var)
-                | Instruction::LogicalNot(_, var)
-                | Instruction::LogicalAnd(_, _, var)
-                | Instruction::LogicalOr(_, _, var)
-                | Instruction::BitwiseNot(_, var)
-                | Instruction::BitwiseAnd(_, _, var)
-                | Instruction::BitwiseOr(_, _, var)
-                | Instruction::BitwiseXor(_, _, var)
-                | Instruction::Phi(_, var) => {
-                    assert!(
-                        !assignments.contains_key(var.variable_id),
-                        "Duplicate assignment to {:?} in {block_id:?}, instruction {idx}",
-                        var.variable_id
-                    );
-                    assignments.insert(var.variable_id, (block_id, idx));
-                }
-                Instruction::Call(_, _, None)
-                | Instruction::Jump(..)
-                | Instruction::Branch(..)
-                | Instruction::Return => {}
+            if let Instruction::Store(..) = instr {
+                panic!("Unexpected Store at {block_id:?}, instruction {idx}")
+            }
+            if let Some(var) = get_assigned_variable(instr) {
+                assert!(
+                    !assignments.contains_key(var.variable_id),
+                    "Duplicate assignment to {:?} in {block_id:?}, instruction {idx}",
+                    var.variable_id
+                );
+                assignments.insert(var.variable_id, (block_id, idx));
+            }
+        }
+    }
+    assignments
+}
+
+/// Given an instruction, return the variable it assigns, if any.
+#[must_use]
+pub fn get_assigned_variable(instr: &Instruction) -> Option<Variable> {
+    match instr {
+        Instruction::Store(_, var)
+        | Instruction::Call(_, _, Some(var))
+        | Instruction::Add(_, _, var)
+        | Instruction::Sub(_, _, var)
+        | Instruction::Mul(_, _, var)
+        | Instruction::Sdiv(_, _, var)
+        | Instruction::Srem(_, _, var)
+        | Instruction::Shl(_, _, var)
+        | Instruction::Ashr(_, _, var)
+        | Instruction::Icmp(_, _, _, var)
+        | Instruction::Fadd(_, _, var)
+        | Instruction::Fsub(_, _, var)
+        | Instruction::Fmul(_, _, var)
+        | Instruction::Fdiv(_, _, var)
+        | Instruction::Fcmp(_, _, _, var)
+        | Instruction::Sitofp(_, var)
+        | Instruction::Fptosi(_, var)
+        | Instruction::Load(_, var)
+        | Instruction::LogicalNot(_, var)
+        | Instruction::LogicalAnd(_, _, var)
+        | Instruction::LogicalOr(_, _, var)
+        | Instruction::BitwiseNot(_, var)
+        | Instruction::BitwiseAnd(_, _, var)
+        | Instruction::BitwiseOr(_, _, var)
+        | Instruction::BitwiseXor(_, _, var)
+        | Instruction::Phi(_, var) => Some(*var),
+        Instruction::Call(_, _, None)
+        | Instruction::Jump(..)
+        | Instruction::Branch(..)
+        | Instruction::Return => None,
+    }
+}
 
-                Instruction::Store(..) => {
-                    panic!("Unexpected Store at {block_id:?}, instruction {idx}")
+/// Given an instruction, return its operands.
+/// N.B. the condition of a branch is a variable rather than an operand, so it is not included.
+#[must_use]
+pub fn get_operands(instr: &Instruction) -> Vec<Operand> {
+    match instr {
+        Instruction::Store(opr, _)
+        | Instruction::LogicalNot(opr, _)
+        | Instruction::BitwiseNot(opr, _)
+        | Instruction::Sitofp(opr, _)
+        | Instruction::Fptosi(opr, _)
+        | Instruction::Load(opr, _) => vec![*opr],
+        Instruction::Add(lhs, rhs, _)
+        | Instruction::Sub(lhs, rhs, _)
+        | Instruction::Mul(lhs, rhs, _)
+        | Instruction::Sdiv(lhs, rhs, _)
+        | Instruction::Srem(lhs, rhs, _)
+        | Instruction::Shl(lhs, rhs, _)
+        | Instruction::Ashr(lhs, rhs, _)
+        | Instruction::Icmp(_, lhs, rhs, _)
+        | Instruction::Fadd(lhs, rhs, _)
+        | Instruction::Fsub(lhs, rhs, _)
+        | Instruction::Fmul(lhs, rhs, _)
+        | Instruction::Fdiv(lhs, rhs, _)
+        | Instruction::Fcmp(_, lhs, rhs, _)
+        | Instruction::LogicalAnd(lhs, rhs, _)
+        | Instruction::LogicalOr(lhs, rhs, _)
+        | Instruction::BitwiseAnd(lhs, rhs, _)
+        | Instruction::BitwiseOr(lhs, rhs, _)
+        | Instruction::BitwiseXor(lhs, rhs, _) => vec![*lhs, *rhs],
+        Instruction::Call(_, args, _) => args.clone(),
+        Instruction::Phi(args, _) => args.iter().map(|(opr, _)| *opr).collect(),
+        Instruction::Jump(..) | Instruction::Branch(..) | Instruction::Return => Vec::new(),
+    }
+}
+
+/// Given an instruction, return mutable references to its operands.
+/// N.B. the condition of a branch is a variable rather than an operand, so it is not included.
+pub fn get_operands_mut(instr: &mut Instruction) -> Vec<&mut Operand> {
+    match instr {
+        Instruction::Store(opr, _)
+        | Instruction::LogicalNot(opr, _)
+        | Instruction::BitwiseNot(opr, _)
+        | Instruction::Sitofp(opr, _)
+        | Instruction::Fptosi(opr, _)
+        | Instruction::Load(opr, _) => vec![opr],
+        Instruction::Add(lhs, rhs, _)
+        | Instruction::Sub(lhs, rhs, _)
+        | Instruction::Mul(lhs, rhs, _)
+        | Instruction::Sdiv(lhs, rhs, _)
+        | Instruction::Srem(lhs, rhs, _)
+        | Instruction::Shl(lhs, rhs, _)
+        | Instruction::Ashr(lhs, rhs, _)
+        | Instruction::Icmp(_, lhs, rhs, _)
+        | Instruction::Fadd(lhs, rhs, _)
+        | Instruction::Fsub(lhs, rhs, _)
+        | Instruction::Fmul(lhs, rhs, _)
+        | Instruction::Fdiv(lhs, rhs, _)
+        | Instruction::Fcmp(_, lhs, rhs, _)
+        | Instruction::LogicalAnd(lhs, rhs, _)
+        | Instruction::LogicalOr(lhs, rhs, _)
+        | Instruction::BitwiseAnd(lhs, rhs, _)
+        | Instruction::BitwiseOr(lhs, rhs, _)
+        | Instruction::BitwiseXor(lhs, rhs, _) => vec![lhs, rhs],
+        Instruction::Call(_, args, _) => args.iter_mut().collect(),
+        Instruction::Phi(args, _) => args.iter_mut().map(|(opr, _)| opr).collect(),
+        Instruction::Jump(..) | Instruction::Branch(..) | Instruction::Return => Vec::new(),
+    }
+}
+
+/// Replace every use of the variables in the given map with the operand they map to.
+/// Branch conditions can only be replaced by other variables, so uses in branches that map to literals are left as is.
+pub(crate) fn replace_variable_uses(
+    program: &mut Program,
+    replacements: &FxHashMap<VariableId, Operand>,
+) {
+    for block in program.blocks.values_mut() {
+        for instr in &mut block.0 {
+            if let Instruction::Branch(cond, _, _) = instr {
+                if let Some(Operand::Variable(var)) = replacements.get(&cond.variable_id) {
+                    *cond = *var;
+                }
+                continue;
+            }
+            for opr in get_operands_mut(instr) {
+                if let Operand::Variable(var) = opr {
+                    if let Some(replacement) = replacements.get(&var.variable_id) {
+                        *opr = *replacement;
+                    }
                 }
             }
         }
     }
-    assignments
 }
@@ -0,0 +1,152 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use num_bigint::BigUint;
+use num_complex::Complex;
+use qsc::{Backend, MeasurementReplay};
+use qsc_eval::val::Value;
+
+/// Backend wrapper that performs every reset as a measurement followed by a correction, so that the wrapped
+/// [`MeasurementReplay`] records and replays its outcome like that of any other measurement. Resetting a qubit that is
+/// entangled with others collapses their state too, so both paths must agree on the outcome of every reset.
+pub struct ReplayedResets<T> {
+    pub backend: MeasurementReplay<T>,
+}
+
+impl<T> ReplayedResets<T>
+where
+    T: Backend<ResultType = bool>,
+{
+    pub fn new(backend: T) -> Self {
+        Self {
+            backend: MeasurementReplay::new(backend),
+        }
+    }
+}
+
+impl<T> Backend for ReplayedResets<T>
+where
+    T: Backend<ResultType = bool>,
+{
+    type ResultType = bool;
+
+    fn ccx(&mut self, ctl0: usize, ctl1: usize, q: usize) {
+        self.backend.ccx(ctl0, ctl1, q);
+    }
+
+    fn cx(&mut self, ctl: usize, q: usize) {
+        self.backend.cx(ctl, q);
+    }
+
+    fn cy(&mut self, ctl: usize, q: usize) {
+        self.backend.cy(ctl, q);
+    }
+
+    fn cz(&mut self, ctl: usize, q: usize) {
+        self.backend.cz(ctl, q);
+    }
+
+    fn h(&mut self, q: usize) {
+        self.backend.h(q);
+    }
+
+    fn m(&mut self, q: usize) -> Self::ResultType {
+        self.backend.m(q)
+    }
+
+    fn mresetz(&mut self, q: usize) -> Self::ResultType {
+        self.backend.mresetz(q)
+    }
+
+    fn m_forced(&mut self, q: usize, result: bool) -> Result<Self::ResultType, String> {
+        self.backend.m_forced(q, result)
+    }
+
+    fn reset(&mut self, q: usize) {
+        self.backend.mresetz(q);
+    }
+
+    fn rx(&mut self, theta: f64, q: usize) {
+        self.backend.rx(theta, q);
+    }
+
+    fn rxx(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.backend.rxx(theta, q0, q1);
+    }
+
+    fn ry(&mut self, theta: f64, q: usize) {
+        self.backend.ry(theta, q);
+    }
+
+    fn ryy(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.backend.ryy(theta, q0, q1);
+    }
+
+    fn rz(&mut self, theta: f64, q: usize) {
+        self.backend.rz(theta, q);
+    }
+
+    fn rzz(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.backend.rzz(theta, q0, q1);
+    }
+
+    fn sadj(&mut self, q: usize) {
+        self.backend.sadj(q);
+    }
+
+    fn s(&mut self, q: usize) {
+        self.backend.s(q);
+    }
+
+    fn swap(&mut self, q0: usize, q1: usize) {
+        self.backend.swap(q0, q1);
+    }
+
+    fn tadj(&mut self, q: usize) {
+        self.backend.tadj(q);
+    }
+
+    fn t(&mut self, q: usize) {
+        self.backend.t(q);
+    }
+
+    fn x(&mut self, q: usize) {
+        self.backend.x(q);
+    }
+
+    fn y(&mut self, q: usize) {
+        self.backend.y(q);
+    }
+
+    fn z(&mut self, q: usize) {
+        self.backend.z(q);
+    }
+
+    fn qubit_allocate(&mut self) -> usize {
+        self.backend.qubit_allocate()
+    }
+
+    fn qubit_release(&mut self, q: usize) {
+        self.backend.qubit_release(q);
+    }
+
+    fn capture_quantum_state(&mut self) -> (Vec<(BigUint, Complex<f64>)>, usize) {
+        self.backend.capture_quantum_state()
+    }
+
+    fn qubit_is_zero(&mut self, q: usize) -> bool {
+        self.backend.qubit_is_zero(q)
+    }
+
+    fn custom_intrinsic(&mut self, name: &str, arg: Value) -> Option<Result<Value, String>> {
+        self.backend.custom_intrinsic(name, arg)
+    }
+
+    fn take_failure(&mut self) -> Option<String> {
+        self.backend.take_failure()
+    }
+
+    fn set_seed(&mut self, seed: Option<u64>) {
+        self.backend.set_seed(seed);
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! The grammar of the random Q# programs used for differential testing, shared by the `partial_eval` fuzz target,
+//! which draws its choices from the fuzzer's input, and by the tests, which draw them from a seeded generator.
+//!
+//! The grammar describes the subset of Q# that partial evaluation supports for [`crate::CAPABILITIES`]:
+//!
+//! ```text
+//! program    ::= qubit{1..4} statement{4..15} (measure-and-reset each qubit) return
+//! statement  ::= gate | rotation | two-qubit-gate | reset
+//!              | measure                             (top level only)
+//!              | "let" int "=" "if" condition "{" int-expr "}" "else" "{" int-expr "}"   (top level only)
+//!              | "let" bool "=" bool-expr            (top level only)
+//!              | "let" bool "=" "if" condition "{" ("true" | "false") "}" "else" "{" ("true" | "false") "}"
+//!                                                    (top level only)
+//!              | "if" condition "{" statement{1..3} "}" ["else" "{" statement{1..3} "}"]
+//!              | "if" "M(" qubit ")" "==" "One" "{" statement{1..3} "}"
+//! condition  ::= result "==" ("Zero" | "One") | bool-expr | "M(" qubit ")" "==" "One"
+//! int-expr   ::= int | "0".."4"
+//! bool-expr  ::= int ("==" | "!=") "0".."4" | bool ("==" | "!=") bool | result "==" ("Zero" | "One")
+//!              | bool | "true" | "false"
+//! return     ::= "return" "(" "[" result* "]" "," int-expr "," bool-expr ")"
+//! ```
+//!
+//! Values are only bound at the top level of the operation, so every value in scope can be used by later statements,
+//! and branches are at most two levels deep. Every choice made while expanding the grammar reads the next byte of the
+//! input, and once the input runs out every choice is the first alternative, so any input produces a valid program.
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use std::fmt::Write;
+
+const SINGLE_QUBIT_GATES: &[&str] = &["H", "X", "Y", "Z", "S", "T", "Adjoint S", "Adjoint T"];
+const TWO_QUBIT_GATES: &[&str] = &["CNOT", "CY", "CZ", "SWAP"];
+const ROTATIONS: &[&str] = &["Rx", "Ry", "Rz"];
+const ANGLES: &[&str] = &[
+    "0.25",
+    "0.5",
+    "1.0",
+    "1.5707963267948966",
+    "3.141592653589793",
+];
+const MAX_DEPTH: usize = 2;
+const SEEDED_INPUT_LEN: usize = 1024;
+
+/// Generates the source of a program from the grammar, making each choice with the next byte of the input.
+#[must_use]
+pub fn generate(data: &[u8]) -> String {
+    let mut generator = Generator {
+        data: data.iter(),
+        qubits: 0,
+        results: 0,
+        ints: 0,
+        bools: 0,
+        source: String::new(),
+        indent: 2,
+    };
+    generator.program();
+    generator.source
+}
+
+/// Generates the source of a random program from the grammar, deterministically for the given seed.
+#[must_use]
+pub fn generate_seeded(seed: u64) -> String {
+    let mut data = vec![0; SEEDED_INPUT_LEN];
+    StdRng::seed_from_u64(seed).fill_bytes(&mut data);
+    generate(&data)
+}
+
+struct Generator<'a> {
+    data: std::slice::Iter<'a, u8>,
+    qubits: usize,
+    results: usize,
+    ints: usize,
+    bools: usize,
+    source: String,
+    indent: usize,
+}
+
+impl Generator<'_> {
+    fn program(&mut self) {
+        self.qubits = 1 + self.choose(4);
+        self.source
+            .push_str("namespace Test {\n    @EntryPoint()\n");
+        self.source
+            .push_str("    operation Main() : (Result[], Int, Bool) {\n");
+        for q in 0..self.qubits {
+            self.line(&format!("use q{q} = Qubit();"));
+        }
+
+        for _ in 0..4 + self.choose(12) {
+            self.statement(0);
+        }
+
+        let mut outputs = (0..self.results)
+            .map(|r| format!("r{r}"))
+            .collect::<Vec<_>>();
+        for q in 0..self.qubits {
+            self.line(&format!("let m{q} = MResetZ(q{q});"));
+            outputs.push(format!("m{q}"));
+        }
+        let int = self.int_expr();
+        let bool = self.bool_expr();
+        self.line(&format!(
+            "return ([{}], {int}, {bool});",
+            outputs.join(", ")
+        ));
+        self.source.push_str("    }\n}\n");
+    }
+
+    fn statement(&mut self, depth: usize) {
+        let choice = if depth < MAX_DEPTH {
+            self.choose(10)
+        } else {
+            self.choose(5)
+        };
+        match choice {
+            0 | 1 => {
+                let gate = self.pick(SINGLE_QUBIT_GATES);
+                let q = self.qubit();
+                self.line(&format!("{gate}(q{q});"));
+            }
+            2 => {
+                let rotation = self.pick(ROTATIONS);
+                let angle = self.pick(ANGLES);
+                let q = self.qubit();
+                self.line(&format!("{rotation}({angle}, q{q});"));
+            }
+            3 if self.qubits > 1 => {
+                let gate = self.pick(TWO_QUBIT_GATES);
+                let (a, b) = self.qubit_pair();
+                self.line(&format!("{gate}(q{a}, q{b});"));
+            }
+            3 | 4 => {
+                let q = self.qubit();
+                self.line(&format!("Reset(q{q});"));
+            }
+            5 if depth == 0 => {
+                let q = self.qubit();
+                let measurement = self.pick(&["M", "MResetZ"]);
+                self.line(&format!("let r{} = {measurement}(q{q});", self.results));
+                self.results += 1;
+            }
+            6 if depth == 0 => {
+                let condition = self.condition();
+                let then = self.int_expr();
+                let otherwise = self.int_expr();
+                self.line(&format!(
+                    "let c{} = if {condition} {{ {then} }} else {{ {otherwise} }};",
+                    self.ints
+                ));
+                self.ints += 1;
+            }
+            7 if depth == 0 => {
+                let value = if self.choose(2) == 1 {
+                    let condition = self.condition();
+                    let then = self.pick(&["true", "false"]);
+                    let otherwise = self.pick(&["true", "false"]);
+                    format!("if {condition} {{ {then} }} else {{ {otherwise} }}")
+                } else {
+                    self.bool_expr()
+                };
+                self.line(&format!("let b{} = {value};", self.bools));
+                self.bools += 1;
+            }
+            5..=8 => {
+                let condition = self.condition();
+                self.line(&format!("if {condition} {{"));
+                self.block(depth);
+                if self.choose(2) == 1 {
+                    self.line("} else {");
+                    self.block(depth);
+                }
+                self.line("}");
+            }
+            _ => {
+                let q = self.qubit();
+                self.line(&format!("if M(q{q}) == One {{"));
+                self.block(depth);
+                self.line("}");
+            }
+        }
+    }
+
+    fn condition(&mut self) -> String {
+        match self.choose(4) {
+            0 if self.results > 0 => {
+                let r = self.choose(self.results);
+                let value = self.pick(&["Zero", "One"]);
+                format!("r{r} == {value}")
+            }
+            1 if self.ints > 0 || self.bools > 0 => self.bool_expr(),
+            _ => {
+                let q = self.qubit();
+                format!("M(q{q}) == One")
+            }
+        }
+    }
+
+    fn int_expr(&mut self) -> String {
+        if self.ints > 0 && self.choose(2) == 1 {
+            format!("c{}", self.choose(self.ints))
+        } else {
+            self.choose(5).to_string()
+        }
+    }
+
+    fn bool_expr(&mut self) -> String {
+        match self.choose(4) {
+            0 if self.ints > 0 => {
+                let c = self.choose(self.ints);
+                let op = self.pick(&["==", "!="]);
+                format!("c{c} {op} {}", self.choose(5))
+            }
+            1 if self.bools > 1 => {
+                let (a, b) = (self.choose(self.bools), self.choose(self.bools));
+                let op = self.pick(&["==", "!="]);
+                format!("b{a} {op} b{b}")
+            }
+            2 if self.results > 0 => {
+                let r = self.choose(self.results);
+                let value = self.pick(&["Zero", "One"]);
+                format!("r{r} == {value}")
+            }
+            _ if self.bools > 0 => format!("b{}", self.choose(self.bools)),
+            _ => self.pick(&["true", "false"]).to_string(),
+        }
+    }
+
+    fn block(&mut self, depth: usize) {
+        self.indent += 1;
+        for _ in 0..=self.choose(3) {
+            self.statement(depth + 1);
+        }
+        self.indent -= 1;
+    }
+
+    fn line(&mut self, line: &str) {
+        let indent = "    ".repeat(self.indent);
+        writeln!(self.source, "{indent}{line}").expect("writing to a string should succeed");
+    }
+
+    /// Chooses one of `count` alternatives using the next byte of the input, or the first alternative once the input
+    /// runs out.
+    fn choose(&mut self, count: usize) -> usize {
+        self.data
+            .next()
+            .map_or(0, |byte| usize::from(*byte) % count)
+    }
+
+    fn pick<'a>(&mut self, choices: &[&'a str]) -> &'a str {
+        choices[self.choose(choices.len())]
+    }
+
+    fn qubit(&mut self) -> usize {
+        self.choose(self.qubits)
+    }
+
+    fn qubit_pair(&mut self) -> (usize, usize) {
+        let a = self.qubit();
+        let b = (a + 1 + self.choose(self.qubits - 1)) % self.qubits;
+        (a, b)
+    }
+}
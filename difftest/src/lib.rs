@@ -0,0 +1,181 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Differential testing of the evaluator against partial evaluation.
+//!
+//! Each program is first run by the evaluator on a seeded simulator, recording the outcome of every
+//! measurement. The program is then partially evaluated into RIR, and the RIR is executed on a fresh
+//! simulator that is forced to produce the recorded outcomes. Given the same outcomes, both paths must
+//! perform the same measurements and produce the same output. Resets are measured too, since resetting a qubit that
+//! is entangled with others collapses their state.
+
+mod backend;
+pub mod generator;
+
+#[cfg(test)]
+mod tests;
+
+use backend::ReplayedResets;
+use qsc::{
+    codegen::{get_rir, OptimizationLevel},
+    interpret::{output::GenericReceiver, Interpreter},
+    LanguageFeatures, PackageType, SourceContents, SourceMap, SourceName, SparseSim,
+    TargetCapabilityFlags,
+};
+use qsc_eval::output::{output_records, OutputRecord};
+use std::{
+    fmt::{self, Display, Formatter},
+    panic,
+};
+
+/// The capabilities programs are compiled for, on both paths.
+pub const CAPABILITIES: TargetCapabilityFlags = TargetCapabilityFlags::Adaptive
+    .union(TargetCapabilityFlags::IntegerComputations)
+    .union(TargetCapabilityFlags::FloatingPointComputations)
+    .union(TargetCapabilityFlags::QubitReset);
+
+/// The reason a program could not be run through both paths.
+#[derive(Debug)]
+pub enum Error {
+    /// The program does not compile for [`CAPABILITIES`].
+    Compile(String),
+    /// The evaluator failed, or produced a value that has no output records.
+    Eval(String),
+    /// Partial evaluation failed.
+    PartialEval(String),
+    /// Executing the RIR program failed.
+    Exec(String),
+    /// Either path panicked.
+    Panic(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Compile(message) => write!(f, "compilation failed: {message}"),
+            Error::Eval(message) => write!(f, "evaluation failed: {message}"),
+            Error::PartialEval(message) => write!(f, "partial evaluation failed: {message}"),
+            Error::Exec(message) => write!(f, "RIR execution failed: {message}"),
+            Error::Panic(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+/// The measurements and output of a program on both paths.
+#[derive(Debug)]
+pub struct Comparison {
+    pub evaluated_measurements: Vec<bool>,
+    pub evaluated_output: Vec<OutputRecord>,
+    pub executed_measurements: Vec<bool>,
+    pub executed_output: Vec<OutputRecord>,
+}
+
+impl Comparison {
+    /// Whether both paths performed the same measurements and produced the same output.
+    #[must_use]
+    pub fn is_match(&self) -> bool {
+        self.evaluated_measurements == self.executed_measurements
+            && self.evaluated_output == self.executed_output
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "evaluated measurements: {:?}",
+            self.evaluated_measurements
+        )?;
+        writeln!(
+            f,
+            "executed measurements:  {:?}",
+            self.executed_measurements
+        )?;
+        writeln!(f, "evaluated output:")?;
+        for record in &self.evaluated_output {
+            writeln!(f, "    {record}")?;
+        }
+        writeln!(f, "executed output:")?;
+        for record in &self.executed_output {
+            writeln!(f, "    {record}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the program made of the given sources through the evaluator, using the given seed, and
+/// through partial evaluation followed by RIR execution, forcing the same measurement outcomes.
+/// # Errors
+/// Returns an error if either path fails or panics before producing output.
+pub fn run(sources: &[(SourceName, SourceContents)], seed: u64) -> Result<Comparison, Error> {
+    panic::catch_unwind(|| run_both(sources, seed)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(Error::Panic(message))
+    })
+}
+
+fn run_both(sources: &[(SourceName, SourceContents)], seed: u64) -> Result<Comparison, Error> {
+    let source_map = || SourceMap::new(sources.iter().cloned(), None);
+
+    let mut interpreter = Interpreter::new(
+        true,
+        source_map(),
+        PackageType::Exe,
+        CAPABILITIES,
+        LanguageFeatures::default(),
+    )
+    .map_err(|errors| Error::Compile(join(errors)))?;
+    interpreter.set_quantum_seed(Some(seed));
+    interpreter.set_classical_seed(Some(seed));
+    let mut sink = std::io::sink();
+    let mut sim = ReplayedResets::new(SparseSim::new());
+    let value = interpreter
+        .eval_entry_with_sim(&mut sim, &mut GenericReceiver::new(&mut sink))
+        .map_err(|errors| Error::Eval(join(errors)))?;
+    let evaluated_output = output_records(&value)
+        .ok_or_else(|| Error::Eval(format!("value `{value}` has no output records")))?;
+    let evaluated_measurements = sim.backend.recorded().to_vec();
+
+    let program = get_rir(
+        &[],
+        source_map(),
+        LanguageFeatures::default(),
+        CAPABILITIES,
+        OptimizationLevel::Basic,
+    )
+    .map_err(Error::PartialEval)?;
+    let mut sim = ReplayedResets::new(SparseSim::new());
+    sim.backend.set_replay(evaluated_measurements.clone());
+    let executed_output =
+        qsc_eval::rir::exec(&program, &mut sim, &mut GenericReceiver::new(&mut sink))
+            .map_err(|error| Error::Exec(error.to_string()))?;
+
+    Ok(Comparison {
+        evaluated_measurements,
+        evaluated_output,
+        executed_measurements: sim.backend.recorded().to_vec(),
+        executed_output,
+    })
+}
+
+/// Joins the messages of the given errors, each followed by the messages of its sources.
+fn join(errors: impl IntoIterator<Item = impl std::error::Error>) -> String {
+    errors
+        .into_iter()
+        .map(|error| {
+            let mut message = error.to_string();
+            let mut source = error.source();
+            while let Some(error) = source {
+                message.push_str(": ");
+                message.push_str(&error.to_string());
+                source = error.source();
+            }
+            message
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
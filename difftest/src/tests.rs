@@ -0,0 +1,223 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::{generator::generate_seeded, run, Error};
+use qsc::{SourceContents, SourceName};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const SEEDS: u64 = 2;
+const GENERATED_PROGRAMS: u64 = 64;
+
+/// Samples that are not run through both paths, with the reason for each.
+const SKIPPED_SAMPLES: &[(&str, &str)] = &[(
+    "estimation",
+    "resource estimation samples are too large to simulate",
+)];
+
+/// Samples that use features one of the paths does not support for [`crate::CAPABILITIES`], with
+/// the error they are expected to fail with.
+const UNSUPPORTED_SAMPLES: &[(&str, &str)] = &[
+    (
+        "algorithms/BellState.qs",
+        "cannot call a function or operation that can only be resolved at runtime",
+    ),
+    ("algorithms/BernsteinVazirani.qs", "cannot use a closure"),
+    (
+        "algorithms/BernsteinVaziraniNISQ.qs",
+        "cannot use a closure",
+    ),
+    ("algorithms/BitFlipCode.qs", "`DrawRandomInt` not found"),
+    ("algorithms/CatState.qs", "`CheckAllZero` not found"),
+    ("algorithms/DeutschJozsa.qs", "has no output records"),
+    (
+        "algorithms/DeutschJozsaNISQ.qs",
+        "cannot call a function or operation that can only be resolved at runtime",
+    ),
+    ("algorithms/GHZ.qs", "`CheckAllZero` not found"),
+    (
+        "algorithms/Grover.qs",
+        "cannot call a function or operation that can only be resolved at runtime",
+    ),
+    ("algorithms/HiddenShift.qs", "cannot use a closure"),
+    ("algorithms/HiddenShiftNISQ.qs", "cannot use a closure"),
+    (
+        "algorithms/JointMeasurement.qs",
+        "Index Expr not yet implemented",
+    ),
+    ("algorithms/PhaseFlipCode.qs", "`DrawRandomInt` not found"),
+    ("algorithms/QRNG.qs", "cannot call a cyclic operation"),
+    ("algorithms/Shor.qs", "`DrawRandomInt` not found"),
+    (
+        "algorithms/SuperdenseCoding.qs",
+        "`DrawRandomInt` not found",
+    ),
+    (
+        "algorithms/Teleportation.qs",
+        "cannot call a function or operation that can only be resolved at runtime",
+    ),
+    ("language/BigInt.qs", "has no output records"),
+    ("language/DataTypes.qs", "cannot use a closure"),
+    ("language/Diagnostics.qs", "released while not in |0⟩ state"),
+    ("language/FailStatement.qs", "program failed"),
+    ("language/LambdaExpression.qs", "cannot use a closure"),
+    ("language/PartialApplication.qs", "cannot use a closure"),
+    ("language/Pauli.qs", "released while not in |0⟩ state"),
+    ("language/Range.qs", "has no output records"),
+    (
+        "language/RepeatUntilLoops.qs",
+        "cannot have a loop with a dynamic condition",
+    ),
+    ("language/String.qs", "has no output records"),
+    ("language/Tuple.qs", "has no output records"),
+    ("notebooks/test_project", "entry point not found"),
+];
+
+fn samples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("samples")
+}
+
+/// Collects the programs under the given directory. A directory with a `qsharp.json` manifest is a
+/// single program made of the sources under its `src` directory; any other `.qs` file is a program
+/// on its own.
+fn collect_programs(
+    root: &Path,
+    dir: &Path,
+    programs: &mut Vec<(String, Vec<(SourceName, SourceContents)>)>,
+) {
+    let name = dir
+        .strip_prefix(root)
+        .expect("directory should be under the root")
+        .to_string_lossy()
+        .replace('\\', "/");
+    if SKIPPED_SAMPLES.iter().any(|(skipped, _)| *skipped == name) {
+        return;
+    }
+    if dir.join("qsharp.json").exists() {
+        let mut sources = Vec::new();
+        collect_sources(&dir.join("src"), &mut sources);
+        programs.push((name, sources));
+        return;
+    }
+
+    let mut entries = fs::read_dir(dir)
+        .expect("directory should be readable")
+        .map(|entry| entry.expect("entry should be readable").path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_programs(root, &path, programs);
+        } else if path.extension().is_some_and(|ext| ext == "qs") {
+            let name = path
+                .strip_prefix(root)
+                .expect("file should be under the root")
+                .to_string_lossy()
+                .replace('\\', "/");
+            if !SKIPPED_SAMPLES.iter().any(|(skipped, _)| *skipped == name) {
+                let contents = fs::read_to_string(&path).expect("file should be readable");
+                programs.push((name.clone(), vec![(name.into(), contents.into())]));
+            }
+        }
+    }
+}
+
+fn collect_sources(dir: &Path, sources: &mut Vec<(SourceName, SourceContents)>) {
+    let mut entries = fs::read_dir(dir)
+        .expect("directory should be readable")
+        .map(|entry| entry.expect("entry should be readable").path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_sources(&path, sources);
+        } else if path.extension().is_some_and(|ext| ext == "qs") {
+            let contents = fs::read_to_string(&path).expect("file should be readable");
+            sources.push((path.to_string_lossy().into(), contents.into()));
+        }
+    }
+}
+
+#[test]
+fn samples_match_evaluation() {
+    let root = samples_dir();
+    let mut programs = Vec::new();
+    collect_programs(&root, &root, &mut programs);
+    assert!(!programs.is_empty(), "samples should be found");
+    for (unsupported, _) in UNSUPPORTED_SAMPLES {
+        assert!(
+            programs.iter().any(|(name, _)| name == unsupported),
+            "unsupported sample `{unsupported}` should exist"
+        );
+    }
+
+    let mut failures = Vec::new();
+    for (name, sources) in &programs {
+        let expected_error = UNSUPPORTED_SAMPLES
+            .iter()
+            .find(|(unsupported, _)| unsupported == name)
+            .map(|(_, error)| *error);
+        for seed in 0..SEEDS {
+            match (run(sources, seed), expected_error) {
+                (Ok(comparison), None) if comparison.is_match() => {}
+                (Ok(comparison), None) => {
+                    failures.push(format!("{name} (seed {seed}):\n{comparison}"));
+                }
+                (Ok(_), Some(expected)) => {
+                    failures.push(format!(
+                        "{name} (seed {seed}): expected to fail with `{expected}`, but succeeded"
+                    ));
+                }
+                // Unsupported features are reported before either path runs to completion, so the
+                // remaining seeds would fail the same way.
+                (
+                    Err(error @ (Error::Compile(_) | Error::Eval(_) | Error::PartialEval(_))),
+                    Some(expected),
+                ) if error.to_string().contains(expected) => break,
+                (Err(error), _) => failures.push(format!("{name} (seed {seed}): {error}")),
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "samples failed:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn generated_programs_match_evaluation() {
+    let mut mismatches = Vec::new();
+    for seed in 0..GENERATED_PROGRAMS {
+        let source = generate_seeded(seed);
+        match run(&[("generated.qs".into(), source.clone().into())], seed) {
+            Ok(comparison) if comparison.is_match() => {}
+            Ok(comparison) => mismatches.push(format!("seed {seed}:\n{source}\n{comparison}")),
+            Err(error) => mismatches.push(format!("seed {seed}:\n{source}\n{error}")),
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "generated programs failed:\n{}",
+        mismatches.join("\n")
+    );
+}
+
+#[test]
+fn generation_is_deterministic() {
+    assert_eq!(generate_seeded(7), generate_seeded(7));
+    assert_ne!(generate_seeded(7), generate_seeded(8));
+}
+
+#[test]
+fn failed_compilation_is_reported() {
+    let error = run(&[("test.qs".into(), "namespace Test {".into())], 0)
+        .expect_err("program should not compile");
+    assert!(matches!(error, Error::Compile(_)), "{error}");
+}
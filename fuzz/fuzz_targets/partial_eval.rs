@@ -0,0 +1,31 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![no_main]
+
+allocator::assign_global!();
+
+#[cfg(feature = "do_fuzz")]
+use libfuzzer_sys::fuzz_target;
+
+fn partial_eval(data: &[u8]) {
+    let source = difftest::generator::generate(data);
+    match difftest::run(&[("fuzzed_code".into(), source.clone().into())], 0) {
+        Ok(comparison) => assert!(
+            comparison.is_match(),
+            "partial evaluation does not match evaluation:\n{source}\n{comparison}"
+        ),
+        Err(error) => panic!("program from the grammar failed:\n{source}\n{error}"),
+    }
+}
+
+#[cfg(feature = "do_fuzz")]
+fuzz_target!(|data: &[u8]| {
+    partial_eval(data);
+});
+
+#[cfg(not(feature = "do_fuzz"))]
+#[no_mangle]
+pub extern "C" fn main() {
+    partial_eval(&[]);
+}
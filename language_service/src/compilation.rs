@@ -15,6 +15,7 @@ use qsc::{
     CompileUnit, LanguageFeatures, PackageStore, PackageType, PassContext, SourceMap, Span,
 };
 use qsc_linter::LintConfig;
+use qsc_project::DependencyPackage;
 use std::sync::Arc;
 
 /// Represents an immutable compilation state that can be used
@@ -34,8 +35,11 @@ pub(crate) struct Compilation {
 pub(crate) enum CompilationKind {
     /// An open Q# project.
     /// In an `OpenProject` compilation, the user package contains
-    /// one or more sources, and a target profile.
-    OpenProject,
+    /// one or more sources, and a target profile. The packages the
+    /// project depends on are compiled into the package store ahead of it.
+    OpenProject {
+        dependencies: Vec<DependencyPackage>,
+    },
     /// A Q# notebook. In a notebook compilation, the user package
     /// contains multiple `Source`s, with each source corresponding
     /// to a cell.
@@ -46,6 +50,7 @@ impl Compilation {
     /// Creates a new `Compilation` by compiling sources.
     pub(crate) fn new(
         sources: &[(Arc<str>, Arc<str>)],
+        dependencies: Vec<DependencyPackage>,
         package_type: PackageType,
         target_profile: Profile,
         language_features: LanguageFeatures,
@@ -63,14 +68,23 @@ impl Compilation {
         let std_package_id =
             package_store.insert(compile::std(&package_store, target_profile.into()));
 
-        let (unit, mut errors) = compile::compile(
-            &package_store,
+        let (mut package_dependencies, mut errors) = compile::compile_dependencies(
+            &mut package_store,
             &[std_package_id],
+            &dependencies,
+            target_profile.into(),
+        );
+        package_dependencies.insert(0, std_package_id);
+
+        let (unit, unit_errors) = compile::compile(
+            &package_store,
+            &package_dependencies,
             source_map,
             package_type,
             target_profile.into(),
             language_features,
         );
+        errors.extend(unit_errors);
 
         // Compute new lints and append them to the errors Vec.
         // Lints are only computed if the erros vector is empty. For performance
@@ -112,7 +126,7 @@ impl Compilation {
             package_store,
             user_package_id: package_id,
             errors,
-            kind: CompilationKind::OpenProject,
+            kind: CompilationKind::OpenProject { dependencies },
         }
     }
 
@@ -256,9 +270,10 @@ impl Compilation {
             .iter()
             .map(|source| (source.name.clone(), source.contents.clone()));
 
-        let new = match self.kind {
-            CompilationKind::OpenProject => Self::new(
+        let new = match &self.kind {
+            CompilationKind::OpenProject { dependencies } => Self::new(
                 &sources.collect::<Vec<_>>(),
+                dependencies.clone(),
                 package_type,
                 target_profile,
                 language_features,
@@ -48,7 +48,7 @@ pub(crate) fn get_completions(
     context_finder.visit_package(user_ast_package);
 
     let insert_open_at = match compilation.kind {
-        CompilationKind::OpenProject => context_finder.start_of_namespace,
+        CompilationKind::OpenProject { .. } => context_finder.start_of_namespace,
         // Since notebooks don't typically contain namespace declarations,
         // open statements should just get before the first non-whitespace
         // character (i.e. at the top of the cell)
@@ -118,7 +118,7 @@ pub(crate) fn get_completions(
             builder.push_item_decl_keywords();
         }
         Context::NoCompilation | Context::TopLevel => match compilation.kind {
-            CompilationKind::OpenProject => builder.push_namespace_keyword(),
+            CompilationKind::OpenProject { .. } => builder.push_namespace_keyword(),
             CompilationKind::Notebook => {
                 // For notebooks, the top-level allows for
                 // more syntax.
@@ -220,7 +220,17 @@ impl CompletionListBuilder {
     }
 
     fn push_item_decl_keywords(&mut self) {
-        static ITEM_KEYWORDS: [&str; 5] = ["operation", "open", "internal", "function", "newtype"];
+        static ITEM_KEYWORDS: [&str; 9] = [
+            "operation",
+            "open",
+            "internal",
+            "function",
+            "newtype",
+            "struct",
+            "enum",
+            "export",
+            "const",
+        ];
 
         self.push_completions(
             ITEM_KEYWORDS
@@ -334,7 +344,9 @@ impl CompletionListBuilder {
     }
 
     fn push_stmt_keywords(&mut self) {
-        static STMT_KEYWORDS: [&str; 5] = ["let", "return", "use", "mutable", "borrow"];
+        static STMT_KEYWORDS: [&str; 7] = [
+            "let", "return", "use", "mutable", "borrow", "break", "continue",
+        ];
 
         self.push_completions(
             STMT_KEYWORDS
@@ -344,9 +356,9 @@ impl CompletionListBuilder {
     }
 
     fn push_expr_keywords(&mut self) {
-        static EXPR_KEYWORDS: [&str; 11] = [
+        static EXPR_KEYWORDS: [&str; 13] = [
             "if", "for", "in", "within", "apply", "repeat", "until", "fixup", "set", "while",
-            "fail",
+            "fail", "new", "match",
         ];
 
         self.push_completions(
@@ -433,9 +445,11 @@ impl CompletionListBuilder {
                                 }
                             }
                         }
-                        return match &i.kind {
+                        let (kind, exported_name) = resolve_export(package, &i.kind)?;
+                        return match kind {
                             ItemKind::Callable(callable_decl) => {
-                                let name = callable_decl.name.name.as_ref();
+                                let name =
+                                    exported_name.unwrap_or(callable_decl.name.name.as_ref());
                                 let detail =
                                     Some(display.hir_callable_decl(callable_decl).to_string());
                                 // Everything that starts with a __ goes last in the list
@@ -548,6 +562,20 @@ impl CompletionListBuilder {
     }
 }
 
+/// Exported items are offered under their exported name, with the kind of the item they export.
+fn resolve_export<'a>(
+    package: &'a Package,
+    kind: &'a ItemKind,
+) -> Option<(&'a ItemKind, Option<&'a str>)> {
+    Some(match kind {
+        ItemKind::Export(name, target) if target.package.is_none() => (
+            &package.items.get(target.item)?.kind,
+            Some(name.name.as_ref()),
+        ),
+        kind => (kind, None),
+    })
+}
+
 fn local_completion(
     candidate: &Local,
     compilation: &Compilation,
@@ -571,6 +599,18 @@ fn local_completion(
                 ItemKind::Namespace(_, _) => {
                     panic!("did not expect local namespace item")
                 }
+                ItemKind::Export(_, _) => {
+                    panic!("did not expect local export item")
+                }
+                ItemKind::Const(name, value) => {
+                    if !include_terms {
+                        return None;
+                    }
+                    (
+                        Some(format!("const {} : {}", name.name, value.ty.display())),
+                        CompletionItemKind::Variable,
+                    )
+                }
                 ItemKind::Ty(_, udt) => {
                     if !include_terms && !include_tys {
                         return None;
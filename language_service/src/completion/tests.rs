@@ -588,6 +588,58 @@ fn in_block_from_other_namespace() {
     );
 }
 
+#[test]
+fn in_block_contains_exported_callable() {
+    check(
+        indoc! {r#"
+    namespace Test {
+        operation Bar() : Unit {
+            ↘
+        }
+    }
+    namespace Other.Implementation {
+        operation Foo() : Unit {}
+    }
+    namespace Other {
+        export Other.Implementation.Foo as Run;
+    }"#},
+        &["Run"],
+        &expect![[r#"
+            [
+                Some(
+                    CompletionItem {
+                        label: "Run",
+                        kind: Function,
+                        sort_text: Some(
+                            "0600Run",
+                        ),
+                        detail: Some(
+                            "operation Foo() : Unit",
+                        ),
+                        additional_text_edits: Some(
+                            [
+                                TextEdit {
+                                    new_text: "open Other;\n    ",
+                                    range: Range {
+                                        start: Position {
+                                            line: 1,
+                                            column: 4,
+                                        },
+                                        end: Position {
+                                            line: 1,
+                                            column: 4,
+                                        },
+                                    },
+                                },
+                            ],
+                        ),
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn auto_open_multiple_files() {
     check_project(
@@ -241,6 +241,46 @@ fn udt_field_ref() {
     );
 }
 
+#[test]
+fn struct_ref() {
+    assert_definition(
+        r#"
+    namespace Test {
+        struct ◉Pair◉ { Fst : Int, Snd : Double }
+        operation Foo() : Unit {
+            let a = new P↘air { Fst = 1, Snd = 2.3 };
+        }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn struct_field_ref_in_constructor() {
+    assert_definition(
+        r#"
+    namespace Test {
+        struct Pair { Fst : Int, ◉Snd◉ : Double }
+        operation Foo() : Unit {
+            let a = new Pair { Fst = 1, S↘nd = 2.3 };
+        }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn ty_ref_in_enum_variant() {
+    assert_definition(
+        r#"
+    namespace Test {
+        struct ◉Pair◉ { Fst : Int, Snd : Double }
+        enum Shape { Point(P↘air), Empty }
+    }
+    "#,
+    );
+}
+
 #[test]
 fn lambda_param() {
     assert_definition(
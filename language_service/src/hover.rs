@@ -154,6 +154,14 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
         });
     }
 
+    fn at_struct_def(&mut self, type_name: &'a ast::Ident, decl: &'a ast::StructDecl) {
+        let contents = markdown_fenced_block(self.display.ast_struct_decl(decl));
+        self.hover = Some(Hover {
+            contents,
+            span: self.range(type_name.span),
+        });
+    }
+
     fn at_new_type_ref(
         &mut self,
         path: &'a ast::Path,
@@ -301,6 +309,12 @@ fn is_param(param_pats: &[&ast::Pat], node_id: ast::NodeId) -> bool {
             ast::PatKind::Discard(_) | ast::PatKind::Elided | ast::PatKind::Err => false,
             ast::PatKind::Paren(inner) => find_in_pat(inner, node_id),
             ast::PatKind::Tuple(inner) => inner.iter().any(|x| find_in_pat(x, node_id)),
+            ast::PatKind::Array(inner, rest) => {
+                inner.iter().chain(rest).any(|x| find_in_pat(x, node_id))
+            }
+            ast::PatKind::Variant(_, payload) => payload
+                .as_ref()
+                .is_some_and(|payload| find_in_pat(payload, node_id)),
         }
     }
 
@@ -130,6 +130,54 @@ fn callable_ref() {
     );
 }
 
+#[test]
+fn callable_exported_ref() {
+    check(
+        indoc! {r#"
+        namespace Implementation {
+            /// Doc comment
+            operation Bar() : Unit {}
+        }
+        namespace Facade {
+            export Implementation.Bar as Baz;
+        }
+        namespace Test {
+            open Facade;
+            operation Foo() : Unit { ◉B↘az◉(); }
+        }
+    "#},
+        &expect![[r#"
+            ```qsharp
+            Implementation
+            operation Bar() : Unit
+            ```
+            ---
+            Doc comment
+        "#]],
+    );
+}
+
+#[test]
+fn callable_export_path() {
+    check(
+        indoc! {r#"
+        namespace Implementation {
+            operation Bar() : Unit {}
+        }
+        namespace Facade {
+            open Implementation;
+            export ◉B↘ar◉ as Baz;
+        }
+    "#},
+        &expect![[r#"
+            ```qsharp
+            Implementation
+            operation Bar() : Unit
+            ```
+        "#]],
+    );
+}
+
 #[test]
 fn callable_with_type_params_ref() {
     check(
@@ -753,6 +801,57 @@ fn udt_field_ref() {
     );
 }
 
+#[test]
+fn struct_def() {
+    check(
+        indoc! {r#"
+        namespace Test {
+            struct ◉P↘air◉ { Fst : Int, Snd : Double }
+        }
+    "#},
+        &expect![[r#"
+            ```qsharp
+            struct Pair { Fst : Int, Snd : Double }
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn struct_field_def() {
+    check(
+        indoc! {r#"
+        namespace Test {
+            struct Pair { Fst : Int, ◉S↘nd◉ : Double }
+        }
+    "#},
+        &expect![[r#"
+            ```qsharp
+            Snd : Double
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn struct_field_ref_in_constructor() {
+    check(
+        indoc! {r#"
+        namespace Test {
+            struct Pair { Fst : Int, Snd : Double }
+            operation Foo() : Unit {
+                let a = new Pair { Fst = 1, ◉S↘nd◉ = 2.3 };
+            }
+        }
+    "#},
+        &expect![[r#"
+            ```qsharp
+            Snd : Double
+            ```
+        "#]],
+    );
+}
+
 #[test]
 fn primitive_type() {
     check_none(indoc! {r#"
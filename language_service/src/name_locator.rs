@@ -45,6 +45,8 @@ pub(crate) trait Handler<'package> {
 
     fn at_new_type_def(&mut self, type_name: &'package ast::Ident, def: &'package ast::TyDef);
 
+    fn at_struct_def(&mut self, type_name: &'package ast::Ident, decl: &'package ast::StructDecl);
+
     fn at_new_type_ref(
         &mut self,
         path: &'package ast::Path,
@@ -147,11 +149,12 @@ impl<'inner, 'package, T: Handler<'package>> Visitor<'package> for Locator<'inne
 
                         // walk callable decl
                         decl.generics.iter().for_each(|p| {
-                            if span_touches(p.span, self.offset) {
+                            if span_touches(p.name.span, self.offset) {
                                 if let Some(resolve::Res::Param(param_id)) =
-                                    self.compilation.get_res(p.id)
+                                    self.compilation.get_res(p.name.id)
                                 {
-                                    self.inner.at_type_param_def(&self.context, p, *param_id);
+                                    self.inner
+                                        .at_type_param_def(&self.context, &p.name, *param_id);
                                 }
                             }
                         });
@@ -191,6 +194,31 @@ impl<'inner, 'package, T: Handler<'package>> Visitor<'package> for Locator<'inne
                         self.context.current_udt_id = context;
                     }
                 }
+                ast::ItemKind::Struct(decl) => {
+                    if let Some(resolve::Res::Item(item_id, _)) =
+                        self.compilation.get_res(decl.name.id)
+                    {
+                        let context = self.context.current_udt_id;
+                        self.context.current_udt_id = Some(item_id);
+
+                        if span_touches(decl.name.span, self.offset) {
+                            self.inner.at_struct_def(&decl.name, decl);
+                        } else {
+                            decl.fields.iter().for_each(|f| self.visit_field_def(f));
+                        }
+
+                        self.context.current_udt_id = context;
+                    }
+                }
+                ast::ItemKind::Enum(decl) => {
+                    decl.variants
+                        .iter()
+                        .filter_map(|v| v.ty.as_deref())
+                        .for_each(|ty| self.visit_ty(ty));
+                }
+                ast::ItemKind::Export(decl) => {
+                    decl.items.iter().for_each(|e| self.visit_path(&e.path));
+                }
                 _ => {}
             }
             self.context.current_item_doc = context;
@@ -229,15 +257,30 @@ impl<'inner, 'package, T: Handler<'package>> Visitor<'package> for Locator<'inne
         }
     }
 
+    // Handles struct field definitions
+    fn visit_field_def(&mut self, def: &'package ast::FieldDef) {
+        if span_touches(def.span, self.offset) {
+            if span_touches(def.name.span, self.offset) {
+                self.inner.at_field_def(&self.context, &def.name, &def.ty);
+            } else {
+                self.visit_ty(&def.ty);
+            }
+        }
+    }
+
     // Handles type param references
     fn visit_ty(&mut self, ty: &'package ast::Ty) {
         if span_touches(ty.span, self.offset) {
             if let ast::TyKind::Param(param) = &*ty.kind {
                 if let Some(resolve::Res::Param(param_id)) = self.compilation.get_res(param.id) {
                     if let Some(curr) = self.context.current_callable {
-                        if let Some(def_name) = curr.generics.get(usize::from(*param_id)) {
-                            self.inner
-                                .at_type_param_ref(&self.context, param, *param_id, def_name);
+                        if let Some(def) = curr.generics.get(usize::from(*param_id)) {
+                            self.inner.at_type_param_ref(
+                                &self.context,
+                                param,
+                                *param_id,
+                                &def.name,
+                            );
                         }
                     }
                 }
@@ -289,6 +332,35 @@ impl<'inner, 'package, T: Handler<'package>> Visitor<'package> for Locator<'inne
                         }
                     }
                 }
+                ast::ExprKind::Struct(_, _, fields) => {
+                    let field_ref = fields
+                        .iter()
+                        .find(|f| span_touches(f.field.span, self.offset));
+                    match (field_ref, self.compilation.get_ty(expr.id)) {
+                        (Some(assign), Some(hir::ty::Ty::Udt(_, res))) => {
+                            let (item, resolved_item_id) = self
+                                .compilation
+                                .resolve_item_res(self.compilation.user_package_id, res);
+                            match &item.kind {
+                                hir::ItemKind::Ty(_, udt) => {
+                                    if let Some(field_def) =
+                                        udt.find_field_by_name(&assign.field.name)
+                                    {
+                                        self.inner.at_field_ref(
+                                            &assign.field,
+                                            &assign.value.id,
+                                            &resolved_item_id,
+                                            field_def,
+                                        );
+                                    }
+                                }
+                                _ => panic!("UDT has invalid resolution."),
+                            }
+                        }
+                        (Some(_), _) => {}
+                        (None, _) => walk_expr(self, expr),
+                    }
+                }
                 ast::ExprKind::Lambda(_, pat, expr) => {
                     self.context.in_lambda_params = true;
                     self.visit_pat(pat);
@@ -337,6 +409,13 @@ impl<'inner, 'package, T: Handler<'package>> Visitor<'package> for Locator<'inne
                                     path.id
                                 )
                             }
+                            hir::ItemKind::Export(_, _) => {
+                                panic!(
+                                    "Reference node should refer to the exported item: {}",
+                                    path.id
+                                )
+                            }
+                            hir::ItemKind::Const(_, _) => {}
                         }
                     }
                     resolve::Res::Local(node_id) => {
@@ -106,6 +106,14 @@ impl<'a> Handler<'a> for NameHandler<'a> {
         }
     }
 
+    fn at_struct_def(&mut self, type_name: &'a ast::Ident, _: &'a ast::StructDecl) {
+        if let Some(resolve::Res::Item(item_id, _)) =
+            self.reference_finder.compilation.get_res(type_name.id)
+        {
+            self.references = self.reference_finder.for_item(item_id);
+        }
+    }
+
     fn at_new_type_ref(
         &mut self,
         _: &'a ast::Path,
@@ -188,7 +196,10 @@ impl<'a> ReferenceFinder<'a> {
         if self.include_declaration {
             let def_span = match &def.kind {
                 hir::ItemKind::Callable(decl) => decl.name.span,
-                hir::ItemKind::Namespace(name, _) | hir::ItemKind::Ty(name, _) => name.span,
+                hir::ItemKind::Namespace(name, _)
+                | hir::ItemKind::Ty(name, _)
+                | hir::ItemKind::Export(name, _)
+                | hir::ItemKind::Const(name, _) => name.span,
             };
             locations.push(
                 self.location(
@@ -362,6 +373,18 @@ impl<'a> Visitor<'_> for FindFieldRefs<'a> {
                     }
                 }
             }
+        } else if let ast::ExprKind::Struct(_, _, fields) = &*expr.kind {
+            if let Some(Ty::Udt(_, Res::Item(id))) = self.compilation.get_ty(expr.id) {
+                if self.eq(id) {
+                    self.locations.extend(
+                        fields
+                            .iter()
+                            .filter(|f| f.field.name == self.field_name)
+                            .map(|f| f.field.span),
+                    );
+                }
+            }
+            walk_expr(self, expr);
         } else {
             walk_expr(self, expr);
         }
@@ -421,10 +444,10 @@ impl<'a> Visitor<'_> for FindTyParamLocations<'a> {
     fn visit_callable_decl(&mut self, decl: &'_ ast::CallableDecl) {
         if self.include_declaration {
             decl.generics.iter().for_each(|p| {
-                let res = self.compilation.get_res(p.id);
+                let res = self.compilation.get_res(p.name.id);
                 if let Some(resolve::Res::Param(param_id)) = res {
                     if *param_id == self.param_id {
-                        self.locations.push(p.span);
+                        self.locations.push(p.name.span);
                     }
                 }
             });
@@ -325,6 +325,21 @@ fn field_exclude_def() {
     );
 }
 
+#[test]
+fn struct_field_ref() {
+    check_include_decl(
+        r#"
+        namespace Test {
+            struct Bar { ◉fst◉ : Int, snd : Int }
+            operation Foo() : Unit {
+                let bar = new Bar { ◉fst◉ = 1, snd = 2 };
+                let baz = new Bar { ...bar, ◉f↘st◉ = bar::◉fst◉ };
+            }
+        }
+    "#,
+    );
+}
+
 #[test]
 fn std_field_ref() {
     check_with_std(
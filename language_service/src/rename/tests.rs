@@ -241,6 +241,20 @@ fn udt_field_complex_ref() {
     );
 }
 
+#[test]
+fn struct_def() {
+    check(
+        r#"
+        namespace Test {
+            struct ◉F↘oo◉ { fst : Int, snd : Int }
+            operation Bar(x : ◉Foo◉) : ◉Foo◉ {
+                new ◉Foo◉ { ...x, fst = 1 }
+            }
+        }
+    "#,
+    );
+}
+
 #[test]
 fn no_rename_namespace() {
     assert_no_rename(
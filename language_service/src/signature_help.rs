@@ -160,19 +160,32 @@ impl SignatureHelpFinder<'_> {
         let mut offset = self.display.get_param_offset(decl);
 
         match &decl.input.kind {
-            hir::PatKind::Discard | hir::PatKind::Err | hir::PatKind::Bind(_) => {
-                self.make_wrapped_params(offset, &decl.input, doc)
+            hir::PatKind::Discard
+            | hir::PatKind::Err
+            | hir::PatKind::Bind(_)
+            | hir::PatKind::Array(..) => {
+                self.make_wrapped_params(offset, &decl.input, decl.defaults.first(), doc)
+            }
+            hir::PatKind::Tuple(_) => {
+                self.make_param_with_offset(&mut offset, &decl.input, &decl.defaults, doc)
             }
-            hir::PatKind::Tuple(_) => self.make_param_with_offset(&mut offset, &decl.input, doc),
         }
     }
 
+    /// The length of the ` = value` text shown after a parameter with a default value.
+    fn default_len(&self, default: Option<&hir::Expr>) -> u32 {
+        default.map_or(0, |default| {
+            usize_to_u32(format!(" = {}", self.display.hir_default(default)).len())
+        })
+    }
+
     /// Callables with a single parameter in their parameter list are special-cased
     /// because we need to re-wrap the parameter into a tuple.
     fn make_wrapped_params(
         &self,
         offset: u32,
         pat: &hir::Pat,
+        default: Option<&hir::Expr>,
         doc: &str,
     ) -> Vec<ParameterInformation> {
         let documentation = if let hir::PatKind::Bind(name) = &pat.kind {
@@ -182,7 +195,8 @@ impl SignatureHelpFinder<'_> {
             None
         };
 
-        let len = usize_to_u32(self.display.hir_pat(pat).to_string().len());
+        let len =
+            usize_to_u32(self.display.hir_pat(pat).to_string().len()) + self.default_len(default);
         let param = ParameterInformation {
             label: (offset + 1, offset + len + 1),
             documentation,
@@ -196,14 +210,20 @@ impl SignatureHelpFinder<'_> {
         vec![wrapper, param]
     }
 
+    /// The `defaults` are the default values of the trailing items of a tuple pattern, which are
+    /// only present at the top level of the callable input.
     fn make_param_with_offset(
         &self,
         offset: &mut u32,
         pat: &hir::Pat,
+        defaults: &[hir::Expr],
         doc: &str,
     ) -> Vec<ParameterInformation> {
         match &pat.kind {
-            hir::PatKind::Bind(_) | hir::PatKind::Discard | hir::PatKind::Err => {
+            hir::PatKind::Bind(_)
+            | hir::PatKind::Discard
+            | hir::PatKind::Array(..)
+            | hir::PatKind::Err => {
                 let documentation = if let hir::PatKind::Bind(name) = &pat.kind {
                     let documentation = parse_doc_for_param(doc, &name.name);
                     (!documentation.is_empty()).then_some(documentation)
@@ -220,22 +240,25 @@ impl SignatureHelpFinder<'_> {
                 }]
             }
             hir::PatKind::Tuple(items) => {
-                let len = usize_to_u32(self.display.hir_pat(pat).to_string().len());
                 let mut rtrn = vec![ParameterInformation {
-                    label: (*offset, *offset + len),
+                    label: (*offset, *offset),
                     documentation: None,
                 }];
                 *offset += 1; // for the open parenthesis
-                let mut is_first = true;
-                for item in items {
-                    if is_first {
-                        is_first = false;
-                    } else {
+                let first_default = items.len().saturating_sub(defaults.len());
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
                         *offset += 2; // 2 for the comma and space
                     }
-                    rtrn.extend(self.make_param_with_offset(offset, item, doc));
+                    let start = rtrn.len();
+                    rtrn.extend(self.make_param_with_offset(offset, item, &[], doc));
+                    let default = i.checked_sub(first_default).and_then(|i| defaults.get(i));
+                    let default_len = self.default_len(default);
+                    *offset += default_len;
+                    rtrn[start].label.1 += default_len;
                 }
                 *offset += 1; // for the close parenthesis
+                rtrn[0].label.1 = *offset;
                 rtrn
             }
         }
@@ -361,7 +384,10 @@ fn make_fake_pat(ty: &hir::ty::Ty) -> hir::Pat {
 fn process_args(args: &ast::Expr, location: u32, params: &hir::Pat) -> u32 {
     fn count_params(params: &hir::Pat) -> i32 {
         match &params.kind {
-            hir::PatKind::Bind(_) | hir::PatKind::Discard | hir::PatKind::Err => 1,
+            hir::PatKind::Bind(_)
+            | hir::PatKind::Discard
+            | hir::PatKind::Array(..)
+            | hir::PatKind::Err => 1,
             hir::PatKind::Tuple(items) => items.iter().map(count_params).sum::<i32>() + 1,
         }
     }
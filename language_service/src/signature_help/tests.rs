@@ -3283,3 +3283,101 @@ fn std_callable_with_type_params() {
         "#]],
     );
 }
+
+#[test]
+fn callable_with_default_values() {
+    check(
+        indoc! {r#"
+        namespace Test {
+            operation Foo(x : Int, y : Double = -0.5, z : Pauli[] = [PauliX, PauliZ]) : Unit {}
+            operation Bar() : Unit {
+                Foo(1, ↘)
+            }
+        }
+    "#},
+        &expect![[r#"
+            SignatureHelp {
+                signatures: [
+                    SignatureInformation {
+                        label: "operation Foo(x : Int, y : Double = -0.5, z : Pauli[] = [PauliX, PauliZ]) : Unit",
+                        documentation: None,
+                        parameters: [
+                            ParameterInformation {
+                                label: (
+                                    13,
+                                    73,
+                                ),
+                                documentation: None,
+                            },
+                            ParameterInformation {
+                                label: (
+                                    14,
+                                    21,
+                                ),
+                                documentation: None,
+                            },
+                            ParameterInformation {
+                                label: (
+                                    23,
+                                    40,
+                                ),
+                                documentation: None,
+                            },
+                            ParameterInformation {
+                                label: (
+                                    42,
+                                    72,
+                                ),
+                                documentation: None,
+                            },
+                        ],
+                    },
+                ],
+                active_signature: 0,
+                active_parameter: 2,
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn single_param_callable_with_default_value() {
+    check(
+        indoc! {r#"
+        namespace Test {
+            operation Foo(x : Int = 2 ^ 3) : Unit {}
+            operation Bar() : Unit {
+                Foo(↘)
+            }
+        }
+    "#},
+        &expect![[r#"
+            SignatureHelp {
+                signatures: [
+                    SignatureInformation {
+                        label: "operation Foo(x : Int = ...) : Unit",
+                        documentation: None,
+                        parameters: [
+                            ParameterInformation {
+                                label: (
+                                    13,
+                                    28,
+                                ),
+                                documentation: None,
+                            },
+                            ParameterInformation {
+                                label: (
+                                    14,
+                                    27,
+                                ),
+                                documentation: None,
+                            },
+                        ],
+                    },
+                ],
+                active_signature: 0,
+                active_parameter: 1,
+            }
+        "#]],
+    );
+}
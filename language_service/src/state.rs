@@ -11,7 +11,7 @@ use log::{error, trace};
 use miette::Diagnostic;
 use qsc::{compile::Error, target::Profile, LanguageFeatures, PackageType};
 use qsc_linter::LintConfig;
-use qsc_project::{FileSystemAsync, JSFileEntry};
+use qsc_project::{DependencyPackage, FileSystemAsync, JSFileEntry};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::{cell::RefCell, fmt::Debug, future::Future, mem::take, pin::Pin, rc::Rc, sync::Arc};
 
@@ -128,6 +128,7 @@ pub(super) struct CompilationStateUpdater<'a> {
 struct LoadManifestResult {
     compilation_uri: Arc<str>,
     sources: Vec<(Arc<str>, Arc<str>)>,
+    dependencies: Vec<DependencyPackage>,
     language_features: LanguageFeatures,
     lints: Vec<LintConfig>,
 }
@@ -173,6 +174,7 @@ impl<'a> CompilationStateUpdater<'a> {
         let LoadManifestResult {
             compilation_uri,
             sources,
+            dependencies,
             language_features,
             lints: lints_config,
         } = project.unwrap_or_else(|| {
@@ -180,6 +182,7 @@ impl<'a> CompilationStateUpdater<'a> {
             LoadManifestResult {
                 compilation_uri: doc_uri.clone(),
                 sources: vec![(doc_uri.clone(), text.clone())],
+                dependencies: Vec::default(),
                 language_features: LanguageFeatures::default(),
                 lints: Vec::default(),
             }
@@ -209,6 +212,7 @@ impl<'a> CompilationStateUpdater<'a> {
 
         self.insert_buffer_aware_compilation(
             sources,
+            dependencies,
             &compilation_uri,
             language_features,
             &lints_config,
@@ -228,6 +232,7 @@ impl<'a> CompilationStateUpdater<'a> {
                 Ok(o) => Some(LoadManifestResult {
                     compilation_uri: manifest.compilation_uri(),
                     sources: o.sources,
+                    dependencies: o.dependencies,
                     language_features: manifest
                         .manifest
                         .language_features
@@ -253,6 +258,7 @@ impl<'a> CompilationStateUpdater<'a> {
     fn insert_buffer_aware_compilation(
         &mut self,
         mut sources: Vec<(Arc<str>, Arc<str>)>,
+        dependencies: Vec<DependencyPackage>,
         compilation_uri: &Arc<str>,
         language_features: LanguageFeatures,
         lints_config: &[LintConfig],
@@ -270,6 +276,7 @@ impl<'a> CompilationStateUpdater<'a> {
 
             let compilation = Compilation::new(
                 &sources,
+                dependencies,
                 self.configuration.package_type,
                 self.configuration.target_profile,
                 language_features,
@@ -297,6 +304,7 @@ impl<'a> CompilationStateUpdater<'a> {
             // for this document
             if let Some(LoadManifestResult {
                 sources,
+                dependencies,
                 compilation_uri,
                 language_features,
                 lints: lints_config,
@@ -304,6 +312,7 @@ impl<'a> CompilationStateUpdater<'a> {
             {
                 self.insert_buffer_aware_compilation(
                     sources,
+                    dependencies,
                     &compilation_uri,
                     language_features,
                     &lints_config,
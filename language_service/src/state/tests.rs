@@ -1416,6 +1416,87 @@ async fn loading_lints_config_from_manifest() {
     .await;
 }
 
+#[tokio::test]
+async fn project_dependencies_are_compiled_as_separate_packages() {
+    let fs = FsNode::Dir(
+        [
+            dir(
+                "project",
+                [
+                    file(
+                        "qsharp.json",
+                        r#"{ "dependencies": { "Library": { "path": "../library" } } }"#,
+                    ),
+                    dir(
+                        "src",
+                        [file(
+                            "this_file.qs",
+                            "namespace Foo { function Main() : Int { Library.Four() + Library.Two() } }",
+                        )],
+                    ),
+                ],
+            ),
+            dir(
+                "library",
+                [
+                    file("qsharp.json", "{}"),
+                    dir(
+                        "src",
+                        [file(
+                            "library.qs",
+                            "namespace Library { function Four() : Int { Two() * 2 } internal function Two() : Int { 2 } }",
+                        )],
+                    ),
+                ],
+            ),
+        ]
+        .into_iter()
+        .collect(),
+    );
+
+    let fs = Rc::new(RefCell::new(fs));
+    let received_errors = RefCell::new(Vec::new());
+    let mut updater = new_updater_with_file_system(&received_errors, &fs);
+
+    updater
+        .update_document(
+            "project/src/this_file.qs",
+            1,
+            "namespace Foo { function Main() : Int { Library.Four() + Library.Two() } }",
+        )
+        .await;
+
+    // Only the internal item of the dependency is reported as not found.
+    expect_errors(
+        &received_errors,
+        &expect![[r#"
+        [
+            (
+                "project/src/this_file.qs",
+                Some(
+                    1,
+                ),
+                [
+                    Frontend(
+                        Error(
+                            Resolve(
+                                NotFound(
+                                    "Two",
+                                    Span {
+                                        lo: 65,
+                                        hi: 68,
+                                    },
+                                ),
+                            ),
+                        ),
+                    ),
+                ],
+            ),
+        ]
+    "#]],
+    );
+}
+
 #[tokio::test]
 async fn lints_update_after_manifest_change() {
     let this_file_qs = "namespace Foo { operation Main() : Unit { let x = 5 / 0 + (2 ^ 4); } }";
@@ -84,7 +84,9 @@ fn compile_project_with_fake_stdlib_and_markers_cursor_optional(
         Compilation {
             package_store,
             user_package_id: package_id,
-            kind: CompilationKind::OpenProject,
+            kind: CompilationKind::OpenProject {
+                dependencies: Vec::new(),
+            },
             errors,
         },
         cursor_location,
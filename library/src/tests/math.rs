@@ -191,6 +191,14 @@ fn check_min() {
     );
     test_expression("Microsoft.Quantum.Math.Min([5, 7, 1, 10])", &Value::Int(1));
     test_expression("Microsoft.Quantum.Math.Min([1])", &Value::Int(1));
+    test_expression(
+        "Microsoft.Quantum.Math.Min([2.5, -1.5])",
+        &Value::Double(-1.5),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.Min([3L, -4L])",
+        &Value::BigInt(BigInt::from(-4)),
+    );
 }
 
 #[test]
@@ -201,6 +209,14 @@ fn check_max() {
     );
     test_expression("Microsoft.Quantum.Math.Max([5, 7, 1, 20])", &Value::Int(20));
     test_expression("Microsoft.Quantum.Math.Max([1])", &Value::Int(1));
+    test_expression(
+        "Microsoft.Quantum.Math.Max([2.5, -1.5])",
+        &Value::Double(2.5),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.Max([3L, -4L])",
+        &Value::BigInt(BigInt::from(3)),
+    );
 }
 
 //
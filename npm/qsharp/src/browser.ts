@@ -5,7 +5,10 @@
 // the "./main.js" module is the entry point.
 
 import * as wasm from "../lib/web/qsc_wasm.js";
-import initWasm, { TargetProfile } from "../lib/web/qsc_wasm.js";
+import initWasm, {
+  OptimizationLevel,
+  TargetProfile,
+} from "../lib/web/qsc_wasm.js";
 import {
   Compiler,
   ICompiler,
@@ -164,7 +167,9 @@ export type {
   ICodeLens,
   ILocation,
   IOperationInfo,
+  IPackageDependency,
   IPosition,
+  IProjectSources,
   IRange,
   IStackFrame,
   VSDiagnostic,
@@ -190,7 +195,12 @@ export {
 } from "./katas.js";
 export { type LanguageServiceEvent } from "./language-service/language-service.js";
 export { default as samples } from "./samples.generated.js";
-export { log, type LogLevel, type TargetProfile };
+export {
+  log,
+  type LogLevel,
+  type OptimizationLevel,
+  type TargetProfile,
+};
 export type {
   ICompiler,
   ICompilerWorker,
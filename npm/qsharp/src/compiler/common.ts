@@ -19,6 +19,14 @@ interface DumpMsg {
   stateLatex: string;
 }
 
+// Each DumpOperation output is represented as a matrix of [real, imag] pairs,
+// one row per basis state of the operation's output.
+interface MatrixMsg {
+  type: "Matrix";
+  matrix: [number, number][][];
+  matrixLatex: string;
+}
+
 interface MessageMsg {
   type: "Message";
   message: string;
@@ -29,7 +37,7 @@ interface ResultMsg {
   result: Result;
 }
 
-type EventMsg = ResultMsg | DumpMsg | MessageMsg;
+type EventMsg = ResultMsg | DumpMsg | MatrixMsg | MessageMsg;
 
 function outputAsResult(msg: string): ResultMsg | null {
   try {
@@ -73,12 +81,29 @@ function outputAsDump(msg: string): DumpMsg | null {
   return null;
 }
 
+function outputAsMatrix(msg: string): MatrixMsg | null {
+  try {
+    const obj = JSON.parse(msg);
+    if (obj?.type == "Matrix" && Array.isArray(obj.matrix)) {
+      return obj as MatrixMsg;
+    }
+  } catch {
+    return null;
+  }
+  return null;
+}
+
 export function eventStringToMsg(msg: string): EventMsg | null {
-  return outputAsResult(msg) || outputAsMessage(msg) || outputAsDump(msg);
+  return (
+    outputAsResult(msg) ||
+    outputAsMessage(msg) ||
+    outputAsDump(msg) ||
+    outputAsMatrix(msg)
+  );
 }
 
 export type ShotResult = {
   success: boolean;
   result: string | VSDiagnostic;
-  events: Array<MessageMsg | DumpMsg>;
+  events: Array<MessageMsg | DumpMsg | MatrixMsg>;
 };
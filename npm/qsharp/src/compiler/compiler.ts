@@ -4,10 +4,14 @@
 import { type Circuit as CircuitData } from "@microsoft/quantum-viz.js/lib/circuit.js";
 import {
   IDocFile,
+  IExecutionLimits,
   IOperationInfo,
+  IPackageDependency,
+  OptimizationLevel,
   TargetProfile,
   type VSDiagnostic,
 } from "../../lib/web/qsc_wasm.js";
+import { CancellationToken } from "../cancellation.js";
 import { log } from "../log.js";
 import {
   IServiceProxy,
@@ -55,11 +59,16 @@ export interface ICompiler {
     shots: number,
     eventHandler: IQscEventTarget,
   ): Promise<void>;
+  /**
+   * Runs the program for the given number of shots. When the compiler runs in a worker,
+   * cancelling the token while the program is running terminates the worker.
+   */
   run(
     config: ProgramConfig,
     expr: string,
     shots: number,
     eventHandler: IQscEventTarget,
+    cancellationToken?: CancellationToken,
   ): Promise<void>;
 
   /** @deprecated -- switch to using `ProgramConfig`-based overload. Instead of passing
@@ -110,6 +119,12 @@ export type ProgramConfig = {
   languageFeatures?: string[];
   /** Target compilation profile. */
   profile?: TargetProfile;
+  /** Optimization level used when generating QIR for profiles other than Base. Defaults to "basic". */
+  optimizationLevel?: OptimizationLevel;
+  /** Bounds on the work each shot may do before it is stopped with an error. */
+  executionLimits?: IExecutionLimits;
+  /** Packages the program depends on, each compiled as its own package. */
+  dependencies?: IPackageDependency[];
 };
 
 // WebWorker also support being explicitly terminated to tear down the worker thread
@@ -163,15 +178,23 @@ export class Compiler implements ICompiler {
     sources,
     languageFeatures = [],
     profile = "base",
+    dependencies = [],
+    optimizationLevel,
   }: ProgramConfig): Promise<string> {
-    return this.wasm.get_qir(sources, languageFeatures, profile);
+    return this.wasm.get_qir(
+      sources,
+      languageFeatures,
+      dependencies,
+      profile,
+      optimizationLevel,
+    );
   }
 
   async deprecatedGetQir(
     sources: [string, string][],
     languageFeatures: string[],
   ): Promise<string> {
-    return this.wasm.get_qir(sources, languageFeatures, "base");
+    return this.wasm.get_qir(sources, languageFeatures, [], "base", undefined);
   }
 
   async getEstimates(
@@ -191,10 +214,15 @@ export class Compiler implements ICompiler {
   }
 
   async newGetEstimates(
-    { sources, languageFeatures }: ProgramConfig,
+    { sources, languageFeatures, dependencies }: ProgramConfig,
     params: string,
   ): Promise<string> {
-    return this.wasm.get_estimates(sources, params, languageFeatures || []);
+    return this.wasm.get_estimates(
+      sources,
+      params,
+      languageFeatures || [],
+      dependencies || [],
+    );
   }
 
   async deprecatedGetEstimates(
@@ -202,7 +230,7 @@ export class Compiler implements ICompiler {
     params: string,
     languageFeatures: string[],
   ): Promise<string> {
-    return this.wasm.get_estimates(sources, params, languageFeatures);
+    return this.wasm.get_estimates(sources, params, languageFeatures, []);
   }
 
   async getAst(
@@ -234,9 +262,15 @@ export class Compiler implements ICompiler {
     expr: string,
     shots: number,
     eventHandler: IQscEventTarget,
+    cancellationToken?: CancellationToken,
   ): Promise<void> {
+    // Wasm runs synchronously, so the token can only be checked before starting.
+    if (cancellationToken?.isCancellationRequested) throw "cancelled";
+
     let sources;
     let languageFeatures: string[] = [];
+    let executionLimits: IExecutionLimits | undefined;
+    let dependencies: IPackageDependency[] = [];
 
     if (Array.isArray(sourcesOrConfig)) {
       // this is the deprecated API
@@ -245,6 +279,8 @@ export class Compiler implements ICompiler {
       // this is the new API
       sources = sourcesOrConfig.sources;
       languageFeatures = sourcesOrConfig.languageFeatures || [];
+      executionLimits = sourcesOrConfig.executionLimits;
+      dependencies = sourcesOrConfig.dependencies || [];
     }
     // All results are communicated as events, but if there is a compiler error (e.g. an invalid
     // entry expression or similar), it may throw on run. The caller should expect this promise
@@ -255,6 +291,8 @@ export class Compiler implements ICompiler {
       (msg: string) => onCompilerEvent(msg, eventHandler!),
       shots!,
       languageFeatures,
+      dependencies,
+      executionLimits,
     );
   }
 
@@ -268,6 +306,7 @@ export class Compiler implements ICompiler {
       config.sources,
       target,
       config.languageFeatures || [],
+      config.dependencies || [],
       simulate,
       operation,
     );
@@ -312,6 +351,12 @@ export function onCompilerEvent(msg: string, eventTarget: IQscEventTarget) {
         stateLatex: qscMsg.stateLatex,
       });
       break;
+    case "Matrix":
+      qscEvent = makeEvent("Matrix", {
+        matrix: qscMsg.matrix,
+        matrixLatex: qscMsg.matrixLatex,
+      });
+      break;
     case "Result":
       qscEvent = makeEvent("Result", qscMsg.result);
       break;
@@ -337,5 +382,5 @@ export const compilerProtocol: ServiceProtocol<ICompiler, QscEventData> = {
     run: "requestWithProgress",
     checkExerciseSolution: "requestWithProgress",
   },
-  eventNames: ["DumpMachine", "Message", "Result"],
+  eventNames: ["DumpMachine", "Matrix", "Message", "Result"],
 };
@@ -9,6 +9,10 @@ import { IServiceEventTarget } from "../workers/common.js";
 export type QscEventData =
   | { type: "Message"; detail: string }
   | { type: "DumpMachine"; detail: { state: Dump; stateLatex: string } }
+  | {
+      type: "Matrix";
+      detail: { matrix: [number, number][][]; matrixLatex: string };
+    }
   | { type: "Result"; detail: Result };
 
 export type QscEvents = Event & QscEventData;
@@ -80,6 +84,7 @@ export class QscEventTarget implements IQscEventTarget {
       this.addEventListener("DumpMachine", (ev) =>
         this.onDumpMachine(ev.detail),
       );
+      this.addEventListener("Matrix", (ev) => this.onMatrix(ev.detail));
       this.addEventListener("Result", (ev) => this.onResult(ev.detail));
     }
   }
@@ -106,6 +111,22 @@ export class QscEventTarget implements IQscEventTarget {
     this.queueUiRefresh();
   }
 
+  private onMatrix(detail: {
+    matrix: [number, number][][];
+    matrixLatex: string;
+  }) {
+    this.ensureActiveShot();
+
+    const shotIdx = this.results.length - 1;
+    this.results[shotIdx].events.push({
+      type: "Matrix",
+      matrix: detail.matrix,
+      matrixLatex: detail.matrixLatex,
+    });
+
+    this.queueUiRefresh();
+  }
+
   private onResult(result: Result) {
     this.ensureActiveShot();
 
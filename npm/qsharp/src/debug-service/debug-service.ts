@@ -5,6 +5,7 @@ import { type Circuit as CircuitData } from "@microsoft/quantum-viz.js/lib/circu
 import type {
   DebugService,
   IBreakpointSpan,
+  IPackageDependency,
   IQuantumState,
   IStackFrame,
   IStructStepResult,
@@ -31,6 +32,7 @@ export interface IDebugService {
     target: TargetProfile,
     entry: string | undefined,
     language_features: string[],
+    dependencies?: IPackageDependency[],
   ): Promise<string>;
   getBreakpoints(path: string): Promise<IBreakpointSpan[]>;
   getLocalVariables(): Promise<Array<IVariable>>;
@@ -73,12 +75,14 @@ export class QSharpDebugService implements IDebugService {
     target: TargetProfile,
     entry: string | undefined,
     language_features: string[],
+    dependencies?: IPackageDependency[],
   ): Promise<string> {
     return this.debugService.load_source(
       sources,
       target,
       entry,
       language_features,
+      dependencies ?? [],
     );
   }
 
@@ -165,6 +169,12 @@ export function onCompilerEvent(msg: string, eventTarget: IQscEventTarget) {
         stateLatex: qscMsg.stateLatex,
       });
       break;
+    case "Matrix":
+      qscEvent = makeEvent("Matrix", {
+        matrix: qscMsg.matrix,
+        matrixLatex: qscMsg.matrixLatex,
+      });
+      break;
     case "Result":
       qscEvent = makeEvent("Result", qscMsg.result);
       break;
@@ -195,5 +205,5 @@ export const debugServiceProtocol: ServiceProtocol<
     evalStepOut: "requestWithProgress",
     dispose: "request",
   },
-  eventNames: ["DumpMachine", "Message", "Result"],
+  eventNames: ["DumpMachine", "Matrix", "Message", "Result"],
 };
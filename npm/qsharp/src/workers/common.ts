@@ -34,7 +34,9 @@ export type ServiceMethods<T> = { [x in keyof T]: (...args: any[]) => any };
  *
  * "requestWithProgress" methods take an `IServiceEventTarget` to
  *   communicate events back to the main thread as they run. They also set
- *   the service state to "busy" while they run.
+ *   the service state to "busy" while they run. They may be passed a
+ *   `CancellationToken` after the event target, which terminates the worker
+ *   if it is cancelled while the request is running.
  *
  * "addEventListener" and "removeEventListener" methods are used to
  *   subscribe to events from the service.
@@ -179,14 +181,26 @@ export function createProxyInternal<
     cancellationToken?: CancellationToken,
   ): Promise<ResultOf<ResponseMessage<TService>>> {
     return new Promise((resolve, reject) => {
-      queue.push({
+      const request = {
         type: msg.type,
         args: msg.args,
         resolve,
         reject,
         requestEventTarget,
         cancellationToken,
-      } as RequestState<TService, TServiceEventMsg>);
+      } as RequestState<TService, TServiceEventMsg>;
+      queue.push(request);
+
+      cancellationToken?.onCancellationRequested(() => {
+        // The worker can't observe the cancellation while it is busy running the request,
+        // so the only way to stop it is to terminate the worker.
+        if (curr === request) {
+          log.info("Proxy: Request cancelled, terminating the worker");
+          curr.reject("cancelled");
+          curr = undefined;
+          proxy.terminate();
+        }
+      });
 
       // If nothing was running when this got added, kick off processing
       if (queue.length === 1) doNextRequest();
@@ -289,6 +303,7 @@ export function createProxyInternal<
       let requestEventTarget:
         | IServiceEventTarget<TServiceEventMsg>
         | undefined = undefined;
+      let cancellationToken: CancellationToken | undefined = undefined;
 
       switch (methods[methodName]) {
         case "addEventListener":
@@ -304,7 +319,12 @@ export function createProxyInternal<
           }
           break;
         case "requestWithProgress": {
-          // For progress methods, the last argument is the event target
+          // For progress methods, the event target is the last argument,
+          // optionally followed by a cancellation token
+          if (isCancellationToken(args[args.length - 1])) {
+            cancellationToken = args[args.length - 1];
+            args = args.slice(0, args.length - 1);
+          }
           requestEventTarget = args[args.length - 1];
           args = args.slice(0, args.length - 1);
         }
@@ -313,6 +333,7 @@ export function createProxyInternal<
           return queueRequest(
             { type: methodName, args } as RequestMessage<TService>,
             requestEventTarget,
+            cancellationToken,
           );
         }
       }
@@ -346,6 +367,15 @@ export function createProxyInternal<
   return proxy;
 }
 
+function isCancellationToken(arg: unknown): arg is CancellationToken {
+  return (
+    typeof arg === "object" &&
+    arg !== null &&
+    "isCancellationRequested" in arg &&
+    "onCancellationRequested" in arg
+  );
+}
+
 /**
  * Function to wrap a service in a dispatcher. To be used in the worker thread.
  *
@@ -150,6 +150,30 @@ test("dump and message output", async () => {
   assert(result.events[1].message == "hello, qsharp");
 });
 
+test("dump operation output", async () => {
+  let code = `namespace Test {
+        operation Main() : Unit {
+            Microsoft.Quantum.Diagnostics.DumpOperation(1, qs => X(qs[0]));
+        }
+    }`;
+  let expr = `Test.Main()`;
+
+  const result = await runSingleShot(code, expr, true);
+  assert(result.success);
+  assert.equal(result.events.length, 1);
+  assert.equal(result.events[0].type, "Matrix");
+  assert.deepEqual(result.events[0].matrix, [
+    [
+      [0, 0],
+      [1, 0],
+    ],
+    [
+      [1, 0],
+      [0, 0],
+    ],
+  ]);
+});
+
 async function runExerciseSolutionCheck(exercise, solution) {
   const evtTarget = new QscEventTarget(true);
   const compiler = getCompiler();
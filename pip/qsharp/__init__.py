@@ -8,15 +8,21 @@ from ._qsharp import (
     compile,
     circuit,
     estimate,
+    run_qir,
+    run_rir,
+    estimate_qir,
     set_quantum_seed,
     set_classical_seed,
+    set_limits,
+    replay_measurements,
+    save_measurements,
     dump_machine,
     dump_circuit,
     StateDump,
     ShotResult,
 )
 
-from ._native import Result, Pauli, QSharpError, TargetProfile
+from ._native import Result, Pauli, QSharpError, TargetProfile, qir_to_rir
 
 # IPython notebook specific features
 try:
@@ -35,11 +41,18 @@ __all__ = [
     "run",
     "set_quantum_seed",
     "set_classical_seed",
+    "set_limits",
+    "replay_measurements",
+    "save_measurements",
     "dump_machine",
     "dump_circuit",
     "compile",
     "circuit",
     "estimate",
+    "qir_to_rir",
+    "run_qir",
+    "run_rir",
+    "estimate_qir",
     "Result",
     "Pauli",
     "QSharpError",
@@ -65,6 +65,7 @@ class Interpreter:
         :returns value: The value returned by the last statement in the input.
 
         :raises QSharpError: If there is an error interpreting the input.
+        :raises KeyboardInterrupt: If the evaluation is interrupted.
         """
         ...
 
@@ -78,16 +79,22 @@ class Interpreter:
         :returns values: A result or runtime errors.
 
         :raises QSharpError: If there is an error interpreting the input.
+        :raises KeyboardInterrupt: If the evaluation is interrupted.
         """
         ...
 
-    def qir(self, entry_expr: str) -> str:
+    def qir(self, entry_expr: str, optimization_level: Optional[str]) -> str:
         """
         Generates QIR from Q# source code.
 
         :param entry_expr: The entry expression.
 
+        :param optimization_level: The optimization level to use when generating
+        QIR for targets other than Base, one of "none", "basic" or "full". Defaults to "basic".
+
         :returns qir: The QIR string.
+
+        :raises KeyboardInterrupt: If the generation is interrupted.
         """
         ...
 
@@ -107,6 +114,7 @@ class Interpreter:
         qubits or arrays of qubits as parameters.
 
         :raises QSharpError: If there is an error synthesizing the circuit.
+        :raises KeyboardInterrupt: If the synthesis is interrupted.
         """
         ...
 
@@ -118,6 +126,8 @@ class Interpreter:
         :param params: The parameters to configure estimation.
 
         :returns resources: The estimated resources.
+
+        :raises KeyboardInterrupt: If the estimation is interrupted.
         """
         ...
 
@@ -139,6 +149,43 @@ class Interpreter:
         """
         ...
 
+    def set_limits(
+        self,
+        max_steps: Optional[int],
+        time_limit: Optional[float],
+        max_qubits: Optional[int],
+        max_array_length: Optional[int],
+        max_call_depth: Optional[int],
+    ) -> None:
+        """
+        Sets the limits applied to each evaluation, including the evaluations done to
+        generate QIR, synthesize circuits and estimate resources. A limit that is None
+        is not applied.
+
+        :param max_steps: The maximum number of evaluation steps.
+        :param time_limit: The maximum time to spend evaluating, in seconds.
+        :param max_qubits: The maximum number of qubits allocated at the same time.
+        :param max_array_length: The maximum length of any array created during evaluation.
+        :param max_call_depth: The maximum depth of the call stack.
+        """
+        ...
+
+    def set_measurement_replay(self, record: str) -> None:
+        """
+        Forces the outcomes in the given measurement record, in order, on subsequent
+        measurements. Measurements beyond the end of the record are simulated normally.
+
+        :param record: The measurement record, one 0 or 1 outcome per line.
+        """
+        ...
+
+    def measurement_record(self) -> str:
+        """
+        Returns the outcomes measured since the interpreter was created or the
+        measurement replay was last set, as a measurement record.
+        """
+        ...
+
     def dump_machine(self) -> StateDumpData:
         """
         Returns the sparse state vector of the simulator as a StateDump object.
@@ -226,3 +273,58 @@ def physical_estimates(logical_resources: str, params: str) -> str:
     :returns resources: The estimated resources.
     """
     ...
+
+def qir_to_rir(qir: str) -> str:
+    """
+    Reads a Base or Adaptive profile QIR program and returns the equivalent
+    RIR program in its textual form.
+
+    :param qir: The QIR program, in LLVM textual form.
+
+    :returns rir: The RIR program.
+
+    :raises QSharpError: If the program is malformed or uses constructs that
+        are not supported by the Base and Adaptive profiles.
+    """
+    ...
+
+def run_qir(qir: str, shots: int, seed: Optional[int]) -> List[Any]:
+    """
+    Runs a Base or Adaptive profile QIR program on the sparse simulator.
+
+    :param qir: The QIR program, in LLVM textual form.
+    :param shots: The number of times to run the program.
+    :param seed: The seed for the simulator, or None to seed it from entropy.
+
+    :returns values: The value recorded as output by each shot.
+
+    :raises QSharpError: If the program cannot be read or fails at runtime.
+    """
+    ...
+
+def run_rir(rir: str, shots: int, seed: Optional[int]) -> List[Any]:
+    """
+    Runs a RIR program, in its textual form, on the sparse simulator.
+
+    :param rir: The RIR program, in its textual form.
+    :param shots: The number of times to run the program.
+    :param seed: The seed for the simulator, or None to seed it from entropy.
+
+    :returns values: The value recorded as output by each shot.
+
+    :raises QSharpError: If the program cannot be parsed or fails at runtime.
+    """
+    ...
+
+def estimate_qir(qir: str, job_params: str) -> str:
+    """
+    Estimates resources for a Base or Adaptive profile QIR program.
+
+    :param qir: The QIR program, in LLVM textual form.
+    :param job_params: The parameters to configure physical estimation.
+
+    :returns resources: The estimated resources.
+
+    :raises QSharpError: If the program cannot be read or fails at runtime.
+    """
+    ...
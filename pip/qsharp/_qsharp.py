@@ -9,6 +9,7 @@ from ._native import (
     Output,
     Circuit,
 )
+from . import _native
 from warnings import warn
 from typing import Any, Callable, Dict, Optional, Tuple, TypedDict, Union, List
 from .estimator._estimator import EstimatorResult, EstimatorParams
@@ -219,12 +220,16 @@ class QirInputData:
         return self._ll_str
 
 
-def compile(entry_expr: str) -> QirInputData:
+def compile(
+    entry_expr: str, optimization_level: Optional[str] = None
+) -> QirInputData:
     """
     Compiles the Q# source code into a program that can be submitted to a target.
 
     :param entry_expr: The Q# expression that will be used as the entrypoint
         for the program.
+    :param optimization_level: The optimization level to use when generating QIR
+        for targets other than Base, one of "none", "basic" or "full". Defaults to "basic".
 
     :returns QirInputData: The compiled program.
 
@@ -237,7 +242,7 @@ def compile(entry_expr: str) -> QirInputData:
         with open('myfile.ll', 'w') as file:
             file.write(str(program))
     """
-    ll_str = get_interpreter().qir(entry_expr)
+    ll_str = get_interpreter().qir(entry_expr, optimization_level)
     return QirInputData("main", ll_str)
 
 
@@ -270,6 +275,72 @@ def estimate(
 
     :returns resources: The estimated resources.
     """
+    return EstimatorResult(
+        json.loads(
+            get_interpreter().estimate(entry_expr, _estimator_params_json(params))
+        )
+    )
+
+
+def run_qir(
+    qir: Union[str, QirInputData], shots: int = 1, seed: Optional[int] = None
+) -> List[Any]:
+    """
+    Runs a Base or Adaptive profile QIR program, such as one produced by another
+    toolchain, on the sparse simulator.
+
+    :param qir: The QIR program, in LLVM textual form.
+    :param shots: The number of times to run the program.
+    :param seed: The seed for the simulator. If None, the seed will be generated
+        from entropy.
+
+    :returns values: The value recorded as output by each shot.
+
+    :raises QSharpError: If the program cannot be read or fails at runtime.
+    """
+    return _native.run_qir(str(qir), shots, seed)
+
+
+def run_rir(rir: str, shots: int = 1, seed: Optional[int] = None) -> List[Any]:
+    """
+    Runs a RIR program, in its textual form, on the sparse simulator. This can be
+    used to check that a program read with `qir_to_rir` behaves as expected.
+
+    :param rir: The RIR program, in its textual form.
+    :param shots: The number of times to run the program.
+    :param seed: The seed for the simulator. If None, the seed will be generated
+        from entropy.
+
+    :returns values: The value recorded as output by each shot.
+
+    :raises QSharpError: If the program cannot be parsed or fails at runtime.
+    """
+    return _native.run_rir(rir, shots, seed)
+
+
+def estimate_qir(
+    qir: Union[str, QirInputData],
+    params: Optional[Union[Dict[str, Any], List, EstimatorParams]] = None,
+) -> EstimatorResult:
+    """
+    Estimates resources for a Base or Adaptive profile QIR program, such as one
+    produced by another toolchain.
+
+    :param qir: The QIR program, in LLVM textual form.
+    :param params: The parameters to configure physical estimation.
+
+    :returns resources: The estimated resources.
+
+    :raises QSharpError: If the program cannot be read or fails at runtime.
+    """
+    return EstimatorResult(
+        json.loads(_native.estimate_qir(str(qir), _estimator_params_json(params)))
+    )
+
+
+def _estimator_params_json(
+    params: Optional[Union[Dict[str, Any], List, EstimatorParams]]
+) -> str:
     if params is None:
         params = [{}]
     elif isinstance(params, EstimatorParams):
@@ -279,9 +350,7 @@ def estimate(
             params = [params.as_dict()]
     elif isinstance(params, dict):
         params = [params]
-    return EstimatorResult(
-        json.loads(get_interpreter().estimate(entry_expr, json.dumps(params)))
-    )
+    return json.dumps(params)
 
 
 def set_quantum_seed(seed: Optional[int]) -> None:
@@ -307,6 +376,53 @@ def set_classical_seed(seed: Optional[int]) -> None:
     get_interpreter().set_classical_seed(seed)
 
 
+def set_limits(
+    max_steps: Optional[int] = None,
+    time_limit: Optional[float] = None,
+    max_qubits: Optional[int] = None,
+    max_array_length: Optional[int] = None,
+    max_call_depth: Optional[int] = None,
+) -> None:
+    """
+    Sets the limits applied to each evaluation, replacing any previously set limits.
+    This applies to all Q# code executed, compiled, or estimated. Exceeding a limit
+    stops the evaluation with a QSharpError.
+
+    :param max_steps: The maximum number of evaluation steps.
+    :param time_limit: The maximum time to spend evaluating, in seconds.
+    :param max_qubits: The maximum number of qubits allocated at the same time.
+    :param max_array_length: The maximum length of any array created during evaluation.
+    :param max_call_depth: The maximum depth of the call stack.
+    """
+    get_interpreter().set_limits(
+        max_steps, time_limit, max_qubits, max_array_length, max_call_depth
+    )
+
+
+def replay_measurements(path: str) -> None:
+    """
+    Forces the measurement outcomes recorded in the given file, in order, on subsequent
+    measurements, and clears the recorded outcomes. Measurements beyond the end of the
+    file are simulated normally.
+
+    :param path: The path of a file written by `save_measurements`.
+    :raises QSharpError: If the file is not a valid measurement record.
+    """
+    with open(path, "r", encoding="utf-8") as f:
+        get_interpreter().set_measurement_replay(f.read())
+
+
+def save_measurements(path: str) -> None:
+    """
+    Writes the outcome of every measurement performed since the interpreter was
+    initialized, or since `replay_measurements` was last called, to the given file.
+
+    :param path: The path of the file to write.
+    """
+    with open(path, "w", encoding="utf-8") as f:
+        f.write(get_interpreter().measurement_record())
+
+
 class StateDump:
     """
     A state dump returned from the Q# interpreter.
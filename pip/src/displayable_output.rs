@@ -6,7 +6,7 @@ mod tests;
 
 use num_bigint::BigUint;
 use num_complex::{Complex64, ComplexFloat};
-use qsc::{fmt_basis_state_label, fmt_complex, format_state_id, get_phase};
+use qsc::{fmt_basis_state_label, fmt_complex, format_state_id, get_matrix_latex, get_phase};
 use std::fmt::Write;
 
 #[derive(Clone)]
@@ -53,7 +53,39 @@ impl DisplayableState {
     }
 }
 
+#[derive(Clone)]
+pub struct DisplayableMatrix(pub Vec<Vec<Complex64>>);
+
+impl DisplayableMatrix {
+    pub fn to_plain(&self) -> String {
+        format!(
+            "MATRIX:{}",
+            self.0.iter().fold(String::new(), |mut output, row| {
+                let row = row.iter().map(fmt_complex).collect::<Vec<_>>();
+                let _ = write!(output, "\n{}", row.join(" "));
+                output
+            })
+        )
+    }
+
+    pub fn to_html(&self) -> String {
+        let rows = self.0.iter().fold(String::new(), |mut output, row| {
+            let cells = row.iter().fold(String::new(), |mut cells, cell| {
+                let _ = write!(cells, "<td>{}</td>", fmt_complex(cell));
+                cells
+            });
+            let _ = write!(output, "<tr>{cells}</tr>");
+            output
+        });
+        format!(
+            "<table><tbody>{rows}</tbody></table><p>{}</p>",
+            get_matrix_latex(&self.0)
+        )
+    }
+}
+
 pub enum DisplayableOutput {
     State(DisplayableState),
+    Matrix(DisplayableMatrix),
     Message(String),
 }
@@ -4,7 +4,7 @@
 use num_bigint::BigUint;
 use num_complex::Complex;
 
-use crate::displayable_output::DisplayableState;
+use crate::displayable_output::{DisplayableMatrix, DisplayableState};
 
 #[test]
 fn display_neg_zero() {
@@ -39,3 +39,15 @@ fn display_preserves_order() {
         s.to_plain()
     );
 }
+
+#[test]
+fn display_matrix_rows() {
+    let m = DisplayableMatrix(vec![
+        vec![Complex::new(0.0, 0.0), Complex::new(1.0, 0.0)],
+        vec![Complex::new(0.0, 1.0), Complex::new(-0.0, -0.0)],
+    ]);
+    assert_eq!(
+        "MATRIX:\n0.0000+0.0000𝑖 1.0000+0.0000𝑖\n0.0000+1.0000𝑖 0.0000+0.0000𝑖",
+        m.to_plain()
+    );
+}
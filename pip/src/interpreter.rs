@@ -2,8 +2,9 @@
 // Licensed under the MIT License.
 
 use crate::{
-    displayable_output::{DisplayableOutput, DisplayableState},
+    displayable_output::{DisplayableMatrix, DisplayableOutput, DisplayableState},
     fs::file_system,
+    interrupt::{cancellation_token, with_keyboard_interrupt},
 };
 use miette::Report;
 use num_bigint::BigUint;
@@ -16,18 +17,20 @@ use pyo3::{
     types::{PyComplex, PyDict, PyList, PyString, PyTuple},
 };
 use qsc::{
-    fir,
+    codegen::OptimizationLevel,
+    fir, format_measurement_record,
     interpret::{
         self,
-        output::{Error, Receiver},
-        CircuitEntryPoint, Value,
+        output::{output_value, Error, Receiver},
+        CircuitEntryPoint, GenericReceiver, Limits, Value,
     },
+    parse_measurement_record,
     project::{FileSystem, Manifest, ManifestDescriptor},
     target::Profile,
-    LanguageFeatures, PackageType, SourceMap,
+    Backend, LanguageFeatures, PackageType, SourceMap, SparseSim,
 };
 use resource_estimator::{self as re, estimate_expr};
-use std::fmt::Write;
+use std::{fmt::Write, str::FromStr, time::Duration};
 
 #[pymodule]
 fn _native(py: Python, m: &PyModule) -> PyResult<()> {
@@ -39,6 +42,10 @@ fn _native(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<StateDumpData>()?;
     m.add_class::<Circuit>()?;
     m.add_function(wrap_pyfunction!(physical_estimates, m)?)?;
+    m.add_function(wrap_pyfunction!(qir_to_rir, m)?)?;
+    m.add_function(wrap_pyfunction!(run_qir, m)?)?;
+    m.add_function(wrap_pyfunction!(run_rir, m)?)?;
+    m.add_function(wrap_pyfunction!(estimate_qir, m)?)?;
     m.add("QSharpError", py.get_type::<QSharpError>())?;
 
     Ok(())
@@ -128,7 +135,7 @@ impl Interpreter {
             (None, None) => vec![],
         };
 
-        let sources = if let Some(manifest_descriptor) = manifest_descriptor {
+        let (sources, dependencies) = if let Some(manifest_descriptor) = manifest_descriptor {
             let project = file_system(
                 py,
                 read_file.expect(
@@ -140,21 +147,25 @@ impl Interpreter {
             )
             .load_project(&manifest_descriptor.0)
             .map_py_err()?;
-            SourceMap::new(project.sources, None)
+            (SourceMap::new(project.sources, None), project.dependencies)
         } else {
-            SourceMap::default()
+            (SourceMap::default(), Vec::new())
         };
 
         let language_features = LanguageFeatures::from_iter(language_features);
 
-        match interpret::Interpreter::new(
+        match interpret::Interpreter::with_dependencies(
             true,
+            &dependencies,
             sources,
             PackageType::Lib,
             target.into(),
             language_features,
         ) {
-            Ok(interpreter) => Ok(Self { interpreter }),
+            Ok(mut interpreter) => {
+                interpreter.set_cancellation_token(cancellation_token());
+                Ok(Self { interpreter })
+            }
             Err(errors) => Err(QSharpError::new_err(format_errors(errors))),
         }
     }
@@ -167,6 +178,7 @@ impl Interpreter {
     /// :returns value: The value returned by the last statement in the input.
     ///
     /// :raises QSharpError: If there is an error interpreting the input.
+    /// :raises KeyboardInterrupt: If the evaluation is interrupted.
     fn interpret(
         &mut self,
         py: Python,
@@ -174,7 +186,7 @@ impl Interpreter {
         callback: Option<PyObject>,
     ) -> PyResult<PyObject> {
         let mut receiver = OptionalCallbackReceiver { callback, py };
-        match self.interpreter.eval_fragments(&mut receiver, input) {
+        match with_keyboard_interrupt(|| self.interpreter.eval_fragments(&mut receiver, input))? {
             Ok(value) => Ok(ValueWrapper(value).into_py(py)),
             Err(errors) => Err(QSharpError::new_err(format_errors(errors))),
         }
@@ -190,6 +202,44 @@ impl Interpreter {
         self.interpreter.set_classical_seed(seed);
     }
 
+    /// Sets the limits applied to each evaluation. A limit that is not given is not applied.
+    fn set_limits(
+        &mut self,
+        max_steps: Option<u64>,
+        time_limit: Option<f64>,
+        max_qubits: Option<usize>,
+        max_array_length: Option<usize>,
+        max_call_depth: Option<usize>,
+    ) -> PyResult<()> {
+        let time_limit = time_limit
+            .map(Duration::try_from_secs_f64)
+            .transpose()
+            .map_err(|_| {
+                PyException::new_err("time_limit must be a non-negative number of seconds")
+            })?;
+        self.interpreter.set_limits(Limits {
+            max_steps,
+            time_limit,
+            max_qubits,
+            max_array_len: max_array_length,
+            max_call_depth,
+        });
+        Ok(())
+    }
+
+    /// Forces the outcomes in the given measurement record, in order, on subsequent measurements.
+    fn set_measurement_replay(&mut self, record: &str) -> PyResult<()> {
+        let outcomes = parse_measurement_record(record).map_err(QSharpError::new_err)?;
+        self.interpreter.set_measurement_replay(outcomes);
+        Ok(())
+    }
+
+    /// Returns the measurement record of the outcomes measured since the interpreter was
+    /// created or the measurement replay was last set.
+    fn measurement_record(&self) -> String {
+        format_measurement_record(self.interpreter.recorded_measurements())
+    }
+
     /// Dumps the quantum state of the interpreter.
     /// Returns a tuple of (amplitudes, num_qubits), where amplitudes is a dictionary from integer indices to
     /// pairs of real and imaginary amplitudes.
@@ -213,7 +263,7 @@ impl Interpreter {
         callback: Option<PyObject>,
     ) -> PyResult<PyObject> {
         let mut receiver = OptionalCallbackReceiver { callback, py };
-        match self.interpreter.run(&mut receiver, entry_expr) {
+        match with_keyboard_interrupt(|| self.interpreter.run(&mut receiver, entry_expr))? {
             Ok(result) => match result {
                 Ok(v) => Ok(ValueWrapper(v).into_py(py)),
                 Err(errors) => Err(QSharpError::new_err(format_errors(errors))),
@@ -222,8 +272,20 @@ impl Interpreter {
         }
     }
 
-    fn qir(&mut self, _py: Python, entry_expr: &str) -> PyResult<String> {
-        match self.interpreter.qirgen(entry_expr) {
+    fn qir(
+        &mut self,
+        _py: Python,
+        entry_expr: &str,
+        optimization_level: Option<&str>,
+    ) -> PyResult<String> {
+        let optimization_level = match optimization_level {
+            Some(level) => OptimizationLevel::from_str(level).map_err(|()| {
+                QSharpError::new_err(format!("invalid optimization level {level}"))
+            })?,
+            None => OptimizationLevel::Basic,
+        };
+        self.interpreter.set_optimization_level(optimization_level);
+        match with_keyboard_interrupt(|| self.interpreter.qirgen(entry_expr))? {
             Ok(qir) => Ok(qir),
             Err(errors) => Err(QSharpError::new_err(format_errors(errors))),
         }
@@ -239,6 +301,7 @@ impl Interpreter {
     /// qubits or arrays of qubits as parameters.
     ///
     /// :raises QSharpError: If there is an error synthesizing the circuit.
+    /// :raises KeyboardInterrupt: If the synthesis is interrupted.
     fn circuit(
         &mut self,
         py: Python,
@@ -255,14 +318,16 @@ impl Interpreter {
             }
         };
 
-        match self.interpreter.circuit(entrypoint, false) {
+        match with_keyboard_interrupt(|| self.interpreter.circuit(entrypoint, false))? {
             Ok(circuit) => Ok(Circuit(circuit).into_py(py)),
             Err(errors) => Err(QSharpError::new_err(format_errors(errors))),
         }
     }
 
     fn estimate(&mut self, _py: Python, entry_expr: &str, job_params: &str) -> PyResult<String> {
-        match estimate_expr(&mut self.interpreter, entry_expr, job_params) {
+        match with_keyboard_interrupt(|| {
+            estimate_expr(&mut self.interpreter, entry_expr, job_params)
+        })? {
             Ok(estimate) => Ok(estimate),
             Err(errors) if matches!(errors[0], re::Error::Interpreter(_)) => {
                 Err(QSharpError::new_err(format_errors(
@@ -270,7 +335,7 @@ impl Interpreter {
                         .into_iter()
                         .map(|e| match e {
                             re::Error::Interpreter(e) => e,
-                            re::Error::Estimation(_) => unreachable!(),
+                            re::Error::Rir(_) | re::Error::Estimation(_) => unreachable!(),
                         })
                         .collect::<Vec<_>>(),
                 )))
@@ -280,7 +345,7 @@ impl Interpreter {
                     .into_iter()
                     .map(|e| match e {
                         re::Error::Estimation(e) => e.to_string(),
-                        re::Error::Interpreter(_) => unreachable!(),
+                        re::Error::Interpreter(_) | re::Error::Rir(_) => unreachable!(),
                     })
                     .collect::<Vec<_>>()
                     .join("\n"),
@@ -297,6 +362,74 @@ pub fn physical_estimates(logical_resources: &str, job_params: &str) -> PyResult
     }
 }
 
+#[pyfunction]
+pub fn qir_to_rir(qir: &str) -> PyResult<String> {
+    read_qir(qir).map(|program| program.to_string())
+}
+
+#[pyfunction]
+pub fn run_qir(py: Python, qir: &str, shots: u32, seed: Option<u64>) -> PyResult<PyObject> {
+    run_program(py, &read_qir(qir)?, shots, seed)
+}
+
+#[pyfunction]
+pub fn run_rir(py: Python, rir: &str, shots: u32, seed: Option<u64>) -> PyResult<PyObject> {
+    let program = qsc::rir::parse_program(rir).map_err(|error| {
+        let report = Report::new(error).with_source_code(rir.to_string());
+        QSharpError::new_err(format!("{report:?}"))
+    })?;
+    run_program(py, &program, shots, seed)
+}
+
+/// Runs the program on the sparse simulator for the given number of shots, returning the value recorded as
+/// output by each shot.
+fn run_program(
+    py: Python,
+    program: &qsc::rir::Program,
+    shots: u32,
+    seed: Option<u64>,
+) -> PyResult<PyObject> {
+    let mut sim = SparseSim::new();
+    sim.set_seed(seed);
+    let mut stdout = std::io::sink();
+    let mut out = GenericReceiver::new(&mut stdout);
+    let mut results = Vec::new();
+    for _ in 0..shots {
+        let records = qsc::rir::exec(program, &mut sim, &mut out)
+            .map_err(|error| QSharpError::new_err(format!("{:?}", Report::new(error))))?;
+        let value = output_value(&records).ok_or_else(|| {
+            QSharpError::new_err("the output records of the program do not form a single value")
+        })?;
+        results.push(ValueWrapper(value).into_py(py));
+    }
+    Ok(PyList::new(py, results).into_py(py))
+}
+
+#[pyfunction]
+pub fn estimate_qir(qir: &str, job_params: &str) -> PyResult<String> {
+    let program = read_qir(qir)?;
+    re::estimate_rir(&program, job_params).map_err(|errors| {
+        QSharpError::new_err(
+            errors
+                .into_iter()
+                .map(|e| match e {
+                    re::Error::Rir(e) => format!("{:?}", Report::new(e)),
+                    re::Error::Estimation(e) => e.to_string(),
+                    re::Error::Interpreter(_) => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    })
+}
+
+fn read_qir(qir: &str) -> PyResult<qsc::rir::Program> {
+    qsc::codegen::read_qir(qir).map_err(|error| {
+        let report = Report::new(error).with_source_code(qir.to_string());
+        QSharpError::new_err(format!("{report:?}"))
+    })
+}
+
 create_exception!(
     module,
     QSharpError,
@@ -337,11 +470,12 @@ pub(crate) struct Output(DisplayableOutput);
 
 #[pymethods]
 /// An output returned from the Q# interpreter.
-/// Outputs can be a state dumps or messages. These are normally printed to the console.
+/// Outputs can be state dumps, operation matrices or messages. These are normally printed to the console.
 impl Output {
     fn __repr__(&self) -> String {
         match &self.0 {
             DisplayableOutput::State(state) => state.to_plain(),
+            DisplayableOutput::Matrix(matrix) => matrix.to_plain(),
             DisplayableOutput::Message(msg) => msg.clone(),
         }
     }
@@ -353,6 +487,7 @@ impl Output {
     fn _repr_html_(&self) -> String {
         match &self.0 {
             DisplayableOutput::State(state) => state.to_html(),
+            DisplayableOutput::Matrix(matrix) => matrix.to_html(),
             DisplayableOutput::Message(msg) => format!("<p>{msg}</p>"),
         }
     }
@@ -360,7 +495,7 @@ impl Output {
     fn state_dump(&self) -> Option<StateDumpData> {
         match &self.0 {
             DisplayableOutput::State(state) => Some(StateDumpData(state.clone())),
-            DisplayableOutput::Message(_) => None,
+            DisplayableOutput::Matrix(_) | DisplayableOutput::Message(_) => None,
         }
     }
 }
@@ -534,6 +669,22 @@ impl Receiver for OptionalCallbackReceiver<'_> {
         Ok(())
     }
 
+    fn matrix(&mut self, matrix: Vec<Vec<Complex64>>) -> core::result::Result<(), Error> {
+        if let Some(callback) = &self.callback {
+            let out = DisplayableOutput::Matrix(DisplayableMatrix(matrix));
+            callback
+                .call1(
+                    self.py,
+                    PyTuple::new(
+                        self.py,
+                        &[Py::new(self.py, Output(out)).expect("should be able to create output")],
+                    ),
+                )
+                .map_err(|_| Error)?;
+        }
+        Ok(())
+    }
+
     fn message(&mut self, msg: &str) -> core::result::Result<(), Error> {
         if let Some(callback) = &self.callback {
             let out = DisplayableOutput::Message(msg.to_owned());
@@ -0,0 +1,46 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Python only runs its `KeyboardInterrupt` handler between bytecode instructions,
+//! so Ctrl-C is never observed while native code is evaluating a Q# program.
+//! While an evaluation is running, a native `SIGINT` handler is installed instead,
+//! which cancels the token checked by the evaluator.
+
+use pyo3::{exceptions::PyKeyboardInterrupt, PyResult};
+use qsc::interpret::CancellationToken;
+use std::sync::OnceLock;
+
+/// The token cancelled by the signal handler. It can be shared by all interpreters
+/// because evaluations only run while the GIL is held, so at most one runs at a time.
+static TOKEN: OnceLock<CancellationToken> = OnceLock::new();
+
+pub(crate) fn cancellation_token() -> CancellationToken {
+    TOKEN.get_or_init(CancellationToken::new).clone()
+}
+
+extern "C" fn on_sigint(_signal: libc::c_int) {
+    // Only atomic operations happen here, which keeps the handler async-signal-safe.
+    if let Some(token) = TOKEN.get() {
+        token.cancel();
+    }
+}
+
+/// Runs `f` with Ctrl-C routed to the evaluator, raising `KeyboardInterrupt`
+/// if the evaluation was cancelled.
+pub(crate) fn with_keyboard_interrupt<T>(f: impl FnOnce() -> T) -> PyResult<T> {
+    let token = cancellation_token();
+    token.reset();
+
+    // SAFETY: `on_sigint` is async-signal-safe, and the previous handler
+    // (normally Python's own) is restored before returning.
+    let previous = unsafe { libc::signal(libc::SIGINT, on_sigint as libc::sighandler_t) };
+    let result = f();
+    unsafe { libc::signal(libc::SIGINT, previous) };
+
+    if token.is_cancelled() {
+        token.reset();
+        Err(PyKeyboardInterrupt::new_err(()))
+    } else {
+        Ok(result)
+    }
+}
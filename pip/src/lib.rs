@@ -6,3 +6,4 @@ allocator::assign_global!();
 mod displayable_output;
 mod fs;
 mod interpreter;
+mod interrupt;
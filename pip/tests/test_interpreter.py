@@ -8,6 +8,7 @@ from qsharp._native import (
     Pauli,
     QSharpError,
     TargetProfile,
+    qir_to_rir,
 )
 import pytest
 
@@ -272,6 +273,24 @@ def test_entry_expr_circuit() -> None:
     )
 
 
+def test_limits_apply_to_circuit_synthesis() -> None:
+    e = Interpreter(TargetProfile.Unrestricted)
+    e.interpret("operation Foo() : Unit { use q = Qubit(); while true { X(q); } }")
+    e.set_limits(1000, None, None, None, None)
+    with pytest.raises(QSharpError) as excinfo:
+        e.circuit("Foo()", None)
+    assert str(excinfo.value).find("exceeded the limit of 1000 steps") != -1
+
+
+def test_limits_apply_to_resource_estimation() -> None:
+    e = Interpreter(TargetProfile.Unrestricted)
+    e.interpret("operation Foo() : Unit { use qs = Qubit[3]; }")
+    e.set_limits(None, None, 2, None, None)
+    with pytest.raises(QSharpError) as excinfo:
+        e.estimate("Foo()", "[{}]")
+    assert str(excinfo.value).find("exceeded the limit of 2 qubits") != -1
+
+
 # this is by design
 def test_callables_failing_profile_validation_are_still_registered() -> None:
     e = Interpreter(TargetProfile.Quantinuum)
@@ -379,6 +398,41 @@ def test_quantinuum_qir_can_be_generated() -> None:
     )
 
 
+def test_quantinuum_qir_can_be_generated_with_full_optimization() -> None:
+    adaptive_input = """
+        namespace Test {
+            open Microsoft.Quantum.Math;
+            open QIR.Intrinsic;
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                let pi_over_two = 4.0 / 2.0;
+                __quantum__qis__rz__body(pi_over_two, q);
+                mutable some_angle = ArcSin(0.0);
+                __quantum__qis__rz__body(some_angle, q);
+                set some_angle = ArcCos(-1.0) / PI();
+                __quantum__qis__rz__body(some_angle, q);
+                __quantum__qis__mresetz__body(q)
+            }
+        }
+        """
+    e = Interpreter(TargetProfile.Quantinuum)
+    e.interpret(adaptive_input)
+    qir = e.qir("Test.Main()", "full")
+    assert qir.count("call void @__quantum__qis__rz__body") == 1
+    assert (
+        "call void @__quantum__qis__rz__body(double 3.0, %Qubit* inttoptr (i64 0 to %Qubit*))"
+        in qir
+    )
+
+
+def test_invalid_optimization_level_raises_error() -> None:
+    e = Interpreter(TargetProfile.Quantinuum)
+    e.interpret("operation Main() : Unit {}")
+    with pytest.raises(QSharpError) as excinfo:
+        e.qir("Main()", "aggressive")
+    assert "invalid optimization level aggressive" in str(excinfo)
+
 def test_base_qir_can_be_generated() -> None:
     base_input = """
         namespace Test {
@@ -453,6 +507,21 @@ def test_base_qir_can_be_generated() -> None:
     )
 
 
+def test_base_qir_can_be_read_into_rir() -> None:
+    e = Interpreter(TargetProfile.Base)
+    e.interpret("operation Main() : Result { use q = Qubit(); H(q); M(q) }")
+    rir = qir_to_rir(e.qir("Main()"))
+    assert "name: __quantum__qis__h__body" in rir
+    assert "call_type: Measurement" in rir
+    assert "num_qubits: 1" in rir
+
+
+def test_unsupported_qir_raises_error() -> None:
+    with pytest.raises(QSharpError) as excinfo:
+        qir_to_rir("define void @main() {\n  %0 = alloca i64\n  ret void\n}\n")
+    assert "instruction `alloca`" in str(excinfo.value)
+
+
 def test_operation_circuit() -> None:
     e = Interpreter(TargetProfile.Unrestricted)
     e.interpret("operation Foo(q: Qubit) : Result { H(q); return M(q) }")
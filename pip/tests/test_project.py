@@ -24,6 +24,19 @@ def test_project(qsharp) -> None:
     assert result == 4
 
 
+def test_project_with_dependency(qsharp) -> None:
+    qsharp.init(project_root="/with_dependency")
+    result = qsharp.eval("Test.ReturnsEight()")
+    assert result == 8
+
+
+def test_project_dependency_internal_items_are_not_visible(qsharp) -> None:
+    qsharp.init(project_root="/with_dependency")
+    with pytest.raises(Exception) as excinfo:
+        qsharp.eval("Library.Two()")
+    assert str(excinfo.value).startswith("Qsc.Resolve.NotFound")
+
+
 def test_project_compile_error(qsharp) -> None:
     with pytest.raises(Exception) as excinfo:
         qsharp.init(project_root="/compile_error")
@@ -68,6 +81,18 @@ memfs = {
             },
             "qsharp.json": "{}",
         },
+        "with_dependency": {
+            "src": {
+                "test.qs": "namespace Test { operation ReturnsEight() : Int { Library.Four() * 2 } }",
+            },
+            "qsharp.json": '{ "dependencies": { "Library": { "path": "../library" } } }',
+        },
+        "library": {
+            "src": {
+                "library.qs": "namespace Library { function Four() : Int { Two() * 2 } internal function Two() : Int { 2 } }",
+            },
+            "qsharp.json": "{}",
+        },
         "compile_error": {
             "src": {
                 "test.qs": "namespace Test { operation ReturnsFour() : Int { 4.0 } }",
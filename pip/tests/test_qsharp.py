@@ -4,7 +4,9 @@
 import qsharp
 import qsharp.utils
 from contextlib import redirect_stdout
+from textwrap import dedent
 import io
+import pytest
 
 # Tests for the Python library for Q#
 
@@ -73,6 +75,34 @@ def test_classical_seed() -> None:
     assert value1 != value3
 
 
+def test_replay_measurements(tmp_path) -> None:
+    qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
+    value1 = qsharp.eval(
+        "{ use qs = Qubit[16]; for q in qs { H(q); }; Microsoft.Quantum.Measurement.MResetEachZ(qs) }"
+    )
+    record = tmp_path / "measurements.txt"
+    qsharp.save_measurements(str(record))
+    assert len(record.read_text().splitlines()) == 16
+    qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
+    qsharp.replay_measurements(str(record))
+    value2 = qsharp.eval(
+        "{ use qs = Qubit[16]; for q in qs { H(q); }; Microsoft.Quantum.Measurement.MResetEachZ(qs) }"
+    )
+    assert value1 == value2
+
+
+def test_replay_impossible_measurement_fails(tmp_path) -> None:
+    qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
+    record = tmp_path / "measurements.txt"
+    record.write_text("1\n")
+    qsharp.replay_measurements(str(record))
+    try:
+        qsharp.eval("{ use q = Qubit(); M(q) }")
+        assert False, "replay should have failed"
+    except qsharp.QSharpError as e:
+        assert "has zero probability" in str(e)
+
+
 def test_dump_machine() -> None:
     qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
     qsharp.eval(
@@ -265,6 +295,46 @@ def test_compile_qir_str() -> None:
     assert "define void @ENTRYPOINT__main()" in qir
 
 
+def test_run_qir() -> None:
+    qsharp.init(target_profile=qsharp.TargetProfile.Base)
+    qsharp.eval(
+        "operation Program() : Result[] { use qs = Qubit[2]; X(qs[0]); MResetEachZ(qs) }"
+    )
+    results = qsharp.run_qir(qsharp.compile("Program()"), 3, seed=42)
+    assert results == [[qsharp.Result.One, qsharp.Result.Zero]] * 3
+
+
+def test_run_rir() -> None:
+    qsharp.init(target_profile=qsharp.TargetProfile.Base)
+    qsharp.eval(
+        "operation Program() : Result[] { use qs = Qubit[2]; X(qs[1]); MResetEachZ(qs) }"
+    )
+    rir = qsharp.qir_to_rir(str(qsharp.compile("Program()")))
+    results = qsharp.run_rir(rir, 2, seed=42)
+    assert results == [[qsharp.Result.Zero, qsharp.Result.One]] * 2
+
+
+def test_run_qir_runtime_error_is_raised() -> None:
+    qir = dedent(
+        """\
+        %Qubit = type opaque
+
+        define void @ENTRYPOINT__main() #0 {
+        block_0:
+          call void @__quantum__qis__h__body(%Qubit* inttoptr (i64 5 to %Qubit*))
+          ret void
+        }
+
+        declare void @__quantum__qis__h__body(%Qubit*)
+
+        attributes #0 = { "entry_point" "output_labeling_schema" "qir_profiles"="base_profile" "required_num_qubits"="1" "required_num_results"="0" }
+        """
+    )
+    with pytest.raises(qsharp.QSharpError) as excinfo:
+        qsharp.run_qir(qir)
+    assert "qubit 5 is out of range" in str(excinfo.value)
+
+
 def test_run_with_result(capsys) -> None:
     qsharp.init()
     qsharp.eval('operation Foo() : Result { Message("Hello, world!"); Zero }')
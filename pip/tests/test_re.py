@@ -30,6 +30,25 @@ def test_qsharp_estimation() -> None:
     )
 
 
+def test_qir_estimation() -> None:
+    qsharp.init(target_profile=qsharp.TargetProfile.Base)
+    qsharp.eval(
+        "operation Program() : Result[] { use qs = Qubit[10]; for q in qs { T(q); } MResetEachZ(qs) }"
+    )
+    res = qsharp.estimate_qir(qsharp.compile("Program()"))
+    assert res["status"] == "success"
+    assert res.logical_counts == LogicalCounts(
+        {
+            "numQubits": 10,
+            "tCount": 10,
+            "rotationCount": 0,
+            "rotationDepth": 0,
+            "cczCount": 0,
+            "measurementCount": 10,
+        }
+    )
+
+
 def test_qsharp_estimation_from_precalculated_counts() -> None:
     qsharp.init(target_profile=qsharp.TargetProfile.Unrestricted)
     res = qsharp.estimate(
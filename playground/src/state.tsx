@@ -72,3 +72,34 @@ export function StateTable(props: { dump: Dump; latexDump: string }) {
     </div>
   );
 }
+
+export function MatrixTable(props: {
+  matrix: [number, number][][];
+  latexMatrix: string;
+}) {
+  const mjRender = useRef<HTMLDivElement>(null);
+  useEffect(() => {
+    if (!mjRender.current) return;
+    MathJax.typesetClear([mjRender.current]);
+    mjRender.current.innerHTML = props.latexMatrix;
+    MathJax.typesetPromise([mjRender.current]);
+  }, [props.latexMatrix]);
+
+  return (
+    <div>
+      <table class="state-table">
+        <tbody>
+          {props.matrix.map((row) => (
+            <tr>
+              {row.map(([real, imag]) => (
+                <td style="text-align: right">{formatComplex(real, imag)}</td>
+              ))}
+            </tr>
+          ))}
+        </tbody>
+      </table>
+      <div ref={mjRender}></div>
+      <br></br>
+    </div>
+  );
+}
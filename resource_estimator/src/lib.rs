@@ -34,6 +34,7 @@ use thiserror::Error;
 #[diagnostic(transparent)]
 pub enum Error {
     Interpreter(interpret::Error),
+    Rir(qsc::rir::Error),
     Estimation(system::Error),
 }
 
@@ -63,3 +64,13 @@ pub fn estimate_expr(
     estimate_physical_resources(counter.logical_resources(), params)
         .map_err(|e| vec![Error::Estimation(e)])
 }
+
+/// Estimates the resources of a RIR program, such as one read from QIR with `qsc::codegen::read_qir`.
+pub fn estimate_rir(program: &qsc::rir::Program, params: &str) -> Result<String, Vec<Error>> {
+    let mut counter = LogicalCounter::default();
+    let mut stdout = std::io::sink();
+    let mut out = GenericReceiver::new(&mut stdout);
+    qsc::rir::exec(program, &mut counter, &mut out).map_err(|e| vec![Error::Rir(e)])?;
+    estimate_physical_resources(counter.logical_resources(), params)
+        .map_err(|e| vec![Error::Estimation(e)])
+}
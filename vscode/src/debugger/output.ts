@@ -3,6 +3,15 @@
 
 import { QscEventTarget } from "qsharp-lang";
 
+function formatComplex(real: number, imag: number) {
+  // Format -0 as 0
+  // Also using Unicode Minus Sign instead of ASCII Hyphen-Minus
+  // and Unicode Mathematical Italic Small I instead of ASCII i.
+  const r = `${real <= -0.00005 ? "−" : ""}${Math.abs(real).toFixed(4)}`;
+  const i = `${imag <= -0.00005 ? "−" : "+"}${Math.abs(imag).toFixed(4)}𝑖`;
+  return `${r}${i}`;
+}
+
 export function createDebugConsoleEventTarget(out: (message: string) => void) {
   const eventTarget = new QscEventTarget(false);
 
@@ -11,15 +20,6 @@ export function createDebugConsoleEventTarget(out: (message: string) => void) {
   });
 
   eventTarget.addEventListener("DumpMachine", (evt) => {
-    function formatComplex(real: number, imag: number) {
-      // Format -0 as 0
-      // Also using Unicode Minus Sign instead of ASCII Hyphen-Minus
-      // and Unicode Mathematical Italic Small I instead of ASCII i.
-      const r = `${real <= -0.00005 ? "−" : ""}${Math.abs(real).toFixed(4)}`;
-      const i = `${imag <= -0.00005 ? "−" : "+"}${Math.abs(imag).toFixed(4)}𝑖`;
-      return `${r}${i}`;
-    }
-
     function formatProbabilityPercent(real: number, imag: number) {
       const probabilityPercent = (real * real + imag * imag) * 100;
       return `${probabilityPercent.toFixed(4)}%`;
@@ -58,6 +58,18 @@ export function createDebugConsoleEventTarget(out: (message: string) => void) {
     out(out_str);
   });
 
+  eventTarget.addEventListener("Matrix", (evt) => {
+    let out_str = "\n";
+    out_str += "DumpOperation:\n\n";
+    for (const row of evt.detail.matrix) {
+      out_str +=
+        " " + row.map(([real, imag]) => formatComplex(real, imag)).join(" ");
+      out_str += "\n";
+    }
+
+    out(out_str);
+  });
+
   eventTarget.addEventListener("Result", (evt) => {
     const resultJson = JSON.stringify(evt.detail.value, null, 2);
     out(`Result: ${resultJson}`);
@@ -24,6 +24,7 @@ import {
 import { DebugProtocol } from "@vscode/debugprotocol";
 import {
   IDebugServiceWorker,
+  IPackageDependency,
   IStructStepResult,
   QscEventTarget,
   StepResultId,
@@ -81,6 +82,7 @@ export class QscDebugSession extends LoggingDebugSession {
     private config: vscode.DebugConfiguration,
     private sources: [string, string][],
     private languageFeatures: string[],
+    private dependencies: IPackageDependency[],
   ) {
     super();
 
@@ -116,6 +118,7 @@ export class QscDebugSession extends LoggingDebugSession {
       this.targetProfile,
       this.config.entry,
       this.languageFeatures,
+      this.dependencies,
     );
     for (const [path, _contents] of this.sources) {
       if (failureMessage == "") {
@@ -4,7 +4,12 @@
 import { Utils, URI } from "vscode-uri";
 import * as vscode from "vscode";
 
-import { getProjectLoader, log } from "qsharp-lang";
+import {
+  getProjectLoader,
+  log,
+  type IPackageDependency,
+  type IProjectSources,
+} from "qsharp-lang";
 import { updateQSharpJsonDiagnostics } from "./diagnostics";
 
 /**
@@ -15,6 +20,7 @@ export async function getManifest(uri: string): Promise<{
   manifestDirectory: string;
   languageFeatures: string[] | undefined;
   lints: { lint: string; level: string }[];
+  dependencies: { [name: string]: { path: string } };
 } | null> {
   const manifestDocument = await findManifestDocument(uri);
   if (manifestDocument === null) {
@@ -28,10 +34,7 @@ export async function getManifest(uri: string): Promise<{
       `failed to parse manifest at ${manifestDocument.uri.toString()}`,
       e,
     );
-    updateQSharpJsonDiagnostics(
-      manifestDocument.uri,
-      "Failed to parse Q# manifest. For a minimal Q# project manifest, try: {}",
-    );
+    // `getManifestThrowsOnParseFailure` has already reported the diagnostic
     return null;
   }
   return result;
@@ -166,11 +169,13 @@ async function getManifestThrowsOnParseFailure(uri: string): Promise<{
   manifestDirectory: string;
   languageFeatures: string[] | undefined;
   lints: { lint: string; level: string }[];
+  dependencies: { [name: string]: { path: string } };
 } | null> {
   const manifestDocument = await findManifestDocument(uri);
   let parsedManifest: {
     languageFeatures: string[];
     lints: { lint: string; level: string }[] | undefined;
+    dependencies: { [name: string]: { path: string } } | undefined;
   } | null = null;
 
   if (manifestDocument) {
@@ -186,6 +191,25 @@ async function getManifestThrowsOnParseFailure(uri: string): Promise<{
       );
     }
 
+    const dependencies = parsedManifest?.dependencies;
+    if (
+      dependencies !== undefined &&
+      (typeof dependencies !== "object" ||
+        dependencies === null ||
+        Array.isArray(dependencies) ||
+        Object.values(dependencies).some(
+          (dep: any) =>
+            typeof dep !== "object" ||
+            dep === null ||
+            typeof dep.path !== "string",
+        ))
+    ) {
+      const message =
+        'Invalid "dependencies" in qsharp.json. Expected an object mapping package names to { "path": string }.';
+      updateQSharpJsonDiagnostics(manifestDocument.uri, message);
+      throw new Error(message);
+    }
+
     updateQSharpJsonDiagnostics(manifestDocument.uri);
 
     const manifestDirectory = Utils.dirname(manifestDocument.uri);
@@ -194,6 +218,7 @@ async function getManifestThrowsOnParseFailure(uri: string): Promise<{
       manifestDirectory: manifestDirectory.toString(),
       languageFeatures: parsedManifest?.languageFeatures,
       lints: parsedManifest?.lints || [],
+      dependencies: parsedManifest?.dependencies || {},
     };
   }
   return null;
@@ -205,6 +230,7 @@ export async function loadProject(documentUri: vscode.Uri): Promise<{
   sources: [string, string][];
   languageFeatures: string[];
   lints: { lint: string; level: string }[];
+  dependencies: IPackageDependency[];
 }> {
   // get the project using this.program
   const manifest = await getManifestThrowsOnParseFailure(
@@ -218,17 +244,18 @@ export async function loadProject(documentUri: vscode.Uri): Promise<{
       sources: [[documentUri.toString(), file.getText()]],
       languageFeatures: [],
       lints: [],
+      dependencies: [],
     };
   }
 
   if (!projectLoader) {
     projectLoader = await getProjectLoader(readFile, listDir, getManifest);
   }
-  const project: [string, string][] =
-    await projectLoader.load_project(manifest);
+  const project: IProjectSources = await projectLoader.load_project(manifest);
   return {
-    sources: project,
+    sources: project.sources,
     languageFeatures: manifest.languageFeatures || [],
     lints: manifest.lints,
+    dependencies: project.dependencies,
   };
 }
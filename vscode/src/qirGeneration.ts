@@ -2,7 +2,12 @@
 // Licensed under the MIT License.
 
 import * as vscode from "vscode";
-import { getCompilerWorker, log, ProgramConfig } from "qsharp-lang";
+import {
+  getCompilerWorker,
+  IPackageDependency,
+  log,
+  ProgramConfig,
+} from "qsharp-lang";
 import { isQsharpDocument } from "./common";
 import { EventType, sendTelemetryEvent } from "./telemetry";
 import { getRandomGuid } from "./utils";
@@ -54,10 +59,12 @@ export async function getQirForActiveWindow(): Promise<string> {
   }
   let sources: [string, string][] = [];
   let languageFeatures: string[] = [];
+  let dependencies: IPackageDependency[] = [];
   try {
     const result = await loadProject(editor.document.uri);
     sources = result.sources;
     languageFeatures = result.languageFeatures || [];
+    dependencies = result.dependencies;
   } catch (e: any) {
     throw new QirGenerationError(e.message);
   }
@@ -88,6 +95,7 @@ export async function getQirForActiveWindow(): Promise<string> {
       sources,
       languageFeatures,
       profile: getTarget(),
+      dependencies,
     } as ProgramConfig;
     result = await worker.getQir(config);
 
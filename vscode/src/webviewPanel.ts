@@ -205,7 +205,7 @@ export function registerWebViewCommands(context: ExtensionContext) {
       }, compilerRunTimeoutMs);
 
       try {
-        const { sources, languageFeatures } = await loadProject(
+        const { sources, languageFeatures, dependencies } = await loadProject(
           editor.document.uri,
         );
 
@@ -216,9 +216,8 @@ export function registerWebViewCommands(context: ExtensionContext) {
           {},
         );
         const estimatesStr = await worker.getEstimates(
-          sources,
+          { sources, languageFeatures, dependencies },
           JSON.stringify(params),
-          languageFeatures,
         );
         sendTelemetryEvent(
           EventType.ResourceEstimationEnd,
@@ -346,7 +345,7 @@ export function registerWebViewCommands(context: ExtensionContext) {
           };
           sendMessageToPanel("histogram", false, message);
         });
-        const { sources, languageFeatures } = await loadProject(
+        const { sources, languageFeatures, dependencies } = await loadProject(
           editor.document.uri,
         );
         const start = performance.now();
@@ -354,6 +353,7 @@ export function registerWebViewCommands(context: ExtensionContext) {
         const config = {
           sources,
           languageFeatures,
+          dependencies,
         };
         await worker.run(config, "", parseInt(numberOfShots), evtTarget);
         sendTelemetryEvent(
@@ -9,6 +9,7 @@ use qsc::line_column::Encoding;
 use qsc::{fmt_complex, target::Profile, LanguageFeatures};
 
 use crate::line_column::{Location, Range};
+use crate::project_system::{get_dependency_packages, IPackageDependency};
 use crate::{get_source_map, serializable_type, CallbackReceiver};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -34,12 +35,20 @@ impl DebugService {
         target_profile: &str,
         entry: Option<String>,
         language_features: Vec<String>,
+        dependencies: Vec<IPackageDependency>,
     ) -> String {
         let source_map = get_source_map(sources, &entry);
+        let dependencies = get_dependency_packages(dependencies);
         let target = Profile::from_str(target_profile)
             .unwrap_or_else(|()| panic!("Invalid target : {target_profile}"));
         let features = LanguageFeatures::from_iter(language_features);
-        match Debugger::new(source_map, target.into(), Encoding::Utf16, features) {
+        match Debugger::new(
+            &dependencies,
+            source_map,
+            target.into(),
+            Encoding::Utf16,
+            features,
+        ) {
             Ok(debugger) => {
                 self.debugger = Some(debugger);
                 String::new()
@@ -219,6 +219,7 @@ fn interpret_error_labels(err: &interpret::Error) -> Vec<Label> {
         | interpret::Error::PartialEvaluation(_)
         | interpret::Error::UnsupportedRuntimeCapabilities
         | interpret::Error::Circuit(_)
-        | interpret::Error::NotAnOperation => Vec::new(),
+        | interpret::Error::NotAnOperation
+        | interpret::Error::EvaluationIncomplete => Vec::new(),
     }
 }
@@ -8,15 +8,17 @@ use katas::check_solution;
 use language_service::IOperationInfo;
 use num_bigint::BigUint;
 use num_complex::Complex64;
-use project_system::into_async_rust_fn_with;
+use project_system::{get_dependency_packages, into_async_rust_fn_with, IPackageDependency};
 use qsc::{
-    compile, format_state_id, get_latex,
+    codegen::OptimizationLevel,
+    compile, format_state_id, get_latex, get_matrix_latex,
     hir::PackageId,
     interpret::{
         self,
         output::{self, Receiver},
-        CircuitEntryPoint,
+        CircuitEntryPoint, Limits,
     },
+    project::DependencyPackage,
     target::Profile,
     LanguageFeatures, PackageStore, PackageType, SourceContents, SourceMap, SourceName, SparseSim,
 };
@@ -75,32 +77,57 @@ pub fn get_source_map(sources: Vec<js_sys::Array>, entry: &Option<String>) -> So
 pub fn get_qir(
     sources: Vec<js_sys::Array>,
     language_features: Vec<String>,
+    dependencies: Vec<IPackageDependency>,
     profile: &str,
+    optimization_level: Option<String>,
 ) -> Result<String, String> {
     let language_features = LanguageFeatures::from_iter(language_features);
     let sources = get_source_map(sources, &None);
+    let dependencies = get_dependency_packages(dependencies);
     let profile =
         Profile::from_str(profile).map_err(|()| format!("Invalid target profile {profile}"))?;
     if profile == Profile::Unrestricted {
         return Err("Invalid target profile for QIR generation".to_string());
     }
+    let optimization_level = match optimization_level {
+        Some(level) => OptimizationLevel::from_str(&level)
+            .map_err(|()| format!("Invalid optimization level {level}"))?,
+        None => OptimizationLevel::Basic,
+    };
     if language_features.contains(LanguageFeatures::PreviewQirGen) {
-        qsc::codegen::get_qir(sources, language_features, profile.into())
+        qsc::codegen::get_qir(
+            &dependencies,
+            sources,
+            language_features,
+            profile.into(),
+            optimization_level,
+        )
     } else {
-        _get_qir(sources, language_features)
+        _get_qir(&dependencies, sources, language_features)
     }
 }
 
 // allows testing without wasm bindings.
-fn _get_qir(sources: SourceMap, language_features: LanguageFeatures) -> Result<String, String> {
+fn _get_qir(
+    dependencies: &[DependencyPackage],
+    sources: SourceMap,
+    language_features: LanguageFeatures,
+) -> Result<String, String> {
     let core = compile::core();
     let mut store = PackageStore::new(core);
     let std = compile::std(&store, Profile::Base.into());
     let std = store.insert(std);
 
+    let (direct, dependency_errors) =
+        compile::compile_dependencies(&mut store, &[std], dependencies, Profile::Base.into());
+    if !dependency_errors.is_empty() {
+        return Err("Failed to generate QIR".to_string());
+    }
+    let package_ids = std::iter::once(std).chain(direct).collect::<Vec<_>>();
+
     let (unit, errors) = qsc::compile::compile(
         &store,
-        &[std],
+        &package_ids,
         sources,
         PackageType::Exe,
         Profile::Base.into(),
@@ -124,13 +151,15 @@ pub fn get_estimates(
     sources: Vec<js_sys::Array>,
     params: &str,
     language_features: Vec<String>,
+    dependencies: Vec<IPackageDependency>,
 ) -> Result<String, String> {
     let sources = get_source_map(sources, &None);
 
     let language_features = LanguageFeatures::from_iter(language_features);
 
-    let mut interpreter = interpret::Interpreter::new(
+    let mut interpreter = interpret::Interpreter::with_dependencies(
         true,
+        &get_dependency_packages(dependencies),
         sources,
         PackageType::Exe,
         Profile::Unrestricted.into(),
@@ -141,6 +170,7 @@ pub fn get_estimates(
     estimate_entry(&mut interpreter, params).map_err(|e| match &e[0] {
         re::Error::Interpreter(interpret::Error::Eval(e)) => e.to_string(),
         re::Error::Interpreter(_) => unreachable!("interpreter errors should be eval errors"),
+        re::Error::Rir(e) => e.to_string(),
         re::Error::Estimation(e) => e.to_string(),
     })
 }
@@ -150,6 +180,7 @@ pub fn get_circuit(
     sources: Vec<js_sys::Array>,
     targetProfile: &str,
     language_features: Vec<String>,
+    dependencies: Vec<IPackageDependency>,
     simulate: bool,
     operation: Option<IOperationInfo>,
 ) -> Result<JsValue, String> {
@@ -168,8 +199,9 @@ pub fn get_circuit(
         }
     };
 
-    let mut interpreter = interpret::Interpreter::new(
+    let mut interpreter = interpret::Interpreter::with_dependencies(
         true,
+        &get_dependency_packages(dependencies),
         sources,
         package_type,
         target_profile.into(),
@@ -306,6 +338,17 @@ where
         Ok(())
     }
 
+    fn matrix(&mut self, matrix: Vec<Vec<Complex64>>) -> Result<(), output::Error> {
+        let latex = get_matrix_latex(&matrix);
+        let matrix = matrix
+            .iter()
+            .map(|row| row.iter().map(|c| [c.re, c.im]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let matrix_json = json!({"type": "Matrix", "matrix": matrix, "matrixLatex": latex});
+        (self.event_cb)(&matrix_json.to_string());
+        Ok(())
+    }
+
     fn message(&mut self, msg: &str) -> Result<(), output::Error> {
         let msg_json = json!({"type": "Message", "message": msg});
         (self.event_cb)(&msg_json.to_string());
@@ -313,10 +356,12 @@ where
     }
 }
 fn run_internal_with_features<F>(
+    dependencies: &[DependencyPackage],
     sources: SourceMap,
     event_cb: F,
     shots: u32,
     language_features: LanguageFeatures,
+    limits: Limits,
 ) -> Result<(), Box<interpret::Error>>
 where
     F: FnMut(&str),
@@ -328,8 +373,9 @@ where
         .expect("There must be a source to process")
         .to_string();
     let mut out = CallbackReceiver { event_cb };
-    let mut interpreter = match interpret::Interpreter::new(
+    let mut interpreter = match interpret::Interpreter::with_dependencies(
         true,
+        dependencies,
         sources,
         PackageType::Exe,
         Profile::Unrestricted.into(),
@@ -347,6 +393,7 @@ where
             return Err(Box::new(e));
         }
     };
+    interpreter.set_limits(limits);
 
     for _ in 0..shots {
         let result = interpreter.eval_entry_with_sim(&mut SparseSim::new(), &mut out);
@@ -367,6 +414,40 @@ where
     Ok(())
 }
 
+serializable_type! {
+    ExecutionLimits,
+    {
+        #[serde(rename = "maxSteps")]
+        pub steps: Option<u32>,
+        #[serde(rename = "maxQubits")]
+        pub qubits: Option<u32>,
+        #[serde(rename = "maxArrayLength")]
+        pub array_len: Option<u32>,
+        #[serde(rename = "maxCallDepth")]
+        pub call_depth: Option<u32>,
+    },
+    r#"export interface IExecutionLimits {
+        maxSteps?: number;
+        maxQubits?: number;
+        maxArrayLength?: number;
+        maxCallDepth?: number;
+    }"#,
+    IExecutionLimits
+}
+
+impl From<ExecutionLimits> for Limits {
+    fn from(limits: ExecutionLimits) -> Self {
+        Limits {
+            max_steps: limits.steps.map(u64::from),
+            // The system clock is not available in wasm, so there is no time limit.
+            time_limit: None,
+            max_qubits: limits.qubits.map(|n| n as usize),
+            max_array_len: limits.array_len.map(|n| n as usize),
+            max_call_depth: limits.call_depth.map(|n| n as usize),
+        }
+    }
+}
+
 #[wasm_bindgen]
 pub fn run(
     sources: Vec<js_sys::Array>,
@@ -374,19 +455,29 @@ pub fn run(
     event_cb: &js_sys::Function,
     shots: u32,
     language_features: Vec<String>,
+    dependencies: Vec<IPackageDependency>,
+    limits: Option<IExecutionLimits>,
 ) -> Result<bool, JsValue> {
     if !event_cb.is_function() {
         return Err(JsError::new("Events callback function must be provided").into());
     }
 
     let language_features = LanguageFeatures::from_iter(language_features);
+    let limits = limits.map(|limits| ExecutionLimits::from(limits).into());
 
     let sources = get_source_map(sources, &Some(expr.into()));
     let event_cb = |msg: &str| {
         // See example at https://rustwasm.github.io/wasm-bindgen/reference/receiving-js-closures-in-rust.html
         let _ = event_cb.call1(&JsValue::null(), &JsValue::from(msg));
     };
-    match run_internal_with_features(sources, event_cb, shots, language_features) {
+    match run_internal_with_features(
+        &get_dependency_packages(dependencies),
+        sources,
+        event_cb,
+        shots,
+        language_features,
+        limits.unwrap_or_default(),
+    ) {
         Ok(()) => Ok(true),
         Err(e) => Err(JsError::from(e).into()),
     }
@@ -480,3 +571,8 @@ pub fn generate_docs() -> Vec<IDocFile> {
 const TARGET_PROFILE: &'static str = r#"
 export type TargetProfile = "base" | "quantinuum" |"unrestricted";
 "#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const OPTIMIZATION_LEVEL: &'static str = r#"
+export type OptimizationLevel = "none" | "basic" | "full";
+"#;
@@ -1,13 +1,16 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+use crate::serializable_type;
 use async_trait::async_trait;
-use js_sys::JsString;
 use qsc::linter::LintConfig;
-use qsc_project::{EntryType, JSFileEntry, Manifest, ManifestDescriptor, ProjectSystemCallbacks};
+use qsc_project::{
+    Dependency, DependencyPackage, EntryType, JSFileEntry, Manifest, ManifestDescriptor,
+    ProjectSystemCallbacks,
+};
+use serde::{Deserialize, Serialize};
 
-use std::iter::FromIterator;
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 
@@ -32,15 +35,93 @@ extern "C" {
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(
-        typescript_type = "(uri: string) => Promise<{ manifestDirectory: string }| null>"
+        typescript_type = "(uri: string) => Promise<{ manifestDirectory: string, dependencies?: { [name: string]: { path: string } } }| null>"
     )]
     pub type GetManifestCallback;
 }
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(typescript_type = "[string, string][]")]
-    pub type ProjectSources;
+serializable_type! {
+    ProjectSources,
+    {
+        pub sources: Vec<(String, String)>,
+        pub dependencies: Vec<PackageDependency>,
+    },
+    r#"export interface IProjectSources {
+        sources: [string, string][];
+        dependencies: IPackageDependency[];
+    }"#,
+    IProjectSources
+}
+
+serializable_type! {
+    PackageDependency,
+    {
+        pub name: String,
+        pub sources: Vec<(String, String)>,
+        #[serde(rename = "languageFeatures")]
+        pub language_features: Vec<String>,
+        pub dependencies: Vec<usize>,
+        pub direct: bool,
+    },
+    r#"/**
+     * A package that a project depends on. Packages are listed so that every package comes
+     * after the packages it depends on, which `dependencies` refers to by their index.
+     * `direct` is true for the packages the project itself depends on.
+     */
+    export interface IPackageDependency {
+        name: string;
+        sources: [string, string][];
+        languageFeatures: string[];
+        dependencies: number[];
+        direct: boolean;
+    }"#,
+    IPackageDependency
+}
+
+impl From<DependencyPackage> for PackageDependency {
+    fn from(package: DependencyPackage) -> Self {
+        Self {
+            name: package.name.to_string(),
+            sources: package
+                .sources
+                .into_iter()
+                .map(|(path, contents)| (path.to_string(), contents.to_string()))
+                .collect(),
+            language_features: package.manifest.language_features,
+            dependencies: package.dependencies,
+            direct: package.direct,
+        }
+    }
+}
+
+impl From<PackageDependency> for DependencyPackage {
+    fn from(package: PackageDependency) -> Self {
+        Self {
+            name: package.name.into(),
+            sources: package
+                .sources
+                .into_iter()
+                .map(|(path, contents)| (path.into(), contents.into()))
+                .collect(),
+            manifest: Manifest {
+                language_features: package.language_features,
+                ..Manifest::default()
+            },
+            dependencies: package.dependencies,
+            direct: package.direct,
+        }
+    }
+}
+
+/// Converts the packages passed in from JS into the packages to compile ahead of the sources
+/// that depend on them.
+pub(crate) fn get_dependency_packages(
+    dependencies: Vec<IPackageDependency>,
+) -> Vec<DependencyPackage> {
+    dependencies
+        .into_iter()
+        .map(|dependency| PackageDependency::from(dependency).into())
+        .collect()
 }
 
 impl From<ManifestDescriptorObject> for Option<ManifestDescriptor> {
@@ -199,6 +280,17 @@ pub(crate) fn get_manifest_transformer(js_val: JsValue, _: String) -> Option<Man
         _ => Vec::new(),
     };
 
+    let dependencies: BTreeMap<String, Dependency> =
+        match js_sys::Reflect::get(&js_val, &JsValue::from_str("dependencies")) {
+            Ok(v) if !v.is_undefined() && !v.is_null() => serde_wasm_bindgen::from_value(v.clone())
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "manifest callback returned {v:?}, but we expected an object mapping package names to {{ path: string }}: {e}"
+                    )
+                }),
+            _ => BTreeMap::new(),
+        };
+
     log::trace!("found manifest at {manifest_dir:?}");
 
     let manifest_dir = PathBuf::from(manifest_dir);
@@ -207,6 +299,7 @@ pub(crate) fn get_manifest_transformer(js_val: JsValue, _: String) -> Option<Man
         manifest: Manifest {
             language_features,
             lints,
+            dependencies,
             author: Option::default(),
             license: Option::default(),
         },
@@ -242,33 +335,38 @@ impl ProjectLoader {
         })
     }
 
-    pub async fn load_project(&self, manifest: ManifestDescriptorObject) -> ProjectSources {
+    /// Loads the sources of the project and the packages it depends on. Fails if a package
+    /// the project depends on cannot be loaded.
+    pub async fn load_project(
+        &self,
+        manifest: ManifestDescriptorObject,
+    ) -> Result<IProjectSources, String> {
         let manifest: Option<ManifestDescriptor> = manifest.into();
-        match manifest {
-            #[allow(clippy::from_iter_instead_of_collect)]
+        let project = match manifest {
             Some(manifest) => {
-                let res = qsc_project::FileSystemAsync::load_project(self, &manifest)
+                let project = qsc_project::FileSystemAsync::load_project(self, &manifest)
                     .await
-                    .map_or_else(
-                        |_| js_sys::Array::new(),
-                        |proj| {
-                            proj.sources
-                                .into_iter()
-                                .map(|(path, contents)| {
-                                    js_sys::Array::from_iter::<std::slice::Iter<'_, JsString>>(
-                                        [path.to_string().into(), contents.to_string().into()]
-                                            .iter(),
-                                    )
-                                })
-                                .collect::<_>()
-                        },
-                    );
-                ProjectSources { obj: res.into() }
+                    .map_err(|e| {
+                        e.chain()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(": ")
+                    })?;
+                ProjectSources {
+                    sources: project
+                        .sources
+                        .into_iter()
+                        .map(|(path, contents)| (path.to_string(), contents.to_string()))
+                        .collect(),
+                    dependencies: project.dependencies.into_iter().map(Into::into).collect(),
+                }
             }
             None => ProjectSources {
-                obj: js_sys::Array::new().into(),
+                sources: Vec::new(),
+                dependencies: Vec::new(),
             },
-        }
+        };
+        Ok(project.into())
     }
 }
 
@@ -3,7 +3,11 @@
 
 use expect_test::expect;
 use indoc::indoc;
-use qsc::{interpret, LanguageFeatures, SourceMap};
+use qsc::{
+    interpret::{self, Limits},
+    project::DependencyPackage,
+    LanguageFeatures, SourceMap,
+};
 
 use super::run_internal_with_features;
 
@@ -11,7 +15,14 @@ fn run_internal<F>(sources: SourceMap, event_cb: F, shots: u32) -> Result<(), Bo
 where
     F: FnMut(&str),
 {
-    run_internal_with_features(sources, event_cb, shots, LanguageFeatures::default())
+    run_internal_with_features(
+        &[],
+        sources,
+        event_cb,
+        shots,
+        LanguageFeatures::default(),
+        Limits::default(),
+    )
 }
 
 #[test]
@@ -40,12 +51,73 @@ fn test_compile() {
     }}";
 
     let result = crate::_get_qir(
+        &[],
         SourceMap::new([("test.qs".into(), code.into())], None),
         LanguageFeatures::default(),
     );
     assert!(result.is_ok());
 }
 
+fn library_dependency() -> DependencyPackage {
+    DependencyPackage {
+        name: "Library".into(),
+        sources: vec![(
+            "library.qs".into(),
+            indoc! {"
+            namespace Library {
+                operation Prepare(q : Qubit) : Unit {
+                    Flip(q);
+                }
+                internal operation Flip(q : Qubit) : Unit {
+                    X(q);
+                }
+            }"}
+            .into(),
+        )],
+        direct: true,
+        ..DependencyPackage::default()
+    }
+}
+
+#[test]
+fn test_compile_with_dependency() {
+    let code = "namespace test { @EntryPoint() operation Foo(): Result {
+    use q = Qubit();
+    Library.Prepare(q);
+    M(q)
+    }}";
+
+    let result = crate::_get_qir(
+        &[library_dependency()],
+        SourceMap::new([("test.qs".into(), code.into())], None),
+        LanguageFeatures::default(),
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_run_with_dependency_does_not_expose_internal_items() {
+    let code = "namespace test { @EntryPoint() operation Foo(): Result {
+    use q = Qubit();
+    Library.Flip(q);
+    M(q)
+    }}";
+    let count = std::cell::Cell::new(0);
+
+    let _ = run_internal_with_features(
+        &[library_dependency()],
+        SourceMap::new([("test.qs".into(), code.into())], None),
+        |msg| {
+            expect![[r#"{"result":{"code":"Qsc.Resolve.NotFound","message":"name error: `Flip` not found","range":{"end":{"character":16,"line":2},"start":{"character":12,"line":2}},"severity":"error"},"success":false,"type":"Result"}"#]].assert_eq(msg);
+            count.set(count.get() + 1);
+        },
+        1,
+        LanguageFeatures::default(),
+        Limits::default(),
+    );
+    assert_eq!(count.get(), 1);
+}
+
 #[test]
 fn test_run_two_shots() {
     let code = "
@@ -463,3 +535,30 @@ fn test_doc_gen() {
         }
     }
 }
+
+#[test]
+fn test_run_stops_at_qubit_limit() {
+    let mut output = Vec::new();
+    let code = indoc! {"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : Unit {
+                    use qs = Qubit[3];
+                }
+            }"
+    };
+    run_internal_with_features(
+        &[],
+        SourceMap::new([("code".into(), code.into())], None),
+        |s| output.push(s.to_string()),
+        1,
+        LanguageFeatures::default(),
+        Limits {
+            max_qubits: Some(2),
+            ..Limits::default()
+        },
+    )
+    .expect("code should compile and run");
+
+    expect![[r#"{"result":{"code":"Qsc.Eval.QubitLimitExceeded","message":"runtime error: evaluation exceeded the limit of 2 qubits","range":{"end":{"character":1,"line":0},"start":{"character":0,"line":0}},"related":[{"location":{"source":"core/qir.qs","span":{"end":{"character":52,"line":18},"start":{"character":23,"line":18}}},"message":"qubit limit reached here"}],"severity":"error"},"success":false,"type":"Result"}"#]].assert_eq(&output[0]);
+}